@@ -0,0 +1,160 @@
+//! `--anonymize`: scrub identifying fields from a JSON report before it is
+//! shared for support or posted publicly.
+//!
+//! Works on the serialized report rather than the report structs, so every
+//! command that opts in gets the same treatment without per-struct code.
+//! Session ids, project names, agent names, and local paths are dropped;
+//! remaining identifiers are replaced by a short HMAC-SHA256 digest keyed by a
+//! random salt drawn once per export, so rows that shared an identifier still
+//! group together within one report but the digests cannot be reversed by
+//! hashing a dictionary of likely names, nor matched across exports. Token and
+//! cost aggregates are left untouched.
+
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+
+/// Keys removed outright, wherever they appear.
+const REMOVED_KEYS: &[&str] = &[
+    "sessionId",
+    "project",
+    "agent",
+    "workspaceLabel",
+    "paths",
+    "warnings",
+];
+
+/// Keys whose string values (or string array items) are replaced by a digest.
+const HASHED_KEYS: &[&str] = &["workspaceKey", "id", "name", "mcpServers"];
+
+pub fn anonymize(value: &mut Value) {
+    let salt = uuid::Uuid::new_v4();
+    scrub(value, salt.as_bytes());
+}
+
+fn scrub(value: &mut Value, salt: &[u8]) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|key, _| !REMOVED_KEYS.contains(&key.as_str()));
+            for (key, nested) in object.iter_mut() {
+                if HASHED_KEYS.contains(&key.as_str()) {
+                    hash_strings(nested, salt);
+                } else {
+                    scrub(nested, salt);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| scrub(item, salt)),
+        _ => {}
+    }
+}
+
+fn hash_strings(value: &mut Value, salt: &[u8]) {
+    match value {
+        Value::String(text) => *text = digest(text, salt),
+        Value::Array(items) => items.iter_mut().for_each(|item| hash_strings(item, salt)),
+        _ => {}
+    }
+}
+
+fn digest(text: &str, salt: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(salt).expect("HMAC accepts keys of any length");
+    mac.update(text.as_bytes());
+    let hash = format!("{:x}", mac.finalize().into_bytes());
+    format!("anon-{}", &hash[..12])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn drops_sensitive_fields_and_hashes_identifiers() {
+        let mut report = json!({
+            "groupBy": "client,session,model",
+            "entries": [
+                {
+                    "client": "claude",
+                    "sessionId": "9f1c-session",
+                    "workspaceKey": "/home/me/secret-project",
+                    "workspaceLabel": "secret-project",
+                    "agent": "reviewer",
+                    "model": "claude-sonnet-4-6",
+                    "input": 1200,
+                    "cost": 0.42
+                },
+                {
+                    "client": "codex",
+                    "workspaceKey": "/home/me/secret-project",
+                    "model": "gpt-5",
+                    "input": 10,
+                    "cost": 0.01
+                }
+            ],
+            "totalCost": 0.43,
+            "device": {"id": "device-123", "name": "my-laptop"},
+            "diagnostics": [{"code": "x", "paths": [{"path": "/home/me/.claude"}]}],
+            "warnings": ["Cursor cache at /home/me/.config/tokscale"]
+        });
+
+        anonymize(&mut report);
+
+        let text = report.to_string();
+        for secret in [
+            "9f1c-session",
+            "secret-project",
+            "reviewer",
+            "device-123",
+            "my-laptop",
+            "/home/me",
+        ] {
+            assert!(!text.contains(secret), "{secret} leaked: {text}");
+        }
+        let first = &report["entries"][0];
+        assert!(first.get("sessionId").is_none());
+        assert!(first.get("workspaceLabel").is_none());
+        assert!(first.get("agent").is_none());
+        assert!(report.get("warnings").is_none());
+        assert!(report["diagnostics"][0].get("paths").is_none());
+
+        let key = first["workspaceKey"].as_str().unwrap();
+        assert!(key.starts_with("anon-"));
+        assert_eq!(report["entries"][1]["workspaceKey"], key);
+        assert!(report["device"]["id"]
+            .as_str()
+            .unwrap()
+            .starts_with("anon-"));
+
+        assert_eq!(first["client"], "claude");
+        assert_eq!(first["model"], "claude-sonnet-4-6");
+        assert_eq!(first["input"], 1200);
+        assert_eq!(first["cost"], 0.42);
+        assert_eq!(report["totalCost"], 0.43);
+    }
+
+    #[test]
+    fn salts_digests_per_export() {
+        let original = json!({"entries": [{"workspaceKey": "/home/me/project"}]});
+        let plain = format!(
+            "{:x}",
+            <Sha256 as sha2::Digest>::digest(b"/home/me/project")
+        );
+
+        let mut first = original.clone();
+        let mut second = original.clone();
+        anonymize(&mut first);
+        anonymize(&mut second);
+
+        let first_key = first["entries"][0]["workspaceKey"].as_str().unwrap();
+        assert_ne!(first_key, format!("anon-{}", &plain[..12]));
+        assert_ne!(first_key, second["entries"][0]["workspaceKey"]);
+    }
+
+    #[test]
+    fn keeps_null_workspace_keys_null() {
+        let mut report = json!({"entries": [{"workspaceKey": null}]});
+        anonymize(&mut report);
+        assert_eq!(report["entries"][0]["workspaceKey"], Value::Null);
+    }
+}
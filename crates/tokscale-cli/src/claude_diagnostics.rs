@@ -112,6 +112,10 @@ fn diagnostic_paths(home_dir: &Path, desktop_paths: Vec<PathBuf>) -> Vec<Diagnos
             "claudeCodeTranscripts",
             home_dir.join(".claude").join("transcripts"),
         ),
+        (
+            "claudeCodeHistory",
+            home_dir.join(".claude").join("history"),
+        ),
     ] {
         let exists = path.exists();
         paths.push(DiagnosticPath {
@@ -0,0 +1,297 @@
+//! Default flag values read from config files and the environment.
+//!
+//! Resolution order, highest first:
+//! 1. explicit CLI flags
+//! 2. `TOKSCALE_THEME`, `TOKSCALE_GROUP_BY`, `TOKSCALE_REFRESH`,
+//!    `TOKSCALE_CLIENTS`, `TOKSCALE_NO_SPINNER`
+//! 3. the nearest `.tokscalerc` in the working directory or an ancestor
+//! 4. `config.toml` in the tokscale config dir (`~/.config/tokscale/`)
+//! 5. built-in clap defaults
+//!
+//! Both files are TOML with the same keys:
+//!
+//! ```toml
+//! theme = "blue"
+//! group_by = "client,provider,model"
+//! refresh = 30
+//! clients = ["claude", "codex"]
+//! no_spinner = true
+//! ```
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+pub const PROJECT_FILE_NAME: &str = ".tokscalerc";
+
+static GLOBAL: OnceLock<CliDefaults> = OnceLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CliDefaults {
+    pub theme: Option<String>,
+    #[serde(alias = "group-by")]
+    pub group_by: Option<String>,
+    pub refresh: Option<u64>,
+    pub clients: Option<Vec<String>>,
+    #[serde(alias = "no-spinner")]
+    pub no_spinner: Option<bool>,
+}
+
+impl CliDefaults {
+    /// Load and merge every source below the CLI flags themselves.
+    pub fn load() -> Result<Self> {
+        let global = crate::paths::get_config_dir().join(CONFIG_FILE_NAME);
+        let project = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| find_project_file(&cwd));
+        Self::resolve(Some(global.as_path()), project.as_deref(), |key| {
+            std::env::var(key).ok()
+        })
+    }
+
+    /// Merge `global` < `project` < environment. Missing files are skipped;
+    /// unreadable or malformed ones are an error naming the file.
+    pub fn resolve(
+        global: Option<&Path>,
+        project: Option<&Path>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self> {
+        let mut defaults = Self::default();
+        for path in [global, project].into_iter().flatten() {
+            if path.is_file() {
+                defaults = defaults.overlay(Self::from_file(path)?);
+            }
+        }
+        Ok(defaults.overlay(Self::from_env(env)?))
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid config in {}", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let defaults: Self =
+            toml::from_str(content).map_err(|e| anyhow!(e.message().to_string()))?;
+        defaults.validate()?;
+        Ok(defaults)
+    }
+
+    pub fn from_env(env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let var = |key: &str| {
+            env(key)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        let refresh = var("TOKSCALE_REFRESH")
+            .map(|raw| {
+                raw.parse::<u64>().map_err(|_| {
+                    anyhow!("TOKSCALE_REFRESH must be a whole number of seconds, got '{raw}'")
+                })
+            })
+            .transpose()?;
+        let no_spinner = var("TOKSCALE_NO_SPINNER")
+            .map(|raw| match raw.to_ascii_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => Ok(true),
+                "0" | "false" | "no" | "off" => Ok(false),
+                _ => Err(anyhow!(
+                    "TOKSCALE_NO_SPINNER must be true or false, got '{raw}'"
+                )),
+            })
+            .transpose()?;
+        let clients = var("TOKSCALE_CLIENTS").map(|raw| {
+            raw.split(',')
+                .map(|client| client.trim().to_string())
+                .filter(|client| !client.is_empty())
+                .collect()
+        });
+
+        let defaults = Self {
+            theme: var("TOKSCALE_THEME"),
+            group_by: var("TOKSCALE_GROUP_BY"),
+            refresh,
+            clients,
+            no_spinner,
+        };
+        defaults.validate()?;
+        Ok(defaults)
+    }
+
+    /// Fields set in `higher` replace the ones in `self`.
+    pub fn overlay(self, higher: Self) -> Self {
+        Self {
+            theme: higher.theme.or(self.theme),
+            group_by: higher.group_by.or(self.group_by),
+            refresh: higher.refresh.or(self.refresh),
+            clients: higher.clients.or(self.clients),
+            no_spinner: higher.no_spinner.or(self.no_spinner),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(group_by) = &self.group_by {
+            group_by
+                .parse::<tokscale_core::GroupBy>()
+                .map_err(|e| anyhow!("group_by: {e}"))?;
+        }
+        for client in self.clients.iter().flatten() {
+            if crate::ClientFilter::from_filter_str(client).is_none() {
+                bail!("clients: unknown client '{client}'");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Nearest `.tokscalerc` walking up from `start`.
+pub fn find_project_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Install the resolved defaults for the rest of the process. The first call
+/// wins, mirroring `model_alias::set_global`.
+pub fn set_global(defaults: CliDefaults) {
+    let _ = GLOBAL.set(defaults);
+}
+
+/// Default `--client` list from config/env, if any source set one.
+pub fn global_clients() -> Option<&'static [String]> {
+    GLOBAL
+        .get()
+        .and_then(|defaults| defaults.clients.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn env_from(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| map.get(key).cloned()
+    }
+
+    #[test]
+    fn parse_reads_every_supported_key() {
+        let defaults = CliDefaults::parse(
+            r#"
+theme = "blue"
+group_by = "client,provider,model"
+refresh = 30
+clients = ["claude", "codex"]
+no_spinner = true
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            defaults,
+            CliDefaults {
+                theme: Some("blue".to_string()),
+                group_by: Some("client,provider,model".to_string()),
+                refresh: Some(30),
+                clients: Some(vec!["claude".to_string(), "codex".to_string()]),
+                no_spinner: Some(true),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed_and_unknown_entries() {
+        assert!(CliDefaults::parse("theme = ").is_err());
+        assert!(CliDefaults::parse("refresh = \"soon\"").is_err());
+        assert!(CliDefaults::parse("colour = \"blue\"").is_err());
+        let err = CliDefaults::parse("group_by = \"vibes\"").unwrap_err();
+        assert!(err.to_string().contains("group_by"));
+        let err = CliDefaults::parse("clients = [\"claude\", \"nope\"]").unwrap_err();
+        assert!(err.to_string().contains("unknown client 'nope'"));
+    }
+
+    #[test]
+    fn from_file_error_names_the_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(PROJECT_FILE_NAME);
+        fs::write(&path, "refresh = [").unwrap();
+
+        let err = CliDefaults::from_file(&path).unwrap_err();
+        assert!(format!("{err:#}").contains(&path.display().to_string()));
+    }
+
+    #[test]
+    fn resolve_prefers_env_over_project_over_global() {
+        let dir = TempDir::new().unwrap();
+        let global = dir.path().join(CONFIG_FILE_NAME);
+        let project = dir.path().join(PROJECT_FILE_NAME);
+        fs::write(
+            &global,
+            "theme = \"green\"\nrefresh = 10\nno_spinner = true\nclients = [\"claude\"]\n",
+        )
+        .unwrap();
+        fs::write(&project, "theme = \"purple\"\nrefresh = 20\n").unwrap();
+
+        let defaults = CliDefaults::resolve(
+            Some(&global),
+            Some(&project),
+            env_from(&[("TOKSCALE_REFRESH", "45"), ("TOKSCALE_NO_SPINNER", "0")]),
+        )
+        .unwrap();
+
+        assert_eq!(defaults.theme.as_deref(), Some("purple"));
+        assert_eq!(defaults.refresh, Some(45));
+        assert_eq!(defaults.no_spinner, Some(false));
+        assert_eq!(defaults.clients, Some(vec!["claude".to_string()]));
+        assert_eq!(defaults.group_by, None);
+    }
+
+    #[test]
+    fn resolve_skips_missing_files() {
+        let dir = TempDir::new().unwrap();
+        let defaults = CliDefaults::resolve(
+            Some(&dir.path().join(CONFIG_FILE_NAME)),
+            None,
+            env_from(&[]),
+        )
+        .unwrap();
+        assert_eq!(defaults, CliDefaults::default());
+    }
+
+    #[test]
+    fn from_env_rejects_invalid_values() {
+        assert!(CliDefaults::from_env(env_from(&[("TOKSCALE_REFRESH", "fast")])).is_err());
+        assert!(CliDefaults::from_env(env_from(&[("TOKSCALE_NO_SPINNER", "maybe")])).is_err());
+        assert!(CliDefaults::from_env(env_from(&[("TOKSCALE_CLIENTS", "claude,bogus")])).is_err());
+        let defaults =
+            CliDefaults::from_env(env_from(&[("TOKSCALE_CLIENTS", "claude, codex")])).unwrap();
+        assert_eq!(
+            defaults.clients,
+            Some(vec!["claude".to_string(), "codex".to_string()])
+        );
+    }
+
+    #[test]
+    fn find_project_file_walks_up_to_the_nearest_ancestor() {
+        let dir = TempDir::new().unwrap();
+        let nested = dir.path().join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.path().join(PROJECT_FILE_NAME), "").unwrap();
+        fs::write(dir.path().join("a").join(PROJECT_FILE_NAME), "").unwrap();
+
+        assert_eq!(
+            find_project_file(&nested),
+            Some(dir.path().join("a").join(PROJECT_FILE_NAME))
+        );
+    }
+}
@@ -251,6 +251,7 @@ pub fn submit_filters(
         month: settings.month,
         since: settings.since.clone(),
         until: settings.until.clone(),
+        until_exclusive: false,
         year: settings.year.clone(),
     };
     let (since, until) = build_date_filter_for_date(&date, chrono::Local::now().date_naive());
@@ -795,7 +796,14 @@ fn build_date_filter_for_date(
             Some(current_date.format("%Y-%m-%d").to_string()),
         );
     }
-    (date.since.clone(), date.until.clone())
+    (
+        date.since
+            .as_deref()
+            .map(|raw| crate::resolve_date_bound(raw, current_date)),
+        date.until
+            .as_deref()
+            .map(|raw| crate::resolve_date_bound(raw, current_date)),
+    )
 }
 
 #[cfg(test)]
@@ -998,6 +1006,19 @@ mod tests {
         assert_eq!(year, None);
     }
 
+    #[test]
+    fn submit_filters_resolve_relative_dates_on_each_run() {
+        let settings = AutosubmitSettings {
+            since: Some("7d".to_string()),
+            ..AutosubmitSettings::default()
+        };
+
+        let (_, since, _, _) = submit_filters(&settings);
+
+        let expected = chrono::Local::now().date_naive() - chrono::Duration::days(7);
+        assert_eq!(since, Some(expected.format("%Y-%m-%d").to_string()));
+    }
+
     #[test]
     fn submit_filters_default_to_submit_clients_when_unfiltered() {
         let settings = AutosubmitSettings::default();
@@ -0,0 +1,547 @@
+//! `tokscale diff`: compare per-model spend between two date ranges.
+//!
+//! Both ranges run through the regular `get_model_report` pipeline grouped by
+//! model, so client filters, `--home`, and scanner settings behave exactly
+//! like `tokscale models`. The two snapshots are then joined on the
+//! normalized model key.
+
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tokscale_core::{ModelReport, ModelUsage};
+
+pub struct DiffOptions {
+    pub json: bool,
+    pub home_dir: Option<String>,
+    pub clients: Option<Vec<String>>,
+    pub date: crate::DateRangeFlags,
+    pub previous_since: Option<String>,
+    pub previous_until: Option<String>,
+    pub no_spinner: bool,
+}
+
+/// Inclusive `YYYY-MM-DD` date range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DateWindow {
+    pub since: String,
+    pub until: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelSnapshotEntry {
+    pub model: String,
+    pub provider: String,
+    pub total_tokens: i64,
+    pub message_count: i32,
+    pub cost: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RangeSnapshot {
+    pub since: String,
+    pub until: String,
+    pub total_cost: f64,
+    pub entries: Vec<ModelSnapshotEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelDelta {
+    pub model: String,
+    /// `None` when the model has no usage in the current range.
+    pub current_cost: Option<f64>,
+    /// `None` when the model has no usage in the previous range.
+    pub previous_cost: Option<f64>,
+    pub change: f64,
+    /// `None` when there is no non-zero previous cost to compare against.
+    pub percent_change: Option<f64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiffJson {
+    current: RangeSnapshot,
+    previous: RangeSnapshot,
+    total_change: f64,
+    deltas: Vec<ModelDelta>,
+}
+
+/// Resolve the (current, previous) windows.
+///
+/// With no range flags the current window is month-to-date and the previous
+/// window is the whole prior calendar month. When only the current window is
+/// given, the previous window is the equally long span immediately before it.
+pub fn resolve_windows(
+    since: Option<&str>,
+    until: Option<&str>,
+    previous_since: Option<&str>,
+    previous_until: Option<&str>,
+    today: NaiveDate,
+) -> Result<(DateWindow, DateWindow)> {
+    let month_start = today.with_day(1).unwrap_or(today);
+    let current_since = match since {
+        Some(raw) => parse_date(raw, "--since")?,
+        None => month_start,
+    };
+    let current_until = match until {
+        Some(raw) => parse_date(raw, "--until")?,
+        None => today,
+    };
+    if current_since > current_until {
+        anyhow::bail!("--since must not be after --until");
+    }
+
+    let (previous_since, previous_until) = match (previous_since, previous_until) {
+        (Some(from), Some(to)) => (
+            parse_date(from, "--previous-since")?,
+            parse_date(to, "--previous-until")?,
+        ),
+        (None, None) if since.is_none() && until.is_none() => {
+            let previous_until = month_start - Duration::days(1);
+            (
+                previous_until.with_day(1).unwrap_or(previous_until),
+                previous_until,
+            )
+        }
+        (None, None) => {
+            let length = current_until - current_since;
+            let previous_until = current_since - Duration::days(1);
+            (previous_until - length, previous_until)
+        }
+        _ => anyhow::bail!("--previous-since and --previous-until must be passed together"),
+    };
+    if previous_since > previous_until {
+        anyhow::bail!("--previous-since must not be after --previous-until");
+    }
+
+    let format = |date: NaiveDate| date.format("%Y-%m-%d").to_string();
+    Ok((
+        DateWindow {
+            since: format(current_since),
+            until: format(current_until),
+        },
+        DateWindow {
+            since: format(previous_since),
+            until: format(previous_until),
+        },
+    ))
+}
+
+/// The current window's bounds from the shared date flags, resolved the way
+/// every report resolves them: relative values are expanded against `today`,
+/// `--until-exclusive` steps back a day, and `--year` alone covers that
+/// calendar year.
+fn current_bounds(
+    date: &crate::DateRangeFlags,
+    today: NaiveDate,
+) -> (Option<String>, Option<String>) {
+    let (since, until) = crate::build_date_filter_for_date(date, today);
+    let until = crate::inclusive_until(until, date.until_exclusive);
+    match crate::normalize_year_filter(date) {
+        Some(year) if since.is_none() && until.is_none() => {
+            (Some(format!("{year}-01-01")), Some(format!("{year}-12-31")))
+        }
+        _ => (since, until),
+    }
+}
+
+fn parse_date(raw: &str, flag: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("{} expects a YYYY-MM-DD date, got '{}'", flag, raw))
+}
+
+fn snapshot(window: &DateWindow, report: &ModelReport) -> RangeSnapshot {
+    RangeSnapshot {
+        since: window.since.clone(),
+        until: window.until.clone(),
+        total_cost: report.total_cost,
+        entries: report
+            .entries
+            .iter()
+            .map(|entry| ModelSnapshotEntry {
+                model: entry.model.clone(),
+                provider: entry.provider.clone(),
+                total_tokens: crate::saturating_token_total(
+                    entry.input,
+                    entry.output,
+                    entry.cache_read,
+                    entry.cache_write,
+                )
+                .saturating_add(entry.reasoning),
+                message_count: entry.message_count,
+                cost: entry.cost,
+            })
+            .collect(),
+    }
+}
+
+fn cost_by_model(entries: &[ModelUsage]) -> BTreeMap<&str, f64> {
+    let mut costs: BTreeMap<&str, f64> = BTreeMap::new();
+    for entry in entries {
+        *costs.entry(entry.model.as_str()).or_default() += entry.cost;
+    }
+    costs
+}
+
+/// Join two model-grouped reports on the model key and compute per-model
+/// cost deltas, sorted by largest increase first.
+pub fn diff_model_entries(current: &[ModelUsage], previous: &[ModelUsage]) -> Vec<ModelDelta> {
+    let current_costs = cost_by_model(current);
+    let previous_costs = cost_by_model(previous);
+
+    let mut models: Vec<&str> = current_costs
+        .keys()
+        .chain(previous_costs.keys())
+        .copied()
+        .collect();
+    models.sort_unstable();
+    models.dedup();
+
+    let mut deltas: Vec<ModelDelta> = models
+        .into_iter()
+        .map(|model| {
+            let current_cost = current_costs.get(model).copied();
+            let previous_cost = previous_costs.get(model).copied();
+            let change = current_cost.unwrap_or(0.0) - previous_cost.unwrap_or(0.0);
+            let percent_change = previous_cost
+                .filter(|previous| *previous > 0.0)
+                .map(|previous| change / previous * 100.0);
+            ModelDelta {
+                model: model.to_string(),
+                current_cost,
+                previous_cost,
+                change,
+                percent_change,
+            }
+        })
+        .collect();
+
+    deltas.sort_by(|a, b| {
+        b.change
+            .partial_cmp(&a.change)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.model.cmp(&b.model))
+    });
+    deltas
+}
+
+fn format_optional_cost(cost: Option<f64>) -> String {
+    cost.map(crate::format_currency)
+        .unwrap_or_else(|| "—".to_string())
+}
+
+fn format_change(change: f64) -> String {
+    if change < 0.0 {
+        format!("-{}", crate::format_currency(-change))
+    } else {
+        format!("+{}", crate::format_currency(change))
+    }
+}
+
+fn format_percent(percent: Option<f64>) -> String {
+    match percent {
+        Some(value) if value.is_finite() => format!("{:+.1}%", value),
+        _ => "—".to_string(),
+    }
+}
+
+pub fn run(options: DiffOptions) -> Result<()> {
+    use colored::Colorize;
+    use tokio::runtime::Runtime;
+    use tokscale_core::{get_model_report, GroupBy, ReportOptions};
+
+    let today = chrono::Local::now().date_naive();
+    let (since, until) = current_bounds(&options.date, today);
+    let previous_bound = |raw: &Option<String>| {
+        raw.as_deref()
+            .map(|raw| crate::resolve_date_bound(raw, today))
+    };
+    let (current_window, previous_window) = resolve_windows(
+        since.as_deref(),
+        until.as_deref(),
+        previous_bound(&options.previous_since).as_deref(),
+        previous_bound(&options.previous_until).as_deref(),
+        today,
+    )?;
+
+    let spinner = if options.no_spinner {
+        None
+    } else {
+        Some(crate::LightSpinner::start("Scanning session data..."))
+    };
+    let use_env_roots = crate::use_env_roots(&options.home_dir);
+    let scanner_settings = crate::tui::settings::load_scanner_settings_for_home(&options.home_dir);
+    let report_options = |window: &DateWindow| ReportOptions {
+        home_dir: options.home_dir.clone(),
+        use_env_roots,
+        clients: options.clients.clone(),
+        since: Some(window.since.clone()),
+        until: Some(window.until.clone()),
+        group_by: GroupBy::Model,
+        scanner_settings: scanner_settings.clone(),
+        ..crate::parse_flags::report_options()
+    };
+
+    let rt = Runtime::new()?;
+    let reports = rt.block_on(async {
+        let current = get_model_report(report_options(&current_window)).await?;
+        let previous = get_model_report(report_options(&previous_window)).await?;
+        Ok::<_, String>((current, previous))
+    });
+    if let Some(spinner) = spinner {
+        spinner.stop();
+    }
+    let (current, previous) = reports.map_err(|e| anyhow::anyhow!(e))?;
+
+    let deltas = diff_model_entries(&current.entries, &previous.entries);
+    let total_change = current.total_cost - previous.total_cost;
+
+    if options.json {
+        let output = DiffJson {
+            current: snapshot(&current_window, &current),
+            previous: snapshot(&previous_window, &previous),
+            total_change,
+            deltas,
+        };
+        let display_currency = crate::currency::current();
+        if display_currency.is_usd() {
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else {
+            let mut output = serde_json::to_value(&output)?;
+            display_currency.annotate_json(&mut output);
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        return Ok(());
+    }
+
+    use comfy_table::{Attribute, Cell, CellAlignment, Color, ContentArrangement, Table};
+
+    let mut table = Table::new();
+    table.load_preset(crate::table_preset());
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.enforce_styling();
+    table.set_header(vec![
+        Cell::new("Model").fg(Color::Cyan),
+        Cell::new("Current").fg(Color::Cyan),
+        Cell::new("Previous").fg(Color::Cyan),
+        Cell::new("Change").fg(Color::Cyan),
+        Cell::new("Change %").fg(Color::Cyan),
+    ]);
+
+    for delta in &deltas {
+        let change_color = if delta.change > 0.0 {
+            Color::Red
+        } else if delta.change < 0.0 {
+            Color::Green
+        } else {
+            Color::Reset
+        };
+        table.add_row(vec![
+            Cell::new(&delta.model),
+            Cell::new(format_optional_cost(delta.current_cost)).set_alignment(CellAlignment::Right),
+            Cell::new(format_optional_cost(delta.previous_cost))
+                .set_alignment(CellAlignment::Right),
+            Cell::new(format_change(delta.change))
+                .fg(change_color)
+                .set_alignment(CellAlignment::Right),
+            Cell::new(format_percent(delta.percent_change)).set_alignment(CellAlignment::Right),
+        ]);
+    }
+
+    let total_percent =
+        (previous.total_cost > 0.0).then(|| total_change / previous.total_cost * 100.0);
+    table.add_row(vec![
+        Cell::new("Total")
+            .fg(Color::Yellow)
+            .add_attribute(Attribute::Bold),
+        Cell::new(crate::format_currency(current.total_cost))
+            .fg(Color::Yellow)
+            .set_alignment(CellAlignment::Right),
+        Cell::new(crate::format_currency(previous.total_cost))
+            .fg(Color::Yellow)
+            .set_alignment(CellAlignment::Right),
+        Cell::new(format_change(total_change))
+            .fg(Color::Yellow)
+            .set_alignment(CellAlignment::Right),
+        Cell::new(format_percent(total_percent))
+            .fg(Color::Yellow)
+            .set_alignment(CellAlignment::Right),
+    ]);
+
+    println!(
+        "\n  {}\n",
+        format!(
+            "Cost Diff ({} → {} vs {} → {})",
+            current_window.since,
+            current_window.until,
+            previous_window.since,
+            previous_window.until
+        )
+        .cyan()
+    );
+    println!("{}", crate::dim_borders(&table.to_string()));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokscale_core::ModelPerformance;
+
+    fn usage(model: &str, cost: f64) -> ModelUsage {
+        ModelUsage {
+            client: "claude".to_string(),
+            merged_clients: None,
+            workspace_key: None,
+            workspace_label: None,
+            session_id: None,
+            model: model.to_string(),
+            provider: "anthropic".to_string(),
+            raw_provider: "anthropic".to_string(),
+            input: 0,
+            output: 0,
+            cache_read: 0,
+            cache_write: 0,
+            reasoning: 0,
+            message_count: 1,
+            session_count: 1,
+            first_date: "2024-12-01".to_string(),
+            last_date: "2024-12-01".to_string(),
+            cost,
+            pricing_source: None,
+            cost_comparison: None,
+            estimated_savings: 0.0,
+            request_bytes: None,
+            response_bytes: None,
+            performance: ModelPerformance::default(),
+        }
+    }
+
+    fn date(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn diff_joins_on_model_and_sorts_by_largest_increase() {
+        let current = vec![
+            usage("claude-opus-4-6", 12.0),
+            usage("claude-sonnet-4-5", 3.0),
+            usage("gpt-5.5", 1.0),
+        ];
+        let previous = vec![
+            usage("claude-opus-4-6", 8.0),
+            usage("claude-sonnet-4-5", 4.0),
+            usage("gemini-3-pro", 2.0),
+        ];
+
+        let deltas = diff_model_entries(&current, &previous);
+        let order: Vec<&str> = deltas.iter().map(|d| d.model.as_str()).collect();
+        assert_eq!(
+            order,
+            vec![
+                "claude-opus-4-6",
+                "gpt-5.5",
+                "claude-sonnet-4-5",
+                "gemini-3-pro"
+            ]
+        );
+
+        assert_eq!(deltas[0].change, 4.0);
+        assert_eq!(deltas[0].percent_change, Some(50.0));
+
+        let new_model = &deltas[1];
+        assert_eq!(new_model.previous_cost, None);
+        assert_eq!(new_model.percent_change, None);
+        assert_eq!(format_optional_cost(new_model.previous_cost), "—");
+
+        let dropped = &deltas[3];
+        assert_eq!(dropped.current_cost, None);
+        assert_eq!(dropped.change, -2.0);
+        assert_eq!(dropped.percent_change, Some(-100.0));
+    }
+
+    #[test]
+    fn default_windows_compare_month_to_date_with_previous_month() {
+        let (current, previous) = resolve_windows(None, None, None, None, date("2026-03-15"))
+            .expect("default windows resolve");
+        assert_eq!(current.since, "2026-03-01");
+        assert_eq!(current.until, "2026-03-15");
+        assert_eq!(previous.since, "2026-02-01");
+        assert_eq!(previous.until, "2026-02-28");
+    }
+
+    #[test]
+    fn explicit_current_window_defaults_previous_to_equal_length() {
+        let (current, previous) = resolve_windows(
+            Some("2026-03-08"),
+            Some("2026-03-14"),
+            None,
+            None,
+            date("2026-03-20"),
+        )
+        .unwrap();
+        assert_eq!(current.since, "2026-03-08");
+        assert_eq!(previous.since, "2026-03-01");
+        assert_eq!(previous.until, "2026-03-07");
+    }
+
+    #[test]
+    fn current_bounds_use_the_shared_date_flags() {
+        let today = date("2026-03-20");
+        let relative = crate::DateRangeFlags {
+            since: Some("7d".to_string()),
+            until: Some("2026-03-20".to_string()),
+            until_exclusive: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            current_bounds(&relative, today),
+            (
+                Some("2026-03-13".to_string()),
+                Some("2026-03-19".to_string())
+            )
+        );
+
+        let year = crate::DateRangeFlags {
+            year: Some("2025".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            current_bounds(&year, today),
+            (
+                Some("2025-01-01".to_string()),
+                Some("2025-12-31".to_string())
+            )
+        );
+
+        let week = crate::DateRangeFlags {
+            week: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            current_bounds(&week, today),
+            (
+                Some("2026-03-14".to_string()),
+                Some("2026-03-20".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn previous_window_flags_must_be_paired() {
+        assert!(resolve_windows(None, None, Some("2026-01-01"), None, date("2026-03-20")).is_err());
+        assert!(resolve_windows(Some("bad"), None, None, None, date("2026-03-20")).is_err());
+    }
+
+    #[test]
+    fn change_and_percent_formatting() {
+        assert_eq!(format_change(1.5), "+$1.50");
+        assert_eq!(format_change(-0.25), "-$0.25");
+        assert_eq!(format_percent(Some(12.345)), "+12.3%");
+        assert_eq!(format_percent(None), "—");
+    }
+}
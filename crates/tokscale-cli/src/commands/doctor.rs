@@ -0,0 +1,358 @@
+//! `tokscale doctor`: read-only environment diagnostics.
+//!
+//! Reports the home directory, every client's resolved scan path (same
+//! resolution as `tokscale clients`), whether the pricing sources answer,
+//! whether the cache directory is writable, and the local timezone. Failed
+//! checks are marked but never change the exit code.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use tokscale_core::ClientId;
+
+/// How long each pricing source gets to answer before it counts as down.
+const PRICING_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct DoctorOptions {
+    pub json: bool,
+    pub home_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckSection {
+    Clients,
+    Pricing,
+    Cache,
+    Timezone,
+}
+
+impl CheckSection {
+    fn title(self) -> &'static str {
+        match self {
+            CheckSection::Clients => "Client paths",
+            CheckSection::Pricing => "Pricing sources",
+            CheckSection::Cache => "Cache",
+            CheckSection::Timezone => "Timezone",
+        }
+    }
+}
+
+/// Outcome of a single diagnostic line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckResult {
+    pub section: CheckSection,
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+/// What sits at a client's scan path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathState {
+    Missing,
+    Empty,
+    /// A directory with this many top-level entries, or a single file (1).
+    HasFiles(usize),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DoctorJson<'a> {
+    home_dir: String,
+    checks: &'a [CheckResult],
+}
+
+pub fn path_state(path: &Path) -> PathState {
+    if path.is_file() {
+        return PathState::HasFiles(1);
+    }
+    match std::fs::read_dir(path) {
+        Ok(entries) => match entries.count() {
+            0 => PathState::Empty,
+            count => PathState::HasFiles(count),
+        },
+        Err(_) => PathState::Missing,
+    }
+}
+
+pub fn client_path_check(label: &str, path: &str, state: PathState) -> CheckResult {
+    let (ok, status) = match state {
+        PathState::Missing => (false, "not found".to_string()),
+        PathState::Empty => (false, "empty".to_string()),
+        PathState::HasFiles(1) => (true, "1 entry".to_string()),
+        PathState::HasFiles(count) => (true, format!("{count} entries")),
+    };
+    CheckResult {
+        section: CheckSection::Clients,
+        name: label.to_string(),
+        ok,
+        detail: format!("{path} ({status})"),
+    }
+}
+
+/// `outcome` is the HTTP status the source answered with, or the transport
+/// error when it never answered.
+pub fn pricing_check(source: &str, url: &str, outcome: Result<u16, String>) -> CheckResult {
+    let (ok, detail) = match outcome {
+        Ok(status) if (200..300).contains(&status) => (true, format!("{url} (HTTP {status})")),
+        Ok(status) => (false, format!("{url} (HTTP {status})")),
+        Err(err) => (false, format!("{url} ({err})")),
+    };
+    CheckResult {
+        section: CheckSection::Pricing,
+        name: source.to_string(),
+        ok,
+        detail,
+    }
+}
+
+/// `probe` is the result of writing a scratch file where the cache lives.
+pub fn cache_check(dir: &Path, exists: bool, probe: Result<(), String>) -> CheckResult {
+    let dir_str = dir.to_string_lossy();
+    let (ok, detail) = match (exists, probe) {
+        (true, Ok(())) => (true, format!("{dir_str} (writable)")),
+        (false, Ok(())) => (true, format!("{dir_str} (will be created on first run)")),
+        (_, Err(err)) => (false, format!("{dir_str} (not writable: {err})")),
+    };
+    CheckResult {
+        section: CheckSection::Cache,
+        name: "Cache directory".to_string(),
+        ok,
+        detail,
+    }
+}
+
+pub fn timezone_check(tz_env: Option<&str>, offset: chrono::FixedOffset) -> CheckResult {
+    let name = tz_env
+        .map(str::trim)
+        .filter(|tz| !tz.is_empty())
+        .unwrap_or("system local");
+    CheckResult {
+        section: CheckSection::Timezone,
+        name: "Timezone".to_string(),
+        ok: true,
+        detail: format!("{name} (UTC{offset})"),
+    }
+}
+
+/// Write and remove a scratch file in `dir`, or in its nearest existing
+/// ancestor when `dir` has not been created yet. Nothing is left behind.
+fn probe_writable(dir: &Path) -> Result<(), String> {
+    let target = dir
+        .ancestors()
+        .find(|candidate| candidate.is_dir())
+        .ok_or_else(|| "no existing parent directory".to_string())?;
+    let probe = target.join(format!(".tokscale-doctor-{}", std::process::id()));
+    std::fs::write(&probe, b"").map_err(|err| err.to_string())?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+async fn probe_url(client: &reqwest::Client, url: &str) -> Result<u16, String> {
+    client
+        .get(url)
+        .send()
+        .await
+        .map(|response| response.status().as_u16())
+        .map_err(|err| {
+            if err.is_timeout() {
+                "timed out".to_string()
+            } else {
+                "unreachable".to_string()
+            }
+        })
+}
+
+fn probe_sources(urls: &[&str]) -> Result<Vec<Result<u16, String>>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(PRICING_PROBE_TIMEOUT)
+        .build()
+        .map_err(|err| err.to_string())?;
+    let rt = tokio::runtime::Runtime::new().map_err(|err| err.to_string())?;
+    Ok(rt.block_on(async {
+        let mut outcomes = Vec::with_capacity(urls.len());
+        for url in urls {
+            outcomes.push(probe_url(&client, url).await);
+        }
+        outcomes
+    }))
+}
+
+fn pricing_checks() -> Vec<CheckResult> {
+    use tokscale_core::pricing::{litellm, openrouter};
+
+    let sources = [
+        ("LiteLLM", litellm::PRICING_URL),
+        ("OpenRouter", openrouter::MODELS_URL),
+    ];
+    let urls: Vec<&str> = sources.iter().map(|(_, url)| *url).collect();
+    let outcomes = probe_sources(&urls).unwrap_or_else(|err| vec![Err(err); sources.len()]);
+
+    sources
+        .iter()
+        .zip(outcomes)
+        .map(|((source, url), outcome)| pricing_check(source, url, outcome))
+        .collect()
+}
+
+fn client_checks(home_dir: &str, use_env_roots: bool) -> Vec<CheckResult> {
+    ClientId::iter()
+        .map(|client| {
+            let path = client
+                .data()
+                .resolve_path_with_env_strategy(home_dir, use_env_roots);
+            let state = path_state(Path::new(&path));
+            client_path_check(client.display_name(), &path, state)
+        })
+        .collect()
+}
+
+fn print_checks(home_dir: &str, checks: &[CheckResult]) {
+    println!("\n  {}\n", "tokscale doctor".cyan().bold());
+    println!("  {} {}", "Home directory:".bold(), home_dir);
+
+    let mut current: Option<CheckSection> = None;
+    for check in checks {
+        if current != Some(check.section) {
+            current = Some(check.section);
+            println!("\n  {}", check.section.title().bold());
+        }
+        let marker = if check.ok { "✓".green() } else { "✗".red() };
+        println!("  {} {:<20} {}", marker, check.name, check.detail.dimmed());
+    }
+
+    let failed = checks.iter().filter(|check| !check.ok).count();
+    println!();
+    if failed == 0 {
+        println!("  {}", "All checks passed.".green());
+    } else {
+        println!(
+            "  {}",
+            format!(
+                "{failed} of {} checks failed; clients you do not use are expected to be missing.",
+                checks.len()
+            )
+            .yellow()
+        );
+    }
+    println!();
+}
+
+pub fn run(options: DoctorOptions) -> Result<()> {
+    let use_env_roots = crate::use_env_roots(&options.home_dir);
+    let home_dir: PathBuf = crate::resolve_effective_home_dir(&options.home_dir)
+        .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+    let home_dir_str = home_dir.to_string_lossy().to_string();
+
+    let mut checks = client_checks(&home_dir_str, use_env_roots);
+    checks.extend(pricing_checks());
+
+    let cache_dir = crate::paths::get_cache_dir();
+    checks.push(cache_check(
+        &cache_dir,
+        cache_dir.is_dir(),
+        probe_writable(&cache_dir),
+    ));
+    checks.push(timezone_check(
+        std::env::var("TZ").ok().as_deref(),
+        *chrono::Local::now().offset(),
+    ));
+
+    if options.json {
+        let output = DoctorJson {
+            home_dir: home_dir_str,
+            checks: &checks,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print_checks(&home_dir_str, &checks);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_path_check_passes_only_when_files_exist() {
+        let missing = client_path_check("Claude Code", "/h/.claude", PathState::Missing);
+        assert!(!missing.ok);
+        assert_eq!(missing.detail, "/h/.claude (not found)");
+
+        let empty = client_path_check("Claude Code", "/h/.claude", PathState::Empty);
+        assert!(!empty.ok);
+        assert_eq!(empty.detail, "/h/.claude (empty)");
+
+        let found = client_path_check("Claude Code", "/h/.claude", PathState::HasFiles(3));
+        assert!(found.ok);
+        assert_eq!(found.detail, "/h/.claude (3 entries)");
+        assert_eq!(found.section, CheckSection::Clients);
+    }
+
+    #[test]
+    fn path_state_distinguishes_missing_empty_and_populated() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(path_state(&dir.path().join("nope")), PathState::Missing);
+        assert_eq!(path_state(dir.path()), PathState::Empty);
+
+        let file = dir.path().join("session.jsonl");
+        std::fs::write(&file, "{}").unwrap();
+        assert_eq!(path_state(dir.path()), PathState::HasFiles(1));
+        assert_eq!(path_state(&file), PathState::HasFiles(1));
+    }
+
+    #[test]
+    fn pricing_check_requires_a_success_status() {
+        assert!(pricing_check("LiteLLM", "https://x", Ok(200)).ok);
+
+        let throttled = pricing_check("LiteLLM", "https://x", Ok(429));
+        assert!(!throttled.ok);
+        assert_eq!(throttled.detail, "https://x (HTTP 429)");
+
+        let offline = pricing_check("OpenRouter", "https://y", Err("timed out".into()));
+        assert!(!offline.ok);
+        assert_eq!(offline.detail, "https://y (timed out)");
+    }
+
+    #[test]
+    fn cache_check_reports_writability() {
+        let dir = Path::new("/cache/tokscale");
+        assert!(cache_check(dir, true, Ok(())).ok);
+
+        let pending = cache_check(dir, false, Ok(()));
+        assert!(pending.ok);
+        assert!(pending.detail.contains("will be created"));
+
+        let denied = cache_check(dir, true, Err("permission denied".into()));
+        assert!(!denied.ok);
+        assert!(denied.detail.contains("not writable: permission denied"));
+    }
+
+    #[test]
+    fn probe_writable_leaves_no_file_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("not-yet").join("cache");
+        assert!(probe_writable(&cache).is_ok());
+        assert!(!cache.exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn timezone_check_prefers_tz_and_shows_the_offset() {
+        let offset = chrono::FixedOffset::east_opt(9 * 3600).unwrap();
+        assert_eq!(
+            timezone_check(Some("Asia/Tokyo"), offset).detail,
+            "Asia/Tokyo (UTC+09:00)"
+        );
+        assert_eq!(
+            timezone_check(None, chrono::FixedOffset::east_opt(0).unwrap()).detail,
+            "system local (UTC+00:00)"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{ClientFlags, DateRangeFlags};
+use clap::Subcommand;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[derive(Subcommand)]
+pub enum ExportSubcommand {
+    #[command(about = "Write raw parsed messages to an SQLite database for ad-hoc SQL")]
+    Sqlite {
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Database file to write (replaced if it exists)"
+        )]
+        output: String,
+        #[command(flatten)]
+        clients: ClientFlags,
+        #[command(flatten)]
+        date: DateRangeFlags,
+        #[arg(
+            long,
+            help = "Gzip-compress the database (implied by a .gz/.gzip output path)"
+        )]
+        gzip: bool,
+        #[arg(long, help = "Disable spinner")]
+        no_spinner: bool,
+    },
+    #[command(
+        about = "Push per-model token and cost gauges to an OpenTelemetry collector (OTLP/HTTP)"
+    )]
+    Otlp {
+        #[arg(
+            long,
+            value_name = "URL",
+            help = "Collector base URL (default: $OTEL_EXPORTER_OTLP_ENDPOINT, then http://localhost:4318)"
+        )]
+        endpoint: Option<String>,
+        #[command(flatten)]
+        clients: ClientFlags,
+        #[command(flatten)]
+        date: DateRangeFlags,
+        #[arg(long, help = "Disable spinner")]
+        no_spinner: bool,
+    },
+}
+
+/// Whether output should be gzip-compressed: always with `--gzip`, and
+/// otherwise only when writing to a file whose name ends in `.gz`/`.gzip`.
+/// Stdout is never compressed implicitly.
+pub fn wants_gzip(gzip: bool, output: Option<&str>) -> bool {
+    gzip || output.is_some_and(|path| {
+        let path = path.to_ascii_lowercase();
+        path.ends_with(".gz") || path.ends_with(".gzip")
+    })
+}
+
+/// Gzip-compress `data` at the default compression level.
+pub fn gzip_bytes(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+/// Stream-compress the file at `src` into a new gzip file at `dest`.
+pub fn gzip_file(src: &Path, dest: &Path) -> io::Result<()> {
+    let mut input = std::fs::File::open(src)?;
+    let mut encoder = GzEncoder::new(std::fs::File::create(dest)?, Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    fn gunzip(data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        GzDecoder::new(data).read_to_end(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn wants_gzip_follows_flag_and_suffix() {
+        assert!(wants_gzip(true, None));
+        assert!(wants_gzip(true, Some("data.json")));
+        assert!(wants_gzip(false, Some("data.json.gz")));
+        assert!(wants_gzip(false, Some("DATA.JSON.GZIP")));
+        assert!(!wants_gzip(false, Some("data.json")));
+        assert!(!wants_gzip(false, Some("data.gzx")));
+        assert!(!wants_gzip(false, None));
+    }
+
+    #[test]
+    fn gzip_bytes_round_trips_exact_json() {
+        let json = serde_json::to_string_pretty(&serde_json::json!({
+            "contributions": [{ "date": "2026-03-01", "totals": { "tokens": 42 } }],
+            "summary": { "totalCost": 1.25 }
+        }))
+        .unwrap();
+
+        let compressed = gzip_bytes(json.as_bytes()).unwrap();
+
+        assert_eq!(&compressed[..2], &[0x1f, 0x8b]);
+        assert_eq!(gunzip(&compressed), json.as_bytes());
+    }
+
+    #[test]
+    fn gzip_file_round_trips_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("usage.db");
+        let dest = dir.path().join("usage.db.gz");
+        let contents: Vec<u8> = (0..10_000u32).flat_map(|n| n.to_le_bytes()).collect();
+        std::fs::write(&src, &contents).unwrap();
+
+        gzip_file(&src, &dest).unwrap();
+
+        assert_eq!(gunzip(&std::fs::read(&dest).unwrap()), contents);
+    }
+}
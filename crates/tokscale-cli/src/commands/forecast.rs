@@ -0,0 +1,341 @@
+//! `tokscale forecast`: project spend forward from the recent daily trend.
+//!
+//! Daily costs for the lookback window come from `get_model_report` with
+//! `daily_costs` set, which is built on `aggregator::aggregate_by_date`, so
+//! client filters, `--home`, and scanner settings behave exactly like
+//! `tokscale models`. The window ends yesterday because today is still
+//! partial; the projection starts today.
+//!
+//! Two models, both deliberately simple:
+//! - `linear`: an ordinary least-squares line through (day index, cost).
+//!   Day `i` of the horizon is projected at `intercept + slope * (n + i)`,
+//!   where `n` is the lookback length.
+//! - `moving-average`: the mean cost of the last seven lookback days,
+//!   repeated for every projected day.
+//!
+//! Projected days never go below zero.
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::HashMap;
+use tokscale_core::DailyCost;
+
+pub const DEFAULT_LOOKBACK_DAYS: u32 = 30;
+pub const DEFAULT_HORIZON_DAYS: u32 = 30;
+
+/// Days averaged by [`ForecastMethod::MovingAverage`].
+const MOVING_AVERAGE_DAYS: usize = 7;
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ForecastMethod {
+    #[default]
+    Linear,
+    MovingAverage,
+}
+
+pub struct ForecastOptions {
+    pub json: bool,
+    pub home_dir: Option<String>,
+    pub clients: Option<Vec<String>>,
+    pub lookback: u32,
+    pub horizon: u32,
+    pub method: ForecastMethod,
+    pub no_spinner: bool,
+}
+
+/// Least-squares fit `cost = intercept + slope * day`, with `day` counted
+/// from 0 at the start of the lookback window.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinearFit {
+    pub slope: f64,
+    pub intercept: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectedDay {
+    pub date: String,
+    pub cost: f64,
+    pub cumulative_cost: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ForecastJson<'a> {
+    method: ForecastMethod,
+    lookback_since: String,
+    lookback_until: String,
+    lookback_cost: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fit: Option<LinearFit>,
+    horizon_days: u32,
+    projected_total: f64,
+    days: &'a [ProjectedDay],
+}
+
+/// Least-squares line through `(i, costs[i])`. A single point (or none) is
+/// a flat line at its value.
+pub fn fit_linear(costs: &[f64]) -> LinearFit {
+    let n = costs.len() as f64;
+    if costs.len() < 2 {
+        return LinearFit {
+            slope: 0.0,
+            intercept: costs.first().copied().unwrap_or(0.0),
+        };
+    }
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = costs.iter().sum::<f64>() / n;
+    let (covariance, variance) =
+        costs
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(covariance, variance), (day, cost)| {
+                let dx = day as f64 - mean_x;
+                (covariance + dx * (cost - mean_y), variance + dx * dx)
+            });
+    let slope = covariance / variance;
+    LinearFit {
+        slope,
+        intercept: mean_y - slope * mean_x,
+    }
+}
+
+/// Mean of the last [`MOVING_AVERAGE_DAYS`] costs (fewer when the series is
+/// shorter).
+pub fn moving_average(costs: &[f64]) -> f64 {
+    let recent = &costs[costs.len().saturating_sub(MOVING_AVERAGE_DAYS)..];
+    if recent.is_empty() {
+        return 0.0;
+    }
+    recent.iter().sum::<f64>() / recent.len() as f64
+}
+
+/// Costs for each of the `horizon` days after a lookback of `costs`, with a
+/// running total, dated from `first_day`.
+pub fn project(
+    costs: &[f64],
+    method: ForecastMethod,
+    horizon: u32,
+    first_day: NaiveDate,
+) -> Vec<ProjectedDay> {
+    let fit = fit_linear(costs);
+    let average = moving_average(costs);
+    let mut cumulative_cost = 0.0;
+    (0..horizon)
+        .map(|offset| {
+            let cost = match method {
+                ForecastMethod::Linear => {
+                    fit.intercept + fit.slope * (costs.len() as f64 + f64::from(offset))
+                }
+                ForecastMethod::MovingAverage => average,
+            }
+            .max(0.0);
+            cumulative_cost += cost;
+            ProjectedDay {
+                date: (first_day + Duration::days(i64::from(offset)))
+                    .format("%Y-%m-%d")
+                    .to_string(),
+                cost,
+                cumulative_cost,
+            }
+        })
+        .collect()
+}
+
+/// One cost per day from `since` through `until`, zero for days the report
+/// has no usage on.
+pub fn window_costs(daily_costs: &[DailyCost], since: NaiveDate, until: NaiveDate) -> Vec<f64> {
+    let by_date: HashMap<&str, f64> = daily_costs
+        .iter()
+        .map(|day| (day.date.as_str(), day.cost))
+        .collect();
+    since
+        .iter_days()
+        .take_while(|day| *day <= until)
+        .map(|day| {
+            by_date
+                .get(day.format("%Y-%m-%d").to_string().as_str())
+                .copied()
+                .unwrap_or(0.0)
+        })
+        .collect()
+}
+
+pub fn run(options: ForecastOptions) -> Result<()> {
+    use tokio::runtime::Runtime;
+    use tokscale_core::{get_model_report, GroupBy, ReportOptions};
+
+    let today = chrono::Local::now().date_naive();
+    let lookback_until = today - Duration::days(1);
+    let lookback_since = today - Duration::days(i64::from(options.lookback));
+    let format = |date: NaiveDate| date.format("%Y-%m-%d").to_string();
+
+    let spinner = if options.no_spinner {
+        None
+    } else {
+        Some(crate::LightSpinner::start("Scanning session data..."))
+    };
+    let rt = Runtime::new()?;
+    let report = rt.block_on(get_model_report(ReportOptions {
+        home_dir: options.home_dir.clone(),
+        use_env_roots: crate::use_env_roots(&options.home_dir),
+        clients: options.clients.clone(),
+        since: Some(format(lookback_since)),
+        until: Some(format(lookback_until)),
+        group_by: GroupBy::Model,
+        scanner_settings: crate::tui::settings::load_scanner_settings_for_home(&options.home_dir),
+        daily_costs: true,
+        ..crate::parse_flags::report_options()
+    }));
+    if let Some(spinner) = spinner {
+        spinner.stop();
+    }
+    let report = report.map_err(|e| anyhow::anyhow!(e))?;
+
+    let costs = window_costs(
+        report.daily_costs.as_deref().unwrap_or_default(),
+        lookback_since,
+        lookback_until,
+    );
+    let days = project(&costs, options.method, options.horizon, today);
+    let projected_total = days.last().map_or(0.0, |day| day.cumulative_cost);
+
+    if options.json {
+        let output = ForecastJson {
+            method: options.method,
+            lookback_since: format(lookback_since),
+            lookback_until: format(lookback_until),
+            lookback_cost: report.total_cost,
+            fit: (options.method == ForecastMethod::Linear).then(|| fit_linear(&costs)),
+            horizon_days: options.horizon,
+            projected_total,
+            days: &days,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    use colored::Colorize;
+    use comfy_table::{Cell, CellAlignment, Color, ContentArrangement, Table};
+
+    let mut table = Table::new();
+    table.load_preset(crate::table_preset());
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.enforce_styling();
+    table.set_header(vec![
+        Cell::new("Date").fg(Color::Cyan),
+        Cell::new("Projected").fg(Color::Cyan),
+        Cell::new("Cumulative").fg(Color::Cyan),
+    ]);
+    for day in &days {
+        table.add_row(vec![
+            Cell::new(&day.date),
+            Cell::new(crate::format_currency(day.cost)).set_alignment(CellAlignment::Right),
+            Cell::new(crate::format_currency(day.cumulative_cost))
+                .fg(Color::Green)
+                .set_alignment(CellAlignment::Right),
+        ]);
+    }
+
+    let method = match options.method {
+        ForecastMethod::Linear => "linear trend",
+        ForecastMethod::MovingAverage => "7-day moving average",
+    };
+    println!(
+        "\n  {}\n",
+        format!("Cost Forecast ({}, next {} days)", method, options.horizon).bold()
+    );
+    println!("{}", crate::dim_borders(&table.to_string()));
+    println!(
+        "\n  {}  {}  {}",
+        "Projected:".bold(),
+        crate::format_currency(projected_total).green(),
+        format!(
+            "(lookback {} to {}: {})",
+            format(lookback_since),
+            format(lookback_until),
+            crate::format_currency(report.total_cost)
+        )
+        .bright_black()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn linear_fit_recovers_a_linear_series() {
+        let costs: Vec<f64> = (0..30).map(|day| 2.0 + 0.5 * f64::from(day)).collect();
+        let fit = fit_linear(&costs);
+        assert!((fit.slope - 0.5).abs() < 1e-9, "{fit:?}");
+        assert!((fit.intercept - 2.0).abs() < 1e-9, "{fit:?}");
+
+        let days = project(&costs, ForecastMethod::Linear, 10, date("2025-03-01"));
+        assert_eq!(days.len(), 10);
+        assert_eq!(days[0].date, "2025-03-01");
+        assert_eq!(days[9].date, "2025-03-10");
+        assert!((days[0].cost - 17.0).abs() < 1e-9);
+        // 17.0 + 17.5 + ... + 21.5
+        assert!((days[9].cumulative_cost - 192.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn linear_projection_stops_at_zero() {
+        let costs: Vec<f64> = (0..10).map(|day| 10.0 - f64::from(day)).collect();
+        let days = project(&costs, ForecastMethod::Linear, 5, date("2025-03-01"));
+        assert!(days.iter().all(|day| day.cost == 0.0));
+        assert_eq!(days[4].cumulative_cost, 0.0);
+    }
+
+    #[test]
+    fn moving_average_repeats_the_last_week() {
+        let costs = [100.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        assert!((moving_average(&costs) - 4.0).abs() < 1e-9);
+        assert_eq!(moving_average(&[]), 0.0);
+
+        let days = project(&costs, ForecastMethod::MovingAverage, 3, date("2025-03-01"));
+        let cumulative: Vec<f64> = days.iter().map(|day| day.cumulative_cost).collect();
+        assert_eq!(cumulative, vec![4.0, 8.0, 12.0]);
+    }
+
+    #[test]
+    fn short_series_fit_flat_lines() {
+        assert_eq!(
+            fit_linear(&[3.0]),
+            LinearFit {
+                slope: 0.0,
+                intercept: 3.0
+            }
+        );
+        assert_eq!(fit_linear(&[]).intercept, 0.0);
+    }
+
+    #[test]
+    fn window_costs_fill_days_without_usage() {
+        let daily = [
+            DailyCost {
+                date: "2025-03-02".to_string(),
+                cost: 1.5,
+            },
+            DailyCost {
+                date: "2025-03-04".to_string(),
+                cost: 2.0,
+            },
+        ];
+        assert_eq!(
+            window_costs(&daily, date("2025-03-01"), date("2025-03-05")),
+            vec![0.0, 1.5, 0.0, 2.0, 0.0]
+        );
+    }
+}
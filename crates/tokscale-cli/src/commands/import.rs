@@ -214,6 +214,7 @@ pub fn parse_clawdboard_export(json: &str) -> Result<ImportOutcome> {
                 cache_read: clamp_i64(agg.cache_read_tokens, &mut negative_values_clamped),
                 cache_write: clamp_i64(agg.cache_creation_tokens, &mut negative_values_clamped),
                 reasoning: 0,
+                ..Default::default()
             };
             if cost > 0.0 && tokens.total() == 0 && !is_cursor_legacy_tokenless(&client, &model) {
                 suspect_cost_rows += 1;
@@ -233,6 +234,7 @@ pub fn parse_clawdboard_export(json: &str) -> Result<ImportOutcome> {
                     cache_read: clamp_i64(mb.cache_read_tokens, &mut negative_values_clamped),
                     cache_write: clamp_i64(mb.cache_creation_tokens, &mut negative_values_clamped),
                     reasoning: 0,
+                    ..Default::default()
                 };
                 let raw_cost = sanitize_cost(mb.cost, &mut non_finite_cost_rows);
                 let cost = clamp_f64(raw_cost, &mut negative_values_clamped);
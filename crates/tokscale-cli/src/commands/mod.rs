@@ -1,7 +1,13 @@
 pub mod apple_fm;
 pub mod autosubmit;
 pub mod codex_activity;
+pub mod diff;
+pub mod doctor;
+pub mod export;
+pub mod forecast;
 pub mod import;
+pub mod otlp;
 pub mod report;
+pub mod top_sessions;
 pub mod usage;
 pub mod wrapped;
@@ -0,0 +1,303 @@
+//! `tokscale export otlp`: push the model report to an OpenTelemetry
+//! collector as gauges, once.
+//!
+//! The metric data model ([`metric_points`]) is always compiled. Sending it
+//! needs the `otlp` cargo feature, which pulls in the OpenTelemetry SDK;
+//! without it [`ensure_supported`] and [`push`] return an error naming the
+//! feature.
+
+use tokscale_core::ModelReport;
+
+/// One instrument in the export. Every point carries `client`, `provider`,
+/// and `model` attributes; token points add a `kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Tokens,
+    Messages,
+    Cost,
+}
+
+impl Metric {
+    #[cfg_attr(not(feature = "otlp"), allow(dead_code))]
+    pub fn name(self) -> &'static str {
+        match self {
+            Metric::Tokens => "tokscale.tokens",
+            Metric::Messages => "tokscale.messages",
+            Metric::Cost => "tokscale.cost",
+        }
+    }
+
+    #[cfg_attr(not(feature = "otlp"), allow(dead_code))]
+    fn unit(self) -> &'static str {
+        match self {
+            Metric::Tokens => "{token}",
+            Metric::Messages => "{message}",
+            Metric::Cost => "USD",
+        }
+    }
+
+    #[cfg_attr(not(feature = "otlp"), allow(dead_code))]
+    fn description(self) -> &'static str {
+        match self {
+            Metric::Tokens => "Tokens used in the reported range, by kind",
+            Metric::Messages => "Messages in the reported range",
+            Metric::Cost => "Cost of the reported range",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricPoint {
+    pub metric: Metric,
+    pub attributes: Vec<(&'static str, String)>,
+    pub value: f64,
+}
+
+/// Flatten a report (grouped by client, provider, and model) into gauge
+/// points. Costs that are not finite are left out rather than exported as
+/// NaN.
+pub fn metric_points(report: &ModelReport) -> Vec<MetricPoint> {
+    let mut points = Vec::with_capacity(report.entries.len() * 7);
+    for entry in &report.entries {
+        let attributes = vec![
+            ("client", entry.client.clone()),
+            ("provider", entry.provider.clone()),
+            ("model", entry.model.clone()),
+        ];
+        for (kind, count) in [
+            ("input", entry.input),
+            ("output", entry.output),
+            ("cache_read", entry.cache_read),
+            ("cache_write", entry.cache_write),
+            ("reasoning", entry.reasoning),
+        ] {
+            let mut attributes = attributes.clone();
+            attributes.push(("kind", kind.to_string()));
+            points.push(MetricPoint {
+                metric: Metric::Tokens,
+                attributes,
+                value: count.max(0) as f64,
+            });
+        }
+        points.push(MetricPoint {
+            metric: Metric::Messages,
+            attributes: attributes.clone(),
+            value: entry.message_count.max(0) as f64,
+        });
+        if entry.cost.is_finite() {
+            points.push(MetricPoint {
+                metric: Metric::Cost,
+                attributes,
+                value: entry.cost,
+            });
+        }
+    }
+    points
+}
+
+/// The OTLP/HTTP metrics URL for a collector base URL, matching how
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` is resolved.
+#[cfg_attr(not(feature = "otlp"), allow(dead_code))]
+fn metrics_endpoint(base: &str) -> String {
+    let base = base.trim_end_matches('/');
+    if base.ends_with("/v1/metrics") {
+        base.to_string()
+    } else {
+        format!("{base}/v1/metrics")
+    }
+}
+
+#[cfg(feature = "otlp")]
+pub fn ensure_supported() -> anyhow::Result<()> {
+    Ok(())
+}
+
+#[cfg(not(feature = "otlp"))]
+pub fn ensure_supported() -> anyhow::Result<()> {
+    anyhow::bail!("this build of tokscale has no OTLP support; rebuild with `--features otlp`")
+}
+
+/// Send `points` over OTLP/HTTP and wait for the export to finish.
+///
+/// `endpoint` is a collector base URL. When it is `None` the exporter reads
+/// the standard `OTEL_EXPORTER_OTLP_*` variables (endpoint, headers,
+/// timeout) and falls back to `http://localhost:4318`.
+#[cfg(feature = "otlp")]
+pub fn push(points: &[MetricPoint], endpoint: Option<&str>) -> anyhow::Result<()> {
+    use opentelemetry::metrics::MeterProvider as _;
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::{MetricExporter, WithExportConfig};
+    use opentelemetry_sdk::metrics::SdkMeterProvider;
+    use opentelemetry_sdk::Resource;
+
+    let mut exporter = MetricExporter::builder().with_http();
+    if let Some(endpoint) = endpoint {
+        exporter = exporter.with_endpoint(metrics_endpoint(endpoint));
+    }
+    let provider = SdkMeterProvider::builder()
+        .with_periodic_exporter(exporter.build()?)
+        .with_resource(Resource::builder().with_service_name("tokscale").build())
+        .build();
+
+    let meter = provider.meter("tokscale");
+    let u64_gauge = |metric: Metric| {
+        meter
+            .u64_gauge(metric.name())
+            .with_unit(metric.unit())
+            .with_description(metric.description())
+            .build()
+    };
+    let tokens = u64_gauge(Metric::Tokens);
+    let messages = u64_gauge(Metric::Messages);
+    let cost = meter
+        .f64_gauge(Metric::Cost.name())
+        .with_unit(Metric::Cost.unit())
+        .with_description(Metric::Cost.description())
+        .build();
+
+    for point in points {
+        let attributes: Vec<KeyValue> = point
+            .attributes
+            .iter()
+            .map(|(key, value)| KeyValue::new(*key, value.clone()))
+            .collect();
+        match point.metric {
+            Metric::Tokens => tokens.record(point.value as u64, &attributes),
+            Metric::Messages => messages.record(point.value as u64, &attributes),
+            Metric::Cost => cost.record(point.value, &attributes),
+        }
+    }
+
+    // Shutdown collects and exports once more, so it doubles as the
+    // one-shot push; a separate flush would send every point twice.
+    provider
+        .shutdown()
+        .map_err(|e| anyhow::anyhow!("OTLP export failed: {e}"))
+}
+
+#[cfg(not(feature = "otlp"))]
+pub fn push(_points: &[MetricPoint], _endpoint: Option<&str>) -> anyhow::Result<()> {
+    ensure_supported()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokscale_core::{ModelPerformance, ModelUsage};
+
+    fn usage(client: &str, model: &str, input: i64, cost: f64) -> ModelUsage {
+        ModelUsage {
+            client: client.to_string(),
+            merged_clients: None,
+            workspace_key: None,
+            workspace_label: None,
+            session_id: None,
+            model: model.to_string(),
+            provider: "anthropic".to_string(),
+            raw_provider: "anthropic".to_string(),
+            input,
+            output: 20,
+            cache_read: 5,
+            cache_write: 0,
+            reasoning: 0,
+            message_count: 3,
+            session_count: 1,
+            first_date: "2024-12-01".to_string(),
+            last_date: "2024-12-01".to_string(),
+            cost,
+            pricing_source: None,
+            cost_comparison: None,
+            estimated_savings: 0.0,
+            request_bytes: None,
+            response_bytes: None,
+            performance: ModelPerformance::default(),
+        }
+    }
+
+    fn point<'a>(
+        points: &'a [MetricPoint],
+        metric: Metric,
+        model: &str,
+        kind: Option<&str>,
+    ) -> &'a MetricPoint {
+        points
+            .iter()
+            .find(|point| {
+                point.metric == metric
+                    && point.attributes.contains(&("model", model.to_string()))
+                    && kind
+                        .is_none_or(|kind| point.attributes.contains(&("kind", kind.to_string())))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn metric_points_cover_tokens_by_kind_messages_and_cost() {
+        let report = ModelReport {
+            entries: vec![
+                usage("claude", "claude-sonnet-4-5", 100, 1.5),
+                usage("opencode", "gpt-5", 40, f64::NAN),
+            ],
+            total_input: 140,
+            total_output: 40,
+            total_cache_read: 10,
+            total_cache_write: 0,
+            total_reasoning: 0,
+            total_messages: 6,
+            total_sessions: 2,
+            total_cost: 1.5,
+            total_estimated_savings: 0.0,
+            processing_time_ms: 0,
+            omitted: None,
+            client_timings: None,
+            daily_costs: None,
+            unpriced_models: Vec::new(),
+            pricing_unavailable: false,
+        };
+
+        let points = metric_points(&report);
+
+        // Five token kinds plus messages per row, and cost only where known.
+        assert_eq!(points.len(), 7 + 6);
+        let input = point(&points, Metric::Tokens, "claude-sonnet-4-5", Some("input"));
+        assert_eq!(input.value, 100.0);
+        assert_eq!(
+            input.attributes,
+            vec![
+                ("client", "claude".to_string()),
+                ("provider", "anthropic".to_string()),
+                ("model", "claude-sonnet-4-5".to_string()),
+                ("kind", "input".to_string()),
+            ]
+        );
+        assert_eq!(
+            point(&points, Metric::Tokens, "gpt-5", Some("cache_read")).value,
+            5.0
+        );
+        assert_eq!(point(&points, Metric::Messages, "gpt-5", None).value, 3.0);
+        assert_eq!(
+            point(&points, Metric::Cost, "claude-sonnet-4-5", None).value,
+            1.5
+        );
+        assert!(!points
+            .iter()
+            .any(|p| p.metric == Metric::Cost
+                && p.attributes.contains(&("model", "gpt-5".to_string()))));
+    }
+
+    #[test]
+    fn metrics_endpoint_appends_signal_path_once() {
+        assert_eq!(
+            metrics_endpoint("http://localhost:4318"),
+            "http://localhost:4318/v1/metrics"
+        );
+        assert_eq!(
+            metrics_endpoint("https://otel.example.com/"),
+            "https://otel.example.com/v1/metrics"
+        );
+        assert_eq!(
+            metrics_endpoint("http://collector:4318/v1/metrics"),
+            "http://collector:4318/v1/metrics"
+        );
+    }
+}
@@ -112,8 +112,10 @@ fn populate_wiki_from_sessions(db: &WikiDb, opts: &ReportOptions) -> Result<()>
         clients: None,
         since: None,
         until: None,
+        until_exclusive: false,
         year: None,
         scanner_settings: opts.scanner_settings.clone(),
+        ..crate::parse_flags::local_parse_options()
     })
     .map_err(|e| anyhow::anyhow!("{}", e))?;
 
@@ -1017,10 +1019,10 @@ fn print_report_table(
 
     println!();
     println!(
-        "  {} sessions | {} summarized | ${:.2} total | {} tokens",
+        "  {} sessions | {} summarized | {} total | {} tokens",
         total_sessions.to_string().cyan(),
         summarized.to_string().green(),
-        total_cost,
+        crate::format_currency(total_cost),
         format_tokens(total_tokens).yellow(),
     );
     println!();
@@ -1057,7 +1059,7 @@ fn print_report_table(
             model,
             count,
             format_tokens(*tokens),
-            format!("${:.2}", cost),
+            crate::format_currency(*cost),
         );
     }
     println!("  {}", "─".repeat(62));
@@ -1066,7 +1068,7 @@ fn print_report_table(
         "TOTAL",
         total_sessions,
         format_tokens(total_tokens),
-        format!("${:.2}", total_cost),
+        crate::format_currency(total_cost),
     );
     println!();
 
@@ -1109,7 +1111,7 @@ fn print_report_table(
             display_group.bold(),
             count,
             format_tokens(*tokens),
-            format!("${:.2}", cost),
+            crate::format_currency(*cost),
         );
         if *count > 1 {
             for t in titles.iter().take(3) {
@@ -1134,7 +1136,7 @@ fn print_report_table(
             format!("… +{} more", groups.len() - 15),
             rest_count,
             format_tokens(rest_tokens),
-            format!("${:.2}", rest_cost),
+            crate::format_currency(rest_cost),
         );
     }
     println!("  {}", "─".repeat(67));
@@ -1182,7 +1184,7 @@ fn print_daily_breakdown(entries: &[WikiEntry], full: bool) {
             date.cyan(),
             count,
             format_tokens(*tokens),
-            format!("${:.2}", cost),
+            crate::format_currency(*cost),
         );
         let daily_limit = if full { sessions.len() } else { 5 };
         for s in sessions.iter().take(daily_limit) {
@@ -1195,7 +1197,7 @@ fn print_daily_breakdown(entries: &[WikiEntry], full: bool) {
             };
             println!(
                 "    {:>6} {:<18} {}",
-                format!("${:.2}", s.total_cost),
+                crate::format_currency(s.total_cost),
                 model.dimmed(),
                 display_title,
             );
@@ -1222,7 +1224,7 @@ fn print_session_list(entries: &[WikiEntry], full: bool) {
 
             let title = entry.title.as_deref().unwrap_or("(pending summarization)");
             let model = entry.models_used.first().map(|s| s.as_str()).unwrap_or("-");
-            let cost = format!("${:.2}", entry.total_cost);
+            let cost = crate::format_currency(entry.total_cost);
 
             println!(
                 "  {} {:>6} {:<20} {}",
@@ -1401,8 +1403,17 @@ where
 fn load_pricing_service() -> Option<std::sync::Arc<PricingService>> {
     let fresh = tokio::runtime::Runtime::new()
         .ok()
-        .and_then(|rt| rt.block_on(async { PricingService::get_or_init().await.ok() }));
-    fresh.or_else(|| PricingService::load_cached_any_age().map(std::sync::Arc::new))
+        .and_then(|rt| rt.block_on(async { crate::parse_flags::load_pricing().await.ok() }));
+    // A pinned `--pricing-date` snapshot must not fall back to today's cache.
+    let pricing = fresh.or_else(|| {
+        crate::parse_flags::current()
+            .pricing_date
+            .is_none()
+            .then(PricingService::load_cached_any_age)
+            .flatten()
+            .map(std::sync::Arc::new)
+    })?;
+    Some(std::sync::Arc::new(crate::parse_flags::pricing(&pricing)))
 }
 
 /// Computes a message's cost using the canonical [`PricingService`], honoring
@@ -1421,6 +1432,7 @@ fn compute_msg_cost(msg: &ParsedMessage, pricing: Option<&PricingService>) -> f6
             cache_read: msg.cache_read,
             cache_write: msg.cache_write,
             reasoning: msg.reasoning,
+            ..Default::default()
         },
     )
 }
@@ -1506,6 +1518,7 @@ mod tests {
                 cache_read: msg.cache_read,
                 cache_write: msg.cache_write,
                 reasoning: msg.reasoning,
+                ..Default::default()
             },
         );
 
@@ -0,0 +1,442 @@
+//! `tokscale top-sessions`: leaderboard of the most expensive individual
+//! sessions across every client.
+//!
+//! Sessions come from `get_top_sessions_report`, which runs the regular
+//! report pipeline, so client filters, date flags, `--home`, and scanner
+//! settings behave exactly like `tokscale models`.
+//!
+//! `--reconcile` swaps the leaderboard for a check of the parsers. Some
+//! clients record their own totals (OpenClaw's `totalTokens` and cost). Each
+//! session's reported figures are compared with what tokscale counted and
+//! estimated for the same messages. Sessions that differ by more than
+//! `--reconcile-threshold` percent are listed.
+
+use anyhow::Result;
+use serde::Serialize;
+use tokscale_core::{SessionUsage, TopSessionsReport};
+
+/// Rows shown in the table when `--top` is not given.
+pub const DEFAULT_TOP: usize = 20;
+
+/// Percent difference that flags a session under `--reconcile`.
+pub const DEFAULT_RECONCILE_THRESHOLD_PCT: f64 = 5.0;
+
+/// Longest session label printed before it is elided.
+const MAX_LABEL_CHARS: usize = 48;
+
+pub struct TopSessionsOptions {
+    pub json: bool,
+    pub top: Option<usize>,
+    pub include_zero_cost: bool,
+    /// Set by `--reconcile`: list diverging sessions instead of the
+    /// leaderboard.
+    pub reconcile_threshold: Option<f64>,
+    pub home_dir: Option<String>,
+    pub clients: Option<Vec<String>>,
+    pub project_filter: tokscale_core::ProjectFilter,
+    pub model_filter: tokscale_core::ModelFilter,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub until_exclusive: bool,
+    pub year: Option<String>,
+    pub date_range: Option<String>,
+    pub no_spinner: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TopSessionsJson<'a> {
+    entries: Vec<&'a SessionUsage>,
+    total_sessions: usize,
+    total_cost: f64,
+    processing_time_ms: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FlaggedSessionJson<'a> {
+    #[serde(flatten)]
+    session: &'a SessionUsage,
+    token_diff_pct: Option<f64>,
+    cost_diff_pct: Option<f64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReconcileJson<'a> {
+    threshold_pct: f64,
+    entries: Vec<FlaggedSessionJson<'a>>,
+    /// Sessions that carried any client-reported total.
+    reconciled_sessions: usize,
+    processing_time_ms: u32,
+}
+
+/// Title when the client recorded one, else the raw session id.
+fn session_label(entry: &SessionUsage) -> String {
+    let label = entry
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .unwrap_or(&entry.session_id);
+    if label.chars().count() <= MAX_LABEL_CHARS {
+        return label.to_string();
+    }
+    let truncated: String = label.chars().take(MAX_LABEL_CHARS - 1).collect();
+    format!("{truncated}…")
+}
+
+/// The first `top` sessions, plus every later zero-cost (subscription or
+/// unpriced) session when `include_zero_cost` is set, so `--top` never hides
+/// them.
+fn visible_sessions(
+    entries: &[SessionUsage],
+    top: usize,
+    include_zero_cost: bool,
+) -> Vec<&SessionUsage> {
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(rank, entry)| *rank < top || (include_zero_cost && entry.cost == 0.0))
+        .map(|(_, entry)| entry)
+        .collect()
+}
+
+/// Sessions whose reported totals differ from tokscale's by more than
+/// `threshold_pct`, in report order.
+fn flagged_sessions(entries: &[SessionUsage], threshold_pct: f64) -> Vec<&SessionUsage> {
+    entries
+        .iter()
+        .filter(|entry| {
+            entry
+                .reconciliation
+                .as_ref()
+                .is_some_and(|reconciliation| reconciliation.diverges(threshold_pct))
+        })
+        .collect()
+}
+
+fn format_pct(pct: Option<f64>) -> String {
+    pct.map_or_else(|| "-".to_string(), |pct| format!("{pct:+.1}%"))
+}
+
+fn date_span(entry: &SessionUsage) -> String {
+    if entry.first_date == entry.last_date {
+        entry.first_date.clone()
+    } else {
+        format!("{} → {}", entry.first_date, entry.last_date)
+    }
+}
+
+pub fn run(options: TopSessionsOptions) -> Result<()> {
+    use tokio::runtime::Runtime;
+    use tokscale_core::{get_top_sessions_report, ReportOptions};
+
+    let spinner = if options.no_spinner {
+        None
+    } else {
+        Some(crate::LightSpinner::start("Scanning session data..."))
+    };
+    let use_env_roots = crate::use_env_roots(&options.home_dir);
+    let rt = Runtime::new()?;
+    let report = rt.block_on(get_top_sessions_report(ReportOptions {
+        home_dir: options.home_dir.clone(),
+        use_env_roots,
+        clients: options.clients.clone(),
+        since: options.since.clone(),
+        until: options.until.clone(),
+        until_exclusive: options.until_exclusive,
+        year: options.year.clone(),
+        scanner_settings: crate::tui::settings::load_scanner_settings_for_home(&options.home_dir),
+        project_filter: options.project_filter,
+        model_filter: options.model_filter,
+        ..crate::parse_flags::report_options()
+    }));
+    if let Some(spinner) = spinner {
+        spinner.stop();
+    }
+    let report: TopSessionsReport = report.map_err(|e| anyhow::anyhow!(e))?;
+    let total_sessions = report.entries.len();
+
+    if let Some(threshold_pct) = options.reconcile_threshold {
+        return print_reconciliation(&report, threshold_pct, options.top, options.json);
+    }
+
+    if options.json {
+        let top = options.top.unwrap_or(total_sessions);
+        let output = TopSessionsJson {
+            entries: visible_sessions(&report.entries, top, options.include_zero_cost),
+            total_sessions,
+            total_cost: report.total_cost,
+            processing_time_ms: report.processing_time_ms,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    use colored::Colorize;
+    use comfy_table::{Cell, CellAlignment, Color, ContentArrangement, Table};
+
+    let visible = visible_sessions(
+        &report.entries,
+        options.top.unwrap_or(DEFAULT_TOP),
+        options.include_zero_cost,
+    );
+    let shown = visible.len();
+    let mut table = Table::new();
+    table.load_preset(crate::table_preset());
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.enforce_styling();
+    table.set_header(vec![
+        Cell::new("#").fg(Color::Cyan),
+        Cell::new("Source").fg(Color::Cyan),
+        Cell::new("Session").fg(Color::Cyan),
+        Cell::new("Model").fg(Color::Cyan),
+        Cell::new("Msgs").fg(Color::Cyan),
+        Cell::new("Tokens").fg(Color::Cyan),
+        Cell::new("Dates").fg(Color::Cyan),
+        Cell::new("Cost").fg(Color::Cyan),
+    ]);
+
+    for (rank, entry) in visible.iter().enumerate() {
+        table.add_row(vec![
+            Cell::new(rank + 1).set_alignment(CellAlignment::Right),
+            Cell::new(crate::capitalize_client(&entry.client)),
+            Cell::new(session_label(entry)).fg(Color::White),
+            Cell::new(crate::format_model_name(&entry.dominant_model)),
+            Cell::new(entry.message_count).set_alignment(CellAlignment::Right),
+            Cell::new(crate::format_tokens_with_commas(entry.total_tokens))
+                .set_alignment(CellAlignment::Right),
+            Cell::new(date_span(entry)),
+            Cell::new(crate::format_currency(entry.cost))
+                .fg(Color::Green)
+                .set_alignment(CellAlignment::Right),
+        ]);
+    }
+
+    let title = match &options.date_range {
+        Some(range) => format!("Top Sessions ({range})"),
+        None => "Top Sessions".to_string(),
+    };
+    println!("\n  {}\n", title.bold());
+    println!("{}", crate::dim_borders(&table.to_string()));
+    println!(
+        "\n  {}  {} of {} sessions  {}",
+        "Showing:".bold(),
+        shown,
+        total_sessions,
+        format!(
+            "(all sessions: {})",
+            crate::format_currency(report.total_cost)
+        )
+        .bright_black()
+    );
+    if options.include_zero_cost && visible.iter().any(|entry| entry.cost == 0.0) {
+        println!(
+            "  {}",
+            "$0.00 sessions are subscription or unpriced usage".bright_black()
+        );
+    }
+
+    Ok(())
+}
+
+fn print_reconciliation(
+    report: &TopSessionsReport,
+    threshold_pct: f64,
+    top: Option<usize>,
+    json: bool,
+) -> Result<()> {
+    use colored::Colorize;
+    use comfy_table::{Cell, CellAlignment, Color, ContentArrangement, Table};
+
+    let reconciled_sessions = report
+        .entries
+        .iter()
+        .filter(|entry| entry.reconciliation.is_some())
+        .count();
+    let mut flagged = flagged_sessions(&report.entries, threshold_pct);
+    if let Some(top) = top {
+        flagged.truncate(top);
+    }
+
+    if json {
+        let output = ReconcileJson {
+            threshold_pct,
+            entries: flagged
+                .iter()
+                .map(|session| {
+                    let reconciliation = session.reconciliation.as_ref();
+                    FlaggedSessionJson {
+                        session,
+                        token_diff_pct: reconciliation.and_then(|r| r.token_difference_pct()),
+                        cost_diff_pct: reconciliation.and_then(|r| r.cost_difference_pct()),
+                    }
+                })
+                .collect(),
+            reconciled_sessions,
+            processing_time_ms: report.processing_time_ms,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let summary = format!(
+        "{} of {} sessions with client-reported totals differ by more than {}%",
+        flagged.len(),
+        reconciled_sessions,
+        threshold_pct
+    );
+    if flagged.is_empty() {
+        println!("\n  {}\n", summary.green());
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(crate::table_preset());
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.enforce_styling();
+    table.set_header(vec![
+        Cell::new("Source").fg(Color::Cyan),
+        Cell::new("Session").fg(Color::Cyan),
+        Cell::new("Reported Tokens").fg(Color::Cyan),
+        Cell::new("Counted").fg(Color::Cyan),
+        Cell::new("Tokens Diff").fg(Color::Cyan),
+        Cell::new("Reported Cost").fg(Color::Cyan),
+        Cell::new("Estimated").fg(Color::Cyan),
+        Cell::new("Cost Diff").fg(Color::Cyan),
+    ]);
+    let right = |value: String| Cell::new(value).set_alignment(CellAlignment::Right);
+    for entry in &flagged {
+        let Some(reconciliation) = entry.reconciliation.as_ref() else {
+            continue;
+        };
+        let (reported_tokens, counted_tokens) = match reconciliation.reported_tokens {
+            Some(reported) => (
+                crate::format_tokens_with_commas(reported),
+                crate::format_tokens_with_commas(reconciliation.counted_tokens),
+            ),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        let (reported_cost, estimated_cost) = match reconciliation.reported_cost {
+            Some(reported) => (
+                crate::format_currency(reported),
+                crate::format_currency(reconciliation.estimated_cost),
+            ),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        table.add_row(vec![
+            Cell::new(crate::capitalize_client(&entry.client)),
+            Cell::new(session_label(entry)).fg(Color::White),
+            right(reported_tokens),
+            right(counted_tokens),
+            right(format_pct(reconciliation.token_difference_pct())).fg(Color::Yellow),
+            right(reported_cost),
+            right(estimated_cost),
+            right(format_pct(reconciliation.cost_difference_pct())).fg(Color::Yellow),
+        ]);
+    }
+
+    println!("\n  {}\n", "Session Reconciliation".bold());
+    println!("{}", crate::dim_borders(&table.to_string()));
+    println!("\n  {}", summary.bright_black());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(title: Option<&str>, first_date: &str, last_date: &str) -> SessionUsage {
+        SessionUsage {
+            client: "claude".to_string(),
+            session_id: "0b6c3c1e-session".to_string(),
+            title: title.map(str::to_string),
+            workspace_label: None,
+            dominant_model: "claude-opus-4-5".to_string(),
+            input: 0,
+            output: 0,
+            cache_read: 0,
+            cache_write: 0,
+            reasoning: 0,
+            total_tokens: 0,
+            message_count: 1,
+            first_date: first_date.to_string(),
+            last_date: last_date.to_string(),
+            cost: 0.0,
+            reconciliation: None,
+        }
+    }
+
+    #[test]
+    fn include_zero_cost_keeps_free_sessions_past_top() {
+        let mut priced = session(None, "2025-01-01", "2025-01-01");
+        priced.cost = 2.0;
+        let mut cheaper = priced.clone();
+        cheaper.cost = 1.0;
+        let free = session(None, "2025-01-02", "2025-01-02");
+        let entries = vec![priced, cheaper, free];
+
+        assert_eq!(visible_sessions(&entries, 1, false).len(), 1);
+        let visible = visible_sessions(&entries, 1, true);
+        let costs: Vec<f64> = visible.iter().map(|entry| entry.cost).collect();
+        assert_eq!(costs, vec![2.0, 0.0]);
+    }
+
+    #[test]
+    fn reconcile_lists_only_sessions_past_the_threshold() {
+        let reconciled = |reported_tokens: i64, counted_tokens: i64| {
+            let mut entry = session(None, "2025-01-01", "2025-01-01");
+            entry.reconciliation = Some(tokscale_core::SessionReconciliation {
+                reported_tokens: Some(reported_tokens),
+                counted_tokens,
+                ..Default::default()
+            });
+            entry
+        };
+        let entries = vec![
+            reconciled(1_000, 1_030),
+            reconciled(1_000, 800),
+            session(None, "2025-01-01", "2025-01-01"),
+        ];
+
+        let flagged = flagged_sessions(&entries, DEFAULT_RECONCILE_THRESHOLD_PCT);
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(
+            flagged[0].reconciliation.as_ref().unwrap().counted_tokens,
+            800
+        );
+        assert_eq!(flagged_sessions(&entries, 2.0).len(), 2);
+        assert_eq!(format_pct(Some(-20.0)), "-20.0%");
+        assert_eq!(format_pct(None), "-");
+    }
+
+    #[test]
+    fn label_prefers_title_and_elides_long_ones() {
+        assert_eq!(
+            session_label(&session(Some("Fix login"), "2025-01-01", "2025-01-01")),
+            "Fix login"
+        );
+        assert_eq!(
+            session_label(&session(Some("  "), "2025-01-01", "2025-01-01")),
+            "0b6c3c1e-session"
+        );
+        let long = "x".repeat(60);
+        let label = session_label(&session(Some(&long), "2025-01-01", "2025-01-01"));
+        assert_eq!(label.chars().count(), MAX_LABEL_CHARS);
+        assert!(label.ends_with('…'));
+    }
+
+    #[test]
+    fn date_span_collapses_single_day_sessions() {
+        assert_eq!(
+            date_span(&session(None, "2025-01-01", "2025-01-01")),
+            "2025-01-01"
+        );
+        assert_eq!(
+            date_span(&session(None, "2025-01-01", "2025-01-03")),
+            "2025-01-01 → 2025-01-03"
+        );
+    }
+}
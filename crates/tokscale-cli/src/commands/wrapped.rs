@@ -11,7 +11,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tokio::runtime::Runtime;
 use tokscale_core::{
-    generate_graph, parse_local_clients, ClientId, GroupBy, LocalParseOptions, ReportOptions,
+    generate_graph, parse_local_clients, ClientId, LocalParseOptions, ReportOptions,
 };
 
 const SCALE: i32 = 2;
@@ -48,42 +48,60 @@ pub struct WrappedOptions {
     pub short: bool,
     pub include_agents: bool,
     pub pin_sisyphus: bool,
+    /// Fewest tokens a day needs to count toward active days and streaks.
+    pub active_threshold: i64,
+    /// `--home`: scan this directory instead of the current user's home.
+    pub home_dir: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+/// The stats behind the wrapped image; `wrapped --json` prints them as-is.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 struct WrappedData {
     year: String,
     active_days: i32,
     total_tokens: i64,
     total_cost: f64,
+    current_streak: i32,
     longest_streak: i32,
+    busiest_day: Option<WrappedBusiestDay>,
     top_models: Vec<WrappedRankedEntry>,
     top_clients: Vec<WrappedRankedEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     top_agents: Option<Vec<WrappedAgentEntry>>,
     contributions: Vec<WrappedContribution>,
     total_messages: i32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 struct WrappedRankedEntry {
     name: String,
     cost: f64,
     tokens: i64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 struct WrappedAgentEntry {
     name: String,
     tokens: i64,
     messages: i32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 struct WrappedContribution {
     date: String,
     level: u8,
 }
 
+/// The highest-cost day of the year.
+#[derive(Debug, Clone, serde::Serialize)]
+struct WrappedBusiestDay {
+    date: String,
+    tokens: i64,
+    cost: f64,
+    messages: i32,
+}
+
 #[derive(Debug, Clone)]
 struct FontSet {
     regular: FontArc,
@@ -102,6 +120,13 @@ pub fn run(options: WrappedOptions) -> Result<String> {
     rt.block_on(async move { generate_wrapped(options).await })
 }
 
+/// The wrapped stats as pretty-printed JSON, without rendering the image.
+pub fn run_json(options: WrappedOptions) -> Result<String> {
+    let rt = Runtime::new()?;
+    let data = rt.block_on(async move { load_wrapped_data(&options).await })?;
+    Ok(serde_json::to_string_pretty(&data)?)
+}
+
 async fn generate_wrapped(options: WrappedOptions) -> Result<String> {
     let data = load_wrapped_data(&options).await?;
 
@@ -177,11 +202,12 @@ async fn load_wrapped_data(options: &WrappedOptions) -> Result<WrappedData> {
     let since = format!("{}-01-01", year);
     let until = format!("{}-12-31", year);
 
-    let has_cursor_cache = cursor::has_cursor_usage_cache();
+    let has_cursor_cache = crate::has_cursor_usage_cache_for_report(&options.home_dir);
     let cursor_logged_in = cursor::is_cursor_logged_in();
     let mut cursor_sync_result: Option<cursor::SyncCursorResult> = None;
 
-    if include_cursor && cursor_logged_in {
+    // Syncing writes the current user's cache, not the one under `--home`.
+    if include_cursor && cursor_logged_in && options.home_dir.is_none() {
         cursor_sync_result = Some(cursor::sync_cursor_cache().await);
     }
 
@@ -193,7 +219,7 @@ async fn load_wrapped_data(options: &WrappedOptions) -> Result<WrappedData> {
                 } else {
                     "Cursor sync failed; using cached data"
                 };
-                println!("{}", format!("  {}: {}", prefix, error).yellow());
+                eprintln!("{}", format!("  {}: {}", prefix, error).yellow());
             }
         }
     }
@@ -226,13 +252,17 @@ async fn load_wrapped_data(options: &WrappedOptions) -> Result<WrappedData> {
     let parsed_local = if options.include_agents && !local_clients.is_empty() {
         Some(
             parse_local_clients(LocalParseOptions {
-                home_dir: None,
-                use_env_roots: true,
+                home_dir: options.home_dir.clone(),
+                use_env_roots: crate::use_env_roots(&options.home_dir),
                 clients: Some(local_clients),
                 since: Some(since.clone()),
                 until: Some(until.clone()),
+                until_exclusive: false,
                 year: Some(year.clone()),
-                scanner_settings: crate::tui::settings::load_scanner_settings(),
+                scanner_settings: crate::tui::settings::load_scanner_settings_for_home(
+                    &options.home_dir,
+                ),
+                ..crate::parse_flags::local_parse_options()
             })
             .map_err(anyhow::Error::msg)?,
         )
@@ -241,14 +271,15 @@ async fn load_wrapped_data(options: &WrappedOptions) -> Result<WrappedData> {
     };
 
     let graph = generate_graph(ReportOptions {
-        home_dir: None,
-        use_env_roots: true,
+        home_dir: options.home_dir.clone(),
+        use_env_roots: crate::use_env_roots(&options.home_dir),
         clients: Some(graph_clients),
         since: Some(since),
         until: Some(until),
         year: Some(year.clone()),
-        group_by: GroupBy::default(),
-        scanner_settings: crate::tui::settings::load_scanner_settings(),
+        scanner_settings: crate::tui::settings::load_scanner_settings_for_home(&options.home_dir),
+        active_threshold: options.active_threshold,
+        ..crate::parse_flags::report_options()
     })
     .await
     .map_err(anyhow::Error::msg)?;
@@ -335,25 +366,46 @@ async fn load_wrapped_data(options: &WrappedOptions) -> Result<WrappedData> {
         })
         .collect();
 
-    let mut sorted_dates: Vec<String> = contributions
+    let mut sorted_dates: Vec<String> = graph
+        .contributions
         .iter()
+        .filter(|c| tokscale_core::is_active_day(&c.totals, options.active_threshold))
         .map(|c| c.date.clone())
         .filter(|date| date.starts_with(&year))
         .collect();
     sorted_dates.sort();
 
-    let (_current_streak, longest_streak) = calculate_streaks(&sorted_dates);
+    let (current_streak, longest_streak) = calculate_streaks(&sorted_dates);
     let _first_day = sorted_dates
         .first()
         .cloned()
         .unwrap_or_else(|| format!("{}-01-01", year));
 
+    let busiest_day = graph
+        .contributions
+        .iter()
+        .max_by(|a, b| {
+            a.totals
+                .cost
+                .partial_cmp(&b.totals.cost)
+                .unwrap_or(Ordering::Equal)
+                .then(a.totals.tokens.cmp(&b.totals.tokens))
+        })
+        .map(|day| WrappedBusiestDay {
+            date: day.date.clone(),
+            tokens: day.totals.tokens,
+            cost: day.totals.cost,
+            messages: day.totals.messages,
+        });
+
     Ok(WrappedData {
         year,
         active_days: graph.summary.active_days,
         total_tokens: graph.summary.total_tokens,
         total_cost: graph.summary.total_cost,
+        current_streak,
         longest_streak,
+        busiest_day,
         top_models,
         top_clients,
         top_agents,
@@ -1282,7 +1334,7 @@ fn resolve_wrapped_cache_path(subdir: &str, filename: &str, canonical_path: &Pat
 }
 
 fn first_existing_legacy_wrapped_cache_file(subdir: &str, filename: &str) -> Option<PathBuf> {
-    if crate::paths::is_config_dir_overridden() {
+    if crate::paths::is_cache_dir_overridden() {
         return None;
     }
 
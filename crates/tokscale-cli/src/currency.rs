@@ -0,0 +1,325 @@
+//! `--currency`: show costs in another currency. Pricing data is in USD, so
+//! every displayed cost is multiplied by a USD→currency rate taken from
+//! `TOKSCALE_FX_RATE`, or fetched once per run when the `fx-fetch` feature
+//! is built in.
+//!
+//! Tables and text render the converted amount with the currency's symbol.
+//! JSON keeps raw numbers: converted values replace the cost fields, the USD
+//! originals sit next to them under a `_usd` suffix (`cost_usd`), and the
+//! report gains `currency` and `fx_rate`. USD output is unchanged.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use serde_json::Value;
+
+use crate::locale::NumberLocale;
+
+/// Environment variable holding the USD→currency rate.
+pub const FX_RATE_ENV: &str = "TOKSCALE_FX_RATE";
+
+/// JSON keys holding USD amounts that `--currency` converts. `monthly
+/// --budget` takes its cap in USD like the pricing data, so the budget
+/// figures are converted along with the costs.
+const COST_KEYS: &[&str] = &[
+    "cost",
+    "totalCost",
+    "clientCost",
+    "estimatedCost",
+    "avgCostPerActiveDay",
+    "estimatedSavings",
+    "totalEstimatedSavings",
+    "budget",
+    "spentSoFar",
+    "projected",
+    "currentCost",
+    "previousCost",
+    "change",
+    "totalChange",
+];
+
+static CURRENT: OnceLock<DisplayCurrency> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayCurrency {
+    code: String,
+    rate: f64,
+}
+
+impl Default for DisplayCurrency {
+    fn default() -> Self {
+        Self {
+            code: "USD".to_string(),
+            rate: 1.0,
+        }
+    }
+}
+
+impl DisplayCurrency {
+    /// `code` is an ISO 4217 code in any case; `rate` converts one USD into
+    /// it. USD always uses a rate of 1.
+    pub fn new(code: &str, rate: f64) -> Result<Self, String> {
+        let code = parse_code(code)?;
+        if code == "USD" {
+            return Ok(Self::default());
+        }
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(format!("Invalid exchange rate {rate}: must be positive"));
+        }
+        Ok(Self { code, rate })
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn is_usd(&self) -> bool {
+        self.code == "USD"
+    }
+
+    /// Prefix for rendered amounts. Codes without a well-known symbol are
+    /// written out, e.g. `SEK 12.00`.
+    pub fn symbol(&self) -> String {
+        let symbol = match self.code.as_str() {
+            "USD" => "$",
+            "EUR" => "€",
+            "GBP" => "£",
+            "JPY" => "¥",
+            "CNY" => "CN¥",
+            "KRW" => "₩",
+            "INR" => "₹",
+            "CAD" => "CA$",
+            "AUD" => "A$",
+            "BRL" => "R$",
+            code => return format!("{code} "),
+        };
+        symbol.to_string()
+    }
+
+    pub fn convert(&self, usd: f64) -> f64 {
+        usd * self.rate
+    }
+
+    /// `usd` converted and formatted with the currency symbol.
+    pub fn render(&self, usd: f64, precision: u8, locale: NumberLocale) -> String {
+        format!(
+            "{}{}",
+            self.symbol(),
+            locale.decimal(self.convert(usd), precision)
+        )
+    }
+
+    /// Convert the cost fields of a serialized report in place, keeping the
+    /// USD originals, and record the currency and rate at the top level. A
+    /// no-op for USD.
+    pub fn annotate_json(&self, report: &mut Value) {
+        if self.is_usd() {
+            return;
+        }
+        self.convert_costs(report);
+        if let Value::Object(object) = report {
+            object.insert("currency".to_string(), Value::from(self.code()));
+            object.insert("fx_rate".to_string(), Value::from(self.rate()));
+        }
+    }
+
+    fn convert_costs(&self, value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                let mut originals = Vec::new();
+                for (key, nested) in object.iter_mut() {
+                    match nested.as_f64() {
+                        Some(usd) if COST_KEYS.contains(&key.as_str()) => {
+                            originals.push((format!("{key}_usd"), usd));
+                            *nested = Value::from(self.convert(usd));
+                        }
+                        _ => self.convert_costs(nested),
+                    }
+                }
+                for (key, usd) in originals {
+                    object.insert(key, Value::from(usd));
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.convert_costs(item)),
+            _ => {}
+        }
+    }
+}
+
+fn parse_code(code: &str) -> Result<String, String> {
+    let code = code.trim().to_ascii_uppercase();
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(code)
+    } else {
+        Err(format!(
+            "Invalid currency '{code}': expected a three-letter ISO 4217 code such as EUR"
+        ))
+    }
+}
+
+/// Resolve `--currency CODE` to a rate: `TOKSCALE_FX_RATE` when set, else a
+/// live lookup if this build has the `fx-fetch` feature.
+pub fn resolve(code: &str, env_rate: Option<String>) -> Result<DisplayCurrency, String> {
+    let normalized = parse_code(code)?;
+    if normalized == "USD" {
+        return Ok(DisplayCurrency::default());
+    }
+    let rate = match env_rate.filter(|value| !value.trim().is_empty()) {
+        Some(value) => value
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid {FX_RATE_ENV} '{value}': expected a number"))?,
+        None => fetch_rate(&normalized)?,
+    };
+    DisplayCurrency::new(&normalized, rate)
+}
+
+#[cfg(feature = "fx-fetch")]
+fn fetch_rate(code: &str) -> Result<f64, String> {
+    const RATES_URL: &str = "https://api.frankfurter.app/latest?from=USD";
+
+    let url = format!("{RATES_URL}&to={code}");
+    let rt = tokio::runtime::Runtime::new().map_err(|err| err.to_string())?;
+    let body: Value = rt
+        .block_on(async {
+            reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()?
+                .get(&url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await
+        })
+        .map_err(|err| format!("Could not fetch the USD→{code} rate: {err}"))?;
+    body["rates"][code]
+        .as_f64()
+        .ok_or_else(|| format!("No USD→{code} rate available; set {FX_RATE_ENV}"))
+}
+
+#[cfg(not(feature = "fx-fetch"))]
+fn fetch_rate(code: &str) -> Result<f64, String> {
+    Err(format!(
+        "--currency {code} needs an exchange rate: set {FX_RATE_ENV} to the number of {code} per USD"
+    ))
+}
+
+/// Select the display currency for this process. The first call wins.
+pub fn set_current(currency: DisplayCurrency) {
+    let _ = CURRENT.set(currency);
+}
+
+pub fn current() -> &'static DisplayCurrency {
+    CURRENT.get_or_init(DisplayCurrency::default)
+}
+
+pub const DEFAULT_PRECISION: u8 = 2;
+pub const MAX_PRECISION: u8 = 6;
+
+/// Decimal places for displayed costs, set once from `--currency-precision`
+/// at startup.
+static PRECISION: AtomicU8 = AtomicU8::new(DEFAULT_PRECISION);
+
+/// Values above [`MAX_PRECISION`] are clamped.
+pub fn set_precision(precision: u8) {
+    PRECISION.store(precision.min(MAX_PRECISION), Ordering::Relaxed);
+}
+
+pub fn precision() -> u8 {
+    PRECISION.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn set_precision_clamps_to_the_maximum() {
+        set_precision(MAX_PRECISION + 3);
+        assert_eq!(precision(), MAX_PRECISION);
+        set_precision(0);
+        assert_eq!(precision(), 0);
+        set_precision(DEFAULT_PRECISION);
+    }
+
+    #[test]
+    fn converts_and_renders_with_the_currency_symbol() {
+        let eur = DisplayCurrency::new("eur", 0.9).unwrap();
+        assert!((eur.convert(10.0) - 9.0).abs() < 1e-9);
+        assert_eq!(eur.render(10.0, 2, NumberLocale::En), "€9.00");
+        assert_eq!(eur.render(1500.0, 2, NumberLocale::De), "€1.350,00");
+
+        let gbp = DisplayCurrency::new("GBP", 0.8).unwrap();
+        assert_eq!(gbp.render(2.5, 2, NumberLocale::En), "£2.00");
+
+        let jpy = DisplayCurrency::new("JPY", 150.0).unwrap();
+        assert_eq!(jpy.render(1.0, 0, NumberLocale::En), "¥150");
+
+        let sek = DisplayCurrency::new("SEK", 10.0).unwrap();
+        assert_eq!(sek.render(1.2, 2, NumberLocale::En), "SEK 12.00");
+    }
+
+    #[test]
+    fn usd_is_the_identity() {
+        let usd = DisplayCurrency::new("usd", 0.5).unwrap();
+        assert_eq!(usd, DisplayCurrency::default());
+        assert_eq!(usd.render(12.345, 2, NumberLocale::En), "$12.35");
+    }
+
+    #[test]
+    fn rejects_bad_codes_and_rates() {
+        assert!(DisplayCurrency::new("EURO", 0.9).is_err());
+        assert!(DisplayCurrency::new("E1R", 0.9).is_err());
+        assert!(DisplayCurrency::new("EUR", 0.0).is_err());
+        assert!(DisplayCurrency::new("EUR", f64::NAN).is_err());
+        assert!(resolve("EUR", Some("abc".to_string())).is_err());
+    }
+
+    #[test]
+    fn resolve_reads_the_rate_from_the_environment() {
+        let eur = resolve("eur", Some(" 0.92 ".to_string())).unwrap();
+        assert_eq!(eur.code(), "EUR");
+        assert!((eur.rate() - 0.92).abs() < 1e-12);
+        assert!(resolve("usd", None).unwrap().is_usd());
+    }
+
+    #[test]
+    fn annotate_json_keeps_usd_alongside_converted_costs() {
+        let eur = DisplayCurrency::new("EUR", 0.5).unwrap();
+        let mut report = json!({
+            "entries": [{ "model": "gpt-5", "cost": 4.0, "input": 10 }],
+            "totalCost": 4.0,
+        });
+        eur.annotate_json(&mut report);
+        assert_eq!(
+            report,
+            json!({
+                "entries": [{ "model": "gpt-5", "cost": 2.0, "cost_usd": 4.0, "input": 10 }],
+                "totalCost": 2.0,
+                "totalCost_usd": 4.0,
+                "currency": "EUR",
+                "fx_rate": 0.5,
+            })
+        );
+
+        let mut ordered =
+            json!({ "totalCost": 4.0, "entries": [{ "model": "gpt-5", "cost": 4.0 }] });
+        eur.annotate_json(&mut ordered);
+        assert_eq!(
+            ordered.to_string(),
+            r#"{"totalCost":2.0,"entries":[{"model":"gpt-5","cost":2.0,"cost_usd":4.0}],"totalCost_usd":4.0,"currency":"EUR","fx_rate":0.5}"#
+        );
+
+        let mut untouched = json!({ "totalCost": 4.0 });
+        DisplayCurrency::default().annotate_json(&mut untouched);
+        assert_eq!(untouched, json!({ "totalCost": 4.0 }));
+    }
+}
@@ -39,8 +39,13 @@ fn old_cursor_credentials_path(home_dir: &Path) -> PathBuf {
     home_dir.join(".tokscale/cursor-credentials.json")
 }
 
+/// Resolved the same way the scanner resolves the Cursor client's path: the
+/// real home honors `TOKSCALE_CONFIG_DIR` (and `XDG_CONFIG_HOME` on Linux),
+/// while any other home (`--home`, tests) is taken at face value.
 fn cursor_cache_dir(home_dir: &Path) -> PathBuf {
-    home_dir.join(".config/tokscale/cursor-cache")
+    let use_env_roots = dirs::home_dir().as_deref() == Some(home_dir);
+    tokscale_core::paths::config_dir_for_home(&home_dir.to_string_lossy(), use_env_roots)
+        .join("cursor-cache")
 }
 
 fn old_cursor_cache_dir(home_dir: &Path) -> PathBuf {
@@ -664,14 +669,6 @@ pub fn has_cursor_usage_cache_in_home(home_dir: &Path) -> bool {
     }
 }
 
-pub fn has_cursor_usage_cache() -> bool {
-    let home_dir = match home_dir() {
-        Ok(home_dir) => home_dir,
-        Err(_) => return false,
-    };
-    has_cursor_usage_cache_in_home(&home_dir)
-}
-
 fn expected_cursor_usage_cache_paths_in(home_dir: &Path) -> Vec<PathBuf> {
     let cache_dir = cursor_cache_dir(home_dir);
 
@@ -1313,6 +1310,41 @@ mod tests {
     use std::collections::HashMap;
     use tempfile::TempDir;
 
+    #[test]
+    #[serial_test::serial]
+    fn cursor_cache_dir_follows_config_dir_override_for_the_real_home() {
+        let temp = TempDir::new().unwrap();
+        let prev: Vec<_> = ["HOME", "TOKSCALE_CONFIG_DIR"]
+            .into_iter()
+            .map(|key| (key, std::env::var_os(key)))
+            .collect();
+        let relocated = temp.path().join("relocated");
+        unsafe {
+            std::env::set_var("HOME", temp.path());
+            std::env::set_var("TOKSCALE_CONFIG_DIR", &relocated);
+        }
+
+        assert_eq!(
+            get_cursor_cache_dir().unwrap(),
+            relocated.join("cursor-cache")
+        );
+        // Any other home, like `--home`, is read at face value.
+        let other = temp.path().join("other-home");
+        assert_eq!(
+            cursor_cache_dir(&other),
+            other.join(".config/tokscale/cursor-cache")
+        );
+
+        unsafe {
+            for (key, value) in prev {
+                match value {
+                    Some(value) => std::env::set_var(key, value),
+                    None => std::env::remove_var(key),
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_extract_user_id_from_session_token_with_url_encoding() {
         // Test URL-encoded separator (%3A%3A)
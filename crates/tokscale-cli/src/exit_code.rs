@@ -0,0 +1,74 @@
+//! Process exit codes scripts can rely on.
+//!
+//! `models`, `graph`, and `submit` use these so a wrapper can tell "nothing
+//! matched" apart from a failure without parsing output. JSON output is still
+//! printed (empty-shaped) before exiting with [`EMPTY`]. A run that found
+//! usage exits 0.
+//!
+//! Commands return a [`Failure`] through `anyhow` instead of exiting, and
+//! `main` turns it into the process exit code with [`code_for`].
+
+/// Invalid arguments, or any failure not covered by a more specific code.
+pub const USAGE: i32 = 1;
+/// Pricing data could not be fetched or the server was unreachable.
+pub const NETWORK: i32 = 2;
+/// The command ran but no usage matched the filters.
+pub const EMPTY: i32 = 3;
+/// A headless capture subprocess was killed after its timeout.
+pub const TIMEOUT: i32 = 124;
+
+/// An outcome that ends the process with one of the codes above.
+#[derive(Debug)]
+pub enum Failure {
+    /// The command ran but no usage matched; its output was already printed.
+    Empty,
+    /// Pricing data or the server could not be reached.
+    Network(String),
+    /// A headless capture subprocess was killed; the details were already
+    /// printed.
+    Timeout,
+}
+
+impl Failure {
+    pub fn code(&self) -> i32 {
+        match self {
+            Failure::Empty => EMPTY,
+            Failure::Network(_) => NETWORK,
+            Failure::Timeout => TIMEOUT,
+        }
+    }
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Empty => f.write_str("No usage matched the filters"),
+            Failure::Network(message) => f.write_str(message),
+            Failure::Timeout => f.write_str("Subprocess timed out"),
+        }
+    }
+}
+
+impl std::error::Error for Failure {}
+
+/// The exit code for an error returned from a command: the [`Failure`]'s
+/// own code, else [`USAGE`].
+pub fn code_for(err: &anyhow::Error) -> i32 {
+    err.downcast_ref::<Failure>().map_or(USAGE, Failure::code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_map_to_their_codes_and_other_errors_to_usage() {
+        assert_eq!(code_for(&Failure::Empty.into()), EMPTY);
+        assert_eq!(
+            code_for(&Failure::Network("offline".into()).into()),
+            NETWORK
+        );
+        assert_eq!(code_for(&Failure::Timeout.into()), TIMEOUT);
+        assert_eq!(code_for(&anyhow::anyhow!("bad input")), USAGE);
+    }
+}
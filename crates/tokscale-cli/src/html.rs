@@ -0,0 +1,347 @@
+//! `--html` output: the models or monthly report as one self-contained HTML
+//! page (inline CSS, no scripts or external assets) for sharing with people
+//! who won't run the CLI.
+//!
+//! Renders straight from the core report structs rather than the `--light`
+//! table, so the page carries every row regardless of terminal width. All
+//! text that came from session data is escaped.
+
+use crate::{locale, BudgetProjection};
+use anyhow::Result;
+use colored::Colorize;
+use tokscale_core::{ModelReport, MonthlyReport};
+
+const STYLE: &str = "\
+body{font-family:-apple-system,BlinkMacSystemFont,\"Segoe UI\",Helvetica,Arial,sans-serif;margin:2rem;color:#1f2328;background:#fff}\
+h1{font-size:1.5rem;margin:0 0 .25rem}\
+.meta{color:#59636e;margin:0 0 1.25rem}\
+.summary{display:flex;flex-wrap:wrap;gap:1rem;margin:0 0 1.5rem;padding:0}\
+.summary div{border:1px solid #d1d9e0;border-radius:6px;padding:.75rem 1rem;min-width:9rem}\
+.summary dt{color:#59636e;font-size:.8rem}\
+.summary dd{margin:0;font-size:1.25rem;font-weight:600}\
+table{border-collapse:collapse;width:100%;font-size:.9rem}\
+th,td{padding:.4rem .6rem;border-bottom:1px solid #d1d9e0;text-align:left}\
+th{background:#f6f8fa}\
+td.num,th.num{text-align:right;font-variant-numeric:tabular-nums}\
+tbody tr:nth-child(even){background:#fafbfc}";
+
+/// Where an `--html` page goes: the `--output` path, or stdout.
+pub struct HtmlOutput {
+    pub path: Option<String>,
+}
+
+impl HtmlOutput {
+    pub fn emit(&self, document: &str) -> Result<()> {
+        match &self.path {
+            Some(path) => {
+                std::fs::write(path, document)?;
+                eprintln!("{}", format!("✓ HTML report written to {}", path).green());
+            }
+            None => print!("{document}"),
+        }
+        Ok(())
+    }
+}
+
+/// The models report as an HTML page. `date_range` is the `--since`/`--week`
+/// style label, if any.
+pub fn models_document(report: &ModelReport, group_by: &str, date_range: Option<&str>) -> String {
+    let show_project = report.entries.iter().any(|e| e.workspace_label.is_some());
+    let show_session = report.entries.iter().any(|e| e.session_id.is_some());
+
+    let mut header = vec![
+        Column::text("Client"),
+        Column::text("Provider"),
+        Column::text("Model"),
+    ];
+    if show_project {
+        header.push(Column::text("Project"));
+    }
+    if show_session {
+        header.push(Column::text("Session"));
+    }
+    header.extend(
+        [
+            "Input",
+            "Output",
+            "Cache Read",
+            "Cache Write",
+            "Messages",
+            "Cost",
+        ]
+        .map(Column::num),
+    );
+
+    let rows = report.entries.iter().map(|e| {
+        let mut cells = vec![e.client.clone(), e.provider.clone(), e.model.clone()];
+        if show_project {
+            cells.push(e.workspace_label.clone().unwrap_or_default());
+        }
+        if show_session {
+            cells.push(e.session_id.clone().unwrap_or_default());
+        }
+        cells.extend([
+            tokens(e.input),
+            tokens(e.output),
+            tokens(e.cache_read),
+            tokens(e.cache_write),
+            tokens(i64::from(e.message_count)),
+            cost(e.cost),
+        ]);
+        cells
+    });
+
+    let total_tokens = [
+        report.total_input,
+        report.total_output,
+        report.total_cache_read,
+        report.total_cache_write,
+    ]
+    .into_iter()
+    .fold(0i64, i64::saturating_add);
+    document(
+        "Model usage",
+        &format!("Grouped by {group_by}"),
+        date_range,
+        &[
+            ("Total cost", cost(report.total_cost)),
+            ("Total tokens", tokens(total_tokens)),
+            ("Messages", tokens(i64::from(report.total_messages))),
+            ("Sessions", tokens(i64::from(report.total_sessions))),
+        ],
+        &header,
+        rows,
+    )
+}
+
+/// The monthly report as an HTML page, with the `--budget` projection in the
+/// summary when one was asked for.
+pub fn monthly_document(
+    report: &MonthlyReport,
+    date_range: Option<&str>,
+    budget: Option<&BudgetProjection>,
+) -> String {
+    let header = [Column::text("Month"), Column::text("Models")]
+        .into_iter()
+        .chain(
+            [
+                "Input",
+                "Output",
+                "Cache Read",
+                "Cache Write",
+                "Messages",
+                "Cost",
+                "Days",
+                "Cost/Day",
+            ]
+            .map(Column::num),
+        )
+        .collect::<Vec<_>>();
+    let rows = report.entries.iter().map(|e| {
+        let mut models = e.models.clone();
+        models.sort();
+        vec![
+            e.month.clone(),
+            models.join(", "),
+            tokens(e.input),
+            tokens(e.output),
+            tokens(e.cache_read),
+            tokens(e.cache_write),
+            tokens(i64::from(e.message_count)),
+            cost(e.cost),
+            tokens(i64::from(e.active_days)),
+            cost(e.avg_cost_per_active_day),
+        ]
+    });
+    let total_tokens = report
+        .entries
+        .iter()
+        .flat_map(|e| [e.input, e.output, e.cache_read, e.cache_write])
+        .fold(0i64, i64::saturating_add);
+    let mut summary = vec![
+        ("Total cost", cost(report.total_cost)),
+        ("Total tokens", tokens(total_tokens)),
+        ("Months", tokens(report.entries.len() as i64)),
+    ];
+    if let Some(budget) = budget {
+        summary.extend([
+            ("Monthly budget", cost(budget.budget)),
+            ("Spent this month", cost(budget.spent_so_far)),
+            ("Projected", cost(budget.projected)),
+            (
+                "Budget status",
+                if budget.over_budget {
+                    "Over budget"
+                } else {
+                    "Within budget"
+                }
+                .to_string(),
+            ),
+        ]);
+    }
+    document(
+        "Monthly usage",
+        "By calendar month",
+        date_range,
+        &summary,
+        &header,
+        rows,
+    )
+}
+
+struct Column {
+    label: &'static str,
+    numeric: bool,
+}
+
+impl Column {
+    fn text(label: &'static str) -> Self {
+        Self {
+            label,
+            numeric: false,
+        }
+    }
+
+    fn num(label: &'static str) -> Self {
+        Self {
+            label,
+            numeric: true,
+        }
+    }
+
+    fn open_tag(&self, tag: &str) -> String {
+        if self.numeric {
+            format!("<{tag} class=\"num\">")
+        } else {
+            format!("<{tag}>")
+        }
+    }
+}
+
+fn document(
+    title: &str,
+    subtitle: &str,
+    date_range: Option<&str>,
+    summary: &[(&str, String)],
+    header: &[Column],
+    rows: impl Iterator<Item = Vec<String>>,
+) -> String {
+    let generated = chrono::Local::now().format("%Y-%m-%d %H:%M");
+    let range = date_range.unwrap_or("All time");
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str(&format!("<title>Tokscale — {}</title>\n", escape(title)));
+    out.push_str(&format!("<style>{STYLE}</style>\n</head>\n<body>\n"));
+    out.push_str(&format!("<h1>Tokscale — {}</h1>\n", escape(title)));
+    out.push_str(&format!(
+        "<p class=\"meta\">{} · {} · generated {}</p>\n",
+        escape(range),
+        escape(subtitle),
+        generated
+    ));
+
+    out.push_str("<dl class=\"summary\">\n");
+    for (label, value) in summary {
+        out.push_str(&format!(
+            "<div><dt>{}</dt><dd>{}</dd></div>\n",
+            escape(label),
+            escape(value)
+        ));
+    }
+    out.push_str("</dl>\n<table>\n<thead>\n<tr>");
+    for column in header {
+        out.push_str(&column.open_tag("th"));
+        out.push_str(&escape(column.label));
+        out.push_str("</th>");
+    }
+    out.push_str("</tr>\n</thead>\n<tbody>\n");
+    for row in rows {
+        out.push_str("<tr>");
+        for (column, cell) in header.iter().zip(&row) {
+            out.push_str(&column.open_tag("td"));
+            out.push_str(&escape(cell));
+            out.push_str("</td>");
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    out
+}
+
+fn tokens(n: i64) -> String {
+    locale::current().group(n)
+}
+
+fn cost(n: f64) -> String {
+    format!("${}", locale::current().decimal(n, 2))
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_markup_characters() {
+        assert_eq!(
+            escape(r#"<script>"a" & 'b'</script>"#),
+            "&lt;script&gt;&quot;a&quot; &amp; &#39;b&#39;&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn monthly_document_lists_each_month() {
+        let report = MonthlyReport {
+            entries: vec![tokscale_core::MonthlyUsage {
+                month: "2026-03".to_string(),
+                models: vec!["gpt-5".to_string(), "claude<beta>".to_string()],
+                input: 1200,
+                output: 300,
+                cache_read: 0,
+                cache_write: 0,
+                reasoning: 0,
+                message_count: 4,
+                cost: 1.5,
+                active_days: 2,
+                avg_cost_per_active_day: 0.75,
+            }],
+            total_cost: 1.5,
+            processing_time_ms: 0,
+            unpriced_models: Vec::new(),
+        };
+        let html = monthly_document(&report, Some("Last 30 days"), None);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<td>2026-03</td>"));
+        assert!(html.contains("<td>claude&lt;beta&gt;, gpt-5</td>"));
+        assert!(html.contains("Last 30 days"));
+        assert!(!html.contains("<link") && !html.contains("<script"));
+        assert!(!html.contains("Monthly budget"));
+
+        let budget = BudgetProjection {
+            budget: 10.0,
+            spent_so_far: 4.0,
+            projected: 12.0,
+            over_budget: true,
+        };
+        let html = monthly_document(&report, None, Some(&budget));
+        assert!(html.contains("<dt>Monthly budget</dt><dd>$10.00</dd>"));
+        assert!(html.contains("<dt>Projected</dt><dd>$12.00</dd>"));
+        assert!(html.contains("<dd>Over budget</dd>"));
+    }
+}
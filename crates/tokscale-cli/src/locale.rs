@@ -0,0 +1,199 @@
+//! `--locale` number formatting: thousands separator and decimal mark for
+//! token counts and costs in table and text output. JSON stays raw numeric.
+//!
+//! Without the flag the locale comes from `LC_ALL`, then `LC_NUMERIC`; any
+//! language other than the supported ones falls back to `en`.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NumberLocale {
+    /// `1,234,567` and `$1234.56`.
+    #[default]
+    En,
+    /// `1.234.567` and `$1.234,56`.
+    De,
+    /// `1 234 567` (narrow no-break space) and `$1 234,56`.
+    Fr,
+}
+
+pub const SUPPORTED: &[&str] = &["en", "de", "fr"];
+
+static CURRENT: AtomicU8 = AtomicU8::new(NumberLocale::En as u8);
+
+impl NumberLocale {
+    /// Parse a BCP 47 or POSIX tag (`de`, `de-AT`, `fr_CH.UTF-8`) by its
+    /// language subtag.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag
+            .split(['-', '_', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Self::En),
+            "de" => Some(Self::De),
+            "fr" => Some(Self::Fr),
+            _ => None,
+        }
+    }
+
+    /// The locale named by `LC_ALL` / `LC_NUMERIC`, if it is a supported one.
+    pub fn from_env(env: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        ["LC_ALL", "LC_NUMERIC"]
+            .into_iter()
+            .filter_map(env)
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_tag(&value))
+    }
+
+    fn thousands_separator(self) -> char {
+        match self {
+            Self::En => ',',
+            Self::De => '.',
+            Self::Fr => '\u{202f}',
+        }
+    }
+
+    fn decimal_mark(self) -> char {
+        match self {
+            Self::En => '.',
+            Self::De | Self::Fr => ',',
+        }
+    }
+
+    /// Integer with the locale's thousands grouping.
+    pub fn group(self, n: i64) -> String {
+        let digits = n.unsigned_abs().to_string();
+        let mut result = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+        if n < 0 {
+            result.push('-');
+        }
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                result.push(self.thousands_separator());
+            }
+            result.push(c);
+        }
+        result
+    }
+
+    /// Fixed-point amount with the locale's decimal mark. `en` keeps the
+    /// ungrouped integer part the cost columns have always used; the other
+    /// locales group it.
+    pub fn decimal(self, value: f64, precision: u8) -> String {
+        let formatted = format!("{:.*}", usize::from(precision), value);
+        if self == Self::En {
+            return formatted;
+        }
+        let (sign, unsigned) = match formatted.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", formatted.as_str()),
+        };
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (unsigned, None),
+        };
+        let mut result = String::from(sign);
+        match integer.parse::<i64>() {
+            Ok(n) => result.push_str(&self.group(n)),
+            Err(_) => result.push_str(integer),
+        }
+        if let Some(fraction) = fraction {
+            result.push(self.decimal_mark());
+            result.push_str(fraction);
+        }
+        result
+    }
+}
+
+pub fn set_current(locale: NumberLocale) {
+    CURRENT.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn current() -> NumberLocale {
+    match CURRENT.load(Ordering::Relaxed) {
+        x if x == NumberLocale::De as u8 => NumberLocale::De,
+        x if x == NumberLocale::Fr as u8 => NumberLocale::Fr,
+        _ => NumberLocale::En,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_thousands_per_locale() {
+        let cases = [
+            (NumberLocale::En, ["0", "999", "1,000", "1,234,567"]),
+            (NumberLocale::De, ["0", "999", "1.000", "1.234.567"]),
+            (
+                NumberLocale::Fr,
+                ["0", "999", "1\u{202f}000", "1\u{202f}234\u{202f}567"],
+            ),
+        ];
+        for (locale, expected) in cases {
+            for (n, want) in [0, 999, 1_000, 1_234_567].into_iter().zip(expected) {
+                assert_eq!(locale.group(n), want, "{locale:?} {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn groups_negative_values_without_a_leading_separator() {
+        assert_eq!(NumberLocale::En.group(-123), "-123");
+        assert_eq!(NumberLocale::En.group(-1_234_567), "-1,234,567");
+        assert_eq!(NumberLocale::De.group(-1_234), "-1.234");
+        assert_eq!(NumberLocale::Fr.group(-123_456), "-123\u{202f}456");
+        assert_eq!(
+            NumberLocale::En.group(i64::MIN),
+            "-9,223,372,036,854,775,808"
+        );
+    }
+
+    #[test]
+    fn formats_decimals_per_locale() {
+        assert_eq!(NumberLocale::En.decimal(1234.56, 2), "1234.56");
+        assert_eq!(NumberLocale::De.decimal(1234.56, 2), "1.234,56");
+        assert_eq!(NumberLocale::Fr.decimal(1234.56, 2), "1\u{202f}234,56");
+        assert_eq!(NumberLocale::De.decimal(1234.56, 0), "1.235");
+        assert_eq!(NumberLocale::De.decimal(-1234.5, 1), "-1.234,5");
+        assert_eq!(NumberLocale::Fr.decimal(-0.25, 2), "-0,25");
+        assert_eq!(NumberLocale::En.decimal(-12.5, 2), "-12.50");
+    }
+
+    #[test]
+    fn parses_tags_and_environment() {
+        assert_eq!(NumberLocale::from_tag("de"), Some(NumberLocale::De));
+        assert_eq!(NumberLocale::from_tag("de-AT"), Some(NumberLocale::De));
+        assert_eq!(
+            NumberLocale::from_tag("fr_CH.UTF-8"),
+            Some(NumberLocale::Fr)
+        );
+        assert_eq!(NumberLocale::from_tag("EN_us"), Some(NumberLocale::En));
+        assert_eq!(NumberLocale::from_tag("ja-JP"), None);
+        assert_eq!(NumberLocale::from_tag("C"), None);
+
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(
+            NumberLocale::from_env(env(&[("LC_NUMERIC", "de_DE.UTF-8")])),
+            Some(NumberLocale::De)
+        );
+        assert_eq!(
+            NumberLocale::from_env(env(&[("LC_ALL", "fr_FR"), ("LC_NUMERIC", "de_DE")])),
+            Some(NumberLocale::Fr)
+        );
+        assert_eq!(
+            NumberLocale::from_env(env(&[("LC_ALL", ""), ("LC_NUMERIC", "de_DE")])),
+            Some(NumberLocale::De)
+        );
+        assert_eq!(NumberLocale::from_env(env(&[("LC_ALL", "C.UTF-8")])), None);
+    }
+}
@@ -1,17 +1,26 @@
+mod anonymize;
 mod antigravity;
 mod auth;
 mod claude_diagnostics;
+mod cli_defaults;
 mod commands;
+mod currency;
 mod cursor;
 mod device;
+mod exit_code;
+mod html;
+mod locale;
+mod markdown;
+mod parse_flags;
 mod paths;
+mod table_style;
 mod trae;
 mod tui;
 mod warp;
+mod watch;
 
-use crate::tui::client_ui;
 use anyhow::Result;
-use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -67,9 +76,62 @@ struct Cli {
     )]
     hide_zero: bool,
 
+    #[arg(
+        long = "min-cost",
+        value_name = "USD",
+        value_parser = parse_min_cost_arg,
+        help = "Hide entries costing less than this amount (USD). Report totals still include them. Implies the static report view instead of the interactive TUI."
+    )]
+    min_cost: Option<f64>,
+
+    #[arg(
+        long = "min-tokens",
+        value_name = "N",
+        value_parser = clap::value_parser!(i64).range(0..),
+        help = "Hide entries with fewer than this many tokens, counted like the Total column (reasoning included). Report totals still include them. Implies the static report view instead of the interactive TUI."
+    )]
+    min_tokens: Option<i64>,
+
+    #[arg(
+        long = "show-sessions",
+        help = "Add a Sessions column with the number of distinct sessions per row. Implies the static report view instead of the interactive TUI."
+    )]
+    show_sessions: bool,
+
+    #[arg(
+        long = "show-dates",
+        help = "Add First/Last columns with the first and last day each row was used. Implies the static report view instead of the interactive TUI."
+    )]
+    show_dates: bool,
+
+    #[arg(
+        long = "show-source",
+        help = "Add a Source column naming the pricing dataset behind each row's cost (LiteLLM, OpenRouter, Cursor, Custom, ...): `client` when the client reported the cost, `none` when no price matched, `mixed` when no source priced most of the row. Implies the static report view instead of the interactive TUI."
+    )]
+    show_source: bool,
+
+    #[arg(
+        long = "include-total-row",
+        requires = "json",
+        help = "With --json, append a synthetic `__total__` entry flagged `isTotal` that carries the report totals"
+    )]
+    include_total_row: bool,
+
     #[command(flatten)]
     clients: ClientFlags,
 
+    #[command(flatten)]
+    agents: AgentFlags,
+
+    #[command(flatten)]
+    projects: ProjectFlags,
+
+    #[command(flatten)]
+    models: ModelFlags,
+
+    #[command(flatten)]
+    order: OrderFlags,
+
     #[command(flatten)]
     date: DateRangeFlags,
 
@@ -81,14 +143,104 @@ struct Cli {
     )]
     home: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "NAME",
+        global = true,
+        help = "Keep caches for this profile in their own directory (<cache dir>/<NAME>/) so separate datasets never share cached results. Defaults to TOKSCALE_PROFILE."
+    )]
+    profile: Option<String>,
+
+    #[arg(
+        long = "no-dedup",
+        global = true,
+        help = "Keep OpenCode and Claude Code messages that share a dedup key instead of dropping the repeats, to compare raw and deduplicated totals. A diagnostic aid; totals may double-count."
+    )]
+    no_dedup: bool,
+
     #[arg(long, help = "Show processing time")]
     benchmark: bool,
 
+    #[arg(
+        long = "benchmark-detailed",
+        help = "Show processing time split by client (scan time, parse time, file count). Implies the static report view instead of the interactive TUI."
+    )]
+    benchmark_detailed: bool,
+
+    #[arg(
+        long = "currency-precision",
+        value_name = "N",
+        global = true,
+        default_value_t = currency::DEFAULT_PRECISION,
+        value_parser = clap::value_parser!(u8).range(0..=currency::MAX_PRECISION as i64),
+        help = "Decimal places for costs in table and text output (0-6). JSON output is unaffected."
+    )]
+    currency_precision: u8,
+
+    #[arg(
+        long = "currency",
+        value_name = "CODE",
+        global = true,
+        help = "Show costs in this currency (e.g. EUR) instead of USD, converted at the rate in TOKSCALE_FX_RATE (units of CODE per USD). JSON keeps the USD amounts next to the converted ones."
+    )]
+    currency: Option<String>,
+
+    #[arg(
+        long = "pricing-date",
+        value_name = "YYYY-MM-DD",
+        global = true,
+        value_parser = parse_pricing_date_arg,
+        help = "Price usage with the pricing snapshot recorded on this date (or the nearest earlier one) instead of live pricing"
+    )]
+    pricing_date: Option<chrono::NaiveDate>,
+
+    #[arg(
+        long = "cost-source",
+        value_name = "SOURCE",
+        global = true,
+        help = "Price costs from this dataset first when a model is listed in several: litellm or openrouter (default: auto). Custom overrides still win, and models the dataset lacks keep their usual price."
+    )]
+    cost_source: Option<tokscale_core::pricing::CostSourcePreference>,
+
+    #[arg(
+        long = "locale",
+        value_name = "TAG",
+        global = true,
+        value_parser = parse_locale_arg,
+        help = "Number format for token counts and costs in table and text output: en (1,234.5), de (1.234,5), fr (1 234,5). Defaults to LC_ALL/LC_NUMERIC, else en. JSON output is unaffected."
+    )]
+    locale: Option<locale::NumberLocale>,
+
+    #[arg(
+        long = "table-style",
+        value_name = "STYLE",
+        global = true,
+        value_enum,
+        help = "Table borders: unicode (box drawing), ascii (+-|), or none (whitespace-separated columns). Defaults to unicode on a terminal and ascii when stdout is piped."
+    )]
+    table_style: Option<table_style::TableStyle>,
+
+    #[arg(
+        long = "threads",
+        value_name = "N",
+        global = true,
+        help = "Cap the threads used to scan and parse session files (default: one per core, or TOKSCALE_SCAN_THREADS). 0 means the default."
+    )]
+    threads: Option<usize>,
+
+    #[arg(
+        long = "max-future-days",
+        value_name = "N",
+        global = true,
+        help = "Drop messages dated more than N days after today (clock skew). By default future-dated messages are counted as today."
+    )]
+    max_future_days: Option<u32>,
+
     #[arg(
         long,
         value_name = "STRATEGY",
         default_value = "client,model",
-        help = "Grouping strategy for --light and --json output: model, client,model, client,provider,model, workspace,model, session,model, client,session,model"
+        help = "Grouping strategy for --light and --json output: model, client,model, client,provider,model, workspace,model, project, session, session,model, client,session,model, family"
     )]
     group_by: String,
 
@@ -99,24 +251,74 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     #[command(about = "Show model usage report")]
+    #[command(group(clap::ArgGroup::new("json_output").args(["json", "json_compact"]).multiple(true)))]
     Models {
         #[arg(long)]
         json: bool,
+        #[arg(
+            long = "json-compact",
+            conflicts_with_all = ["markdown", "watch"],
+            help = "Like --json, but print single-line JSON instead of pretty-printed (for machine consumers)"
+        )]
+        json_compact: bool,
         #[arg(long)]
         light: bool,
+        #[arg(
+            long,
+            conflicts_with = "json",
+            help = "Print a GitHub-flavored markdown table (same columns as --light, no color)"
+        )]
+        markdown: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["json_output", "markdown", "watch"],
+            help = "Write the report as a self-contained HTML page (summary plus styled table), to stdout or --output"
+        )]
+        html: bool,
+        #[arg(
+            long,
+            value_name = "PATH",
+            requires = "html",
+            help = "With --html, write the page to this file instead of stdout"
+        )]
+        output: Option<String>,
         #[command(flatten)]
         clients: ClientFlags,
         #[command(flatten)]
+        agents: AgentFlags,
+        #[command(flatten)]
+        projects: ProjectFlags,
+        #[command(flatten)]
+        models: ModelFlags,
+        #[command(flatten)]
+        order: OrderFlags,
+        #[command(flatten)]
         date: DateRangeFlags,
         #[arg(long, help = "Show processing time")]
         benchmark: bool,
+        #[arg(
+            long = "benchmark-detailed",
+            help = "Show processing time split by client (scan time, parse time, file count). Implies the static report view instead of the interactive TUI."
+        )]
+        benchmark_detailed: bool,
         #[arg(
             long,
             value_name = "STRATEGY",
             default_value = "client,model",
-            help = "Grouping strategy for --light and --json output: model, client,model, client,provider,model, workspace,model, session,model, client,session,model"
+            help = "Grouping strategy for --light and --json output: model, client,model, client,provider,model, workspace,model, project, session, session,model, client,session,model, family"
         )]
         group_by: String,
+        #[arg(
+            long = "merge-providers",
+            help = "With --group-by client,provider,model, join a client+model's providers into one row (normalized, deduplicated, sorted)"
+        )]
+        merge_providers: bool,
+        #[arg(
+            long = "by-hour",
+            conflicts_with_all = ["markdown", "html", "watch", "summary_only", "include_total_row"],
+            help = "Total tokens and cost for each hour of the day (0-23, local time) across the date range instead of per model. Implies the static report view instead of the interactive TUI."
+        )]
+        by_hour: bool,
         #[arg(
             long = "write-cache",
             requires = "light",
@@ -136,6 +338,98 @@ enum Commands {
             help = "Hide entries whose token counts, cost, and duration are all zero. Report totals still include them. Implies the static report view instead of the interactive TUI."
         )]
         hide_zero: bool,
+        #[arg(
+            long = "min-cost",
+            value_name = "USD",
+            value_parser = parse_min_cost_arg,
+            help = "Hide entries costing less than this amount (USD). Report totals still include them. Implies the static report view instead of the interactive TUI."
+        )]
+        min_cost: Option<f64>,
+        #[arg(
+            long = "min-tokens",
+            value_name = "N",
+            value_parser = clap::value_parser!(i64).range(0..),
+            help = "Hide entries with fewer than this many tokens, counted like the Total column (reasoning included). Report totals still include them. Implies the static report view instead of the interactive TUI."
+        )]
+        min_tokens: Option<i64>,
+        #[arg(
+            long = "include-zero-cost",
+            help = "Keep $0 rows (subscription or unpriced usage) past --min-cost and order them by tokens. Implies the static report view instead of the interactive TUI."
+        )]
+        include_zero_cost: bool,
+        #[arg(
+            long = "show-sessions",
+            help = "Add a Sessions column with the number of distinct sessions per row. Implies the static report view instead of the interactive TUI."
+        )]
+        show_sessions: bool,
+        #[arg(
+            long = "show-dates",
+            help = "Add First/Last columns with the first and last day each row was used. Implies the static report view instead of the interactive TUI."
+        )]
+        show_dates: bool,
+        #[arg(
+            long = "show-source",
+            help = "Add a Source column naming the pricing dataset behind each row's cost (LiteLLM, OpenRouter, Cursor, Custom, ...): `client` when the client reported the cost, `none` when no price matched, `mixed` when no source priced most of the row. Implies the static report view instead of the interactive TUI."
+        )]
+        show_source: bool,
+        #[arg(
+            long = "show-bytes",
+            help = "Add Req Bytes and Resp Bytes columns with the request and response sizes clients logged (Cursor usage exports with byte columns). Rows without size data show `-`. Implies the static report view instead of the interactive TUI."
+        )]
+        show_bytes: bool,
+        #[arg(
+            long = "compare-estimate",
+            conflicts_with_all = ["no_pricing", "summary_only"],
+            help = "For messages whose client recorded its own cost (Amp credits, Cursor's usage CSV), add Client Cost, Estimated, and Diff columns comparing that cost with tokscale's pricing estimate. Implies the static report view instead of the interactive TUI."
+        )]
+        compare_estimate: bool,
+        #[arg(
+            long = "include-total-row",
+            requires = "json_output",
+            help = "With --json, append a synthetic `__total__` entry flagged `isTotal` that carries the report totals"
+        )]
+        include_total_row: bool,
+        #[arg(
+            long = "summary-only",
+            conflicts_with_all = ["markdown", "html", "watch", "include_total_row", "write_cache"],
+            help = "Print only the grand totals (messages, tokens with breakdown, cost) and skip the table; with --json, omits `entries`. Implies the static report view instead of the interactive TUI."
+        )]
+        summary_only: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["markdown", "html"],
+            help = "Print a one-line sparkline of daily cost under the totals. Skipped with --json or when stdout is not a terminal. Implies the static report view instead of the interactive TUI."
+        )]
+        sparkline: bool,
+        #[arg(
+            long = "env",
+            conflicts_with_all = ["json_output", "markdown", "html", "watch", "by_hour", "summary_only", "sparkline"],
+            help = "Print the report totals as TOKSCALE_TOTAL_COST, TOKSCALE_TOTAL_TOKENS, and TOKSCALE_TOTAL_MESSAGES lines with unquoted values, safe to eval or source"
+        )]
+        env_output: bool,
+        #[arg(
+            long,
+            requires = "json_output",
+            help = "With --json, drop session ids, project/agent names, and local paths and replace remaining identifiers with HMAC-SHA256 digests keyed by a random per-run salt, for sharing (consistent within one report, not reversible or comparable across runs)"
+        )]
+        anonymize: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["json_output", "anonymize", "html", "summary_only", "env_output"],
+            help = "Re-print the table whenever a session file changes, until Ctrl-C"
+        )]
+        watch: bool,
+        #[arg(
+            long = "no-pricing",
+            conflicts_with_all = ["pricing_date", "min_cost", "write_cache"],
+            help = "Skip pricing entirely for a fast, offline token-only report; costs show as — (JSON keeps client-reported costs, usually 0). Implies the static report view instead of the interactive TUI."
+        )]
+        no_pricing: bool,
+        #[arg(
+            long = "warn-duplicates",
+            help = "Print how many duplicate messages each client's dedup removed (to stderr); with --json, adds per-client `timings` carrying `duplicatesRemoved`. Implies the static report view instead of the interactive TUI."
+        )]
+        warn_duplicates: bool,
         #[arg(long, help = "Disable spinner")]
         no_spinner: bool,
     },
@@ -143,11 +437,38 @@ enum Commands {
     Monthly {
         #[arg(long)]
         json: bool,
+        #[arg(
+            long = "json-compact",
+            conflicts_with_all = ["markdown"],
+            help = "Like --json, but print single-line JSON instead of pretty-printed (for machine consumers)"
+        )]
+        json_compact: bool,
         #[arg(long)]
         light: bool,
+        #[arg(
+            long,
+            conflicts_with = "json",
+            help = "Print a GitHub-flavored markdown table (same columns as --light, no color)"
+        )]
+        markdown: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["json", "json_compact", "markdown"],
+            help = "Write the report as a self-contained HTML page (summary plus styled table), to stdout or --output"
+        )]
+        html: bool,
+        #[arg(
+            long,
+            value_name = "PATH",
+            requires = "html",
+            help = "With --html, write the page to this file instead of stdout"
+        )]
+        output: Option<String>,
         #[command(flatten)]
         clients: ClientFlags,
         #[command(flatten)]
+        agents: AgentFlags,
+        #[command(flatten)]
         date: DateRangeFlags,
         #[arg(long, help = "Show processing time")]
         benchmark: bool,
@@ -156,6 +477,19 @@ enum Commands {
             help = "Hide entries whose token counts and cost are all zero. Report totals still include them. Implies the static report view instead of the interactive TUI."
         )]
         hide_zero: bool,
+        #[arg(
+            long,
+            value_name = "USD",
+            value_parser = parse_positive_amount_arg,
+            help = "Monthly budget cap in USD, like the pricing data; --currency shows it converted. Projects this month's spend from days elapsed and warns when it would exceed the cap. Implies the static report view."
+        )]
+        budget: Option<f64>,
+        #[arg(
+            long = "summary-only",
+            conflicts_with_all = ["markdown", "html"],
+            help = "Print only the grand totals (messages, tokens with breakdown, cost) and skip the table; with --json, omits `entries`. Implies the static report view."
+        )]
+        summary_only: bool,
         #[arg(long, help = "Disable spinner")]
         no_spinner: bool,
     },
@@ -181,15 +515,43 @@ enum Commands {
     },
     #[command(about = "Show pricing for a model")]
     Pricing {
-        #[arg(help = "Model ID to look up, or `list-overrides`")]
-        model_id: String,
+        #[arg(
+            help = "Model ID to look up, or `list-overrides`",
+            required_unless_present = "search"
+        )]
+        model_id: Option<String>,
+        #[arg(
+            long,
+            value_name = "QUERY",
+            conflicts_with = "model_id",
+            help = "List the pricing keys most similar to QUERY instead of looking one up"
+        )]
+        search: Option<String>,
+        #[arg(
+            long,
+            default_value_t = 10,
+            requires = "search",
+            help = "Maximum number of --search candidates to show"
+        )]
+        limit: usize,
         #[arg(long, help = "Output as JSON")]
         json: bool,
+        #[arg(
+            long = "json-compact",
+            help = "Like --json, but print single-line JSON instead of pretty-printed (for machine consumers)"
+        )]
+        json_compact: bool,
         #[arg(
             long,
             help = "Force specific pricing source (custom, litellm, openrouter, or models.dev)"
         )]
         provider: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "search",
+            help = "Also show local usage of the model and its effective blended $/1M rate"
+        )]
+        with_usage: bool,
         #[arg(long, help = "Disable spinner")]
         no_spinner: bool,
     },
@@ -197,6 +559,28 @@ enum Commands {
     Clients {
         #[arg(long, help = "Output as JSON")]
         json: bool,
+        #[arg(
+            long,
+            conflicts_with = "json",
+            help = "Print only existing session paths, one per line"
+        )]
+        paths_only: bool,
+    },
+    #[command(about = "Diagnose scan paths, pricing reachability, cache, and timezone")]
+    Doctor {
+        #[arg(long, help = "Output as JSON")]
+        json: bool,
+    },
+    #[command(about = "Check that local session files parse and report the ones that don't")]
+    Validate {
+        #[arg(long, help = "Output as JSON")]
+        json: bool,
+        #[arg(long, help = "Exit with a nonzero status if any file failed to parse")]
+        strict: bool,
+        #[command(flatten)]
+        clients: ClientFlags,
+        #[arg(long, help = "Disable spinner")]
+        no_spinner: bool,
     },
     #[command(about = "Login to Tokscale (opens browser for GitHub auth)")]
     Login {
@@ -222,12 +606,58 @@ enum Commands {
         #[command(flatten)]
         clients: ClientFlags,
         #[command(flatten)]
+        agents: AgentFlags,
+        #[command(flatten)]
         date: DateRangeFlags,
+        #[arg(
+            long,
+            default_value = "linear",
+            help = "Day intensity scaling: linear, log, quantile"
+        )]
+        intensity: String,
+        #[arg(
+            long,
+            default_value = "cost",
+            help = "Daily total that drives intensity: cost, tokens"
+        )]
+        intensity_basis: String,
+        #[arg(
+            long = "active-threshold",
+            value_name = "TOKENS",
+            default_value_t = 0,
+            help = "Fewest tokens a day needs to count as active (0: any usage)"
+        )]
+        active_threshold: i64,
+        #[arg(
+            long,
+            help = "Add a per-model token/cost breakdown to each day (`models` array)"
+        )]
+        with_models: bool,
+        #[arg(
+            long,
+            help = "Gzip-compress the output (implied by a .gz/.gzip --output path)"
+        )]
+        gzip: bool,
+        #[arg(
+            long = "json-compact",
+            help = "Write single-line JSON instead of pretty-printed (for machine consumers)"
+        )]
+        json_compact: bool,
+        #[arg(
+            long,
+            help = "Strip identifying fields and replace remaining identifiers with HMAC-SHA256 digests keyed by a random per-run salt, for sharing (consistent within one export, not reversible or comparable across runs)"
+        )]
+        anonymize: bool,
         #[arg(long, help = "Show processing time")]
         benchmark: bool,
         #[arg(long, help = "Disable spinner")]
         no_spinner: bool,
     },
+    #[command(about = "Export raw usage data")]
+    Export {
+        #[command(subcommand)]
+        subcommand: commands::export::ExportSubcommand,
+    },
     #[command(
         about = "Import historical usage from a third-party aggregate export (e.g. clawdboard) into tokscale JSON"
     )]
@@ -266,6 +696,12 @@ enum Commands {
             help = "Show what would be submitted without actually submitting"
         )]
         dry_run: bool,
+        #[arg(
+            long,
+            requires = "dry_run",
+            help = "With --dry-run, print the exact JSON body that would be POSTed"
+        )]
+        show_payload: bool,
     },
     #[command(about = "Manage periodic usage submission")]
     Autosubmit {
@@ -282,9 +718,115 @@ enum Commands {
         format: Option<String>,
         #[arg(long, help = "Write captured output to file")]
         output: Option<String>,
+        #[arg(
+            long,
+            value_name = "BYTES",
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "Rotate to a new numbered capture file (-001, -002, ...) once the current one reaches this size"
+        )]
+        max_file_bytes: Option<u64>,
         #[arg(long, help = "Do not auto-add JSON output flags")]
         no_auto_flags: bool,
     },
+    #[command(about = "Compare per-model cost between two date ranges")]
+    Diff {
+        #[arg(long, help = "Output as JSON")]
+        json: bool,
+        #[command(flatten)]
+        clients: ClientFlags,
+        /// The current range; month-to-date when no date flag is given.
+        #[command(flatten)]
+        date: DateRangeFlags,
+        #[arg(
+            long,
+            requires = "previous_until",
+            value_parser = parse_date_bound_arg,
+            help = "Start of the previous range (YYYY-MM-DD, or relative to today: 7d, 12w, 3m, 1y; default: the equally long span before the current range)"
+        )]
+        previous_since: Option<String>,
+        #[arg(
+            long,
+            requires = "previous_since",
+            value_parser = parse_date_bound_arg,
+            help = "End of the previous range, inclusive (YYYY-MM-DD, or relative to today: 7d, 12w, 3m, 1y)"
+        )]
+        previous_until: Option<String>,
+        #[arg(long, help = "Disable loading spinner (for scripting)")]
+        no_spinner: bool,
+    },
+    #[command(about = "Project spend forward from the recent daily cost trend")]
+    Forecast {
+        #[arg(long, help = "Output as JSON")]
+        json: bool,
+        #[command(flatten)]
+        clients: ClientFlags,
+        #[arg(
+            long,
+            value_name = "DAYS",
+            default_value_t = commands::forecast::DEFAULT_LOOKBACK_DAYS,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Days of history (ending yesterday) to fit the trend to"
+        )]
+        lookback: u32,
+        #[arg(
+            long,
+            value_name = "DAYS",
+            default_value_t = commands::forecast::DEFAULT_HORIZON_DAYS,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Days to project forward, starting today"
+        )]
+        horizon: u32,
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = commands::forecast::ForecastMethod::Linear,
+            help = "linear: least-squares trend line; moving-average: mean of the last 7 days"
+        )]
+        method: commands::forecast::ForecastMethod,
+        #[arg(long, help = "Disable loading spinner (for scripting)")]
+        no_spinner: bool,
+    },
+    #[command(about = "Rank individual sessions across all clients by cost")]
+    TopSessions {
+        #[arg(long, help = "Output as JSON (every session unless --top is given)")]
+        json: bool,
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+            help = "Number of sessions to show (default: 20 in the table, all in JSON)"
+        )]
+        top: Option<usize>,
+        #[arg(
+            long = "include-zero-cost",
+            help = "Keep $0 sessions (subscription or unpriced usage) in the list even past --top"
+        )]
+        include_zero_cost: bool,
+        #[arg(
+            long,
+            help = "List only sessions whose parsed tokens or estimated cost differ from the totals the client reported (OpenClaw) by more than --reconcile-threshold"
+        )]
+        reconcile: bool,
+        #[arg(
+            long = "reconcile-threshold",
+            value_name = "PCT",
+            requires = "reconcile",
+            default_value_t = commands::top_sessions::DEFAULT_RECONCILE_THRESHOLD_PCT,
+            value_parser = parse_non_negative_pct,
+            help = "Percent difference from a reported total that flags a session"
+        )]
+        reconcile_threshold: f64,
+        #[command(flatten)]
+        clients: ClientFlags,
+        #[command(flatten)]
+        projects: ProjectFlags,
+        #[command(flatten)]
+        models: ModelFlags,
+        #[command(flatten)]
+        date: DateRangeFlags,
+        #[arg(long, help = "Disable loading spinner (for scripting)")]
+        no_spinner: bool,
+    },
     #[command(about = "Generate year-in-review wrapped image")]
     Wrapped {
         #[arg(long, help = "Output file path (default: tokscale-{year}-wrapped.png)")]
@@ -307,6 +849,19 @@ enum Commands {
         show_clients: bool,
         #[arg(long, help = "Disable pinning of Sisyphus agents in rankings")]
         disable_pinned: bool,
+        #[arg(
+            long = "active-threshold",
+            value_name = "TOKENS",
+            default_value_t = 0,
+            help = "Fewest tokens a day needs to count as active and extend a streak (0: any usage)"
+        )]
+        active_threshold: i64,
+        #[arg(
+            long,
+            conflicts_with = "output",
+            help = "Print the wrapped stats as JSON instead of rendering the image"
+        )]
+        json: bool,
         #[arg(long, help = "Disable loading spinner (for scripting)")]
         no_spinner: bool,
     },
@@ -342,6 +897,11 @@ enum Commands {
         #[command(subcommand)]
         subcommand: WarpSubcommand,
     },
+    #[command(about = "Print JSON Schemas for tokscale's machine-readable output")]
+    Schema {
+        #[command(subcommand)]
+        subcommand: SchemaSubcommand,
+    },
     #[command(about = "Delete all submitted usage data from the server")]
     DeleteSubmittedData,
     #[command(
@@ -384,6 +944,31 @@ enum Commands {
     },
 }
 
+impl Commands {
+    fn no_spinner_mut(&mut self) -> Option<&mut bool> {
+        match self {
+            Self::Models { no_spinner, .. }
+            | Self::Monthly { no_spinner, .. }
+            | Self::Hourly { no_spinner, .. }
+            | Self::Pricing { no_spinner, .. }
+            | Self::Validate { no_spinner, .. }
+            | Self::Graph { no_spinner, .. }
+            | Self::Diff { no_spinner, .. }
+            | Self::Forecast { no_spinner, .. }
+            | Self::TopSessions { no_spinner, .. }
+            | Self::Wrapped { no_spinner, .. }
+            | Self::TimeMetrics { no_spinner, .. } => Some(no_spinner),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum SchemaSubcommand {
+    #[command(about = "Schema for `tokscale graph` output and `/api/submit` payloads")]
+    Graph,
+}
+
 #[derive(Subcommand)]
 enum CursorSubcommand {
     #[command(about = "Login to Cursor with a browser session token")]
@@ -528,10 +1113,73 @@ enum WarpSubcommand {
     },
 }
 
-fn main() -> Result<()> {
+/// Print a clap error and exit. Help and version requests keep clap's own
+/// exit code; real usage errors exit with [`exit_code::USAGE`] instead of
+/// clap's default 2, which is reserved for pricing/network failures.
+fn exit_on_clap_error(err: clap::Error) -> ! {
+    if !err.use_stderr() {
+        err.exit();
+    }
+    let _ = err.print();
+    std::process::exit(exit_code::USAGE);
+}
+
+fn main() {
+    if let Err(err) = run() {
+        print_failure(&err);
+        std::process::exit(exit_code::code_for(&err));
+    }
+}
+
+/// Print a command's error the way `main -> Result` would, except for
+/// [`exit_code::Failure`]s: network failures get the red one-line form, and
+/// empty results and timeouts already reported themselves.
+fn print_failure(err: &anyhow::Error) {
+    use colored::Colorize;
+
+    match err.downcast_ref::<exit_code::Failure>() {
+        Some(exit_code::Failure::Network(message)) => {
+            eprintln!("\n  {}\n", format!("Error: {message}").red());
+        }
+        Some(_) => {}
+        None => eprintln!("Error: {err:?}"),
+    }
+}
+
+fn run() -> Result<()> {
     use std::io::IsTerminal;
 
-    let cli = Cli::parse();
+    let matches = Cli::command()
+        .try_get_matches()
+        .unwrap_or_else(|e| exit_on_clap_error(e));
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| exit_on_clap_error(e));
+    let defaults = cli_defaults::CliDefaults::load()?;
+    apply_cli_defaults(&mut cli, &matches, &defaults);
+    cli_defaults::set_global(defaults);
+    currency::set_precision(cli.currency_precision);
+    configure_currency(cli.currency.as_deref());
+    locale::set_current(
+        cli.locale
+            .or_else(|| locale::NumberLocale::from_env(|key| std::env::var(key).ok()))
+            .unwrap_or_default(),
+    );
+    table_style::set_current(table_style::TableStyle::resolve(
+        cli.table_style,
+        std::io::stdout().is_terminal(),
+    ));
+    configure_profile(cli.profile.as_deref());
+    if cli.no_dedup {
+        eprintln!(
+            "[tokscale] Note: dedup is disabled (--no-dedup); repeated OpenCode and Claude Code messages are all counted"
+        );
+    }
+    parse_flags::set_current(parse_flags::ParseFlags {
+        cost_source: cli.cost_source.unwrap_or_default(),
+        max_future_days: cli.max_future_days,
+        no_dedup: cli.no_dedup,
+        threads: scan_threads(cli.threads),
+        pricing_date: cli.pricing_date.map(pricing_snapshot_date),
+    });
     // Install user-configured model aliases once, before any report/graph/TUI
     // path runs, so model-name variants fold consistently across every command.
     // Honors the global `--home` override exactly like scanner settings; an
@@ -550,38 +1198,178 @@ fn main() -> Result<()> {
     match cli.command {
         Some(Commands::Models {
             json,
+            json_compact,
             light,
+            markdown,
+            html,
+            output,
             clients,
+            agents,
+            projects,
+            models,
+            order,
             date,
             benchmark,
+            benchmark_detailed,
             group_by,
+            merge_providers,
+            by_hour,
             write_cache,
             no_write_cache,
             hide_zero,
+            min_cost,
+            min_tokens,
+            include_zero_cost,
+            show_sessions,
+            show_dates,
+            show_source,
+            show_bytes,
+            compare_estimate,
+            include_total_row,
+            summary_only,
+            sparkline,
+            env_output,
+            anonymize,
+            watch,
+            no_pricing,
+            warn_duplicates,
             no_spinner,
         }) => {
             use tokscale_core::GroupBy;
 
-            let group_by: GroupBy = group_by.parse().unwrap_or_else(|e| {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
-            });
+            let json = json || json_compact;
+            let json_style = JsonStyle::from_compact_flag(json_compact);
+            let html = html.then_some(html::HtmlOutput { path: output });
+            let group_by: GroupBy = group_by
+                .parse::<GroupBy>()
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                })
+                .with_merged_providers(merge_providers);
             let clients = build_client_filter(clients, &cli.home);
-            if json || light || hide_zero || !can_use_tui {
-                run_models_report(
+            let agent_filter = agents.into_filter();
+            let project_filter = projects.into_filter();
+            let model_filter = models.into_filter()?;
+            let has_threshold = min_cost.is_some() || min_tokens.is_some();
+            let custom_order = order.is_set();
+            let model_order = order.into_order();
+            if by_hour {
+                run_hour_of_day_report(
                     json,
+                    json_style,
                     cli.home.clone(),
                     clients,
+                    agent_filter,
+                    project_filter,
+                    model_filter,
+                    &date,
+                    no_spinner,
+                )
+            } else if watch {
+                let dirs = watch::source_dirs(&cli.home, &clients)?;
+                watch::run(&dirs, || {
+                    run_models_report(
+                        ModelsOutputOptions {
+                            json_style,
+                            markdown,
+                            warn_duplicates,
+                            benchmark,
+                            benchmark_detailed,
+                            no_spinner: true,
+                            hide_zero,
+                            include_zero_cost,
+                            show_sessions,
+                            show_dates,
+                            show_source,
+                            show_bytes,
+                            compare_estimate,
+                            include_total_row,
+                            sparkline,
+                            // clap rejects --watch with the JSON, HTML,
+                            // --summary-only and --env outputs.
+                            ..Default::default()
+                        },
+                        no_pricing,
+                        cli.home.clone(),
+                        clients.clone(),
+                        agent_filter.clone(),
+                        project_filter.clone(),
+                        model_filter.clone(),
+                        &date,
+                        group_by.clone(),
+                        model_order,
+                        write_cache,
+                        no_write_cache,
+                        min_cost,
+                        min_tokens,
+                    )
+                    .map(|_| ())
+                })
+            } else if json
+                || light
+                || markdown
+                || html.is_some()
+                || no_pricing
+                || warn_duplicates
+                || hide_zero
+                || has_threshold
+                || include_zero_cost
+                || custom_order
+                || show_sessions
+                || show_dates
+                || show_source
+                || show_bytes
+                || compare_estimate
+                || summary_only
+                || sparkline
+                || env_output
+                || !agent_filter.is_empty()
+                || !project_filter.is_empty()
+                || !model_filter.is_empty()
+                || benchmark_detailed
+                || !can_use_tui
+            {
+                run_models_report(
+                    ModelsOutputOptions {
+                        json,
+                        json_style,
+                        anonymize,
+                        markdown,
+                        html,
+                        warn_duplicates,
+                        benchmark,
+                        benchmark_detailed,
+                        no_spinner: no_spinner || !can_use_tui,
+                        hide_zero,
+                        include_zero_cost,
+                        show_sessions,
+                        show_dates,
+                        show_source,
+                        show_bytes,
+                        compare_estimate,
+                        include_total_row,
+                        summary_only,
+                        sparkline,
+                        env_output,
+                    },
+                    no_pricing,
+                    cli.home.clone(),
+                    clients,
+                    agent_filter,
+                    project_filter,
+                    model_filter,
                     &date,
-                    benchmark,
-                    no_spinner || !can_use_tui,
                     group_by,
+                    model_order,
                     write_cache,
                     no_write_cache,
-                    hide_zero,
+                    min_cost,
+                    min_tokens,
                 )
+                .and_then(fail_if_empty)
             } else {
-                let (since, until) = build_date_filter(&date);
+                let (since, until) = build_tui_date_filter(&date);
                 let year = normalize_year_filter(&date);
                 ensure_home_supported_for_tui(&cli.home)?;
                 auto_sync_cursor_before_tui(&cli.home, &clients)?;
@@ -599,26 +1387,51 @@ fn main() -> Result<()> {
         }
         Some(Commands::Monthly {
             json,
+            json_compact,
             light,
+            markdown,
+            html,
+            output,
             clients,
+            agents,
             date,
             benchmark,
             hide_zero,
+            budget,
+            summary_only,
             no_spinner,
         }) => {
+            let json = json || json_compact;
+            let html = html.then_some(html::HtmlOutput { path: output });
             let clients = build_client_filter(clients, &cli.home);
-            if json || light || hide_zero || !can_use_tui {
+            let agent_filter = agents.into_filter();
+            if json
+                || light
+                || markdown
+                || html.is_some()
+                || hide_zero
+                || budget.is_some()
+                || summary_only
+                || !agent_filter.is_empty()
+                || !can_use_tui
+            {
                 run_monthly_report(
                     json,
+                    JsonStyle::from_compact_flag(json_compact),
+                    markdown,
+                    html,
                     cli.home.clone(),
                     clients,
+                    agent_filter,
                     &date,
                     benchmark,
                     no_spinner || !can_use_tui,
                     hide_zero,
+                    budget,
+                    summary_only,
                 )
             } else {
-                let (since, until) = build_date_filter(&date);
+                let (since, until) = build_tui_date_filter(&date);
                 let year = normalize_year_filter(&date);
                 ensure_home_supported_for_tui(&cli.home)?;
                 auto_sync_cursor_before_tui(&cli.home, &clients)?;
@@ -655,7 +1468,7 @@ fn main() -> Result<()> {
                     hide_zero,
                 )
             } else {
-                let (since, until) = build_date_filter(&date);
+                let (since, until) = build_tui_date_filter(&date);
                 let year = normalize_year_filter(&date);
                 ensure_home_supported_for_tui(&cli.home)?;
                 auto_sync_cursor_before_tui(&cli.home, &clients)?;
@@ -673,14 +1486,50 @@ fn main() -> Result<()> {
         }
         Some(Commands::Pricing {
             model_id,
+            search,
+            limit,
             json,
+            json_compact,
             provider,
+            with_usage,
+            no_spinner,
+        }) => {
+            // Only the local usage scan of --with-usage reads a home.
+            if !with_usage {
+                reject_unsupported_home_override(&cli.home, "pricing")?;
+            }
+            let json = json || json_compact;
+            let json_style = JsonStyle::from_compact_flag(json_compact);
+            match (search, model_id) {
+                (Some(query), _) => run_pricing_search(&query, limit, json, json_style, no_spinner),
+                (None, Some(model_id)) => run_pricing_lookup(
+                    &model_id,
+                    json,
+                    json_style,
+                    provider.as_deref(),
+                    with_usage,
+                    cli.home.clone(),
+                    no_spinner,
+                ),
+                (None, None) => unreachable!("clap requires a model id or --search"),
+            }
+        }
+        Some(Commands::Clients { json, paths_only }) => {
+            run_clients_command(json, paths_only, cli.home.clone())
+        }
+        Some(Commands::Doctor { json }) => commands::doctor::run(commands::doctor::DoctorOptions {
+            json,
+            home_dir: cli.home.clone(),
+        }),
+        Some(Commands::Validate {
+            json,
+            strict,
+            clients,
             no_spinner,
         }) => {
-            reject_unsupported_home_override(&cli.home, "pricing")?;
-            run_pricing_lookup(&model_id, json, provider.as_deref(), no_spinner)
+            let clients = build_client_filter(clients, &cli.home);
+            run_validate_command(json, strict, cli.home.clone(), clients, no_spinner)
         }
-        Some(Commands::Clients { json }) => run_clients_command(json, cli.home.clone()),
         Some(Commands::Login { token }) => {
             reject_unsupported_home_override(&cli.home, "login")?;
             run_login_command(token)
@@ -700,10 +1549,28 @@ fn main() -> Result<()> {
         Some(Commands::Graph {
             output,
             clients,
+            agents,
             date,
+            intensity,
+            intensity_basis,
+            active_threshold,
+            with_models,
+            gzip,
+            json_compact,
+            anonymize,
             benchmark,
             no_spinner,
         }) => {
+            let intensity_mode: tokscale_core::IntensityMode =
+                intensity.parse().unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            let intensity_basis: tokscale_core::IntensityBasis =
+                intensity_basis.parse().unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
             let (since, until) = build_date_filter(&date);
             let year = normalize_year_filter(&date);
             let clients = build_client_filter(clients, &cli.home);
@@ -711,13 +1578,66 @@ fn main() -> Result<()> {
                 output,
                 cli.home.clone(),
                 clients,
+                agents.into_filter(),
                 since,
                 until,
+                date.until_exclusive,
                 year,
+                intensity_mode,
+                intensity_basis,
+                active_threshold,
+                with_models,
+                gzip,
+                JsonStyle::from_compact_flag(json_compact),
+                anonymize,
                 benchmark,
                 no_spinner,
             )
         }
+        Some(Commands::Export { subcommand }) => match subcommand {
+            commands::export::ExportSubcommand::Sqlite {
+                output,
+                clients,
+                date,
+                gzip,
+                no_spinner,
+            } => {
+                let (since, until) = build_date_filter(&date);
+                let year = normalize_year_filter(&date);
+                let clients = build_client_filter(clients, &cli.home);
+                run_export_sqlite_command(
+                    output,
+                    cli.home.clone(),
+                    clients,
+                    since,
+                    until,
+                    date.until_exclusive,
+                    year,
+                    gzip,
+                    no_spinner,
+                )
+            }
+            commands::export::ExportSubcommand::Otlp {
+                endpoint,
+                clients,
+                date,
+                no_spinner,
+            } => {
+                let (since, until) = build_date_filter(&date);
+                let year = normalize_year_filter(&date);
+                let clients = build_client_filter(clients, &cli.home);
+                run_export_otlp_command(
+                    endpoint,
+                    cli.home.clone(),
+                    clients,
+                    since,
+                    until,
+                    date.until_exclusive,
+                    year,
+                    no_spinner,
+                )
+            }
+        },
         Some(Commands::Import {
             file,
             format,
@@ -729,7 +1649,7 @@ fn main() -> Result<()> {
         }
         Some(Commands::Tui { clients, date }) => {
             ensure_home_supported_for_tui(&cli.home)?;
-            let (since, until) = build_date_filter(&date);
+            let (since, until) = build_tui_date_filter(&date);
             let year = normalize_year_filter(&date);
             let clients = build_client_filter(clients, &cli.home);
             auto_sync_cursor_before_tui(&cli.home, &clients)?;
@@ -748,6 +1668,7 @@ fn main() -> Result<()> {
             clients,
             date,
             dry_run,
+            show_payload,
         }) => {
             reject_unsupported_home_override(&cli.home, "submit")?;
             let (since, until) = build_date_filter(&date);
@@ -762,8 +1683,9 @@ fn main() -> Result<()> {
                 clients,
                 since,
                 until,
+                date.until_exclusive,
                 year,
-                dry_run,
+                SubmitPreview::from_flags(dry_run, show_payload),
                 SubmitMode::Interactive,
             )
         }
@@ -776,10 +1698,80 @@ fn main() -> Result<()> {
             args,
             format,
             output,
+            max_file_bytes,
             no_auto_flags,
         }) => {
             reject_unsupported_home_override(&cli.home, "headless")?;
-            run_headless_command(&source, args, format, output, no_auto_flags)
+            run_headless_command(&source, args, format, output, max_file_bytes, no_auto_flags)
+        }
+        Some(Commands::Diff {
+            json,
+            clients,
+            date,
+            previous_since,
+            previous_until,
+            no_spinner,
+        }) => {
+            let clients = build_client_filter(clients, &cli.home);
+            commands::diff::run(commands::diff::DiffOptions {
+                json,
+                home_dir: cli.home.clone(),
+                clients,
+                date,
+                previous_since,
+                previous_until,
+                no_spinner,
+            })
+        }
+        Some(Commands::Forecast {
+            json,
+            clients,
+            lookback,
+            horizon,
+            method,
+            no_spinner,
+        }) => {
+            let clients = build_client_filter(clients, &cli.home);
+            commands::forecast::run(commands::forecast::ForecastOptions {
+                json,
+                home_dir: cli.home.clone(),
+                clients,
+                lookback,
+                horizon,
+                method,
+                no_spinner,
+            })
+        }
+        Some(Commands::TopSessions {
+            json,
+            top,
+            include_zero_cost,
+            reconcile,
+            reconcile_threshold,
+            clients,
+            projects,
+            models,
+            date,
+            no_spinner,
+        }) => {
+            let clients = build_client_filter(clients, &cli.home);
+            let (since, until) = build_date_filter(&date);
+            commands::top_sessions::run(commands::top_sessions::TopSessionsOptions {
+                json,
+                top,
+                include_zero_cost,
+                reconcile_threshold: reconcile.then_some(reconcile_threshold),
+                home_dir: cli.home.clone(),
+                clients,
+                project_filter: projects.into_filter(),
+                model_filter: models.into_filter()?,
+                since,
+                until,
+                until_exclusive: date.until_exclusive,
+                year: normalize_year_filter(&date),
+                date_range: get_date_range_label(&date),
+                no_spinner,
+            })
         }
         Some(Commands::Wrapped {
             output,
@@ -789,11 +1781,13 @@ fn main() -> Result<()> {
             agents,
             show_clients,
             disable_pinned,
+            active_threshold,
+            json,
             no_spinner: _,
         }) => {
-            reject_unsupported_home_override(&cli.home, "wrapped")?;
             let client_filter = build_client_filter(client_flags, &cli.home);
             run_wrapped_command(
+                cli.home.clone(),
                 output,
                 year,
                 client_filter,
@@ -801,6 +1795,8 @@ fn main() -> Result<()> {
                 agents,
                 show_clients,
                 disable_pinned,
+                active_threshold,
+                json,
             )
         }
         Some(Commands::Cursor { subcommand }) => {
@@ -827,6 +1823,7 @@ fn main() -> Result<()> {
             reject_unsupported_home_override(&cli.home, "warp")?;
             run_warp_command(subcommand)
         }
+        Some(Commands::Schema { subcommand }) => run_schema_command(subcommand),
         Some(Commands::DeleteSubmittedData) => {
             reject_unsupported_home_override(&cli.home, "delete-submitted-data")?;
             run_delete_data_command()
@@ -846,6 +1843,7 @@ fn main() -> Result<()> {
                 clients,
                 since,
                 until,
+                date.until_exclusive,
                 year,
                 no_spinner,
             )
@@ -864,7 +1862,7 @@ fn main() -> Result<()> {
             let today = date.today;
             let week = date.week;
             let month = date.month;
-            let (since, until) = build_date_filter(&date);
+            let (since, until) = build_tui_date_filter(&date);
             commands::report::run_report(commands::report::ReportOptions {
                 json,
                 since,
@@ -889,34 +1887,84 @@ fn main() -> Result<()> {
                 std::process::exit(1);
             });
 
+            let agent_filter = cli.agents.into_filter();
+            let project_filter = cli.projects.into_filter();
+            let model_filter = cli.models.into_filter()?;
+            let has_threshold = cli.min_cost.is_some() || cli.min_tokens.is_some();
+            let custom_order = cli.order.is_set();
+            let model_order = cli.order.into_order();
+
             if cli.json {
                 run_models_report(
-                    cli.json,
+                    ModelsOutputOptions {
+                        json: cli.json,
+                        benchmark: cli.benchmark,
+                        benchmark_detailed: cli.benchmark_detailed,
+                        no_spinner: cli.no_spinner || cli.json,
+                        hide_zero: cli.hide_zero,
+                        show_sessions: cli.show_sessions,
+                        show_dates: cli.show_dates,
+                        show_source: cli.show_source,
+                        include_total_row: cli.include_total_row,
+                        ..Default::default()
+                    },
+                    false,
                     cli.home.clone(),
                     clients,
+                    agent_filter,
+                    project_filter,
+                    model_filter,
                     &cli.date,
-                    cli.benchmark,
-                    cli.no_spinner || cli.json,
                     group_by,
+                    model_order,
                     cli.write_cache,
                     cli.no_write_cache,
-                    cli.hide_zero,
+                    cli.min_cost,
+                    cli.min_tokens,
                 )
-            } else if cli.light || cli.hide_zero || !can_use_tui {
+                .and_then(fail_if_empty)
+            } else if cli.light
+                || cli.hide_zero
+                || has_threshold
+                || custom_order
+                || cli.show_sessions
+                || cli.show_dates
+                || cli.show_source
+                || !agent_filter.is_empty()
+                || !project_filter.is_empty()
+                || !model_filter.is_empty()
+                || cli.benchmark_detailed
+                || !can_use_tui
+            {
                 run_models_report(
+                    ModelsOutputOptions {
+                        benchmark: cli.benchmark,
+                        benchmark_detailed: cli.benchmark_detailed,
+                        no_spinner: cli.no_spinner || !can_use_tui,
+                        hide_zero: cli.hide_zero,
+                        show_sessions: cli.show_sessions,
+                        show_dates: cli.show_dates,
+                        show_source: cli.show_source,
+                        include_total_row: cli.include_total_row,
+                        ..Default::default()
+                    },
                     false,
                     cli.home.clone(),
                     clients,
+                    agent_filter,
+                    project_filter,
+                    model_filter,
                     &cli.date,
-                    cli.benchmark,
-                    cli.no_spinner || !can_use_tui,
                     group_by,
+                    model_order,
                     cli.write_cache,
                     cli.no_write_cache,
-                    cli.hide_zero,
+                    cli.min_cost,
+                    cli.min_tokens,
                 )
+                .and_then(fail_if_empty)
             } else {
-                let (since, until) = build_date_filter(&cli.date);
+                let (since, until) = build_tui_date_filter(&cli.date);
                 let year = normalize_year_filter(&cli.date);
                 ensure_home_supported_for_tui(&cli.home)?;
                 auto_sync_cursor_before_tui(&cli.home, &clients)?;
@@ -996,6 +2044,7 @@ pub enum ClientFilter {
     DevinCli,
     #[value(name = "devin-desktop")]
     DevinDesktop,
+    Aider,
     Synthetic,
 }
 
@@ -1045,6 +2094,7 @@ impl ClientFilter {
             Self::Workbuddy => "workbuddy",
             Self::DevinCli => "devin-cli",
             Self::DevinDesktop => "devin-desktop",
+            Self::Aider => "aider",
             Self::Synthetic => "synthetic",
         }
     }
@@ -1097,6 +2147,7 @@ impl ClientFilter {
             Self::Workbuddy => Some(ClientId::WorkBuddy),
             Self::DevinCli => Some(ClientId::DevinCli),
             Self::DevinDesktop => Some(ClientId::DevinDesktop),
+            Self::Aider => Some(ClientId::Aider),
             Self::Synthetic => None,
         }
     }
@@ -1145,6 +2196,7 @@ impl ClientFilter {
             ClientId::WorkBuddy => Self::Workbuddy,
             ClientId::DevinCli => Self::DevinCli,
             ClientId::DevinDesktop => Self::DevinDesktop,
+            ClientId::Aider => Self::Aider,
         }
     }
 
@@ -1194,7 +2246,131 @@ pub struct ClientFlags {
         ignore_case = true,
         help = "Filter by client(s). Repeatable or comma-separated (e.g. -c opencode,claude)."
     )]
-    pub clients: Vec<ClientFilter>,
+    pub clients: Vec<ClientFilter>,
+    /// Clients to leave out. Applied to `--client` when given, otherwise to
+    /// every client. Example: `--exclude-client cursor`.
+    #[arg(
+        id = "exclude_client",
+        long = "exclude-client",
+        value_name = "CLIENTS",
+        value_enum,
+        value_delimiter = ',',
+        action = clap::ArgAction::Append,
+        ignore_case = true,
+        help = "Exclude client(s). Repeatable or comma-separated; applies to --client if given, else to all clients."
+    )]
+    pub exclude_clients: Vec<ClientFilter>,
+}
+
+#[derive(Args, Clone, Debug, Default)]
+pub struct AgentFlags {
+    /// Agent filter matched against each message's `agent` tag, e.g.
+    /// `--agent headless`. `(none)` selects untagged messages.
+    #[arg(
+        id = "agent_filter",
+        long = "agent",
+        value_name = "AGENTS",
+        value_delimiter = ',',
+        action = clap::ArgAction::Append,
+        help = "Only count messages from these agent(s). Repeatable or comma-separated; use '(none)' for messages without an agent."
+    )]
+    pub agents: Vec<String>,
+    #[arg(
+        id = "exclude_agent",
+        long = "exclude-agent",
+        value_name = "AGENTS",
+        value_delimiter = ',',
+        action = clap::ArgAction::Append,
+        help = "Leave out messages from these agent(s). Repeatable or comma-separated; use '(none)' for messages without an agent."
+    )]
+    pub exclude_agents: Vec<String>,
+}
+
+impl AgentFlags {
+    fn into_filter(self) -> tokscale_core::AgentFilter {
+        tokscale_core::AgentFilter::new(self.agents, self.exclude_agents)
+    }
+}
+
+#[derive(Args, Clone, Debug, Default)]
+pub struct ProjectFlags {
+    /// Project filter matched against each message's workspace, e.g.
+    /// `--project tokscale`. `(unknown)` selects messages without one.
+    #[arg(
+        id = "project_filter",
+        long = "project",
+        value_name = "PROJECTS",
+        value_delimiter = ',',
+        action = clap::ArgAction::Append,
+        help = "Only count messages from these project(s) (workspace name or full key). Repeatable or comma-separated; use '(unknown)' for messages without a project."
+    )]
+    pub projects: Vec<String>,
+}
+
+impl ProjectFlags {
+    fn into_filter(self) -> tokscale_core::ProjectFilter {
+        tokscale_core::ProjectFilter::new(self.projects)
+    }
+}
+
+#[derive(Args, Clone, Debug, Default)]
+pub struct ModelFlags {
+    #[arg(
+        id = "model_filter",
+        long = "model",
+        value_name = "REGEX",
+        action = clap::ArgAction::Append,
+        help = "Only count models whose normalized id matches this regex. Repeatable; a model matching any of them is kept."
+    )]
+    pub include: Vec<String>,
+    #[arg(
+        long = "exclude-model",
+        value_name = "REGEX",
+        action = clap::ArgAction::Append,
+        help = "Drop models whose normalized id matches this regex, after --model is applied. Repeatable."
+    )]
+    pub exclude: Vec<String>,
+}
+
+impl ModelFlags {
+    fn into_filter(self) -> Result<tokscale_core::ModelFilter> {
+        tokscale_core::ModelFilter::new(self.include, self.exclude).map_err(|e| anyhow::anyhow!(e))
+    }
+}
+
+#[derive(Args, Clone, Debug, Default)]
+pub struct OrderFlags {
+    #[arg(
+        long = "order-by",
+        value_name = "KEY",
+        help = "Sort model rows by cost, tokens, messages, model, input, or output (default: cost). Implies the static report view."
+    )]
+    pub order_by: Option<tokscale_core::ModelOrderKey>,
+    #[arg(
+        long,
+        conflicts_with = "desc",
+        help = "Sort ascending (the default for --order-by model)"
+    )]
+    pub asc: bool,
+    #[arg(long, help = "Sort descending (the default for every other key)")]
+    pub desc: bool,
+}
+
+impl OrderFlags {
+    fn is_set(&self) -> bool {
+        self.order_by.is_some() || self.asc || self.desc
+    }
+
+    fn into_order(self) -> tokscale_core::ModelOrder {
+        let descending = if self.asc {
+            Some(false)
+        } else if self.desc {
+            Some(true)
+        } else {
+            None
+        };
+        tokscale_core::ModelOrder::new(self.order_by.unwrap_or_default(), descending)
+    }
 }
 
 #[derive(Args, Clone, Debug, Default)]
@@ -1223,10 +2399,24 @@ pub struct DateRangeFlags {
         conflicts_with_all = ["since", "until", "year"]
     )]
     pub month: bool,
-    #[arg(long, help = "Start date (YYYY-MM-DD)")]
+    #[arg(
+        long,
+        value_parser = parse_date_bound_arg,
+        help = "Start date (YYYY-MM-DD, or relative to today: 7d, 12w, 3m, 1y)"
+    )]
     pub since: Option<String>,
-    #[arg(long, help = "End date (YYYY-MM-DD)")]
+    #[arg(
+        long,
+        value_parser = parse_date_bound_arg,
+        help = "End date (YYYY-MM-DD, or relative to today: 7d, 12w, 3m, 1y)"
+    )]
     pub until: Option<String>,
+    #[arg(
+        long,
+        requires = "until",
+        help = "Exclude the --until date itself, so back-to-back windows never share a day"
+    )]
+    pub until_exclusive: bool,
     #[arg(long, help = "Filter by year (YYYY)")]
     pub year: Option<String>,
 }
@@ -1242,10 +2432,60 @@ pub struct DateRangeFlags {
 /// Returns `None` when no filters are active *and* no defaults configured
 /// so the caller can scan all clients.
 fn build_client_filter(flags: ClientFlags, home_dir: &Option<String>) -> Option<Vec<String>> {
-    let defaults = tui::settings::load_default_clients_for_home(home_dir);
+    // `clients` from config.toml / .tokscalerc / TOKSCALE_CLIENTS takes
+    // precedence over settings.json `defaultClients`.
+    let defaults = match cli_defaults::global_clients() {
+        Some(clients) => clients.to_vec(),
+        None => tui::settings::load_default_clients_for_home(home_dir),
+    };
     build_client_filter_with_defaults(flags, &defaults)
 }
 
+/// Fill in flags the user did not pass with values from `CliDefaults`.
+/// Anything given on the command line wins; clap's built-in defaults only
+/// survive when no config file or env var sets the value.
+fn apply_cli_defaults(
+    cli: &mut Cli,
+    matches: &clap::ArgMatches,
+    defaults: &cli_defaults::CliDefaults,
+) {
+    fn passed(matches: &clap::ArgMatches, id: &str) -> bool {
+        matches.value_source(id) == Some(clap::parser::ValueSource::CommandLine)
+    }
+
+    if cli.theme.is_none() {
+        cli.theme = defaults.theme.clone();
+    }
+    if let Some(refresh) = defaults.refresh {
+        if !passed(matches, "refresh") {
+            cli.refresh = refresh;
+        }
+    }
+    if let Some(group_by) = &defaults.group_by {
+        if !passed(matches, "group_by") {
+            cli.group_by = group_by.clone();
+        }
+        if let (
+            Some(Commands::Models {
+                group_by: models_group_by,
+                ..
+            }),
+            Some(sub),
+        ) = (&mut cli.command, matches.subcommand_matches("models"))
+        {
+            if !passed(sub, "group_by") {
+                *models_group_by = group_by.clone();
+            }
+        }
+    }
+    if defaults.no_spinner == Some(true) {
+        cli.no_spinner = true;
+        if let Some(no_spinner) = cli.command.as_mut().and_then(Commands::no_spinner_mut) {
+            *no_spinner = true;
+        }
+    }
+}
+
 /// Pure variant of [`build_client_filter`] for unit-testable resolution.
 /// `defaults` is the (already-validated) list of canonical filter ids that
 /// should apply when no CLI flag is present.
@@ -1263,6 +2503,27 @@ fn build_client_filter_with_defaults(
         }
     }
 
+    // `--exclude-client` starts from the explicit `--client` set, or from
+    // every client when none was given; configured defaults are ignored so
+    // "everything except X" means exactly that.
+    if !flags.exclude_clients.is_empty() {
+        if ordered.is_empty() {
+            let all = ClientFilter::default_set();
+            ordered = ClientFilter::value_variants()
+                .iter()
+                .filter(|client| all.contains(client))
+                .map(|client| client.as_filter_str().to_string())
+                .collect();
+        }
+        let excluded: std::collections::HashSet<&str> = flags
+            .exclude_clients
+            .iter()
+            .map(|client| client.as_filter_str())
+            .collect();
+        ordered.retain(|id| !excluded.contains(id.as_str()));
+        return Some(ordered);
+    }
+
     // Defaults only apply when the user passed no canonical `--client` flags.
     // CLI flags always win — predictable semantics over "merge". Unknown /
     // typo'd ids are dropped silently so a stale settings.json entry never
@@ -1374,6 +2635,38 @@ fn cursor_setup_warnings_for_report(
     )]
 }
 
+/// Footer line for [`tokscale_core::ModelReport::total_estimated_savings`],
+/// `None` when caching saved nothing (or pricing was skipped).
+fn cache_savings_line(savings: f64) -> Option<String> {
+    (savings > 0.0).then(|| {
+        format!(
+            "Cache reads saved an estimated {} over input pricing",
+            format_currency(savings)
+        )
+    })
+}
+
+/// "3 models had no pricing data: a, b, c" for a report's
+/// `unpriced_models`, `None` when every model with usage was priced.
+fn unpriced_models_warning(models: &[String]) -> Option<String> {
+    match models.len() {
+        0 => None,
+        1 => Some(format!("1 model had no pricing data: {}", models[0])),
+        n => Some(format!(
+            "{} models had no pricing data: {}",
+            n,
+            models.join(", ")
+        )),
+    }
+}
+
+fn print_unpriced_models_warning(models: &[String]) {
+    if let Some(warning) = unpriced_models_warning(models) {
+        use colored::Colorize;
+        println!("{}", format!("  {}", warning).yellow());
+    }
+}
+
 fn emit_cursor_setup_warnings(warnings: &[String]) {
     if warnings.is_empty() {
         return;
@@ -1634,7 +2927,128 @@ fn build_date_filter_for_date(
         );
     }
 
-    (date.since.clone(), date.until.clone())
+    (
+        date.since
+            .as_deref()
+            .map(|raw| resolve_date_bound(raw, current_date)),
+        date.until
+            .as_deref()
+            .map(|raw| resolve_date_bound(raw, current_date)),
+    )
+}
+
+/// Parse a relative `--since`/`--until` value such as `7d`, `12w`, `3m`, or
+/// `1y` into the date that many days/weeks/months/years before `today`.
+///
+/// `0d` is `today`. Month and year steps clamp to the last day of the target
+/// month, so Mar 31 minus `1m` is Feb 28 (or 29). Returns `Ok(None)` for
+/// values that are not relative, such as an absolute `YYYY-MM-DD`.
+fn parse_relative_date(
+    raw: &str,
+    today: chrono::NaiveDate,
+) -> Result<Option<chrono::NaiveDate>, String> {
+    use chrono::{Days, Months};
+
+    let raw = raw.trim();
+    let Some(unit) = raw.chars().last() else {
+        return Ok(None);
+    };
+    let amount = &raw[..raw.len() - unit.len_utf8()];
+    if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return Ok(None);
+    }
+
+    let out_of_range = || format!("Relative date '{}' is out of range", raw);
+    let amount: u32 = amount.parse().map_err(|_| out_of_range())?;
+    let date = match unit.to_ascii_lowercase() {
+        'd' => today.checked_sub_days(Days::new(u64::from(amount))),
+        'w' => today.checked_sub_days(Days::new(u64::from(amount) * 7)),
+        'm' => today.checked_sub_months(Months::new(amount)),
+        'y' => amount
+            .checked_mul(12)
+            .and_then(|months| today.checked_sub_months(Months::new(months))),
+        _ => return Ok(None),
+    };
+    date.map(Some).ok_or_else(out_of_range)
+}
+
+/// Expand a relative date bound to `YYYY-MM-DD`; anything else is returned
+/// unchanged.
+fn resolve_date_bound(raw: &str, today: chrono::NaiveDate) -> String {
+    match parse_relative_date(raw, today) {
+        Ok(Some(date)) => date.format("%Y-%m-%d").to_string(),
+        _ => raw.to_string(),
+    }
+}
+
+/// Clap parser for `--since`/`--until`: rejects relative values that cannot
+/// be resolved, but keeps the raw text so it is expanded against the current
+/// day when the report runs.
+fn parse_date_bound_arg(raw: &str) -> Result<String, String> {
+    parse_relative_date(raw, chrono::Local::now().date_naive())?;
+    Ok(raw.to_string())
+}
+
+fn parse_locale_arg(raw: &str) -> Result<locale::NumberLocale, String> {
+    locale::NumberLocale::from_tag(raw).ok_or_else(|| {
+        format!(
+            "unsupported locale '{raw}', expected one of: {}",
+            locale::SUPPORTED.join(", ")
+        )
+    })
+}
+
+fn parse_non_negative_pct(raw: &str) -> Result<f64, String> {
+    raw.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|pct| pct.is_finite() && *pct >= 0.0)
+        .ok_or_else(|| format!("invalid percent '{raw}', expected a number >= 0"))
+}
+
+/// Clap parser for `--min-cost`: a NaN, infinite or negative amount can't
+/// hide anything meaningfully.
+fn parse_min_cost_arg(raw: &str) -> Result<f64, String> {
+    raw.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|cost| cost.is_finite() && *cost >= 0.0)
+        .ok_or_else(|| format!("invalid amount '{raw}', expected a non-negative number"))
+}
+
+/// Clap parser for `--budget`: a budget of zero or less can't be tracked
+/// against.
+fn parse_positive_amount_arg(raw: &str) -> Result<f64, String> {
+    raw.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|amount| amount.is_finite() && *amount > 0.0)
+        .ok_or_else(|| format!("invalid amount '{raw}', expected a positive number"))
+}
+
+fn parse_pricing_date_arg(raw: &str) -> Result<chrono::NaiveDate, String> {
+    chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date '{raw}', expected YYYY-MM-DD"))
+}
+
+/// The TUI and `report` only understand inclusive bounds, so an exclusive
+/// `--until` is handed to them as the day before.
+fn build_tui_date_filter(date: &DateRangeFlags) -> (Option<String>, Option<String>) {
+    let (since, until) = build_date_filter(date);
+    (since, inclusive_until(until, date.until_exclusive))
+}
+
+fn inclusive_until(until: Option<String>, exclusive: bool) -> Option<String> {
+    if !exclusive {
+        return until;
+    }
+    until.map(|raw| {
+        chrono::NaiveDate::parse_from_str(&raw, "%Y-%m-%d")
+            .ok()
+            .and_then(|day| day.pred_opt())
+            .map(|day| day.format("%Y-%m-%d").to_string())
+            .unwrap_or(raw)
+    })
 }
 
 fn normalize_year_filter(date: &DateRangeFlags) -> Option<String> {
@@ -1670,10 +3084,10 @@ fn get_date_range_label_for_date(
     }
     let mut parts = Vec::new();
     if let Some(s) = &date.since {
-        parts.push(format!("from {}", s));
+        parts.push(format!("from {}", resolve_date_bound(s, current_date)));
     }
     if let Some(u) = &date.until {
-        parts.push(format!("to {}", u));
+        parts.push(format!("to {}", resolve_date_bound(u, current_date)));
     }
     if parts.is_empty() {
         None
@@ -1687,7 +3101,30 @@ struct LightSpinner {
     handle: Option<JoinHandle<()>>,
 }
 
-const TABLE_PRESET: &str = "││──├─┼┤│─┼├┤┬┴┌┐└┘";
+/// `comfy_table` preset for the selected `--table-style`.
+fn table_preset() -> &'static str {
+    table_style::current().preset()
+}
+
+/// Column order of the wide (non-compact) `client,model` /
+/// `client,provider,model` models table. Reasoning sits next to the other
+/// token buckets and is included in `Total`, so thinking-model spend isn't
+/// hidden behind the input/output columns.
+const CLIENT_MODEL_WIDE_HEADERS: [&str; 13] = [
+    "Client",
+    "Provider",
+    "Model",
+    "Resolved",
+    "Input",
+    "Output",
+    "Cache Write",
+    "Cache Read",
+    "Reasoning",
+    "Total",
+    "ms/1K",
+    "Cost",
+    "Cost/1M",
+];
 
 impl LightSpinner {
     const WIDTH: usize = 8;
@@ -1699,7 +3136,20 @@ impl LightSpinner {
     const FRAME_MS: u64 = 40;
 
     fn start(message: &'static str) -> Self {
+        let style = SpinnerStyle::detect(
+            io::stderr().is_terminal(),
+            std::env::var_os("NO_COLOR").is_some(),
+        );
         let running = Arc::new(AtomicBool::new(true));
+        if style == SpinnerStyle::Hidden {
+            // Redirected stderr would only collect `\r` redraws and cursor
+            // escapes, so stay silent and let the caller's output speak.
+            return Self {
+                running,
+                handle: None,
+            };
+        }
+
         let running_thread = Arc::clone(&running);
         let message = message.to_string();
 
@@ -1711,7 +3161,10 @@ impl LightSpinner {
             let _ = stderr.flush();
 
             while running_thread.load(Ordering::Relaxed) {
-                let spinner = Self::frame(frame);
+                let spinner = match style {
+                    SpinnerStyle::Color => Self::frame(frame),
+                    _ => Self::frame_plain(frame),
+                };
                 let _ = write!(stderr, "\r\x1b[K  {} {}", spinner, message);
                 let _ = stderr.flush();
                 frame = frame.wrapping_add(1);
@@ -1740,11 +3193,38 @@ impl LightSpinner {
     }
 
     fn frame(frame: usize) -> String {
-        let (position, forward) = Self::scanner_state(frame);
-        let mut out = String::new();
+        Self::trail_distances(frame)
+            .map(|distance| {
+                if distance < Self::TRAIL_LENGTH {
+                    let color = Self::TRAIL_COLORS[distance.min(Self::TRAIL_COLORS.len() - 1)];
+                    format!("\x1b[38;5;{}m■\x1b[0m", color)
+                } else {
+                    format!("\x1b[38;5;{}m⬝\x1b[0m", Self::INACTIVE_COLOR)
+                }
+            })
+            .collect()
+    }
+
+    /// Same scanner animation as [`Self::frame`] without color escapes, for
+    /// `NO_COLOR` terminals.
+    fn frame_plain(frame: usize) -> String {
+        Self::trail_distances(frame)
+            .map(|distance| {
+                if distance < Self::TRAIL_LENGTH {
+                    '■'
+                } else {
+                    '⬝'
+                }
+            })
+            .collect()
+    }
 
-        for i in 0..Self::WIDTH {
-            let distance = if forward {
+    /// Distance of each cell behind the scanner head, `usize::MAX` for cells
+    /// the head has not passed yet.
+    fn trail_distances(frame: usize) -> impl Iterator<Item = usize> {
+        let (position, forward) = Self::scanner_state(frame);
+        (0..Self::WIDTH).map(move |i| {
+            if forward {
                 if position >= i {
                     position - i
                 } else {
@@ -1754,17 +3234,8 @@ impl LightSpinner {
                 i - position
             } else {
                 usize::MAX
-            };
-
-            if distance < Self::TRAIL_LENGTH {
-                let color = Self::TRAIL_COLORS[distance.min(Self::TRAIL_COLORS.len() - 1)];
-                out.push_str(&format!("\x1b[38;5;{}m■\x1b[0m", color));
-            } else {
-                out.push_str(&format!("\x1b[38;5;{}m⬝\x1b[0m", Self::INACTIVE_COLOR));
             }
-        }
-
-        out
+        })
     }
 
     fn scanner_state(frame: usize) -> (usize, bool) {
@@ -1788,29 +3259,297 @@ impl LightSpinner {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpinnerStyle {
+    Color,
+    Plain,
+    Hidden,
+}
+
+impl SpinnerStyle {
+    fn detect(stderr_is_terminal: bool, no_color: bool) -> Self {
+        if !stderr_is_terminal {
+            Self::Hidden
+        } else if no_color {
+            Self::Plain
+        } else {
+            Self::Color
+        }
+    }
+}
+
 impl Drop for LightSpinner {
     fn drop(&mut self) {
         self.stop_inner();
     }
 }
 
+/// How `--json` output is serialized: pretty by default, one line with
+/// `--json-compact`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum JsonStyle {
+    #[default]
+    Pretty,
+    Compact,
+}
+
+impl JsonStyle {
+    fn from_compact_flag(compact: bool) -> Self {
+        if compact {
+            Self::Compact
+        } else {
+            Self::Pretty
+        }
+    }
+
+    fn render<T: serde::Serialize + ?Sized>(self, value: &T) -> serde_json::Result<String> {
+        match self {
+            Self::Pretty => serde_json::to_string_pretty(value),
+            Self::Compact => serde_json::to_string(value),
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
-fn run_models_report(
+fn run_hour_of_day_report(
     json: bool,
+    json_style: JsonStyle,
     home_dir: Option<String>,
     clients: Option<Vec<String>>,
+    agent_filter: tokscale_core::AgentFilter,
+    project_filter: tokscale_core::ProjectFilter,
+    model_filter: tokscale_core::ModelFilter,
     date: &DateRangeFlags,
+    no_spinner: bool,
+) -> Result<()> {
+    use comfy_table::{Attribute, Cell, CellAlignment, Color, Table};
+    use tokio::runtime::Runtime;
+    use tokscale_core::{get_hour_of_day_report, ReportOptions};
+
+    let (since, until) = build_date_filter(date);
+    let year = normalize_year_filter(date);
+    let date_range = get_date_range_label(date);
+
+    let had_cursor_cache = has_cursor_usage_cache_for_report(&home_dir);
+    let explicit_cursor_filter = client_filter_explicitly_requests_cursor(&clients);
+    let spinner = if no_spinner {
+        None
+    } else {
+        Some(LightSpinner::start("Scanning session data..."))
+    };
+    let cursor_sync_result = auto_sync_cursor_for_local_report(&home_dir, &clients);
+    let cursor_setup_warnings = setup_warnings_for_report(&home_dir, &clients);
+    let use_env_roots = use_env_roots(&home_dir);
+    let rt = Runtime::new()?;
+    let report = rt
+        .block_on(async {
+            get_hour_of_day_report(ReportOptions {
+                home_dir: home_dir.clone(),
+                use_env_roots,
+                clients,
+                since,
+                until,
+                until_exclusive: date.until_exclusive,
+                year,
+                scanner_settings: tui::settings::load_scanner_settings_for_home(&home_dir),
+                agent_filter,
+                project_filter,
+                model_filter,
+                ..parse_flags::report_options()
+            })
+            .await
+        })
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    if let Some(spinner) = spinner {
+        spinner.stop();
+    }
+    emit_cursor_sync_warning(
+        cursor_sync_result.as_ref(),
+        had_cursor_cache,
+        explicit_cursor_filter,
+    );
+
+    if json {
+        #[derive(serde::Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct HourOfDayReportJson {
+            entries: Vec<tokscale_core::HourOfDayUsage>,
+            total_cost: f64,
+            untimed_messages: i32,
+            processing_time_ms: u32,
+            #[serde(skip_serializing_if = "Vec::is_empty")]
+            warnings: Vec<String>,
+        }
+
+        let output = HourOfDayReportJson {
+            entries: report.entries,
+            total_cost: report.total_cost,
+            untimed_messages: report.untimed_messages,
+            processing_time_ms: report.processing_time_ms,
+            warnings: cursor_setup_warnings,
+        };
+        if currency::current().is_usd() {
+            println!("{}", json_style.render(&output)?);
+        } else {
+            let mut output = serde_json::to_value(&output)?;
+            currency::current().annotate_json(&mut output);
+            println!("{}", json_style.render(&output)?);
+        }
+        return Ok(());
+    }
+
+    use colored::Colorize;
+
+    emit_cursor_setup_warnings(&cursor_setup_warnings);
+    let peak_cost = report
+        .entries
+        .iter()
+        .map(|e| e.cost)
+        .fold(0.0_f64, f64::max);
+
+    let mut table = Table::new();
+    table.load_preset(table_preset());
+    table.enforce_styling();
+    table.set_header(vec![
+        Cell::new("Hour").fg(Color::Cyan),
+        Cell::new("Msgs").fg(Color::Cyan),
+        Cell::new("Input").fg(Color::Cyan),
+        Cell::new("Output").fg(Color::Cyan),
+        Cell::new("Cache R").fg(Color::Cyan),
+        Cell::new("Cache W").fg(Color::Cyan),
+        Cell::new("Reasoning").fg(Color::Cyan),
+        Cell::new("Total").fg(Color::Cyan),
+        Cell::new("Cost").fg(Color::Cyan),
+    ]);
+    for entry in &report.entries {
+        let total = saturating_token_total(
+            entry.input,
+            entry.output,
+            entry.cache_read,
+            entry.cache_write,
+        )
+        .saturating_add(entry.reasoning);
+        let cost_cell = Cell::new(format_currency(entry.cost)).set_alignment(CellAlignment::Right);
+        table.add_row(vec![
+            Cell::new(format!("{:02}:00", entry.hour)),
+            Cell::new(entry.message_count).set_alignment(CellAlignment::Right),
+            Cell::new(format_tokens_with_commas(entry.input)).set_alignment(CellAlignment::Right),
+            Cell::new(format_tokens_with_commas(entry.output)).set_alignment(CellAlignment::Right),
+            Cell::new(format_tokens_with_commas(entry.cache_read))
+                .set_alignment(CellAlignment::Right),
+            Cell::new(format_tokens_with_commas(entry.cache_write))
+                .set_alignment(CellAlignment::Right),
+            Cell::new(format_tokens_with_commas(entry.reasoning))
+                .set_alignment(CellAlignment::Right),
+            Cell::new(format_tokens_with_commas(total)).set_alignment(CellAlignment::Right),
+            if peak_cost > 0.0 && entry.cost == peak_cost {
+                cost_cell.fg(Color::Yellow).add_attribute(Attribute::Bold)
+            } else {
+                cost_cell
+            },
+        ]);
+    }
+
+    let title = match &date_range {
+        Some(range) => format!("Token Usage by Hour of Day ({})", range),
+        None => "Token Usage by Hour of Day".to_string(),
+    };
+    println!("\n  \x1b[36m{}\x1b[0m\n", title);
+    println!("{}", dim_borders(&table.to_string()));
+    println!(
+        "\x1b[90m\n  Total Cost: \x1b[32m{}\x1b[90m\x1b[0m",
+        format_currency(report.total_cost)
+    );
+    if report.untimed_messages > 0 {
+        println!(
+            "  {}",
+            format!(
+                "{} messages without a timestamp are not included.",
+                report.untimed_messages
+            )
+            .bright_black()
+        );
+    }
+    println!();
+    Ok(())
+}
+
+/// How `run_models_report` presents a report: the output format and the
+/// display-only flags that don't change which usage is counted.
+#[derive(Default)]
+struct ModelsOutputOptions {
+    json: bool,
+    json_style: JsonStyle,
+    anonymize: bool,
+    markdown: bool,
+    html: Option<html::HtmlOutput>,
+    warn_duplicates: bool,
     benchmark: bool,
+    benchmark_detailed: bool,
     no_spinner: bool,
+    hide_zero: bool,
+    include_zero_cost: bool,
+    show_sessions: bool,
+    show_dates: bool,
+    show_source: bool,
+    show_bytes: bool,
+    compare_estimate: bool,
+    include_total_row: bool,
+    summary_only: bool,
+    sparkline: bool,
+    env_output: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_models_report(
+    output: ModelsOutputOptions,
+    no_pricing: bool,
+    home_dir: Option<String>,
+    clients: Option<Vec<String>>,
+    agent_filter: tokscale_core::AgentFilter,
+    project_filter: tokscale_core::ProjectFilter,
+    model_filter: tokscale_core::ModelFilter,
+    date: &DateRangeFlags,
     group_by: tokscale_core::GroupBy,
+    model_order: tokscale_core::ModelOrder,
     cli_write_cache: bool,
     cli_no_write_cache: bool,
-    hide_zero: bool,
-) -> Result<()> {
+    min_cost: Option<f64>,
+    min_tokens: Option<i64>,
+) -> Result<bool> {
     use std::time::Instant;
     use tokio::runtime::Runtime;
     use tokscale_core::{get_model_report, GroupBy, ReportOptions};
 
+    let ModelsOutputOptions {
+        json,
+        json_style,
+        anonymize,
+        markdown,
+        html,
+        warn_duplicates,
+        benchmark,
+        benchmark_detailed,
+        no_spinner,
+        hide_zero,
+        include_zero_cost,
+        show_sessions,
+        show_dates,
+        show_source,
+        show_bytes,
+        compare_estimate,
+        include_total_row,
+        summary_only,
+        sparkline,
+        env_output,
+    } = output;
+
+    let sparkline = sparkline && !json && std::io::stdout().is_terminal();
+
+    let costs = CostDisplay {
+        unpriced: no_pricing,
+    };
     let (since, until) = build_date_filter(date);
     let year = normalize_year_filter(date);
     let date_range = get_date_range_label(date);
@@ -1836,9 +3575,21 @@ fn run_models_report(
                 clients: clients.clone(),
                 since: since.clone(),
                 until: until.clone(),
+                until_exclusive: date.until_exclusive,
                 year: year.clone(),
                 group_by: group_by.clone(),
                 scanner_settings: tui::settings::load_scanner_settings_for_home(&home_dir),
+                min_cost,
+                min_tokens,
+                agent_filter,
+                project_filter,
+                model_filter,
+                client_timings: benchmark_detailed || warn_duplicates,
+                daily_costs: sparkline,
+                model_order,
+                no_pricing,
+                include_zero_cost,
+                ..parse_flags::report_options()
             })
             .await
         })
@@ -1867,6 +3618,21 @@ fn run_models_report(
         had_cursor_cache,
         explicit_cursor_filter,
     );
+    // Rows a client priced itself still have their cost, so only fail when
+    // the missing pricing actually zeroed part of the report.
+    if report.pricing_unavailable && !report.unpriced_models.is_empty() {
+        return Err(exit_code::Failure::Network(format!(
+            "Could not load pricing data for {}. Rerun online, or pass --no-pricing for tokens only.",
+            report.unpriced_models.join(", ")
+        ))
+        .into());
+    }
+    if warn_duplicates {
+        eprint!(
+            "{}",
+            duplicate_summary(report.client_timings.as_deref().unwrap_or_default())
+        );
+    }
     let processing_time_ms = start.elapsed().as_millis();
     let claude_message_count = report
         .entries
@@ -1874,6 +3640,7 @@ fn run_models_report(
         .filter(|entry| model_usage_includes_client(entry, "claude"))
         .map(|entry| entry.message_count)
         .sum();
+    let is_empty = report.total_messages == 0;
     let diagnostics = effective_home_dir
         .as_deref()
         .map(|home| {
@@ -1885,7 +3652,24 @@ fn run_models_report(
         })
         .unwrap_or_default();
 
-    if json {
+    if env_output {
+        print!(
+            "{}",
+            model_report_env(&report, currency::current(), currency::precision())
+        );
+    } else if let Some(html) = html {
+        emit_client_diagnostics(&diagnostics);
+        emit_cursor_setup_warnings(&cursor_setup_warnings);
+        let mut report = report;
+        if let Some(omitted) = report.omitted.take() {
+            report.entries.push(below_threshold_model_usage(&omitted));
+        }
+        html.emit(&html::models_document(
+            &report,
+            &group_by.to_string(),
+            date_range.as_deref(),
+        ))?;
+    } else if json {
         #[derive(serde::Serialize)]
         #[serde(rename_all = "camelCase")]
         struct ModelUsageJson {
@@ -1899,97 +3683,218 @@ fn run_models_report(
             session_id: Option<String>,
             model: String,
             provider: String,
+            /// Pre-normalization provider ids; only present when they differ
+            /// from `provider`.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            raw_provider: Option<String>,
             input: i64,
             output: i64,
             cache_read: i64,
             cache_write: i64,
             reasoning: i64,
             message_count: i32,
+            session_count: i32,
+            first_date: String,
+            last_date: String,
             cost: f64,
+            cost_pct: f64,
+            tokens_pct: f64,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pricing_source: Option<String>,
+            /// `--compare-estimate` only.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            client_cost: Option<f64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            estimated_cost: Option<f64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            estimate_diff_pct: Option<f64>,
+            estimated_savings: f64,
+            /// Only for rows whose client logged request/response sizes.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            request_bytes: Option<i64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            response_bytes: Option<i64>,
             performance: tokscale_core::ModelPerformance,
+            #[serde(skip_serializing_if = "std::ops::Not::not")]
+            is_total: bool,
         }
 
         #[derive(serde::Serialize)]
         #[serde(rename_all = "camelCase")]
         struct ModelReportJson {
             group_by: String,
-            entries: Vec<ModelUsageJson>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            entries: Option<Vec<ModelUsageJson>>,
             total_input: i64,
             total_output: i64,
             total_cache_read: i64,
             total_cache_write: i64,
+            total_reasoning: i64,
             total_messages: i32,
+            total_sessions: i32,
             total_cost: f64,
+            total_estimated_savings: f64,
             processing_time_ms: u32,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            omitted: Option<tokscale_core::OmittedUsage>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            timings: Option<Vec<tokscale_core::ClientTiming>>,
             #[serde(skip_serializing_if = "Vec::is_empty")]
             warnings: Vec<String>,
             #[serde(skip_serializing_if = "Vec::is_empty")]
             diagnostics: Vec<claude_diagnostics::ClientDiagnostic>,
         }
 
+        let report_tokens = model_report_total_tokens(&report);
+        let to_json = |e: tokscale_core::ModelUsage, is_total: bool| ModelUsageJson {
+            // Read before the fields below move out of `e`.
+            client_cost: compared_cost(&e, compare_estimate).map(|c| c.client_cost),
+            estimated_cost: compared_cost(&e, compare_estimate).map(|c| c.estimated_cost),
+            estimate_diff_pct: compared_cost(&e, compare_estimate)
+                .and_then(|c| c.percent_difference()),
+            cost_pct: percent_of_total(e.cost, report.total_cost),
+            tokens_pct: percent_of_total(model_entry_total_tokens(&e) as f64, report_tokens as f64),
+            workspace_key: if matches!(group_by, GroupBy::WorkspaceModel | GroupBy::Project) {
+                Some(
+                    e.workspace_key
+                        .map(serde_json::Value::String)
+                        .unwrap_or(serde_json::Value::Null),
+                )
+            } else {
+                None
+            },
+            workspace_label: if matches!(group_by, GroupBy::WorkspaceModel | GroupBy::Project) {
+                e.workspace_label
+            } else {
+                None
+            },
+            session_id: if matches!(
+                group_by,
+                GroupBy::Session | GroupBy::SessionModel | GroupBy::ClientSessionModel
+            ) {
+                e.session_id
+            } else {
+                None
+            },
+            client: e.client,
+            merged_clients: e.merged_clients,
+            raw_provider: (e.raw_provider != e.provider).then_some(e.raw_provider),
+            model: e.model,
+            provider: e.provider,
+            input: e.input,
+            output: e.output,
+            cache_read: e.cache_read,
+            cache_write: e.cache_write,
+            reasoning: e.reasoning,
+            message_count: e.message_count,
+            session_count: e.session_count,
+            first_date: e.first_date,
+            last_date: e.last_date,
+            cost: e.cost,
+            pricing_source: e.pricing_source,
+            estimated_savings: e.estimated_savings,
+            request_bytes: e.request_bytes,
+            response_bytes: e.response_bytes,
+            performance: e.performance,
+            is_total,
+        };
+        // Built before `entries` is consumed; it is appended after every
+        // real entry so slicing the entries never drops it.
+        let total_row = include_total_row.then(|| total_model_usage(&report));
         let output = ModelReportJson {
             group_by: group_by.to_string(),
-            entries: report
-                .entries
-                .into_iter()
-                .map(|e| ModelUsageJson {
-                    workspace_key: if group_by == GroupBy::WorkspaceModel {
-                        Some(
-                            e.workspace_key
-                                .map(serde_json::Value::String)
-                                .unwrap_or(serde_json::Value::Null),
-                        )
-                    } else {
-                        None
-                    },
-                    workspace_label: if group_by == GroupBy::WorkspaceModel {
-                        e.workspace_label
-                    } else {
-                        None
-                    },
-                    session_id: if matches!(group_by, GroupBy::Session | GroupBy::ClientSession) {
-                        e.session_id
-                    } else {
-                        None
-                    },
-                    client: e.client,
-                    merged_clients: e.merged_clients,
-                    model: e.model,
-                    provider: e.provider,
-                    input: e.input,
-                    output: e.output,
-                    cache_read: e.cache_read,
-                    cache_write: e.cache_write,
-                    reasoning: e.reasoning,
-                    message_count: e.message_count,
-                    cost: e.cost,
-                    performance: e.performance,
-                })
-                .collect(),
+            entries: (!summary_only).then(|| {
+                report
+                    .entries
+                    .into_iter()
+                    .map(|e| to_json(e, false))
+                    .chain(total_row.map(|e| to_json(e, true)))
+                    .collect()
+            }),
             total_input: report.total_input,
             total_output: report.total_output,
             total_cache_read: report.total_cache_read,
             total_cache_write: report.total_cache_write,
+            total_reasoning: report.total_reasoning,
             total_messages: report.total_messages,
+            total_sessions: report.total_sessions,
             total_cost: report.total_cost,
+            total_estimated_savings: report.total_estimated_savings,
             processing_time_ms: report.processing_time_ms,
-            warnings: cursor_setup_warnings,
+            omitted: report.omitted,
+            timings: report.client_timings,
+            warnings: cursor_setup_warnings
+                .into_iter()
+                .chain(unpriced_models_warning(&report.unpriced_models))
+                .collect(),
             diagnostics,
         };
-        println!("{}", serde_json::to_string_pretty(&output)?);
+        let display_currency = currency::current();
+        if anonymize || !display_currency.is_usd() {
+            let mut output = serde_json::to_value(&output)?;
+            display_currency.annotate_json(&mut output);
+            if anonymize {
+                anonymize::anonymize(&mut output);
+            }
+            println!("{}", json_style.render(&output)?);
+        } else {
+            println!("{}", json_style.render(&output)?);
+        }
+    } else if summary_only {
+        emit_client_diagnostics(&diagnostics);
+        emit_cursor_setup_warnings(&cursor_setup_warnings);
+        print_summary_only(
+            "Token Usage Summary",
+            date_range.as_deref(),
+            i64::from(report.total_messages),
+            (
+                report.total_input,
+                report.total_output,
+                report.total_cache_read,
+                report.total_cache_write,
+            ),
+            report.total_reasoning,
+            report.total_cost,
+            costs,
+        );
+        print_cost_sparkline(report.daily_costs.as_deref());
+        if benchmark || benchmark_detailed {
+            use colored::Colorize;
+            println!(
+                "{}",
+                format!("  Processing time: {}ms (Rust native)", processing_time_ms).bright_black()
+            );
+        }
+        if let Some(timings) = report
+            .client_timings
+            .as_ref()
+            .filter(|_| benchmark_detailed)
+        {
+            print_client_timings(timings);
+        }
     } else {
         use comfy_table::{Attribute, Cell, CellAlignment, Color, ContentArrangement, Table};
         emit_client_diagnostics(&diagnostics);
 
         emit_cursor_setup_warnings(&cursor_setup_warnings);
-        let total_performance = aggregate_model_report_performance(&report.entries);
         let term_width = crossterm::terminal::size()
             .map(|(w, _)| w as usize)
             .unwrap_or(120);
-        let compact = term_width < 100;
+        // Markdown isn't bound by the terminal width, so it always gets the
+        // wide layout.
+        let compact = !markdown && term_width < 100;
+
+        // Every layout summarizes the threshold-dropped rows as one
+        // synthetic entry. Pushing it into `entries` keeps the appended
+        // columns row-aligned.
+        let mut report = report;
+        if let Some(omitted) = report.omitted.take() {
+            report.entries.push(below_threshold_model_usage(&omitted));
+        }
+        let total_performance = aggregate_model_report_performance(&report.entries);
 
         let mut table = Table::new();
-        table.load_preset(TABLE_PRESET);
+        table.load_preset(table_preset());
         let arrangement = if std::io::stdout().is_terminal() {
             ContentArrangement::DynamicFullWidth
         } else {
@@ -1999,14 +3904,19 @@ fn run_models_report(
         table.enforce_styling();
 
         let workspace_name = |label: Option<&str>| label.unwrap_or("Unknown workspace").to_string();
+        let model_header = if group_by == GroupBy::Family {
+            "Family"
+        } else {
+            "Model"
+        };
 
         if compact {
             match group_by {
-                GroupBy::Model => {
+                GroupBy::Model | GroupBy::Family => {
                     table.set_header(vec![
                         Cell::new("Clients").fg(Color::Cyan),
                         Cell::new("Providers").fg(Color::Cyan),
-                        Cell::new("Model").fg(Color::Cyan),
+                        Cell::new(model_header).fg(Color::Cyan),
                         Cell::new("Input").fg(Color::Cyan),
                         Cell::new("Output").fg(Color::Cyan),
                         Cell::new("ms/1K").fg(Color::Cyan),
@@ -2021,12 +3931,7 @@ fn run_models_report(
                             .map(capitalize_client)
                             .collect::<Vec<_>>()
                             .join(", ");
-                        let total_tokens = saturating_token_total(
-                            entry.input,
-                            entry.output,
-                            entry.cache_read,
-                            entry.cache_write,
-                        );
+                        let total_tokens = model_entry_total_tokens(entry);
                         table.add_row(vec![
                             Cell::new(capitalized_clients),
                             Cell::new(crate::tui::ui::widgets::get_provider_display_name(
@@ -2040,19 +3945,14 @@ fn run_models_report(
                                 .set_alignment(CellAlignment::Right),
                             Cell::new(format_ms_per_1k(entry.performance.ms_per_1k_tokens))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_currency(entry.cost))
+                            Cell::new(costs.currency(entry.cost))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_cost_per_million(entry.cost, total_tokens))
+                            Cell::new(costs.per_million(entry.cost, total_tokens))
                                 .set_alignment(CellAlignment::Right),
                         ]);
                     }
 
-                    let total_tokens = saturating_token_total(
-                        report.total_input,
-                        report.total_output,
-                        report.total_cache_read,
-                        report.total_cache_write,
-                    );
+                    let total_tokens = model_report_total_tokens(&report);
                     table.add_row(vec![
                         Cell::new("Total")
                             .fg(Color::Yellow)
@@ -2068,10 +3968,10 @@ fn run_models_report(
                         Cell::new(format_ms_per_1k(total_performance.ms_per_1k_tokens))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_currency(report.total_cost))
+                        Cell::new(costs.currency(report.total_cost))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_cost_per_million(report.total_cost, total_tokens))
+                        Cell::new(costs.per_million(report.total_cost, total_tokens))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
                     ]);
@@ -2089,12 +3989,7 @@ fn run_models_report(
                     ]);
 
                     for entry in &report.entries {
-                        let total_tokens = saturating_token_total(
-                            entry.input,
-                            entry.output,
-                            entry.cache_read,
-                            entry.cache_write,
-                        );
+                        let total_tokens = model_entry_total_tokens(entry);
                         table.add_row(vec![
                             Cell::new(capitalize_client(&entry.client)),
                             Cell::new(crate::tui::ui::widgets::get_provider_display_name(
@@ -2108,19 +4003,14 @@ fn run_models_report(
                                 .set_alignment(CellAlignment::Right),
                             Cell::new(format_ms_per_1k(entry.performance.ms_per_1k_tokens))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_currency(entry.cost))
+                            Cell::new(costs.currency(entry.cost))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_cost_per_million(entry.cost, total_tokens))
+                            Cell::new(costs.per_million(entry.cost, total_tokens))
                                 .set_alignment(CellAlignment::Right),
                         ]);
                     }
 
-                    let total_tokens = saturating_token_total(
-                        report.total_input,
-                        report.total_output,
-                        report.total_cache_read,
-                        report.total_cache_write,
-                    );
+                    let total_tokens = model_report_total_tokens(&report);
                     table.add_row(vec![
                         Cell::new("Total")
                             .fg(Color::Yellow)
@@ -2136,16 +4026,56 @@ fn run_models_report(
                         Cell::new(format_ms_per_1k(total_performance.ms_per_1k_tokens))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_currency(report.total_cost))
+                        Cell::new(costs.currency(report.total_cost))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(costs.per_million(report.total_cost, total_tokens))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                    ]);
+                }
+                GroupBy::Session => {
+                    table.set_header(vec![
+                        Cell::new("Client").fg(Color::Cyan),
+                        Cell::new("Session").fg(Color::Cyan),
+                        Cell::new("Started").fg(Color::Cyan),
+                        Cell::new("Model").fg(Color::Cyan),
+                        Cell::new("Total").fg(Color::Cyan),
+                        Cell::new("Cost").fg(Color::Cyan),
+                    ]);
+
+                    for entry in &report.entries {
+                        let total = model_entry_total_tokens(entry);
+                        table.add_row(vec![
+                            Cell::new(capitalize_client(&entry.client)),
+                            Cell::new(entry.session_id.as_deref().unwrap_or("(unknown)")),
+                            Cell::new(&entry.first_date),
+                            Cell::new(&entry.model),
+                            Cell::new(format_tokens_with_commas(total))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(costs.currency(entry.cost))
+                                .set_alignment(CellAlignment::Right),
+                        ]);
+                    }
+
+                    let total_all = model_report_total_tokens(&report);
+                    table.add_row(vec![
+                        Cell::new("Total")
+                            .fg(Color::Yellow)
+                            .add_attribute(Attribute::Bold),
+                        Cell::new(""),
+                        Cell::new(""),
+                        Cell::new(""),
+                        Cell::new(format_tokens_with_commas(total_all))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_cost_per_million(report.total_cost, total_tokens))
+                        Cell::new(costs.currency(report.total_cost))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
                     ]);
                 }
-                GroupBy::Session | GroupBy::ClientSession => {
-                    let show_client = group_by == GroupBy::ClientSession;
+                GroupBy::SessionModel | GroupBy::ClientSessionModel => {
+                    let show_client = group_by == GroupBy::ClientSessionModel;
                     let mut header = Vec::with_capacity(6);
                     if show_client {
                         header.push(Cell::new("Client").fg(Color::Cyan));
@@ -2159,12 +4089,7 @@ fn run_models_report(
                     table.set_header(header);
 
                     for entry in &report.entries {
-                        let total_tokens = saturating_token_total(
-                            entry.input,
-                            entry.output,
-                            entry.cache_read,
-                            entry.cache_write,
-                        );
+                        let total_tokens = model_entry_total_tokens(entry);
                         let session_label = entry
                             .session_id
                             .clone()
@@ -2178,18 +4103,13 @@ fn run_models_report(
                             Cell::new(&entry.model),
                             Cell::new(format_tokens_with_commas(total_tokens))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_currency(entry.cost))
+                            Cell::new(costs.currency(entry.cost))
                                 .set_alignment(CellAlignment::Right),
                         ]);
                         table.add_row(row);
                     }
 
-                    let total_all = saturating_token_total(
-                        report.total_input,
-                        report.total_output,
-                        report.total_cache_read,
-                        report.total_cache_write,
-                    );
+                    let total_all = model_report_total_tokens(&report);
                     let mut total_row = Vec::with_capacity(6);
                     if show_client {
                         total_row.push(
@@ -2212,12 +4132,55 @@ fn run_models_report(
                             .set_alignment(CellAlignment::Right),
                     );
                     total_row.push(
-                        Cell::new(format_currency(report.total_cost))
+                        Cell::new(costs.currency(report.total_cost))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
                     );
                     table.add_row(total_row);
                 }
+                GroupBy::Project => {
+                    table.set_header(vec![
+                        Cell::new("Project").fg(Color::Cyan),
+                        Cell::new("Sources").fg(Color::Cyan),
+                        Cell::new("Model").fg(Color::Cyan),
+                        Cell::new("Total").fg(Color::Cyan),
+                        Cell::new("Cost").fg(Color::Cyan),
+                    ]);
+
+                    for entry in &report.entries {
+                        let total = model_entry_total_tokens(entry);
+                        let clients_str = entry.merged_clients.as_deref().unwrap_or(&entry.client);
+                        let capitalized_clients = clients_str
+                            .split(", ")
+                            .map(capitalize_client)
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        table.add_row(vec![
+                            Cell::new(workspace_name(entry.workspace_label.as_deref())),
+                            Cell::new(capitalized_clients),
+                            Cell::new(&entry.model),
+                            Cell::new(format_tokens_with_commas(total))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(costs.currency(entry.cost))
+                                .set_alignment(CellAlignment::Right),
+                        ]);
+                    }
+
+                    let total_all = model_report_total_tokens(&report);
+                    table.add_row(vec![
+                        Cell::new("Total")
+                            .fg(Color::Yellow)
+                            .add_attribute(Attribute::Bold),
+                        Cell::new(""),
+                        Cell::new(""),
+                        Cell::new(format_tokens_with_commas(total_all))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(costs.currency(report.total_cost))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                    ]);
+                }
                 GroupBy::WorkspaceModel => {
                     table.set_header(vec![
                         Cell::new("Workspace").fg(Color::Cyan),
@@ -2232,7 +4195,7 @@ fn run_models_report(
                             Cell::new(&entry.model),
                             Cell::new(format_ms_per_1k(entry.performance.ms_per_1k_tokens))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_currency(entry.cost))
+                            Cell::new(costs.currency(entry.cost))
                                 .set_alignment(CellAlignment::Right),
                         ]);
                     }
@@ -2245,7 +4208,7 @@ fn run_models_report(
                         Cell::new(format_ms_per_1k(total_performance.ms_per_1k_tokens))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_currency(report.total_cost))
+                        Cell::new(costs.currency(report.total_cost))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
                     ]);
@@ -2253,11 +4216,11 @@ fn run_models_report(
             }
         } else {
             match group_by {
-                GroupBy::Model => {
+                GroupBy::Model | GroupBy::Family => {
                     table.set_header(vec![
                         Cell::new("Clients").fg(Color::Cyan),
                         Cell::new("Providers").fg(Color::Cyan),
-                        Cell::new("Model").fg(Color::Cyan),
+                        Cell::new(model_header).fg(Color::Cyan),
                         Cell::new("Input").fg(Color::Cyan),
                         Cell::new("Output").fg(Color::Cyan),
                         Cell::new("Cache Write").fg(Color::Cyan),
@@ -2269,12 +4232,7 @@ fn run_models_report(
                     ]);
 
                     for entry in &report.entries {
-                        let total = saturating_token_total(
-                            entry.input,
-                            entry.output,
-                            entry.cache_read,
-                            entry.cache_write,
-                        );
+                        let total = model_entry_total_tokens(entry);
 
                         let clients_str = entry.merged_clients.as_deref().unwrap_or(&entry.client);
                         let capitalized_clients = clients_str
@@ -2301,19 +4259,14 @@ fn run_models_report(
                                 .set_alignment(CellAlignment::Right),
                             Cell::new(format_ms_per_1k(entry.performance.ms_per_1k_tokens))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_currency(entry.cost))
+                            Cell::new(costs.currency(entry.cost))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_cost_per_million(entry.cost, total))
+                            Cell::new(costs.per_million(entry.cost, total))
                                 .set_alignment(CellAlignment::Right),
                         ]);
                     }
 
-                    let total_all = saturating_token_total(
-                        report.total_input,
-                        report.total_output,
-                        report.total_cache_read,
-                        report.total_cache_write,
-                    );
+                    let total_all = model_report_total_tokens(&report);
                     table.add_row(vec![
                         Cell::new("Total")
                             .fg(Color::Yellow)
@@ -2338,16 +4291,80 @@ fn run_models_report(
                         Cell::new(format_ms_per_1k(total_performance.ms_per_1k_tokens))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_currency(report.total_cost))
+                        Cell::new(costs.currency(report.total_cost))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(costs.per_million(report.total_cost, total_all))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_cost_per_million(report.total_cost, total_all))
+                    ]);
+                }
+                GroupBy::Session => {
+                    table.set_header(vec![
+                        Cell::new("Client").fg(Color::Cyan),
+                        Cell::new("Session").fg(Color::Cyan),
+                        Cell::new("Started").fg(Color::Cyan),
+                        Cell::new("Model").fg(Color::Cyan),
+                        Cell::new("Msgs").fg(Color::Cyan),
+                        Cell::new("Input").fg(Color::Cyan),
+                        Cell::new("Output").fg(Color::Cyan),
+                        Cell::new("Total").fg(Color::Cyan),
+                        Cell::new("Cost").fg(Color::Cyan),
+                        Cell::new("Cost/1M").fg(Color::Cyan),
+                    ]);
+
+                    for entry in &report.entries {
+                        let total = model_entry_total_tokens(entry);
+                        table.add_row(vec![
+                            Cell::new(capitalize_client(&entry.client)),
+                            Cell::new(entry.session_id.as_deref().unwrap_or("(unknown)")),
+                            Cell::new(&entry.first_date),
+                            Cell::new(&entry.model),
+                            Cell::new(format_tokens_with_commas(entry.message_count as i64))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(format_tokens_with_commas(entry.input))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(format_tokens_with_commas(entry.output))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(format_tokens_with_commas(total))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(costs.currency(entry.cost))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(costs.per_million(entry.cost, total))
+                                .set_alignment(CellAlignment::Right),
+                        ]);
+                    }
+
+                    let total_all = model_report_total_tokens(&report);
+                    table.add_row(vec![
+                        Cell::new("Total")
+                            .fg(Color::Yellow)
+                            .add_attribute(Attribute::Bold),
+                        Cell::new(""),
+                        Cell::new(""),
+                        Cell::new(""),
+                        Cell::new(format_tokens_with_commas(report.total_messages as i64))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(format_tokens_with_commas(report.total_input))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(format_tokens_with_commas(report.total_output))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(format_tokens_with_commas(total_all))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(costs.currency(report.total_cost))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(costs.per_million(report.total_cost, total_all))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
                     ]);
                 }
-                GroupBy::Session | GroupBy::ClientSession => {
-                    let show_client = group_by == GroupBy::ClientSession;
+                GroupBy::SessionModel | GroupBy::ClientSessionModel => {
+                    let show_client = group_by == GroupBy::ClientSessionModel;
                     let mut header = Vec::with_capacity(9);
                     if show_client {
                         header.push(Cell::new("Client").fg(Color::Cyan));
@@ -2365,12 +4382,7 @@ fn run_models_report(
                     table.set_header(header);
 
                     for entry in &report.entries {
-                        let total = saturating_token_total(
-                            entry.input,
-                            entry.output,
-                            entry.cache_read,
-                            entry.cache_write,
-                        );
+                        let total = model_entry_total_tokens(entry);
                         let session_label = entry
                             .session_id
                             .clone()
@@ -2392,20 +4404,15 @@ fn run_models_report(
                                 .set_alignment(CellAlignment::Right),
                             Cell::new(format_tokens_with_commas(total))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_currency(entry.cost))
+                            Cell::new(costs.currency(entry.cost))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_cost_per_million(entry.cost, total))
+                            Cell::new(costs.per_million(entry.cost, total))
                                 .set_alignment(CellAlignment::Right),
                         ]);
                         table.add_row(row);
                     }
 
-                    let total_all = saturating_token_total(
-                        report.total_input,
-                        report.total_output,
-                        report.total_cache_read,
-                        report.total_cache_write,
-                    );
+                    let total_all = model_report_total_tokens(&report);
                     let mut total_row: Vec<Cell> = Vec::with_capacity(9);
                     total_row.push(
                         Cell::new("Total")
@@ -2432,40 +4439,27 @@ fn run_models_report(
                             .set_alignment(CellAlignment::Right),
                     );
                     total_row.push(
-                        Cell::new(format_currency(report.total_cost))
+                        Cell::new(costs.currency(report.total_cost))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
                     );
                     total_row.push(
-                        Cell::new(format_cost_per_million(report.total_cost, total_all))
+                        Cell::new(costs.per_million(report.total_cost, total_all))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
                     );
                     table.add_row(total_row);
                 }
                 GroupBy::ClientModel | GroupBy::ClientProviderModel => {
-                    table.set_header(vec![
-                        Cell::new("Client").fg(Color::Cyan),
-                        Cell::new("Provider").fg(Color::Cyan),
-                        Cell::new("Model").fg(Color::Cyan),
-                        Cell::new("Resolved").fg(Color::Cyan),
-                        Cell::new("Input").fg(Color::Cyan),
-                        Cell::new("Output").fg(Color::Cyan),
-                        Cell::new("Cache Write").fg(Color::Cyan),
-                        Cell::new("Cache Read").fg(Color::Cyan),
-                        Cell::new("Total").fg(Color::Cyan),
-                        Cell::new("ms/1K").fg(Color::Cyan),
-                        Cell::new("Cost").fg(Color::Cyan),
-                        Cell::new("Cost/1M").fg(Color::Cyan),
-                    ]);
+                    table.set_header(
+                        CLIENT_MODEL_WIDE_HEADERS
+                            .iter()
+                            .map(|header| Cell::new(*header).fg(Color::Cyan))
+                            .collect::<Vec<_>>(),
+                    );
 
                     for entry in &report.entries {
-                        let total = saturating_token_total(
-                            entry.input,
-                            entry.output,
-                            entry.cache_read,
-                            entry.cache_write,
-                        );
+                        let total = model_entry_total_tokens(entry);
 
                         table.add_row(vec![
                             Cell::new(capitalize_client(&entry.client)),
@@ -2483,23 +4477,20 @@ fn run_models_report(
                                 .set_alignment(CellAlignment::Right),
                             Cell::new(format_tokens_with_commas(entry.cache_read))
                                 .set_alignment(CellAlignment::Right),
+                            Cell::new(format_tokens_with_commas(entry.reasoning))
+                                .set_alignment(CellAlignment::Right),
                             Cell::new(format_tokens_with_commas(total))
                                 .set_alignment(CellAlignment::Right),
                             Cell::new(format_ms_per_1k(entry.performance.ms_per_1k_tokens))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_currency(entry.cost))
+                            Cell::new(costs.currency(entry.cost))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_cost_per_million(entry.cost, total))
+                            Cell::new(costs.per_million(entry.cost, total))
                                 .set_alignment(CellAlignment::Right),
                         ]);
                     }
 
-                    let total_all = saturating_token_total(
-                        report.total_input,
-                        report.total_output,
-                        report.total_cache_read,
-                        report.total_cache_write,
-                    );
+                    let total_all = model_report_total_tokens(&report);
                     table.add_row(vec![
                         Cell::new("Total")
                             .fg(Color::Yellow)
@@ -2513,22 +4504,92 @@ fn run_models_report(
                         Cell::new(format_tokens_with_commas(report.total_output))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_tokens_with_commas(report.total_cache_write))
+                        Cell::new(format_tokens_with_commas(report.total_cache_write))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(format_tokens_with_commas(report.total_cache_read))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(format_tokens_with_commas(report.total_reasoning))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(format_tokens_with_commas(total_all))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(format_ms_per_1k(total_performance.ms_per_1k_tokens))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(costs.currency(report.total_cost))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(costs.per_million(report.total_cost, total_all))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                    ]);
+                }
+                GroupBy::Project => {
+                    table.set_header(vec![
+                        Cell::new("Project").fg(Color::Cyan),
+                        Cell::new("Sources").fg(Color::Cyan),
+                        Cell::new("Model").fg(Color::Cyan),
+                        Cell::new("Msgs").fg(Color::Cyan),
+                        Cell::new("Input").fg(Color::Cyan),
+                        Cell::new("Output").fg(Color::Cyan),
+                        Cell::new("Total").fg(Color::Cyan),
+                        Cell::new("Cost").fg(Color::Cyan),
+                        Cell::new("Cost/1M").fg(Color::Cyan),
+                    ]);
+
+                    for entry in &report.entries {
+                        let total = model_entry_total_tokens(entry);
+                        let clients_str = entry.merged_clients.as_deref().unwrap_or(&entry.client);
+                        let capitalized_clients = clients_str
+                            .split(", ")
+                            .map(capitalize_client)
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        table.add_row(vec![
+                            Cell::new(workspace_name(entry.workspace_label.as_deref())),
+                            Cell::new(capitalized_clients),
+                            Cell::new(&entry.model),
+                            Cell::new(format_tokens_with_commas(entry.message_count as i64))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(format_tokens_with_commas(entry.input))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(format_tokens_with_commas(entry.output))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(format_tokens_with_commas(total))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(costs.currency(entry.cost))
+                                .set_alignment(CellAlignment::Right),
+                            Cell::new(costs.per_million(entry.cost, total))
+                                .set_alignment(CellAlignment::Right),
+                        ]);
+                    }
+
+                    let total_all = model_report_total_tokens(&report);
+                    table.add_row(vec![
+                        Cell::new("Total")
+                            .fg(Color::Yellow)
+                            .add_attribute(Attribute::Bold),
+                        Cell::new(""),
+                        Cell::new(""),
+                        Cell::new(format_tokens_with_commas(report.total_messages as i64))
+                            .fg(Color::Yellow)
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(format_tokens_with_commas(report.total_input))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_tokens_with_commas(report.total_cache_read))
+                        Cell::new(format_tokens_with_commas(report.total_output))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
                         Cell::new(format_tokens_with_commas(total_all))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_ms_per_1k(total_performance.ms_per_1k_tokens))
-                            .fg(Color::Yellow)
-                            .set_alignment(CellAlignment::Right),
-                        Cell::new(format_currency(report.total_cost))
+                        Cell::new(costs.currency(report.total_cost))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_cost_per_million(report.total_cost, total_all))
+                        Cell::new(costs.per_million(report.total_cost, total_all))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
                     ]);
@@ -2549,12 +4610,7 @@ fn run_models_report(
                     ]);
 
                     for entry in &report.entries {
-                        let total = saturating_token_total(
-                            entry.input,
-                            entry.output,
-                            entry.cache_read,
-                            entry.cache_write,
-                        );
+                        let total = model_entry_total_tokens(entry);
                         let clients_str = entry.merged_clients.as_deref().unwrap_or(&entry.client);
                         let capitalized_clients = clients_str
                             .split(", ")
@@ -2582,17 +4638,12 @@ fn run_models_report(
                                 .set_alignment(CellAlignment::Right),
                             Cell::new(format_ms_per_1k(entry.performance.ms_per_1k_tokens))
                                 .set_alignment(CellAlignment::Right),
-                            Cell::new(format_currency(entry.cost))
+                            Cell::new(costs.currency(entry.cost))
                                 .set_alignment(CellAlignment::Right),
                         ]);
                     }
 
-                    let total_all = saturating_token_total(
-                        report.total_input,
-                        report.total_output,
-                        report.total_cache_read,
-                        report.total_cache_write,
-                    );
+                    let total_all = model_report_total_tokens(&report);
                     table.add_row(vec![
                         Cell::new("Total")
                             .fg(Color::Yellow)
@@ -2618,41 +4669,71 @@ fn run_models_report(
                         Cell::new(format_ms_per_1k(total_performance.ms_per_1k_tokens))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
-                        Cell::new(format_currency(report.total_cost))
+                        Cell::new(costs.currency(report.total_cost))
                             .fg(Color::Yellow)
                             .set_alignment(CellAlignment::Right),
                     ]);
                 }
             }
+            append_cost_share_column(&mut table, &report, costs);
+        }
+        if show_sessions {
+            append_sessions_column(&mut table, &report);
+        }
+        if show_dates {
+            append_dates_columns(&mut table, &report);
+        }
+        if show_source {
+            append_source_column(&mut table, &report);
+        }
+        if show_bytes {
+            append_bytes_columns(&mut table, &report);
+        }
+        if compare_estimate {
+            append_estimate_columns(&mut table, &report, costs);
         }
 
-        let title = match &date_range {
-            Some(range) => format!("Token Usage Report by Model ({})", range),
-            None => "Token Usage Report by Model".to_string(),
-        };
-        println!("\n  \x1b[36m{}\x1b[0m\n", title);
-        println!("{}", dim_borders(&table.to_string()));
-
-        let total_tokens = saturating_token_total(
-            report.total_input,
-            report.total_output,
-            report.total_cache_read,
-            report.total_cache_write,
-        );
-        println!(
-            "\x1b[90m\n  Total: {} messages, {} tokens, \x1b[32m{}\x1b[90m\x1b[0m",
-            format_tokens_with_commas(report.total_messages as i64),
-            format_tokens_with_commas(total_tokens),
-            format_currency(report.total_cost)
-        );
+        if markdown {
+            print!("{}", markdown::render(&table));
+        } else {
+            let title = match &date_range {
+                Some(range) => format!("Token Usage Report by Model ({})", range),
+                None => "Token Usage Report by Model".to_string(),
+            };
+            println!("\n  \x1b[36m{}\x1b[0m\n", title);
+            println!("{}", dim_borders(&table.to_string()));
 
-        if benchmark {
+            let total_tokens = model_report_total_tokens(&report);
+            println!(
+                "\x1b[90m\n  Total: {} messages, {} tokens, \x1b[32m{}\x1b[90m\x1b[0m",
+                format_tokens_with_commas(report.total_messages as i64),
+                format_tokens_with_commas(total_tokens),
+                costs.currency(report.total_cost)
+            );
+            if let Some(line) = cache_savings_line(report.total_estimated_savings) {
+                println!("\x1b[90m  {}\x1b[0m", line);
+            }
+            print_cost_sparkline(report.daily_costs.as_deref());
+            if include_zero_cost && report.entries.iter().any(|e| e.cost == 0.0) {
+                println!("\x1b[90m  $0.00 rows are subscription or unpriced usage\x1b[0m");
+            }
+            print_unpriced_models_warning(&report.unpriced_models);
+        }
+
+        if benchmark || benchmark_detailed {
             use colored::Colorize;
             println!(
                 "{}",
                 format!("  Processing time: {}ms (Rust native)", processing_time_ms).bright_black()
             );
         }
+        if let Some(timings) = report
+            .client_timings
+            .as_ref()
+            .filter(|_| benchmark_detailed)
+        {
+            print_client_timings(timings);
+        }
 
         io::stdout().flush()?;
 
@@ -2662,17 +4743,466 @@ fn run_models_report(
         }
     }
 
+    Ok(is_empty)
+}
+
+/// [`exit_code::Failure::Empty`] when a one-shot report matched nothing.
+fn fail_if_empty(is_empty: bool) -> Result<()> {
+    if is_empty {
+        return Err(exit_code::Failure::Empty.into());
+    }
     Ok(())
 }
 
+/// Print the `--benchmark-detailed` breakdown, slowest client first. Clients
+/// that found no files and took no measurable time are left out.
+fn print_client_timings(timings: &[tokscale_core::ClientTiming]) {
+    use comfy_table::{Cell, CellAlignment, Color, Table};
+
+    let mut rows: Vec<&tokscale_core::ClientTiming> = timings
+        .iter()
+        .filter(|t| t.file_count > 0 || t.scan_ms + t.parse_ms >= 0.05)
+        .collect();
+    rows.sort_by(|a, b| (b.scan_ms + b.parse_ms).total_cmp(&(a.scan_ms + a.parse_ms)));
+
+    let mut table = Table::new();
+    table.load_preset(table_preset());
+    table.set_header(vec![
+        Cell::new("Client").fg(Color::Cyan),
+        Cell::new("Files").fg(Color::Cyan),
+        Cell::new("Scan").fg(Color::Cyan),
+        Cell::new("Parse").fg(Color::Cyan),
+        Cell::new("Dupes").fg(Color::Cyan),
+    ]);
+    for timing in rows {
+        table.add_row(vec![
+            Cell::new(capitalize_client(&timing.client)),
+            Cell::new(format_tokens_with_commas(timing.file_count as i64))
+                .set_alignment(CellAlignment::Right),
+            Cell::new(format!("{:.1}ms", timing.scan_ms)).set_alignment(CellAlignment::Right),
+            Cell::new(format!("{:.1}ms", timing.parse_ms)).set_alignment(CellAlignment::Right),
+            Cell::new(format_tokens_with_commas(timing.duplicates_removed as i64))
+                .set_alignment(CellAlignment::Right),
+        ]);
+    }
+    println!("\n  \x1b[36mProcessing time by client\x1b[0m\n");
+    println!("{}", dim_borders(&table.to_string()));
+}
+
+/// `--warn-duplicates` summary: one line per client whose dedup dropped
+/// anything, largest first.
+fn duplicate_summary(timings: &[tokscale_core::ClientTiming]) -> String {
+    let mut rows: Vec<&tokscale_core::ClientTiming> = timings
+        .iter()
+        .filter(|t| t.duplicates_removed > 0)
+        .collect();
+    if rows.is_empty() {
+        return "No duplicate messages removed\n".to_string();
+    }
+    rows.sort_by(|a, b| {
+        b.duplicates_removed
+            .cmp(&a.duplicates_removed)
+            .then_with(|| a.client.cmp(&b.client))
+    });
+    rows.iter()
+        .map(|t| {
+            format!(
+                "{}: {} duplicate{} removed\n",
+                capitalize_client(&t.client),
+                format_tokens_with_commas(t.duplicates_removed as i64),
+                if t.duplicates_removed == 1 { "" } else { "s" }
+            )
+        })
+        .collect()
+}
+
+/// Append the `--show-sessions` column to an already populated models table.
+///
+/// Every layout adds one row per report entry followed by a single total row,
+/// so the column is attached after the fact instead of in each layout. The
+/// synthetic below-threshold row carries no session count and renders as `-`.
+fn append_sessions_column(table: &mut comfy_table::Table, report: &tokscale_core::ModelReport) {
+    use comfy_table::{Cell, CellAlignment, Color};
+
+    if let Some(mut header) = table.header().cloned() {
+        header.add_cell(Cell::new("Sessions").fg(Color::Cyan));
+        table.set_header(header);
+    }
+
+    let total_index = report.entries.len();
+    for (index, row) in table.row_iter_mut().enumerate() {
+        let cell = match report.entries.get(index) {
+            Some(entry) if entry.session_count > 0 => {
+                Cell::new(format_tokens_with_commas(entry.session_count as i64))
+            }
+            Some(_) => Cell::new("-"),
+            None if index == total_index => {
+                Cell::new(format_tokens_with_commas(report.total_sessions as i64)).fg(Color::Yellow)
+            }
+            None => continue,
+        };
+        row.add_cell(cell.set_alignment(CellAlignment::Right));
+    }
+    table.discover_columns();
+}
+
+/// Append the `--show-source` column, in the same row order as
+/// [`append_sessions_column`]. Synthetic rows have no source and render as
+/// `-`; `--no-pricing` reports show `-` throughout.
+fn append_source_column(table: &mut comfy_table::Table, report: &tokscale_core::ModelReport) {
+    use comfy_table::{Attribute, Cell, Color};
+
+    if let Some(mut header) = table.header().cloned() {
+        header.add_cell(Cell::new("Source").fg(Color::Cyan));
+        table.set_header(header);
+    }
+
+    let total_index = report.entries.len();
+    for (index, row) in table.row_iter_mut().enumerate() {
+        let cell = match report.entries.get(index) {
+            Some(entry) => Cell::new(entry.pricing_source.as_deref().unwrap_or("-"))
+                .add_attribute(Attribute::Dim),
+            None if index == total_index => Cell::new(""),
+            None => continue,
+        };
+        row.add_cell(cell);
+    }
+    table.discover_columns();
+}
+
+/// Append the `--show-bytes` Req Bytes and Resp Bytes columns, in the same
+/// row order as [`append_sessions_column`]. Rows whose messages logged no
+/// sizes render as `-`.
+fn append_bytes_columns(table: &mut comfy_table::Table, report: &tokscale_core::ModelReport) {
+    use comfy_table::{Cell, CellAlignment, Color};
+
+    if let Some(mut header) = table.header().cloned() {
+        header.add_cell(Cell::new("Req Bytes").fg(Color::Cyan));
+        header.add_cell(Cell::new("Resp Bytes").fg(Color::Cyan));
+        table.set_header(header);
+    }
+
+    let bytes_cell = |bytes: Option<i64>| {
+        Cell::new(bytes.map_or_else(|| "-".to_string(), format_tokens_with_commas))
+            .set_alignment(CellAlignment::Right)
+    };
+    let total_index = report.entries.len();
+    for (index, row) in table.row_iter_mut().enumerate() {
+        match report.entries.get(index) {
+            Some(entry) => {
+                row.add_cell(bytes_cell(entry.request_bytes));
+                row.add_cell(bytes_cell(entry.response_bytes));
+            }
+            None if index == total_index => {
+                let total = |bytes: fn(&tokscale_core::ModelUsage) -> Option<i64>| {
+                    tokscale_core::sum_bytes(report.entries.iter().map(bytes))
+                };
+                row.add_cell(bytes_cell(total(|e| e.request_bytes)).fg(Color::Yellow));
+                row.add_cell(bytes_cell(total(|e| e.response_bytes)).fg(Color::Yellow));
+            }
+            None => continue,
+        }
+    }
+    table.discover_columns();
+}
+
+/// A row's [`tokscale_core::CostComparison`] when `--compare-estimate` asked
+/// for it.
+fn compared_cost(
+    entry: &tokscale_core::ModelUsage,
+    compare_estimate: bool,
+) -> Option<&tokscale_core::CostComparison> {
+    entry.cost_comparison.as_ref().filter(|_| compare_estimate)
+}
+
+/// Append the monthly table's Days and Cost/Day columns: how many days of
+/// each month saw usage, and the cost averaged over just those days. The
+/// total row averages over every active day in the report.
+fn append_active_days_columns(
+    table: &mut comfy_table::Table,
+    entries: &[tokscale_core::MonthlyUsage],
+    total_cost: f64,
+) {
+    use comfy_table::{Cell, CellAlignment, Color};
+
+    if let Some(mut header) = table.header().cloned() {
+        header.add_cell(Cell::new("Days").fg(Color::Cyan));
+        header.add_cell(Cell::new("Cost/Day").fg(Color::Cyan));
+        table.set_header(header);
+    }
+
+    let total_days: i32 = entries.iter().map(|e| e.active_days).sum();
+    let total_avg = if total_days > 0 {
+        total_cost / f64::from(total_days)
+    } else {
+        0.0
+    };
+    for (index, row) in table.row_iter_mut().enumerate() {
+        let (days, avg, color) = match entries.get(index) {
+            Some(entry) => (entry.active_days, entry.avg_cost_per_active_day, None),
+            None if index == entries.len() => (total_days, total_avg, Some(Color::Yellow)),
+            None => continue,
+        };
+        for value in [days.to_string(), format_currency(avg)] {
+            let cell = Cell::new(value).set_alignment(CellAlignment::Right);
+            row.add_cell(match color {
+                Some(color) => cell.fg(color),
+                None => cell,
+            });
+        }
+    }
+    table.discover_columns();
+}
+
+/// Append the `--compare-estimate` columns: the cost the client recorded,
+/// tokscale's estimate for the same messages, and how far apart they are.
+/// Rows without a client-recorded cost show `-`; the total row sums every
+/// compared row.
+fn append_estimate_columns(
+    table: &mut comfy_table::Table,
+    report: &tokscale_core::ModelReport,
+    costs: CostDisplay,
+) {
+    use comfy_table::{Cell, CellAlignment, Color};
+
+    if let Some(mut header) = table.header().cloned() {
+        header.add_cell(Cell::new("Client Cost").fg(Color::Cyan));
+        header.add_cell(Cell::new("Estimated").fg(Color::Cyan));
+        header.add_cell(Cell::new("Diff").fg(Color::Cyan));
+        table.set_header(header);
+    }
+
+    let total = report
+        .entries
+        .iter()
+        .filter_map(|e| e.cost_comparison.as_ref())
+        .fold(None, |acc: Option<tokscale_core::CostComparison>, c| {
+            let mut acc = acc.unwrap_or_default();
+            acc.client_cost += c.client_cost;
+            acc.estimated_cost += c.estimated_cost;
+            acc.message_count += c.message_count;
+            Some(acc)
+        });
+    let cells = |comparison: Option<&tokscale_core::CostComparison>| match comparison {
+        Some(c) => [
+            costs.currency(c.client_cost),
+            costs.currency(c.estimated_cost),
+            c.percent_difference()
+                .map(|pct| format!("{:+.1}%", pct))
+                .unwrap_or_else(|| "-".to_string()),
+        ],
+        None => ["-".to_string(), "-".to_string(), "-".to_string()],
+    };
+
+    let total_index = report.entries.len();
+    for (index, row) in table.row_iter_mut().enumerate() {
+        let values = match report.entries.get(index) {
+            Some(entry) => cells(entry.cost_comparison.as_ref()),
+            None if index == total_index => cells(total.as_ref()),
+            None => continue,
+        };
+        for value in values {
+            row.add_cell(Cell::new(value).set_alignment(CellAlignment::Right));
+        }
+    }
+    table.discover_columns();
+}
+
+/// Append the `--show-dates` First/Last columns to an already populated
+/// models table, in the same row order as [`append_sessions_column`]. The
+/// total row spans the earliest and latest dates across all entries; the
+/// synthetic below-threshold row has no dates and renders as `-`.
+fn append_dates_columns(table: &mut comfy_table::Table, report: &tokscale_core::ModelReport) {
+    use comfy_table::{Cell, Color};
+
+    if let Some(mut header) = table.header().cloned() {
+        header.add_cell(Cell::new("First").fg(Color::Cyan));
+        header.add_cell(Cell::new("Last").fg(Color::Cyan));
+        table.set_header(header);
+    }
+
+    let dated = || report.entries.iter().filter(|e| !e.first_date.is_empty());
+    let total_first = dated().map(|e| e.first_date.as_str()).min();
+    let total_last = dated().map(|e| e.last_date.as_str()).max();
+    let date_cell = |date: Option<&str>| match date {
+        Some(date) if !date.is_empty() => Cell::new(date),
+        _ => Cell::new("-"),
+    };
+
+    let total_index = report.entries.len();
+    for (index, row) in table.row_iter_mut().enumerate() {
+        match report.entries.get(index) {
+            Some(entry) => {
+                row.add_cell(date_cell(Some(&entry.first_date)));
+                row.add_cell(date_cell(Some(&entry.last_date)));
+            }
+            None if index == total_index => {
+                row.add_cell(date_cell(total_first).fg(Color::Yellow));
+                row.add_cell(date_cell(total_last).fg(Color::Yellow));
+            }
+            None => continue,
+        }
+    }
+    table.discover_columns();
+}
+
+/// `part` as a percentage of `total`, or 0.0 when there is no total to
+/// divide by.
+fn percent_of_total(part: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        part / total * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Append a "% Cost" column with each row's share of the report's total
+/// cost. The total row always reads 100.0% unless nothing was spent.
+fn append_cost_share_column(
+    table: &mut comfy_table::Table,
+    report: &tokscale_core::ModelReport,
+    costs: CostDisplay,
+) {
+    use comfy_table::{Cell, CellAlignment, Color};
+
+    if let Some(mut header) = table.header().cloned() {
+        header.add_cell(Cell::new("% Cost").fg(Color::Cyan));
+        table.set_header(header);
+    }
+
+    let share = |cost: f64| {
+        if costs.unpriced {
+            "—".to_string()
+        } else {
+            format!("{:.1}%", percent_of_total(cost, report.total_cost))
+        }
+    };
+    let total_index = report.entries.len();
+    for (index, row) in table.row_iter_mut().enumerate() {
+        let cell = match report.entries.get(index) {
+            Some(entry) => Cell::new(share(entry.cost)),
+            None if index == total_index => Cell::new(share(report.total_cost)).fg(Color::Yellow),
+            None => continue,
+        };
+        row.add_cell(cell.set_alignment(CellAlignment::Right));
+    }
+    table.discover_columns();
+}
+
+/// Spend from the first of `today`'s month through `today`, for `--budget`.
+/// The report covers that window already when no date filter was given;
+/// otherwise the month is queried on its own, so a `--since`/`--until`/`--year`
+/// range elsewhere can't read as $0 spent.
+fn month_to_date_spend(
+    rt: &tokio::runtime::Runtime,
+    options: &tokscale_core::ReportOptions,
+    report: &tokscale_core::MonthlyReport,
+    today: chrono::NaiveDate,
+) -> Result<f64> {
+    let current_month = today.format("%Y-%m").to_string();
+    let month_cost = |report: &tokscale_core::MonthlyReport| {
+        report
+            .entries
+            .iter()
+            .filter(|e| e.month == current_month)
+            .fold(0.0, |total, e| total + e.cost)
+    };
+    if options.since.is_none() && options.until.is_none() && options.year.is_none() {
+        return Ok(month_cost(report));
+    }
+
+    let month_to_date = tokscale_core::ReportOptions {
+        since: Some(format!("{current_month}-01")),
+        until: Some(today.format("%Y-%m-%d").to_string()),
+        until_exclusive: false,
+        year: None,
+        ..options.clone()
+    };
+    let report = rt
+        .block_on(tokscale_core::get_monthly_report(month_to_date))
+        .map_err(|e| anyhow::anyhow!(e))?;
+    Ok(month_cost(&report))
+}
+
+/// End-of-month spend projection for `tokscale monthly --budget`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BudgetProjection {
+    budget: f64,
+    spent_so_far: f64,
+    projected: f64,
+    over_budget: bool,
+}
+
+/// Linearly extrapolate `spent_so_far` over the calendar month containing
+/// `today`, counting `today` as an elapsed day. Spending exactly the budget
+/// is not over budget.
+fn project_monthly_budget(
+    budget: f64,
+    spent_so_far: f64,
+    today: chrono::NaiveDate,
+) -> BudgetProjection {
+    use chrono::Datelike;
+
+    let days_elapsed = today.day() as f64;
+    let days_in_month = today
+        .with_day(1)
+        .and_then(|first| first.checked_add_months(chrono::Months::new(1)))
+        .and_then(|next_first| next_first.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(30) as f64;
+    let projected = spent_so_far / days_elapsed * days_in_month;
+    BudgetProjection {
+        budget,
+        spent_so_far,
+        projected,
+        over_budget: projected > budget,
+    }
+}
+
+/// The `--budget` lines under the text report and `--summary-only`.
+fn print_budget_projection(projection: &BudgetProjection) {
+    use colored::Colorize;
+    println!(
+        "{}",
+        format!(
+            "  Budget: {} · Spent this month: {} · Projected: {}",
+            format_currency(projection.budget),
+            format_currency(projection.spent_so_far),
+            format_currency(projection.projected)
+        )
+        .bright_black()
+    );
+    if projection.over_budget {
+        println!(
+            "{}",
+            format!(
+                "  Warning: on track to exceed the monthly budget by {}",
+                format_currency(projection.projected - projection.budget)
+            )
+            .red()
+            .bold()
+        );
+    } else {
+        println!("{}", "  On track to stay within the monthly budget".green());
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn run_monthly_report(
     json: bool,
+    json_style: JsonStyle,
+    markdown: bool,
+    html: Option<html::HtmlOutput>,
     home_dir: Option<String>,
     clients: Option<Vec<String>>,
+    agent_filter: tokscale_core::AgentFilter,
     date: &DateRangeFlags,
     benchmark: bool,
     no_spinner: bool,
     hide_zero: bool,
+    budget: Option<f64>,
+    summary_only: bool,
 ) -> Result<()> {
     use std::time::Instant;
     use tokio::runtime::Runtime;
@@ -2694,21 +5224,30 @@ fn run_monthly_report(
     let use_env_roots = use_env_roots(&home_dir);
     let start = Instant::now();
     let rt = Runtime::new()?;
+    let options = ReportOptions {
+        home_dir: home_dir.clone(),
+        use_env_roots,
+        clients,
+        since,
+        until,
+        until_exclusive: date.until_exclusive,
+        year,
+        group_by: GroupBy::default(),
+        scanner_settings: tui::settings::load_scanner_settings_for_home(&home_dir),
+        agent_filter,
+        ..parse_flags::report_options()
+    };
     let report = rt
-        .block_on(async {
-            get_monthly_report(ReportOptions {
-                home_dir: home_dir.clone(),
-                use_env_roots,
-                clients,
-                since,
-                until,
-                year,
-                group_by: GroupBy::default(),
-                scanner_settings: tui::settings::load_scanner_settings_for_home(&home_dir),
-            })
-            .await
-        })
+        .block_on(get_monthly_report(options.clone()))
         .map_err(|e| anyhow::anyhow!(e))?;
+    let budget_projection = match budget {
+        Some(budget) => {
+            let today = chrono::Local::now().date_naive();
+            let spent_so_far = month_to_date_spend(&rt, &options, &report, today)?;
+            Some(project_monthly_budget(budget, spent_so_far, today))
+        }
+        None => None,
+    };
     let mut report = report;
     if hide_zero {
         // Display-only filter: totals still include the hidden rows.
@@ -2717,6 +5256,7 @@ fn run_monthly_report(
                 || e.output != 0
                 || e.cache_read != 0
                 || e.cache_write != 0
+                || e.reasoning != 0
                 || e.cost != 0.0
         });
     }
@@ -2733,7 +5273,14 @@ fn run_monthly_report(
 
     let processing_time_ms = start.elapsed().as_millis();
 
-    if json {
+    if let Some(html) = html {
+        emit_cursor_setup_warnings(&cursor_setup_warnings);
+        html.emit(&html::monthly_document(
+            &report,
+            date_range.as_deref(),
+            budget_projection.as_ref(),
+        ))?;
+    } else if json {
         #[derive(serde::Serialize)]
         #[serde(rename_all = "camelCase")]
         struct MonthlyUsageJson {
@@ -2743,41 +5290,111 @@ fn run_monthly_report(
             output: i64,
             cache_read: i64,
             cache_write: i64,
+            reasoning: i64,
             message_count: i32,
             cost: f64,
+            active_days: i32,
+            avg_cost_per_active_day: f64,
+        }
+
+        /// Token totals for `--summary-only`, which drops the entries they
+        /// would otherwise be summed from.
+        #[derive(serde::Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct MonthlyTotalsJson {
+            total_input: i64,
+            total_output: i64,
+            total_cache_read: i64,
+            total_cache_write: i64,
+            total_reasoning: i64,
+            total_messages: i64,
         }
 
         #[derive(serde::Serialize)]
         #[serde(rename_all = "camelCase")]
         struct MonthlyReportJson {
-            entries: Vec<MonthlyUsageJson>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            entries: Option<Vec<MonthlyUsageJson>>,
+            #[serde(flatten, skip_serializing_if = "Option::is_none")]
+            totals: Option<MonthlyTotalsJson>,
             total_cost: f64,
             processing_time_ms: u32,
+            #[serde(flatten, skip_serializing_if = "Option::is_none")]
+            budget: Option<BudgetProjection>,
             #[serde(skip_serializing_if = "Vec::is_empty")]
             warnings: Vec<String>,
         }
 
+        let totals = summary_only.then(|| {
+            let (total_input, total_output, total_cache_read, total_cache_write) =
+                monthly_token_field_totals(&report.entries);
+            MonthlyTotalsJson {
+                total_input,
+                total_output,
+                total_cache_read,
+                total_cache_write,
+                total_reasoning: monthly_reasoning_total(&report.entries),
+                total_messages: monthly_message_total(&report.entries),
+            }
+        });
         let output = MonthlyReportJson {
-            entries: report
-                .entries
-                .into_iter()
-                .map(|e| MonthlyUsageJson {
-                    month: e.month,
-                    models: e.models,
-                    input: e.input,
-                    output: e.output,
-                    cache_read: e.cache_read,
-                    cache_write: e.cache_write,
-                    message_count: e.message_count,
-                    cost: e.cost,
-                })
-                .collect(),
+            entries: (!summary_only).then(|| {
+                report
+                    .entries
+                    .into_iter()
+                    .map(|e| MonthlyUsageJson {
+                        month: e.month,
+                        models: e.models,
+                        input: e.input,
+                        output: e.output,
+                        cache_read: e.cache_read,
+                        cache_write: e.cache_write,
+                        reasoning: e.reasoning,
+                        message_count: e.message_count,
+                        cost: e.cost,
+                        active_days: e.active_days,
+                        avg_cost_per_active_day: e.avg_cost_per_active_day,
+                    })
+                    .collect()
+            }),
+            totals,
             total_cost: report.total_cost,
             processing_time_ms: report.processing_time_ms,
-            warnings: cursor_setup_warnings,
+            budget: budget_projection,
+            warnings: cursor_setup_warnings
+                .into_iter()
+                .chain(unpriced_models_warning(&report.unpriced_models))
+                .collect(),
         };
 
-        println!("{}", serde_json::to_string_pretty(&output)?);
+        if currency::current().is_usd() {
+            println!("{}", json_style.render(&output)?);
+        } else {
+            let mut output = serde_json::to_value(&output)?;
+            currency::current().annotate_json(&mut output);
+            println!("{}", json_style.render(&output)?);
+        }
+    } else if summary_only {
+        emit_cursor_setup_warnings(&cursor_setup_warnings);
+        print_summary_only(
+            "Monthly Token Usage Summary",
+            date_range.as_deref(),
+            monthly_message_total(&report.entries),
+            monthly_token_field_totals(&report.entries),
+            monthly_reasoning_total(&report.entries),
+            report.total_cost,
+            CostDisplay::default(),
+        );
+        if let Some(projection) = &budget_projection {
+            print_budget_projection(projection);
+        }
+        if benchmark {
+            use colored::Colorize;
+            println!(
+                "{}",
+                format!("  Processing time: {}ms (Rust native)", processing_time_ms).bright_black()
+            );
+        }
     } else {
         use comfy_table::{Attribute, Cell, CellAlignment, Color, ContentArrangement, Table};
 
@@ -2785,10 +5402,12 @@ fn run_monthly_report(
         let term_width = crossterm::terminal::size()
             .map(|(w, _)| w as usize)
             .unwrap_or(120);
-        let compact = term_width < 100;
+        // Markdown isn't bound by the terminal width, so it always gets the
+        // wide layout.
+        let compact = !markdown && term_width < 100;
 
         let mut table = Table::new();
-        table.load_preset(TABLE_PRESET);
+        table.load_preset(table_preset());
         let arrangement = if std::io::stdout().is_terminal() {
             ContentArrangement::DynamicFullWidth
         } else {
@@ -2824,12 +5443,7 @@ fn run_monthly_report(
                         .collect::<Vec<_>>()
                         .join("\n")
                 };
-                let total_tokens = saturating_token_total(
-                    entry.input,
-                    entry.output,
-                    entry.cache_read,
-                    entry.cache_write,
-                );
+                let total_tokens = monthly_entry_total_tokens(entry);
 
                 table.add_row(vec![
                     Cell::new(entry.month.clone()),
@@ -2844,14 +5458,8 @@ fn run_monthly_report(
                 ]);
             }
 
-            let (total_input, total_output, total_cache_read, total_cache_write) =
-                monthly_token_field_totals(&report.entries);
-            let total_tokens = saturating_token_total(
-                total_input,
-                total_output,
-                total_cache_read,
-                total_cache_write,
-            );
+            let (total_input, total_output, _, _) = monthly_token_field_totals(&report.entries);
+            let total_tokens = monthly_total_tokens(&report.entries);
             table.add_row(vec![
                 Cell::new("Total")
                     .fg(Color::Yellow)
@@ -2878,6 +5486,7 @@ fn run_monthly_report(
                 Cell::new("Output").fg(Color::Cyan),
                 Cell::new("Cache Write").fg(Color::Cyan),
                 Cell::new("Cache Read").fg(Color::Cyan),
+                Cell::new("Reasoning").fg(Color::Cyan),
                 Cell::new("Total").fg(Color::Cyan),
                 Cell::new("Cost").fg(Color::Cyan),
                 Cell::new("Cost/1M").fg(Color::Cyan),
@@ -2901,12 +5510,7 @@ fn run_monthly_report(
                         .collect::<Vec<_>>()
                         .join("\n")
                 };
-                let total = saturating_token_total(
-                    entry.input,
-                    entry.output,
-                    entry.cache_read,
-                    entry.cache_write,
-                );
+                let total = monthly_entry_total_tokens(entry);
 
                 table.add_row(vec![
                     Cell::new(entry.month.clone()),
@@ -2919,6 +5523,8 @@ fn run_monthly_report(
                         .set_alignment(CellAlignment::Right),
                     Cell::new(format_tokens_with_commas(entry.cache_read))
                         .set_alignment(CellAlignment::Right),
+                    Cell::new(format_tokens_with_commas(entry.reasoning))
+                        .set_alignment(CellAlignment::Right),
                     Cell::new(format_tokens_with_commas(total)).set_alignment(CellAlignment::Right),
                     Cell::new(format_currency(entry.cost)).set_alignment(CellAlignment::Right),
                     Cell::new(format_cost_per_million(entry.cost, total))
@@ -2928,12 +5534,8 @@ fn run_monthly_report(
 
             let (total_input, total_output, total_cache_read, total_cache_write) =
                 monthly_token_field_totals(&report.entries);
-            let total_all = saturating_token_total(
-                total_input,
-                total_output,
-                total_cache_read,
-                total_cache_write,
-            );
+            let total_reasoning = monthly_reasoning_total(&report.entries);
+            let total_all = monthly_total_tokens(&report.entries);
 
             table.add_row(vec![
                 Cell::new("Total")
@@ -2952,6 +5554,9 @@ fn run_monthly_report(
                 Cell::new(format_tokens_with_commas(total_cache_read))
                     .fg(Color::Yellow)
                     .set_alignment(CellAlignment::Right),
+                Cell::new(format_tokens_with_commas(total_reasoning))
+                    .fg(Color::Yellow)
+                    .set_alignment(CellAlignment::Right),
                 Cell::new(format_tokens_with_commas(total_all))
                     .fg(Color::Yellow)
                     .set_alignment(CellAlignment::Right),
@@ -2963,18 +5568,28 @@ fn run_monthly_report(
                     .set_alignment(CellAlignment::Right),
             ]);
         }
+        append_active_days_columns(&mut table, &report.entries, report.total_cost);
 
-        let title = match &date_range {
-            Some(range) => format!("Monthly Token Usage Report ({})", range),
-            None => "Monthly Token Usage Report".to_string(),
-        };
-        println!("\n  \x1b[36m{}\x1b[0m\n", title);
-        println!("{}", dim_borders(&table.to_string()));
+        if markdown {
+            print!("{}", markdown::render(&table));
+        } else {
+            let title = match &date_range {
+                Some(range) => format!("Monthly Token Usage Report ({})", range),
+                None => "Monthly Token Usage Report".to_string(),
+            };
+            println!("\n  \x1b[36m{}\x1b[0m\n", title);
+            println!("{}", dim_borders(&table.to_string()));
+
+            println!(
+                "\x1b[90m\n  Total Cost: \x1b[32m{}\x1b[90m\x1b[0m",
+                format_currency(report.total_cost)
+            );
+            print_unpriced_models_warning(&report.unpriced_models);
+        }
 
-        println!(
-            "\x1b[90m\n  Total Cost: \x1b[32m{}\x1b[90m\x1b[0m",
-            format_currency(report.total_cost)
-        );
+        if let Some(projection) = &budget_projection {
+            print_budget_projection(projection);
+        }
 
         if benchmark {
             use colored::Colorize;
@@ -2999,7 +5614,7 @@ fn run_hourly_report(
 ) -> Result<()> {
     use std::time::Instant;
     use tokio::runtime::Runtime;
-    use tokscale_core::{get_hourly_report, GroupBy, ReportOptions};
+    use tokscale_core::{get_hourly_report, ReportOptions};
 
     let (since, until) = build_date_filter(date);
     let year = normalize_year_filter(date);
@@ -3025,9 +5640,10 @@ fn run_hourly_report(
                 clients,
                 since,
                 until,
+                until_exclusive: date.until_exclusive,
                 year,
-                group_by: GroupBy::default(),
                 scanner_settings: tui::settings::load_scanner_settings_for_home(&home_dir),
+                ..parse_flags::report_options()
             })
             .await
         })
@@ -3116,7 +5732,7 @@ fn run_hourly_report(
         let compact = term_width < 100;
 
         let mut table = Table::new();
-        table.load_preset(TABLE_PRESET);
+        table.load_preset(table_preset());
         let arrangement = if std::io::stdout().is_terminal() {
             ContentArrangement::DynamicFullWidth
         } else {
@@ -3291,7 +5907,9 @@ fn run_hourly_report(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_wrapped_command(
+    home_dir: Option<String>,
     output: Option<String>,
     year: Option<String>,
     client_filter: Option<Vec<String>>,
@@ -3299,14 +5917,11 @@ fn run_wrapped_command(
     agents: bool,
     show_clients: bool,
     disable_pinned: bool,
+    active_threshold: i64,
+    json: bool,
 ) -> Result<()> {
     use colored::Colorize;
 
-    println!("{}", "\n  Tokscale - Generate Wrapped Image\n".cyan());
-
-    println!("{}", "  Generating wrapped image...".bright_black());
-    println!();
-
     let include_agents = !show_clients || agents;
     let wrapped_options = commands::wrapped::WrappedOptions {
         output,
@@ -3315,8 +5930,20 @@ fn run_wrapped_command(
         short,
         include_agents,
         pin_sisyphus: !disable_pinned,
+        active_threshold,
+        home_dir,
     };
 
+    if json {
+        println!("{}", commands::wrapped::run_json(wrapped_options)?);
+        return Ok(());
+    }
+
+    println!("{}", "\n  Tokscale - Generate Wrapped Image\n".cyan());
+
+    println!("{}", "  Generating wrapped image...".bright_black());
+    println!();
+
     match commands::wrapped::run(wrapped_options) {
         Ok(output_path) => {
             println!(
@@ -3334,20 +5961,105 @@ fn run_wrapped_command(
     Ok(())
 }
 
+/// Local usage of a single model as seen by `tokscale pricing --with-usage`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ObservedModelUsage {
+    input: i64,
+    output: i64,
+    cache_read: i64,
+    cache_write: i64,
+    reasoning: i64,
+    total_tokens: i64,
+    cost: f64,
+    /// Observed cost divided by observed tokens, in dollars per 1M tokens.
+    effective_cost_per_million: f64,
+}
+
+/// Sum every model-grouped entry whose grouping name matches `model_id`.
+/// Returns `None` when the model has no recorded tokens, so callers fall back
+/// to the static rates alone.
+fn observed_model_usage(
+    entries: &[tokscale_core::ModelUsage],
+    model_id: &str,
+) -> Option<ObservedModelUsage> {
+    let wanted = tokscale_core::normalize_model_for_grouping(model_id);
+    let mut usage = ObservedModelUsage {
+        input: 0,
+        output: 0,
+        cache_read: 0,
+        cache_write: 0,
+        reasoning: 0,
+        total_tokens: 0,
+        cost: 0.0,
+        effective_cost_per_million: 0.0,
+    };
+    for entry in entries
+        .iter()
+        .filter(|entry| tokscale_core::normalize_model_for_grouping(&entry.model) == wanted)
+    {
+        usage.input = usage.input.saturating_add(entry.input);
+        usage.output = usage.output.saturating_add(entry.output);
+        usage.cache_read = usage.cache_read.saturating_add(entry.cache_read);
+        usage.cache_write = usage.cache_write.saturating_add(entry.cache_write);
+        usage.reasoning = usage.reasoning.saturating_add(entry.reasoning);
+        usage.cost += entry.cost;
+    }
+    usage.total_tokens = saturating_token_total(
+        usage.input,
+        usage.output,
+        usage.cache_read,
+        usage.cache_write,
+    )
+    .saturating_add(usage.reasoning);
+    if usage.total_tokens <= 0 {
+        return None;
+    }
+    usage.effective_cost_per_million = usage.cost * 1_000_000.0 / usage.total_tokens as f64;
+    Some(usage)
+}
+
+/// Scan local usage of just `model_id` (matched by its grouping name), for
+/// `pricing --with-usage`.
+fn load_observed_model_usage(
+    model_id: &str,
+    home_dir: Option<String>,
+) -> Result<Option<ObservedModelUsage>, String> {
+    use tokio::runtime::Runtime;
+    use tokscale_core::{get_model_report, GroupBy, ModelFilter, ReportOptions};
+
+    let pattern = format!(
+        "^{}$",
+        regex::escape(&tokscale_core::normalize_model_for_grouping(model_id))
+    );
+    let rt = Runtime::new().map_err(|e| e.to_string())?;
+    let report = rt.block_on(get_model_report(ReportOptions {
+        use_env_roots: use_env_roots(&home_dir),
+        scanner_settings: tui::settings::load_scanner_settings_for_home(&home_dir),
+        home_dir,
+        group_by: GroupBy::Model,
+        model_filter: ModelFilter::new([pattern], [])?,
+        ..parse_flags::report_options()
+    }))?;
+    Ok(observed_model_usage(&report.entries, model_id))
+}
+
 fn run_pricing_lookup(
     model_id: &str,
     json: bool,
+    json_style: JsonStyle,
     provider: Option<&str>,
+    with_usage: bool,
+    home_dir: Option<String>,
     no_spinner: bool,
 ) -> Result<()> {
     use colored::Colorize;
     use indicatif::ProgressBar;
     use indicatif::ProgressStyle;
     use tokio::runtime::Runtime;
-    use tokscale_core::pricing::PricingService;
 
     if model_id.eq_ignore_ascii_case("list-overrides") {
-        return run_pricing_list_overrides(json);
+        return run_pricing_list_overrides(json, json_style);
     }
 
     let provider_normalized = provider.map(|p| p.to_lowercase());
@@ -3378,7 +6090,7 @@ fn run_pricing_lookup(
 
     let rt = Runtime::new()?;
     let result = match rt.block_on(async {
-        let svc = PricingService::get_or_init().await?;
+        let svc = parse_flags::load_pricing().await?;
         Ok::<_, String>(svc.lookup_with_source(model_id, provider_normalized.as_deref()))
     }) {
         Ok(result) => result,
@@ -3395,7 +6107,7 @@ fn run_pricing_lookup(
                 }
                 println!(
                     "{}",
-                    serde_json::to_string_pretty(&ErrorOutput {
+                    json_style.render(&ErrorOutput {
                         error: err,
                         model_id: model_id.to_string(),
                     })?
@@ -3406,6 +6118,23 @@ fn run_pricing_lookup(
         }
     };
 
+    let observed = if with_usage && result.is_some() {
+        if let Some(pb) = &spinner {
+            pb.set_message("Scanning session data...");
+        }
+        match load_observed_model_usage(model_id, home_dir) {
+            Ok(observed) => observed,
+            Err(err) => {
+                if let Some(pb) = spinner {
+                    pb.finish_and_clear();
+                }
+                return Err(anyhow::anyhow!(err));
+            }
+        }
+    } else {
+        None
+    };
+
     if let Some(pb) = spinner {
         pb.finish_and_clear();
     }
@@ -3431,6 +6160,8 @@ fn run_pricing_lookup(
                     matched_key: String,
                     source: String,
                     pricing: PricingValues,
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    observed_usage: Option<ObservedModelUsage>,
                 }
 
                 let output = PricingOutput {
@@ -3445,9 +6176,10 @@ fn run_pricing_lookup(
                             .pricing
                             .cache_creation_input_token_cost,
                     },
+                    observed_usage: observed,
                 };
 
-                println!("{}", serde_json::to_string_pretty(&output)?);
+                println!("{}", json_style.render(&output)?);
             }
             None => {
                 #[derive(serde::Serialize)]
@@ -3462,7 +6194,7 @@ fn run_pricing_lookup(
                     model_id: model_id.to_string(),
                 };
 
-                println!("{}", serde_json::to_string_pretty(&output)?);
+                println!("{}", json_style.render(&output)?);
                 std::process::exit(1);
             }
         }
@@ -3497,6 +6229,40 @@ fn run_pricing_lookup(
                     );
                 }
                 println!();
+                if let Some(observed) = observed {
+                    println!("  {}", "Observed usage".bold());
+                    println!(
+                        "  Input:  {} tokens",
+                        format_tokens_with_commas(observed.input)
+                    );
+                    println!(
+                        "  Output: {} tokens",
+                        format_tokens_with_commas(observed.output)
+                    );
+                    println!(
+                        "  Cache Read:  {} tokens",
+                        format_tokens_with_commas(observed.cache_read)
+                    );
+                    println!(
+                        "  Cache Write: {} tokens",
+                        format_tokens_with_commas(observed.cache_write)
+                    );
+                    println!(
+                        "  Reasoning:   {} tokens",
+                        format_tokens_with_commas(observed.reasoning)
+                    );
+                    println!("  Total Cost: {}", format_currency(observed.cost));
+                    println!(
+                        "  Effective:   ${:.2} / 1M tokens (blended)",
+                        observed.effective_cost_per_million
+                    );
+                    println!();
+                } else if with_usage {
+                    println!(
+                        "  {}\n",
+                        "No local usage recorded for this model.".bright_black()
+                    );
+                }
             }
             None => {
                 println!("\n  {}\n", format!("Model not found: {}", model_id).red());
@@ -3508,7 +6274,77 @@ fn run_pricing_lookup(
     Ok(())
 }
 
-fn run_pricing_list_overrides(json: bool) -> Result<()> {
+fn run_pricing_search(
+    query: &str,
+    limit: usize,
+    json: bool,
+    json_style: JsonStyle,
+    no_spinner: bool,
+) -> Result<()> {
+    use colored::Colorize;
+    use comfy_table::{Cell, CellAlignment, Color, ContentArrangement, Table};
+    use indicatif::ProgressBar;
+    use indicatif::ProgressStyle;
+    use tokio::runtime::Runtime;
+
+    let spinner = if no_spinner {
+        None
+    } else {
+        let pb = ProgressBar::new_spinner();
+        pb.set_style(ProgressStyle::default_spinner());
+        pb.set_message("Fetching pricing data...");
+        pb.enable_steady_tick(std::time::Duration::from_millis(100));
+        Some(pb)
+    };
+
+    let rt = Runtime::new()?;
+    let result = rt.block_on(async {
+        let svc = parse_flags::load_pricing().await?;
+        Ok::<_, String>(svc.search(query, limit))
+    });
+    if let Some(pb) = spinner {
+        pb.finish_and_clear();
+    }
+    let matches = result.map_err(|e| anyhow::anyhow!(e))?;
+
+    if json {
+        println!("{}", json_style.render(&matches)?);
+        return Ok(());
+    }
+
+    if matches.is_empty() {
+        println!(
+            "\n  {}\n",
+            format!("No pricing keys match: {}", query).red()
+        );
+        std::process::exit(1);
+    }
+
+    let mut table = Table::new();
+    table.load_preset(table_preset());
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.enforce_styling();
+    table.set_header(vec![
+        Cell::new("Key").fg(Color::Cyan),
+        Cell::new("Source").fg(Color::Cyan),
+        Cell::new("Score").fg(Color::Cyan),
+    ]);
+    for candidate in &matches {
+        table.add_row(vec![
+            Cell::new(&candidate.key),
+            Cell::new(&candidate.source),
+            Cell::new(format!("{:.2}", candidate.score)).set_alignment(CellAlignment::Right),
+        ]);
+    }
+
+    println!("\n  Pricing keys matching: {}\n", query.bold());
+    println!("{}", dim_borders(&table.to_string()));
+    println!();
+
+    Ok(())
+}
+
+fn run_pricing_list_overrides(json: bool, json_style: JsonStyle) -> Result<()> {
     use colored::Colorize;
     use tokscale_core::pricing::custom::CustomPricing;
     use tokscale_core::pricing::ModelPricing;
@@ -3564,7 +6400,7 @@ fn run_pricing_list_overrides(json: bool) -> Result<()> {
 
         println!(
             "{}",
-            serde_json::to_string_pretty(&Output {
+            json_style.render(&Output {
                 path: path.display().to_string(),
                 count: entries.len(),
                 models: entries,
@@ -3607,8 +6443,104 @@ fn run_pricing_list_overrides(json: bool) -> Result<()> {
     Ok(())
 }
 
+/// The recorded snapshot every pricing lookup in this run uses for
+/// `--pricing-date`. Exits with a usage error when no snapshot can serve the
+/// date.
+fn pricing_snapshot_date(requested: chrono::NaiveDate) -> chrono::NaiveDate {
+    use tokscale_core::pricing::{history::Selection, PricingService};
+
+    match PricingService::select_snapshot(requested) {
+        Ok(Selection::Exact(date)) => date,
+        Ok(Selection::Earlier(date)) => {
+            eprintln!(
+                "[tokscale] Warning: no pricing snapshot for {requested}; using the nearest earlier one from {date}"
+            );
+            date
+        }
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(exit_code::USAGE);
+        }
+    }
+}
+
+/// `--threads` wins over `TOKSCALE_SCAN_THREADS`; neither (or 0) keeps
+/// rayon's one-thread-per-core global pool.
+fn scan_threads(flag: Option<usize>) -> usize {
+    let threads = match flag {
+        Some(threads) => Some(threads),
+        None => tokscale_core::scan_pool::threads_from_env(|key| std::env::var(key).ok())
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {e}");
+                std::process::exit(exit_code::USAGE);
+            }),
+    };
+    threads.unwrap_or(0)
+}
+
+/// Pin the display currency from `--currency`. An unknown code or a missing
+/// or malformed rate is a usage error.
+fn configure_currency(flag: Option<&str>) {
+    let Some(code) = flag else {
+        return;
+    };
+    match currency::resolve(code, std::env::var(currency::FX_RATE_ENV).ok()) {
+        Ok(selected) => currency::set_current(selected),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(exit_code::USAGE);
+        }
+    }
+}
+
+/// Pin the cache profile from `--profile`, else `TOKSCALE_PROFILE`. A name
+/// that is not a single path component is a usage error either way.
+fn configure_profile(flag: Option<&str>) {
+    use tokscale_core::paths;
+
+    let requested = match flag {
+        Some(name) => Some(name.to_string()),
+        None => std::env::var(paths::PROFILE_ENV)
+            .ok()
+            .filter(|name| !name.is_empty()),
+    };
+    if let Some(name) = requested {
+        if let Err(e) = paths::set_profile(&name) {
+            eprintln!("Error: {e}");
+            std::process::exit(exit_code::USAGE);
+        }
+    }
+}
+
+/// How a report's cost cells read. `models --no-pricing` never computes
+/// costs, so they read `—` rather than a misleading `$0.00`.
+#[derive(Debug, Clone, Copy, Default)]
+struct CostDisplay {
+    unpriced: bool,
+}
+
+impl CostDisplay {
+    fn currency(self, n: f64) -> String {
+        if self.unpriced {
+            return "—".to_string();
+        }
+        format_currency(n)
+    }
+
+    fn per_million(self, cost: f64, total_tokens: i64) -> String {
+        if self.unpriced {
+            return "—".to_string();
+        }
+        format_cost_per_million(cost, total_tokens)
+    }
+}
+
 fn format_currency(n: f64) -> String {
-    format!("${:.2}", n)
+    format_currency_with_precision(n, currency::precision())
+}
+
+fn format_currency_with_precision(n: f64, precision: u8) -> String {
+    currency::current().render(n, precision, locale::current())
 }
 
 fn format_cost_per_million(cost: f64, total_tokens: i64) -> String {
@@ -3619,7 +6551,10 @@ fn format_cost_per_million(cost: f64, total_tokens: i64) -> String {
     if !cost_per_m.is_finite() {
         "—".to_string()
     } else {
-        format!("${:.2}/M", cost_per_m)
+        format!(
+            "{}/M",
+            currency::current().render(cost_per_m, 2, locale::current())
+        )
     }
 }
 
@@ -3671,6 +6606,130 @@ fn monthly_token_field_totals(entries: &[tokscale_core::MonthlyUsage]) -> (i64,
     )
 }
 
+fn monthly_reasoning_total(entries: &[tokscale_core::MonthlyUsage]) -> i64 {
+    entries
+        .iter()
+        .fold(0i64, |total, entry| total.saturating_add(entry.reasoning))
+}
+
+/// A month's tokens across every bucket, reasoning included; the Total and
+/// Cost/1M columns of both monthly table layouts use it.
+fn monthly_entry_total_tokens(entry: &tokscale_core::MonthlyUsage) -> i64 {
+    saturating_token_total(
+        entry.input,
+        entry.output,
+        entry.cache_read,
+        entry.cache_write,
+    )
+    .saturating_add(entry.reasoning)
+}
+
+fn monthly_total_tokens(entries: &[tokscale_core::MonthlyUsage]) -> i64 {
+    entries.iter().fold(0i64, |total, entry| {
+        total.saturating_add(monthly_entry_total_tokens(entry))
+    })
+}
+
+fn monthly_message_total(entries: &[tokscale_core::MonthlyUsage]) -> i64 {
+    entries.iter().map(|e| i64::from(e.message_count)).sum()
+}
+
+/// `--env`: the report totals as `KEY=value` lines for `eval` or `source`.
+/// Values are bare numbers; the cost is in the display currency.
+fn model_report_env(
+    report: &tokscale_core::ModelReport,
+    currency: &currency::DisplayCurrency,
+    precision: u8,
+) -> String {
+    let total_tokens = model_report_total_tokens(report);
+    format!(
+        "TOKSCALE_TOTAL_COST={:.precision$}\nTOKSCALE_TOTAL_TOKENS={}\nTOKSCALE_TOTAL_MESSAGES={}\n",
+        currency.convert(report.total_cost),
+        total_tokens,
+        report.total_messages,
+        precision = usize::from(precision),
+    )
+}
+
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One block character per value, scaled from zero to the largest value.
+/// An all-zero series is a flat line of the lowest block.
+fn cost_sparkline(costs: &[f64]) -> String {
+    let max = costs.iter().copied().fold(0.0_f64, f64::max);
+    costs
+        .iter()
+        .map(|&cost| {
+            let level = if max > 0.0 && cost.is_finite() {
+                ((cost.max(0.0) / max) * 7.0).round() as usize
+            } else {
+                0
+            };
+            SPARKLINE_LEVELS[level.min(7)]
+        })
+        .collect()
+}
+
+/// The `--sparkline` line under a models summary, when the report carries
+/// daily costs.
+fn print_cost_sparkline(daily_costs: Option<&[tokscale_core::DailyCost]>) {
+    let Some(days) = daily_costs.filter(|days| !days.is_empty()) else {
+        return;
+    };
+    let costs: Vec<f64> = days.iter().map(|day| day.cost).collect();
+    println!(
+        "\x1b[90m  Daily cost: \x1b[0m{}\x1b[90m ({} to {})\x1b[0m",
+        cost_sparkline(&costs),
+        days[0].date,
+        days[days.len() - 1].date
+    );
+}
+
+/// The `--summary-only` view: the grand totals a report's table would end
+/// with, without the table. `tokens` is (input, output, cache read, cache
+/// write).
+fn print_summary_only(
+    title: &str,
+    date_range: Option<&str>,
+    messages: i64,
+    tokens: (i64, i64, i64, i64),
+    reasoning: i64,
+    cost: f64,
+    costs: CostDisplay,
+) {
+    let (input, output, cache_read, cache_write) = tokens;
+    let title = match date_range {
+        Some(range) => format!("{} ({})", title, range),
+        None => title.to_string(),
+    };
+    println!("\n  \x1b[36m{}\x1b[0m\n", title);
+    println!("  Messages: {}", format_tokens_with_commas(messages));
+    println!(
+        "  Tokens:   {} (input {}, output {}, cache read {}, cache write {}, reasoning {})",
+        format_tokens_with_commas(
+            saturating_token_total(input, output, cache_read, cache_write)
+                .saturating_add(reasoning)
+        ),
+        format_tokens_with_commas(input),
+        format_tokens_with_commas(output),
+        format_tokens_with_commas(cache_read),
+        format_tokens_with_commas(cache_write),
+        format_tokens_with_commas(reasoning)
+    );
+    println!("  Cost:     \x1b[32m{}\x1b[0m", costs.currency(cost));
+}
+
+/// The models report's grand total, with the same buckets as
+/// [`model_entry_total_tokens`]; shares and Cost/1M divide by it.
+fn model_report_total_tokens(report: &tokscale_core::ModelReport) -> i64 {
+    report
+        .total_input
+        .saturating_add(report.total_output)
+        .saturating_add(report.total_cache_read)
+        .saturating_add(report.total_cache_write)
+        .saturating_add(report.total_reasoning)
+}
+
 fn model_entry_total_tokens(entry: &tokscale_core::ModelUsage) -> i64 {
     // saturating_add (mirrors tokscale_core::TokenBreakdown::total) so a
     // clamped (i64::MAX) bucket from a corrupt source can't overflow the
@@ -3684,6 +6743,78 @@ fn model_entry_total_tokens(entry: &tokscale_core::ModelUsage) -> i64 {
         .saturating_add(entry.reasoning.max(0))
 }
 
+const TOTAL_ROW_MODEL: &str = "__total__";
+
+/// Synthetic `(below threshold)` row summarizing entries dropped by
+/// `--min-cost` / `--min-tokens` in the models table and HTML.
+fn below_threshold_model_usage(omitted: &tokscale_core::OmittedUsage) -> tokscale_core::ModelUsage {
+    const LABEL: &str = "(below threshold)";
+    tokscale_core::ModelUsage {
+        client: LABEL.to_string(),
+        merged_clients: None,
+        workspace_key: None,
+        workspace_label: Some(LABEL.to_string()),
+        session_id: Some(LABEL.to_string()),
+        model: format!("{} models", omitted.entries),
+        provider: String::new(),
+        raw_provider: String::new(),
+        input: omitted.input,
+        output: omitted.output,
+        cache_read: omitted.cache_read,
+        cache_write: omitted.cache_write,
+        reasoning: omitted.reasoning,
+        message_count: omitted.message_count,
+        session_count: 0,
+        first_date: String::new(),
+        last_date: String::new(),
+        cost: omitted.cost,
+        pricing_source: None,
+        cost_comparison: None,
+        estimated_savings: 0.0,
+        request_bytes: None,
+        response_bytes: None,
+        performance: tokscale_core::ModelPerformance::default(),
+    }
+}
+
+/// Synthetic `__total__` row carrying a model report's grand totals, for
+/// `--include-total-row` JSON consumers that would otherwise re-sum entries.
+fn total_model_usage(report: &tokscale_core::ModelReport) -> tokscale_core::ModelUsage {
+    let dated = || report.entries.iter().filter(|e| !e.first_date.is_empty());
+    tokscale_core::ModelUsage {
+        client: String::new(),
+        merged_clients: None,
+        workspace_key: None,
+        workspace_label: None,
+        session_id: None,
+        model: TOTAL_ROW_MODEL.to_string(),
+        provider: String::new(),
+        raw_provider: String::new(),
+        input: report.total_input,
+        output: report.total_output,
+        cache_read: report.total_cache_read,
+        cache_write: report.total_cache_write,
+        reasoning: report.total_reasoning,
+        message_count: report.total_messages,
+        session_count: report.total_sessions,
+        first_date: dated()
+            .map(|e| e.first_date.clone())
+            .min()
+            .unwrap_or_default(),
+        last_date: dated()
+            .map(|e| e.last_date.clone())
+            .max()
+            .unwrap_or_default(),
+        cost: report.total_cost,
+        pricing_source: None,
+        cost_comparison: None,
+        estimated_savings: report.total_estimated_savings,
+        request_bytes: tokscale_core::sum_bytes(report.entries.iter().map(|e| e.request_bytes)),
+        response_bytes: tokscale_core::sum_bytes(report.entries.iter().map(|e| e.response_bytes)),
+        performance: aggregate_model_report_performance(&report.entries),
+    }
+}
+
 fn aggregate_model_report_performance(
     entries: &[tokscale_core::ModelUsage],
 ) -> tokscale_core::ModelPerformance {
@@ -3729,68 +6860,190 @@ fn osc8_link_with_text(url: &str, text: &str) -> String {
     }
 }
 
-fn dim_borders(table_str: &str) -> String {
-    let border_chars: &[char] = &['┌', '─', '┬', '┐', '│', '├', '┼', '┤', '└', '┴', '┘'];
-    let mut result = String::with_capacity(table_str.len() * 2);
+fn dim_borders(table_str: &str) -> String {
+    if table_style::current() != table_style::TableStyle::Unicode {
+        return table_str.to_string();
+    }
+    let border_chars: &[char] = &['┌', '─', '┬', '┐', '│', '├', '┼', '┤', '└', '┴', '┘'];
+    let mut result = String::with_capacity(table_str.len() * 2);
+
+    for ch in table_str.chars() {
+        if border_chars.contains(&ch) {
+            result.push_str("\x1b[90m");
+            result.push(ch);
+            result.push_str("\x1b[0m");
+        } else {
+            result.push(ch);
+        }
+    }
+
+    result
+}
+
+fn format_model_name(model: &str) -> String {
+    let name = model.strip_prefix("claude-").unwrap_or(model);
+    if name.len() > 9 {
+        let potential_date = &name[name.len() - 8..];
+        if potential_date.chars().all(|c| c.is_ascii_digit())
+            && name.as_bytes()[name.len() - 9] == b'-'
+        {
+            return name[..name.len() - 9].to_string();
+        }
+    }
+    name.to_string()
+}
+
+fn capitalize_client(client: &str) -> String {
+    match tokscale_core::ClientId::from_str(client) {
+        Some(id) => id.display_name().to_string(),
+        // The 9Router bridge writes gjc-format sessions under its own name.
+        None if client == "9router" => "9Router".to_string(),
+        None => client.to_string(),
+    }
+}
+
+fn run_validate_command(
+    json: bool,
+    strict: bool,
+    home_dir: Option<String>,
+    clients: Option<Vec<String>>,
+    no_spinner: bool,
+) -> Result<()> {
+    use colored::Colorize;
+    use comfy_table::{Cell, CellAlignment, Color, Table};
+    use std::collections::BTreeMap;
+    use tokscale_core::validate::{validate_sources, SourceStatus, ValidateOptions};
+
+    let spinner = if no_spinner || json {
+        None
+    } else {
+        Some(LightSpinner::start("Validating session files..."))
+    };
+    let results = validate_sources(&ValidateOptions {
+        home_dir: home_dir.clone(),
+        use_env_roots: use_env_roots(&home_dir),
+        clients,
+        scanner_settings: tui::settings::load_scanner_settings_for_home(&home_dir),
+    })
+    .map_err(|e| anyhow::anyhow!(e))?;
+    if let Some(spinner) = spinner {
+        spinner.stop();
+    }
+
+    let failed = results
+        .iter()
+        .filter(|r| r.status == SourceStatus::Failed)
+        .count();
+
+    if json {
+        #[derive(serde::Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ValidateJson<'a> {
+            files: usize,
+            failed: usize,
+            sources: &'a [tokscale_core::validate::SourceValidation],
+        }
+        let output = ValidateJson {
+            files: results.len(),
+            failed,
+            sources: &results,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else if results.is_empty() {
+        println!("\n  {}\n", "No session files found.".yellow());
+    } else {
+        #[derive(Default)]
+        struct ClientSummary {
+            files: usize,
+            parsed: usize,
+            empty: usize,
+            failed: usize,
+            messages: usize,
+            skipped_lines: usize,
+        }
+        let mut by_client: BTreeMap<&str, ClientSummary> = BTreeMap::new();
+        for result in &results {
+            let summary = by_client.entry(result.client.as_str()).or_default();
+            summary.files += 1;
+            summary.messages += result.messages;
+            summary.skipped_lines += result.skipped_lines;
+            match result.status {
+                SourceStatus::Parsed => summary.parsed += 1,
+                SourceStatus::Empty => summary.empty += 1,
+                SourceStatus::Failed => summary.failed += 1,
+            }
+        }
+
+        let mut table = Table::new();
+        table.load_preset(table_preset());
+        table.enforce_styling();
+        table.set_header(vec![
+            Cell::new("Client").fg(Color::Cyan),
+            Cell::new("Files").fg(Color::Cyan),
+            Cell::new("Parsed").fg(Color::Cyan),
+            Cell::new("Empty").fg(Color::Cyan),
+            Cell::new("Failed").fg(Color::Cyan),
+            Cell::new("Messages").fg(Color::Cyan),
+            Cell::new("Skipped Lines").fg(Color::Cyan),
+        ]);
+        for (client, summary) in &by_client {
+            let failed_cell = Cell::new(summary.failed).set_alignment(CellAlignment::Right);
+            table.add_row(vec![
+                Cell::new(capitalize_client(client)),
+                Cell::new(summary.files).set_alignment(CellAlignment::Right),
+                Cell::new(summary.parsed).set_alignment(CellAlignment::Right),
+                Cell::new(summary.empty).set_alignment(CellAlignment::Right),
+                if summary.failed > 0 {
+                    failed_cell.fg(Color::Red)
+                } else {
+                    failed_cell
+                },
+                Cell::new(format_tokens_with_commas(summary.messages as i64))
+                    .set_alignment(CellAlignment::Right),
+                Cell::new(format_tokens_with_commas(summary.skipped_lines as i64))
+                    .set_alignment(CellAlignment::Right),
+            ]);
+        }
+        println!("{table}");
 
-    for ch in table_str.chars() {
-        if border_chars.contains(&ch) {
-            result.push_str("\x1b[90m");
-            result.push(ch);
-            result.push_str("\x1b[0m");
+        let truncated = results.iter().filter(|r| r.truncated_tail).count();
+        if truncated > 0 {
+            println!(
+                "\n  {}",
+                format!(
+                    "{truncated} file(s) end in a partially written line, which was skipped; rerun once the session is idle."
+                )
+                .bright_black()
+            );
+        }
+
+        if failed > 0 {
+            println!("\n  {}", "Failed files:".red().bold());
+            for result in results.iter().filter(|r| r.status == SourceStatus::Failed) {
+                println!("  {} {}", "✗".red(), result.path.display());
+                if let Some(error) = &result.error {
+                    println!("    {}", error.bright_black());
+                }
+            }
+            println!(
+                "\n  {}\n",
+                format!("{} of {} files failed to parse", failed, results.len()).red()
+            );
         } else {
-            result.push(ch);
+            println!(
+                "\n  {}\n",
+                format!("✓ {} files checked, none failed to parse", results.len()).green()
+            );
         }
     }
 
-    result
-}
-
-fn format_model_name(model: &str) -> String {
-    let name = model.strip_prefix("claude-").unwrap_or(model);
-    if name.len() > 9 {
-        let potential_date = &name[name.len() - 8..];
-        if potential_date.chars().all(|c| c.is_ascii_digit())
-            && name.as_bytes()[name.len() - 9] == b'-'
-        {
-            return name[..name.len() - 9].to_string();
-        }
+    if strict && failed > 0 {
+        std::process::exit(1);
     }
-    name.to_string()
+    Ok(())
 }
 
-fn capitalize_client(client: &str) -> String {
-    match client {
-        "opencode" => "OpenCode".to_string(),
-        "claude" => "Claude".to_string(),
-        "codex" => "Codex".to_string(),
-        "cursor" => "Cursor".to_string(),
-        "gemini" => "Gemini".to_string(),
-        "amp" => "Amp".to_string(),
-        "codebuff" => "Codebuff".to_string(),
-        "droid" => "Droid".to_string(),
-        "crush" => "Crush".to_string(),
-        "openclaw" => "openclaw".to_string(),
-        "hermes" => "Hermes Agent".to_string(),
-        "goose" => "Goose".to_string(),
-        "warp" => "Warp".to_string(),
-        "grok" => "Grok Build".to_string(),
-        "9router" => "9Router".to_string(),
-        "pi" => "Pi".to_string(),
-        "gjc" => "Gajae-Code".to_string(),
-        "jcode" => "Jcode".to_string(),
-        "commandcode" => "Command Code".to_string(),
-        "junie" => "Junie".to_string(),
-        "zcode" => "ZCode".to_string(),
-        "codebuddy" => "CodeBuddy".to_string(),
-        "workbuddy" => "WorkBuddy".to_string(),
-        "devin-cli" => "Devin CLI".to_string(),
-        "devin-desktop" => "Devin Desktop".to_string(),
-        other => other.to_string(),
-    }
-}
-
-fn run_clients_command(json: bool, home_dir: Option<String>) -> Result<()> {
+fn run_clients_command(json: bool, paths_only: bool, home_dir: Option<String>) -> Result<()> {
     use tokscale_core::{
         built_in_extra_scan_paths_for, extra_scan_paths_for, parse_local_clients, ClientId,
         LocalParseOptions,
@@ -3816,8 +7069,10 @@ fn run_clients_command(json: bool, home_dir: Option<String>) -> Result<()> {
         ),
         since: None,
         until: None,
+        until_exclusive: false,
         year: None,
         scanner_settings: scanner_settings.clone(),
+        ..parse_flags::local_parse_options()
     })
     .map_err(|e| anyhow::anyhow!(e))?;
 
@@ -3916,6 +7171,13 @@ fn run_clients_command(json: bool, home_dir: Option<String>) -> Result<()> {
                         exists: path.exists(),
                     });
                 }
+                if client == ClientId::Aider {
+                    let path = home_dir.join(".aider.llm.history");
+                    additional_paths.push(AdditionalPath {
+                        path: path.to_string_lossy().to_string(),
+                        exists: path.exists(),
+                    });
+                }
                 if client == ClientId::DevinDesktop {
                     for root in tokscale_core::scanner::devin_desktop_additional_roots(
                         &home_dir_str,
@@ -3985,7 +7247,7 @@ fn run_clients_command(json: bool, home_dir: Option<String>) -> Result<()> {
                     ClientId::Cursor => "Cursor IDE",
                     ClientId::Kimi => "Kimi CLI",
                     ClientId::AntigravityCli => "Antigravity CLI",
-                    _ => client_ui::display_name(client),
+                    _ => client.display_name(),
                 }
                 .to_string();
 
@@ -4032,7 +7294,31 @@ fn run_clients_command(json: bool, home_dir: Option<String>) -> Result<()> {
             })
             .collect();
 
-    if json {
+    if paths_only {
+        // Same paths as the other views, filtered to the ones that exist and
+        // deduplicated (a path can be both a built-in and a configured root).
+        let mut seen = std::collections::HashSet::new();
+        for row in &clients {
+            let paths = std::iter::once((row.sessions_path.as_str(), row.sessions_path_exists))
+                .chain(
+                    row.additional_paths
+                        .iter()
+                        .map(|p| (p.path.as_str(), p.exists)),
+                )
+                .chain(row.legacy_paths.iter().map(|p| (p.path.as_str(), p.exists)))
+                .chain(row.extra_paths.iter().map(|p| (p.path.as_str(), p.exists)))
+                .chain(
+                    row.headless_paths
+                        .iter()
+                        .map(|p| (p.path.as_str(), p.exists)),
+                );
+            for (path, exists) in paths {
+                if exists && seen.insert(path) {
+                    println!("{}", path);
+                }
+            }
+        }
+    } else if json {
         #[derive(serde::Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Output {
@@ -4186,23 +7472,6 @@ fn run_clients_command(json: bool, home_dir: Option<String>) -> Result<()> {
     Ok(())
 }
 
-fn get_headless_roots(home_dir: &Path) -> Vec<PathBuf> {
-    let mut roots = Vec::new();
-
-    if let Ok(env_dir) = std::env::var("TOKSCALE_HEADLESS_DIR") {
-        roots.push(PathBuf::from(env_dir));
-    } else {
-        roots.push(home_dir.join(".config/tokscale/headless"));
-
-        #[cfg(target_os = "macos")]
-        {
-            roots.push(home_dir.join("Library/Application Support/tokscale/headless"));
-        }
-    }
-
-    roots
-}
-
 fn describe_path_for_home(path: &str, exists: bool, home: &Path) -> String {
     let path_display = path.replace(&home.to_string_lossy().to_string(), "~");
     if exists {
@@ -4222,7 +7491,7 @@ fn format_number(n: i32) -> String {
     }
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct TsTokenBreakdown {
     input: i64,
@@ -4232,7 +7501,7 @@ struct TsTokenBreakdown {
     reasoning: i64,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct TsSourceContribution {
     client: String,
@@ -4244,7 +7513,16 @@ struct TsSourceContribution {
     messages: i32,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct TsModelContribution {
+    model_id: String,
+    tokens: TsTokenBreakdown,
+    cost: f64,
+    messages: i32,
+}
+
+#[derive(serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct TsDailyTotals {
     tokens: i64,
@@ -4252,7 +7530,7 @@ struct TsDailyTotals {
     messages: i32,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct TsDailyContribution {
     date: String,
@@ -4260,17 +7538,21 @@ struct TsDailyContribution {
     intensity: u8,
     token_breakdown: TsTokenBreakdown,
     clients: Vec<TsSourceContribution>,
+    /// Per-model totals for the day, summed across clients. Only filled in
+    /// by `graph --with-models`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    models: Option<Vec<TsModelContribution>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     active_time_ms: Option<i64>,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, schemars::JsonSchema)]
 struct DateRange {
     start: String,
     end: String,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct TsYearSummary {
     year: String,
@@ -4279,7 +7561,7 @@ struct TsYearSummary {
     range: DateRange,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct TsDataSummary {
     total_tokens: i64,
@@ -4290,9 +7572,15 @@ struct TsDataSummary {
     max_cost_in_single_day: f64,
     clients: Vec<String>,
     models: Vec<String>,
+    current_streak: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_streak_range: Option<DateRange>,
+    longest_streak: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    longest_streak_range: Option<DateRange>,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct TsExportMeta {
     generated_at: String,
@@ -4300,7 +7588,7 @@ struct TsExportMeta {
     date_range: DateRange,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct TsSubmitDevice {
     id: String,
@@ -4308,7 +7596,7 @@ struct TsSubmitDevice {
     name: Option<String>,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct TsTimeMetrics {
     total_active_time_ms: i64,
@@ -4317,7 +7605,7 @@ struct TsTimeMetrics {
     session_count: u32,
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct TsTokenContributionData {
     meta: TsExportMeta,
@@ -4332,6 +7620,13 @@ struct TsTokenContributionData {
     mcp_servers: Option<Vec<String>>,
 }
 
+fn streak_range(streak: &tokscale_core::Streak) -> Option<DateRange> {
+    Some(DateRange {
+        start: streak.start?.format("%Y-%m-%d").to_string(),
+        end: streak.end?.format("%Y-%m-%d").to_string(),
+    })
+}
+
 fn to_ts_token_contribution_data(
     graph: &tokscale_core::GraphResult,
     device: Option<&device::SubmitDevice>,
@@ -4358,6 +7653,10 @@ fn to_ts_token_contribution_data(
             max_cost_in_single_day: graph.summary.max_cost_in_single_day,
             clients: graph.summary.clients.clone(),
             models: graph.summary.models.clone(),
+            current_streak: graph.summary.current_streak.days,
+            current_streak_range: streak_range(&graph.summary.current_streak),
+            longest_streak: graph.summary.longest_streak.days,
+            longest_streak_range: streak_range(&graph.summary.longest_streak),
         },
         years: graph
             .years
@@ -4412,6 +7711,7 @@ fn to_ts_token_contribution_data(
                         messages: s.messages,
                     })
                     .collect(),
+                models: None,
                 active_time_ms: d.active_time_ms,
             })
             .collect(),
@@ -4432,6 +7732,50 @@ fn to_ts_token_contribution_data(
     }
 }
 
+/// Fold a day's per-client rows into one row per model id, ordered by
+/// model id.
+fn daily_model_contributions(
+    clients: &[tokscale_core::ClientContribution],
+) -> Vec<TsModelContribution> {
+    use std::collections::BTreeMap;
+
+    let mut by_model: BTreeMap<&str, TsModelContribution> = BTreeMap::new();
+    for source in clients {
+        let entry =
+            by_model
+                .entry(source.model_id.as_str())
+                .or_insert_with(|| TsModelContribution {
+                    model_id: source.model_id.clone(),
+                    tokens: TsTokenBreakdown {
+                        input: 0,
+                        output: 0,
+                        cache_read: 0,
+                        cache_write: 0,
+                        reasoning: 0,
+                    },
+                    cost: 0.0,
+                    messages: 0,
+                });
+        entry.tokens.input = entry.tokens.input.saturating_add(source.tokens.input);
+        entry.tokens.output = entry.tokens.output.saturating_add(source.tokens.output);
+        entry.tokens.cache_read = entry
+            .tokens
+            .cache_read
+            .saturating_add(source.tokens.cache_read);
+        entry.tokens.cache_write = entry
+            .tokens
+            .cache_write
+            .saturating_add(source.tokens.cache_write);
+        entry.tokens.reasoning = entry
+            .tokens
+            .reasoning
+            .saturating_add(source.tokens.reasoning);
+        entry.cost += source.cost;
+        entry.messages = entry.messages.saturating_add(source.messages);
+    }
+    by_model.into_values().collect()
+}
+
 fn run_login_command(token: Option<String>) -> Result<()> {
     use tokio::runtime::Runtime;
 
@@ -4764,11 +8108,12 @@ fn run_time_metrics_report(
     clients: Option<Vec<String>>,
     since: Option<String>,
     until: Option<String>,
+    until_exclusive: bool,
     year: Option<String>,
     no_spinner: bool,
 ) -> Result<()> {
     use tokio::runtime::Runtime;
-    use tokscale_core::{get_time_metrics_report, GroupBy, ReportOptions};
+    use tokscale_core::{get_time_metrics_report, ReportOptions};
 
     let had_cursor_cache = has_cursor_usage_cache_for_report(&home_dir);
     let explicit_cursor_filter = client_filter_explicitly_requests_cursor(&clients);
@@ -4789,9 +8134,10 @@ fn run_time_metrics_report(
                 clients,
                 since,
                 until,
+                until_exclusive,
                 year,
-                group_by: GroupBy::default(),
                 scanner_settings: tui::settings::load_scanner_settings_for_home(&home_dir),
+                ..parse_flags::report_options()
             })
             .await
         })
@@ -4866,20 +8212,43 @@ fn format_duration_ms(ms: i64) -> String {
     }
 }
 
+/// JSON Schema (draft 2020-12) for the graph/submit payload, derived from
+/// the same structs `to_ts_token_contribution_data` serializes.
+fn graph_json_schema() -> schemars::Schema {
+    schemars::schema_for!(TsTokenContributionData)
+}
+
+fn run_schema_command(subcommand: SchemaSubcommand) -> Result<()> {
+    let schema = match subcommand {
+        SchemaSubcommand::Graph => graph_json_schema(),
+    };
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_graph_command(
     output: Option<String>,
     home_dir: Option<String>,
     clients: Option<Vec<String>>,
+    agent_filter: tokscale_core::AgentFilter,
     since: Option<String>,
     until: Option<String>,
+    until_exclusive: bool,
     year: Option<String>,
+    intensity_mode: tokscale_core::IntensityMode,
+    intensity_basis: tokscale_core::IntensityBasis,
+    active_threshold: i64,
+    with_models: bool,
+    gzip: bool,
+    json_style: JsonStyle,
+    anonymize: bool,
     benchmark: bool,
     no_spinner: bool,
 ) -> Result<()> {
     use colored::Colorize;
     use std::time::Instant;
-    use tokscale_core::{generate_local_graph_report, GroupBy, ReportOptions};
+    use tokscale_core::{generate_local_graph_report, ReportOptions};
 
     let show_progress = output.is_some() && !no_spinner;
     let had_cursor_cache = has_cursor_usage_cache_for_report(&home_dir);
@@ -4905,9 +8274,14 @@ fn run_graph_command(
                 clients,
                 since,
                 until,
+                until_exclusive,
                 year,
-                group_by: GroupBy::default(),
                 scanner_settings: tui::settings::load_scanner_settings_for_home(&home_dir),
+                intensity_mode,
+                intensity_basis,
+                agent_filter,
+                active_threshold,
+                ..parse_flags::report_options()
             })
             .await
         })
@@ -4920,11 +8294,35 @@ fn run_graph_command(
     emit_cursor_setup_warnings(&cursor_setup_warnings);
 
     let processing_time_ms = start.elapsed().as_millis() as u32;
-    let output_data = to_ts_token_contribution_data(&graph_result, None);
-    let json_output = serde_json::to_string_pretty(&output_data)?;
+    let mut output_data = to_ts_token_contribution_data(&graph_result, None);
+    if with_models {
+        for (day, source) in output_data
+            .contributions
+            .iter_mut()
+            .zip(&graph_result.contributions)
+        {
+            day.models = Some(daily_model_contributions(&source.clients));
+        }
+    }
+    let is_empty = output_data.contributions.is_empty();
+    let json_output = if anonymize {
+        let mut value = serde_json::to_value(&output_data)?;
+        anonymize::anonymize(&mut value);
+        json_style.render(&value)?
+    } else {
+        json_style.render(&output_data)?
+    };
+    let gzip = commands::export::wants_gzip(gzip, output.as_deref());
 
     if let Some(output_path) = output {
-        std::fs::write(&output_path, json_output)?;
+        if gzip {
+            std::fs::write(
+                &output_path,
+                commands::export::gzip_bytes(json_output.as_bytes())?,
+            )?;
+        } else {
+            std::fs::write(&output_path, json_output)?;
+        }
 
         eprintln!(
             "{}",
@@ -4971,11 +8369,17 @@ fn run_graph_command(
                 }
             }
         }
+    } else if gzip {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(&commands::export::gzip_bytes(
+            format!("{}\n", json_output).as_bytes(),
+        )?)?;
+        stdout.flush()?;
     } else {
         println!("{}", json_output);
     }
 
-    Ok(())
+    fail_if_empty(is_empty)
 }
 
 /// Import a third-party aggregate export (currently clawdboard) and emit it as
@@ -5367,6 +8771,160 @@ enum SubmitMode {
     Autosubmit,
 }
 
+#[allow(clippy::too_many_arguments)]
+fn run_export_sqlite_command(
+    output: String,
+    home_dir: Option<String>,
+    clients: Option<Vec<String>>,
+    since: Option<String>,
+    until: Option<String>,
+    until_exclusive: bool,
+    year: Option<String>,
+    gzip: bool,
+    no_spinner: bool,
+) -> Result<()> {
+    use colored::Colorize;
+    use tokscale_core::{parse_local_unified_messages, LocalParseOptions};
+
+    let output_path = Path::new(&output);
+    // SQLite can't write through a compressor, so a gzipped export is built
+    // as a plain database next to the target and compressed into place.
+    let gzip = commands::export::wants_gzip(gzip, Some(&output));
+    let db_path = if gzip {
+        let mut name = output_path.file_name().unwrap_or_default().to_os_string();
+        name.push(".sqlite-tmp");
+        output_path.with_file_name(name)
+    } else {
+        output_path.to_path_buf()
+    };
+
+    let had_cursor_cache = has_cursor_usage_cache_for_report(&home_dir);
+    let explicit_cursor_filter = client_filter_explicitly_requests_cursor(&clients);
+    let spinner = if no_spinner {
+        None
+    } else {
+        Some(LightSpinner::start("Scanning session data..."))
+    };
+    let cursor_sync_result = auto_sync_cursor_for_local_report(&home_dir, &clients);
+    let cursor_setup_warnings = setup_warnings_for_report(&home_dir, &clients);
+    let use_env_roots = use_env_roots(&home_dir);
+    let rt = tokio::runtime::Runtime::new()?;
+    let messages = rt
+        .block_on(async {
+            parse_local_unified_messages(LocalParseOptions {
+                home_dir: home_dir.clone(),
+                use_env_roots,
+                clients,
+                since,
+                until,
+                until_exclusive,
+                year,
+                scanner_settings: tui::settings::load_scanner_settings_for_home(&home_dir),
+                ..parse_flags::local_parse_options()
+            })
+            .await
+        })
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let rows = tokscale_core::sqlite_export::write_messages_sqlite(&db_path, messages)
+        .map_err(|e| anyhow::anyhow!(e))?;
+    if gzip {
+        let compressed = commands::export::gzip_file(&db_path, output_path);
+        let _ = std::fs::remove_file(&db_path);
+        compressed.map_err(|e| anyhow::anyhow!("Failed to write {}: {}", output, e))?;
+    }
+
+    if let Some(spinner) = spinner {
+        spinner.stop();
+    }
+    emit_cursor_sync_warning(
+        cursor_sync_result.as_ref(),
+        had_cursor_cache,
+        explicit_cursor_filter,
+    );
+    emit_cursor_setup_warnings(&cursor_setup_warnings);
+
+    eprintln!(
+        "{}",
+        format!("✓ {} messages written to {}", rows, output).green()
+    );
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_export_otlp_command(
+    endpoint: Option<String>,
+    home_dir: Option<String>,
+    clients: Option<Vec<String>>,
+    since: Option<String>,
+    until: Option<String>,
+    until_exclusive: bool,
+    year: Option<String>,
+    no_spinner: bool,
+) -> Result<()> {
+    use colored::Colorize;
+    use tokscale_core::{get_model_report, GroupBy, ReportOptions};
+
+    // Fail before scanning when the binary can't send anything.
+    commands::otlp::ensure_supported()?;
+
+    let had_cursor_cache = has_cursor_usage_cache_for_report(&home_dir);
+    let explicit_cursor_filter = client_filter_explicitly_requests_cursor(&clients);
+    let spinner = if no_spinner {
+        None
+    } else {
+        Some(LightSpinner::start("Scanning session data..."))
+    };
+    let cursor_sync_result = auto_sync_cursor_for_local_report(&home_dir, &clients);
+    let cursor_setup_warnings = setup_warnings_for_report(&home_dir, &clients);
+    let use_env_roots = use_env_roots(&home_dir);
+    let rt = tokio::runtime::Runtime::new()?;
+    let report = rt
+        .block_on(async {
+            get_model_report(ReportOptions {
+                home_dir: home_dir.clone(),
+                use_env_roots,
+                clients,
+                since,
+                until,
+                until_exclusive,
+                year,
+                group_by: GroupBy::ClientProviderModel,
+                scanner_settings: tui::settings::load_scanner_settings_for_home(&home_dir),
+                ..parse_flags::report_options()
+            })
+            .await
+        })
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let points = commands::otlp::metric_points(&report);
+    // The blocking HTTP client must not run on a Tokio worker.
+    drop(rt);
+    let pushed = commands::otlp::push(&points, endpoint.as_deref());
+
+    if let Some(spinner) = spinner {
+        spinner.stop();
+    }
+    emit_cursor_sync_warning(
+        cursor_sync_result.as_ref(),
+        had_cursor_cache,
+        explicit_cursor_filter,
+    );
+    emit_cursor_setup_warnings(&cursor_setup_warnings);
+
+    if let Err(e) = pushed {
+        return Err(exit_code::Failure::Network(format!("{e:#}")).into());
+    }
+    eprintln!(
+        "{}",
+        format!(
+            "✓ {} metric points pushed for {} report rows",
+            points.len(),
+            report.entries.len()
+        )
+        .green()
+    );
+    Ok(())
+}
+
 fn run_autosubmit_command(subcommand: commands::autosubmit::AutosubmitSubcommand) -> Result<()> {
     use commands::autosubmit::{AutosubmitRunDecision, AutosubmitSubcommand};
 
@@ -5398,7 +8956,15 @@ fn run_autosubmit_command(subcommand: commands::autosubmit::AutosubmitSubcommand
             };
 
             let (clients, since, until, year) = commands::autosubmit::submit_filters(&settings);
-            match run_submit_command(clients, since, until, year, false, SubmitMode::Autosubmit) {
+            match run_submit_command(
+                clients,
+                since,
+                until,
+                false,
+                year,
+                None,
+                SubmitMode::Autosubmit,
+            ) {
                 Ok(()) => {
                     commands::autosubmit::record_run_success(
                         chrono::Utc::now().timestamp_millis(),
@@ -5415,18 +8981,49 @@ fn run_autosubmit_command(subcommand: commands::autosubmit::AutosubmitSubcommand
     }
 }
 
+/// What `submit --dry-run` prints instead of submitting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SubmitPreview {
+    Summary,
+    /// `--show-payload`: the request body, byte for byte.
+    Payload,
+}
+
+impl SubmitPreview {
+    fn from_flags(dry_run: bool, show_payload: bool) -> Option<Self> {
+        match (dry_run, show_payload) {
+            (false, _) => None,
+            (true, false) => Some(Self::Summary),
+            (true, true) => Some(Self::Payload),
+        }
+    }
+}
+
+/// The JSON body POSTed to `/api/submit`. The auth token travels only in the
+/// `Authorization` header, so this is safe to print for `--show-payload`.
+fn submit_request_body(
+    graph: &tokscale_core::GraphResult,
+    device: &device::SubmitDevice,
+) -> Result<String> {
+    Ok(serde_json::to_string(&to_ts_token_contribution_data(
+        graph,
+        Some(device),
+    ))?)
+}
+
 fn run_submit_command(
     clients: Option<Vec<String>>,
     since: Option<String>,
     until: Option<String>,
+    until_exclusive: bool,
     year: Option<String>,
-    dry_run: bool,
+    preview: Option<SubmitPreview>,
     mode: SubmitMode,
 ) -> Result<()> {
     use colored::Colorize;
     use std::io::IsTerminal;
     use tokio::runtime::Runtime;
-    use tokscale_core::{generate_graph, GroupBy, ReportOptions};
+    use tokscale_core::{generate_graph, ReportOptions};
 
     let auth_token = match auth::resolve_api_token() {
         Some(token) => token,
@@ -5492,21 +9089,26 @@ fn run_submit_command(
     println!("{}", "  Scanning local session data...".bright_black());
 
     let rt = Runtime::new()?;
-    let mut graph_result = rt
-        .block_on(async {
-            generate_graph(ReportOptions {
-                home_dir: None,
-                use_env_roots: true,
-                clients,
-                since,
-                until,
-                year,
-                group_by: GroupBy::default(),
-                scanner_settings: tui::settings::load_scanner_settings(),
-            })
-            .await
+    // Submitted costs must be priced, so fetch pricing up front: a failure
+    // here is the only network error of the scan, and everything
+    // `generate_graph` reports after it is a local one.
+    rt.block_on(parse_flags::load_pricing()).map_err(|err| {
+        exit_code::Failure::Network(format!("Could not load pricing data: {err}"))
+    })?;
+    let graph_result = rt.block_on(async {
+        generate_graph(ReportOptions {
+            use_env_roots: true,
+            clients,
+            since,
+            until,
+            until_exclusive,
+            year,
+            scanner_settings: tui::settings::load_scanner_settings(),
+            ..parse_flags::report_options()
         })
-        .map_err(|e| anyhow::anyhow!(e))?;
+        .await
+    });
+    let mut graph_result = graph_result.map_err(|err| anyhow::anyhow!(err))?;
 
     // Preserve local-calendar contributions here. The API validator owns the
     // UTC+ timezone buffer; client-side UTC capping silently drops current-day
@@ -5558,12 +9160,27 @@ fn run_submit_command(
 
     if graph_result.summary.total_tokens == 0 {
         println!("{}", "  No usage data found to submit.\n".yellow());
-        return Ok(());
+        if mode == SubmitMode::Autosubmit {
+            return Ok(());
+        }
+        return Err(exit_code::Failure::Empty.into());
     }
 
-    if dry_run {
-        println!("{}", "  Dry run - not submitting data.\n".yellow());
-        return Ok(());
+    match preview {
+        Some(SubmitPreview::Summary) => {
+            println!("{}", "  Dry run - not submitting data.\n".yellow());
+            return Ok(());
+        }
+        Some(SubmitPreview::Payload) => {
+            let submit_device = device::resolve_submit_device()?;
+            println!(
+                "{}",
+                "  Dry run - payload that would be submitted:\n".yellow()
+            );
+            println!("{}", submit_request_body(&graph_result, &submit_device)?);
+            return Ok(());
+        }
+        None => {}
     }
 
     println!("{}", "  Submitting to server...".bright_black());
@@ -5571,14 +9188,14 @@ fn run_submit_command(
     let api_url = auth::get_api_base_url();
 
     let submit_device = device::resolve_submit_device()?;
-    let submit_payload = to_ts_token_contribution_data(&graph_result, Some(&submit_device));
+    let submit_body = submit_request_body(&graph_result, &submit_device)?;
 
     let response = rt.block_on(async {
         reqwest::Client::new()
             .post(format!("{}/api/submit", api_url))
             .header("Content-Type", "application/json")
             .header("Authorization", format!("Bearer {}", auth_token.token))
-            .json(&submit_payload)
+            .body(submit_body)
             .send()
             .await
     });
@@ -5670,12 +9287,9 @@ fn run_submit_command(
             }
         }
         Err(err) => {
-            eprintln!("\n  {}", "Error: Failed to connect to server.".red());
-            eprintln!("{}\n", format!("  {}", err).bright_black());
-            if mode == SubmitMode::Autosubmit {
-                return Err(anyhow::anyhow!("Failed to connect to server: {err}"));
-            }
-            std::process::exit(1);
+            return Err(
+                exit_code::Failure::Network(format!("Failed to connect to server: {err}")).into(),
+            );
         }
     }
 
@@ -6042,36 +9656,142 @@ fn run_warp_command(subcommand: WarpSubcommand) -> Result<()> {
     }
 }
 
+/// Token count grouped per `--locale` (commas by default).
 fn format_tokens_with_commas(n: i64) -> String {
-    let s = n.to_string();
-    let bytes = s.as_bytes();
-    let len = bytes.len();
-    let mut result = String::with_capacity(len + len / 3);
-    for (i, &b) in bytes.iter().enumerate() {
-        if i > 0 && (len - i).is_multiple_of(3) {
-            result.push(',');
-        }
-        result.push(b as char);
-    }
-    result
+    locale::current().group(n)
 }
 
 struct CaptureCommandOutcome {
     exit_code: i32,
     timed_out: bool,
+    output_paths: Vec<PathBuf>,
+}
+
+/// Destination for captured subprocess output.
+///
+/// Without a size limit everything goes to `base_path`. With `max_bytes`
+/// set, output is split across sequentially numbered siblings
+/// (`<stem>-001.<ext>`, `<stem>-002.<ext>`, ...). Rotation only happens at a
+/// line boundary so a JSONL record is never split across two files, which
+/// means a file can overshoot the limit by up to one line.
+struct CaptureWriter {
+    base_path: PathBuf,
+    max_bytes: Option<u64>,
+    current: Option<std::io::BufWriter<std::fs::File>>,
+    current_bytes: u64,
+    paths: Vec<PathBuf>,
+}
+
+impl CaptureWriter {
+    fn create(base_path: &Path, max_bytes: Option<u64>) -> Result<Self> {
+        let mut writer = Self {
+            base_path: base_path.to_path_buf(),
+            max_bytes,
+            current: None,
+            current_bytes: 0,
+            paths: Vec::new(),
+        };
+        // Open the first file up front so an unwritable output path fails
+        // before the subprocess is spawned.
+        writer.open_next()?;
+        Ok(writer)
+    }
+
+    fn rotated_path(base_path: &Path, index: usize) -> PathBuf {
+        let stem = base_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let file_name = match base_path.extension() {
+            Some(ext) => format!("{}-{:03}.{}", stem, index, ext.to_string_lossy()),
+            None => format!("{}-{:03}", stem, index),
+        };
+        base_path.with_file_name(file_name)
+    }
+
+    fn open_next(&mut self) -> Result<()> {
+        self.close_current()?;
+        let path = match self.max_bytes {
+            Some(_) => Self::rotated_path(&self.base_path, self.paths.len() + 1),
+            None => self.base_path.clone(),
+        };
+        let file = std::fs::File::create(&path).map_err(|e| {
+            anyhow::anyhow!("Failed to create output file '{}': {}", path.display(), e)
+        })?;
+        self.current = Some(std::io::BufWriter::new(file));
+        self.current_bytes = 0;
+        self.paths.push(path);
+        Ok(())
+    }
+
+    fn write(&mut self, mut data: &[u8]) -> Result<()> {
+        while !data.is_empty() {
+            if self.current.is_none() {
+                self.open_next()?;
+            }
+            let chunk_len = match self.max_bytes {
+                Some(_) => data
+                    .iter()
+                    .position(|&byte| byte == b'\n')
+                    .map_or(data.len(), |index| index + 1),
+                None => data.len(),
+            };
+            let (chunk, rest) = data.split_at(chunk_len);
+            if let Some(file) = self.current.as_mut() {
+                file.write_all(chunk)
+                    .map_err(|e| anyhow::anyhow!("Failed to write to output file: {}", e))?;
+            }
+            self.current_bytes += chunk.len() as u64;
+            if let Some(max_bytes) = self.max_bytes {
+                // The next file is opened lazily so a capture that ends right
+                // at the limit doesn't leave an empty trailing file behind.
+                if self.current_bytes >= max_bytes && chunk.ends_with(b"\n") {
+                    self.close_current()?;
+                }
+            }
+            data = rest;
+        }
+        Ok(())
+    }
+
+    fn close_current(&mut self) -> Result<()> {
+        if let Some(mut file) = self.current.take() {
+            file.flush()
+                .map_err(|e| anyhow::anyhow!("Failed to flush output file: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+fn copy_capture_output<R: std::io::Read>(mut reader: R, writer: &mut CaptureWriter) -> Result<()> {
+    let mut buffer = [0; 8192];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(n) => writer.write(&buffer[..n])?,
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to read from subprocess stdout: {}",
+                    e
+                ));
+            }
+        }
+    }
 }
 
 fn run_capture_command(
     command: &str,
     args: &[String],
     output_path: &Path,
+    max_file_bytes: Option<u64>,
     timeout: Duration,
 ) -> Result<CaptureCommandOutcome> {
-    use std::io::{Read, Write};
     use std::process::Command;
     use std::thread;
     use std::time::Instant;
 
+    let mut writer = CaptureWriter::create(output_path, max_file_bytes)?;
+
     let mut child = Command::new(command)
         .args(args)
         .stdout(std::process::Stdio::piped())
@@ -6085,31 +9805,12 @@ fn run_capture_command(
         .take()
         .ok_or_else(|| anyhow::anyhow!("Failed to capture stdout from command"))?;
 
-    let mut output_file = std::fs::File::create(output_path).map_err(|e| {
-        anyhow::anyhow!(
-            "Failed to create output file '{}': {}",
-            output_path.display(),
-            e
-        )
-    })?;
-
-    let output_handle = thread::spawn(move || -> Result<()> {
-        let mut reader = std::io::BufReader::new(stdout);
-        let mut buffer = [0; 8192];
-        loop {
-            match reader.read(&mut buffer) {
-                Ok(0) => return Ok(()),
-                Ok(n) => output_file
-                    .write_all(&buffer[..n])
-                    .map_err(|e| anyhow::anyhow!("Failed to write to output file: {}", e))?,
-                Err(e) => {
-                    return Err(anyhow::anyhow!(
-                        "Failed to read from subprocess stdout: {}",
-                        e
-                    ));
-                }
-            }
-        }
+    let output_handle = thread::spawn(move || -> (Vec<PathBuf>, Result<()>) {
+        let copied = copy_capture_output(std::io::BufReader::new(stdout), &mut writer);
+        // Flush whatever was buffered even when the read failed, so a
+        // timed-out capture still leaves its partial output on disk.
+        let flushed = writer.close_current();
+        (writer.paths, copied.and(flushed))
     });
 
     let deadline = Instant::now() + timeout;
@@ -6133,7 +9834,7 @@ fn run_capture_command(
         thread::sleep(Duration::from_millis(25));
     };
 
-    let output_result = output_handle
+    let (output_paths, output_result) = output_handle
         .join()
         .map_err(|_| anyhow::anyhow!("Subprocess stdout reader thread panicked"))?;
     if !timed_out {
@@ -6143,6 +9844,7 @@ fn run_capture_command(
     Ok(CaptureCommandOutcome {
         exit_code: status.code().unwrap_or(1),
         timed_out,
+        output_paths,
     })
 }
 
@@ -6151,6 +9853,7 @@ fn run_headless_command(
     args: Vec<String>,
     format: Option<String>,
     output: Option<String>,
+    max_file_bytes: Option<u64>,
     no_auto_flags: bool,
 ) -> Result<()> {
     use chrono::Utc;
@@ -6179,7 +9882,8 @@ fn run_headless_command(
 
     let home_dir =
         dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
-    let headless_roots = get_headless_roots(&home_dir);
+    // The roots the scanner reads from, so the capture shows up in reports.
+    let headless_roots = tokscale_core::scanner::headless_roots(&home_dir.to_string_lossy());
 
     let output_path = if let Some(custom_output) = output {
         let parent = Path::new(&custom_output)
@@ -6218,14 +9922,25 @@ fn run_headless_command(
     println!("\n  {}", "Headless capture".cyan());
     println!("  {}", format!("source: {}", source_lower).bright_black());
     println!("  {}", format!("output: {}", output_path).bright_black());
+    if let Some(max_bytes) = max_file_bytes {
+        println!(
+            "  {}",
+            format!("rotate: every {} bytes", max_bytes).bright_black()
+        );
+    }
     println!(
         "  {}",
         format!("timeout: {}s", timeout.as_secs()).bright_black()
     );
     println!();
 
-    let outcome =
-        run_capture_command(&source_lower, &final_args, Path::new(&output_path), timeout)?;
+    let outcome = run_capture_command(
+        &source_lower,
+        &final_args,
+        Path::new(&output_path),
+        max_file_bytes,
+        timeout,
+    )?;
 
     if outcome.timed_out {
         eprintln!(
@@ -6234,13 +9949,24 @@ fn run_headless_command(
         );
         eprintln!("{}", "  Partial output saved. Increase timeout with TOKSCALE_NATIVE_TIMEOUT_MS or settings.json".bright_black());
         println!();
-        std::process::exit(124);
+        return Err(exit_code::Failure::Timeout.into());
     }
 
-    println!(
-        "{}",
-        format!("✓ Saved headless output to {}", output_path).green()
-    );
+    match outcome.output_paths.as_slice() {
+        [single] => println!(
+            "{}",
+            format!("✓ Saved headless output to {}", single.display()).green()
+        ),
+        paths => {
+            println!(
+                "{}",
+                format!("✓ Saved headless output to {} files", paths.len()).green()
+            );
+            for path in paths {
+                println!("  {}", path.display().to_string().bright_black());
+            }
+        }
+    }
     println!();
 
     if outcome.exit_code != 0 {
@@ -6260,6 +9986,69 @@ mod tests {
         GraphMeta, GraphResult, TokenBreakdown,
     };
 
+    #[test]
+    fn model_report_env_prints_bare_totals() {
+        let report = tokscale_core::ModelReport {
+            entries: Vec::new(),
+            total_input: 1_000_000,
+            total_output: 200_000,
+            total_cache_read: 30_000,
+            total_cache_write: 4_567,
+            total_reasoning: 9_999,
+            total_messages: 42,
+            total_sessions: 3,
+            total_cost: 12.3449,
+            total_estimated_savings: 0.0,
+            processing_time_ms: 0,
+            omitted: None,
+            client_timings: None,
+            daily_costs: None,
+            unpriced_models: Vec::new(),
+            pricing_unavailable: false,
+        };
+
+        assert_eq!(
+            model_report_env(&report, &currency::DisplayCurrency::default(), 2),
+            "TOKSCALE_TOTAL_COST=12.34\nTOKSCALE_TOTAL_TOKENS=1244566\nTOKSCALE_TOTAL_MESSAGES=42\n"
+        );
+        let eur = currency::DisplayCurrency::new("EUR", 0.5).unwrap();
+        assert!(model_report_env(&report, &eur, 4).starts_with("TOKSCALE_TOTAL_COST=6.1725\n"));
+    }
+
+    #[test]
+    fn cost_sparkline_scales_daily_costs_to_eight_levels() {
+        assert_eq!(
+            cost_sparkline(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]),
+            "▁▂▃▄▅▆▇█"
+        );
+        assert_eq!(cost_sparkline(&[0.5, 10.0, 0.0, 5.0]), "▁█▁▅");
+        assert_eq!(cost_sparkline(&[0.0, 0.0, 0.0]), "▁▁▁");
+        assert_eq!(cost_sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_cache_savings_line_only_when_caching_saved_something() {
+        assert_eq!(cache_savings_line(0.0), None);
+        assert_eq!(
+            cache_savings_line(12.5).as_deref(),
+            Some("Cache reads saved an estimated $12.50 over input pricing")
+        );
+    }
+
+    #[test]
+    fn test_unpriced_models_warning_counts_and_lists_models() {
+        assert_eq!(unpriced_models_warning(&[]), None);
+        assert_eq!(
+            unpriced_models_warning(&["zz-invented".to_string()]).as_deref(),
+            Some("1 model had no pricing data: zz-invented")
+        );
+        let models = ["a".to_string(), "b".to_string(), "c".to_string()];
+        assert_eq!(
+            unpriced_models_warning(&models).as_deref(),
+            Some("3 models had no pricing data: a, b, c")
+        );
+    }
+
     #[test]
     fn test_parse_variant_arg_accepts_known_values() {
         assert_eq!(
@@ -6318,8 +10107,11 @@ mod tests {
             output: 0,
             cache_read: 0,
             cache_write: 0,
+            reasoning: 0,
             message_count: 1,
             cost: 0.0,
+            active_days: 1,
+            avg_cost_per_active_day: 0.0,
         };
         let entries = vec![make(i64::MAX), make(i64::MAX)];
         let (total_input, total_output, total_cache_read, total_cache_write) =
@@ -6328,28 +10120,680 @@ mod tests {
         assert_eq!(total_output, 0);
         assert_eq!(total_cache_read, 0);
         assert_eq!(total_cache_write, 0);
+        assert_eq!(monthly_total_tokens(&entries), i64::MAX);
+
+        let mut thinking = make(100);
+        thinking.reasoning = 50;
+        assert_eq!(monthly_entry_total_tokens(&thinking), 150);
+        assert_eq!(monthly_total_tokens(&[thinking, make(10)]), 160);
+    }
+
+    #[test]
+    fn model_entry_total_tokens_saturates_a_single_entrys_buckets() {
+        let entry = tokscale_core::ModelUsage {
+            client: "antigravity-cli".to_string(),
+            merged_clients: None,
+            workspace_key: None,
+            workspace_label: None,
+            session_id: None,
+            model: "gemini-3-pro".to_string(),
+            provider: "antigravity".to_string(),
+            raw_provider: "antigravity".to_string(),
+            input: i64::MAX,
+            output: 0,
+            cache_read: i64::MAX,
+            cache_write: 0,
+            reasoning: 0,
+            message_count: 1,
+            session_count: 1,
+            first_date: "2024-12-01".to_string(),
+            last_date: "2024-12-01".to_string(),
+            cost: 0.0,
+            pricing_source: None,
+            cost_comparison: None,
+            estimated_savings: 0.0,
+            request_bytes: None,
+            response_bytes: None,
+            performance: tokscale_core::ModelPerformance::default(),
+        };
+        assert_eq!(model_entry_total_tokens(&entry), i64::MAX);
+    }
+
+    #[test]
+    fn append_sessions_column_fills_entry_and_total_rows() {
+        let make = |sessions: i32| tokscale_core::ModelUsage {
+            client: "claude".to_string(),
+            merged_clients: None,
+            workspace_key: None,
+            workspace_label: None,
+            session_id: None,
+            model: "claude-sonnet-4-5".to_string(),
+            provider: "anthropic".to_string(),
+            raw_provider: "anthropic".to_string(),
+            input: 0,
+            output: 0,
+            cache_read: 0,
+            cache_write: 0,
+            reasoning: 0,
+            message_count: 1,
+            session_count: sessions,
+            first_date: "2024-12-01".to_string(),
+            last_date: "2024-12-01".to_string(),
+            cost: 0.0,
+            pricing_source: None,
+            cost_comparison: None,
+            estimated_savings: 0.0,
+            request_bytes: None,
+            response_bytes: None,
+            performance: tokscale_core::ModelPerformance::default(),
+        };
+        let report = tokscale_core::ModelReport {
+            entries: vec![make(1200), make(0)],
+            total_input: 0,
+            total_output: 0,
+            total_cache_read: 0,
+            total_cache_write: 0,
+            total_reasoning: 0,
+            total_messages: 2,
+            total_sessions: 1200,
+            total_cost: 0.0,
+            total_estimated_savings: 0.0,
+            processing_time_ms: 0,
+            omitted: None,
+            client_timings: None,
+            daily_costs: None,
+            unpriced_models: Vec::new(),
+            pricing_unavailable: false,
+        };
+
+        let mut table = comfy_table::Table::new();
+        table.set_header(vec!["Model", "Cost"]);
+        table.add_row(vec!["claude-sonnet-4-5", "$0.00"]);
+        table.add_row(vec!["(below threshold)", "$0.00"]);
+        table.add_row(vec!["Total", "$0.00"]);
+        append_sessions_column(&mut table, &report);
+
+        let last_cell = |row: &comfy_table::Row| row.cell_iter().last().unwrap().content();
+        assert_eq!(last_cell(table.header().unwrap()), "Sessions");
+        let values: Vec<String> = table.row_iter().map(last_cell).collect();
+        assert_eq!(values, vec!["1,200", "-", "1,200"]);
+    }
+
+    #[test]
+    fn append_bytes_columns_show_dashes_without_size_data() {
+        let make =
+            |request_bytes: Option<i64>, response_bytes: Option<i64>| tokscale_core::ModelUsage {
+                client: "cursor".to_string(),
+                merged_clients: None,
+                workspace_key: None,
+                workspace_label: None,
+                session_id: None,
+                model: "composer-2".to_string(),
+                provider: "cursor".to_string(),
+                raw_provider: "cursor".to_string(),
+                input: 0,
+                output: 0,
+                cache_read: 0,
+                cache_write: 0,
+                reasoning: 0,
+                message_count: 1,
+                session_count: 1,
+                first_date: "2026-04-09".to_string(),
+                last_date: "2026-04-09".to_string(),
+                cost: 0.0,
+                pricing_source: None,
+                cost_comparison: None,
+                estimated_savings: 0.0,
+                request_bytes,
+                response_bytes,
+                performance: tokscale_core::ModelPerformance::default(),
+            };
+        let report = tokscale_core::ModelReport {
+            entries: vec![make(Some(48_213), Some(9_120)), make(None, None)],
+            total_input: 0,
+            total_output: 0,
+            total_cache_read: 0,
+            total_cache_write: 0,
+            total_reasoning: 0,
+            total_messages: 2,
+            total_sessions: 2,
+            total_cost: 0.0,
+            total_estimated_savings: 0.0,
+            processing_time_ms: 0,
+            omitted: None,
+            client_timings: None,
+            daily_costs: None,
+            unpriced_models: Vec::new(),
+            pricing_unavailable: false,
+        };
+
+        let mut table = comfy_table::Table::new();
+        table.set_header(vec!["Model", "Cost"]);
+        table.add_row(vec!["composer-2", "$0.00"]);
+        table.add_row(vec!["claude-sonnet-4-5", "$0.00"]);
+        table.add_row(vec!["Total", "$0.00"]);
+        append_bytes_columns(&mut table, &report);
+
+        let tail = |row: &comfy_table::Row| {
+            row.cell_iter()
+                .skip(2)
+                .map(|cell| cell.content())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            tail(table.header().unwrap()),
+            vec!["Req Bytes", "Resp Bytes"]
+        );
+        let values: Vec<Vec<String>> = table.row_iter().map(tail).collect();
+        assert_eq!(
+            values,
+            vec![
+                vec!["48,213", "9,120"],
+                vec!["-", "-"],
+                vec!["48,213", "9,120"]
+            ]
+        );
+    }
+
+    #[test]
+    fn append_dates_columns_fills_entry_and_total_rows() {
+        let make = |first: &str, last: &str| tokscale_core::ModelUsage {
+            client: "claude".to_string(),
+            merged_clients: None,
+            workspace_key: None,
+            workspace_label: None,
+            session_id: None,
+            model: "claude-sonnet-4-5".to_string(),
+            provider: "anthropic".to_string(),
+            raw_provider: "anthropic".to_string(),
+            input: 0,
+            output: 0,
+            cache_read: 0,
+            cache_write: 0,
+            reasoning: 0,
+            message_count: 1,
+            session_count: 1,
+            first_date: first.to_string(),
+            last_date: last.to_string(),
+            cost: 0.0,
+            pricing_source: None,
+            cost_comparison: None,
+            estimated_savings: 0.0,
+            request_bytes: None,
+            response_bytes: None,
+            performance: tokscale_core::ModelPerformance::default(),
+        };
+        let report = tokscale_core::ModelReport {
+            entries: vec![
+                make("2024-12-03", "2025-01-15"),
+                make("2024-11-28", "2024-12-10"),
+                make("", ""),
+            ],
+            total_input: 0,
+            total_output: 0,
+            total_cache_read: 0,
+            total_cache_write: 0,
+            total_reasoning: 0,
+            total_messages: 3,
+            total_sessions: 2,
+            total_cost: 0.0,
+            total_estimated_savings: 0.0,
+            processing_time_ms: 0,
+            omitted: None,
+            client_timings: None,
+            daily_costs: None,
+            unpriced_models: Vec::new(),
+            pricing_unavailable: false,
+        };
+
+        let mut table = comfy_table::Table::new();
+        table.set_header(vec!["Model", "Cost"]);
+        table.add_row(vec!["claude-sonnet-4-5", "$0.00"]);
+        table.add_row(vec!["claude-opus-4-1", "$0.00"]);
+        table.add_row(vec!["(below threshold)", "$0.00"]);
+        table.add_row(vec!["Total", "$0.00"]);
+        append_dates_columns(&mut table, &report);
+
+        let tail = |row: &comfy_table::Row| {
+            let cells: Vec<String> = row.cell_iter().map(|c| c.content()).collect();
+            cells[2..].join(" ")
+        };
+        assert_eq!(tail(table.header().unwrap()), "First Last");
+        let values: Vec<String> = table.row_iter().map(tail).collect();
+        assert_eq!(
+            values,
+            vec![
+                "2024-12-03 2025-01-15",
+                "2024-11-28 2024-12-10",
+                "- -",
+                "2024-11-28 2025-01-15",
+            ]
+        );
+    }
+
+    #[test]
+    fn total_model_usage_carries_report_totals() {
+        let make = |first: &str, last: &str, cost: f64| tokscale_core::ModelUsage {
+            client: "claude".to_string(),
+            merged_clients: None,
+            workspace_key: None,
+            workspace_label: None,
+            session_id: None,
+            model: "claude-sonnet-4-5".to_string(),
+            provider: "anthropic".to_string(),
+            raw_provider: "anthropic".to_string(),
+            input: 100,
+            output: 50,
+            cache_read: 10,
+            cache_write: 5,
+            reasoning: 2,
+            message_count: 3,
+            session_count: 1,
+            first_date: first.to_string(),
+            last_date: last.to_string(),
+            cost,
+            pricing_source: None,
+            cost_comparison: None,
+            estimated_savings: 0.0,
+            request_bytes: None,
+            response_bytes: None,
+            performance: tokscale_core::ModelPerformance::default(),
+        };
+        let report = tokscale_core::ModelReport {
+            entries: vec![
+                make("2024-12-03", "2025-01-15", 1.5),
+                make("2024-11-28", "2024-12-10", 0.25),
+            ],
+            // Totals deliberately differ from the entry sums: the row must
+            // carry the precomputed totals, not re-sum the entries.
+            total_input: 1_000,
+            total_output: 500,
+            total_cache_read: 100,
+            total_cache_write: 50,
+            total_reasoning: 20,
+            total_messages: 9,
+            total_sessions: 2,
+            total_cost: 2.0,
+            total_estimated_savings: 0.0,
+            processing_time_ms: 0,
+            omitted: None,
+            client_timings: None,
+            daily_costs: None,
+            unpriced_models: Vec::new(),
+            pricing_unavailable: false,
+        };
+
+        let total = total_model_usage(&report);
+        assert_eq!(total.model, "__total__");
+        assert_eq!(total.input, report.total_input);
+        assert_eq!(total.output, report.total_output);
+        assert_eq!(total.cache_read, report.total_cache_read);
+        assert_eq!(total.cache_write, report.total_cache_write);
+        assert_eq!(total.reasoning, report.total_reasoning);
+        assert_eq!(total.message_count, report.total_messages);
+        assert_eq!(total.session_count, report.total_sessions);
+        assert_eq!(total.cost, report.total_cost);
+        assert_eq!(total.first_date, "2024-11-28");
+        assert_eq!(total.last_date, "2025-01-15");
+    }
+
+    #[test]
+    fn test_json_compact_renders_one_line_with_the_same_value() {
+        let value = serde_json::json!({"entries": [{"model": "gpt-4o", "cost": 1.5}], "total": 2});
+        let pretty = JsonStyle::Pretty.render(&value).unwrap();
+        let compact = JsonStyle::Compact.render(&value).unwrap();
+        assert!(pretty.contains('\n'));
+        assert!(!compact.contains('\n'));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&compact).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&pretty).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_compact_stands_in_for_json() {
+        assert!(Cli::try_parse_from([
+            "tokscale",
+            "models",
+            "--json-compact",
+            "--include-total-row"
+        ])
+        .is_ok());
+        assert!(
+            Cli::try_parse_from(["tokscale", "models", "--json-compact", "--markdown"]).is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["tokscale", "monthly", "--json-compact", "--markdown"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["tokscale", "models", "--json-compact", "--watch"]).is_err());
+    }
+
+    #[test]
+    fn test_headless_max_file_bytes_must_be_positive() {
+        let parse = |bytes: &str| {
+            Cli::try_parse_from([
+                "tokscale",
+                "headless",
+                "codex",
+                "--max-file-bytes",
+                bytes,
+                "exec",
+            ])
+        };
+        assert!(parse("0").is_err());
+        assert!(parse("1").is_ok());
+    }
+
+    #[test]
+    fn test_include_total_row_requires_json() {
+        assert!(Cli::try_parse_from(["tokscale", "models", "--include-total-row"]).is_err());
+        let cli =
+            Cli::try_parse_from(["tokscale", "models", "--json", "--include-total-row"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Models {
+                include_total_row: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_watch_conflicts_with_json() {
+        assert!(Cli::try_parse_from(["tokscale", "models", "--watch", "--json"]).is_err());
+        for conflicting in [
+            &["--json", "--anonymize"][..],
+            &["--summary-only"][..],
+            &["--env"][..],
+            &["--html"][..],
+        ] {
+            let args = ["tokscale", "models", "--watch"]
+                .iter()
+                .chain(conflicting)
+                .copied();
+            assert!(Cli::try_parse_from(args).is_err(), "{conflicting:?}");
+        }
+        let cli = Cli::try_parse_from(["tokscale", "models", "--watch"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Models { watch: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_no_pricing_conflicts_with_priced_options() {
+        assert!(Cli::try_parse_from(["tokscale", "models", "--no-pricing"]).is_ok());
+        for conflicting in [
+            &["--pricing-date", "2025-01-01"][..],
+            &["--min-cost", "1"],
+            &["--light", "--write-cache"],
+        ] {
+            let args = ["tokscale", "models", "--no-pricing"]
+                .into_iter()
+                .chain(conflicting.iter().copied());
+            assert!(Cli::try_parse_from(args).is_err(), "{conflicting:?}");
+        }
+    }
+
+    #[test]
+    fn test_duplicate_summary_lists_clients_with_removals() {
+        let timing = |client: &str, duplicates_removed| tokscale_core::ClientTiming {
+            client: client.to_string(),
+            scan_ms: 0.0,
+            parse_ms: 0.0,
+            file_count: 1,
+            duplicates_removed,
+        };
+        assert_eq!(
+            duplicate_summary(&[timing("claude", 0)]),
+            "No duplicate messages removed\n"
+        );
+        assert_eq!(
+            duplicate_summary(&[
+                timing("claude", 1),
+                timing("codex", 0),
+                timing("opencode", 1203)
+            ]),
+            "OpenCode: 1,203 duplicates removed\nClaude: 1 duplicate removed\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_conflicts_with_json() {
+        for command in ["models", "monthly"] {
+            assert!(Cli::try_parse_from(["tokscale", command, "--markdown", "--json"]).is_err());
+        }
+        let cli = Cli::try_parse_from(["tokscale", "monthly", "--markdown"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Monthly { markdown: true, .. })
+        ));
+    }
+
+    #[test]
+    fn cost_share_column_percentages_sum_to_one_hundred() {
+        let make = |cost: f64| tokscale_core::ModelUsage {
+            client: "claude".to_string(),
+            merged_clients: None,
+            workspace_key: None,
+            workspace_label: None,
+            session_id: None,
+            model: "claude-sonnet-4-5".to_string(),
+            provider: "anthropic".to_string(),
+            raw_provider: "anthropic".to_string(),
+            input: 0,
+            output: 0,
+            cache_read: 0,
+            cache_write: 0,
+            reasoning: 0,
+            message_count: 1,
+            session_count: 1,
+            first_date: "2024-12-01".to_string(),
+            last_date: "2024-12-01".to_string(),
+            cost,
+            pricing_source: None,
+            cost_comparison: None,
+            estimated_savings: 0.0,
+            request_bytes: None,
+            response_bytes: None,
+            performance: tokscale_core::ModelPerformance::default(),
+        };
+        let mut report = tokscale_core::ModelReport {
+            entries: vec![make(1.0), make(2.0), make(0.5)],
+            total_input: 0,
+            total_output: 0,
+            total_cache_read: 0,
+            total_cache_write: 0,
+            total_reasoning: 0,
+            total_messages: 3,
+            total_sessions: 3,
+            total_cost: 3.5,
+            total_estimated_savings: 0.0,
+            processing_time_ms: 0,
+            omitted: None,
+            client_timings: None,
+            daily_costs: None,
+            unpriced_models: Vec::new(),
+            pricing_unavailable: false,
+        };
+
+        let sum: f64 = report
+            .entries
+            .iter()
+            .map(|entry| percent_of_total(entry.cost, report.total_cost))
+            .sum();
+        assert!((sum - 100.0).abs() < 1e-9, "sum was {sum}");
+
+        let mut table = comfy_table::Table::new();
+        table.set_header(vec!["Model", "Cost"]);
+        for _ in 0..=report.entries.len() {
+            table.add_row(vec!["m", "$0.00"]);
+        }
+        append_cost_share_column(&mut table, &report, CostDisplay::default());
+        let last_cell = |row: &comfy_table::Row| row.cell_iter().last().unwrap().content();
+        assert_eq!(last_cell(table.header().unwrap()), "% Cost");
+        let values: Vec<String> = table.row_iter().map(last_cell).collect();
+        assert_eq!(values, vec!["28.6%", "57.1%", "14.3%", "100.0%"]);
+
+        for entry in &mut report.entries {
+            entry.cost = 0.0;
+        }
+        report.total_cost = 0.0;
+        let mut table = comfy_table::Table::new();
+        table.set_header(vec!["Model", "Cost"]);
+        table.add_row(vec!["m", "$0.00"]);
+        append_cost_share_column(&mut table, &report, CostDisplay::default());
+        assert_eq!(last_cell(table.row_iter().next().unwrap()), "0.0%");
+        assert_eq!(percent_of_total(0.0, 0.0), 0.0);
+
+        let unpriced = CostDisplay { unpriced: true };
+        let mut table = comfy_table::Table::new();
+        table.set_header(vec!["Model", "Cost"]);
+        table.add_row(vec!["m", "$0.00"]);
+        append_cost_share_column(&mut table, &report, unpriced);
+        assert_eq!(last_cell(table.row_iter().next().unwrap()), "—");
+        assert_eq!(unpriced.currency(1.5), "—");
+        assert_eq!(unpriced.per_million(1.5, 1_000), "—");
+        assert_ne!(CostDisplay::default().currency(1.5), "—");
+    }
+
+    #[test]
+    fn observed_model_usage_blends_cost_over_all_token_kinds() {
+        let make = |model: &str, input: i64, output: i64, cache_read: i64, cost: f64| {
+            tokscale_core::ModelUsage {
+                client: "claude".to_string(),
+                merged_clients: None,
+                workspace_key: None,
+                workspace_label: None,
+                session_id: None,
+                model: model.to_string(),
+                provider: "anthropic".to_string(),
+                raw_provider: "anthropic".to_string(),
+                input,
+                output,
+                cache_read,
+                cache_write: 0,
+                reasoning: 0,
+                message_count: 1,
+                session_count: 1,
+                first_date: "2024-12-01".to_string(),
+                last_date: "2024-12-01".to_string(),
+                cost,
+                pricing_source: None,
+                cost_comparison: None,
+                estimated_savings: 0.0,
+                request_bytes: None,
+                response_bytes: None,
+                performance: tokscale_core::ModelPerformance::default(),
+            }
+        };
+        let entries = vec![
+            make("claude-sonnet-4-5", 400_000, 100_000, 0, 3.0),
+            make("claude-sonnet-4-5", 0, 0, 500_000, 0.5),
+            make("gpt-5.5", 1_000_000, 0, 0, 9.0),
+        ];
+
+        let observed = observed_model_usage(&entries, "claude-sonnet-4-5").unwrap();
+        assert_eq!(observed.input, 400_000);
+        assert_eq!(observed.output, 100_000);
+        assert_eq!(observed.cache_read, 500_000);
+        assert_eq!(observed.total_tokens, 1_000_000);
+        assert!((observed.cost - 3.5).abs() < 1e-9);
+        assert!((observed.effective_cost_per_million - 3.5).abs() < 1e-9);
+
+        // Reasoning is billed, so it counts toward the blended rate too.
+        let mut thinking = make("o3", 500_000, 0, 0, 2.0);
+        thinking.reasoning = 500_000;
+        let observed = observed_model_usage(&[thinking], "o3").unwrap();
+        assert_eq!(observed.reasoning, 500_000);
+        assert_eq!(observed.total_tokens, 1_000_000);
+        assert!((observed.effective_cost_per_million - 2.0).abs() < 1e-9);
+
+        assert_eq!(observed_model_usage(&entries, "gemini-3-pro"), None);
+        assert_eq!(
+            observed_model_usage(
+                &[make("claude-sonnet-4-5", 0, 0, 0, 0.0)],
+                "claude-sonnet-4-5"
+            ),
+            None
+        );
+    }
+
+    /// Hands out at most `chunk` bytes per `read`, like a pipe would.
+    struct ChunkedReader {
+        data: Vec<u8>,
+        position: usize,
+        chunk: usize,
+    }
+
+    impl std::io::Read for ChunkedReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let end = (self.position + self.chunk.min(buf.len())).min(self.data.len());
+            let n = end - self.position;
+            buf[..n].copy_from_slice(&self.data[self.position..end]);
+            self.position = end;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn capture_output_rotates_at_line_boundaries() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let base = temp.path().join("codex-run.jsonl");
+        let lines: Vec<String> = (0..5).map(|i| format!("{{\"line\":{}}}\n", i)).collect();
+        let reader = ChunkedReader {
+            data: lines.concat().into_bytes(),
+            position: 0,
+            chunk: 7,
+        };
+
+        let mut writer = CaptureWriter::create(&base, Some(20)).unwrap();
+        copy_capture_output(reader, &mut writer).unwrap();
+        writer.close_current().unwrap();
+
+        let names: Vec<String> = writer
+            .paths
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "codex-run-001.jsonl",
+                "codex-run-002.jsonl",
+                "codex-run-003.jsonl"
+            ]
+        );
+        let contents: Vec<String> = writer
+            .paths
+            .iter()
+            .map(|path| std::fs::read_to_string(path).unwrap())
+            .collect();
+        assert_eq!(contents[0], lines[0..2].concat());
+        assert_eq!(contents[1], lines[2..4].concat());
+        assert_eq!(contents[2], lines[4]);
+        assert!(!base.exists());
     }
 
     #[test]
-    fn model_entry_total_tokens_saturates_a_single_entrys_buckets() {
-        let entry = tokscale_core::ModelUsage {
-            client: "antigravity-cli".to_string(),
-            merged_clients: None,
-            workspace_key: None,
-            workspace_label: None,
-            session_id: None,
-            model: "gemini-3-pro".to_string(),
-            provider: "antigravity".to_string(),
-            input: i64::MAX,
-            output: 0,
-            cache_read: i64::MAX,
-            cache_write: 0,
-            reasoning: 0,
-            message_count: 1,
-            cost: 0.0,
-            performance: tokscale_core::ModelPerformance::default(),
+    fn capture_output_without_limit_writes_a_single_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let base = temp.path().join("codex-run.jsonl");
+        let reader = ChunkedReader {
+            data: b"partial line without newline".to_vec(),
+            position: 0,
+            chunk: 4,
         };
-        assert_eq!(model_entry_total_tokens(&entry), i64::MAX);
+
+        let mut writer = CaptureWriter::create(&base, None).unwrap();
+        copy_capture_output(reader, &mut writer).unwrap();
+        writer.close_current().unwrap();
+
+        assert_eq!(writer.paths, vec![base.clone()]);
+        assert_eq!(
+            std::fs::read_to_string(&base).unwrap(),
+            "partial line without newline"
+        );
     }
 
     #[test]
@@ -6364,13 +10808,22 @@ mod tests {
             session_id: None,
             model: "gemini-3-pro".to_string(),
             provider: "antigravity".to_string(),
+            raw_provider: "antigravity".to_string(),
             input: i64::MAX,
             output: 0,
             cache_read: i64::MAX,
             cache_write: 0,
             reasoning: 0,
             message_count: 1,
+            session_count: 1,
+            first_date: "2024-12-01".to_string(),
+            last_date: "2024-12-01".to_string(),
             cost: 0.0,
+            pricing_source: None,
+            cost_comparison: None,
+            estimated_savings: 0.0,
+            request_bytes: None,
+            response_bytes: None,
             performance: tokscale_core::ModelPerformance::default(),
         };
         let entries = vec![make(), make()];
@@ -6387,6 +10840,7 @@ mod tests {
             cache_read: i64::MAX,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         };
         assert_eq!(client_token_total(&tokens), i64::MAX);
     }
@@ -6398,6 +10852,7 @@ mod tests {
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         }
     }
 
@@ -6561,6 +11016,7 @@ mod tests {
                 ClientFilter::Opencode,
                 ClientFilter::Pi,
             ],
+            exclude_clients: vec![],
         };
         assert_eq!(
             build_client_filter_with_defaults(flags, &[]),
@@ -6580,6 +11036,7 @@ mod tests {
                 ClientFilter::Claude,
                 ClientFilter::Opencode,
             ],
+            exclude_clients: vec![],
         };
         assert_eq!(
             build_client_filter_with_defaults(flags, &[]),
@@ -6587,6 +11044,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_client_filter_exclude_starts_from_every_client() {
+        let flags = ClientFlags {
+            clients: vec![],
+            exclude_clients: vec![ClientFilter::Cursor],
+        };
+        let defaults = vec!["claude".to_string()];
+        let clients = build_client_filter_with_defaults(flags, &defaults).unwrap();
+
+        assert!(!clients.contains(&"cursor".to_string()));
+        assert!(!clients.contains(&"synthetic".to_string()));
+        assert_eq!(clients.len(), ClientFilter::default_set().len() - 1);
+        assert_eq!(clients[0], "opencode");
+    }
+
+    #[test]
+    fn test_build_client_filter_exclude_applies_to_canonical_clients() {
+        let flags = ClientFlags {
+            clients: vec![
+                ClientFilter::Claude,
+                ClientFilter::Cursor,
+                ClientFilter::Codex,
+            ],
+            exclude_clients: vec![ClientFilter::Cursor, ClientFilter::Gemini],
+        };
+        assert_eq!(
+            build_client_filter_with_defaults(flags, &[]),
+            Some(vec!["claude".to_string(), "codex".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_build_client_filter_exclude_parses_repeats_and_rejects_unknown() {
+        let cli = Cli::try_parse_from([
+            "tokscale",
+            "--exclude-client",
+            "cursor,codex",
+            "--exclude-client",
+            "CLAUDE",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.clients.exclude_clients,
+            vec![
+                ClientFilter::Cursor,
+                ClientFilter::Codex,
+                ClientFilter::Claude
+            ]
+        );
+
+        let err = match Cli::try_parse_from(["tokscale", "--exclude-client", "nope"]) {
+            Ok(_) => panic!("unknown --exclude-client value should be rejected"),
+            Err(err) => err.to_string(),
+        };
+        assert!(err.contains("invalid value 'nope'"), "{err}");
+        assert!(err.contains("possible values"), "{err}");
+    }
+
     #[test]
     fn test_client_filter_as_filter_str_matches_client_id_for_overlap() {
         // Every ClientFilter variant except Synthetic and NineRouter must
@@ -6809,6 +11324,7 @@ mod tests {
         // give me X" not "I asked for X but you also added Y from settings".
         let flags = ClientFlags {
             clients: vec![ClientFilter::Codex],
+            exclude_clients: vec![],
         };
         let defaults = vec!["opencode".to_string(), "claude".to_string()];
         assert_eq!(
@@ -6823,6 +11339,7 @@ mod tests {
         // defaults ignored. CLI flags always win over settings.json.
         let flags = ClientFlags {
             clients: vec![ClientFilter::Opencode],
+            exclude_clients: vec![],
         };
         let defaults = vec!["claude".to_string()];
         assert_eq!(
@@ -7094,6 +11611,61 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_export_sqlite_command_parses() {
+        let cli = Cli::try_parse_from([
+            "tokscale",
+            "export",
+            "sqlite",
+            "--output",
+            "usage.db",
+            "--client",
+            "claude",
+            "--since",
+            "2024-12-01",
+        ])
+        .unwrap();
+        let Some(Commands::Export {
+            subcommand:
+                commands::export::ExportSubcommand::Sqlite {
+                    output,
+                    clients,
+                    date,
+                    gzip,
+                    no_spinner,
+                },
+        }) = cli.command
+        else {
+            panic!("expected export sqlite");
+        };
+        assert_eq!(output, "usage.db");
+        assert_eq!(clients.clients, vec![ClientFilter::Claude]);
+        assert_eq!(date.since.as_deref(), Some("2024-12-01"));
+        assert!(!gzip);
+        assert!(!no_spinner);
+
+        assert!(Cli::try_parse_from(["tokscale", "export", "sqlite"]).is_err());
+    }
+
+    #[test]
+    fn test_validate_command_parses() {
+        let cli = Cli::try_parse_from([
+            "tokscale", "validate", "--strict", "--json", "--client", "codex",
+        ])
+        .unwrap();
+        let Some(Commands::Validate {
+            json,
+            strict,
+            clients,
+            no_spinner,
+        }) = cli.command
+        else {
+            panic!("expected validate");
+        };
+        assert!(json && strict && !no_spinner);
+        assert_eq!(clients.clients, vec![ClientFilter::Codex]);
+    }
+
     #[test]
     fn test_login_token_option_parses() {
         let cli = Cli::try_parse_from(["tokscale", "login", "--token", "tt_ci_token"]).unwrap();
@@ -7142,6 +11714,42 @@ mod tests {
         assert_eq!(until, Some("2024-12-31".to_string()));
     }
 
+    #[test]
+    fn test_build_tui_date_filter_shifts_exclusive_until_back_a_day() {
+        let flags = DateRangeFlags {
+            since: Some("2026-02-01".to_string()),
+            until: Some("2026-03-01".to_string()),
+            until_exclusive: true,
+            ..DateRangeFlags::default()
+        };
+        assert_eq!(
+            build_tui_date_filter(&flags),
+            (
+                Some("2026-02-01".to_string()),
+                Some("2026-02-28".to_string())
+            )
+        );
+        // The report paths get the raw bound plus the flag instead.
+        assert_eq!(build_date_filter(&flags).1, Some("2026-03-01".to_string()));
+
+        let inclusive = DateRangeFlags {
+            until_exclusive: false,
+            ..flags
+        };
+        assert_eq!(
+            build_tui_date_filter(&inclusive).1,
+            Some("2026-03-01".to_string())
+        );
+    }
+
+    #[test]
+    fn test_until_exclusive_requires_until() {
+        assert!(Cli::try_parse_from(["tokscale", "--until-exclusive"]).is_err());
+        let cli = Cli::try_parse_from(["tokscale", "--until", "2026-03-01", "--until-exclusive"])
+            .expect("parse ok");
+        assert!(cli.date.until_exclusive);
+    }
+
     #[test]
     fn test_build_date_filter_no_filters() {
         let (since, until) = build_date_filter(&DateRangeFlags::default());
@@ -7149,6 +11757,44 @@ mod tests {
         assert_eq!(until, None);
     }
 
+    #[test]
+    fn test_project_monthly_budget_extrapolates_mid_month() {
+        // 10 of 30 April days elapsed: $40 so far projects to $120.
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 4, 10).unwrap();
+        let projection = project_monthly_budget(100.0, 40.0, today);
+        assert!((projection.projected - 120.0).abs() < 1e-9);
+        assert_eq!(projection.spent_so_far, 40.0);
+        assert!(projection.over_budget);
+
+        // February 2028 is a leap month (29 days).
+        let today = chrono::NaiveDate::from_ymd_opt(2028, 2, 29).unwrap();
+        let projection = project_monthly_budget(100.0, 58.0, today);
+        assert!((projection.projected - 58.0).abs() < 1e-9);
+        assert!(!projection.over_budget);
+    }
+
+    #[test]
+    fn test_project_monthly_budget_exactly_on_budget_is_not_over() {
+        // Day 15 of 30 at $50 projects to exactly the $100 cap.
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 6, 15).unwrap();
+        let projection = project_monthly_budget(100.0, 50.0, today);
+        assert_eq!(projection.projected, 100.0);
+        assert!(!projection.over_budget);
+
+        let projection = project_monthly_budget(100.0, 50.01, today);
+        assert!(projection.over_budget);
+    }
+
+    #[test]
+    fn test_budget_projection_serializes_camel_case_fields() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 6, 15).unwrap();
+        let json = serde_json::to_value(project_monthly_budget(100.0, 50.0, today)).unwrap();
+        assert_eq!(json["budget"], 100.0);
+        assert_eq!(json["spentSoFar"], 50.0);
+        assert_eq!(json["projected"], 100.0);
+        assert_eq!(json["overBudget"], false);
+    }
+
     #[test]
     fn test_build_date_filter_today_uses_provided_local_date() {
         let today = chrono::NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
@@ -7205,6 +11851,71 @@ mod tests {
         assert_eq!(until, Some("2026-03-08".to_string()));
     }
 
+    fn relative_range(since: &str, until: &str, today: chrono::NaiveDate) -> (String, String) {
+        let (since, until) = build_date_filter_for_date(
+            &DateRangeFlags {
+                since: Some(since.to_string()),
+                until: Some(until.to_string()),
+                ..DateRangeFlags::default()
+            },
+            today,
+        );
+        (since.unwrap(), until.unwrap())
+    }
+
+    #[test]
+    fn test_build_date_filter_expands_each_relative_suffix() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
+        for (raw, expected) in [
+            ("0d", "2026-03-08"),
+            ("7d", "2026-03-01"),
+            ("30D", "2026-02-06"),
+            ("2w", "2026-02-22"),
+            ("12w", "2025-12-14"),
+            ("3m", "2025-12-08"),
+            ("1y", "2025-03-08"),
+        ] {
+            assert_eq!(
+                relative_range(raw, "0d", today),
+                (expected.to_string(), "2026-03-08".to_string()),
+                "{raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_date_filter_relative_months_and_years_clamp() {
+        let jan_31 = chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap();
+        assert_eq!(relative_range("1m", "0d", jan_31).0, "2025-12-31");
+
+        let mar_31 = chrono::NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        assert_eq!(relative_range("1m", "0d", mar_31).0, "2024-02-29");
+        assert_eq!(relative_range("13m", "0d", mar_31).0, "2023-02-28");
+
+        let leap_day = chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(relative_range("1y", "0d", leap_day).0, "2023-02-28");
+    }
+
+    #[test]
+    fn test_build_date_filter_keeps_absolute_dates() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
+        assert_eq!(
+            relative_range("2025-01-01", "2025-12-31", today),
+            ("2025-01-01".to_string(), "2025-12-31".to_string())
+        );
+    }
+
+    #[test]
+    fn test_date_bound_arg_rejects_out_of_range_relative_dates() {
+        assert!(parse_date_bound_arg("30d").is_ok());
+        assert!(parse_date_bound_arg("2025-01-01").is_ok());
+        assert!(parse_date_bound_arg("99999999y").is_err());
+        assert!(Cli::try_parse_from(["tokscale", "--since", "99999999999d"]).is_err());
+
+        let cli = Cli::try_parse_from(["tokscale", "--since", "30d"]).unwrap();
+        assert_eq!(cli.date.since.as_deref(), Some("30d"));
+    }
+
     #[test]
     fn test_normalize_year_filter_with_year() {
         let year = normalize_year_filter(&DateRangeFlags {
@@ -7344,21 +12055,75 @@ mod tests {
     }
 
     #[test]
+    #[serial_test::serial]
     fn test_format_currency_zero() {
         assert_eq!(format_currency(0.0), "$0.00");
     }
 
     #[test]
+    #[serial_test::serial]
     fn test_format_currency_small() {
         assert_eq!(format_currency(12.34), "$12.34");
     }
 
     #[test]
+    #[serial_test::serial]
     fn test_format_currency_large() {
         assert_eq!(format_currency(1234.56), "$1234.56");
     }
 
     #[test]
+    fn test_format_currency_with_precision() {
+        assert_eq!(format_currency_with_precision(0.003, 0), "$0");
+        assert_eq!(format_currency_with_precision(12.5, 0), "$12");
+        assert_eq!(format_currency_with_precision(1234.56, 0), "$1235");
+
+        assert_eq!(format_currency_with_precision(0.003, 2), "$0.00");
+        assert_eq!(format_currency_with_precision(12.345, 2), "$12.35");
+        assert_eq!(format_currency_with_precision(1234.56, 2), "$1234.56");
+
+        assert_eq!(format_currency_with_precision(0.003, 4), "$0.0030");
+        assert_eq!(format_currency_with_precision(0.00012345, 4), "$0.0001");
+        assert_eq!(format_currency_with_precision(12.34567, 4), "$12.3457");
+    }
+
+    #[test]
+    fn test_currency_precision_flag_parses_and_is_bounded() {
+        let cli = Cli::try_parse_from(["tokscale"]).unwrap();
+        assert_eq!(cli.currency_precision, 2);
+
+        let cli = Cli::try_parse_from(["tokscale", "models", "--currency-precision", "4"]).unwrap();
+        assert_eq!(cli.currency_precision, 4);
+
+        assert!(Cli::try_parse_from(["tokscale", "--currency-precision", "7"]).is_err());
+    }
+
+    #[test]
+    fn test_pricing_date_flag_is_global_and_validated() {
+        let cli =
+            Cli::try_parse_from(["tokscale", "models", "--pricing-date", "2025-02-01"]).unwrap();
+        assert_eq!(
+            cli.pricing_date,
+            chrono::NaiveDate::from_ymd_opt(2025, 2, 1)
+        );
+
+        assert!(Cli::try_parse_from(["tokscale", "--pricing-date", "last week"]).is_err());
+    }
+
+    #[test]
+    fn test_cost_source_flag_is_global_and_validated() {
+        let cli =
+            Cli::try_parse_from(["tokscale", "monthly", "--cost-source", "openrouter"]).unwrap();
+        assert_eq!(
+            cli.cost_source,
+            Some(tokscale_core::pricing::CostSourcePreference::Openrouter)
+        );
+
+        assert!(Cli::try_parse_from(["tokscale", "--cost-source", "cursor"]).is_err());
+    }
+
+    #[test]
+    #[serial_test::serial]
     fn test_format_currency_rounds() {
         assert_eq!(format_currency(12.345), "$12.35");
         assert_eq!(format_currency(12.344), "$12.34");
@@ -7406,7 +12171,7 @@ mod tests {
 
     #[test]
     fn test_capitalize_client_openclaw() {
-        assert_eq!(capitalize_client("openclaw"), "openclaw");
+        assert_eq!(capitalize_client("openclaw"), "OpenClaw");
     }
 
     #[test]
@@ -7538,6 +12303,31 @@ mod tests {
         assert!(frame.contains("⬝"));
     }
 
+    #[test]
+    fn test_light_spinner_frame_plain_has_no_escape_sequences() {
+        for frame in 0..64 {
+            let plain = LightSpinner::frame_plain(frame);
+            assert!(!plain.contains('\x1b'), "frame {frame}: {plain:?}");
+            assert_eq!(plain.chars().count(), LightSpinner::WIDTH);
+            assert!(plain.contains('■'));
+
+            let stripped = LightSpinner::frame(frame)
+                .replace("\x1b[0m", "")
+                .split("\x1b[38;5;")
+                .map(|part| part.split_once('m').map_or(part, |(_, rest)| rest))
+                .collect::<String>();
+            assert_eq!(plain, stripped, "frame {frame}");
+        }
+    }
+
+    #[test]
+    fn test_spinner_style_detect() {
+        assert_eq!(SpinnerStyle::detect(true, false), SpinnerStyle::Color);
+        assert_eq!(SpinnerStyle::detect(true, true), SpinnerStyle::Plain);
+        assert_eq!(SpinnerStyle::detect(false, false), SpinnerStyle::Hidden);
+        assert_eq!(SpinnerStyle::detect(false, true), SpinnerStyle::Hidden);
+    }
+
     #[test]
     fn test_light_spinner_scanner_state_forward_start() {
         let (position, forward) = LightSpinner::scanner_state(0);
@@ -7739,6 +12529,173 @@ mod tests {
         assert_eq!(graph.summary.total_cost, 0.0);
     }
 
+    fn parse_with_defaults(args: &[&str], defaults: &cli_defaults::CliDefaults) -> Cli {
+        let matches = Cli::command().try_get_matches_from(args).expect("parse ok");
+        let mut cli = Cli::from_arg_matches(&matches).expect("parse ok");
+        apply_cli_defaults(&mut cli, &matches, defaults);
+        cli
+    }
+
+    #[test]
+    fn test_cli_defaults_fill_unset_flags() {
+        let defaults = cli_defaults::CliDefaults {
+            theme: Some("purple".to_string()),
+            group_by: Some("client,provider,model".to_string()),
+            refresh: Some(30),
+            clients: None,
+            no_spinner: Some(true),
+        };
+
+        let cli = parse_with_defaults(&["tokscale"], &defaults);
+        assert_eq!(cli.theme.as_deref(), Some("purple"));
+        assert_eq!(cli.group_by, "client,provider,model");
+        assert_eq!(cli.refresh, 30);
+        assert!(cli.no_spinner);
+
+        let cli = parse_with_defaults(&["tokscale", "models"], &defaults);
+        match cli.command {
+            Some(Commands::Models {
+                group_by,
+                no_spinner,
+                ..
+            }) => {
+                assert_eq!(group_by, "client,provider,model");
+                assert!(no_spinner);
+            }
+            _ => panic!("expected models command"),
+        }
+
+        for subcommand in ["validate", "forecast", "top-sessions"] {
+            let mut cli = parse_with_defaults(&["tokscale", subcommand], &defaults);
+            let no_spinner = cli.command.as_mut().and_then(Commands::no_spinner_mut);
+            assert_eq!(no_spinner.copied(), Some(true), "{subcommand}");
+        }
+    }
+
+    #[test]
+    fn test_cli_flags_override_cli_defaults() {
+        let defaults = cli_defaults::CliDefaults {
+            theme: Some("purple".to_string()),
+            group_by: Some("client,provider,model".to_string()),
+            refresh: Some(30),
+            clients: None,
+            no_spinner: Some(false),
+        };
+
+        // Passing the built-in default value explicitly still counts as a flag.
+        let cli = parse_with_defaults(
+            &[
+                "tokscale",
+                "--theme",
+                "green",
+                "--refresh",
+                "0",
+                "--group-by",
+                "client,model",
+            ],
+            &defaults,
+        );
+        assert_eq!(cli.theme.as_deref(), Some("green"));
+        assert_eq!(cli.refresh, 0);
+        assert_eq!(cli.group_by, "client,model");
+
+        let cli = parse_with_defaults(
+            &["tokscale", "models", "--group-by", "model", "--no-spinner"],
+            &defaults,
+        );
+        match cli.command {
+            Some(Commands::Models {
+                group_by,
+                no_spinner,
+                ..
+            }) => {
+                assert_eq!(group_by, "model");
+                assert!(no_spinner);
+            }
+            _ => panic!("expected models command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_defaults_leave_builtin_values_when_unset() {
+        let cli = parse_with_defaults(&["tokscale"], &cli_defaults::CliDefaults::default());
+        assert_eq!(cli.theme, None);
+        assert_eq!(cli.group_by, "client,model");
+        assert_eq!(cli.refresh, 0);
+        assert!(!cli.no_spinner);
+    }
+
+    #[test]
+    fn test_graph_schema_validates_contribution_payload() {
+        let mut graph = graph_result_with_contributions(vec![daily_contribution(
+            "2026-12-31",
+            20,
+            2.50,
+            "codex",
+            "model-b",
+        )]);
+        graph.contributions[0].active_time_ms = Some(60_000);
+        let device = device::SubmitDevice {
+            id: "dev_test".to_string(),
+            name: None,
+        };
+        let schema = serde_json::to_value(graph_json_schema()).unwrap();
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        for payload in [
+            to_ts_token_contribution_data(&graph, Some(&device)),
+            to_ts_token_contribution_data(&graph, None),
+        ] {
+            let instance = serde_json::to_value(&payload).unwrap();
+            let errors: Vec<String> = validator
+                .iter_errors(&instance)
+                .map(|error| error.to_string())
+                .collect();
+            assert!(errors.is_empty(), "schema rejected payload: {errors:?}");
+        }
+
+        let mut broken = serde_json::to_value(to_ts_token_contribution_data(&graph, None)).unwrap();
+        broken["contributions"][0]["intensity"] = serde_json::json!("high");
+        assert!(!validator.is_valid(&broken));
+    }
+
+    #[test]
+    fn test_daily_model_contributions_split_a_day_by_model() {
+        let mut day = daily_contribution("2026-03-01", 30, 3.0, "codex", "model-a");
+        day.clients.push(ClientContribution {
+            client: "claude".to_string(),
+            model_id: "model-b".to_string(),
+            provider_id: "anthropic".to_string(),
+            tokens: token_breakdown(5),
+            cost: 0.5,
+            messages: 2,
+        });
+        day.clients.push(ClientContribution {
+            client: "opencode".to_string(),
+            model_id: "model-a".to_string(),
+            provider_id: "openai".to_string(),
+            tokens: token_breakdown(7),
+            cost: 0.25,
+            messages: 1,
+        });
+
+        let models = daily_model_contributions(&day.clients);
+
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].model_id, "model-a");
+        assert_eq!(models[0].tokens.input, 37);
+        assert!((models[0].cost - 3.25).abs() < 1e-9);
+        assert_eq!(models[0].messages, 2);
+        assert_eq!(models[1].model_id, "model-b");
+        assert_eq!(models[1].tokens.input, 5);
+        assert!((models[1].cost - 0.5).abs() < 1e-9);
+        assert_eq!(models[1].messages, 2);
+    }
+
     #[test]
     fn test_submit_payload_includes_device_when_provided() {
         let graph = graph_result_with_contributions(vec![daily_contribution(
@@ -7762,6 +12719,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dry_run_payload_is_the_submitted_body() {
+        let graph = graph_result_with_contributions(vec![daily_contribution(
+            "2026-12-31",
+            20,
+            2.50,
+            "codex",
+            "model-b",
+        )]);
+        let device = device::SubmitDevice {
+            id: "dev_test".to_string(),
+            name: None,
+        };
+
+        let body = submit_request_body(&graph, &device).unwrap();
+
+        assert!(!body.contains('\n'));
+        let printed: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            printed,
+            serde_json::to_value(to_ts_token_contribution_data(&graph, Some(&device))).unwrap()
+        );
+        assert_eq!(printed["device"]["id"], "dev_test");
+        let keys: Vec<&str> = printed
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert!(
+            keys.iter().all(|key| !key.to_lowercase().contains("token")),
+            "payload has a token-like key: {keys:?}"
+        );
+        assert_eq!(
+            SubmitPreview::from_flags(true, true),
+            Some(SubmitPreview::Payload)
+        );
+        assert_eq!(SubmitPreview::from_flags(false, false), None);
+    }
+
     #[test]
     #[cfg(target_os = "macos")]
     #[serial_test::serial]
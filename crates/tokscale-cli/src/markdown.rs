@@ -0,0 +1,150 @@
+//! `--markdown` output: the `--light` report table as a GitHub-flavored
+//! markdown table, for pasting into issues, PRs, and docs.
+//!
+//! Rendering works from a populated [`comfy_table::Table`] so the columns
+//! always match the box-drawn view. comfy-table does not expose cell
+//! alignment, so a column is right-aligned when every body cell looks like a
+//! number (counts, `$` amounts, rates, percentages, or an empty/`-`
+//! placeholder).
+
+use comfy_table::{Row, Table};
+
+pub fn render(table: &Table) -> String {
+    let header: Vec<String> = table
+        .header()
+        .map(|row| {
+            row.cell_iter()
+                .map(|cell| escape(&cell.content()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let rows: Vec<Vec<String>> = table.row_iter().map(row_cells).collect();
+    let columns = rows
+        .iter()
+        .map(Vec::len)
+        .chain(std::iter::once(header.len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    push_row(&mut out, &header, columns);
+    let separator: Vec<String> = (0..columns)
+        .map(|index| {
+            let numeric = rows
+                .iter()
+                .filter_map(|row| row.get(index))
+                .all(|cell| is_numeric(cell));
+            if numeric { "---:" } else { "---" }.to_string()
+        })
+        .collect();
+    push_row(&mut out, &separator, columns);
+    for row in &rows {
+        push_row(&mut out, row, columns);
+    }
+    out
+}
+
+fn row_cells(row: &Row) -> Vec<String> {
+    row.cell_iter()
+        .map(|cell| escape(&cell.content()))
+        .collect()
+}
+
+fn push_row(out: &mut String, cells: &[String], columns: usize) {
+    out.push('|');
+    for index in 0..columns {
+        out.push(' ');
+        out.push_str(cells.get(index).map(String::as_str).unwrap_or(""));
+        out.push_str(" |");
+    }
+    out.push('\n');
+}
+
+/// Pipes would end the cell early and newlines the row; multi-line cells
+/// (e.g. the monthly model list) become `<br>`-separated. Angle brackets are
+/// entity-escaped so ids like `<synthetic>` aren't swallowed as HTML tags.
+fn escape(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| {
+            line.trim()
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('|', "\\|")
+        })
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+fn is_numeric(cell: &str) -> bool {
+    if matches!(cell, "" | "-" | "—") {
+        return true;
+    }
+    let value = cell.trim_start_matches('$');
+    let value = ["/M", "ms", "s", "%"]
+        .iter()
+        .find_map(|suffix| value.strip_suffix(suffix))
+        .unwrap_or(value);
+    // Strip every `--locale` grouping / decimal mark; only digit-ness matters.
+    let digits: String = value
+        .chars()
+        .filter(|c| !matches!(c, ',' | '.' | '\u{202f}'))
+        .collect();
+    digits.parse::<f64>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use comfy_table::Cell;
+
+    #[test]
+    fn renders_header_separator_and_rows() {
+        let mut table = Table::new();
+        table.set_header(vec!["Model", "Input", "Cost", "Cost/1M"]);
+        table.add_row(vec![
+            Cell::new("claude-sonnet-4-5"),
+            Cell::new("1,234"),
+            Cell::new("$1.50"),
+            Cell::new("$3.00/M"),
+        ]);
+        table.add_row(vec![
+            Cell::new("- <synthetic>\n- o3|mini"),
+            Cell::new("0"),
+            Cell::new("$0.00"),
+            Cell::new("—"),
+        ]);
+        table.add_row(vec!["Total", "1,234", "$1.50", "$3.00/M"]);
+
+        assert_eq!(
+            render(&table),
+            "| Model | Input | Cost | Cost/1M |\n\
+             | --- | ---: | ---: | ---: |\n\
+             | claude-sonnet-4-5 | 1,234 | $1.50 | $3.00/M |\n\
+             | - &lt;synthetic&gt;<br>- o3\\|mini | 0 | $0.00 | — |\n\
+             | Total | 1,234 | $1.50 | $3.00/M |\n"
+        );
+    }
+
+    #[test]
+    fn placeholders_and_units_count_as_numeric() {
+        for cell in [
+            "",
+            "-",
+            "—",
+            "12",
+            "1,200",
+            "$0.25",
+            "450ms",
+            "1.2s",
+            "12.5%",
+            "1.234,56",
+            "$1\u{202f}234,56",
+        ] {
+            assert!(is_numeric(cell), "{cell}");
+        }
+        for cell in ["claude", "2024-12", "Total", "Anthropic"] {
+            assert!(!is_numeric(cell), "{cell}");
+        }
+    }
+}
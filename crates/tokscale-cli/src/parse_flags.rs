@@ -0,0 +1,120 @@
+//! Global flags that change how sessions are parsed and priced, such as
+//! `--cost-source`, `--pricing-date`, `--max-future-days`, `--no-dedup` and
+//! `--threads`.
+//!
+//! tokscale-core takes these per call, as fields of `ReportOptions` and
+//! `LocalParseOptions`. `run` records them once here, and every command
+//! starts the options it builds from [`report_options`] or
+//! [`local_parse_options`] so none of them can forget a flag.
+
+use std::sync::{Arc, OnceLock};
+
+use tokscale_core::pricing::{CostSourcePreference, PricingService};
+use tokscale_core::{LocalParseOptions, ReportOptions};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseFlags {
+    pub cost_source: CostSourcePreference,
+    pub max_future_days: Option<u32>,
+    pub no_dedup: bool,
+    pub threads: usize,
+    pub pricing_date: Option<chrono::NaiveDate>,
+}
+
+static CURRENT: OnceLock<ParseFlags> = OnceLock::new();
+
+/// Record the flags for this run. The first call wins, mirroring
+/// `cli_defaults::set_global`.
+pub fn set_current(flags: ParseFlags) {
+    let _ = CURRENT.set(flags);
+}
+
+pub fn current() -> ParseFlags {
+    CURRENT.get().copied().unwrap_or_default()
+}
+
+impl ParseFlags {
+    /// Default report options with these flags filled in.
+    pub fn report_options(self) -> ReportOptions {
+        ReportOptions {
+            cost_source: self.cost_source,
+            max_future_days: self.max_future_days,
+            no_dedup: self.no_dedup,
+            threads: self.threads,
+            pricing_date: self.pricing_date,
+            ..Default::default()
+        }
+    }
+
+    /// Default local-parse options with these flags filled in.
+    pub fn local_parse_options(self) -> LocalParseOptions {
+        LocalParseOptions {
+            cost_source: self.cost_source,
+            max_future_days: self.max_future_days,
+            no_dedup: self.no_dedup,
+            threads: self.threads,
+            pricing_date: self.pricing_date,
+            ..Default::default()
+        }
+    }
+}
+
+/// [`ParseFlags::report_options`] for this run.
+pub fn report_options() -> ReportOptions {
+    current().report_options()
+}
+
+/// [`ParseFlags::local_parse_options`] for this run.
+pub fn local_parse_options() -> LocalParseOptions {
+    current().local_parse_options()
+}
+
+/// `pricing` as this run prices costs, for the commands that load pricing
+/// themselves instead of through a core report.
+pub fn pricing(pricing: &PricingService) -> PricingService {
+    pricing.with_cost_source(current().cost_source)
+}
+
+/// Pricing data for this run: the `--pricing-date` snapshot when one is
+/// set, otherwise live data.
+pub async fn load_pricing() -> Result<Arc<PricingService>, String> {
+    PricingService::load(current().pricing_date).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_are_copied_into_both_option_types() {
+        let flags = ParseFlags {
+            cost_source: CostSourcePreference::Openrouter,
+            max_future_days: Some(3),
+            no_dedup: true,
+            threads: 2,
+            pricing_date: chrono::NaiveDate::from_ymd_opt(2025, 2, 1),
+        };
+        assert_eq!(
+            flags.report_options().cost_source,
+            CostSourcePreference::Openrouter
+        );
+        assert_eq!(
+            flags.local_parse_options().cost_source,
+            CostSourcePreference::Openrouter
+        );
+        assert_eq!(flags.report_options().max_future_days, Some(3));
+        assert_eq!(flags.local_parse_options().max_future_days, Some(3));
+        assert!(flags.report_options().no_dedup);
+        assert!(flags.local_parse_options().no_dedup);
+        assert_eq!(flags.report_options().threads, 2);
+        assert_eq!(flags.local_parse_options().threads, 2);
+        assert_eq!(
+            flags.local_parse_options().pricing_date,
+            chrono::NaiveDate::from_ymd_opt(2025, 2, 1)
+        );
+        assert_eq!(
+            ParseFlags::default().report_options().cost_source,
+            CostSourcePreference::Auto
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! `--table-style`: border characters for the static report tables.
+//!
+//! Box-drawing borders turn into mojibake in some terminals and CI logs, so
+//! `ascii` draws them with `+-|` and `none` drops them, leaving columns
+//! separated by whitespace. Without the flag, tables use box drawing when
+//! stdout is a terminal and ASCII otherwise.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use clap::ValueEnum;
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TableStyle {
+    #[default]
+    Unicode,
+    Ascii,
+    None,
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(TableStyle::Unicode as u8);
+
+impl TableStyle {
+    /// The requested style, or the default for where stdout goes.
+    pub fn resolve(flag: Option<Self>, stdout_is_terminal: bool) -> Self {
+        match flag {
+            Some(style) => style,
+            None if stdout_is_terminal => Self::Unicode,
+            None => Self::Ascii,
+        }
+    }
+
+    /// `comfy_table` preset string. Spaces leave a border out entirely.
+    pub fn preset(self) -> &'static str {
+        match self {
+            Self::Unicode => "││──├─┼┤│─┼├┤┬┴┌┐└┘",
+            Self::Ascii => "||--+-++|-+++++++++",
+            Self::None => comfy_table::presets::NOTHING,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Ascii,
+            2 => Self::None,
+            _ => Self::Unicode,
+        }
+    }
+}
+
+/// Select the table style for this process.
+pub fn set_current(style: TableStyle) {
+    CURRENT.store(style as u8, Ordering::Relaxed);
+}
+
+pub fn current() -> TableStyle {
+    TableStyle::from_u8(CURRENT.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use comfy_table::Table;
+
+    fn render(style: TableStyle) -> String {
+        let mut table = Table::new();
+        table.load_preset(style.preset());
+        table.set_header(vec!["Model", "Cost"]);
+        table.add_row(vec!["gpt-5", "$1.00"]);
+        table.add_row(vec!["claude-sonnet-4", "$2.50"]);
+        table.to_string()
+    }
+
+    #[test]
+    fn ascii_and_none_styles_contain_no_box_drawing() {
+        let is_box_drawing = |c: char| ('\u{2500}'..='\u{257f}').contains(&c);
+
+        let ascii = render(TableStyle::Ascii);
+        assert!(!ascii.chars().any(is_box_drawing), "{ascii}");
+        assert!(
+            ascii.starts_with("+-") && ascii.contains("| gpt-5"),
+            "{ascii}"
+        );
+
+        let none = render(TableStyle::None);
+        assert!(!none.chars().any(is_box_drawing), "{none}");
+        assert!(!none.contains(['|', '+']), "{none}");
+        assert!(none.contains("gpt-5") && none.contains("$2.50"), "{none}");
+
+        assert!(render(TableStyle::Unicode).chars().any(is_box_drawing));
+    }
+
+    #[test]
+    fn defaults_to_ascii_off_a_terminal() {
+        assert_eq!(TableStyle::resolve(None, true), TableStyle::Unicode);
+        assert_eq!(TableStyle::resolve(None, false), TableStyle::Ascii);
+        assert_eq!(
+            TableStyle::resolve(Some(TableStyle::Unicode), false),
+            TableStyle::Unicode
+        );
+        assert_eq!(
+            TableStyle::resolve(Some(TableStyle::None), true),
+            TableStyle::None
+        );
+    }
+}
@@ -19,8 +19,8 @@ use super::codex_login::{
     CodexLoginOutcome,
 };
 use super::data::{
-    AgentUsage, DailyUsage, DataLoader, HourlyUsage, MinutelyUsage, ModelUsage, MonthlyUsage,
-    SessionUsage, TokenBreakdown, UsageData,
+    AgentUsage, DailyUsage, DataLoader, HourlyUsage, MinutelyUsage, ModelDailyUsage, ModelUsage,
+    MonthlyUsage, SessionUsage, TokenBreakdown, UsageData,
 };
 use super::privacy::looks_like_email;
 use super::settings::Settings;
@@ -186,6 +186,35 @@ pub struct DailyDetailRow<'a> {
     pub messages: u64,
 }
 
+/// Identifies the row opened in the Models-tab drill-down. Matches on the
+/// same fields `get_sorted_models` breaks ties with, so the drill-down
+/// survives re-sorting and data refreshes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelDetailKey {
+    pub model: String,
+    pub provider: String,
+    pub client: String,
+    pub workspace_key: Option<String>,
+}
+
+impl ModelDetailKey {
+    pub fn of(model: &ModelUsage) -> Self {
+        Self {
+            model: model.model.clone(),
+            provider: model.provider.clone(),
+            client: model.client.clone(),
+            workspace_key: model.workspace_key.clone(),
+        }
+    }
+
+    fn matches(&self, model: &ModelUsage) -> bool {
+        self.model == model.model
+            && self.provider == model.provider
+            && self.client == model.client
+            && self.workspace_key == model.workspace_key
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ClickAction {
     Tab(Tab),
@@ -320,6 +349,10 @@ pub struct App {
     monthly_list_selected_index: usize,
     monthly_list_scroll_offset: usize,
 
+    pub selected_model_detail: Option<ModelDetailKey>,
+    models_list_selected_index: usize,
+    models_list_scroll_offset: usize,
+
     pub selected_graph_cell: Option<(usize, usize)>,
     pub stats_breakdown_total_lines: usize,
 
@@ -363,6 +396,8 @@ pub struct App {
     usage_rx: Option<std::sync::mpsc::Receiver<UsageFetchReport>>,
     usage_fetch_preserve_status: bool,
     usage_fetcher: fn() -> UsageFetchReport,
+    /// Where `e` exports are written; the working directory by default.
+    pub(crate) export_dir: std::path::PathBuf,
     codex_reset_rx: Option<
         std::sync::mpsc::Receiver<
             Result<crate::commands::usage::codex::RateLimitResetConsumeResult, String>,
@@ -431,7 +466,9 @@ impl App {
             config.until,
             config.year,
         )
-        .with_minutely_enabled(settings.minutely_tab_enabled);
+        .with_minutely_enabled(settings.minutely_tab_enabled)
+        .with_graph_days(settings.graph_days)
+        .with_intensity_basis(settings.graph_intensity_basis);
 
         let data = cached_data.unwrap_or_default();
         let has_data = !data.models.is_empty();
@@ -470,6 +507,9 @@ impl App {
             selected_monthly_detail_month: None,
             monthly_list_selected_index: 0,
             monthly_list_scroll_offset: 0,
+            selected_model_detail: None,
+            models_list_selected_index: 0,
+            models_list_scroll_offset: 0,
             selected_graph_cell: None,
             stats_breakdown_total_lines: 0,
             auto_refresh,
@@ -522,6 +562,7 @@ impl App {
                     default_usage_fetcher
                 }
             },
+            export_dir: std::path::PathBuf::new(),
             codex_reset_rx: None,
             codex_login_rx: None,
             codex_login_child: None,
@@ -568,6 +609,15 @@ impl App {
             }
         }
 
+        // Same for the Models drill-down: exit if the model disappeared.
+        if let Some(ref detail) = self.selected_model_detail {
+            if !self.data.models.iter().any(|m| detail.matches(m)) {
+                self.selected_model_detail = None;
+                self.selected_index = self.models_list_selected_index;
+                self.scroll_offset = self.models_list_scroll_offset;
+            }
+        }
+
         self.clamp_selection();
     }
 
@@ -919,6 +969,9 @@ impl App {
             KeyCode::Enter if self.current_tab == Tab::Monthly => {
                 self.open_selected_monthly_detail();
             }
+            KeyCode::Enter if self.current_tab == Tab::Models => {
+                self.open_selected_model_detail();
+            }
             KeyCode::Enter if self.current_tab == Tab::Stats => {
                 self.handle_graph_selection();
             }
@@ -932,6 +985,11 @@ impl App {
             {
                 self.close_monthly_detail();
             }
+            KeyCode::Esc | KeyCode::Backspace
+                if self.current_tab == Tab::Models && self.is_model_detail_active() =>
+            {
+                self.close_model_detail();
+            }
             KeyCode::Esc if self.selected_graph_cell.is_some() => {
                 self.selected_graph_cell = None;
                 self.stats_breakdown_total_lines = 0;
@@ -1583,6 +1641,9 @@ impl App {
         self.selected_monthly_detail_month = None;
         self.monthly_list_selected_index = 0;
         self.monthly_list_scroll_offset = 0;
+        self.selected_model_detail = None;
+        self.models_list_selected_index = 0;
+        self.models_list_scroll_offset = 0;
         self.selected_graph_cell = None;
         self.stats_breakdown_total_lines = 0;
     }
@@ -1597,6 +1658,9 @@ impl App {
         if target != Tab::Monthly {
             self.selected_monthly_detail_month = None;
         }
+        if target != Tab::Models {
+            self.selected_model_detail = None;
+        }
 
         let (field, dir) = self
             .tab_sort_state
@@ -1761,6 +1825,7 @@ impl App {
 
     fn get_current_list_len(&self) -> usize {
         match self.current_tab {
+            Tab::Models if self.is_model_detail_active() => self.get_model_detail_days().len(),
             Tab::Overview | Tab::Models => self.data.models.len(),
             Tab::Agents => self.data.agents.len(),
             Tab::Daily if self.is_daily_detail_active() => {
@@ -1802,6 +1867,7 @@ impl App {
         self.persist_current_sort();
         if (self.current_tab == Tab::Daily && self.is_daily_detail_active())
             || (self.current_tab == Tab::Monthly && self.is_monthly_detail_active())
+            || (self.current_tab == Tab::Models && self.is_model_detail_active())
         {
             self.selected_index = 0;
             self.scroll_offset = 0;
@@ -2007,6 +2073,55 @@ impl App {
         self.clamp_selection();
     }
 
+    fn open_selected_model_detail(&mut self) {
+        if self.is_model_detail_active() {
+            return;
+        }
+
+        let selected = {
+            let models = self.get_sorted_models();
+            models
+                .get(self.selected_index)
+                .map(|m| (ModelDetailKey::of(m), m.model.clone()))
+        };
+
+        if let Some((detail, name)) = selected {
+            self.models_list_selected_index = self.selected_index;
+            self.models_list_scroll_offset = self.scroll_offset;
+            self.selected_model_detail = Some(detail);
+            self.selected_index = 0;
+            self.scroll_offset = 0;
+            self.set_status(&format!("Viewing usage breakdown for {}", name));
+            self.clamp_selection();
+        }
+    }
+
+    fn close_model_detail(&mut self) {
+        let Some(detail) = self.selected_model_detail.take() else {
+            return;
+        };
+
+        let restored_index = self
+            .get_sorted_models()
+            .iter()
+            .position(|m| detail.matches(m))
+            .unwrap_or(self.models_list_selected_index);
+
+        self.selected_index = restored_index;
+
+        let max_visible = self.max_visible_items.max(1);
+        let viewport_still_holds = restored_index >= self.models_list_scroll_offset
+            && restored_index < self.models_list_scroll_offset + max_visible;
+        self.scroll_offset = if viewport_still_holds {
+            self.models_list_scroll_offset
+        } else {
+            restored_index.saturating_sub(max_visible / 2)
+        };
+
+        self.set_status("Returned to model usage");
+        self.clamp_selection();
+    }
+
     fn toggle_auto_refresh(&mut self) {
         self.auto_refresh = !self.auto_refresh;
         if self.auto_refresh {
@@ -2143,19 +2258,14 @@ impl App {
         }
     }
 
+    /// Exports what's on screen: the loaded data already reflects the
+    /// enabled sources and date filters, so nothing is rescanned.
     fn export_to_json(&mut self) {
-        let filename = format!(
-            "tokscale-export-{}.json",
-            chrono::Utc::now().format("%Y%m%d-%H%M%S")
-        );
-
-        match super::export::build_export_json(&self.data) {
-            Ok(json) => match std::fs::write(&filename, json) {
-                Ok(_) => self.set_status(&format!("Exported to {}", filename)),
-                Err(e) => self.set_status(&format!("Export failed: {}", e)),
-            },
-            Err(e) => self.set_status(&format!("Export failed: {}", e)),
-        }
+        let status = match super::export::write_export(&self.data, &self.export_dir) {
+            Ok(path) => format!("Exported to {}", path.display()),
+            Err(e) => format!("Export failed: {}", e),
+        };
+        self.set_status(&status);
     }
 
     fn handle_graph_selection(&mut self) {
@@ -2283,6 +2393,23 @@ impl App {
         self.selected_daily_detail_date
     }
 
+    pub fn is_model_detail_active(&self) -> bool {
+        self.selected_model_detail.is_some()
+    }
+
+    /// The model opened in the Models-tab drill-down, if any.
+    pub fn model_detail(&self) -> Option<&ModelUsage> {
+        let detail = self.selected_model_detail.as_ref()?;
+        self.data.models.iter().find(|m| detail.matches(m))
+    }
+
+    /// Daily rows of the drilled-down model, newest first.
+    pub fn get_model_detail_days(&self) -> &[ModelDailyUsage] {
+        self.model_detail()
+            .map(|m| m.drilldown.daily.as_slice())
+            .unwrap_or_default()
+    }
+
     pub fn is_monthly_detail_active(&self) -> bool {
         self.selected_monthly_detail_month.is_some()
     }
@@ -2711,6 +2838,7 @@ mod tests {
                 cost: 0.0,
                 performance: Default::default(),
                 session_count: 1,
+                drilldown: Default::default(),
                 workspace_key: None,
                 workspace_label: None,
             },
@@ -2723,6 +2851,7 @@ mod tests {
                 cost: 0.0,
                 performance: Default::default(),
                 session_count: 1,
+                drilldown: Default::default(),
                 workspace_key: None,
                 workspace_label: None,
             },
@@ -2762,6 +2891,7 @@ mod tests {
                 cost: 0.0,
                 performance: Default::default(),
                 session_count: 1,
+                drilldown: Default::default(),
                 workspace_key: None,
                 workspace_label: None,
             },
@@ -2774,6 +2904,7 @@ mod tests {
                 cost: 0.0,
                 performance: Default::default(),
                 session_count: 1,
+                drilldown: Default::default(),
                 workspace_key: None,
                 workspace_label: None,
             },
@@ -2812,6 +2943,7 @@ mod tests {
             cost: 0.0,
             performance: Default::default(),
             session_count: 1,
+            drilldown: Default::default(),
             workspace_key: None,
             workspace_label: None,
         }];
@@ -3114,6 +3246,7 @@ mod tests {
                 cost: 0.0,
                 performance: Default::default(),
                 session_count: 1,
+                drilldown: Default::default(),
                 workspace_key: None,
                 workspace_label: None,
             })
@@ -3475,6 +3608,95 @@ mod tests {
         assert!(!app.is_monthly_detail_active());
     }
 
+    fn make_app_with_model_days(n: usize) -> App {
+        let mut app = make_app_with_models(n);
+        for (i, model) in app.data.models.iter_mut().enumerate() {
+            model.drilldown.daily = (0..=i)
+                .map(|day| ModelDailyUsage {
+                    date: NaiveDate::from_ymd_opt(2026, 5, 10 - day as u32).unwrap(),
+                    tokens: TokenBreakdown::default(),
+                    cost: 1.0,
+                    message_count: 1,
+                })
+                .collect();
+        }
+        app
+    }
+
+    #[test]
+    fn test_enter_opens_model_detail_for_selected_model() {
+        let mut app = make_app_with_model_days(3);
+        app.switch_tab(Tab::Models);
+        app.selected_index = 1;
+
+        app.handle_key_event(key(KeyCode::Enter));
+
+        assert!(app.is_model_detail_active());
+        assert_eq!(app.model_detail().map(|m| m.model.as_str()), Some("model1"));
+        assert_eq!(app.get_model_detail_days().len(), 2);
+        assert_eq!(app.get_current_list_len(), 2);
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[test]
+    fn test_esc_closes_model_detail_and_restores_selection() {
+        let mut app = make_app_with_model_days(3);
+        app.switch_tab(Tab::Models);
+        app.selected_index = 2;
+
+        app.handle_key_event(key(KeyCode::Enter));
+        assert!(app.is_model_detail_active());
+        app.handle_key_event(key(KeyCode::Down));
+
+        app.handle_key_event(key(KeyCode::Esc));
+
+        assert!(!app.is_model_detail_active());
+        assert_eq!(app.current_tab, Tab::Models);
+        assert_eq!(app.selected_index, 2);
+    }
+
+    #[test]
+    fn test_enter_on_overview_does_not_open_model_detail() {
+        let mut app = make_app_with_model_days(2);
+        app.switch_tab(Tab::Overview);
+
+        app.handle_key_event(key(KeyCode::Enter));
+
+        assert!(!app.is_model_detail_active());
+    }
+
+    #[test]
+    fn test_switch_tab_clears_model_detail() {
+        let mut app = make_app_with_model_days(2);
+        app.switch_tab(Tab::Models);
+        app.open_selected_model_detail();
+        assert!(app.is_model_detail_active());
+
+        app.switch_tab(Tab::Daily);
+
+        assert!(!app.is_model_detail_active());
+    }
+
+    #[test]
+    fn test_update_data_keeps_model_detail_only_while_model_exists() {
+        let mut app = make_app_with_model_days(2);
+        app.switch_tab(Tab::Models);
+        app.open_selected_model_detail();
+        let models = app.data.models.clone();
+
+        app.update_data(UsageData {
+            models: models.clone(),
+            ..Default::default()
+        });
+        assert!(app.is_model_detail_active());
+
+        app.update_data(UsageData {
+            models: models[1..].to_vec(),
+            ..Default::default()
+        });
+        assert!(!app.is_model_detail_active());
+    }
+
     fn key(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code, KeyModifiers::NONE)
     }
@@ -4134,15 +4356,40 @@ mod tests {
 
     #[test]
     fn test_handle_key_export() {
+        let dir = tempfile::tempdir().unwrap();
         let mut app = make_app();
+        app.export_dir = dir.path().join("missing");
+
         app.handle_key_event(key(KeyCode::Char('e')));
-        assert!(app.status_message.is_some());
-        let msg = app.status_message.as_ref().unwrap();
-        assert!(
-            msg.contains("Exported to") || msg.contains("Export failed"),
-            "unexpected status: {}",
-            msg
+
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|status| status.starts_with("Export failed: ")));
+    }
+
+    #[test]
+    fn test_export_key_writes_the_loaded_data_into_the_export_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = make_app();
+        app.export_dir = dir.path().to_path_buf();
+        app.data.total_tokens = 42;
+
+        app.handle_key_event(key(KeyCode::Char('e')));
+
+        let written: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(written.len(), 1);
+        assert_eq!(
+            app.status_message,
+            Some(format!("Exported to {}", written[0].display()))
         );
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&written[0]).unwrap()).unwrap();
+        assert_eq!(json["totals"]["tokens"], 42);
+        assert!(json["graph"]["contributions"].is_array());
     }
 
     // ── handle_key_event: refresh ───────────────────────────────────
@@ -4876,6 +5123,7 @@ mod tests {
             cost,
             performance: Default::default(),
             session_count: 1,
+            drilldown: Default::default(),
         }
     }
 
@@ -5027,6 +5275,7 @@ mod tests {
                 cost: 10.0,
                 performance: Default::default(),
                 session_count: 1,
+                drilldown: Default::default(),
             },
             ModelUsage {
                 model: "gpt-5".to_string(),
@@ -5039,6 +5288,7 @@ mod tests {
                 cost: 1.0,
                 performance: Default::default(),
                 session_count: 1,
+                drilldown: Default::default(),
             },
         ];
         app.build_model_shade_map();
@@ -5096,6 +5346,7 @@ mod tests {
                 cost: 10.0,
                 performance: Default::default(),
                 session_count: 1,
+                drilldown: Default::default(),
             },
             ModelUsage {
                 model: "sonnet-shared".to_string(),
@@ -5108,6 +5359,7 @@ mod tests {
                 cost: 5.0,
                 performance: Default::default(),
                 session_count: 1,
+                drilldown: Default::default(),
             },
         ];
         app.build_model_shade_map();
@@ -5144,6 +5396,7 @@ mod tests {
             cost: 3.0,
             performance: Default::default(),
             session_count: 1,
+            drilldown: Default::default(),
         };
         app.data.models = vec![
             ModelUsage {
@@ -71,7 +71,7 @@ fn cache_file() -> Option<PathBuf> {
 }
 
 fn legacy_cache_files() -> Vec<PathBuf> {
-    if crate::paths::is_config_dir_overridden() {
+    if crate::paths::is_cache_dir_overridden() {
         return Vec::new();
     }
 
@@ -297,6 +297,7 @@ impl From<CachedModelUsage> for ModelUsage {
             cost: m.cost,
             performance: m.performance,
             session_count: m.session_count,
+            drilldown: Default::default(),
         }
     }
 }
@@ -154,6 +154,7 @@ mod tests {
             cost,
             performance: Default::default(),
             session_count: 1,
+            drilldown: Default::default(),
         }
     }
 
@@ -7,8 +7,9 @@ use tokio::runtime::{Handle, Runtime};
 
 use tokscale_core::sessions::UnifiedMessage;
 use tokscale_core::{
-    model_name_for_grouping, normalize_model_for_grouping, parse_local_unified_messages, sessions,
-    ClientId, GroupBy, LocalParseOptions, ModelPerformance,
+    model_family, model_name_for_grouping, normalize_model_for_grouping, normalize_provider,
+    parse_local_unified_messages, sessions, ClientId, GroupBy, IntensityBasis, LocalParseOptions,
+    ModelPerformance,
 };
 
 /// Returns the scanner settings that `DataLoader` should use when building
@@ -43,6 +44,21 @@ impl TokenBreakdown {
             .saturating_add(self.cache_write)
             .saturating_add(self.reasoning)
     }
+
+    /// Add a parsed message's token counts, clamping negative buckets to zero.
+    fn add_message_tokens(&mut self, tokens: &tokscale_core::TokenBreakdown) {
+        self.input = self.input.saturating_add(tokens.input.max(0) as u64);
+        self.output = self.output.saturating_add(tokens.output.max(0) as u64);
+        self.cache_read = self
+            .cache_read
+            .saturating_add(tokens.cache_read.max(0) as u64);
+        self.cache_write = self
+            .cache_write
+            .saturating_add(tokens.cache_write.max(0) as u64);
+        self.reasoning = self
+            .reasoning
+            .saturating_add(tokens.reasoning.max(0) as u64);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -57,6 +73,29 @@ pub struct ModelUsage {
     pub cost: f64,
     pub performance: ModelPerformance,
     pub session_count: u32,
+    pub drilldown: ModelDrilldown,
+}
+
+/// Maximum number of sessions kept per model for the Models-tab drill-down.
+pub const MODEL_TOP_SESSIONS: usize = 10;
+
+/// Per-model breakdowns behind the Models-tab drill-down. Not persisted in
+/// the TUI cache, so it stays empty until the first fresh load.
+#[derive(Debug, Clone, Default)]
+pub struct ModelDrilldown {
+    /// Days this model was used, newest first.
+    pub daily: Vec<ModelDailyUsage>,
+    /// The model's most expensive sessions, highest cost first, capped at
+    /// [`MODEL_TOP_SESSIONS`].
+    pub top_sessions: Vec<SessionUsage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModelDailyUsage {
+    pub date: NaiveDate,
+    pub tokens: TokenBreakdown,
+    pub cost: f64,
+    pub message_count: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -202,8 +241,16 @@ pub struct DataLoader {
     pub until: Option<String>,
     pub year: Option<String>,
     pub minutely_enabled: bool,
+    /// Contribution-graph window length in days, ending today. `None` uses
+    /// the default one-year window; ignored when `year` is set.
+    pub graph_days: Option<u32>,
+    /// Daily total the contribution-graph intensity is computed from.
+    pub intensity_basis: IntensityBasis,
 }
 
+/// Default contribution-graph window: the last year, like GitHub's graph.
+const DEFAULT_GRAPH_DAYS: u32 = 365;
+
 const UNKNOWN_WORKSPACE_LABEL: &str = "Unknown workspace";
 const UNKNOWN_WORKSPACE_GROUP_KEY: &str = "\0unknown-workspace";
 
@@ -255,11 +302,16 @@ fn daily_source_model_key(
     model: &str,
 ) -> String {
     match group_by {
-        GroupBy::WorkspaceModel => workspace_model_daily_key(workspace_group_key, model),
-        GroupBy::ClientProviderModel => format!("{provider_id}:{model}"),
-        GroupBy::Model | GroupBy::ClientModel | GroupBy::Session | GroupBy::ClientSession => {
-            model.to_string()
+        GroupBy::WorkspaceModel | GroupBy::Project => {
+            workspace_model_daily_key(workspace_group_key, model)
         }
+        GroupBy::ClientProviderModel => format!("{provider_id}:{model}"),
+        GroupBy::Model
+        | GroupBy::ClientModel
+        | GroupBy::Session
+        | GroupBy::SessionModel
+        | GroupBy::ClientSessionModel
+        | GroupBy::Family => model.to_string(),
     }
 }
 
@@ -270,11 +322,16 @@ fn daily_source_model_display_name(
     model: &str,
 ) -> String {
     match group_by {
-        GroupBy::WorkspaceModel => workspace_model_display_label(workspace_label, model),
-        GroupBy::ClientProviderModel => format!("{provider_id} / {model}"),
-        GroupBy::Model | GroupBy::ClientModel | GroupBy::Session | GroupBy::ClientSession => {
-            model.to_string()
+        GroupBy::WorkspaceModel | GroupBy::Project => {
+            workspace_model_display_label(workspace_label, model)
         }
+        GroupBy::ClientProviderModel => format!("{provider_id} / {model}"),
+        GroupBy::Model
+        | GroupBy::ClientModel
+        | GroupBy::Session
+        | GroupBy::SessionModel
+        | GroupBy::ClientSessionModel
+        | GroupBy::Family => model.to_string(),
     }
 }
 
@@ -284,8 +341,11 @@ fn model_color_key(group_by: &GroupBy, _provider_id: &str, model: &str) -> Strin
         GroupBy::Model
         | GroupBy::ClientModel
         | GroupBy::WorkspaceModel
+        | GroupBy::Project
         | GroupBy::Session
-        | GroupBy::ClientSession => model.to_string(),
+        | GroupBy::SessionModel
+        | GroupBy::ClientSessionModel
+        | GroupBy::Family => model.to_string(),
     }
 }
 
@@ -295,8 +355,11 @@ fn hourly_model_key(group_by: &GroupBy, provider_id: &str, model: &str) -> Strin
         GroupBy::Model
         | GroupBy::ClientModel
         | GroupBy::WorkspaceModel
+        | GroupBy::Project
         | GroupBy::Session
-        | GroupBy::ClientSession => model.to_string(),
+        | GroupBy::SessionModel
+        | GroupBy::ClientSessionModel
+        | GroupBy::Family => model.to_string(),
     }
 }
 
@@ -306,8 +369,11 @@ fn hourly_model_display_name(group_by: &GroupBy, provider_id: &str, model: &str)
         GroupBy::Model
         | GroupBy::ClientModel
         | GroupBy::WorkspaceModel
+        | GroupBy::Project
         | GroupBy::Session
-        | GroupBy::ClientSession => model.to_string(),
+        | GroupBy::SessionModel
+        | GroupBy::ClientSessionModel
+        | GroupBy::Family => model.to_string(),
     }
 }
 
@@ -319,6 +385,8 @@ impl DataLoader {
             until: None,
             year: None,
             minutely_enabled: false,
+            graph_days: None,
+            intensity_basis: IntensityBasis::default(),
         }
     }
 
@@ -334,6 +402,8 @@ impl DataLoader {
             until,
             year,
             minutely_enabled: false,
+            graph_days: None,
+            intensity_basis: IntensityBasis::default(),
         }
     }
 
@@ -342,6 +412,16 @@ impl DataLoader {
         self
     }
 
+    pub fn with_graph_days(mut self, graph_days: Option<u32>) -> Self {
+        self.graph_days = graph_days;
+        self
+    }
+
+    pub fn with_intensity_basis(mut self, basis: IntensityBasis) -> Self {
+        self.intensity_basis = basis;
+        self
+    }
+
     pub fn load(
         &self,
         enabled_clients: &[ClientId],
@@ -367,8 +447,10 @@ impl DataLoader {
             clients: Some(sources),
             since: self.since.clone(),
             until: self.until.clone(),
+            until_exclusive: false,
             year: self.year.clone(),
             scanner_settings: data_loader_scanner_settings(),
+            ..crate::parse_flags::local_parse_options()
         };
 
         let messages = if Handle::try_current().is_ok() {
@@ -415,9 +497,11 @@ impl DataLoader {
             clients: Some(sources),
             since: self.since.clone(),
             until: self.until.clone(),
+            until_exclusive: false,
             year: self.year.clone(),
             use_env_roots: false,
             scanner_settings: data_loader_scanner_settings(),
+            ..Default::default()
         };
 
         let messages = if Handle::try_current().is_ok() {
@@ -456,39 +540,53 @@ impl DataLoader {
         let mut minutely_map: HashMap<NaiveDateTime, MinutelyUsage> = HashMap::new();
         let mut model_session_ids: HashMap<String, HashSet<String>> = HashMap::new();
         let mut session_map: HashMap<String, SessionUsage> = HashMap::new();
+        let mut model_daily: HashMap<String, HashMap<NaiveDate, ModelDailyUsage>> = HashMap::new();
+        let mut model_sessions: HashMap<String, HashMap<String, SessionUsage>> = HashMap::new();
 
         for msg in &messages {
-            let normalized_model =
+            let mut normalized_model =
                 model_name_for_grouping(&msg.client, &msg.provider_id, &msg.model_id);
-            let model_key = normalize_model_for_grouping(&msg.model_id);
+            let mut model_key = normalize_model_for_grouping(&msg.model_id);
+            if *group_by == GroupBy::Family {
+                normalized_model = model_family(&normalized_model).to_string();
+                model_key = normalized_model.clone();
+            }
+            let provider = normalize_provider(&msg.provider_id);
             let (workspace_group_key, workspace_key, workspace_label) = workspace_bucket(msg);
             let key = match group_by {
-                GroupBy::Model => normalized_model.clone(),
+                GroupBy::Model | GroupBy::Family => normalized_model.clone(),
                 GroupBy::ClientModel => format!("{}:{}", msg.client, normalized_model),
                 GroupBy::ClientProviderModel => {
-                    format!("{}:{}:{}", msg.client, msg.provider_id, normalized_model)
+                    format!("{}:{}:{}", msg.client, provider, normalized_model)
                 }
-                GroupBy::WorkspaceModel => {
+                // Like Session, project rows still split by model in the TUI.
+                GroupBy::WorkspaceModel | GroupBy::Project => {
                     format!("{}:{}", workspace_group_key, normalized_model)
                 }
-                GroupBy::Session => format!("{}:{}", msg.session_id, normalized_model),
-                GroupBy::ClientSession => {
+                GroupBy::SessionModel => format!("{}:{}", msg.session_id, normalized_model),
+                // The TUI model table has one row per model, so a session-only
+                // grouping still splits sessions by model here; per-session
+                // totals live in the Sessions tab.
+                GroupBy::Session | GroupBy::ClientSessionModel => {
                     format!("{}:{}:{}", msg.client, msg.session_id, normalized_model)
                 }
             };
-            let merge_clients = matches!(group_by, GroupBy::Model | GroupBy::WorkspaceModel);
+            let merge_clients = matches!(
+                group_by,
+                GroupBy::Model | GroupBy::WorkspaceModel | GroupBy::Project | GroupBy::Family
+            );
 
             let model_entry = model_map.entry(key.clone()).or_insert_with(|| ModelUsage {
                 model: normalized_model.clone(),
                 color_key: model_key.clone(),
-                provider: msg.provider_id.clone(),
+                provider: provider.clone(),
                 client: msg.client.clone(),
-                workspace_key: if *group_by == GroupBy::WorkspaceModel {
+                workspace_key: if matches!(group_by, GroupBy::WorkspaceModel | GroupBy::Project) {
                     workspace_key.clone()
                 } else {
                     None
                 },
-                workspace_label: if *group_by == GroupBy::WorkspaceModel {
+                workspace_label: if matches!(group_by, GroupBy::WorkspaceModel | GroupBy::Project) {
                     Some(workspace_label.clone())
                 } else {
                     None
@@ -497,6 +595,7 @@ impl DataLoader {
                 cost: 0.0,
                 performance: ModelPerformance::default(),
                 session_count: 0,
+                drilldown: ModelDrilldown::default(),
             });
 
             if merge_clients && !model_entry.client.split(", ").any(|s| s == msg.client) {
@@ -504,12 +603,9 @@ impl DataLoader {
             }
 
             if *group_by != GroupBy::ClientProviderModel
-                && !model_entry
-                    .provider
-                    .split(", ")
-                    .any(|p| p == msg.provider_id)
+                && !model_entry.provider.split(", ").any(|p| p == provider)
             {
-                model_entry.provider = format!("{}, {}", model_entry.provider, msg.provider_id);
+                model_entry.provider = format!("{}, {}", model_entry.provider, provider);
             }
 
             model_entry.tokens.input = model_entry
@@ -543,11 +639,37 @@ impl DataLoader {
                 .record_message(positive_unified_token_total(&msg.tokens), msg.duration_ms);
 
             let session_key = format!("{}:{}", msg.client, msg.session_id);
-            let model_sessions = model_session_ids.entry(key).or_default();
-            if model_sessions.insert(session_key) {
+            let session_ids = model_session_ids.entry(key.clone()).or_default();
+            if session_ids.insert(session_key.clone()) {
                 model_entry.session_count += 1;
             }
 
+            if let Some(date) = parse_date(&msg.date) {
+                let day = model_daily
+                    .entry(key.clone())
+                    .or_default()
+                    .entry(date)
+                    .or_insert_with(|| ModelDailyUsage {
+                        date,
+                        tokens: TokenBreakdown::default(),
+                        cost: 0.0,
+                        message_count: 0,
+                    });
+                day.tokens.add_message_tokens(&msg.tokens);
+                day.cost += msg_cost;
+                day.message_count = day
+                    .message_count
+                    .saturating_add(msg.message_count.max(0) as u32);
+            }
+            if !msg.session_id.is_empty() {
+                let session = model_sessions
+                    .entry(key)
+                    .or_default()
+                    .entry(session_key)
+                    .or_insert_with(|| empty_session_usage(msg));
+                record_session_message(session, msg, msg_cost);
+            }
+
             if let Some(agent) = msg.agent.as_ref() {
                 let normalized_agent = if msg.client == "opencode" {
                     sessions::normalize_opencode_agent_name(agent)
@@ -672,7 +794,7 @@ impl DataLoader {
                 let daily_model_key = daily_source_model_key(
                     group_by,
                     &workspace_group_key,
-                    &msg.provider_id,
+                    &provider,
                     &normalized_model,
                 );
 
@@ -680,14 +802,14 @@ impl DataLoader {
                     .models
                     .entry(daily_model_key)
                     .or_insert_with(|| DailyModelInfo {
-                        provider: msg.provider_id.clone(),
+                        provider: provider.clone(),
                         display_name: daily_source_model_display_name(
                             group_by,
                             &workspace_label,
-                            &msg.provider_id,
+                            &provider,
                             &normalized_model,
                         ),
-                        color_key: model_color_key(group_by, &msg.provider_id, &model_key),
+                        color_key: model_color_key(group_by, &provider, &model_key),
                         tokens: TokenBreakdown::default(),
                         cost: 0.0,
                         messages: 0,
@@ -765,19 +887,18 @@ impl DataLoader {
                 }
                 hourly_entry.clients.insert(msg.client.clone());
 
-                let hourly_model_key =
-                    hourly_model_key(group_by, &msg.provider_id, &normalized_model);
+                let hourly_model_key = hourly_model_key(group_by, &provider, &normalized_model);
                 let h_model = hourly_entry
                     .models
                     .entry(hourly_model_key)
                     .or_insert_with(|| HourlyModelInfo {
-                        provider: msg.provider_id.clone(),
+                        provider: provider.clone(),
                         display_name: hourly_model_display_name(
                             group_by,
-                            &msg.provider_id,
+                            &provider,
                             &normalized_model,
                         ),
-                        color_key: model_color_key(group_by, &msg.provider_id, &model_key),
+                        color_key: model_color_key(group_by, &provider, &model_key),
                         tokens: TokenBreakdown::default(),
                         cost: 0.0,
                     });
@@ -859,19 +980,19 @@ impl DataLoader {
                 }
                 minutely_entry.clients.insert(msg.client.clone());
 
-                let m_model_key = hourly_model_key(group_by, &msg.provider_id, &normalized_model);
+                let m_model_key = hourly_model_key(group_by, &provider, &normalized_model);
                 let m_model =
                     minutely_entry
                         .models
                         .entry(m_model_key)
                         .or_insert_with(|| HourlyModelInfo {
-                            provider: msg.provider_id.clone(),
+                            provider: provider.clone(),
                             display_name: hourly_model_display_name(
                                 group_by,
-                                &msg.provider_id,
+                                &provider,
                                 &normalized_model,
                             ),
-                            color_key: model_color_key(group_by, &msg.provider_id, &model_key),
+                            color_key: model_color_key(group_by, &provider, &model_key),
                             tokens: TokenBreakdown::default(),
                             cost: 0.0,
                         });
@@ -905,77 +1026,32 @@ impl DataLoader {
             // "no-session" row.
             if !msg.session_id.is_empty() {
                 let session_key = format!("{}:{}", msg.client, msg.session_id);
-                let session_entry =
-                    session_map
-                        .entry(session_key)
-                        .or_insert_with(|| SessionUsage {
-                            session_id: msg.session_id.clone(),
-                            client: msg.client.clone(),
-                            title: None,
-                            tokens: TokenBreakdown::default(),
-                            cost: 0.0,
-                            message_count: 0,
-                            turn_count: 0,
-                            first_active_ms: 0,
-                            last_active_ms: 0,
-                        });
-
-                session_entry.tokens.input = session_entry
-                    .tokens
-                    .input
-                    .saturating_add(msg.tokens.input.max(0) as u64);
-                session_entry.tokens.output = session_entry
-                    .tokens
-                    .output
-                    .saturating_add(msg.tokens.output.max(0) as u64);
-                session_entry.tokens.cache_read = session_entry
-                    .tokens
-                    .cache_read
-                    .saturating_add(msg.tokens.cache_read.max(0) as u64);
-                session_entry.tokens.cache_write = session_entry
-                    .tokens
-                    .cache_write
-                    .saturating_add(msg.tokens.cache_write.max(0) as u64);
-                session_entry.tokens.reasoning = session_entry
-                    .tokens
-                    .reasoning
-                    .saturating_add(msg.tokens.reasoning.max(0) as u64);
-                session_entry.cost += msg_cost;
-                session_entry.message_count = session_entry
-                    .message_count
-                    .saturating_add(msg.message_count.max(0) as u32);
-                if msg.is_turn_start {
-                    session_entry.turn_count += 1;
-                }
-
-                let ts = message_timestamp_ms(msg);
-                if ts > 0 {
-                    if session_entry.first_active_ms == 0 || ts < session_entry.first_active_ms {
-                        session_entry.first_active_ms = ts;
-                    }
-                    if ts > session_entry.last_active_ms {
-                        session_entry.last_active_ms = ts;
-                    }
-                }
-
-                // Adopt the first non-empty session_title seen across the
-                // session's messages. Parsers that don't populate the field
-                // leave it `None` and the Sessions tab falls back to the ID.
-                if session_entry.title.is_none() {
-                    if let Some(ref title) = msg.session_title {
-                        let trimmed = title.trim();
-                        if !trimmed.is_empty() {
-                            session_entry.title = Some(trimmed.to_string());
-                        }
-                    }
-                }
+                let session_entry = session_map
+                    .entry(session_key)
+                    .or_insert_with(|| empty_session_usage(msg));
+                record_session_message(session_entry, msg, msg_cost);
             }
         }
 
         let mut models: Vec<ModelUsage> = model_map
-            .into_values()
-            .map(|mut model| {
+            .into_iter()
+            .map(|(key, mut model)| {
                 model.performance.finalize(model.tokens.total() as i64);
+                let mut daily: Vec<ModelDailyUsage> = model_daily
+                    .remove(&key)
+                    .map(|days| days.into_values().collect())
+                    .unwrap_or_default();
+                daily.sort_by_key(|d| std::cmp::Reverse(d.date));
+                let mut top_sessions: Vec<SessionUsage> = model_sessions
+                    .remove(&key)
+                    .map(|sessions| sessions.into_values().collect())
+                    .unwrap_or_default();
+                sort_sessions_by_cost(&mut top_sessions);
+                top_sessions.truncate(MODEL_TOP_SESSIONS);
+                model.drilldown = ModelDrilldown {
+                    daily,
+                    top_sessions,
+                };
                 model
             })
             .collect();
@@ -1012,13 +1088,7 @@ impl DataLoader {
         let monthly = aggregate_monthly_from_daily(&daily);
 
         let mut sessions: Vec<SessionUsage> = session_map.into_values().collect();
-        sessions.sort_by(|a, b| {
-            b.cost
-                .total_cmp(&a.cost)
-                .then_with(|| b.last_active_ms.cmp(&a.last_active_ms))
-                .then_with(|| a.client.cmp(&b.client))
-                .then_with(|| a.session_id.cmp(&b.session_id))
-        });
+        sort_sessions_by_cost(&mut sessions);
 
         // Plain `.sum()` panics (debug) / wraps (release) on overflow across
         // many models; a single corrupt/huge bucket must not poison the
@@ -1032,7 +1102,12 @@ impl DataLoader {
             .map(|m| if m.cost.is_finite() { m.cost } else { 0.0 })
             .sum();
 
-        let graph = build_contribution_graph(&daily);
+        let (graph_start, graph_end) = graph_window(
+            self.year.as_deref(),
+            self.graph_days,
+            Local::now().date_naive(),
+        );
+        let graph = build_contribution_graph(&daily, graph_start, graph_end, self.intensity_basis);
         let (current_streak, longest_streak) = calculate_streaks(&daily);
 
         Ok(UsageData {
@@ -1054,6 +1129,63 @@ impl DataLoader {
     }
 }
 
+fn empty_session_usage(msg: &UnifiedMessage) -> SessionUsage {
+    SessionUsage {
+        session_id: msg.session_id.clone(),
+        client: msg.client.clone(),
+        title: None,
+        tokens: TokenBreakdown::default(),
+        cost: 0.0,
+        message_count: 0,
+        turn_count: 0,
+        first_active_ms: 0,
+        last_active_ms: 0,
+    }
+}
+
+fn record_session_message(entry: &mut SessionUsage, msg: &UnifiedMessage, msg_cost: f64) {
+    entry.tokens.add_message_tokens(&msg.tokens);
+    entry.cost += msg_cost;
+    entry.message_count = entry
+        .message_count
+        .saturating_add(msg.message_count.max(0) as u32);
+    if msg.is_turn_start {
+        entry.turn_count += 1;
+    }
+
+    let ts = message_timestamp_ms(msg);
+    if ts > 0 {
+        if entry.first_active_ms == 0 || ts < entry.first_active_ms {
+            entry.first_active_ms = ts;
+        }
+        if ts > entry.last_active_ms {
+            entry.last_active_ms = ts;
+        }
+    }
+
+    // Adopt the first non-empty session_title seen across the
+    // session's messages. Parsers that don't populate the field
+    // leave it `None` and the Sessions tab falls back to the ID.
+    if entry.title.is_none() {
+        if let Some(ref title) = msg.session_title {
+            let trimmed = title.trim();
+            if !trimmed.is_empty() {
+                entry.title = Some(trimmed.to_string());
+            }
+        }
+    }
+}
+
+fn sort_sessions_by_cost(sessions: &mut [SessionUsage]) {
+    sessions.sort_by(|a, b| {
+        b.cost
+            .total_cmp(&a.cost)
+            .then_with(|| b.last_active_ms.cmp(&a.last_active_ms))
+            .then_with(|| a.client.cmp(&b.client))
+            .then_with(|| a.session_id.cmp(&b.session_id))
+    });
+}
+
 fn parse_date(date_str: &str) -> Option<NaiveDate> {
     NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok()
 }
@@ -1144,22 +1276,53 @@ fn minute_bucket_with_fallback(timestamp_ms: i64, date_str: &str) -> Option<Naiv
     parse_date(date_str).and_then(|d| d.and_hms_opt(0, 0, 0))
 }
 
-fn build_contribution_graph(daily: &[DailyUsage]) -> GraphData {
-    build_contribution_graph_for_today(daily, Local::now().date_naive())
+/// First and last day shown in the contribution graph: that calendar year
+/// when `year` is set, otherwise the last `graph_days` days ending `today`.
+fn graph_window(
+    year: Option<&str>,
+    graph_days: Option<u32>,
+    today: NaiveDate,
+) -> (NaiveDate, NaiveDate) {
+    let year_bounds = year
+        .and_then(|year| year.parse::<i32>().ok())
+        .and_then(|year| {
+            Some((
+                NaiveDate::from_ymd_opt(year, 1, 1)?,
+                NaiveDate::from_ymd_opt(year, 12, 31)?,
+            ))
+        });
+    if let Some(bounds) = year_bounds {
+        return bounds;
+    }
+
+    let days = graph_days.unwrap_or(DEFAULT_GRAPH_DAYS).max(1);
+    (today - chrono::Duration::days(days as i64 - 1), today)
 }
 
-fn build_contribution_graph_for_today(daily: &[DailyUsage], today: NaiveDate) -> GraphData {
+fn build_contribution_graph(
+    daily: &[DailyUsage],
+    start: NaiveDate,
+    end: NaiveDate,
+    basis: IntensityBasis,
+) -> GraphData {
     if daily.is_empty() {
         return GraphData { weeks: vec![] };
     }
 
-    let days_to_sunday = today.weekday().num_days_from_sunday();
-    let end_date = today;
-    let start_date = end_date - chrono::Duration::days(364 + days_to_sunday as i64);
+    // Weeks run Sunday..Saturday, so pad the first week back to its Sunday.
+    let days_to_sunday = start.weekday().num_days_from_sunday();
+    let end_date = end;
+    let start_date = start - chrono::Duration::days(days_to_sunday as i64);
 
     let daily_map: HashMap<NaiveDate, &DailyUsage> = daily.iter().map(|d| (d.date, d)).collect();
 
-    let max_cost = daily.iter().map(|d| d.cost).fold(0.0_f64, |a, b| a.max(b));
+    // Same per-day value the core graph report buckets on, so the Stats tab
+    // and `tokscale graph` agree for a given basis.
+    let day_value = |d: &DailyUsage| {
+        let tokens = i64::try_from(d.tokens.total()).unwrap_or(i64::MAX);
+        tokscale_core::intensity_value(basis, d.cost, tokens)
+    };
+    let max_value = daily.iter().map(day_value).fold(0.0_f64, f64::max);
 
     let mut weeks: Vec<Vec<Option<ContributionDay>>> = Vec::new();
     let mut current_week: Vec<Option<ContributionDay>> = Vec::new();
@@ -1167,8 +1330,8 @@ fn build_contribution_graph_for_today(daily: &[DailyUsage], today: NaiveDate) ->
     let mut current_date = start_date;
     while current_date <= end_date {
         let day = if let Some(usage) = daily_map.get(&current_date) {
-            let raw_intensity = if max_cost > 0.0 {
-                usage.cost / max_cost
+            let raw_intensity = if max_value > 0.0 {
+                day_value(usage) / max_value
             } else {
                 0.0
             };
@@ -1246,52 +1409,9 @@ pub fn aggregate_monthly_from_daily(daily: &[DailyUsage]) -> Vec<MonthlyUsage> {
 }
 
 fn calculate_streaks_for_today(daily: &[DailyUsage], today: NaiveDate) -> (u32, u32) {
-    if daily.is_empty() {
-        return (0, 0);
-    }
-
-    let dates: HashSet<NaiveDate> = daily.iter().map(|d| d.date).collect();
-
-    let mut current_streak = 0u32;
-    let mut check_date = today;
-
-    while dates.contains(&check_date) {
-        current_streak += 1;
-        check_date -= chrono::Duration::days(1);
-    }
-
-    if current_streak == 0 {
-        let yesterday = today - chrono::Duration::days(1);
-        check_date = yesterday;
-        while dates.contains(&check_date) {
-            current_streak += 1;
-            check_date -= chrono::Duration::days(1);
-        }
-    }
-
-    let mut longest_streak = 0u32;
-    let mut sorted_dates: Vec<NaiveDate> = dates.into_iter().collect();
-    sorted_dates.sort();
-
-    let mut streak = 0u32;
-    let mut prev_date: Option<NaiveDate> = None;
-
-    for date in sorted_dates {
-        if let Some(prev) = prev_date {
-            if date == prev + chrono::Duration::days(1) {
-                streak += 1;
-            } else {
-                longest_streak = longest_streak.max(streak);
-                streak = 1;
-            }
-        } else {
-            streak = 1;
-        }
-        prev_date = Some(date);
-    }
-    longest_streak = longest_streak.max(streak);
-
-    (current_streak, longest_streak)
+    let dates: Vec<NaiveDate> = daily.iter().map(|d| d.date).collect();
+    let streaks = tokscale_core::calculate_streaks(&dates, today);
+    (streaks.current.days, streaks.longest.days)
 }
 
 /// Time-of-day period bucket for profile view
@@ -1411,6 +1531,7 @@ mod tests {
             cache_read: i64::MAX,
             cache_write: -5,
             reasoning: 0,
+            ..Default::default()
         };
         assert_eq!(positive_unified_token_total(&tokens), i64::MAX);
     }
@@ -1473,8 +1594,10 @@ mod tests {
             clients: Some(sources),
             since: loader.since.clone(),
             until: loader.until.clone(),
+            until_exclusive: false,
             year: loader.year.clone(),
             scanner_settings: data_loader_scanner_settings(),
+            ..Default::default()
         };
 
         let messages = if Handle::try_current().is_ok() {
@@ -1531,6 +1654,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             cost,
         );
@@ -1586,248 +1710,104 @@ mod tests {
 
     #[test]
     fn test_client_as_str() {
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::OpenCode),
-            "OpenCode"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Claude),
-            "Claude"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Codex),
-            "Codex"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Copilot),
-            "Copilot"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Cursor),
-            "Cursor"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Gemini),
-            "Gemini"
-        );
-        assert_eq!(crate::tui::client_ui::display_name(ClientId::Amp), "Amp");
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Droid),
-            "Droid"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::OpenClaw),
-            "OpenClaw"
-        );
-        assert_eq!(crate::tui::client_ui::display_name(ClientId::Pi), "Pi");
-        assert_eq!(crate::tui::client_ui::display_name(ClientId::Kimi), "Kimi");
-        assert_eq!(crate::tui::client_ui::display_name(ClientId::Qwen), "Qwen");
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::RooCode),
-            "Roo Code"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::KiloCode),
-            "KiloCode"
-        );
-        assert_eq!(crate::tui::client_ui::display_name(ClientId::Mux), "Mux");
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Kilo),
-            "Kilo CLI"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Crush),
-            "Crush"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Hermes),
-            "Hermes Agent"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Codebuff),
-            "Codebuff"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Antigravity),
-            "Antigravity"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Zed),
-            "Zed Agent"
-        );
-        assert_eq!(crate::tui::client_ui::display_name(ClientId::Kiro), "Kiro");
-        assert_eq!(crate::tui::client_ui::display_name(ClientId::Trae), "Trae");
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Cline),
-            "Cline"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Grok),
-            "Grok Build"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Jcode),
-            "Jcode"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::AntigravityCli),
-            "Antigravity CLI"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::Junie),
-            "Junie"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::CodeBuddy),
-            "CodeBuddy"
-        );
-        assert_eq!(
-            crate::tui::client_ui::display_name(ClientId::WorkBuddy),
-            "WorkBuddy"
-        );
+        assert_eq!(ClientId::OpenCode.display_name(), "OpenCode");
+        assert_eq!(ClientId::Claude.display_name(), "Claude");
+        assert_eq!(ClientId::Codex.display_name(), "Codex");
+        assert_eq!(ClientId::Copilot.display_name(), "Copilot");
+        assert_eq!(ClientId::Cursor.display_name(), "Cursor");
+        assert_eq!(ClientId::Gemini.display_name(), "Gemini");
+        assert_eq!(ClientId::Amp.display_name(), "Amp");
+        assert_eq!(ClientId::Droid.display_name(), "Droid");
+        assert_eq!(ClientId::OpenClaw.display_name(), "OpenClaw");
+        assert_eq!(ClientId::Pi.display_name(), "Pi");
+        assert_eq!(ClientId::Kimi.display_name(), "Kimi");
+        assert_eq!(ClientId::Qwen.display_name(), "Qwen");
+        assert_eq!(ClientId::RooCode.display_name(), "Roo Code");
+        assert_eq!(ClientId::KiloCode.display_name(), "KiloCode");
+        assert_eq!(ClientId::Mux.display_name(), "Mux");
+        assert_eq!(ClientId::Kilo.display_name(), "Kilo CLI");
+        assert_eq!(ClientId::Crush.display_name(), "Crush");
+        assert_eq!(ClientId::Hermes.display_name(), "Hermes Agent");
+        assert_eq!(ClientId::Codebuff.display_name(), "Codebuff");
+        assert_eq!(ClientId::Antigravity.display_name(), "Antigravity");
+        assert_eq!(ClientId::Zed.display_name(), "Zed Agent");
+        assert_eq!(ClientId::Kiro.display_name(), "Kiro");
+        assert_eq!(ClientId::Trae.display_name(), "Trae");
+        assert_eq!(ClientId::Cline.display_name(), "Cline");
+        assert_eq!(ClientId::Grok.display_name(), "Grok Build");
+        assert_eq!(ClientId::Jcode.display_name(), "Jcode");
+        assert_eq!(ClientId::AntigravityCli.display_name(), "Antigravity CLI");
+        assert_eq!(ClientId::Junie.display_name(), "Junie");
+        assert_eq!(ClientId::CodeBuddy.display_name(), "CodeBuddy");
+        assert_eq!(ClientId::WorkBuddy.display_name(), "WorkBuddy");
     }
 
     #[test]
     fn test_client_key() {
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::OpenCode), '1');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Claude), '2');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Codex), '3');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Copilot), 'c');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Cursor), '4');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Gemini), '5');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Amp), '6');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Droid), '7');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::OpenClaw), '8');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Pi), '9');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Kimi), '0');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Qwen), 'w');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::RooCode), 'r');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::KiloCode), 'k');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Mux), 'x');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Kilo), 'l');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Crush), 'h');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Hermes), 'e');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Codebuff), 'b');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Antigravity), 'a');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Zed), 'z');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Kiro), 'i');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Trae), 'y');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Cline), 'n');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Gjc), 'g');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Grok), 'u');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Jcode), 'j');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::AntigravityCli), 'f');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::Junie), 'p');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::CodeBuddy), 'C');
-        assert_eq!(crate::tui::client_ui::hotkey(ClientId::WorkBuddy), 'B');
+        assert_eq!(ClientId::OpenCode.hotkey(), '1');
+        assert_eq!(ClientId::Claude.hotkey(), '2');
+        assert_eq!(ClientId::Codex.hotkey(), '3');
+        assert_eq!(ClientId::Copilot.hotkey(), 'c');
+        assert_eq!(ClientId::Cursor.hotkey(), '4');
+        assert_eq!(ClientId::Gemini.hotkey(), '5');
+        assert_eq!(ClientId::Amp.hotkey(), '6');
+        assert_eq!(ClientId::Droid.hotkey(), '7');
+        assert_eq!(ClientId::OpenClaw.hotkey(), '8');
+        assert_eq!(ClientId::Pi.hotkey(), '9');
+        assert_eq!(ClientId::Kimi.hotkey(), '0');
+        assert_eq!(ClientId::Qwen.hotkey(), 'w');
+        assert_eq!(ClientId::RooCode.hotkey(), 'r');
+        assert_eq!(ClientId::KiloCode.hotkey(), 'k');
+        assert_eq!(ClientId::Mux.hotkey(), 'x');
+        assert_eq!(ClientId::Kilo.hotkey(), 'l');
+        assert_eq!(ClientId::Crush.hotkey(), 'h');
+        assert_eq!(ClientId::Hermes.hotkey(), 'e');
+        assert_eq!(ClientId::Codebuff.hotkey(), 'b');
+        assert_eq!(ClientId::Antigravity.hotkey(), 'a');
+        assert_eq!(ClientId::Zed.hotkey(), 'z');
+        assert_eq!(ClientId::Kiro.hotkey(), 'i');
+        assert_eq!(ClientId::Trae.hotkey(), 'y');
+        assert_eq!(ClientId::Cline.hotkey(), 'n');
+        assert_eq!(ClientId::Gjc.hotkey(), 'g');
+        assert_eq!(ClientId::Grok.hotkey(), 'u');
+        assert_eq!(ClientId::Jcode.hotkey(), 'j');
+        assert_eq!(ClientId::AntigravityCli.hotkey(), 'f');
+        assert_eq!(ClientId::Junie.hotkey(), 'p');
+        assert_eq!(ClientId::CodeBuddy.hotkey(), 'C');
+        assert_eq!(ClientId::WorkBuddy.hotkey(), 'B');
     }
 
     #[test]
     fn test_client_from_key() {
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('1'),
-            Some(ClientId::OpenCode)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('2'),
-            Some(ClientId::Claude)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('3'),
-            Some(ClientId::Codex)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('c'),
-            Some(ClientId::Copilot)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('4'),
-            Some(ClientId::Cursor)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('5'),
-            Some(ClientId::Gemini)
-        );
-        assert_eq!(crate::tui::client_ui::from_hotkey('6'), Some(ClientId::Amp));
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('7'),
-            Some(ClientId::Droid)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('8'),
-            Some(ClientId::OpenClaw)
-        );
-        assert_eq!(crate::tui::client_ui::from_hotkey('9'), Some(ClientId::Pi));
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('0'),
-            Some(ClientId::Kimi)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('w'),
-            Some(ClientId::Qwen)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('r'),
-            Some(ClientId::RooCode)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('k'),
-            Some(ClientId::KiloCode)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('l'),
-            Some(ClientId::Kilo)
-        );
-        assert_eq!(crate::tui::client_ui::from_hotkey('x'), Some(ClientId::Mux));
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('h'),
-            Some(ClientId::Crush)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('e'),
-            Some(ClientId::Hermes)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('b'),
-            Some(ClientId::Codebuff)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('a'),
-            Some(ClientId::Antigravity)
-        );
-        assert_eq!(crate::tui::client_ui::from_hotkey('z'), Some(ClientId::Zed));
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('i'),
-            Some(ClientId::Kiro)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('y'),
-            Some(ClientId::Trae)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('u'),
-            Some(ClientId::Grok)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('j'),
-            Some(ClientId::Jcode)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('f'),
-            Some(ClientId::AntigravityCli)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('p'),
-            Some(ClientId::Junie)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('C'),
-            Some(ClientId::CodeBuddy)
-        );
-        assert_eq!(
-            crate::tui::client_ui::from_hotkey('B'),
-            Some(ClientId::WorkBuddy)
-        );
+        assert_eq!(ClientId::from_hotkey('1'), Some(ClientId::OpenCode));
+        assert_eq!(ClientId::from_hotkey('2'), Some(ClientId::Claude));
+        assert_eq!(ClientId::from_hotkey('3'), Some(ClientId::Codex));
+        assert_eq!(ClientId::from_hotkey('c'), Some(ClientId::Copilot));
+        assert_eq!(ClientId::from_hotkey('4'), Some(ClientId::Cursor));
+        assert_eq!(ClientId::from_hotkey('5'), Some(ClientId::Gemini));
+        assert_eq!(ClientId::from_hotkey('6'), Some(ClientId::Amp));
+        assert_eq!(ClientId::from_hotkey('7'), Some(ClientId::Droid));
+        assert_eq!(ClientId::from_hotkey('8'), Some(ClientId::OpenClaw));
+        assert_eq!(ClientId::from_hotkey('9'), Some(ClientId::Pi));
+        assert_eq!(ClientId::from_hotkey('0'), Some(ClientId::Kimi));
+        assert_eq!(ClientId::from_hotkey('w'), Some(ClientId::Qwen));
+        assert_eq!(ClientId::from_hotkey('r'), Some(ClientId::RooCode));
+        assert_eq!(ClientId::from_hotkey('k'), Some(ClientId::KiloCode));
+        assert_eq!(ClientId::from_hotkey('l'), Some(ClientId::Kilo));
+        assert_eq!(ClientId::from_hotkey('x'), Some(ClientId::Mux));
+        assert_eq!(ClientId::from_hotkey('h'), Some(ClientId::Crush));
+        assert_eq!(ClientId::from_hotkey('e'), Some(ClientId::Hermes));
+        assert_eq!(ClientId::from_hotkey('b'), Some(ClientId::Codebuff));
+        assert_eq!(ClientId::from_hotkey('a'), Some(ClientId::Antigravity));
+        assert_eq!(ClientId::from_hotkey('z'), Some(ClientId::Zed));
+        assert_eq!(ClientId::from_hotkey('i'), Some(ClientId::Kiro));
+        assert_eq!(ClientId::from_hotkey('y'), Some(ClientId::Trae));
+        assert_eq!(ClientId::from_hotkey('u'), Some(ClientId::Grok));
+        assert_eq!(ClientId::from_hotkey('j'), Some(ClientId::Jcode));
+        assert_eq!(ClientId::from_hotkey('f'), Some(ClientId::AntigravityCli));
+        assert_eq!(ClientId::from_hotkey('p'), Some(ClientId::Junie));
+        assert_eq!(ClientId::from_hotkey('C'), Some(ClientId::CodeBuddy));
+        assert_eq!(ClientId::from_hotkey('B'), Some(ClientId::WorkBuddy));
     }
 
     #[test]
@@ -1928,10 +1908,35 @@ mod tests {
         assert_eq!(parse_date(""), None);
     }
 
+    fn graph_for_today(daily: &[DailyUsage], today: NaiveDate) -> GraphData {
+        let (start, end) = graph_window(None, None, today);
+        build_contribution_graph(daily, start, end, IntensityBasis::Cost)
+    }
+
+    fn graph_days(graph: &GraphData) -> Vec<NaiveDate> {
+        graph
+            .weeks
+            .iter()
+            .flatten()
+            .filter_map(|day| day.as_ref().map(|day| day.date))
+            .collect()
+    }
+
+    fn one_daily_usage(date: NaiveDate) -> Vec<DailyUsage> {
+        vec![DailyUsage {
+            date,
+            tokens: TokenBreakdown::default(),
+            cost: 0.0,
+            source_breakdown: BTreeMap::new(),
+            message_count: 0,
+            turn_count: 0,
+        }]
+    }
+
     #[test]
     fn test_build_contribution_graph_uses_provided_today() {
         let today = NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
-        let graph = build_contribution_graph_for_today(&[], today);
+        let graph = graph_for_today(&[], today);
         assert!(graph.weeks.is_empty());
 
         let daily = vec![DailyUsage {
@@ -1942,7 +1947,7 @@ mod tests {
             message_count: 0,
             turn_count: 0,
         }];
-        let graph = build_contribution_graph_for_today(&daily, today);
+        let graph = graph_for_today(&daily, today);
         let last_day = graph
             .weeks
             .last()
@@ -1952,6 +1957,58 @@ mod tests {
         assert_eq!(last_day, Some(today));
     }
 
+    #[test]
+    fn test_build_contribution_graph_ninety_day_window() {
+        // Saturday; 90 days back lands on Monday 2024-03-18, padded to Sunday.
+        let today = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap();
+        let (start, end) = graph_window(None, Some(90), today);
+        assert_eq!(start, NaiveDate::from_ymd_opt(2024, 3, 18).unwrap());
+        assert_eq!(end, today);
+
+        let graph =
+            build_contribution_graph(&one_daily_usage(today), start, end, IntensityBasis::Cost);
+        assert_eq!(graph.weeks.len(), 13);
+        assert!(graph.weeks.iter().all(|week| week.len() == 7));
+
+        let days = graph_days(&graph);
+        assert_eq!(days.first(), NaiveDate::from_ymd_opt(2024, 3, 17).as_ref());
+        assert_eq!(days.first().unwrap().weekday(), chrono::Weekday::Sun);
+        assert_eq!(days.last(), Some(&today));
+    }
+
+    #[test]
+    fn test_build_contribution_graph_year_window_covers_leap_year() {
+        // `year` wins over `graph_days`: the graph spans Jan 1..Dec 31.
+        let today = NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
+        let (start, end) = graph_window(Some("2024"), Some(90), today);
+        assert_eq!(start, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2024, 12, 31).unwrap());
+
+        let graph =
+            build_contribution_graph(&one_daily_usage(start), start, end, IntensityBasis::Cost);
+        // 2024-01-01 is a Monday, so one padding Sunday precedes 366 days:
+        // 52 full weeks plus a trailing Sun..Tue week.
+        assert_eq!(graph.weeks.len(), 53);
+        assert_eq!(graph.weeks.last().unwrap().len(), 3);
+
+        let days = graph_days(&graph);
+        assert_eq!(days.len(), 367);
+        assert_eq!(days.first(), NaiveDate::from_ymd_opt(2023, 12, 31).as_ref());
+        assert_eq!(days.last(), Some(&end));
+    }
+
+    #[test]
+    fn test_graph_window_defaults_to_one_year() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
+        assert_eq!(
+            graph_window(None, None, today),
+            (NaiveDate::from_ymd_opt(2025, 3, 9).unwrap(), today)
+        );
+        // Unparseable years fall back to the rolling window; zero is clamped
+        // to a single day.
+        assert_eq!(graph_window(Some("all"), Some(0), today), (today, today));
+    }
+
     #[test]
     fn test_aggregate_messages_builds_agent_usage() {
         let loader = DataLoader::new(None);
@@ -1968,6 +2025,7 @@ mod tests {
                     cache_read: 0,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 1.25,
                 Some("builder".to_string()),
@@ -1984,6 +2042,7 @@ mod tests {
                     cache_read: 0,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 2.75,
                 Some("builder".to_string()),
@@ -2022,6 +2081,7 @@ mod tests {
                         cache_read: 0,
                         cache_write: 0,
                         reasoning: 0,
+                        ..Default::default()
                     },
                     0.0,
                 )
@@ -2273,6 +2333,7 @@ mod tests {
                             cache_read: 0,
                             cache_write: 0,
                             reasoning: 0,
+                            ..Default::default()
                         },
                         1.0,
                     ),
@@ -2288,6 +2349,7 @@ mod tests {
                             cache_read: 0,
                             cache_write: 0,
                             reasoning: 0,
+                            ..Default::default()
                         },
                         2.0,
                     ),
@@ -2339,6 +2401,7 @@ mod tests {
                             cache_read: 0,
                             cache_write: 0,
                             reasoning: 0,
+                            ..Default::default()
                         },
                         1.0,
                     ),
@@ -2354,6 +2417,7 @@ mod tests {
                             cache_read: 0,
                             cache_write: 0,
                             reasoning: 0,
+                            ..Default::default()
                         },
                         2.0,
                     ),
@@ -2396,6 +2460,7 @@ mod tests {
                     cache_read: 100,
                     cache_write: 20,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 1.5,
                 Some("Sisyphus".to_string()),
@@ -2412,6 +2477,7 @@ mod tests {
                     cache_read: 200,
                     cache_write: 40,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 2.5,
                 Some("Sisyphus (Ultraworker)".to_string()),
@@ -2446,6 +2512,7 @@ mod tests {
                     cache_read: 0,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 1.5,
                 Some("Hephaestus".to_string()),
@@ -2462,6 +2529,7 @@ mod tests {
                     cache_read: 0,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 2.5,
                 Some("hephaestus".to_string()),
@@ -2495,6 +2563,7 @@ mod tests {
                     cache_read: 0,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 1.5,
                 Some("Sisyphus".to_string()),
@@ -2511,6 +2580,7 @@ mod tests {
                     cache_read: 0,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 2.5,
                 Some("Sisyphus (Ultraworker)".to_string()),
@@ -2627,6 +2697,7 @@ after"#,
                 cache_read: 32_000,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
         ) + expected_message_cost(
             &pricing,
@@ -2638,6 +2709,7 @@ after"#,
                 cache_read: 12_000,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
         );
         let reviewer_expected = expected_message_cost(
@@ -2650,6 +2722,7 @@ after"#,
                 cache_read: 8_000,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
         ) + expected_message_cost(
             &pricing,
@@ -2661,6 +2734,7 @@ after"#,
                 cache_read: 3_000,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
         );
 
@@ -2724,6 +2798,7 @@ after"#,
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
         );
 
@@ -2785,11 +2860,122 @@ after"#,
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             cost,
         )
     }
 
+    #[test]
+    fn test_aggregate_messages_retains_model_drilldown() {
+        let loader = DataLoader::new(None);
+        let day1 = 1_735_689_600_000; // 2025-01-01T00:00:00Z
+        let day2 = day1 + 86_400_000;
+        let mut other_model = make_msg(day1 + 1_000, 1, 1, 9.0);
+        other_model.model_id = "gpt-5".to_string();
+        other_model.provider_id = "openai".to_string();
+        let mut cheap_repeat = make_msg(day2 + 1_000, 3, 0, 0.5);
+        cheap_repeat.session_id = format!("session-{day1}");
+
+        let usage = loader
+            .aggregate_messages(
+                vec![
+                    make_msg(day1, 10, 5, 1.0),
+                    make_msg(day2, 20, 10, 2.0),
+                    cheap_repeat,
+                    other_model,
+                ],
+                &GroupBy::Model,
+            )
+            .unwrap();
+
+        let sonnet = usage
+            .models
+            .iter()
+            .find(|m| m.model.starts_with("claude-sonnet"))
+            .unwrap();
+        let days: Vec<(String, f64)> = sonnet
+            .drilldown
+            .daily
+            .iter()
+            .map(|d| (d.date.to_string(), d.cost))
+            .collect();
+        assert_eq!(
+            days,
+            vec![(message_date(day2), 2.5), (message_date(day1), 1.0),]
+        );
+
+        let sessions: Vec<(&str, f64)> = sonnet
+            .drilldown
+            .top_sessions
+            .iter()
+            .map(|s| (s.session_id.as_str(), s.cost))
+            .collect();
+        let session2 = format!("session-{day2}");
+        let session1 = format!("session-{day1}");
+        assert_eq!(
+            sessions,
+            vec![(session2.as_str(), 2.0), (session1.as_str(), 1.5)]
+        );
+    }
+
+    fn message_date(timestamp_ms: i64) -> String {
+        make_msg(timestamp_ms, 0, 0, 0.0).date
+    }
+
+    #[test]
+    fn test_contribution_graph_intensity_matches_core_graph_for_each_basis() {
+        let day = 86_400_000;
+        let base = 1_735_732_800_000; // 2025-01-01T12:00:00Z
+
+        // Costs and token volumes rank the days differently.
+        let messages = vec![
+            make_msg(base, 1_000_000, 0, 1.0),
+            make_msg(base + day, 100_000, 0, 10.0),
+            make_msg(base + 2 * day, 400_000, 0, 4.0),
+            make_msg(base + 3 * day, 0, 0, 0.0),
+        ];
+
+        for basis in [IntensityBasis::Cost, IntensityBasis::Tokens] {
+            let loader = DataLoader::with_filters(None, None, None, Some("2025".to_string()))
+                .with_intensity_basis(basis);
+            let usage = loader
+                .aggregate_messages(messages.clone(), &GroupBy::Model)
+                .unwrap();
+            let tui: HashMap<String, u8> = usage
+                .graph
+                .unwrap()
+                .weeks
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|d| {
+                    (
+                        d.date.to_string(),
+                        tokscale_core::intensity_for_ratio(d.intensity),
+                    )
+                })
+                .collect();
+
+            let mut core = tokscale_core::aggregate_by_date(messages.clone());
+            tokscale_core::calculate_intensities_with(
+                &mut core,
+                tokscale_core::IntensityMode::Linear,
+                basis,
+            );
+
+            assert_eq!(core.len(), 4);
+            for contribution in &core {
+                assert_eq!(
+                    tui.get(&contribution.date),
+                    Some(&contribution.intensity),
+                    "{basis} on {}",
+                    contribution.date
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_minutely_aggregation_skipped_when_flag_disabled() {
         let loader = DataLoader::new(None);
@@ -1,13 +1,15 @@
+use std::path::{Path, PathBuf};
+
 use anyhow::Result;
-use serde_json::json;
+use serde_json::{json, Value};
 
-use super::data::UsageData;
+use super::data::{TokenBreakdown, UsageData};
 
-/// Serializes `UsageData` into the pretty-printed JSON payload used by the
-/// `e` export hotkey. Pure: callers are responsible for file I/O and any
-/// user-facing status messages.
-pub fn build_export_json(data: &UsageData) -> Result<String> {
-    let export_data = json!({
+/// Serializes `UsageData` into the export payload: models, agents, daily,
+/// monthly, sessions and totals. Pure: callers are responsible for file I/O
+/// and any user-facing status messages.
+pub fn build_export_json(data: &UsageData) -> Value {
+    json!({
         "models": data.models.iter().map(|m| json!({
             "model": m.model,
             "provider": m.provider,
@@ -83,7 +85,221 @@ pub fn build_export_json(data: &UsageData) -> Result<String> {
             "tokens": data.total_tokens,
             "cost": data.total_cost
         }
-    });
+    })
+}
+
+/// Writes the export for `data` (the usage from [`build_export_json`] plus
+/// its contribution graph under `graph`, in the same format as
+/// `tokscale graph`) to a new `tokscale-export-<timestamp>.json` in `dir`.
+/// Works from the loaded data only, so nothing is rescanned or repriced.
+pub fn write_export(data: &UsageData, dir: &Path) -> Result<PathBuf> {
+    let mut usage = build_export_json(data);
+    usage["graph"] = serde_json::to_value(crate::to_ts_token_contribution_data(
+        &build_graph(data),
+        None,
+    ))?;
+    write_export_file(dir, &serde_json::to_string_pretty(&usage)?)
+}
+
+/// Writes `json` to a new `tokscale-export-<timestamp>.json` in `dir`.
+fn write_export_file(dir: &Path, json: &str) -> Result<PathBuf> {
+    let path = dir.join(format!(
+        "tokscale-export-{}.json",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::write(&path, json)?;
+    Ok(path)
+}
+
+/// The contribution graph of the loaded days: one row per client and model
+/// each day, summarized the way `tokscale graph` summarizes a scan. The TUI
+/// keeps no session intervals, so `timeMetrics` is left out.
+fn build_graph(data: &UsageData) -> tokscale_core::GraphResult {
+    use tokscale_core::{ClientContribution, DailyContribution, DailyTotals};
+
+    let mut contributions: Vec<DailyContribution> = data
+        .daily
+        .iter()
+        .map(|day| {
+            let mut clients: Vec<ClientContribution> = Vec::new();
+            for (client, source) in &day.source_breakdown {
+                for model in source.models.values() {
+                    let existing = clients.iter_mut().find(|row| {
+                        row.client == *client
+                            && row.model_id == model.color_key
+                            && row.provider_id == model.provider
+                    });
+                    match existing {
+                        Some(row) => {
+                            add_tokens(&mut row.tokens, &model.tokens);
+                            row.cost += model.cost;
+                            row.messages = row.messages.saturating_add(clamp_i32(model.messages));
+                        }
+                        None => clients.push(ClientContribution {
+                            client: client.clone(),
+                            model_id: model.color_key.clone(),
+                            provider_id: model.provider.clone(),
+                            tokens: core_tokens(&model.tokens),
+                            cost: model.cost,
+                            messages: clamp_i32(model.messages),
+                        }),
+                    }
+                }
+            }
+            let token_breakdown = core_tokens(&day.tokens);
+            DailyContribution {
+                date: day.date.format("%Y-%m-%d").to_string(),
+                totals: DailyTotals {
+                    tokens: clamp_i64(day.tokens.total()),
+                    cost: day.cost,
+                    messages: clamp_i32(u64::from(day.message_count)),
+                },
+                intensity: 0,
+                token_breakdown,
+                clients,
+                active_time_ms: None,
+            }
+        })
+        .collect();
+    contributions.sort_by(|a, b| a.date.cmp(&b.date));
+    tokscale_core::calculate_intensities(&mut contributions);
+    tokscale_core::generate_graph_result(contributions, 0)
+}
+
+fn core_tokens(tokens: &TokenBreakdown) -> tokscale_core::TokenBreakdown {
+    tokscale_core::TokenBreakdown {
+        input: clamp_i64(tokens.input),
+        output: clamp_i64(tokens.output),
+        cache_read: clamp_i64(tokens.cache_read),
+        cache_write: clamp_i64(tokens.cache_write),
+        reasoning: clamp_i64(tokens.reasoning),
+        ..Default::default()
+    }
+}
+
+fn add_tokens(total: &mut tokscale_core::TokenBreakdown, tokens: &TokenBreakdown) {
+    let tokens = core_tokens(tokens);
+    total.input = total.input.saturating_add(tokens.input);
+    total.output = total.output.saturating_add(tokens.output);
+    total.cache_read = total.cache_read.saturating_add(tokens.cache_read);
+    total.cache_write = total.cache_write.saturating_add(tokens.cache_write);
+    total.reasoning = total.reasoning.saturating_add(tokens.reasoning);
+}
+
+fn clamp_i64(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+fn clamp_i32(value: u64) -> i32 {
+    i32::try_from(value).unwrap_or(i32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::data::{DailyModelInfo, DailySourceInfo, DailyUsage};
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn tokens(input: u64, reasoning: u64) -> TokenBreakdown {
+        TokenBreakdown {
+            input,
+            reasoning,
+            ..Default::default()
+        }
+    }
+
+    fn model(model: &str, workspace: &str, input: u64) -> (String, DailyModelInfo) {
+        (
+            format!("{workspace}::{model}"),
+            DailyModelInfo {
+                provider: "anthropic".to_string(),
+                display_name: format!("{workspace} / {model}"),
+                color_key: model.to_string(),
+                tokens: tokens(input, 0),
+                cost: 0.25,
+                messages: 2,
+            },
+        )
+    }
+
+    fn usage() -> UsageData {
+        let models: BTreeMap<_, _> = [
+            model("claude-sonnet-4-6", "alpha", 100),
+            model("claude-sonnet-4-6", "beta", 50),
+        ]
+        .into_iter()
+        .collect();
+        UsageData {
+            daily: vec![DailyUsage {
+                date: chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+                tokens: tokens(150, 10),
+                cost: 0.5,
+                source_breakdown: [(
+                    "claude".to_string(),
+                    DailySourceInfo {
+                        tokens: tokens(150, 10),
+                        cost: 0.5,
+                        models,
+                    },
+                )]
+                .into_iter()
+                .collect(),
+                message_count: 4,
+                turn_count: 2,
+            }],
+            total_tokens: 160,
+            total_cost: 0.5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn export_file_is_written_into_the_given_dir() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let path = write_export_file(dir.path(), "{}").unwrap();
+
+        assert_eq!(path.parent(), Some(dir.path()));
+        let name = path.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("tokscale-export-") && name.ends_with(".json"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{}");
+    }
+
+    #[test]
+    fn export_keeps_the_usage_keys_and_adds_the_graph() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let path = write_export(&usage(), dir.path()).unwrap();
+
+        let json: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        for key in ["models", "agents", "daily", "monthly", "sessions"] {
+            assert!(json[key].is_array(), "missing {key}");
+        }
+        assert_eq!(json["totals"]["tokens"], 160);
+        assert_eq!(json["graph"]["meta"]["dateRange"]["start"], "2026-03-02");
+        assert_eq!(json["graph"]["summary"]["totalTokens"], 160);
+    }
+
+    #[test]
+    fn graph_folds_each_days_models_per_client() {
+        let graph = build_graph(&usage());
 
-    Ok(serde_json::to_string_pretty(&export_data)?)
+        assert_eq!(graph.contributions.len(), 1);
+        let day = &graph.contributions[0];
+        assert_eq!(day.date, "2026-03-02");
+        assert_eq!(day.totals.tokens, 160);
+        assert_eq!(day.totals.messages, 4);
+        assert_eq!(day.token_breakdown.reasoning, 10);
+        assert_eq!(day.intensity, 4);
+        assert_eq!(day.clients.len(), 1);
+        let row = &day.clients[0];
+        assert_eq!(row.client, "claude");
+        assert_eq!(row.model_id, "claude-sonnet-4-6");
+        assert_eq!(row.provider_id, "anthropic");
+        assert_eq!(row.tokens.input, 150);
+        assert_eq!(row.cost, 0.5);
+        assert_eq!(row.messages, 4);
+        assert_eq!(graph.summary.active_days, 1);
+        assert_eq!(graph.years[0].year, "2026");
+    }
 }
@@ -1,6 +1,5 @@
 mod app;
 mod cache;
-pub mod client_ui;
 pub(crate) mod codex_login;
 mod colors;
 pub mod config;
@@ -44,7 +43,7 @@ use crossterm::{
     },
 };
 use ratatui::prelude::*;
-use tokscale_core::ClientId;
+use tokscale_core::{ClientId, IntensityBasis};
 
 use crate::ClientFilter;
 
@@ -62,8 +61,13 @@ fn background_data_loader(
     until: Option<String>,
     year: Option<String>,
     minutely_enabled: bool,
+    graph_days: Option<u32>,
+    intensity_basis: IntensityBasis,
 ) -> DataLoader {
-    DataLoader::with_filters(None, since, until, year).with_minutely_enabled(minutely_enabled)
+    DataLoader::with_filters(None, since, until, year)
+        .with_minutely_enabled(minutely_enabled)
+        .with_graph_days(graph_days)
+        .with_intensity_basis(intensity_basis)
 }
 
 fn background_cache_scope(
@@ -198,9 +202,18 @@ pub fn run(
         let bg_group_by = app.group_by.borrow().clone();
         let bg_report_scope = background_cache_scope(&since, &until, &year);
         let bg_minutely_enabled = app.settings.minutely_tab_enabled;
+        let bg_graph_days = app.settings.graph_days;
+        let bg_intensity_basis = app.settings.graph_intensity_basis;
 
         thread::spawn(move || {
-            let loader = background_data_loader(bg_since, bg_until, bg_year, bg_minutely_enabled);
+            let loader = background_data_loader(
+                bg_since,
+                bg_until,
+                bg_year,
+                bg_minutely_enabled,
+                bg_graph_days,
+                bg_intensity_basis,
+            );
             let result = loader.load(&bg_clients, &bg_group_by, bg_include_synthetic);
 
             if let Ok(ref data) = result {
@@ -323,9 +336,18 @@ fn run_loop_with_background(
             let group_by = app.group_by.borrow().clone();
             let report_scope = background_cache_scope(&since, &until, &year);
             let minutely_enabled = app.settings.minutely_tab_enabled;
+            let graph_days = app.settings.graph_days;
+            let intensity_basis = app.settings.graph_intensity_basis;
 
             thread::spawn(move || {
-                let loader = background_data_loader(since, until, year, minutely_enabled);
+                let loader = background_data_loader(
+                    since,
+                    until,
+                    year,
+                    minutely_enabled,
+                    graph_days,
+                    intensity_basis,
+                );
                 let result = loader.load(&clients, &group_by, include_synthetic);
                 if let Ok(ref data) = result {
                     save_cached_data(data, &enabled_clients, &group_by, &report_scope);
@@ -428,13 +450,26 @@ mod tests {
 
     #[test]
     fn background_loader_preserves_minutely_toggle() {
-        let enabled = background_data_loader(None, None, None, true);
+        let enabled = background_data_loader(None, None, None, true, None, IntensityBasis::Cost);
         assert!(enabled.minutely_enabled);
 
-        let disabled = background_data_loader(None, None, None, false);
+        let disabled = background_data_loader(None, None, None, false, None, IntensityBasis::Cost);
         assert!(!disabled.minutely_enabled);
     }
 
+    #[test]
+    fn background_loader_preserves_graph_days() {
+        let loader =
+            background_data_loader(None, None, None, false, Some(90), IntensityBasis::Cost);
+        assert_eq!(loader.graph_days, Some(90));
+    }
+
+    #[test]
+    fn background_loader_preserves_intensity_basis() {
+        let loader = background_data_loader(None, None, None, false, None, IntensityBasis::Tokens);
+        assert_eq!(loader.intensity_basis, IntensityBasis::Tokens);
+    }
+
     #[test]
     fn background_cache_scope_uses_date_filters() {
         let scope = background_cache_scope(
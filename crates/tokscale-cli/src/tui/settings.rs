@@ -149,6 +149,15 @@ pub struct Settings {
     /// tab and enable its aggregation in subsequent loads.
     #[serde(default)]
     pub minutely_tab_enabled: bool,
+    /// Contribution-graph window length in days, ending today. `None`
+    /// keeps the default one-year graph; `--year` always shows that
+    /// calendar year instead.
+    #[serde(default)]
+    pub graph_days: Option<u32>,
+    /// Daily total that drives contribution-graph colors: `cost` (default)
+    /// or `tokens`. Matches `tokscale graph --intensity-basis`.
+    #[serde(default)]
+    pub graph_intensity_basis: tokscale_core::IntensityBasis,
     #[serde(default)]
     pub autosubmit: AutosubmitSettings,
     /// User-defined model-name aliases folded at grouping time. Different
@@ -210,6 +219,8 @@ impl Default for Settings {
             default_clients: Vec::new(),
             light: LightSettings::default(),
             minutely_tab_enabled: false,
+            graph_days: None,
+            graph_intensity_basis: tokscale_core::IntensityBasis::default(),
             autosubmit: AutosubmitSettings::default(),
             model_aliases: tokscale_core::ModelAliasMap::default(),
         }
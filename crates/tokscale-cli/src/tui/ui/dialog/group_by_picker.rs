@@ -54,15 +54,20 @@ impl GroupByPickerDialog {
                 description: "Group local usage by workspace key, then model",
             },
             GroupByOption {
-                value: GroupBy::Session,
+                value: GroupBy::SessionModel,
                 label: "Session + Model",
                 description: "One row per session_id and model (attribute cost per session)",
             },
             GroupByOption {
-                value: GroupBy::ClientSession,
+                value: GroupBy::ClientSessionModel,
                 label: "Client + Session + Model",
                 description: "One row per client, session_id, and model",
             },
+            GroupByOption {
+                value: GroupBy::Family,
+                label: "Model Family",
+                description: "Roll models up to their family (claude, gpt, gemini, ...)",
+            },
         ];
 
         let cursor = options.iter().position(|o| o.value == current).unwrap_or(1);
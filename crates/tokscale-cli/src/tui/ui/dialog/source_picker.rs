@@ -12,17 +12,17 @@ use ratatui::{
     Frame,
 };
 
-use crate::tui::client_ui;
 use crate::tui::themes::Theme;
 use crate::ClientFilter;
+use tokscale_core::ClientId;
 
 use super::{DialogContent, DialogResult};
 
 /// Hotkey assigned to the Synthetic option in the dialog.
 ///
 /// `'n'` is reserved here for Synthetic so it does not collide with any
-/// real client hotkey in [`crate::tui::client_ui::CLIENT_UI`]. The toggle
-/// path checks `client_ui::from_hotkey` first, so any future client that
+/// real client hotkey in [`tokscale_core::clients::CLIENTS`]. The toggle
+/// path checks `ClientId::from_hotkey` first, so any future client that
 /// also wants `'n'` would silently shadow Synthetic — keep this value in
 /// sync with the client hotkey table.
 const SYNTHETIC_HOTKEY: char = 'n';
@@ -246,9 +246,9 @@ impl DialogContent for ClientPickerDialog {
             KeyCode::Char(c) => {
                 // Hotkey toggle: route through the centralized
                 // `ClientFilter` mapping so adding a new hotkey only
-                // requires editing `client_ui.rs` + (if it's a non-client
+                // requires editing `clients.rs` in core + (if it's a non-client
                 // meta source) updating SYNTHETIC_HOTKEY here.
-                if let Some(client_id) = client_ui::from_hotkey(c) {
+                if let Some(client_id) = ClientId::from_hotkey(c) {
                     self.toggle(ClientFilter::from_client_id(client_id));
                 } else if c == SYNTHETIC_HOTKEY {
                     self.toggle(ClientFilter::Synthetic);
@@ -263,12 +263,12 @@ impl DialogContent for ClientPickerDialog {
     }
 }
 
-/// Display name for a `ClientFilter` row in the picker. Delegates to the
-/// existing `client_ui` registry for `ClientId`-backed variants and adds
+/// Display name for a `ClientFilter` row in the picker. Delegates to
+/// `ClientId::display_name` for `ClientId`-backed variants and adds
 /// the meta-client label for `Synthetic`.
 fn display_name(client: ClientFilter) -> &'static str {
     match client.to_client_id() {
-        Some(id) => client_ui::display_name(id),
+        Some(id) => id.display_name(),
         None => "Synthetic",
     }
 }
@@ -276,7 +276,7 @@ fn display_name(client: ClientFilter) -> &'static str {
 /// Hotkey for a `ClientFilter` row. Mirrors `display_name`'s split.
 fn hotkey(client: ClientFilter) -> char {
     match client.to_client_id() {
-        Some(id) => client_ui::hotkey(id),
+        Some(id) => id.hotkey(),
         None => SYNTHETIC_HOTKEY,
     }
 }
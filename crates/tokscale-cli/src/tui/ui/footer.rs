@@ -150,6 +150,9 @@ fn render_main_row(frame: &mut Frame, app: &mut App, area: Rect) {
 
 fn current_count_label(app: &App) -> String {
     match app.current_tab {
+        Tab::Models if app.is_model_detail_active() => {
+            format!(" ({} days)", app.get_model_detail_days().len())
+        }
         Tab::Overview | Tab::Models => format!(" ({} models)", app.data.models.len()),
         Tab::Agents => format!(" ({} agents)", app.data.agents.len()),
         Tab::Daily if app.is_daily_detail_active() => {
@@ -206,6 +209,14 @@ fn render_help_row(frame: &mut Frame, app: &App, area: Rect) {
                 spans.push(Span::styled("↵", Style::default().fg(Color::Yellow)));
             }
         }
+        if app.current_tab == Tab::Models {
+            spans.push(Span::styled("·", Style::default().fg(app.theme.muted)));
+            if app.is_model_detail_active() {
+                spans.push(Span::styled("esc", Style::default().fg(Color::Yellow)));
+            } else {
+                spans.push(Span::styled("↵", Style::default().fg(Color::Yellow)));
+            }
+        }
         if app.current_tab == Tab::Hourly {
             spans.push(Span::styled("·", Style::default().fg(app.theme.muted)));
             spans.push(Span::styled("v", Style::default().fg(Color::Yellow)));
@@ -253,6 +264,20 @@ fn render_help_row(frame: &mut Frame, app: &App, area: Rect) {
             }
             spans.push(Span::styled(" • ", Style::default().fg(app.theme.muted)));
         }
+        if app.current_tab == Tab::Models {
+            if app.is_model_detail_active() {
+                spans.push(Span::styled(
+                    "[esc:back]",
+                    Style::default().fg(Color::Yellow),
+                ));
+            } else {
+                spans.push(Span::styled(
+                    "[enter:details]",
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            spans.push(Span::styled(" • ", Style::default().fg(app.theme.muted)));
+        }
         if app.current_tab == Tab::Hourly {
             spans.push(Span::styled(
                 "[v:profile]",
@@ -19,7 +19,7 @@ fn workspace_label(model: &crate::tui::data::ModelUsage) -> &str {
 }
 
 fn model_display_name(model: &crate::tui::data::ModelUsage, group_by: &GroupBy) -> String {
-    if *group_by == GroupBy::WorkspaceModel {
+    if matches!(group_by, GroupBy::WorkspaceModel | GroupBy::Project) {
         format!("{} / {}", workspace_label(model), model.model)
     } else {
         model.model.clone()
@@ -27,6 +27,11 @@ fn model_display_name(model: &crate::tui::data::ModelUsage, group_by: &GroupBy)
 }
 
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    if app.is_model_detail_active() {
+        render_detail(frame, app, area);
+        return;
+    }
+
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(app.theme.border))
@@ -75,7 +80,7 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         vec!["Model", "Cost"]
     } else if is_narrow {
         vec!["Model", "Tokens", "Cost"]
-    } else if group_by == GroupBy::WorkspaceModel {
+    } else if matches!(group_by, GroupBy::WorkspaceModel | GroupBy::Project) {
         vec![
             "#",
             "Workspace",
@@ -165,7 +170,7 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
                     total_tokens_cell(model.tokens.total(), &app.theme),
                     Cell::from(format_cost(model.cost)).style(Style::default().fg(Color::Green)),
                 ]
-            } else if group_by == GroupBy::WorkspaceModel {
+            } else if matches!(group_by, GroupBy::WorkspaceModel | GroupBy::Project) {
                 vec![
                     Cell::from(format!("{}", idx + 1)).style(Style::default().fg(theme_muted)),
                     Cell::from(truncate_text(workspace_label(model), 18)).style(
@@ -246,7 +251,7 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
             Constraint::Percentage(25),
             Constraint::Percentage(25),
         ]
-    } else if group_by == GroupBy::WorkspaceModel {
+    } else if matches!(group_by, GroupBy::WorkspaceModel | GroupBy::Project) {
         vec![
             Constraint::Length(3),
             Constraint::Length(18),
@@ -304,3 +309,231 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         );
     }
 }
+
+/// Drill-down for the model selected on the Models tab: its usage per day
+/// (scrollable) above its most expensive sessions.
+fn render_detail(frame: &mut Frame, app: &mut App, area: Rect) {
+    let group_by = app.group_by.borrow().clone();
+    let Some(model) = app.model_detail().cloned() else {
+        return;
+    };
+    let display_name = model_display_name(&model, &group_by);
+    let model_color = app.model_color_for(&model.provider, &model.color_key);
+
+    let sessions_height = if model.drilldown.top_sessions.is_empty() {
+        0
+    } else {
+        // Borders + header + one line per session.
+        (model.drilldown.top_sessions.len() as u16 + 3).min(area.height / 2)
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(sessions_height)])
+        .split(area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border))
+        .title(Span::styled(
+            format!(" Daily Breakdown: {} ", display_name),
+            Style::default()
+                .fg(model_color)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::default().bg(app.theme.background));
+
+    let inner = block.inner(chunks[0]);
+    frame.render_widget(block, chunks[0]);
+
+    let visible_height = inner.height.saturating_sub(1) as usize;
+    app.set_max_visible_items(visible_height);
+
+    let is_narrow = app.is_narrow();
+    let scroll_offset = app.scroll_offset;
+    let selected_index = app.selected_index;
+    let theme_accent = app.theme.accent;
+    let theme_muted = app.theme.muted;
+    let theme_selection = app.theme.selection;
+    let metric_input_style = app.theme.metric_input_style();
+    let metric_output_style = app.theme.metric_output_style();
+    let metric_cache_read_style = app.theme.metric_cache_read_style();
+    let metric_cache_write_style = app.theme.metric_cache_write_style();
+    let striped_row_style = app.theme.striped_row_style();
+
+    let days = &model.drilldown.daily;
+    if days.is_empty() {
+        let empty_msg = Paragraph::new("No daily data found for this model. Press Esc to go back.")
+            .style(Style::default().fg(theme_muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(empty_msg, inner);
+    } else {
+        let header_cells = if is_narrow {
+            vec!["Date", "Tokens", "Cost"]
+        } else {
+            vec![
+                "Date", "Msgs", "Input", "Output", "Cache R", "Cache W", "Total", "Cost",
+            ]
+        };
+        let header = Row::new(header_cells)
+            .style(
+                Style::default()
+                    .fg(theme_accent)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .height(1);
+
+        let days_len = days.len();
+        let start = scroll_offset.min(days_len.saturating_sub(1));
+        let end = (start + visible_height).min(days_len);
+
+        let rows: Vec<Row> = days[start..end]
+            .iter()
+            .enumerate()
+            .map(|(i, day)| {
+                let idx = i + start;
+                let date = Cell::from(day.date.format("%Y-%m-%d").to_string());
+                let cost =
+                    Cell::from(format_cost(day.cost)).style(Style::default().fg(Color::Green));
+                let cells = if is_narrow {
+                    vec![
+                        date,
+                        total_tokens_cell(day.tokens.total(), &app.theme),
+                        cost,
+                    ]
+                } else {
+                    vec![
+                        date,
+                        Cell::from(day.message_count.to_string())
+                            .style(Style::default().fg(theme_muted)),
+                        Cell::from(format_tokens(day.tokens.input)).style(metric_input_style),
+                        Cell::from(format_tokens(day.tokens.output)).style(metric_output_style),
+                        Cell::from(format_tokens(day.tokens.cache_read))
+                            .style(metric_cache_read_style),
+                        Cell::from(format_tokens(day.tokens.cache_write))
+                            .style(metric_cache_write_style),
+                        total_tokens_cell(day.tokens.total(), &app.theme),
+                        cost,
+                    ]
+                };
+
+                let row_style = if idx == selected_index {
+                    Style::default().bg(theme_selection)
+                } else if idx % 2 == 1 {
+                    striped_row_style
+                } else {
+                    Style::default()
+                };
+                Row::new(cells).style(row_style).height(1)
+            })
+            .collect();
+
+        let widths = if is_narrow {
+            vec![
+                Constraint::Percentage(40),
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+            ]
+        } else {
+            vec![
+                Constraint::Length(12),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ]
+        };
+        frame.render_widget(Table::new(rows, widths).header(header), inner);
+
+        if days_len > visible_height {
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(Some("▲"))
+                .end_symbol(Some("▼"));
+            let mut scrollbar_state =
+                viewport_scrollbar_state(days_len, scroll_offset, visible_height);
+            frame.render_stateful_widget(
+                scrollbar,
+                chunks[0].inner(Margin {
+                    horizontal: 0,
+                    vertical: 1,
+                }),
+                &mut scrollbar_state,
+            );
+        }
+    }
+
+    if sessions_height == 0 {
+        return;
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border))
+        .title(Span::styled(
+            " Top Sessions ",
+            Style::default()
+                .fg(theme_accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::default().bg(app.theme.background));
+    let inner = block.inner(chunks[1]);
+    frame.render_widget(block, chunks[1]);
+
+    let header = Row::new(if is_narrow {
+        vec!["Session", "Cost"]
+    } else {
+        vec!["Session", "Source", "Msgs", "Tokens", "Cost"]
+    })
+    .style(
+        Style::default()
+            .fg(theme_accent)
+            .add_modifier(Modifier::BOLD),
+    )
+    .height(1);
+
+    let rows: Vec<Row> = model
+        .drilldown
+        .top_sessions
+        .iter()
+        .map(|session| {
+            // Same fallback as the Sessions tab: title when stored, else ID.
+            let label = session
+                .title
+                .as_deref()
+                .filter(|t| !t.is_empty())
+                .unwrap_or(&session.session_id);
+            let label = Cell::from(truncate_text(label, if is_narrow { 24 } else { 48 }));
+            let cost =
+                Cell::from(format_cost(session.cost)).style(Style::default().fg(Color::Green));
+            let cells = if is_narrow {
+                vec![label, cost]
+            } else {
+                vec![
+                    label,
+                    Cell::from(get_client_display_name(&session.client))
+                        .style(Style::default().fg(theme_muted)),
+                    Cell::from(session.message_count.to_string())
+                        .style(Style::default().fg(theme_muted)),
+                    total_tokens_cell(session.tokens.total(), &app.theme),
+                    cost,
+                ]
+            };
+            Row::new(cells).height(1)
+        })
+        .collect();
+
+    let widths = if is_narrow {
+        vec![Constraint::Percentage(70), Constraint::Percentage(30)]
+    } else {
+        vec![
+            Constraint::Min(20),
+            Constraint::Length(14),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(10),
+        ]
+    };
+    frame.render_widget(Table::new(rows, widths).header(header), inner);
+}
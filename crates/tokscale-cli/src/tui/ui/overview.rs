@@ -19,7 +19,7 @@ struct ModelRowData {
 }
 
 fn overview_model_label(group_by: &GroupBy, model: &str, workspace_label: Option<&str>) -> String {
-    if *group_by == GroupBy::WorkspaceModel {
+    if matches!(group_by, GroupBy::WorkspaceModel | GroupBy::Project) {
         format!(
             "{} / {}",
             workspace_label.unwrap_or("Unknown workspace"),
@@ -31,7 +31,7 @@ fn overview_model_label(group_by: &GroupBy, model: &str, workspace_label: Option
 }
 
 fn overview_color_key<'a>(group_by: &GroupBy, model: &'a str) -> &'a str {
-    if *group_by == GroupBy::WorkspaceModel {
+    if matches!(group_by, GroupBy::WorkspaceModel | GroupBy::Project) {
         model
             .rsplit_once(" / ")
             .map(|(_, base_model)| base_model)
@@ -113,14 +113,7 @@ fn render_graph(frame: &mut Frame, app: &mut App, area: Rect) {
         } else {
             0.0
         };
-        let idx = match safe_intensity {
-            x if x <= 0.0 => 0,
-            x if x < 0.25 => 1,
-            x if x < 0.50 => 2,
-            x if x < 0.75 => 3,
-            _ => 4,
-        };
-        theme_colors[idx]
+        theme_colors[tokscale_core::intensity_for_ratio(safe_intensity) as usize]
     };
 
     let mut click_areas_to_add: Vec<(Rect, usize, usize)> = Vec::new();
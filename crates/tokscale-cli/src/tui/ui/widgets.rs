@@ -2,7 +2,6 @@ use ratatui::prelude::*;
 use ratatui::widgets::{Cell, ScrollbarState};
 use tokscale_core::ClientId;
 
-use crate::tui::client_ui;
 use crate::tui::config::TokscaleConfig;
 use crate::tui::themes::Theme;
 
@@ -27,15 +26,7 @@ pub(crate) fn total_tokens_cell(total_tokens: u64, theme: &Theme) -> Cell<'stati
 }
 
 pub fn format_tokens_with_commas(n: u64) -> String {
-    let s = n.to_string();
-    let mut result = String::new();
-    for (i, c) in s.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            result.insert(0, ',');
-        }
-        result.insert(0, c);
-    }
-    result
+    crate::locale::current().group(i64::try_from(n).unwrap_or(i64::MAX))
 }
 
 pub fn format_cost(cost: f64) -> String {
@@ -453,7 +444,7 @@ pub fn get_client_display_name(client: &str) -> String {
         return "🦞 OpenClaw".to_string();
     }
     if let Some(client_id) = ClientId::from_str(&client_lower) {
-        return client_ui::display_name(client_id).to_string();
+        return client_id.display_name().to_string();
     }
     client.to_string()
 }
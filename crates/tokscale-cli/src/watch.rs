@@ -0,0 +1,160 @@
+//! `tokscale models --watch`: re-print a report whenever a session file
+//! changes, without launching the TUI.
+//!
+//! Agents append to their transcripts many times per turn, so filesystem
+//! events are debounced and the report re-renders once the sources have been
+//! quiet for [`DEBOUNCE`].
+
+use anyhow::Result;
+use notify::{RecursiveMode, Watcher};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+pub const DEBOUNCE: Duration = Duration::from_secs(1);
+/// How often the loop wakes without events to check for Ctrl-C and a
+/// settled debounce window.
+const TICK: Duration = Duration::from_millis(100);
+
+/// Coalesces a burst of change events into a single render.
+#[derive(Debug)]
+pub struct Debouncer {
+    delay: Duration,
+    last_event: Option<Instant>,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            last_event: None,
+        }
+    }
+
+    pub fn record(&mut self, now: Instant) {
+        self.last_event = Some(now);
+    }
+
+    /// True once `delay` has passed since the latest recorded event; the
+    /// pending state is cleared so the same burst never fires twice.
+    pub fn fire(&mut self, now: Instant) -> bool {
+        match self.last_event {
+            Some(last) if now.saturating_duration_since(last) >= self.delay => {
+                self.last_event = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Directories holding the sources a report for `clients` would read.
+pub fn source_dirs(
+    home_dir: &Option<String>,
+    clients: &Option<Vec<String>>,
+) -> Result<Vec<PathBuf>> {
+    let home = tokscale_core::get_home_dir_string(home_dir).map_err(|e| anyhow::anyhow!(e))?;
+    let use_env_roots = crate::use_env_roots(home_dir);
+    let scan = tokscale_core::scan_all_clients_with_scanner_settings(
+        &home,
+        clients.as_deref().unwrap_or_default(),
+        use_env_roots,
+        &crate::tui::settings::load_scanner_settings_for_home(home_dir),
+    );
+    Ok(scan.watch_dirs(&home, use_env_roots))
+}
+
+/// Render once, then again after every settled burst of changes under
+/// `dirs`, until Ctrl-C. A failed render is printed in place of the report
+/// and the next change tries again.
+pub fn run(dirs: &[PathBuf], mut render: impl FnMut() -> Result<()>) -> Result<()> {
+    let stop = Arc::new(AtomicBool::new(false));
+    #[cfg(unix)]
+    for signal in [signal_hook::consts::SIGINT, signal_hook::consts::SIGTERM] {
+        signal_hook::flag::register(signal, Arc::clone(&stop))?;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        // Parsing opens every source, so access events would re-trigger
+        // the render that caused them.
+        if event.is_ok_and(|event| !event.kind.is_access()) {
+            let _ = tx.send(());
+        }
+    })?;
+    let watched = dirs
+        .iter()
+        .filter(|dir| watcher.watch(dir, RecursiveMode::Recursive).is_ok())
+        .count();
+    if watched == 0 {
+        anyhow::bail!("No session directories found to watch");
+    }
+
+    redraw(&mut render, watched, &rx);
+    let mut debouncer = Debouncer::new(DEBOUNCE);
+    while !stop.load(Ordering::Relaxed) {
+        match rx.recv_timeout(TICK) {
+            Ok(()) => debouncer.record(Instant::now()),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+        if debouncer.fire(Instant::now()) {
+            redraw(&mut render, watched, &rx);
+        }
+    }
+    Ok(())
+}
+
+fn redraw(render: &mut impl FnMut() -> Result<()>, watched: usize, events: &mpsc::Receiver<()>) {
+    use colored::Colorize;
+
+    // Clear the screen and scrollback, then home the cursor.
+    print!("\x1b[2J\x1b[3J\x1b[H");
+    // A source caught mid-write can fail to parse; the next write
+    // re-renders, so one bad pass must not end the watch.
+    if let Err(err) = render() {
+        eprintln!("{}", format!("\n  Error: {err:#}").red());
+    }
+    println!(
+        "{}",
+        format!(
+            "\n  Watching {} director{} · updated {} · Ctrl-C to exit",
+            watched,
+            if watched == 1 { "y" } else { "ies" },
+            chrono::Local::now().format("%H:%M:%S"),
+        )
+        .bright_black()
+    );
+    let _ = std::io::stdout().flush();
+    // Drop events caused by the render itself (cache and cursor-sync
+    // writes) so they don't schedule another one.
+    while events.try_recv().is_ok() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debouncer_fires_once_after_a_quiet_window() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut debouncer = Debouncer::new(Duration::from_millis(1000));
+
+        assert!(!debouncer.fire(at(5_000)), "nothing recorded yet");
+
+        // A burst keeps pushing the deadline out.
+        debouncer.record(at(0));
+        debouncer.record(at(600));
+        debouncer.record(at(1_200));
+        assert!(!debouncer.fire(at(1_900)));
+        assert!(debouncer.fire(at(2_200)));
+        assert!(!debouncer.fire(at(2_300)), "a burst fires only once");
+
+        debouncer.record(at(3_000));
+        assert!(!debouncer.fire(at(3_999)));
+        assert!(debouncer.fire(at(4_000)));
+    }
+}
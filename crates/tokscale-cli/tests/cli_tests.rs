@@ -303,9 +303,15 @@ fn create_positive_utc_offset_submit_fixture_dir() -> (TempDir, String) {
     let base = tmp.path();
     prime_pricing_cache(base);
 
-    let utc_today = chrono::Utc::now().date_naive();
-    let utc_noon = utc_today.and_hms_opt(12, 0, 0).unwrap().and_utc();
-    let local_date = utc_today.succ_opt().unwrap().format("%Y-%m-%d").to_string();
+    // Yesterday's UTC noon is already the next day in UTC+14, and never in
+    // the future there (future dates would be clamped to today).
+    let utc_yesterday = chrono::Utc::now().date_naive().pred_opt().unwrap();
+    let utc_noon = utc_yesterday.and_hms_opt(12, 0, 0).unwrap().and_utc();
+    let local_date = utc_yesterday
+        .succ_opt()
+        .unwrap()
+        .format("%Y-%m-%d")
+        .to_string();
     let session = base.join(".local/share/opencode/storage/message/session1");
     fs::create_dir_all(&session).unwrap();
 
@@ -481,12 +487,19 @@ fn cmd_with_home(tmp: &Path) -> Command {
         .env_remove("TOKSCALE_EXTRA_DIRS")
         .env_remove("TOKSCALE_HEADLESS_DIR")
         .env_remove("CODEX_HOME")
+        .env_remove("OPENCODE_DATA_DIR")
         .env_remove("COPILOT_OTEL_FILE_EXPORTER_PATH")
         .env_remove("GOOSE_PATH_ROOT")
         .env_remove("CODEBUFF_DATA_DIR")
         .env_remove("GEMINI_CLI_HOME")
         .env_remove("HERMES_HOME")
-        .env_remove("TOKSCALE_CONFIG_DIR");
+        .env_remove("TOKSCALE_CONFIG_DIR")
+        .env_remove("TOKSCALE_CACHE_DIR")
+        .env_remove("TOKSCALE_PROFILE")
+        // Likewise a developer's German or French shell locale would regroup
+        // every number the table assertions look for.
+        .env_remove("LC_ALL")
+        .env_remove("LC_NUMERIC");
     cmd
 }
 
@@ -517,12 +530,17 @@ fn offline_cmd_with_home(tmp: &Path) -> Command {
         .env_remove("TOKSCALE_EXTRA_DIRS")
         .env_remove("TOKSCALE_HEADLESS_DIR")
         .env_remove("CODEX_HOME")
+        .env_remove("OPENCODE_DATA_DIR")
         .env_remove("COPILOT_OTEL_FILE_EXPORTER_PATH")
         .env_remove("GOOSE_PATH_ROOT")
         .env_remove("CODEBUFF_DATA_DIR")
         .env_remove("GEMINI_CLI_HOME")
         .env_remove("HERMES_HOME")
-        .env_remove("TOKSCALE_CONFIG_DIR");
+        .env_remove("TOKSCALE_CONFIG_DIR")
+        .env_remove("TOKSCALE_CACHE_DIR")
+        .env_remove("TOKSCALE_PROFILE")
+        .env_remove("LC_ALL")
+        .env_remove("LC_NUMERIC");
     cmd
 }
 
@@ -867,6 +885,23 @@ fn test_clients_command_help() {
         .stdout(predicate::str::contains("Show local scan locations"));
 }
 
+#[test]
+fn test_schema_graph_prints_draft_2020_12_schema() {
+    let mut cmd = cargo_bin_cmd!("tokscale");
+    let output = cmd.args(["schema", "graph"]).output().unwrap();
+    assert!(output.status.success());
+
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    assert_eq!(schema["type"], "object");
+    assert!(schema["properties"]["contributions"].is_object());
+    assert!(schema["$defs"]["TsDailyContribution"].is_object());
+    assert!(schema["$defs"]["TsSourceContribution"].is_object());
+}
+
 #[test]
 fn test_codex_command_help() {
     let mut cmd = cargo_bin_cmd!("tokscale");
@@ -900,6 +935,143 @@ fn test_graph_command_help() {
         .stdout(predicate::str::contains("Export contribution graph data"));
 }
 
+#[test]
+fn test_graph_rejects_unknown_intensity_mode() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    let output = offline_cmd_with_home(tmp.path())
+        .args(["graph", "--client", "opencode", "--no-spinner"])
+        .args(["--intensity", "cubic"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid intensity value"));
+
+    let output = offline_cmd_with_home(tmp.path())
+        .args(["graph", "--client", "opencode", "--no-spinner"])
+        .args(["--intensity", "log"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_graph_intensity_basis_flag() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    let output = offline_cmd_with_home(tmp.path())
+        .args(["graph", "--client", "opencode", "--no-spinner"])
+        .args(["--intensity-basis", "messages"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid intensity basis"));
+
+    let output = offline_cmd_with_home(tmp.path())
+        .args(["graph", "--client", "opencode", "--no-spinner"])
+        .args(["--intensity-basis", "tokens"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_graph_active_threshold_counts_only_busy_days() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    let graph = |threshold: &str| -> serde_json::Value {
+        let output = offline_cmd_with_home(tmp.path())
+            .args(["graph", "--client", "opencode", "--no-spinner"])
+            .args(["--active-threshold", threshold])
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    let all = graph("0");
+    assert_eq!(all["summary"]["activeDays"], 2);
+    let quietest = all["contributions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|day| day["totals"]["tokens"].as_i64().unwrap())
+        .min()
+        .unwrap();
+
+    let at_boundary = graph(&quietest.to_string());
+    assert_eq!(at_boundary["summary"]["activeDays"], 2);
+    let above = graph(&(quietest + 1).to_string());
+    assert_eq!(above["summary"]["activeDays"], 1);
+    assert_eq!(above["summary"]["totalDays"], 2);
+}
+
+#[test]
+fn test_agent_filter_flags_select_untagged_messages() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    let model_entries = |extra: &[&str]| -> usize {
+        let output = offline_cmd_with_home(tmp.path())
+            .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+            .args(extra)
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let entries = json["entries"].as_array().unwrap().len();
+        assert_eq!(
+            output.status.code(),
+            Some(if entries == 0 { 3 } else { 0 }),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        entries
+    };
+
+    // The opencode fixture messages carry no agent tag.
+    let all = model_entries(&[]);
+    assert!(all > 0);
+    assert_eq!(model_entries(&["--agent", "(none)"]), all);
+    assert_eq!(model_entries(&["--agent", "headless"]), 0);
+    assert_eq!(model_entries(&["--exclude-agent", "(none)"]), 0);
+    assert_eq!(model_entries(&["--exclude-agent", "headless"]), all);
+}
+
+#[test]
+fn test_models_json_benchmark_detailed_adds_client_timings() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    let run = |extra: &[&str]| -> serde_json::Value {
+        let output = offline_cmd_with_home(tmp.path())
+            .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    assert!(run(&[]).get("timings").is_none());
+
+    let json = run(&["--benchmark-detailed"]);
+    let timings = json["timings"].as_array().unwrap();
+    assert_eq!(timings.len(), 1);
+    assert_eq!(timings[0]["client"], "opencode");
+    assert!(timings[0]["fileCount"].as_u64().unwrap() > 0);
+    assert!(timings[0]["scanMs"].as_f64().is_some());
+    assert!(timings[0]["parseMs"].as_f64().is_some());
+}
+
 #[test]
 fn test_import_stdout_is_pure_json() {
     // `tokscale import export.json > out.json` must produce a valid JSON
@@ -1027,12 +1199,26 @@ fn test_codex_accounts_empty_json() {
     let mut cmd = cargo_bin_cmd!("tokscale");
     cmd.env("HOME", tmp.path())
         .env_remove("CODEX_HOME")
+        .env_remove("OPENCODE_DATA_DIR")
         .args(["codex", "accounts", "--json"])
         .assert()
         .success()
         .stdout(predicate::str::contains(r#""accounts": []"#));
 }
 
+#[test]
+fn test_usage_errors_exit_with_code_1() {
+    let tmp = create_empty_fixture_dir();
+    cmd_with_home(tmp.path())
+        .args(["models", "--no-such-flag"])
+        .assert()
+        .code(1);
+    cmd_with_home(tmp.path())
+        .args(["models", "--json", "--group-by", "bogus", "--no-spinner"])
+        .assert()
+        .code(1);
+}
+
 #[test]
 fn test_pricing_command_missing_model() {
     let mut cmd = cargo_bin_cmd!("tokscale");
@@ -1066,7 +1252,7 @@ fn test_models_with_invalid_date_format() {
         .arg("--since")
         .arg("invalid-date")
         .assert()
-        .success();
+        .code(3);
 }
 
 #[test]
@@ -1080,7 +1266,7 @@ fn test_models_with_invalid_year() {
         .arg("--year")
         .arg("not-a-year")
         .assert()
-        .success();
+        .code(3);
 }
 
 #[test]
@@ -1136,6 +1322,115 @@ fn test_monthly_with_date_filters() {
         .stdout(predicate::str::contains("2025-01"));
 }
 
+#[test]
+fn test_monthly_budget_counts_this_month_outside_the_date_filter() {
+    let tmp = create_temp_fixture_dir();
+    let session = tmp
+        .path()
+        .join(".local/share/opencode/storage/message/session-today");
+    fs::create_dir_all(&session).unwrap();
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    fs::write(
+        session.join("msg_today.json"),
+        format!(
+            r#"{{
+                "id": "msg_today",
+                "sessionID": "session-today",
+                "role": "assistant",
+                "modelID": "gpt-4o",
+                "providerID": "openai",
+                "cost": 0.5,
+                "tokens": {{
+                    "input": 1000,
+                    "output": 100,
+                    "reasoning": 0,
+                    "cache": {{ "read": 0, "write": 0 }}
+                }},
+                "time": {{ "created": {now_ms}.0 }}
+            }}"#
+        ),
+    )
+    .unwrap();
+
+    let output = cmd_with_home(tmp.path())
+        .args(["monthly", "--json", "--client", "opencode", "--no-spinner"])
+        .args(["--year", "2024", "--budget", "100"])
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    // The rows stay limited to 2024, but the budget covers this month.
+    assert!(json["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|entry| entry["month"].as_str().unwrap().starts_with("2024-")));
+    assert!(json["spentSoFar"].as_f64().unwrap() > 0.0);
+}
+
+#[test]
+fn test_monthly_budget_json_is_converted_with_the_other_costs() {
+    let tmp = create_temp_fixture_dir();
+    let output = cmd_with_home(tmp.path())
+        .env("TOKSCALE_FX_RATE", "0.5")
+        .args(["monthly", "--json", "--client", "opencode", "--no-spinner"])
+        .args(["--currency", "EUR", "--budget", "100"])
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["currency"], "EUR");
+    assert_eq!(json["fx_rate"], 0.5);
+    assert_eq!(json["budget"], 50.0);
+    assert_eq!(json["budget_usd"], 100.0);
+    assert_eq!(json["spentSoFar_usd"], 0.0);
+    assert_eq!(json["projected_usd"], 0.0);
+    assert_eq!(json["overBudget"], false);
+    let total_usd = json["totalCost_usd"].as_f64().unwrap();
+    assert!((json["totalCost"].as_f64().unwrap() - total_usd * 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_monthly_budget_is_printed_with_summary_only() {
+    let tmp = create_temp_fixture_dir();
+    cmd_with_home(tmp.path())
+        .args([
+            "monthly",
+            "--summary-only",
+            "--no-spinner",
+            "--budget",
+            "100",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Budget: "))
+        .stdout(predicate::str::contains("monthly budget"));
+}
+
+#[test]
+fn test_monthly_budget_rejects_non_positive_amounts() {
+    let tmp = create_temp_fixture_dir();
+    for budget in ["0", "-5", "NaN", "inf"] {
+        cmd_with_home(tmp.path())
+            .args(["monthly", "--json", "--no-spinner"])
+            .arg(format!("--budget={budget}"))
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--budget"))
+            .stderr(predicate::str::contains("expected a positive number"));
+    }
+}
+
 #[test]
 fn test_models_home_override_ignores_conflicting_xdg_env() {
     let real_home = create_temp_fixture_dir();
@@ -1386,7 +1681,7 @@ fn test_models_with_no_matching_date() {
         .args(["--since", "2099-01-01", "--until", "2099-12-31"])
         .output()
         .unwrap();
-    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(3));
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     let entries = json["entries"].as_array().unwrap();
     assert!(
@@ -1443,6 +1738,121 @@ fn test_graph_with_year_filter() {
     }
 }
 
+#[test]
+fn test_wrapped_json_emits_stats_without_image() {
+    let tmp = create_temp_fixture_dir();
+    let output = cmd_with_home(tmp.path())
+        .current_dir(tmp.path())
+        .args([
+            "wrapped", "--json", "--year", "2024", "--client", "opencode",
+        ])
+        .args(["--clients", "--no-spinner"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(json["year"], "2024");
+    for key in [
+        "activeDays",
+        "totalTokens",
+        "totalCost",
+        "currentStreak",
+        "longestStreak",
+        "totalMessages",
+    ] {
+        assert!(json[key].is_number(), "{key}: {json}");
+    }
+    assert!(json["totalTokens"].as_i64().unwrap() > 0);
+    assert!(json["busiestDay"]["date"]
+        .as_str()
+        .is_some_and(|date| date.starts_with("2024-")));
+    let top_models = json["topModels"].as_array().unwrap();
+    assert!(!top_models.is_empty());
+    assert!(top_models[0]["name"].is_string());
+    assert_eq!(json["topClients"][0]["name"], "OpenCode");
+    assert!(json["contributions"].is_array());
+    assert!(!tmp.path().join("tokscale-2024-wrapped.png").exists());
+}
+
+#[test]
+fn test_wrapped_home_override_ignores_conflicting_xdg_env() {
+    let real_home = create_temp_fixture_dir();
+    let conflicting_home = create_conflicting_opencode_fixture_dir();
+
+    let output = cmd_with_conflicting_env(conflicting_home.path())
+        .current_dir(real_home.path())
+        .args([
+            "wrapped",
+            "--json",
+            "--year",
+            "2024",
+            "--client",
+            "opencode",
+            "--clients",
+            "--no-spinner",
+            "--home",
+            real_home.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["totalTokens"].as_i64().unwrap() > 0);
+    assert_eq!(json["topClients"][0]["name"], "OpenCode");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("gemini-2.5-pro"));
+}
+
+#[test]
+fn test_json_compact_matches_pretty_output() {
+    let tmp = create_temp_fixture_dir();
+    // Drops the fields that differ between two runs.
+    fn stable(mut value: serde_json::Value) -> serde_json::Value {
+        if let Some(object) = value.as_object_mut() {
+            object.remove("processingTimeMs");
+            object.remove("generatedAt");
+            for nested in object.values_mut() {
+                *nested = stable(nested.take());
+            }
+        }
+        value
+    }
+    let run = |args: &[&str]| {
+        let output = cmd_with_home(tmp.path())
+            .args(args)
+            .args(["--client", "opencode", "--no-spinner"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "command failed: {:?}", output);
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    for (pretty_args, compact_args) in [
+        (&["models", "--json"][..], &["models", "--json-compact"][..]),
+        (
+            &["monthly", "--json"][..],
+            &["monthly", "--json-compact"][..],
+        ),
+        (&["graph"][..], &["graph", "--json-compact"][..]),
+    ] {
+        let pretty = run(pretty_args);
+        let compact = run(compact_args);
+        assert!(pretty.trim_end().contains('\n'), "{pretty_args:?}");
+        assert!(!compact.trim_end().contains('\n'), "{compact_args:?}");
+        assert_eq!(
+            stable(serde_json::from_str(&compact).unwrap()),
+            stable(serde_json::from_str(&pretty).unwrap()),
+            "{compact_args:?}"
+        );
+    }
+}
+
 // ── Client filtering tests ─────────────────────────────────────────────────
 
 #[test]
@@ -1530,7 +1940,7 @@ fn test_models_cursor_explicit_missing_cache_reports_setup_warning_json() {
         .output()
         .unwrap();
 
-    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(3));
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     assert_cursor_setup_warning(&json);
 }
@@ -1555,7 +1965,7 @@ fn test_models_cursor_explicit_local_cursor_state_still_reports_setup_warning_js
         .output()
         .unwrap();
 
-    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(3));
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     assert_cursor_setup_warning(&json);
 }
@@ -1602,7 +2012,7 @@ fn test_models_cursor_explicit_home_override_reports_fixture_cache_path() {
         .output()
         .unwrap();
 
-    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(3));
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     let warnings = json["warnings"]
         .as_array()
@@ -1625,7 +2035,7 @@ fn test_models_cursor_explicit_missing_cache_reports_setup_warning_text() {
     cmd_with_home(tmp.path())
         .args(["models", "--client", "cursor", "--no-spinner"])
         .assert()
-        .success()
+        .code(3)
         .stderr(predicate::str::contains("Cursor usage requires"))
         .stderr(predicate::str::contains("tokscale cursor login"))
         .stderr(predicate::str::contains("tokscale cursor sync --json"))
@@ -1642,7 +2052,7 @@ fn test_models_default_missing_cursor_cache_does_not_emit_setup_warning_json() {
         .output()
         .unwrap();
 
-    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(3));
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     assert!(
         json.get("warnings")
@@ -1662,7 +2072,7 @@ fn test_models_cursor_explicit_existing_cache_suppresses_setup_warning_json() {
         .output()
         .unwrap();
 
-    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(3));
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     assert!(
         json.get("warnings")
@@ -1685,8 +2095,9 @@ fn test_models_cursor_logged_in_missing_cache_suggests_sync_only_json() {
         .output()
         .unwrap();
 
-    assert!(
-        output.status.success(),
+    assert_eq!(
+        output.status.code(),
+        Some(3),
         "stderr: {}",
         String::from_utf8_lossy(&output.stderr)
     );
@@ -1729,7 +2140,7 @@ fn test_graph_cursor_explicit_missing_cache_reports_setup_warning_text() {
     cmd_with_home(tmp.path())
         .args(["graph", "--client", "cursor", "--no-spinner"])
         .assert()
-        .success()
+        .code(3)
         .stderr(predicate::str::contains("Cursor usage requires"))
         .stderr(predicate::str::contains("tokscale cursor login"));
 }
@@ -1748,8 +2159,9 @@ fn test_graph_fresh_cursor_cache_skips_auto_sync_warning() {
         .output()
         .unwrap();
 
-    assert!(
-        output.status.success(),
+    assert_eq!(
+        output.status.code(),
+        Some(3),
         "stderr: {}",
         String::from_utf8_lossy(&output.stderr)
     );
@@ -1767,7 +2179,7 @@ fn test_submit_cursor_explicit_missing_cache_reports_setup_warning_text() {
         .env("TOKSCALE_API_TOKEN", "test-token")
         .args(["submit", "--client", "cursor", "--dry-run"])
         .assert()
-        .success()
+        .code(3)
         .stderr(predicate::str::contains("Cursor usage requires"))
         .stderr(predicate::str::contains("tokscale cursor login"));
 }
@@ -1913,33 +2325,194 @@ fn test_models_json_offline_without_pricing_cache_still_succeeds() {
 }
 
 #[test]
-fn test_monthly_json_offline_without_pricing_cache_still_succeeds() {
+fn test_models_reports_session_counts() {
     let tmp = create_temp_fixture_dir_without_pricing_cache();
     let output = offline_cmd_with_home(tmp.path())
-        .args(["monthly", "--json", "--client", "opencode", "--no-spinner"])
+        .args(["models", "--json", "--client", "opencode", "--no-spinner"])
         .output()
         .unwrap();
-    assert!(
-        output.status.success(),
-        "stderr: {}",
-        String::from_utf8_lossy(&output.stderr)
-    );
+    assert!(output.status.success());
 
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
-    let entries = json["entries"].as_array().unwrap();
-    assert_eq!(entries.len(), 2);
-    assert_eq!(entries[0]["month"].as_str().unwrap(), "2024-06");
-    assert_eq!(entries[1]["month"].as_str().unwrap(), "2025-01");
-    // Without pricing, embedded source costs are preserved
-    let total_cost = json["totalCost"].as_f64().unwrap();
-    assert!(
-        (total_cost - 0.10).abs() < 1e-9,
-        "unexpected totalCost without pricing: {total_cost}"
-    );
-}
+    let total_sessions = json["totalSessions"].as_i64().unwrap();
+    for entry in json["entries"].as_array().unwrap() {
+        let sessions = entry["sessionCount"].as_i64().unwrap();
+        assert!(sessions >= 1 && sessions <= total_sessions, "{entry}");
+    }
 
-#[test]
-fn test_graph_offline_without_pricing_cache_still_succeeds() {
+    let output = offline_cmd_with_home(tmp.path())
+        .args([
+            "models",
+            "--show-sessions",
+            "--client",
+            "opencode",
+            "--no-spinner",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Sessions"));
+}
+
+#[test]
+fn test_models_reports_first_and_last_dates() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    let output = offline_cmd_with_home(tmp.path())
+        .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    for entry in json["entries"].as_array().unwrap() {
+        let first = entry["firstDate"].as_str().unwrap();
+        let last = entry["lastDate"].as_str().unwrap();
+        assert_eq!(first.len(), 10, "{entry}");
+        assert!(first <= last, "{entry}");
+    }
+
+    let output = offline_cmd_with_home(tmp.path())
+        .args([
+            "models",
+            "--show-dates",
+            "--client",
+            "opencode",
+            "--no-spinner",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("First") && stdout.contains("Last"));
+}
+
+#[test]
+fn test_models_json_include_total_row_matches_report_totals() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    let output = offline_cmd_with_home(tmp.path())
+        .args([
+            "models",
+            "--json",
+            "--include-total-row",
+            "--client",
+            "opencode",
+            "--no-spinner",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    let (total, rest) = entries.split_last().unwrap();
+    assert!(!rest.is_empty());
+    assert!(rest.iter().all(|entry| entry.get("isTotal").is_none()));
+
+    assert_eq!(total["model"], "__total__");
+    assert_eq!(total["isTotal"], true);
+    assert_eq!(total["input"], json["totalInput"]);
+    assert_eq!(total["output"], json["totalOutput"]);
+    assert_eq!(total["cacheRead"], json["totalCacheRead"]);
+    assert_eq!(total["cacheWrite"], json["totalCacheWrite"]);
+    assert_eq!(total["messageCount"], json["totalMessages"]);
+    assert_eq!(total["sessionCount"], json["totalSessions"]);
+    assert_eq!(total["cost"], json["totalCost"]);
+}
+
+#[test]
+fn test_models_json_order_by_model_and_direction() {
+    let tmp = create_temp_fixture_dir();
+    let models = |extra: &[&str]| -> Vec<String> {
+        let output = cmd_with_home(tmp.path())
+            .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["model"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let ascending = models(&["--order-by", "model"]);
+    assert!(ascending.len() > 1);
+    let mut sorted = ascending.clone();
+    sorted.sort();
+    assert_eq!(ascending, sorted);
+
+    let mut descending = models(&["--order-by", "model", "--desc"]);
+    descending.reverse();
+    assert_eq!(descending, ascending);
+
+    cmd_with_home(tmp.path())
+        .args(["models", "--json", "--order-by", "date", "--no-spinner"])
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn test_models_json_includes_share_of_total() {
+    let tmp = create_temp_fixture_dir();
+    let output = cmd_with_home(tmp.path())
+        .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    assert!(!entries.is_empty());
+    let tokens_pct: f64 = entries
+        .iter()
+        .map(|entry| entry["tokensPct"].as_f64().unwrap())
+        .sum();
+    assert!(
+        (tokens_pct - 100.0).abs() < 1e-6,
+        "tokensPct sum {tokens_pct}"
+    );
+    let cost_pct: f64 = entries
+        .iter()
+        .map(|entry| entry["costPct"].as_f64().unwrap())
+        .sum();
+    if json["totalCost"].as_f64().unwrap() > 0.0 {
+        assert!((cost_pct - 100.0).abs() < 1e-6, "costPct sum {cost_pct}");
+    } else {
+        assert_eq!(cost_pct, 0.0);
+    }
+}
+
+#[test]
+fn test_monthly_json_offline_without_pricing_cache_still_succeeds() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    let output = offline_cmd_with_home(tmp.path())
+        .args(["monthly", "--json", "--client", "opencode", "--no-spinner"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["month"].as_str().unwrap(), "2024-06");
+    assert_eq!(entries[1]["month"].as_str().unwrap(), "2025-01");
+    // Without pricing, embedded source costs are preserved
+    let total_cost = json["totalCost"].as_f64().unwrap();
+    assert!(
+        (total_cost - 0.10).abs() < 1e-9,
+        "unexpected totalCost without pricing: {total_cost}"
+    );
+}
+
+#[test]
+fn test_graph_offline_without_pricing_cache_still_succeeds() {
     let tmp = create_temp_fixture_dir_without_pricing_cache();
     let output = offline_cmd_with_home(tmp.path())
         .args(["graph", "--client", "opencode", "--no-spinner"])
@@ -2000,6 +2573,28 @@ fn test_hourly_json_offline_without_pricing_cache_still_succeeds() {
     );
 }
 
+#[test]
+fn test_models_offline_without_pricing_exits_network_for_unpriced_usage() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    add_uncosted_opencode_message(tmp.path());
+
+    let output = offline_cmd_with_home(tmp.path())
+        .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Could not load pricing data for gpt-4o")
+    );
+
+    offline_cmd_with_home(tmp.path())
+        .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+        .arg("--no-pricing")
+        .assert()
+        .success();
+}
+
 #[test]
 fn test_models_json_offline_uses_stale_pricing_cache_when_available() {
     let tmp = create_temp_fixture_dir_without_pricing_cache();
@@ -2122,91 +2717,648 @@ fn test_empty_report_total_cost_is_positive_zero() {
             .args([subcmd, "--json", "--client", "crush", "--no-spinner"])
             .output()
             .unwrap();
+        // Only `models` reports an empty result through its exit code.
+        let expected_code = if subcmd == "models" { 3 } else { 0 };
+        assert_eq!(
+            output.status.code(),
+            Some(expected_code),
+            "{subcmd} stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            !stdout.contains("-0.0"),
+            "{subcmd} JSON contains negative zero: {stdout}"
+        );
+
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let total_cost = json["totalCost"].as_f64().unwrap();
+        assert_eq!(total_cost, 0.0, "{subcmd} totalCost should be zero");
+        assert!(
+            total_cost.is_sign_positive(),
+            "{subcmd} totalCost serialized as -0.0"
+        );
+    }
+}
+
+#[test]
+fn test_hide_zero_drops_all_zero_entries_but_keeps_totals() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    add_zero_usage_opencode_message(tmp.path());
+
+    let run = |args: &[&str]| -> serde_json::Value {
+        let output = offline_cmd_with_home(tmp.path())
+            .args(args)
+            .args(["--json", "--client", "opencode", "--no-spinner"])
+            .output()
+            .unwrap();
         assert!(
             output.status.success(),
-            "{subcmd} stderr: {}",
+            "{args:?} stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    // models: the all-zero (opencode, zero-model) row disappears with the flag
+    let full = run(&["models"]);
+    let filtered = run(&["models", "--hide-zero"]);
+    let has_zero_model = |json: &serde_json::Value| {
+        json["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|e| e["model"] == "zero-model")
+    };
+    assert!(has_zero_model(&full), "zero row must show without the flag");
+    assert!(!has_zero_model(&filtered), "--hide-zero must drop the row");
+    assert!(filtered["entries"].as_array().unwrap().iter().all(|e| {
+        e["input"].as_i64().unwrap() != 0
+            || e["output"].as_i64().unwrap() != 0
+            || e["cost"].as_f64().unwrap() != 0.0
+    }));
+    // totals are display-independent: hidden rows still count
+    assert_eq!(full["totalMessages"], filtered["totalMessages"]);
+    assert_eq!(full["totalCost"], filtered["totalCost"]);
+
+    // monthly: the all-zero 2023-03 bucket disappears with the flag
+    let full = run(&["monthly"]);
+    let filtered = run(&["monthly", "--hide-zero"]);
+    let months = |json: &serde_json::Value| -> Vec<String> {
+        json["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["month"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert!(months(&full).contains(&"2023-03".to_string()));
+    assert!(!months(&filtered).contains(&"2023-03".to_string()));
+    assert_eq!(full["totalCost"], filtered["totalCost"]);
+
+    // a month with only reasoning tokens is not all zero
+    let session = tmp
+        .path()
+        .join(".local/share/opencode/storage/message/session4");
+    fs::create_dir_all(&session).unwrap();
+    // 2023-04-15 12:00:00 UTC = 1681560000000 ms
+    fs::write(
+        session.join("msg_r.json"),
+        r#"{
+            "id": "msg_r",
+            "sessionID": "session4",
+            "role": "assistant",
+            "modelID": "reasoning-model",
+            "providerID": "openai",
+            "cost": 0.0,
+            "tokens": {
+                "input": 0,
+                "output": 0,
+                "reasoning": 50,
+                "cache": { "read": 0, "write": 0 }
+            },
+            "time": { "created": 1681560000000.0 }
+        }"#,
+    )
+    .unwrap();
+    assert!(months(&run(&["monthly", "--hide-zero"])).contains(&"2023-04".to_string()));
+
+    // hourly: exactly one all-zero hour bucket disappears with the flag
+    let full = run(&["hourly"]);
+    let filtered = run(&["hourly", "--hide-zero"]);
+    assert_eq!(
+        full["entries"].as_array().unwrap().len(),
+        filtered["entries"].as_array().unwrap().len() + 1
+    );
+    assert_eq!(full["totalCost"], filtered["totalCost"]);
+}
+
+#[test]
+fn test_min_cost_moves_cheap_rows_into_omitted_but_keeps_totals() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    add_zero_usage_opencode_message(tmp.path());
+
+    let run = |args: &[&str]| -> serde_json::Value {
+        let output = offline_cmd_with_home(tmp.path())
+            .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{args:?} stderr: {}",
             String::from_utf8_lossy(&output.stderr)
         );
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    let full = run(&[]);
+    let filtered = run(&["--min-cost", "0.000001"]);
+    assert!(full.get("omitted").is_none());
+    assert!(filtered["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|e| e["model"] != "zero-model"));
+    assert_eq!(filtered["omitted"]["entries"], 1);
+    assert_eq!(full["totalMessages"], filtered["totalMessages"]);
+    assert_eq!(full["totalCost"], filtered["totalCost"]);
+
+    let filtered = run(&["--min-tokens", "1"]);
+    assert_eq!(filtered["omitted"]["entries"], 1);
+    assert_eq!(full["totalInput"], filtered["totalInput"]);
+    // JSON reports the dropped rows as totals, never as a fake model entry.
+    assert_eq!(
+        filtered["entries"].as_array().unwrap().len() + 1,
+        full["entries"].as_array().unwrap().len()
+    );
+    assert!(filtered["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|e| e["client"] != "(below threshold)"));
+}
+
+#[test]
+fn test_below_threshold_row_in_every_models_table_layout() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    add_zero_usage_opencode_message(tmp.path());
+
+    let table = |group_by: &str| -> String {
+        let output = offline_cmd_with_home(tmp.path())
+            .args([
+                "models",
+                "--markdown",
+                "--client",
+                "opencode",
+                "--no-spinner",
+            ])
+            .args(["--group-by", group_by, "--min-tokens", "1"])
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert!(table("model").contains("(below threshold)"));
+    assert!(table("client,model").contains("(below threshold)"));
+
+    let light = offline_cmd_with_home(tmp.path())
+        .args(["models", "--light", "--client", "opencode", "--no-spinner"])
+        .args(["--min-tokens", "1"])
+        .output()
+        .unwrap();
+    assert!(light.status.success());
+    assert!(String::from_utf8(light.stdout)
+        .unwrap()
+        .contains("(below threshold)"));
+}
+
+#[test]
+fn test_negative_thresholds_are_rejected() {
+    let tmp = create_temp_fixture_dir();
+    for (flag, message) in [
+        ("--min-cost=-1", "invalid value '-1' for '--min-cost <USD>'"),
+        (
+            "--min-cost=NaN",
+            "invalid value 'NaN' for '--min-cost <USD>'",
+        ),
+        (
+            "--min-tokens=-1",
+            "invalid value '-1' for '--min-tokens <N>'",
+        ),
+    ] {
+        cmd_with_home(tmp.path())
+            .args(["models", "--json", "--no-spinner", flag])
+            .assert()
+            .code(1)
+            .stderr(predicate::str::contains(message));
+        cmd_with_home(tmp.path())
+            .args(["--json", "--no-spinner", flag])
+            .assert()
+            .code(1)
+            .stderr(predicate::str::contains(message));
+    }
+}
+
+#[test]
+fn test_scan_threads_flag_and_env_are_validated() {
+    let tmp = create_temp_fixture_dir();
+    let messages = |cmd: &mut Command| {
+        let output = cmd
+            .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "command failed: {:?}", output);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["totalMessages"].as_i64().unwrap()
+    };
+
+    assert_eq!(
+        messages(cmd_with_home(tmp.path()).args(["--threads", "1"])),
+        3
+    );
+    assert_eq!(
+        messages(cmd_with_home(tmp.path()).env("TOKSCALE_SCAN_THREADS", "2")),
+        3
+    );
+    assert_eq!(
+        messages(
+            cmd_with_home(tmp.path())
+                .env("TOKSCALE_SCAN_THREADS", "lots")
+                .args(["--threads", "0"])
+        ),
+        3
+    );
+
+    cmd_with_home(tmp.path())
+        .env("TOKSCALE_SCAN_THREADS", "lots")
+        .args(["models", "--json", "--no-spinner"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("TOKSCALE_SCAN_THREADS"));
+}
+
+#[test]
+fn test_top_sessions_ranks_sessions_by_cost() {
+    let tmp = create_temp_fixture_dir();
+    let output = cmd_with_home(tmp.path())
+        .args([
+            "top-sessions",
+            "--json",
+            "--client",
+            "opencode",
+            "--no-spinner",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    let ranking: Vec<&str> = entries
+        .iter()
+        .map(|e| e["sessionId"].as_str().unwrap())
+        .collect();
+    assert_eq!(ranking, vec!["session1", "session2"]);
+    assert!(entries[0]["cost"].as_f64().unwrap() > entries[1]["cost"].as_f64().unwrap());
+    assert_eq!(entries[0]["messageCount"], 2);
+    assert_eq!(entries[0]["firstDate"], "2024-06-15");
+    assert_eq!(entries[1]["dominantModel"], "gpt-4o");
+    assert_eq!(json["totalSessions"], 2);
+
+    let capped = cmd_with_home(tmp.path())
+        .args([
+            "top-sessions",
+            "--json",
+            "--top",
+            "1",
+            "--client",
+            "opencode",
+        ])
+        .arg("--no-spinner")
+        .output()
+        .unwrap();
+    let capped: serde_json::Value = serde_json::from_slice(&capped.stdout).unwrap();
+    assert_eq!(capped["entries"].as_array().unwrap().len(), 1);
+    assert_eq!(capped["entries"][0]["sessionId"], "session1");
+    assert_eq!(capped["totalSessions"], 2);
+
+    cmd_with_home(tmp.path())
+        .args(["top-sessions", "--client", "opencode", "--no-spinner"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Top Sessions"))
+        .stdout(predicate::str::contains("2 of 2 sessions"));
+}
+
+#[test]
+fn test_include_zero_cost_keeps_free_rows_past_top_and_min_cost() {
+    let tmp = create_temp_fixture_dir();
+    let session3 = tmp
+        .path()
+        .join(".local/share/opencode/storage/message/session3");
+    fs::create_dir_all(&session3).unwrap();
+    fs::write(
+        session3.join("msg_d.json"),
+        r#"{
+            "id": "msg_d",
+            "sessionID": "session3",
+            "role": "assistant",
+            "modelID": "copilot-free-model",
+            "providerID": "github-copilot",
+            "cost": 0,
+            "tokens": {
+                "input": 5000,
+                "output": 1000,
+                "reasoning": 0,
+                "cache": { "read": 0, "write": 0 }
+            },
+            "time": { "created": 1736510400000.0, "completed": 1736510401000.0 }
+        }"#,
+    )
+    .unwrap();
+    let run = |args: &[&str]| {
+        let output = cmd_with_home(tmp.path())
+            .args(args)
+            .args(["--json", "--client", "opencode", "--no-spinner"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "command failed: {:?}", output);
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+    };
+    let session_ids = |json: &serde_json::Value| -> Vec<String> {
+        json["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["sessionId"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let capped = run(&["top-sessions", "--top", "1"]);
+    assert_eq!(session_ids(&capped), vec!["session1"]);
+    let with_free = run(&["top-sessions", "--top", "1", "--include-zero-cost"]);
+    assert_eq!(session_ids(&with_free), vec!["session1", "session3"]);
+    assert_eq!(with_free["entries"][1]["cost"], 0.0);
+
+    let models = |json: &serde_json::Value| -> Vec<String> {
+        json["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["model"].as_str().unwrap().to_string())
+            .collect()
+    };
+    let filtered = run(&["models", "--group-by", "model", "--min-cost", "0.01"]);
+    assert!(!models(&filtered).contains(&"copilot-free-model".to_string()));
+    let kept = run(&[
+        "models",
+        "--group-by",
+        "model",
+        "--min-cost",
+        "0.01",
+        "--include-zero-cost",
+    ]);
+    assert!(models(&kept).contains(&"copilot-free-model".to_string()));
+}
+
+#[test]
+fn test_models_html_escapes_cells_and_writes_file() {
+    let tmp = create_temp_fixture_dir();
+    let session3 = tmp
+        .path()
+        .join(".local/share/opencode/storage/message/session3");
+    fs::create_dir_all(&session3).unwrap();
+    fs::write(
+        session3.join("msg_d.json"),
+        r#"{
+            "id": "msg_d",
+            "sessionID": "session3",
+            "role": "assistant",
+            "modelID": "custom<beta>-model",
+            "providerID": "anthropic",
+            "cost": 0.01,
+            "tokens": {
+                "input": 50,
+                "output": 10,
+                "reasoning": 0,
+                "cache": { "read": 0, "write": 0 }
+            },
+            "time": { "created": 1736510400000.0, "completed": 1736510401000.0 }
+        }"#,
+    )
+    .unwrap();
+
+    let output = cmd_with_home(tmp.path())
+        .args(["models", "--html", "--client", "opencode", "--no-spinner"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let html = String::from_utf8(output.stdout).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<td>claude-sonnet-4</td>"));
+    assert!(html.contains("<td>gpt-4o</td>"));
+    assert!(html.contains("custom&lt;beta&gt;-model"));
+    assert!(!html.contains("custom<beta>"));
+
+    let path = tmp.path().join("monthly.html");
+    cmd_with_home(tmp.path())
+        .args(["monthly", "--html", "--output"])
+        .arg(&path)
+        .args(["--client", "opencode", "--no-spinner"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+    let page = fs::read_to_string(&path).unwrap();
+    assert!(page.contains("<td>2024-06</td>"));
+    assert!(page.contains("<td>2025-01</td>"));
+
+    cmd_with_home(tmp.path())
+        .args(["models", "--html", "--json"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_summary_only_omits_entries_and_keeps_totals() {
+    let tmp = create_temp_fixture_dir();
+    let json_of = |args: &[&str]| -> serde_json::Value {
+        let output = cmd_with_home(tmp.path())
+            .args(args)
+            .args(["--json", "--client", "opencode", "--no-spinner"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "command failed: {:?}", output);
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    let full = json_of(&["models"]);
+    let summary = json_of(&["models", "--summary-only"]);
+    assert!(summary.get("entries").is_none());
+    for key in [
+        "totalInput",
+        "totalOutput",
+        "totalCacheRead",
+        "totalCacheWrite",
+        "totalMessages",
+        "totalCost",
+    ] {
+        assert_eq!(summary[key], full[key], "{key}");
+    }
+
+    let monthly = json_of(&["monthly", "--summary-only"]);
+    assert!(monthly.get("entries").is_none());
+    assert_eq!(monthly["totalMessages"], full["totalMessages"]);
+    assert_eq!(monthly["totalInput"], full["totalInput"]);
+    assert_eq!(monthly["totalCost"], full["totalCost"]);
+
+    let output = cmd_with_home(tmp.path())
+        .args([
+            "models",
+            "--light",
+            "--summary-only",
+            "--client",
+            "opencode",
+        ])
+        .arg("--no-spinner")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.contains("Messages:") && text.contains("cache write"));
+    assert!(!text.contains("claude-sonnet-4"));
+}
+
+#[test]
+fn test_models_show_source_names_the_pricing_dataset() {
+    let tmp = create_temp_fixture_dir();
+    let output = cmd_with_home(tmp.path())
+        .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    assert!(!entries.is_empty());
+    for entry in entries {
+        assert!(entry["pricingSource"].is_string(), "{entry}");
+    }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        assert!(
-            !stdout.contains("-0.0"),
-            "{subcmd} JSON contains negative zero: {stdout}"
-        );
+    let output = cmd_with_home(tmp.path())
+        .args(["models", "--light", "--show-source", "--client", "opencode"])
+        .arg("--no-spinner")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let text = String::from_utf8_lossy(&output.stdout);
+    // The fixture's OpenCode messages carry their own cost.
+    assert!(text.contains("Source") && text.contains("client"));
 
-        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
-        let total_cost = json["totalCost"].as_f64().unwrap();
-        assert_eq!(total_cost, 0.0, "{subcmd} totalCost should be zero");
-        assert!(
-            total_cost.is_sign_positive(),
-            "{subcmd} totalCost serialized as -0.0"
-        );
-    }
+    let output = cmd_with_home(tmp.path())
+        .args(["models", "--json", "--no-pricing", "--client", "opencode"])
+        .arg("--no-spinner")
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["entries"][0].get("pricingSource").is_none());
 }
 
 #[test]
-fn test_hide_zero_drops_all_zero_entries_but_keeps_totals() {
-    let tmp = create_temp_fixture_dir_without_pricing_cache();
-    add_zero_usage_opencode_message(tmp.path());
-
-    let run = |args: &[&str]| -> serde_json::Value {
-        let output = offline_cmd_with_home(tmp.path())
-            .args(args)
-            .args(["--json", "--client", "opencode", "--no-spinner"])
+fn test_max_future_days_drops_clock_skewed_messages() {
+    let tmp = create_temp_fixture_dir();
+    let session3 = tmp
+        .path()
+        .join(".local/share/opencode/storage/message/session3");
+    fs::create_dir_all(&session3).unwrap();
+    fs::write(
+        session3.join("msg_d.json"),
+        r#"{
+            "id": "msg_d",
+            "sessionID": "session3",
+            "role": "assistant",
+            "modelID": "skewed-clock-model",
+            "providerID": "anthropic",
+            "cost": 0.04,
+            "tokens": {
+                "input": 500,
+                "output": 100,
+                "reasoning": 0,
+                "cache": { "read": 0, "write": 0 }
+            },
+            "time": { "created": 32472144000000.0, "completed": 32472144001000.0 }
+        }"#,
+    )
+    .unwrap();
+    let models = |extra: &[&str]| -> Vec<String> {
+        let output = cmd_with_home(tmp.path())
+            .args(["models", "--json", "--group-by", "model"])
+            .args(["--client", "opencode", "--no-spinner"])
+            .args(extra)
             .output()
             .unwrap();
-        assert!(
-            output.status.success(),
-            "{args:?} stderr: {}",
-            String::from_utf8_lossy(&output.stderr)
-        );
-        serde_json::from_slice(&output.stdout).unwrap()
-    };
-
-    // models: the all-zero (opencode, zero-model) row disappears with the flag
-    let full = run(&["models"]);
-    let filtered = run(&["models", "--hide-zero"]);
-    let has_zero_model = |json: &serde_json::Value| {
-        json["entries"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .any(|e| e["model"] == "zero-model")
-    };
-    assert!(has_zero_model(&full), "zero row must show without the flag");
-    assert!(!has_zero_model(&filtered), "--hide-zero must drop the row");
-    assert!(filtered["entries"].as_array().unwrap().iter().all(|e| {
-        e["input"].as_i64().unwrap() != 0
-            || e["output"].as_i64().unwrap() != 0
-            || e["cost"].as_f64().unwrap() != 0.0
-    }));
-    // totals are display-independent: hidden rows still count
-    assert_eq!(full["totalMessages"], filtered["totalMessages"]);
-    assert_eq!(full["totalCost"], filtered["totalCost"]);
-
-    // monthly: the all-zero 2023-03 bucket disappears with the flag
-    let full = run(&["monthly"]);
-    let filtered = run(&["monthly", "--hide-zero"]);
-    let months = |json: &serde_json::Value| -> Vec<String> {
+        assert!(output.status.success(), "command failed: {:?}", output);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
         json["entries"]
             .as_array()
             .unwrap()
             .iter()
-            .map(|e| e["month"].as_str().unwrap().to_string())
+            .map(|e| e["model"].as_str().unwrap().to_string())
             .collect()
     };
-    assert!(months(&full).contains(&"2023-03".to_string()));
-    assert!(!months(&filtered).contains(&"2023-03".to_string()));
-    assert_eq!(full["totalCost"], filtered["totalCost"]);
 
-    // hourly: exactly one all-zero hour bucket disappears with the flag
-    let full = run(&["hourly"]);
-    let filtered = run(&["hourly", "--hide-zero"]);
-    assert_eq!(
-        full["entries"].as_array().unwrap().len(),
-        filtered["entries"].as_array().unwrap().len() + 1
+    assert!(models(&[]).contains(&"skewed-clock-model".to_string()));
+    assert!(!models(&["--max-future-days", "30"]).contains(&"skewed-clock-model".to_string()));
+}
+
+#[test]
+fn test_diff_json_reports_both_snapshots_and_deltas() {
+    let tmp = create_temp_fixture_dir_without_pricing_cache();
+    let output = offline_cmd_with_home(tmp.path())
+        .args([
+            "diff",
+            "--json",
+            "--client",
+            "opencode",
+            "--no-spinner",
+            "--since",
+            "2000-01-01",
+            "--until",
+            "2099-12-31",
+            "--previous-since",
+            "1990-01-01",
+            "--previous-until",
+            "1990-12-31",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
     );
-    assert_eq!(full["totalCost"], filtered["totalCost"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(json["previous"]["since"], "1990-01-01");
+    assert!(json["previous"]["entries"].as_array().unwrap().is_empty());
+    let current = json["current"]["entries"].as_array().unwrap();
+    let deltas = json["deltas"].as_array().unwrap();
+    assert!(!deltas.is_empty());
+    assert!(deltas.iter().all(|d| d["previousCost"].is_null()));
+    assert!(deltas
+        .iter()
+        .all(|d| current.iter().any(|e| e["model"] == d["model"])));
+}
+
+#[test]
+fn test_diff_accepts_relative_dates_and_converts_json_costs() {
+    let tmp = create_temp_fixture_dir();
+    let output = cmd_with_home(tmp.path())
+        .env("TOKSCALE_FX_RATE", "0.5")
+        .args(["diff", "--json", "--client", "opencode", "--no-spinner"])
+        .args(["--currency", "EUR", "--since", "30d", "--until", "0d"])
+        .arg("--until-exclusive")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let yesterday = (chrono::Local::now().date_naive() - chrono::Duration::days(1))
+        .format("%Y-%m-%d")
+        .to_string();
+    assert_eq!(json["current"]["until"], yesterday.as_str());
+    assert_eq!(json["currency"], "EUR");
+    assert_eq!(json["fx_rate"], 0.5);
+    assert!(json["totalChange_usd"].is_number());
+    assert!(json["current"]["totalCost_usd"].is_number());
 }
 
 #[test]
@@ -2781,14 +3933,280 @@ fn test_models_group_by_session_emits_session_id_per_entry() {
     );
 
     for entry in entries {
-        assert!(
-            entry.get("workspaceKey").is_none(),
-            "session grouping should not expose workspaceKey"
-        );
-        assert!(entry.get("model").is_some());
-        assert!(entry.get("provider").is_some());
-        assert!(entry.get("cost").is_some());
+        assert!(
+            entry.get("workspaceKey").is_none(),
+            "session grouping should not expose workspaceKey"
+        );
+        assert!(entry.get("model").is_some());
+        assert!(entry.get("provider").is_some());
+        assert!(entry.get("cost").is_some());
+    }
+}
+
+#[test]
+fn test_models_markdown_prints_plain_gfm_table() {
+    let tmp = create_temp_fixture_dir();
+    let output = cmd_with_home(tmp.path())
+        .args([
+            "models",
+            "--markdown",
+            "--client",
+            "opencode",
+            "--no-spinner",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        !stdout.contains('\x1b'),
+        "markdown must not contain ANSI codes"
+    );
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[0].starts_with("| Client | Provider | Model |"));
+    assert!(lines[1].starts_with("| --- | --- | --- |"));
+    assert!(lines[1].contains("| ---: |"));
+    assert!(lines
+        .iter()
+        .all(|line| line.starts_with('|') && line.ends_with('|')));
+    assert!(lines.last().unwrap().starts_with("| Total |"));
+}
+
+#[test]
+fn test_models_no_pricing_renders_costs_as_unpriced() {
+    let tmp = create_temp_fixture_dir();
+    let output = cmd_with_home(tmp.path())
+        .args([
+            "models",
+            "--markdown",
+            "--no-pricing",
+            "--client",
+            "opencode",
+            "--no-spinner",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    let header: Vec<&str> = stdout.lines().next().unwrap().split(" | ").collect();
+    let cost_column = header.iter().position(|cell| *cell == "Cost").unwrap();
+    for row in stdout.lines().skip(2) {
+        let cells: Vec<&str> = row.split(" | ").collect();
+        assert_eq!(cells[cost_column], "—", "{row}");
+    }
+    assert!(!stdout.contains('$'), "{stdout}");
+}
+
+#[test]
+fn test_models_warn_duplicates_reports_overlapping_messages() {
+    let tmp = create_temp_fixture_dir();
+    // A copy of msg_a under a second file name: OpenCode dedups on message id.
+    let session1 = tmp
+        .path()
+        .join(".local/share/opencode/storage/message/session1");
+    fs::copy(
+        session1.join("msg_a.json"),
+        session1.join("msg_a_copy.json"),
+    )
+    .unwrap();
+
+    let output = cmd_with_home(tmp.path())
+        .args([
+            "models",
+            "--json",
+            "--warn-duplicates",
+            "--client",
+            "opencode",
+        ])
+        .arg("--no-spinner")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("OpenCode: 1 duplicate removed"),
+        "stderr: {stderr}"
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let opencode = json["timings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["client"] == "opencode")
+        .unwrap();
+    assert_eq!(opencode["duplicatesRemoved"], 1);
+}
+
+/// Writes one Claude Code session under `.claude/projects/<project>/`.
+fn write_claude_project_session(home: &Path, project: &str, id: &str, input: u64) {
+    let dir = home.join(".claude/projects").join(project);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join(format!("{id}.jsonl")),
+        format!(
+            r#"{{"type":"assistant","timestamp":"2025-03-01T10:00:00.000Z","requestId":"req_{id}","message":{{"id":"msg_{id}","model":"claude-sonnet-4-20250514","usage":{{"input_tokens":{input},"output_tokens":10}}}}}}"#
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_models_group_by_project_attributes_claude_sessions() {
+    let tmp = TempDir::new().unwrap();
+    prime_pricing_cache(tmp.path());
+    write_claude_project_session(tmp.path(), "repo-a", "a1", 100);
+    write_claude_project_session(tmp.path(), "repo-a", "a2", 200);
+    write_claude_project_session(tmp.path(), "repo-b", "b1", 400);
+
+    let run = |extra: &[&str]| {
+        let output = cmd_with_home(tmp.path())
+            .args(["models", "--json", "--client", "claude", "--no-spinner"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "command failed: {:?}", output);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let mut rows: Vec<(String, u64)> = json["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| {
+                (
+                    e["workspaceLabel"].as_str().unwrap().to_string(),
+                    e["input"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        rows.sort();
+        rows
+    };
+
+    assert_eq!(
+        run(&["--group-by", "project"]),
+        vec![("repo-a".to_string(), 300), ("repo-b".to_string(), 400)]
+    );
+    assert_eq!(
+        run(&["--group-by", "project", "--project", "REPO-B"]),
+        vec![("repo-b".to_string(), 400)]
+    );
+}
+
+#[test]
+fn test_models_locale_regroups_table_numbers() {
+    let tmp = create_temp_fixture_dir();
+    let run = |configure: &dyn Fn(&mut Command)| {
+        let mut cmd = cmd_with_home(tmp.path());
+        cmd.args([
+            "models",
+            "--markdown",
+            "--client",
+            "opencode",
+            "--no-spinner",
+        ]);
+        configure(&mut cmd);
+        let output = cmd.output().unwrap();
+        assert!(output.status.success(), "command failed: {:?}", output);
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // Fixture inputs sum to 2,400 tokens and $0.10.
+    let en = run(&|_| {});
+    assert!(en.contains("| 2,400 |") && en.contains("$0.10"), "{en}");
+
+    let de = run(&|cmd| {
+        cmd.args(["--locale", "de"]);
+    });
+    assert!(de.contains("| 2.400 |") && de.contains("$0,10"), "{de}");
+
+    let fr = run(&|cmd| {
+        cmd.env("LC_NUMERIC", "fr_FR.UTF-8");
+    });
+    assert!(
+        fr.contains("| 2\u{202f}400 |") && fr.contains("$0,10"),
+        "{fr}"
+    );
+
+    let json_output = cmd_with_home(tmp.path())
+        .args(["models", "--json", "--locale", "de", "--client", "opencode"])
+        .arg("--no-spinner")
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json_output.stdout).unwrap();
+    assert_eq!(json["totalInput"], 2400);
+
+    cmd_with_home(tmp.path())
+        .args(["models", "--locale", "ja", "--light"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unsupported locale 'ja'"));
+}
+
+#[test]
+fn test_models_group_by_session_emits_one_row_per_session() {
+    let tmp = create_temp_fixture_dir();
+    let output = cmd_with_home(tmp.path())
+        .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+        .args(["--group-by", "session"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "command failed: {:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["groupBy"].as_str().unwrap(), "session");
+
+    let mut session_ids: Vec<&str> = json["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            assert!(e["firstDate"].as_str().is_some_and(|d| !d.is_empty()));
+            e["sessionId"].as_str().unwrap()
+        })
+        .collect();
+    session_ids.sort();
+    assert_eq!(session_ids, vec!["session1", "session2"]);
+
+    cmd_with_home(tmp.path())
+        .args(["models", "--light", "--client", "opencode", "--no-spinner"])
+        .args(["--group-by", "session"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Started"))
+        .stdout(predicate::str::contains("session1"));
+}
+
+#[test]
+fn test_models_anonymize_strips_session_ids_and_keeps_totals() {
+    let tmp = create_temp_fixture_dir();
+    let run = |extra: &[&str]| {
+        let output = cmd_with_home(tmp.path())
+            .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+            .args(["--group-by", "session"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "command failed: {:?}", output);
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+    };
+    let plain = run(&[]);
+    let anonymized = run(&["--anonymize"]);
+
+    assert!(!anonymized.to_string().contains("session1"));
+    let entries = anonymized["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    for entry in entries {
+        assert!(entry.get("sessionId").is_none());
+    }
+    for key in ["totalInput", "totalOutput", "totalCost", "totalMessages"] {
+        assert_eq!(anonymized[key], plain[key], "{key}");
     }
+
+    cmd_with_home(tmp.path())
+        .args(["models", "--anonymize", "--light"])
+        .assert()
+        .failure();
 }
 
 #[test]
@@ -2931,6 +4349,53 @@ fn test_pricing_command_success() {
         .stdout(predicate::str::contains("Output"));
 }
 
+#[test]
+fn test_pricing_search_lists_claude_opus_variants() {
+    let tmp = TempDir::new().unwrap();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before unix epoch")
+        .as_secs();
+    let litellm = format!(
+        r#"{{"timestamp":{},"data":{{
+            "claude-opus-4-5":{{"input_cost_per_token":0.000005,"output_cost_per_token":0.000025}},
+            "claude-opus-4-1":{{"input_cost_per_token":0.000015,"output_cost_per_token":0.000075}},
+            "gpt-4o":{{"input_cost_per_token":0.0000025,"output_cost_per_token":0.00001}}
+        }}}}"#,
+        now
+    );
+    let empty = format!(r#"{{"timestamp":{},"data":{{}}}}"#, now);
+    for dir in [
+        tmp.path().join("Library/Caches/tokscale"),
+        tmp.path().join(".cache/tokscale"),
+        tmp.path().join(".config/tokscale/cache"),
+    ] {
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("pricing-litellm.json"), &litellm).unwrap();
+        fs::write(dir.join("pricing-openrouter.json"), &empty).unwrap();
+    }
+
+    let output = offline_cmd_with_home(tmp.path())
+        .args(["pricing", "--search", "opus", "--json", "--no-spinner"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let keys: Vec<&str> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["key"].as_str().unwrap())
+        .collect();
+    assert!(keys.contains(&"claude-opus-4-5"), "{keys:?}");
+    assert!(keys.contains(&"claude-opus-4-1"), "{keys:?}");
+    assert!(!keys.contains(&"gpt-4o"), "{keys:?}");
+}
+
 #[test]
 fn test_pricing_command_json() {
     let output = cargo_bin_cmd!("tokscale")
@@ -3021,6 +4486,89 @@ fn test_clients_command() {
         .stdout(predicate::str::contains("Claude").or(predicate::str::contains("claude")));
 }
 
+#[test]
+fn test_clients_paths_only_lists_existing_paths() {
+    let tmp = create_temp_fixture_dir();
+    let output = cmd_with_home(tmp.path())
+        .args(["clients", "--paths-only"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let paths: Vec<&str> = stdout.lines().collect();
+    assert!(!paths.is_empty(), "fixture home should have session paths");
+    for path in &paths {
+        assert!(Path::new(path).exists(), "listed path must exist: {path}");
+    }
+    let unique: std::collections::HashSet<&&str> = paths.iter().collect();
+    assert_eq!(unique.len(), paths.len(), "paths must not repeat");
+
+    cmd_with_home(tmp.path())
+        .args(["clients", "--paths-only", "--json"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_validate_reports_failed_files_and_strict_exit() {
+    let tmp = create_temp_fixture_dir();
+    let output = cmd_with_home(tmp.path())
+        .args(["validate", "--json", "--client", "opencode"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["failed"], 0);
+    assert!(json["files"].as_u64().unwrap() > 0);
+
+    let opencode_dir = tmp
+        .path()
+        .join(".local/share/opencode/storage/message/corrupt");
+    fs::create_dir_all(&opencode_dir).unwrap();
+    fs::write(
+        opencode_dir.join("msg_corrupt.json"),
+        "{\"id\": \"msg_corrupt\",",
+    )
+    .unwrap();
+
+    let output = cmd_with_home(tmp.path())
+        .args(["validate", "--json", "--client", "opencode"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "failures only exit nonzero under --strict"
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["failed"], 1);
+    let failed: Vec<&serde_json::Value> = json["sources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|source| source["status"] == "failed")
+        .collect();
+    assert_eq!(failed.len(), 1);
+    assert!(failed[0]["path"]
+        .as_str()
+        .unwrap()
+        .ends_with("msg_corrupt.json"));
+    assert_eq!(failed[0]["messages"], 0);
+    assert!(failed[0]["error"].is_string());
+
+    cmd_with_home(tmp.path())
+        .args([
+            "validate",
+            "--strict",
+            "--no-spinner",
+            "--client",
+            "opencode",
+        ])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("msg_corrupt.json"));
+}
+
 #[test]
 fn test_clients_json() {
     let tmp = create_empty_fixture_dir();
@@ -3156,7 +4704,7 @@ fn test_models_json_includes_claude_desktop_diagnostic_for_empty_explicit_claude
         .args(["models", "--client", "claude", "--json", "--no-spinner"])
         .output()
         .unwrap();
-    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(3));
 
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     let diagnostics = json["diagnostics"].as_array().unwrap();
@@ -3298,6 +4846,154 @@ fn test_monthly_light_output() {
         .stdout(predicate::str::contains("Monthly Token Usage Report"));
 }
 
+#[test]
+fn test_client_model_table_shows_reasoning_and_counts_it_in_total() {
+    let tmp = TempDir::new().unwrap();
+    let session = tmp
+        .path()
+        .join(".local/share/opencode/storage/message/session-reasoning");
+    fs::create_dir_all(&session).unwrap();
+    fs::write(
+        session.join("msg_reasoning.json"),
+        r#"{
+            "id": "msg_reasoning",
+            "sessionID": "session-reasoning",
+            "role": "assistant",
+            "modelID": "claude-sonnet-4-20250514",
+            "providerID": "anthropic",
+            "cost": 0.05,
+            "tokens": {
+                "input": 1000,
+                "output": 200,
+                "reasoning": 60,
+                "cache": { "read": 300, "write": 40 }
+            },
+            "time": { "created": 1718452800000.0 }
+        }"#,
+    )
+    .unwrap();
+
+    let output = offline_cmd_with_home(tmp.path())
+        .args([
+            "models",
+            "--markdown",
+            "--client",
+            "opencode",
+            "--no-spinner",
+        ])
+        .args(["--group-by", "client,model"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let header = stdout.lines().next().unwrap();
+    assert!(
+        header.starts_with(
+            "| Client | Provider | Model | Resolved | Input | Output | Cache Write | Cache Read | Reasoning | Total | ms/1K | Cost | Cost/1M |"
+        ),
+        "{header}"
+    );
+    let total_row = stdout
+        .lines()
+        .find(|line| line.starts_with("| Total |"))
+        .unwrap();
+    // 1,000 + 200 + 40 + 300 + 60 reasoning.
+    assert!(
+        total_row.contains("| 1,000 | 200 | 40 | 300 | 60 | 1,600 |"),
+        "{total_row}"
+    );
+
+    let output = offline_cmd_with_home(tmp.path())
+        .args([
+            "models",
+            "--by-hour",
+            "--client",
+            "opencode",
+            "--no-spinner",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Reasoning"), "{stdout}");
+    let busy_hour = stdout
+        .lines()
+        .find(|line| line.contains(" 1,000 "))
+        .unwrap_or_else(|| panic!("{stdout}"));
+    assert!(busy_hour.contains(" 60 "), "{busy_hour}");
+    assert!(busy_hour.contains(" 1,600 "), "{busy_hour}");
+}
+
+#[test]
+fn test_models_json_counts_reasoning_in_totals_and_shares() {
+    let tmp = TempDir::new().unwrap();
+    let session = tmp
+        .path()
+        .join(".local/share/opencode/storage/message/session-shares");
+    fs::create_dir_all(&session).unwrap();
+    for (id, model, reasoning) in [
+        ("msg_thinking", "gpt-4o", 300),
+        ("msg_plain", "gpt-4o-mini", 0),
+    ] {
+        fs::write(
+            session.join(format!("{id}.json")),
+            format!(
+                r#"{{
+                    "id": "{id}",
+                    "sessionID": "session-shares",
+                    "role": "assistant",
+                    "modelID": "{model}",
+                    "providerID": "openai",
+                    "cost": 0.01,
+                    "tokens": {{
+                        "input": 100,
+                        "output": 0,
+                        "reasoning": {reasoning},
+                        "cache": {{ "read": 0, "write": 0 }}
+                    }},
+                    "time": {{ "created": 1718452800000.0 }}
+                }}"#
+            ),
+        )
+        .unwrap();
+    }
+
+    let output = offline_cmd_with_home(tmp.path())
+        .args(["models", "--json", "--client", "opencode", "--no-spinner"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["totalReasoning"], 300);
+    let share = |model: &str| {
+        json["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry["model"] == model)
+            .unwrap()["tokensPct"]
+            .as_f64()
+            .unwrap()
+    };
+    // 400 of the report's 500 tokens, reasoning included.
+    assert!((share("gpt-4o") - 80.0).abs() < 1e-9);
+    assert!((share("gpt-4o-mini") - 20.0).abs() < 1e-9);
+
+    offline_cmd_with_home(tmp.path())
+        .args(["models", "--env", "--client", "opencode", "--no-spinner"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TOKSCALE_TOTAL_TOKENS=500\n"));
+}
+
 #[test]
 fn test_models_light_with_client_filter() {
     let tmp = create_temp_fixture_dir();
@@ -3354,7 +5050,7 @@ fn test_models_empty_fixture() {
         .args(["models", "--json", "--client", "opencode", "--no-spinner"])
         .output()
         .unwrap();
-    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(3));
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     let entries = json["entries"].as_array().unwrap();
     assert!(
@@ -3372,7 +5068,7 @@ fn test_graph_empty_contributions() {
         .args(["graph", "--client", "opencode", "--no-spinner"])
         .output()
         .unwrap();
-    assert!(output.status.success());
+    assert_eq!(output.status.code(), Some(3));
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     let contributions = json["contributions"].as_array().unwrap();
     assert!(
@@ -3443,6 +5139,59 @@ fn test_graph_output_to_file() {
     assert!(json.get("contributions").is_some());
 }
 
+#[test]
+fn test_graph_gz_output_path_is_compressed() {
+    use std::io::Read;
+
+    let tmp = create_temp_fixture_dir();
+    let output_file = tmp.path().join("graph-output.json.gz");
+    cmd_with_home(tmp.path())
+        .args(["graph", "--client", "opencode", "--no-spinner"])
+        .args(["--output", output_file.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let compressed = fs::read(&output_file).unwrap();
+    let mut content = String::new();
+    flate2::read::GzDecoder::new(compressed.as_slice())
+        .read_to_string(&mut content)
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert!(json.get("meta").is_some());
+    assert!(json.get("contributions").is_some());
+}
+
+// ── SQLite export tests ────────────────────────────────────────────────────
+
+fn sqlite_row_count(path: &Path) -> i64 {
+    let conn = rusqlite::Connection::open(path).unwrap();
+    conn.query_row("SELECT COUNT(*) FROM messages", [], |row| row.get(0))
+        .unwrap()
+}
+
+#[test]
+fn test_export_sqlite_writes_messages_table() {
+    let tmp = create_temp_fixture_dir();
+    let db = tmp.path().join("usage.db");
+    cmd_with_home(tmp.path())
+        .args(["export", "sqlite", "--client", "opencode", "--no-spinner"])
+        .args(["--output", db.to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("messages written to"));
+    assert!(sqlite_row_count(&db) > 0);
+
+    // Date filters apply to the export; a window with no usage yields an
+    // empty table rather than an error.
+    cmd_with_home(tmp.path())
+        .args(["export", "sqlite", "--client", "opencode", "--no-spinner"])
+        .args(["--since", "2099-01-01"])
+        .args(["--output", db.to_str().unwrap()])
+        .assert()
+        .success();
+    assert_eq!(sqlite_row_count(&db), 0);
+}
+
 // ── Root command tests (no subcommand) ─────────────────────────────────────
 
 #[test]
@@ -3493,6 +5242,45 @@ fn light_with_write_cache_writes_to_canonical_path() {
     );
 }
 
+#[test]
+fn test_malformed_config_toml_is_reported_without_panicking() {
+    let tmp = create_temp_fixture_dir();
+    let config_dir = tmp.path().join("config-root");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(config_dir.join("config.toml"), "group_by = [\n").unwrap();
+
+    cmd_with_home(tmp.path())
+        .env("TOKSCALE_CONFIG_DIR", &config_dir)
+        .current_dir(tmp.path())
+        .args(["--json", "--no-spinner"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid config in"))
+        .stderr(predicate::str::contains("config.toml"))
+        .stderr(predicate::str::contains("panicked").not());
+}
+
+#[test]
+fn test_tokscalerc_client_default_applies_and_flag_overrides() {
+    let tmp = create_temp_fixture_dir();
+    fs::write(tmp.path().join(".tokscalerc"), "clients = [\"claude\"]\n").unwrap();
+
+    let from_file = cmd_with_home(tmp.path())
+        .current_dir(tmp.path())
+        .args(["--json", "--no-spinner"])
+        .output()
+        .unwrap();
+    assert_eq!(from_file.status.code(), Some(3));
+    assert!(!String::from_utf8_lossy(&from_file.stdout).contains("gpt-4o"));
+
+    cmd_with_home(tmp.path())
+        .current_dir(tmp.path())
+        .args(["--json", "--client", "opencode", "--no-spinner"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("gpt-4o"));
+}
+
 #[test]
 fn test_root_with_date_filter() {
     let tmp = create_temp_fixture_dir();
@@ -3527,9 +5315,10 @@ fn test_submit_offline_without_pricing_cache_fails() {
         .output()
         .unwrap();
     let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(
-        !output.status.success(),
-        "submit should fail when pricing is unavailable; stdout: {}",
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "submit should fail with the network exit code when pricing is unavailable; stdout: {}",
         String::from_utf8_lossy(&output.stdout)
     );
     // Verify failure is from pricing fetch, not from auth or argument errors
@@ -3621,7 +5410,7 @@ fn test_models_with_client_filter_gjc() {
 
 #[test]
 fn test_client_filter_gjc_empty_is_clean() {
-    // No gjc fixture data on disk — command must still exit successfully
+    // No gjc fixture data on disk — command must exit with the "empty" code
     // and return an empty (zero-entry) result without panicking.
     let tmp = TempDir::new().expect("failed to create temp dir");
     prime_pricing_cache(tmp.path());
@@ -3631,8 +5420,9 @@ fn test_client_filter_gjc_empty_is_clean() {
         .output()
         .unwrap();
 
-    assert!(
-        output.status.success(),
+    assert_eq!(
+        output.status.code(),
+        Some(3),
         "command failed with no gjc data; stderr: {}",
         String::from_utf8_lossy(&output.stderr)
     );
@@ -3660,8 +5450,9 @@ fn test_client_filter_gjc_isolation() {
         .output()
         .unwrap();
 
-    assert!(
-        output.status.success(),
+    assert_eq!(
+        output.status.code(),
+        Some(3),
         "command failed; stderr: {}",
         String::from_utf8_lossy(&output.stderr)
     );
@@ -3711,3 +5502,39 @@ fn report_no_summarize_json_empty_home_emits_valid_json_without_panic() {
         "expected zero entries for empty home, got: {entries:?}"
     );
 }
+
+#[test]
+fn test_no_dedup_counts_overlapping_claude_messages() {
+    let tmp = TempDir::new().unwrap();
+    prime_pricing_cache(tmp.path());
+    // The same request copied into a second project, as happens when a
+    // session is resumed from another directory.
+    write_claude_project_session(tmp.path(), "repo-a", "a1", 100);
+    write_claude_project_session(tmp.path(), "repo-b", "a1", 100);
+    write_claude_project_session(tmp.path(), "repo-b", "b1", 400);
+
+    let run = |extra: &[&str]| {
+        let output = cmd_with_home(tmp.path())
+            .args(["models", "--json", "--client", "claude", "--no-spinner"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "command failed: {:?}", output);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let input: u64 = json["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["input"].as_u64().unwrap())
+            .sum();
+        (input, String::from_utf8(output.stderr).unwrap())
+    };
+
+    let (deduped, stderr) = run(&[]);
+    assert_eq!(deduped, 500);
+    assert!(!stderr.contains("--no-dedup"), "{stderr}");
+
+    let (raw, stderr) = run(&["--no-dedup"]);
+    assert_eq!(raw, 600);
+    assert!(stderr.contains("dedup is disabled"), "{stderr}");
+}
@@ -5,8 +5,9 @@
 use crate::sessions::UnifiedMessage;
 use crate::{
     ClientContribution, DailyContribution, DailyTotals, DataSummary, GraphMeta, GraphResult,
-    SessionContribution, TokenBreakdown, YearSummary,
+    IntensityBasis, IntensityMode, SessionContribution, TokenBreakdown, YearSummary,
 };
+use chrono::{Local, NaiveDate};
 use rayon::prelude::*;
 use std::collections::HashMap;
 
@@ -100,8 +101,87 @@ pub fn aggregate_by_session(messages: Vec<UnifiedMessage>) -> Vec<SessionContrib
     contributions
 }
 
+/// Whether a day counts toward `active_days` (and streaks). With a zero
+/// threshold any usage counts; otherwise the day needs at least
+/// `active_threshold` tokens.
+pub fn is_active_day(totals: &DailyTotals, active_threshold: i64) -> bool {
+    if active_threshold > 0 {
+        return totals.tokens >= active_threshold;
+    }
+    totals.tokens > 0 || totals.cost > 0.0 || totals.messages > 0
+}
+
+/// A run of consecutive days with usage.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct Streak {
+    pub days: u32,
+    /// First and last day of the run; `None` when `days` is 0.
+    pub start: Option<NaiveDate>,
+    pub end: Option<NaiveDate>,
+}
+
+impl Streak {
+    fn from_run(start: NaiveDate, end: NaiveDate) -> Self {
+        Streak {
+            days: (end - start).num_days() as u32 + 1,
+            start: Some(start),
+            end: Some(end),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Streaks {
+    pub current: Streak,
+    pub longest: Streak,
+}
+
+/// Streaks over `dates` (any order, duplicates allowed). The current streak
+/// ends on `today`, or on yesterday when today has no usage yet, so it does
+/// not reset before the day is over. When two runs tie for longest, the
+/// later one wins.
+pub fn calculate_streaks(dates: &[NaiveDate], today: NaiveDate) -> Streaks {
+    let mut sorted = dates.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut runs: Vec<(NaiveDate, NaiveDate)> = Vec::new();
+    for date in sorted {
+        match runs.last_mut() {
+            Some((_, end)) if end.succ_opt() == Some(date) => *end = date,
+            _ => runs.push((date, date)),
+        }
+    }
+
+    let current = [Some(today), today.pred_opt()]
+        .into_iter()
+        .flatten()
+        .find_map(|anchor| {
+            runs.iter()
+                .find(|(start, end)| *start <= anchor && anchor <= *end)
+                .map(|&(start, _)| Streak::from_run(start, anchor))
+        })
+        .unwrap_or_default();
+    let longest = runs
+        .iter()
+        .map(|&(start, end)| Streak::from_run(start, end))
+        .reduce(|best, run| if run.days >= best.days { run } else { best })
+        .unwrap_or_default();
+
+    Streaks { current, longest }
+}
+
 /// Calculate summary statistics
 pub fn calculate_summary(contributions: &[DailyContribution]) -> DataSummary {
+    calculate_summary_with(contributions, 0)
+}
+
+/// [`calculate_summary`] counting only days with at least `active_threshold`
+/// tokens as active.
+pub fn calculate_summary_with(
+    contributions: &[DailyContribution],
+    active_threshold: i64,
+) -> DataSummary {
     // Daily totals already saturate at i64::MAX (clamped extreme inputs), so
     // summing several such days must saturate too rather than overflow.
     let total_tokens: i64 = contributions
@@ -111,13 +191,20 @@ pub fn calculate_summary(contributions: &[DailyContribution]) -> DataSummary {
     let total_cost: f64 = contributions.iter().map(|c| c.totals.cost).sum();
     let active_days = contributions
         .iter()
-        .filter(|c| c.totals.tokens > 0 || c.totals.cost > 0.0 || c.totals.messages > 0)
+        .filter(|c| is_active_day(&c.totals, active_threshold))
         .count() as i32;
     let max_cost = contributions
         .iter()
         .map(|c| c.totals.cost)
         .fold(0.0, f64::max);
 
+    let active_dates: Vec<NaiveDate> = contributions
+        .iter()
+        .filter(|c| is_active_day(&c.totals, active_threshold))
+        .filter_map(|c| NaiveDate::parse_from_str(&c.date, "%Y-%m-%d").ok())
+        .collect();
+    let streaks = calculate_streaks(&active_dates, Local::now().date_naive());
+
     let mut clients_set = std::collections::HashSet::with_capacity(5);
     let mut models_set = std::collections::HashSet::with_capacity(20);
 
@@ -149,6 +236,8 @@ pub fn calculate_summary(contributions: &[DailyContribution]) -> DataSummary {
             v.sort();
             v
         },
+        current_streak: streaks.current,
+        longest_streak: streaks.longest,
     }
 }
 
@@ -196,7 +285,17 @@ pub fn generate_graph_result(
     contributions: Vec<DailyContribution>,
     processing_time_ms: u32,
 ) -> GraphResult {
-    let summary = calculate_summary(&contributions);
+    generate_graph_result_with(contributions, processing_time_ms, 0)
+}
+
+/// [`generate_graph_result`] with an `active_days` token threshold (see
+/// [`is_active_day`]).
+pub fn generate_graph_result_with(
+    contributions: Vec<DailyContribution>,
+    processing_time_ms: u32,
+    active_threshold: i64,
+) -> GraphResult {
+    let summary = calculate_summary_with(&contributions, active_threshold);
     let years = calculate_years(&contributions);
 
     let date_range_start = contributions
@@ -287,25 +386,22 @@ impl DayAccumulator {
             msg.client,
             crate::canonical_model_id(&msg.model_id)
         );
+        let provider = crate::normalize_provider(&msg.provider_id);
         let client_entry = self
             .clients
             .entry(key)
             .or_insert_with(|| ClientContribution {
                 client: msg.client.clone(),
                 model_id: crate::canonical_model_id(&msg.model_id),
-                provider_id: msg.provider_id.clone(),
+                provider_id: provider.clone(),
                 tokens: TokenBreakdown::default(),
                 cost: 0.0,
                 messages: 0,
             });
 
         // Merge provider_id if different provider contributes to same client+model
-        if !client_entry
-            .provider_id
-            .split(", ")
-            .any(|p| p == msg.provider_id)
-        {
-            client_entry.provider_id = format!("{}, {}", client_entry.provider_id, msg.provider_id);
+        if !client_entry.provider_id.split(", ").any(|p| p == provider) {
+            client_entry.provider_id = format!("{}, {}", client_entry.provider_id, provider);
         }
 
         client_entry.tokens.input = client_entry.tokens.input.saturating_add(msg.tokens.input);
@@ -420,6 +516,7 @@ impl DayAccumulator {
             cache_read: self.token_breakdown.cache_read.max(0),
             cache_write: self.token_breakdown.cache_write.max(0),
             reasoning: self.token_breakdown.reasoning.max(0),
+            ..Default::default()
         };
 
         let clients: Vec<ClientContribution> = self
@@ -520,14 +617,15 @@ impl SessionAccumulator {
         // Canonical (alias-free) id — this feeds the submitted/exported payload,
         // so machine-local aliases must not rewrite it (see `add_message`).
         let normalized_model = crate::canonical_model_id(&msg.model_id);
-        let key = format!("{}:{}:{}", msg.client, msg.provider_id, normalized_model);
+        let provider = crate::normalize_provider(&msg.provider_id);
+        let key = format!("{}:{}:{}", msg.client, provider, normalized_model);
         let client_entry = self
             .clients
             .entry(key)
             .or_insert_with(|| ClientContribution {
                 client: msg.client.clone(),
                 model_id: normalized_model.clone(),
-                provider_id: msg.provider_id.clone(),
+                provider_id: provider.clone(),
                 tokens: TokenBreakdown::default(),
                 cost: 0.0,
                 messages: 0,
@@ -651,6 +749,7 @@ impl SessionAccumulator {
             cache_read: self.token_breakdown.cache_read.max(0),
             cache_write: self.token_breakdown.cache_write.max(0),
             reasoning: self.token_breakdown.reasoning.max(0),
+            ..Default::default()
         };
 
         let mut clients: Vec<ClientContribution> = self
@@ -714,28 +813,105 @@ struct YearAccumulator {
 /// Cost-relative intensity buckets (0-4): each day's intensity is a function
 /// of its cost relative to the maximum cost across all `contributions`.
 pub fn calculate_intensities(contributions: &mut [DailyContribution]) {
-    let max_cost = contributions
+    calculate_intensities_with_mode(contributions, IntensityMode::Linear);
+}
+
+/// Cost-based intensity buckets (0-4) using the requested scaling.
+pub fn calculate_intensities_with_mode(
+    contributions: &mut [DailyContribution],
+    mode: IntensityMode,
+) {
+    calculate_intensities_with(contributions, mode, IntensityBasis::Cost);
+}
+
+/// Intensity buckets (0-4) using the requested scaling over the daily total
+/// picked by `basis`. Days whose total is zero are always intensity 0,
+/// whatever the mode.
+pub fn calculate_intensities_with(
+    contributions: &mut [DailyContribution],
+    mode: IntensityMode,
+    basis: IntensityBasis,
+) {
+    let values: Vec<f64> = contributions
         .iter()
-        .map(|c| c.totals.cost)
-        .fold(0.0, f64::max);
+        .map(|c| intensity_value(basis, c.totals.cost, c.totals.tokens))
+        .collect();
+    let max_value = values.iter().copied().fold(0.0, f64::max);
 
-    if max_cost == 0.0 {
+    if max_value == 0.0 {
         return;
     }
 
-    for c in contributions.iter_mut() {
-        let ratio = c.totals.cost / max_cost;
-        c.intensity = if ratio >= 0.75 {
-            4
-        } else if ratio >= 0.5 {
-            3
-        } else if ratio >= 0.25 {
-            2
-        } else if ratio > 0.0 {
-            1
-        } else {
-            0
-        };
+    match mode {
+        IntensityMode::Linear => {
+            for (c, value) in contributions.iter_mut().zip(&values) {
+                c.intensity = intensity_for_ratio(value / max_value);
+            }
+        }
+        IntensityMode::Log => {
+            // Scale by the smallest non-zero day so the curve doesn't depend
+            // on whether costs are cents or hundreds of dollars.
+            let min_value = values
+                .iter()
+                .copied()
+                .filter(|value| *value > 0.0)
+                .fold(max_value, f64::min);
+            let max_log = (max_value / min_value).ln_1p();
+            for (c, value) in contributions.iter_mut().zip(&values) {
+                c.intensity = if *value > 0.0 {
+                    intensity_for_ratio((value / min_value).ln_1p() / max_log)
+                } else {
+                    0
+                };
+            }
+        }
+        IntensityMode::Quantile => {
+            let mut positive: Vec<f64> = values
+                .iter()
+                .copied()
+                .filter(|value| *value > 0.0)
+                .collect();
+            positive.sort_by(f64::total_cmp);
+            let n = positive.len();
+            for (c, value) in contributions.iter_mut().zip(&values) {
+                c.intensity = if *value > 0.0 {
+                    // Days tied on value share a bucket: rank by how many
+                    // days are at or below it.
+                    let at_or_below = positive.partition_point(|v| v <= value);
+                    (4 * at_or_below).div_ceil(n).clamp(1, 4) as u8
+                } else {
+                    0
+                };
+            }
+        }
+    }
+}
+
+/// The daily total `basis` selects, as a non-negative finite value.
+pub fn intensity_value(basis: IntensityBasis, cost: f64, tokens: i64) -> f64 {
+    let value = match basis {
+        IntensityBasis::Cost => cost,
+        IntensityBasis::Tokens => tokens as f64,
+    };
+    if value.is_finite() && value > 0.0 {
+        value
+    } else {
+        0.0
+    }
+}
+
+/// Map a day's share of the busiest day (0.0-1.0) onto the 0-4 graph bucket.
+pub fn intensity_for_ratio(ratio: f64) -> u8 {
+    if ratio >= 0.75 {
+        4
+    } else if ratio >= 0.5 {
+        3
+    } else if ratio >= 0.25 {
+        2
+    } else if ratio > 0.0 {
+        1
+    } else {
+        0
     }
 }
 
@@ -773,15 +949,20 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             cost,
             cost_source: Default::default(),
+            client_cost: None,
             duration_ms: None,
             message_count: 1,
             agent: None,
             dedup_key: None,
             session_title: None,
             is_turn_start: false,
+            request_bytes: None,
+            response_bytes: None,
+            reported_total_tokens: None,
         }
     }
 
@@ -858,6 +1039,7 @@ mod tests {
             cache_read: 50,
             cache_write: 40,
             reasoning: 10,
+            ..Default::default()
         };
 
         let result = aggregate_by_date(vec![msg]);
@@ -869,6 +1051,54 @@ mod tests {
         assert_eq!(result[0].token_breakdown.reasoning, 10);
     }
 
+    #[test]
+    fn test_calculate_streaks_reports_lengths_and_endpoints() {
+        let day = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+        let dates = [
+            "2024-03-01",
+            "2024-03-02",
+            "2024-03-03",
+            "2024-03-05",
+            "2024-03-09",
+            "2024-03-08",
+            "2024-03-08",
+        ]
+        .map(day);
+
+        let streaks = calculate_streaks(&dates, day("2024-03-09"));
+        assert_eq!(
+            streaks.current,
+            Streak {
+                days: 2,
+                start: Some(day("2024-03-08")),
+                end: Some(day("2024-03-09")),
+            }
+        );
+        assert_eq!(
+            streaks.longest,
+            Streak {
+                days: 3,
+                start: Some(day("2024-03-01")),
+                end: Some(day("2024-03-03")),
+            }
+        );
+
+        // No usage yet today: the streak through yesterday still counts.
+        let streaks = calculate_streaks(&dates, day("2024-03-10"));
+        assert_eq!(streaks.current.days, 2);
+        assert_eq!(streaks.current.end, Some(day("2024-03-09")));
+
+        // A full day without usage breaks it.
+        assert_eq!(
+            calculate_streaks(&dates, day("2024-03-11")).current,
+            Streak::default()
+        );
+        assert_eq!(
+            calculate_streaks(&[], day("2024-03-11")),
+            Streaks::default()
+        );
+    }
+
     #[test]
     fn test_calculate_summary_empty() {
         let contributions = Vec::new();
@@ -920,6 +1150,29 @@ mod tests {
         assert!((summary.max_cost_in_single_day - 0.10).abs() < 0.0001);
     }
 
+    #[test]
+    fn test_calculate_summary_active_threshold_boundary() {
+        let messages = vec![
+            mock_unified_message("2024-01-01", 10, 0.01, "claude-3-5-sonnet", "opencode"),
+            mock_unified_message("2024-01-02", 1000, 0.05, "claude-3-5-sonnet", "opencode"),
+            mock_unified_message("2024-01-03", 1002, 0.07, "claude-3-5-sonnet", "opencode"),
+        ];
+        let contributions = aggregate_by_date(messages);
+
+        assert_eq!(calculate_summary_with(&contributions, 0).active_days, 3);
+        // Exactly at the threshold counts as active.
+        let summary = calculate_summary_with(&contributions, 1000);
+        assert_eq!(summary.active_days, 2);
+        assert!((summary.average_per_day - 0.13 / 2.0).abs() < 0.0001);
+        assert_eq!(summary.total_days, 3);
+        assert_eq!(calculate_summary_with(&contributions, 1002).active_days, 1);
+        assert_eq!(calculate_summary_with(&contributions, 1003).active_days, 0);
+        assert_eq!(
+            calculate_summary_with(&contributions, 1003).average_per_day,
+            0.0
+        );
+    }
+
     #[test]
     fn test_calculate_summary_with_zero_token_days() {
         let contributions = vec![
@@ -1185,6 +1438,124 @@ mod tests {
         assert_eq!(contributions[1].intensity, 0);
     }
 
+    fn contributions_with_costs(costs: &[f64]) -> Vec<DailyContribution> {
+        costs
+            .iter()
+            .enumerate()
+            .map(|(i, cost)| DailyContribution {
+                date: format!("2024-01-{:02}", i + 1),
+                totals: DailyTotals {
+                    tokens: (*cost * 1000.0) as i64,
+                    cost: *cost,
+                    messages: 1,
+                },
+                intensity: 0,
+                token_breakdown: TokenBreakdown::default(),
+                clients: Vec::new(),
+                active_time_ms: None,
+            })
+            .collect()
+    }
+
+    fn intensities_with_mode(costs: &[f64], mode: IntensityMode) -> Vec<u8> {
+        let mut contributions = contributions_with_costs(costs);
+        calculate_intensities_with_mode(&mut contributions, mode);
+        contributions.iter().map(|c| c.intensity).collect()
+    }
+
+    #[test]
+    fn test_calculate_intensities_modes_on_bursty_totals() {
+        let costs = [0.0, 1.0, 2.0, 10.0, 100.0, 1000.0];
+
+        assert_eq!(
+            intensities_with_mode(&costs, IntensityMode::Linear),
+            vec![0, 1, 1, 1, 1, 4]
+        );
+        assert_eq!(
+            intensities_with_mode(&costs, IntensityMode::Log),
+            vec![0, 1, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            intensities_with_mode(&costs, IntensityMode::Quantile),
+            vec![0, 1, 2, 3, 4, 4]
+        );
+    }
+
+    #[test]
+    fn test_calculate_intensities_modes_handle_flat_and_zero_days() {
+        for mode in [
+            IntensityMode::Linear,
+            IntensityMode::Log,
+            IntensityMode::Quantile,
+        ] {
+            assert_eq!(
+                intensities_with_mode(&[0.0, 0.0], mode),
+                vec![0, 0],
+                "{mode}"
+            );
+            assert_eq!(
+                intensities_with_mode(&[0.0, 5.0, 5.0], mode),
+                vec![0, 4, 4],
+                "{mode}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_intensity_mode_parses_cli_values() {
+        assert_eq!("log".parse::<IntensityMode>(), Ok(IntensityMode::Log));
+        assert_eq!(
+            " Quantile ".parse::<IntensityMode>(),
+            Ok(IntensityMode::Quantile)
+        );
+        assert_eq!("linear".parse::<IntensityMode>(), Ok(IntensityMode::Linear));
+        assert!("cubic".parse::<IntensityMode>().is_err());
+    }
+
+    #[test]
+    fn test_calculate_intensities_token_basis_ignores_cost() {
+        // Cheap high-volume day vs expensive low-volume day.
+        let mut contributions = contributions_with_costs(&[1.0, 10.0]);
+        contributions[0].totals.tokens = 1_000_000;
+        contributions[1].totals.tokens = 100_000;
+
+        calculate_intensities_with(
+            &mut contributions,
+            IntensityMode::Linear,
+            IntensityBasis::Cost,
+        );
+        assert_eq!(
+            contributions
+                .iter()
+                .map(|c| c.intensity)
+                .collect::<Vec<_>>(),
+            vec![1, 4]
+        );
+
+        calculate_intensities_with(
+            &mut contributions,
+            IntensityMode::Linear,
+            IntensityBasis::Tokens,
+        );
+        assert_eq!(
+            contributions
+                .iter()
+                .map(|c| c.intensity)
+                .collect::<Vec<_>>(),
+            vec![4, 1]
+        );
+    }
+
+    #[test]
+    fn test_intensity_basis_parses_cli_values() {
+        assert_eq!("cost".parse::<IntensityBasis>(), Ok(IntensityBasis::Cost));
+        assert_eq!(
+            " Tokens ".parse::<IntensityBasis>(),
+            Ok(IntensityBasis::Tokens)
+        );
+        assert!("messages".parse::<IntensityBasis>().is_err());
+    }
+
     #[test]
     fn test_calculate_intensities_levels() {
         let mut contributions = vec![
@@ -1397,6 +1768,10 @@ mod tests {
             dedup_key: None,
             session_title: None,
             is_turn_start: false,
+            request_bytes: None,
+            response_bytes: None,
+            reported_total_tokens: None,
+            client_cost: None,
             duration_ms: None,
         }
     }
@@ -1414,6 +1789,7 @@ mod tests {
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         };
         // 10 rows across 3 sessions.
         let messages = vec![
@@ -1559,6 +1935,7 @@ mod tests {
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         };
         let big = TokenBreakdown {
             input: 1000,
@@ -1566,6 +1943,7 @@ mod tests {
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         };
         let messages = vec![
             session_message(
@@ -1619,6 +1997,7 @@ mod tests {
                 cache_read: 1_920,
                 cache_write: 0,
                 reasoning: 40,
+                ..Default::default()
             },
             clients: vec![ClientContribution {
                 client: "codex".to_string(),
@@ -1630,6 +2009,7 @@ mod tests {
                     cache_read: 1_920,
                     cache_write: 0,
                     reasoning: 40,
+                    ..Default::default()
                 },
                 cost: 0.0123,
                 messages: 12,
@@ -21,37 +21,9 @@ impl PathRoot {
                     format!("{}/.local/share", home_dir)
                 }
             }
-            PathRoot::Config => {
-                if use_env_roots {
-                    if let Some(custom) = std::env::var_os("TOKSCALE_CONFIG_DIR") {
-                        if !custom.is_empty() {
-                            return custom.to_string_lossy().into_owned();
-                        }
-                    }
-
-                    #[cfg(target_os = "linux")]
-                    if let Ok(xdg_config_home) = std::env::var("XDG_CONFIG_HOME") {
-                        return format!("{xdg_config_home}/tokscale");
-                    }
-
-                    // Match paths::get_config_dir() so default Windows scans
-                    // read the same %APPDATA% root used by cache writers.
-                    #[cfg(target_os = "windows")]
-                    if let Some(dir) = dirs::config_dir() {
-                        return dir.join("tokscale").to_string_lossy().into_owned();
-                    }
-                }
-
-                #[cfg(target_os = "windows")]
-                if !use_env_roots {
-                    return std::path::Path::new(home_dir)
-                        .join("AppData/Roaming/tokscale")
-                        .to_string_lossy()
-                        .into_owned();
-                }
-
-                format!("{home_dir}/.config/tokscale")
-            }
+            PathRoot::Config => crate::paths::config_dir_for_home(home_dir, use_env_roots)
+                .to_string_lossy()
+                .into_owned(),
             PathRoot::EnvVar {
                 var,
                 fallback_relative,
@@ -78,6 +50,10 @@ impl PathRoot {
 #[derive(Debug, Clone)]
 pub struct ClientDef {
     pub id: &'static str,
+    /// Name shown in tables and the TUI, e.g. `Claude` or `Roo Code`.
+    pub display_name: &'static str,
+    /// Key that toggles the client in the TUI's source picker.
+    pub hotkey: char,
     pub root: PathRoot,
     pub relative_path: &'static str,
     pub pattern: &'static str,
@@ -101,7 +77,7 @@ impl ClientDef {
 }
 
 macro_rules! define_clients {
-    ( $( $variant:ident = $index:expr => { id: $id:expr, root: $root:expr, relative: $rel:expr, pattern: $pat:expr, headless: $hl:expr, parse_local: $pl:expr, submit_default: $sd:expr } ),+ $(,)? ) => {
+    ( $( $variant:ident = $index:expr => { id: $id:expr, display_name: $display:expr, hotkey: $hotkey:expr, root: $root:expr, relative: $rel:expr, pattern: $pat:expr, headless: $hl:expr, parse_local: $pl:expr, submit_default: $sd:expr } ),+ $(,)? ) => {
         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         #[repr(usize)]
         pub enum ClientId {
@@ -120,6 +96,18 @@ macro_rules! define_clients {
                 self.data().id
             }
 
+            pub fn display_name(&self) -> &'static str {
+                self.data().display_name
+            }
+
+            pub fn hotkey(&self) -> char {
+                self.data().hotkey
+            }
+
+            pub fn from_hotkey(key: char) -> Option<ClientId> {
+                Self::ALL.iter().copied().find(|c| c.hotkey() == key)
+            }
+
             pub fn file_pattern(&self) -> &'static str {
                 self.data().pattern
             }
@@ -149,6 +137,8 @@ macro_rules! define_clients {
         pub const CLIENTS: [ClientDef; ClientId::COUNT] = [
             $( ClientDef {
                 id: $id,
+                display_name: $display,
+                hotkey: $hotkey,
                 root: $root,
                 relative_path: $rel,
                 pattern: $pat,
@@ -172,38 +162,46 @@ macro_rules! define_clients {
 define_clients!(
     OpenCode = 0 => {
         id: "opencode",
+        display_name: "OpenCode",
+        hotkey: '1',
         root: PathRoot::XdgData,
         relative: "opencode/storage/message",
-        pattern: "*.json",
+        pattern: "*.json|*.json.gz",
         headless: false,
         parse_local: true,
         submit_default: true
     },
     Claude = 1 => {
         id: "claude",
+        display_name: "Claude",
+        hotkey: '2',
         root: PathRoot::Home,
         relative: ".claude/projects",
-        pattern: "*.jsonl",
+        pattern: "*.jsonl|*.jsonl.gz",
         headless: false,
         parse_local: true,
         submit_default: true
     },
     Codex = 2 => {
         id: "codex",
+        display_name: "Codex",
+        hotkey: '3',
         root: PathRoot::EnvVar {
             var: "CODEX_HOME",
             fallback_relative: ".codex",
         },
         relative: "sessions",
-        pattern: "*.jsonl",
+        pattern: "*.jsonl|*.jsonl.gz",
         headless: true,
         parse_local: true,
         submit_default: true
     },
     Cursor = 3 => {
         id: "cursor",
-        root: PathRoot::Home,
-        relative: ".config/tokscale/cursor-cache",
+        display_name: "Cursor",
+        hotkey: '4',
+        root: PathRoot::Config,
+        relative: "cursor-cache",
         pattern: "usage*.csv",
         headless: false,
         parse_local: false,
@@ -211,18 +209,22 @@ define_clients!(
     },
     Gemini = 4 => {
         id: "gemini",
+        display_name: "Gemini",
+        hotkey: '5',
         root: PathRoot::EnvVar {
             var: "GEMINI_CLI_HOME",
             fallback_relative: ".gemini",
         },
         relative: "tmp",
-        pattern: "*.json|*.jsonl",
+        pattern: "*.json|*.jsonl|*.json.gz|*.jsonl.gz",
         headless: false,
         parse_local: true,
         submit_default: true
     },
     Amp = 5 => {
         id: "amp",
+        display_name: "Amp",
+        hotkey: '6',
         root: PathRoot::XdgData,
         relative: "amp/threads",
         pattern: "T-*.json",
@@ -232,6 +234,8 @@ define_clients!(
     },
     Droid = 6 => {
         id: "droid",
+        display_name: "Droid",
+        hotkey: '7',
         root: PathRoot::Home,
         relative: ".factory/sessions",
         pattern: "*.settings.json",
@@ -241,6 +245,8 @@ define_clients!(
     },
     OpenClaw = 7 => {
         id: "openclaw",
+        display_name: "OpenClaw",
+        hotkey: '8',
         root: PathRoot::Home,
         relative: ".openclaw/agents",
         pattern: "*.jsonl*",
@@ -250,6 +256,8 @@ define_clients!(
     },
     Pi = 8 => {
         id: "pi",
+        display_name: "Pi",
+        hotkey: '9',
         root: PathRoot::Home,
         relative: ".pi/agent/sessions",
         pattern: "*.jsonl",
@@ -259,6 +267,8 @@ define_clients!(
     },
     Kimi = 9 => {
         id: "kimi",
+        display_name: "Kimi",
+        hotkey: '0',
         root: PathRoot::Home,
         relative: ".kimi/sessions",
         pattern: "wire.jsonl",
@@ -268,6 +278,8 @@ define_clients!(
     },
     Qwen = 10 => {
         id: "qwen",
+        display_name: "Qwen",
+        hotkey: 'w',
         root: PathRoot::Home,
         relative: ".qwen/projects",
         pattern: "*.jsonl",
@@ -277,6 +289,8 @@ define_clients!(
     },
     RooCode = 11 => {
         id: "roocode",
+        display_name: "Roo Code",
+        hotkey: 'r',
         root: PathRoot::Home,
         relative: ".config/Code/User/globalStorage/rooveterinaryinc.roo-cline/tasks",
         pattern: "ui_messages.json",
@@ -286,6 +300,8 @@ define_clients!(
     },
     KiloCode = 12 => {
         id: "kilocode",
+        display_name: "KiloCode",
+        hotkey: 'k',
         root: PathRoot::Home,
         relative: ".config/Code/User/globalStorage/kilocode.kilo-code/tasks",
         pattern: "ui_messages.json",
@@ -295,6 +311,8 @@ define_clients!(
     },
     Mux = 13 => {
         id: "mux",
+        display_name: "Mux",
+        hotkey: 'x',
         root: PathRoot::Home,
         relative: ".mux/sessions",
         pattern: "session-usage.json",
@@ -304,6 +322,8 @@ define_clients!(
     },
     Kilo = 14 => {
         id: "kilo",
+        display_name: "Kilo CLI",
+        hotkey: 'l',
         root: PathRoot::XdgData,
         relative: "kilo/kilo.db",
         pattern: "kilo.db",
@@ -313,6 +333,8 @@ define_clients!(
     },
     Crush = 15 => {
         id: "crush",
+        display_name: "Crush",
+        hotkey: 'h',
         root: PathRoot::XdgData,
         relative: "crush/projects.json",
         pattern: "projects.json",
@@ -322,6 +344,8 @@ define_clients!(
     },
     Hermes = 16 => {
         id: "hermes",
+        display_name: "Hermes Agent",
+        hotkey: 'e',
         root: PathRoot::EnvVar {
             var: "HERMES_HOME",
             fallback_relative: ".hermes",
@@ -334,6 +358,8 @@ define_clients!(
     },
     Copilot = 17 => {
         id: "copilot",
+        display_name: "Copilot",
+        hotkey: 'c',
         root: PathRoot::Home,
         relative: ".copilot/otel",
         pattern: "*.jsonl",
@@ -343,6 +369,8 @@ define_clients!(
     },
     Goose = 18 => {
         id: "goose",
+        display_name: "Goose",
+        hotkey: 'o',
         root: PathRoot::XdgData,
         relative: "goose/sessions/sessions.db",
         pattern: "sessions.db",
@@ -352,6 +380,8 @@ define_clients!(
     },
     Codebuff = 19 => {
         id: "codebuff",
+        display_name: "Codebuff",
+        hotkey: 'b',
         root: PathRoot::EnvVar {
             var: "CODEBUFF_DATA_DIR",
             fallback_relative: ".config/manicode",
@@ -364,6 +394,8 @@ define_clients!(
     },
     Antigravity = 20 => {
         id: "antigravity",
+        display_name: "Antigravity",
+        hotkey: 'a',
         root: PathRoot::Config,
         relative: "antigravity-cache/sessions",
         pattern: "*.jsonl",
@@ -373,6 +405,8 @@ define_clients!(
     },
     Zed = 21 => {
         id: "zed",
+        display_name: "Zed Agent",
+        hotkey: 'z',
         root: PathRoot::XdgData,
         relative: "zed/threads/threads.db",
         pattern: "threads.db",
@@ -382,6 +416,8 @@ define_clients!(
     },
     Kiro = 22 => {
         id: "kiro",
+        display_name: "Kiro",
+        hotkey: 'i',
         root: PathRoot::Home,
         relative: ".kiro/sessions/cli",
         pattern: "*.json",
@@ -391,6 +427,8 @@ define_clients!(
     },
     Trae = 23 => {
         id: "trae",
+        display_name: "Trae",
+        hotkey: 'y',
         root: PathRoot::Config,
         relative: "trae-cache/sessions",
         pattern: "*.json",
@@ -400,6 +438,8 @@ define_clients!(
     },
     Warp = 24 => {
         id: "warp",
+        display_name: "Warp",
+        hotkey: 'v',
         root: PathRoot::Config,
         relative: "warp-cache",
         pattern: "usage*.json",
@@ -409,6 +449,8 @@ define_clients!(
     },
     Cline = 25 => {
         id: "cline",
+        display_name: "Cline",
+        hotkey: 'n',
         root: PathRoot::Home,
         relative: ".config/Code/User/globalStorage/saoudrizwan.claude-dev/tasks",
         pattern: "ui_messages.json",
@@ -418,6 +460,8 @@ define_clients!(
     },
     Gjc = 26 => {
         id: "gjc",
+        display_name: "Gajae-Code",
+        hotkey: 'g',
         root: PathRoot::EnvVar {
             var: "GJC_CODING_AGENT_DIR",
             fallback_relative: ".gjc/agent",
@@ -430,6 +474,8 @@ define_clients!(
     },
     Grok = 27 => {
         id: "grok",
+        display_name: "Grok Build",
+        hotkey: 'u',
         root: PathRoot::EnvVar {
             var: "GROK_HOME",
             fallback_relative: ".grok",
@@ -442,6 +488,8 @@ define_clients!(
     },
     Jcode = 28 => {
         id: "jcode",
+        display_name: "Jcode",
+        hotkey: 'j',
         root: PathRoot::EnvVar {
             var: "JCODE_HOME",
             fallback_relative: ".jcode",
@@ -454,6 +502,8 @@ define_clients!(
     },
     CommandCode = 29 => {
         id: "commandcode",
+        display_name: "Command Code",
+        hotkey: 'd',
         root: PathRoot::Home,
         relative: ".commandcode/projects",
         pattern: "*.jsonl",
@@ -463,6 +513,8 @@ define_clients!(
     },
     MiMoCode = 30 => {
         id: "micode",
+        display_name: "MiMo Code",
+        hotkey: 'm',
         root: PathRoot::XdgData,
         relative: "mimocode",
         pattern: "*.db",
@@ -478,6 +530,8 @@ define_clients!(
     // `GEMINI_CLI_HOME` so a relocated Gemini home is picked up.
     AntigravityCli = 31 => {
         id: "antigravity-cli",
+        display_name: "Antigravity CLI",
+        hotkey: 'f',
         root: PathRoot::EnvVar {
             var: "GEMINI_CLI_HOME",
             fallback_relative: ".gemini",
@@ -490,6 +544,8 @@ define_clients!(
     },
     Junie = 32 => {
         id: "junie",
+        display_name: "Junie",
+        hotkey: 'p',
         root: PathRoot::Home,
         relative: ".junie/sessions",
         pattern: "events.jsonl",
@@ -499,6 +555,8 @@ define_clients!(
     },
     Zcode = 33 => {
         id: "zcode",
+        display_name: "ZCode",
+        hotkey: 'q',
         root: PathRoot::Home,
         relative: ".zcode/projects",
         pattern: "*.jsonl",
@@ -508,6 +566,8 @@ define_clients!(
     },
     OpenCodeReview = 34 => {
         id: "opencodereview",
+        display_name: "OpenCodeReview",
+        hotkey: 'O',
         root: PathRoot::Home,
         relative: ".opencodereview/sessions",
         pattern: "*.jsonl",
@@ -517,6 +577,8 @@ define_clients!(
     },
     CodeBuddy = 35 => {
         id: "codebuddy",
+        display_name: "CodeBuddy",
+        hotkey: 'C',
         root: PathRoot::Home,
         relative: ".codebuddy/projects",
         pattern: "*.jsonl",
@@ -526,6 +588,8 @@ define_clients!(
     },
     WorkBuddy = 36 => {
         id: "workbuddy",
+        display_name: "WorkBuddy",
+        hotkey: 'B',
         root: PathRoot::Home,
         relative: ".workbuddy",
         pattern: "workbuddy.db",
@@ -535,6 +599,8 @@ define_clients!(
     },
     DevinCli = 37 => {
         id: "devin-cli",
+        display_name: "Devin CLI",
+        hotkey: 'D',
         root: PathRoot::XdgData,
         relative: "devin/cli/sessions.db",
         pattern: "sessions.db",
@@ -544,12 +610,25 @@ define_clients!(
     },
     DevinDesktop = 38 => {
         id: "devin-desktop",
+        display_name: "Devin Desktop",
+        hotkey: 'E',
         root: PathRoot::Home,
         relative: "Library/Application Support/Devin/User/acp-events",
         pattern: "*.ndjson",
         headless: false,
         parse_local: true,
         submit_default: true
+    },
+    Aider = 39 => {
+        id: "aider",
+        display_name: "Aider",
+        hotkey: 'A',
+        root: PathRoot::Home,
+        relative: ".aider.chat.history.md",
+        pattern: "aider-history",
+        headless: false,
+        parse_local: true,
+        submit_default: true
     }
 );
 
@@ -602,7 +681,7 @@ mod tests {
 
     #[test]
     fn test_client_id_count() {
-        assert_eq!(ClientId::COUNT, 39);
+        assert_eq!(ClientId::COUNT, 40);
     }
 
     #[test]
@@ -658,6 +737,19 @@ mod tests {
         assert!(!client.data().headless);
     }
 
+    #[test]
+    fn test_aider_client_registered_as_local_history_source() {
+        let client = ClientId::from_str("aider").expect("aider client should be registered");
+        assert_eq!(
+            client.data().resolve_path("/tmp/home"),
+            "/tmp/home/.aider.chat.history.md"
+        );
+        assert_eq!(client.data().pattern, "aider-history");
+        assert!(client.data().parse_local);
+        assert!(client.data().submit_default);
+        assert!(!client.data().headless);
+    }
+
     #[test]
     fn test_commandcode_client_registered_as_local_session_source() {
         let client =
@@ -690,6 +782,28 @@ mod tests {
         assert_eq!(ClientId::ALL.len(), ClientId::COUNT);
     }
 
+    #[test]
+    fn test_every_client_has_a_display_name_and_unique_hotkey() {
+        let mut seen = std::collections::HashMap::new();
+        for client in ClientId::iter() {
+            assert!(
+                !client.display_name().trim().is_empty(),
+                "{} has no display name",
+                client.as_str()
+            );
+            if let Some(other) = seen.insert(client.hotkey(), client) {
+                panic!(
+                    "{} and {} share hotkey {:?}",
+                    other.as_str(),
+                    client.as_str(),
+                    client.hotkey()
+                );
+            }
+            assert_eq!(ClientId::from_hotkey(client.hotkey()), Some(client));
+        }
+        assert_eq!(ClientId::OpenClaw.display_name(), "OpenClaw");
+    }
+
     #[test]
     fn test_client_id_string_round_trip() {
         for client in ClientId::iter() {
@@ -910,6 +1024,8 @@ mod tests {
     fn test_client_def_resolve_path_combines_root_and_relative() {
         let client = ClientDef {
             id: "test",
+            display_name: "Test",
+            hotkey: 't',
             root: PathRoot::Home,
             relative_path: ".test/sessions",
             pattern: "*.jsonl",
@@ -0,0 +1,90 @@
+//! Handling for messages dated in the future.
+//!
+//! A machine with a skewed clock can write session files dated days or years
+//! ahead, which then land in the wrong bucket of every date-grouped report.
+//! By default such messages are clamped to today; `max_future_days` in the
+//! parse options switches to dropping anything more than that many days
+//! ahead instead.
+
+use chrono::{Local, NaiveDate};
+
+/// What to do with a message dated after `today`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FutureDatePolicy {
+    today: NaiveDate,
+    now_ms: i64,
+    max_future_days: Option<u32>,
+}
+
+impl FutureDatePolicy {
+    /// The policy for `max_future_days`, relative to the local clock.
+    pub(crate) fn new(max_future_days: Option<u32>) -> Self {
+        let now = Local::now();
+        Self {
+            today: now.date_naive(),
+            now_ms: now.timestamp_millis(),
+            max_future_days,
+        }
+    }
+
+    /// Returns `false` when the message should be dropped. Otherwise a
+    /// future date is clamped to today when no limit is configured, and left
+    /// alone when it is within the limit.
+    pub(crate) fn admit(&self, date: &mut String, timestamp: &mut i64) -> bool {
+        let Ok(day) = NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+            return true;
+        };
+        if day <= self.today {
+            return true;
+        }
+        match self.max_future_days {
+            Some(days) => (day - self.today).num_days() <= i64::from(days),
+            None => {
+                *date = self.today.format("%Y-%m-%d").to_string();
+                *timestamp = (*timestamp).min(self.now_ms);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_future_days: Option<u32>) -> FutureDatePolicy {
+        FutureDatePolicy {
+            today: NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(),
+            now_ms: 1_773_100_000_000,
+            max_future_days,
+        }
+    }
+
+    #[test]
+    fn clamps_future_dates_to_today_by_default() {
+        let mut date = "2099-01-01".to_string();
+        let mut timestamp = 4_070_908_800_000;
+        assert!(policy(None).admit(&mut date, &mut timestamp));
+        assert_eq!(date, "2026-03-10");
+        assert_eq!(timestamp, 1_773_100_000_000);
+
+        let mut past = "2026-03-09".to_string();
+        let mut past_timestamp = 1;
+        assert!(policy(None).admit(&mut past, &mut past_timestamp));
+        assert_eq!(past, "2026-03-09");
+        assert_eq!(past_timestamp, 1);
+    }
+
+    #[test]
+    fn drops_dates_beyond_the_configured_limit() {
+        let limited = policy(Some(2));
+        let mut near = "2026-03-12".to_string();
+        let mut timestamp = 0;
+        assert!(limited.admit(&mut near, &mut timestamp));
+        assert_eq!(near, "2026-03-12");
+
+        let mut far = "2026-03-13".to_string();
+        assert!(!limited.admit(&mut far, &mut timestamp));
+        assert!(!policy(Some(0)).admit(&mut "2026-03-11".to_string(), &mut timestamp));
+    }
+}
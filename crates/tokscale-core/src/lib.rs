@@ -5,6 +5,7 @@ mod cc_mirror;
 pub mod clients;
 pub mod content_extractor;
 pub mod fs_atomic;
+pub mod future_dates;
 pub mod mcp;
 mod message_cache;
 pub mod model_alias;
@@ -13,16 +14,20 @@ mod parser;
 pub mod paths;
 pub mod pricing;
 mod provider_identity;
+pub mod scan_pool;
 pub mod scanner;
 pub mod sessionize;
 pub mod sessions;
+pub mod sqlite_export;
 pub mod tui_signal;
+pub mod validate;
 pub mod wiki;
 
 pub use aggregator::*;
 pub use clients::{ClientCounts, ClientDef, ClientId, PathRoot};
 pub use model_alias::ModelAliasMap;
 pub use parser::*;
+pub use provider_identity::{model_family, normalize_provider};
 pub use scanner::*;
 pub use sessionize::{
     compute_daily_active_time, compute_time_metrics, sessionize, SessionInterval, TimeMetrics,
@@ -31,10 +36,10 @@ pub use sessionize::{
 pub use sessions::{CostSource, UnifiedMessage};
 
 use rayon::prelude::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, OnceLock};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Strip a CLIProxyAPI-style `(level)` reasoning-effort suffix from a model id.
 ///
@@ -197,8 +202,16 @@ pub enum GroupBy {
     ClientModel,
     ClientProviderModel,
     WorkspaceModel,
+    /// One row per project (the session's workspace, usually derived from
+    /// its path), labelled with the project's dominant model.
+    Project,
+    /// One row per `client:session_id`, labelled with the session's dominant
+    /// model (the one that cost the most).
     Session,
-    ClientSession,
+    SessionModel,
+    ClientSessionModel,
+    /// One row per model family (`claude`, `gpt`, ...); see [`model_family`].
+    Family,
 }
 
 impl std::fmt::Display for GroupBy {
@@ -208,8 +221,27 @@ impl std::fmt::Display for GroupBy {
             GroupBy::ClientModel => write!(f, "client,model"),
             GroupBy::ClientProviderModel => write!(f, "client,provider,model"),
             GroupBy::WorkspaceModel => write!(f, "workspace,model"),
-            GroupBy::Session => write!(f, "session,model"),
-            GroupBy::ClientSession => write!(f, "client,session,model"),
+            GroupBy::Project => write!(f, "project"),
+            GroupBy::Session => write!(f, "session"),
+            GroupBy::SessionModel => write!(f, "session,model"),
+            GroupBy::ClientSessionModel => write!(f, "client,session,model"),
+            GroupBy::Family => write!(f, "family"),
+        }
+    }
+}
+
+impl GroupBy {
+    /// `--merge-providers`: `ClientProviderModel` keeps its client+model key
+    /// but stops splitting on provider, so one row lists every provider it
+    /// was routed through, the way `ClientModel` and `Model` rows already do.
+    /// Providers are normalized first (`anthropic-beta` counts as
+    /// `anthropic`), so the label joins the deduplicated, sorted normalized
+    /// names while `raw_provider` keeps the original ids. Other modes are
+    /// unchanged.
+    pub fn with_merged_providers(self, merge: bool) -> GroupBy {
+        match self {
+            GroupBy::ClientProviderModel if merge => GroupBy::ClientModel,
+            other => other,
         }
     }
 }
@@ -224,18 +256,235 @@ impl std::str::FromStr for GroupBy {
             "client,model" | "client-model" => Ok(GroupBy::ClientModel),
             "client,provider,model" | "client-provider-model" => Ok(GroupBy::ClientProviderModel),
             "workspace,model" | "workspace-model" => Ok(GroupBy::WorkspaceModel),
-            "session" | "session,model" | "session-model" => Ok(GroupBy::Session),
+            "project" | "workspace" => Ok(GroupBy::Project),
+            "session" => Ok(GroupBy::Session),
+            "session,model" | "session-model" => Ok(GroupBy::SessionModel),
             "client,session" | "client-session" | "client,session,model" | "client-session-model" => {
-                Ok(GroupBy::ClientSession)
+                Ok(GroupBy::ClientSessionModel)
             }
+            "family" | "model-family" => Ok(GroupBy::Family),
+            _ => Err(format!(
+                "Invalid group-by value: '{}'. Valid options: model, client,model, client,provider,model, workspace,model, project, session, session,model, client,session,model, family",
+                s
+            )),
+        }
+    }
+}
+
+/// How daily cost is bucketed into the 0-4 contribution-graph intensity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub enum IntensityMode {
+    /// Fraction of the busiest day's cost (the historical behaviour).
+    #[default]
+    Linear,
+    /// Like `Linear`, but on a logarithmic scale so bursty days don't flatten
+    /// everything else into the lowest bucket.
+    Log,
+    /// Quartile of the day's cost among all days with non-zero cost.
+    Quantile,
+}
+
+impl std::fmt::Display for IntensityMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntensityMode::Linear => write!(f, "linear"),
+            IntensityMode::Log => write!(f, "log"),
+            IntensityMode::Quantile => write!(f, "quantile"),
+        }
+    }
+}
+
+impl std::str::FromStr for IntensityMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "linear" => Ok(IntensityMode::Linear),
+            "log" | "logarithmic" => Ok(IntensityMode::Log),
+            "quantile" | "quartile" => Ok(IntensityMode::Quantile),
+            _ => Err(format!(
+                "Invalid intensity value: '{}'. Valid options: linear, log, quantile",
+                s
+            )),
+        }
+    }
+}
+
+/// Which daily total drives the contribution-graph intensity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntensityBasis {
+    /// Daily cost in USD (the historical behaviour).
+    #[default]
+    Cost,
+    /// Daily token total, for users whose cost is dominated by a few
+    /// expensive models.
+    Tokens,
+}
+
+impl std::fmt::Display for IntensityBasis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntensityBasis::Cost => write!(f, "cost"),
+            IntensityBasis::Tokens => write!(f, "tokens"),
+        }
+    }
+}
+
+impl std::str::FromStr for IntensityBasis {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "cost" => Ok(IntensityBasis::Cost),
+            "tokens" => Ok(IntensityBasis::Tokens),
+            _ => Err(format!(
+                "Invalid intensity basis: '{}'. Valid options: cost, tokens",
+                s
+            )),
+        }
+    }
+}
+
+/// Column the models report is sorted by (`--order-by`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModelOrderKey {
+    #[default]
+    Cost,
+    /// Token total, counted like `--min-tokens` (reasoning included).
+    Tokens,
+    Messages,
+    Model,
+    Input,
+    Output,
+}
+
+impl std::fmt::Display for ModelOrderKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelOrderKey::Cost => write!(f, "cost"),
+            ModelOrderKey::Tokens => write!(f, "tokens"),
+            ModelOrderKey::Messages => write!(f, "messages"),
+            ModelOrderKey::Model => write!(f, "model"),
+            ModelOrderKey::Input => write!(f, "input"),
+            ModelOrderKey::Output => write!(f, "output"),
+        }
+    }
+}
+
+impl std::str::FromStr for ModelOrderKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "cost" => Ok(ModelOrderKey::Cost),
+            "tokens" => Ok(ModelOrderKey::Tokens),
+            "messages" => Ok(ModelOrderKey::Messages),
+            "model" => Ok(ModelOrderKey::Model),
+            "input" => Ok(ModelOrderKey::Input),
+            "output" => Ok(ModelOrderKey::Output),
             _ => Err(format!(
-                "Invalid group-by value: '{}'. Valid options: model, client,model, client,provider,model, workspace,model, session,model, client,session,model",
+                "Invalid order-by value: '{}'. Valid options: cost, tokens, messages, model, input, output",
                 s
             )),
         }
     }
 }
 
+/// Row order for the models report. Defaults to cost, highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelOrder {
+    pub key: ModelOrderKey,
+    pub descending: bool,
+}
+
+impl ModelOrder {
+    /// Order by `key`; without an explicit direction, `model` sorts A-Z and
+    /// every numeric key sorts highest first.
+    pub fn new(key: ModelOrderKey, descending: Option<bool>) -> Self {
+        Self {
+            key,
+            descending: descending.unwrap_or(key != ModelOrderKey::Model),
+        }
+    }
+}
+
+impl Default for ModelOrder {
+    fn default() -> Self {
+        Self::new(ModelOrderKey::Cost, None)
+    }
+}
+
+/// Sort model-report rows by `order`. NaN costs always sort last, and ties
+/// fall back to the model name (A-Z) so the output is deterministic. Pricing
+/// already skips NaN and infinite rates, so the NaN ordering is defensive.
+pub fn sort_model_usage(entries: &mut [ModelUsage], order: ModelOrder) {
+    use std::cmp::Ordering;
+
+    let token_total = |e: &ModelUsage| {
+        positive_token_total(&TokenBreakdown {
+            input: e.input,
+            output: e.output,
+            cache_read: e.cache_read,
+            cache_write: e.cache_write,
+            reasoning: e.reasoning,
+            ..Default::default()
+        })
+    };
+    let directed = |ordering: Ordering| {
+        if order.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    };
+
+    entries.sort_by(|a, b| {
+        let primary = match order.key {
+            ModelOrderKey::Cost => match (a.cost.is_nan(), b.cost.is_nan()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => directed(a.cost.partial_cmp(&b.cost).unwrap_or(Ordering::Equal)),
+            },
+            ModelOrderKey::Tokens => directed(token_total(a).cmp(&token_total(b))),
+            ModelOrderKey::Messages => directed(a.message_count.cmp(&b.message_count)),
+            ModelOrderKey::Model => directed(a.model.cmp(&b.model)),
+            ModelOrderKey::Input => directed(a.input.cmp(&b.input)),
+            ModelOrderKey::Output => directed(a.output.cmp(&b.output)),
+        };
+        primary.then_with(|| a.model.cmp(&b.model))
+    });
+}
+
+/// Reorder the zero-cost rows of a cost-sorted report by token total (most
+/// first, then model name). Cost sorting leaves them as one contiguous run in
+/// name order, which hides the heaviest subscription usage.
+fn sort_zero_cost_by_tokens(entries: &mut [ModelUsage]) {
+    let Some(start) = entries.iter().position(|e| e.cost == 0.0) else {
+        return;
+    };
+    let end = entries[start..]
+        .iter()
+        .position(|e| e.cost != 0.0)
+        .map_or(entries.len(), |len| start + len);
+    entries[start..end].sort_by(|a, b| {
+        let tokens = |e: &ModelUsage| {
+            positive_token_total(&TokenBreakdown {
+                input: e.input,
+                output: e.output,
+                cache_read: e.cache_read,
+                cache_write: e.cache_write,
+                reasoning: e.reasoning,
+                ..Default::default()
+            })
+        };
+        tokens(b)
+            .cmp(&tokens(a))
+            .then_with(|| a.model.cmp(&b.model))
+    });
+}
+
 #[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct TokenBreakdown {
     pub input: i64,
@@ -243,6 +492,12 @@ pub struct TokenBreakdown {
     pub cache_read: i64,
     pub cache_write: i64,
     pub reasoning: i64,
+    /// Portion of `cache_write` written with Anthropic's 1-hour cache TTL,
+    /// which is billed at a higher rate than the default 5-minute tier.
+    /// Already counted in `cache_write`; zero for sources that don't report
+    /// the split.
+    #[serde(default)]
+    pub cache_write_1h: i64,
 }
 
 impl TokenBreakdown {
@@ -255,6 +510,27 @@ impl TokenBreakdown {
             .saturating_add(self.cache_write)
             .saturating_add(self.reasoning)
     }
+
+    /// Raise any negative bucket to zero and return how many were negative.
+    /// Corrupt logs occasionally carry negative counts, which would otherwise
+    /// subtract from report totals.
+    pub fn clamp_negative(&mut self) -> usize {
+        let mut clamped = 0;
+        for value in [
+            &mut self.input,
+            &mut self.output,
+            &mut self.cache_read,
+            &mut self.cache_write,
+            &mut self.reasoning,
+            &mut self.cache_write_1h,
+        ] {
+            if *value < 0 {
+                *value = 0;
+                clamped += 1;
+            }
+        }
+        clamped
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -404,10 +680,28 @@ pub struct LocalParseOptions {
     pub clients: Option<Vec<String>>,
     pub since: Option<String>,
     pub until: Option<String>,
+    /// Treat `until` as an exclusive upper bound (`date < until`) instead of
+    /// the default inclusive one, so back-to-back windows never share a day.
+    pub until_exclusive: bool,
     pub year: Option<String>,
     /// Persistent scanner config loaded from `~/.config/tokscale/settings.json`.
     /// Defaults to empty when callers don't care about user-configured paths.
     pub scanner_settings: scanner::ScannerSettings,
+    /// Dataset estimated costs are priced from first (`--cost-source`).
+    pub cost_source: pricing::CostSourcePreference,
+    /// Drop messages dated more than this many days after today instead of
+    /// clamping them to today (`--max-future-days`).
+    pub max_future_days: Option<u32>,
+    /// Keep every OpenCode and Claude Code message even when its dedup key
+    /// was already seen (`--no-dedup`), to compare raw totals against
+    /// deduplicated ones.
+    pub no_dedup: bool,
+    /// Threads for the scan and parse fan-out (`--threads`). `0` runs it on
+    /// rayon's global pool, one thread per core.
+    pub threads: usize,
+    /// Price from the recorded snapshot for this date, or the nearest
+    /// earlier one, instead of live or cached data (`--pricing-date`).
+    pub pricing_date: Option<chrono::NaiveDate>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -475,6 +769,10 @@ pub struct DataSummary {
     pub max_cost_in_single_day: f64,
     pub clients: Vec<String>,
     pub models: Vec<String>,
+    /// Active days in a row up to today (or yesterday, when today has no
+    /// usage yet); see [`calculate_streaks`].
+    pub current_streak: Streak,
+    pub longest_streak: Streak,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -503,11 +801,201 @@ pub struct ReportOptions {
     pub clients: Option<Vec<String>>,
     pub since: Option<String>,
     pub until: Option<String>,
+    /// Treat `until` as an exclusive upper bound (`date < until`) instead of
+    /// the default inclusive one, so back-to-back windows never share a day.
+    pub until_exclusive: bool,
     pub year: Option<String>,
     pub group_by: GroupBy,
     /// Persistent scanner config loaded from `~/.config/tokscale/settings.json`.
     /// Defaults to empty when callers don't care about user-configured paths.
     pub scanner_settings: scanner::ScannerSettings,
+    /// Drop model-report entries whose cost is below this USD amount. Applied
+    /// after aggregation; dropped rows still count toward report totals.
+    pub min_cost: Option<f64>,
+    /// Drop model-report entries whose token total is below this count.
+    /// Applied after aggregation; dropped rows still count toward totals.
+    pub min_tokens: Option<i64>,
+    /// Bucketing used for contribution-graph day intensities.
+    pub intensity_mode: IntensityMode,
+    /// Daily total the contribution-graph intensities are computed from.
+    pub intensity_basis: IntensityBasis,
+    /// Keep or drop messages by their `agent` tag.
+    pub agent_filter: AgentFilter,
+    /// Keep only messages from these projects (`--project`).
+    pub project_filter: ProjectFilter,
+    /// Keep or drop messages by model pattern (`--model`, `--exclude-model`).
+    pub model_filter: ModelFilter,
+    /// Attach per-client scan/parse timings to the report
+    /// (`--benchmark-detailed`).
+    pub client_timings: bool,
+    /// Attach the cost of each day to the models report (`--sparkline`).
+    pub daily_costs: bool,
+    /// Row order for the models report (`--order-by`, `--asc`/`--desc`).
+    pub model_order: ModelOrder,
+    /// Skip pricing entirely (`--no-pricing`): no fetch, no cache read, and
+    /// every message keeps the cost its parser reported (usually 0).
+    pub no_pricing: bool,
+    /// Keep zero-cost model-report rows (subscription or unpriced usage) even
+    /// below `min_cost`, and order them by tokens among themselves when the
+    /// report is ordered by cost (`--include-zero-cost`).
+    pub include_zero_cost: bool,
+    /// Fewest tokens a day needs to count toward the graph summary's
+    /// `active_days` (`--active-threshold`). `0` counts any day with usage.
+    pub active_threshold: i64,
+    /// Dataset estimated costs are priced from first (`--cost-source`).
+    pub cost_source: pricing::CostSourcePreference,
+    /// Drop messages dated more than this many days after today instead of
+    /// clamping them to today (`--max-future-days`).
+    pub max_future_days: Option<u32>,
+    /// Keep every OpenCode and Claude Code message even when its dedup key
+    /// was already seen (`--no-dedup`), to compare raw totals against
+    /// deduplicated ones.
+    pub no_dedup: bool,
+    /// Threads for the scan and parse fan-out (`--threads`). `0` runs it on
+    /// rayon's global pool, one thread per core.
+    pub threads: usize,
+    /// Price from the recorded snapshot for this date, or the nearest
+    /// earlier one, instead of live or cached data (`--pricing-date`).
+    pub pricing_date: Option<chrono::NaiveDate>,
+}
+
+/// Label that matches messages without an `agent` tag in an [`AgentFilter`].
+pub const NO_AGENT_LABEL: &str = "(none)";
+
+/// Selects messages by their `agent` value (`--agent` / `--exclude-agent`).
+///
+/// Names are compared case-insensitively; a message without an agent is
+/// matched as [`NO_AGENT_LABEL`]. An empty `include` list keeps every agent,
+/// and `exclude` always wins over `include`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl AgentFilter {
+    pub fn new<I, E>(include: I, exclude: E) -> Self
+    where
+        I: IntoIterator<Item = String>,
+        E: IntoIterator<Item = String>,
+    {
+        let normalize = |names: Vec<String>| -> Vec<String> {
+            names
+                .into_iter()
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect()
+        };
+        Self {
+            include: normalize(include.into_iter().collect()),
+            exclude: normalize(exclude.into_iter().collect()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn matches(&self, agent: Option<&str>) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let agent = agent
+            .map(|agent| agent.trim().to_lowercase())
+            .unwrap_or_else(|| NO_AGENT_LABEL.to_string());
+        let listed = |names: &[String]| names.contains(&agent);
+        (self.include.is_empty() || listed(&self.include)) && !listed(&self.exclude)
+    }
+}
+
+/// Project name for messages whose workspace can't be derived, both as the
+/// `--group-by project` row label and as a [`ProjectFilter`] value.
+pub const UNKNOWN_PROJECT_LABEL: &str = "(unknown)";
+
+/// Selects messages by project (`--project`). A message's project is its
+/// workspace: the label (e.g. `tokscale`) or the full workspace key both
+/// match, case-insensitively, and [`UNKNOWN_PROJECT_LABEL`] matches messages
+/// without one. An empty filter keeps everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectFilter {
+    pub include: Vec<String>,
+}
+
+impl ProjectFilter {
+    pub fn new<I>(include: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        Self {
+            include: include
+                .into_iter()
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty()
+    }
+
+    pub fn matches(&self, message: &UnifiedMessage) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let (_, key, label) = project_bucket(message);
+        let listed = |name: &str| self.include.contains(&name.to_lowercase());
+        listed(&label) || key.as_deref().is_some_and(listed)
+    }
+}
+
+/// Selects messages by model (`--model`, `--exclude-model`). Patterns are
+/// regexes matched against [`normalize_model_for_grouping`] of the message's
+/// model id. A message is kept when it matches any include pattern (or there
+/// are none) and then no exclude pattern. An empty filter keeps everything.
+#[derive(Debug, Clone, Default)]
+pub struct ModelFilter {
+    include: Vec<regex::Regex>,
+    exclude: Vec<regex::Regex>,
+}
+
+impl ModelFilter {
+    /// Compiles every pattern up front, so a bad one fails before any scan.
+    pub fn new<I, E>(include: I, exclude: E) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+        E: IntoIterator<Item = String>,
+    {
+        fn compile<P: IntoIterator<Item = String>>(
+            patterns: P,
+        ) -> Result<Vec<regex::Regex>, String> {
+            patterns
+                .into_iter()
+                .filter(|pattern| !pattern.trim().is_empty())
+                .map(|pattern| {
+                    regex::Regex::new(pattern.trim())
+                        .map_err(|e| format!("Invalid model pattern '{}': {}", pattern, e))
+                })
+                .collect()
+        }
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn matches(&self, message: &UnifiedMessage) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let model = normalize_model_for_grouping(&message.model_id);
+        (self.include.is_empty() || self.include.iter().any(|re| re.is_match(&model)))
+            && !self.exclude.iter().any(|re| re.is_match(&model))
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -518,17 +1006,67 @@ pub struct ModelUsage {
     pub workspace_label: Option<String>,
     pub session_id: Option<String>,
     pub model: String,
+    /// Provider ids after [`normalize_provider`], `", "`-joined when several
+    /// contribute to one row.
     pub provider: String,
+    /// The provider ids as the clients reported them, before normalization.
+    /// Kept for debugging spelling mismatches; not used for grouping.
+    pub raw_provider: String,
     pub input: i64,
     pub output: i64,
     pub cache_read: i64,
     pub cache_write: i64,
     pub reasoning: i64,
     pub message_count: i32,
+    /// Distinct `client:session_id` pairs that contributed to this row.
+    pub session_count: i32,
+    /// Earliest `YYYY-MM-DD` message date in this row.
+    pub first_date: String,
+    /// Latest `YYYY-MM-DD` message date in this row.
+    pub last_date: String,
     pub cost: f64,
+    /// The pricing dataset behind this row's cost (`LiteLLM`, `OpenRouter`,
+    /// `Cursor`, `Custom`, ...), `client` when the client reported the cost
+    /// itself, `none` when no price matched, or `mixed` when no single
+    /// source priced most of the row's messages. `None` when the report ran
+    /// without pricing.
+    pub pricing_source: Option<String>,
+    /// Client-recorded vs estimated cost for the messages whose client
+    /// recorded its own cost. `None` when no message in the row did.
+    pub cost_comparison: Option<CostComparison>,
+    /// What cache reads saved over paying the input rate for the same
+    /// tokens: `cache_read * (input_rate - cache_read_rate)`. Zero when the
+    /// report ran without pricing.
+    pub estimated_savings: f64,
+    /// Summed request sizes of the row's messages that recorded one. `None`
+    /// when no message did, so clients without size data don't read as 0.
+    pub request_bytes: Option<i64>,
+    /// Summed response sizes, like `request_bytes`.
+    pub response_bytes: Option<i64>,
     pub performance: ModelPerformance,
 }
 
+/// What a client said a row's messages cost next to what tokscale's pricing
+/// estimated for the same messages. Only covers messages that carried a
+/// client cost (Amp credits, Cursor's usage CSV, ...), which reports
+/// otherwise replace with the estimate.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct CostComparison {
+    pub client_cost: f64,
+    /// Zero for messages no price matched.
+    pub estimated_cost: f64,
+    pub message_count: i32,
+}
+
+impl CostComparison {
+    /// How far the estimate is from the client cost, in percent of the
+    /// client cost. `None` when the client recorded nothing to compare to.
+    pub fn percent_difference(&self) -> Option<f64> {
+        (self.client_cost > 0.0)
+            .then(|| (self.estimated_cost - self.client_cost) / self.client_cost * 100.0)
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct MonthlyUsage {
     pub month: String,
@@ -537,8 +1075,13 @@ pub struct MonthlyUsage {
     pub output: i64,
     pub cache_read: i64,
     pub cache_write: i64,
+    pub reasoning: i64,
     pub message_count: i32,
     pub cost: f64,
+    /// Distinct days in the month with at least one message.
+    pub active_days: i32,
+    /// `cost / active_days`; zero for a month with no active days.
+    pub avg_cost_per_active_day: f64,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -548,9 +1091,97 @@ pub struct ModelReport {
     pub total_output: i64,
     pub total_cache_read: i64,
     pub total_cache_write: i64,
+    pub total_reasoning: i64,
     pub total_messages: i32,
+    /// Distinct sessions across the whole report. Not the sum of per-row
+    /// `session_count`: one session that used two models counts once.
+    pub total_sessions: i32,
     pub total_cost: f64,
+    /// Sum of every row's [`ModelUsage::estimated_savings`], omitted rows
+    /// included.
+    pub total_estimated_savings: f64,
     pub processing_time_ms: u32,
+    /// Aggregate of the entries removed by `min_cost` / `min_tokens`. `None`
+    /// when no threshold was set or nothing fell below it.
+    pub omitted: Option<OmittedUsage>,
+    /// Per-client scan/parse timings; only set when
+    /// [`ReportOptions::client_timings`] was requested.
+    pub client_timings: Option<Vec<ClientTiming>>,
+    /// Cost of every day from the first to the last one with usage, oldest
+    /// first; days without usage read as zero. Only set when
+    /// [`ReportOptions::daily_costs`] was requested.
+    pub daily_costs: Option<Vec<DailyCost>>,
+    /// Model ids that had token usage but matched no pricing data, so their
+    /// cost reads as zero. Sorted; empty when pricing was skipped.
+    pub unpriced_models: Vec<String>,
+    /// Pricing was wanted but neither a fetch nor any cached dataset could be
+    /// loaded, so `unpriced_models` lists every model no client priced
+    /// itself.
+    pub pricing_unavailable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct DailyCost {
+    pub date: String,
+    pub cost: f64,
+}
+
+/// Cost per message date, with the days between active ones filled in at
+/// zero.
+fn daily_cost_series(day_costs: BTreeMap<String, f64>) -> Vec<DailyCost> {
+    let parse = |date: &str| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    let mut series: Vec<DailyCost> = Vec::with_capacity(day_costs.len());
+    for (date, cost) in day_costs {
+        let gap_start = series
+            .last()
+            .and_then(|previous| parse(&previous.date))
+            .and_then(|previous| previous.succ_opt());
+        if let (Some(mut missing), Some(current)) = (gap_start, parse(&date)) {
+            while missing < current {
+                series.push(DailyCost {
+                    date: missing.format("%Y-%m-%d").to_string(),
+                    cost: 0.0,
+                });
+                missing = match missing.succ_opt() {
+                    Some(next) => next,
+                    None => break,
+                };
+            }
+        }
+        series.push(DailyCost { date, cost });
+    }
+    series
+}
+
+/// Time one enabled client spent in a local parse. Scanning walks every
+/// client's roots in parallel, so `scan_ms` values overlap; `parse_ms` values
+/// are sequential and add up to the parse stage.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientTiming {
+    pub client: String,
+    pub scan_ms: f64,
+    pub parse_ms: f64,
+    pub file_count: usize,
+    /// Messages dropped because an earlier message of this client (from the
+    /// same or another source) already carried their dedup key.
+    pub duplicates_removed: usize,
+}
+
+/// Rollup of model-report entries that fell below a `min_cost` /
+/// `min_tokens` threshold. The report totals already include these values;
+/// this only describes what was hidden from `entries`.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OmittedUsage {
+    pub entries: usize,
+    pub input: i64,
+    pub output: i64,
+    pub cache_read: i64,
+    pub cache_write: i64,
+    pub reasoning: i64,
+    pub message_count: i32,
+    pub cost: f64,
 }
 
 const UNKNOWN_WORKSPACE_LABEL: &str = "Unknown workspace";
@@ -561,6 +1192,8 @@ pub struct MonthlyReport {
     pub entries: Vec<MonthlyUsage>,
     pub total_cost: f64,
     pub processing_time_ms: u32,
+    /// Same as [`ModelReport::unpriced_models`].
+    pub unpriced_models: Vec<String>,
 }
 
 /// Hourly usage entry for a single hour slot (e.g. "2026-03-23 14:00")
@@ -587,6 +1220,109 @@ pub struct HourlyReport {
     pub processing_time_ms: u32,
 }
 
+/// Usage summed over every day for one hour of the day (0-23, local time).
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HourOfDayUsage {
+    pub hour: u32,
+    pub input: i64,
+    pub output: i64,
+    pub cache_read: i64,
+    pub cache_write: i64,
+    pub reasoning: i64,
+    pub message_count: i32,
+    pub cost: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HourOfDayReport {
+    /// Always 24 entries, hour 0 first; idle hours are all zero.
+    pub entries: Vec<HourOfDayUsage>,
+    pub total_cost: f64,
+    /// Messages without a timestamp, which have no hour to land in.
+    pub untimed_messages: i32,
+    pub processing_time_ms: u32,
+}
+
+/// One conversation (`client:session_id`) in the top-sessions leaderboard.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionUsage {
+    pub client: String,
+    pub session_id: String,
+    pub title: Option<String>,
+    pub workspace_label: Option<String>,
+    /// Model that accounted for the most cost in the session (most tokens
+    /// when nothing was priced).
+    pub dominant_model: String,
+    pub input: i64,
+    pub output: i64,
+    pub cache_read: i64,
+    pub cache_write: i64,
+    pub reasoning: i64,
+    pub total_tokens: i64,
+    pub message_count: i32,
+    pub first_date: String,
+    pub last_date: String,
+    pub cost: f64,
+    /// Client-reported totals next to tokscale's figures. `None` when no
+    /// message in the session carried a reported total.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reconciliation: Option<SessionReconciliation>,
+}
+
+/// What a client reported for a session next to what tokscale counted for
+/// the same messages, for `top-sessions --reconcile`. Each side only covers
+/// messages that carried the reported figure, so partial coverage does not
+/// read as a divergence.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionReconciliation {
+    /// Summed client token totals (OpenClaw's `totalTokens`).
+    pub reported_tokens: Option<i64>,
+    /// tokscale's token total for the messages behind `reported_tokens`.
+    pub counted_tokens: i64,
+    /// Summed client-recorded costs that pricing replaced with an estimate.
+    pub reported_cost: Option<f64>,
+    /// tokscale's estimate for the messages behind `reported_cost`.
+    pub estimated_cost: f64,
+}
+
+impl SessionReconciliation {
+    /// How far the counted tokens are from the reported ones, in percent of
+    /// the reported total.
+    pub fn token_difference_pct(&self) -> Option<f64> {
+        self.reported_tokens
+            .filter(|reported| *reported > 0)
+            .map(|reported| (self.counted_tokens - reported) as f64 / reported as f64 * 100.0)
+    }
+
+    /// How far the estimate is from the reported cost, in percent of the
+    /// reported cost.
+    pub fn cost_difference_pct(&self) -> Option<f64> {
+        self.reported_cost
+            .filter(|reported| *reported > 0.0)
+            .map(|reported| (self.estimated_cost - reported) / reported * 100.0)
+    }
+
+    /// True when tokens or cost are off by more than `threshold_pct`.
+    pub fn diverges(&self, threshold_pct: f64) -> bool {
+        [self.token_difference_pct(), self.cost_difference_pct()]
+            .into_iter()
+            .flatten()
+            .any(|pct| pct.abs() > threshold_pct)
+    }
+}
+
+/// Every session in range, most expensive first.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopSessionsReport {
+    pub entries: Vec<SessionUsage>,
+    pub total_cost: f64,
+    pub processing_time_ms: u32,
+}
+
 pub fn get_home_dir_string(home_dir_option: &Option<String>) -> Result<String, String> {
     home_dir_option
         .clone()
@@ -609,6 +1345,7 @@ fn parse_all_messages_with_pricing(
         pricing,
         true,
         &scanner::ScannerSettings::default(),
+        false,
     )
 }
 
@@ -618,7 +1355,30 @@ fn parse_all_messages_with_pricing_with_env_strategy(
     pricing: Option<&pricing::PricingService>,
     use_env_roots: bool,
     scanner_settings: &scanner::ScannerSettings,
+    no_dedup: bool,
 ) -> Vec<UnifiedMessage> {
+    parse_all_messages_with_timings(
+        home_dir,
+        clients,
+        pricing,
+        use_env_roots,
+        scanner_settings,
+        no_dedup,
+    )
+    .0
+}
+
+/// [`parse_all_messages_with_pricing_with_env_strategy`] plus the scan and
+/// parse time spent on each enabled client. Runs on the current rayon pool;
+/// the scan entry points wrap it in [`scan_pool::install`].
+fn parse_all_messages_with_timings(
+    home_dir: &str,
+    clients: &[String],
+    pricing: Option<&pricing::PricingService>,
+    use_env_roots: bool,
+    scanner_settings: &scanner::ScannerSettings,
+    no_dedup: bool,
+) -> (Vec<UnifiedMessage>, Vec<ClientTiming>) {
     #[derive(Debug)]
     struct CachedParseOutcome {
         messages: Vec<UnifiedMessage>,
@@ -626,20 +1386,12 @@ fn parse_all_messages_with_pricing_with_env_strategy(
         invalidate_cache: bool,
     }
 
-    fn apply_pricing_to_messages(
-        messages: &mut [UnifiedMessage],
-        pricing: Option<&pricing::PricingService>,
-    ) {
-        for message in messages {
-            message.refresh_derived_fields();
-            apply_pricing_if_available(message, pricing);
-        }
-    }
-
     fn cached_messages(
         cached: &message_cache::CachedSourceEntry,
         pricing: Option<&pricing::PricingService>,
     ) -> Vec<UnifiedMessage> {
+        #[cfg(test)]
+        message_cache::record_source_cache_hit();
         let mut messages = cached.messages.clone();
         apply_pricing_to_messages(&mut messages, pricing);
         messages
@@ -720,6 +1472,11 @@ fn parse_all_messages_with_pricing_with_env_strategy(
         state: sessions::codex::CodexParseState,
         fallback_timestamp_indices: Vec<usize>,
     ) -> Option<message_cache::CachedSourceEntry> {
+        // Offsets into a gzip log count decompressed bytes, so there is no
+        // prefix to resume from.
+        if sessions::utils::is_gzip_path(path) {
+            return None;
+        }
         let fingerprint = message_cache::SourceFingerprint::from_path(path)?;
         if fingerprint.size != consumed_offset {
             return None;
@@ -948,6 +1705,8 @@ fn parse_all_messages_with_pricing_with_env_strategy(
 
             if cached.fingerprint == fingerprint {
                 if message_cache::codex_cache_entry_matches_fingerprint(cached, &fingerprint) {
+                    #[cfg(test)]
+                    message_cache::record_source_cache_hit();
                     return CachedParseOutcome {
                         messages: finalize_codex_messages(
                             cached.messages.clone(),
@@ -1031,10 +1790,11 @@ fn parse_all_messages_with_pricing_with_env_strategy(
     let include_synthetic = include_all || clients.iter().any(|c| c == "synthetic");
     let include_devin_cli = include_synthetic || clients.iter().any(|c| c == "devin-cli");
     let include_devin_desktop = include_synthetic || clients.iter().any(|c| c == "devin-desktop");
+    let mut parse_clock = ParseClock::start();
 
     // Parse OpenCode: prefer SQLite, collapse forked SQLite history there, then
     // suppress legacy JSON overlap by message identity.
-    let mut opencode_seen: HashSet<String> = HashSet::new();
+    let mut opencode_seen = SeenKeys::unless_no_dedup(no_dedup);
 
     for db_path in &scan_result.opencode_dbs {
         let CachedParseOutcome {
@@ -1057,7 +1817,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             message
                 .dedup_key
                 .as_ref()
-                .is_none_or(|key| opencode_seen.insert(key.clone()))
+                .is_none_or(|key| opencode_seen.insert(key))
         }));
 
         if let Some(entry) = cache_entry {
@@ -1087,15 +1847,16 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             message
                 .dedup_key
                 .as_ref()
-                .is_none_or(|key| opencode_seen.insert(key.clone()))
+                .is_none_or(|key| opencode_seen.insert(key))
         }));
         if let Some(entry) = outcome.cache_entry {
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap_deduped(ClientId::OpenCode, &opencode_seen);
 
     // Parse MiMo Code: SQLite database(s)
-    let mut micode_seen: HashSet<String> = HashSet::new();
+    let mut micode_seen = SeenKeys::default();
 
     for db_path in &scan_result.micode_dbs {
         // Pass `None` so the loader does not reprice: MiMo Code carries an
@@ -1127,7 +1888,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
                     message
                         .dedup_key
                         .as_ref()
-                        .is_none_or(|key| micode_seen.insert(key.clone()))
+                        .is_none_or(|key| micode_seen.insert(key))
                 }),
         );
 
@@ -1135,6 +1896,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap_deduped(ClientId::MiMoCode, &micode_seen);
 
     let claude_home = PathBuf::from(home_dir);
     let claude_outcomes: Vec<CachedParseOutcome> = scan_result
@@ -1168,13 +1930,14 @@ fn parse_all_messages_with_pricing_with_env_strategy(
         }
     }
 
-    let mut seen_keys: HashSet<String> = HashSet::new();
+    let mut seen_keys = SeenKeys::unless_no_dedup(no_dedup);
     let claude_messages: Vec<UnifiedMessage> = claude_messages_raw
         .into_iter()
-        .filter(|(key, _)| key.is_empty() || seen_keys.insert(key.clone()))
+        .filter(|(key, _)| key.is_empty() || seen_keys.insert(key))
         .map(|(_, msg)| msg)
         .collect();
     all_messages.extend(claude_messages);
+    parse_clock.lap_deduped(ClientId::Claude, &seen_keys);
 
     let codex_outcomes: Vec<(PathBuf, CachedParseOutcome)> = scan_result
         .get(ClientId::Codex)
@@ -1186,7 +1949,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             )
         })
         .collect();
-    let mut codex_seen: HashSet<String> = HashSet::new();
+    let mut codex_seen = SeenKeys::default();
     for (path, outcome) in codex_outcomes {
         all_messages.extend(
             outcome
@@ -1203,6 +1966,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             );
         }
     }
+    parse_clock.lap_deduped(ClientId::Codex, &codex_seen);
 
     let copilot_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Copilot)
@@ -1273,6 +2037,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
                 }),
         );
     }
+    parse_clock.lap(ClientId::Copilot);
 
     let gemini_outcomes: Vec<(PathBuf, CachedParseOutcome)> = scan_result
         .get(ClientId::Gemini)
@@ -1303,26 +2068,36 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             );
         }
     }
+    parse_clock.lap(ClientId::Gemini);
 
-    let cursor_outcomes: Vec<CachedParseOutcome> = scan_result
+    let cursor_outcomes: Vec<(CachedParseOutcome, Option<String>)> = scan_result
         .get(ClientId::Cursor)
         .par_iter()
         .map(|path| {
-            load_or_parse_source(
+            let outcome = load_or_parse_source(
                 message_cache::CacheIdentity::for_client(ClientId::Cursor),
                 path,
                 &source_cache,
                 pricing,
                 sessions::cursor::parse_cursor_file,
-            )
+            );
+            // Resolved outside the cached parse: relabeling an account does
+            // not touch the CSV, so a cached label would go stale.
+            (outcome, sessions::cursor::cursor_account_label(path))
         })
         .collect();
-    for outcome in cursor_outcomes {
-        all_messages.extend(outcome.messages);
+    for (outcome, account_label) in cursor_outcomes {
+        all_messages.extend(outcome.messages.into_iter().map(|mut msg| {
+            if msg.agent.is_none() {
+                msg.agent = account_label.clone();
+            }
+            msg
+        }));
         if let Some(entry) = outcome.cache_entry {
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Cursor);
 
     let warp_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Warp)
@@ -1343,6 +2118,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Warp);
 
     let grok_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Grok)
@@ -1367,6 +2143,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Grok);
 
     let jcode_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Jcode)
@@ -1382,7 +2159,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             )
         })
         .collect();
-    let mut jcode_seen: HashSet<String> = HashSet::new();
+    let mut jcode_seen = SeenKeys::default();
     for outcome in jcode_outcomes {
         all_messages.extend(
             outcome
@@ -1394,6 +2171,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap_deduped(ClientId::Jcode, &jcode_seen);
 
     let amp_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Amp)
@@ -1414,6 +2192,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Amp);
 
     let codebuff_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Codebuff)
@@ -1434,6 +2213,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Codebuff);
 
     let droid_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Droid)
@@ -1455,6 +2235,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Droid);
 
     let openclaw_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::OpenClaw)
@@ -1475,6 +2256,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::OpenClaw);
 
     let pi_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Pi)
@@ -1495,6 +2277,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Pi);
 
     // Command Code does not persist token usage or cost locally, so tokens are
     // estimated and priced. The model id comes from ~/.commandcode/config.json
@@ -1516,6 +2299,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
         })
         .collect();
     all_messages.extend(commandcode_messages);
+    parse_clock.lap(ClientId::CommandCode);
 
     // gjc (gajae-code) JSONL sessions. Binding note N1: this cached cluster
     // MUST obtain messages via the non-repricing parser and apply the A1
@@ -1525,7 +2309,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
     // and overwrite gjc's authoritative embedded cost, silently downgrading to
     // A2 on the dominant cached path. Message-level dedup via
     // should_keep_deduped_message collapses depth-1/depth-2 replays.
-    let mut gjc_seen: HashSet<String> = HashSet::new();
+    let mut gjc_seen = SeenKeys::default();
     let gjc_messages: Vec<UnifiedMessage> = scan_result
         .get(ClientId::Gjc)
         .par_iter()
@@ -1546,12 +2330,13 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             .into_iter()
             .filter(|message| should_keep_deduped_message(&mut gjc_seen, message)),
     );
+    parse_clock.lap_deduped(ClientId::Gjc, &gjc_seen);
 
     // Junie events carry authoritative per-call `modelUsage.cost` values.
     // Keep this off the generic source cache because cached_messages()
     // reprices every message unconditionally; only fill cost from pricing
     // when Junie emitted no usable cost.
-    let mut junie_seen: HashSet<String> = HashSet::new();
+    let mut junie_seen = SeenKeys::default();
     let junie_messages: Vec<UnifiedMessage> = scan_result
         .get(ClientId::Junie)
         .par_iter()
@@ -1572,6 +2357,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             .into_iter()
             .filter(|message| should_keep_deduped_message(&mut junie_seen, message)),
     );
+    parse_clock.lap_deduped(ClientId::Junie, &junie_seen);
 
     // ZCode v2 CLI stores authoritative model usage in SQLite.
     if let Some(db_path) = &scan_result.zcode_db {
@@ -1608,6 +2394,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
         })
         .collect();
     all_messages.extend(zcode_messages);
+    parse_clock.lap(ClientId::Zcode);
 
     let kimi_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Kimi)
@@ -1635,6 +2422,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Kimi);
 
     // Parse Qwen files
     let qwen_outcomes: Vec<CachedParseOutcome> = scan_result
@@ -1656,6 +2444,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Qwen);
 
     let roocode_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::RooCode)
@@ -1677,6 +2466,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::RooCode);
 
     let kilocode_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::KiloCode)
@@ -1698,6 +2488,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::KiloCode);
 
     let cline_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Cline)
@@ -1719,6 +2510,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Cline);
 
     let mux_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Mux)
@@ -1739,6 +2531,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Mux);
 
     // Kilo CLI: SQLite database
     if let Some(db_path) = &scan_result.kilo_db {
@@ -1751,8 +2544,9 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             .collect();
         all_messages.extend(kilo_messages);
     }
+    parse_clock.lap(ClientId::Kilo);
 
-    let mut hermes_seen: HashSet<String> = HashSet::new();
+    let mut hermes_seen = SeenKeys::default();
     for db_path in scan_result.hermes_db_paths() {
         let hermes_messages = parse_hermes_sqlite_with_pricing(&db_path, pricing);
         all_messages.extend(
@@ -1761,6 +2555,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
                 .filter(|message| should_keep_deduped_message(&mut hermes_seen, message)),
         );
     }
+    parse_clock.lap_deduped(ClientId::Hermes, &hermes_seen);
 
     if let Some(db_path) = &scan_result.goose_db {
         let goose_messages: Vec<UnifiedMessage> = sessions::goose::parse_goose_sqlite(db_path)
@@ -1772,11 +2567,27 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             .collect();
         all_messages.extend(goose_messages);
     }
+    parse_clock.lap(ClientId::Goose);
+
+    // Aider history files sit next to each project; one directory can hold
+    // both the markdown transcript and the structured log, so they are parsed
+    // together and only one source is read per directory.
+    let aider_messages: Vec<UnifiedMessage> =
+        sessions::aider::parse_aider_sources(scan_result.get(ClientId::Aider))
+            .into_iter()
+            .map(|mut msg| {
+                apply_pricing_if_available(&mut msg, pricing);
+                msg
+            })
+            .collect();
+    all_messages.extend(aider_messages);
+    parse_clock.lap(ClientId::Aider);
 
     // Devin CLI stores authoritative model usage in SQLite. Multiple paths can
     // be configured through scanner extra roots, so parse and dedupe all of
     // them instead of silently ignoring non-default databases.
     let mut devin_cli_session_ids: HashSet<String> = HashSet::new();
+    let mut devin_cli_seen = SeenKeys::default();
     if include_devin_cli {
         let devin_cli_outcomes: Vec<CachedParseOutcome> = scan_result
             .devin_dbs
@@ -1791,7 +2602,6 @@ fn parse_all_messages_with_pricing_with_env_strategy(
                 )
             })
             .collect();
-        let mut devin_cli_seen = HashSet::new();
         for outcome in devin_cli_outcomes {
             for message in outcome
                 .messages
@@ -1806,6 +2616,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             }
         }
     }
+    parse_clock.lap_deduped(ClientId::DevinCli, &devin_cli_seen);
 
     for db_path in scan_result.zed_db_paths() {
         let outcome = load_or_parse_sqlite_source(
@@ -1820,6 +2631,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap(ClientId::Zed);
 
     let kiro_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::Kiro)
@@ -1863,6 +2675,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             .collect();
         all_messages.extend(kiro_db_messages);
     }
+    parse_clock.lap(ClientId::Kiro);
 
     for source in &scan_result.crush_dbs {
         let crush_messages: Vec<UnifiedMessage> =
@@ -1876,6 +2689,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
                 .collect();
         all_messages.extend(crush_messages);
     }
+    parse_clock.lap(ClientId::Crush);
 
     let antigravity_messages: Vec<UnifiedMessage> = scan_result
         .get(ClientId::Antigravity)
@@ -1891,6 +2705,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
         })
         .collect();
     all_messages.extend(antigravity_messages);
+    parse_clock.lap(ClientId::Antigravity);
 
     let antigravity_cli_messages: Vec<UnifiedMessage> = scan_result
         .get(ClientId::AntigravityCli)
@@ -1906,6 +2721,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
         })
         .collect();
     all_messages.extend(antigravity_cli_messages);
+    parse_clock.lap(ClientId::AntigravityCli);
 
     // Trae API dump uses exact dollar_float totals, so pricing lookup is not needed.
     let trae_messages: Vec<UnifiedMessage> = scan_result
@@ -1915,6 +2731,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
         .collect();
     let deduped_trae_messages = dedupe_latest_trae_messages(trae_messages);
     all_messages.extend(deduped_trae_messages);
+    parse_clock.lap(ClientId::Trae);
 
     let codebuddy_outcomes: Vec<CachedParseOutcome> = scan_result
         .get(ClientId::CodeBuddy)
@@ -1929,18 +2746,19 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             )
         })
         .collect();
-    let mut codebuddy_seen: HashSet<String> = HashSet::new();
+    let mut codebuddy_seen = SeenKeys::default();
     for outcome in codebuddy_outcomes {
         all_messages.extend(outcome.messages.into_iter().filter(|message| {
             message
                 .dedup_key
                 .as_ref()
-                .is_none_or(|key| codebuddy_seen.insert(key.clone()))
+                .is_none_or(|key| codebuddy_seen.insert(key))
         }));
         if let Some(entry) = outcome.cache_entry {
             source_cache.insert(entry);
         }
     }
+    parse_clock.lap_deduped(ClientId::CodeBuddy, &codebuddy_seen);
 
     // Devin Desktop ACP file names are unrelated to the CLI database session
     // ids. Resolve their session titles through the database so the CLI can
@@ -2006,6 +2824,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
             }
         }
     }
+    parse_clock.lap(ClientId::DevinDesktop);
 
     let (workbuddy_detailed_paths, workbuddy_fallback_paths) =
         partition_workbuddy_paths(scan_result.get(ClientId::WorkBuddy));
@@ -2051,6 +2870,7 @@ fn parse_all_messages_with_pricing_with_env_strategy(
         workbuddy_detailed_messages,
         workbuddy_fallback_messages,
     ));
+    parse_clock.lap(ClientId::WorkBuddy);
 
     if include_synthetic {
         if let Some(db_path) = &scan_result.synthetic_db {
@@ -2089,7 +2909,61 @@ fn parse_all_messages_with_pricing_with_env_strategy(
 
     source_cache.save_if_dirty();
 
-    all_messages
+    let clamped = sessions::take_clamped_token_count();
+    if clamped > 0 {
+        tracing::debug!(clamped, "clamped negative token counts to zero");
+    }
+    let sanitized = pricing::lookup::take_sanitized_cost_count();
+    if sanitized > 0 {
+        tracing::debug!(sanitized, "skipped NaN or infinite pricing rates");
+    }
+
+    let timings = parse_clock.into_timings(&scan_result);
+    (all_messages, timings)
+}
+
+/// Splits the sequential per-client parse stage into per-client wall times:
+/// each lap is charged to the client whose block just finished.
+struct ParseClock {
+    times: [std::time::Duration; ClientId::COUNT],
+    duplicates: [usize; ClientId::COUNT],
+    last: Instant,
+}
+
+impl ParseClock {
+    fn start() -> Self {
+        Self {
+            times: [std::time::Duration::ZERO; ClientId::COUNT],
+            duplicates: [0; ClientId::COUNT],
+            last: Instant::now(),
+        }
+    }
+
+    fn lap(&mut self, client: ClientId) {
+        let now = Instant::now();
+        self.times[client as usize] += now - self.last;
+        self.last = now;
+    }
+
+    /// [`lap`](Self::lap) for a client whose messages were deduped by `seen`.
+    fn lap_deduped(&mut self, client: ClientId, seen: &SeenKeys) {
+        self.lap(client);
+        self.duplicates[client as usize] += seen.duplicates;
+    }
+
+    fn into_timings(self, scan_result: &scanner::ScanResult) -> Vec<ClientTiming> {
+        scan_result
+            .enabled
+            .iter()
+            .map(|&client| ClientTiming {
+                client: client.as_str().to_string(),
+                scan_ms: scan_result.scan_times[client as usize].as_secs_f64() * 1000.0,
+                parse_ms: self.times[client as usize].as_secs_f64() * 1000.0,
+                file_count: scan_result.source_count(client),
+                duplicates_removed: self.duplicates[client as usize],
+            })
+            .collect()
+    }
 }
 
 fn dedupe_latest_trae_messages(mut messages: Vec<UnifiedMessage>) -> Vec<UnifiedMessage> {
@@ -2151,7 +3025,7 @@ fn merge_workbuddy_messages(
         .filter(|message| !message.session_id.is_empty())
         .map(|message| message.session_id.clone())
         .collect();
-    let mut seen: HashSet<String> = HashSet::new();
+    let mut seen = SeenKeys::default();
     let mut merged: Vec<UnifiedMessage> = detailed_messages
         .into_iter()
         .filter(|message| should_keep_deduped_message(&mut seen, message))
@@ -2164,12 +3038,25 @@ fn merge_workbuddy_messages(
     merged
 }
 
+/// Whether `date` falls on or before `until`, or strictly before it when
+/// `exclusive` is set.
+fn is_within_until(date: &str, until: &str, exclusive: bool) -> bool {
+    if exclusive {
+        date < until
+    } else {
+        date <= until
+    }
+}
+
 fn filter_unified_messages(
     messages: Vec<UnifiedMessage>,
     options: &LocalParseOptions,
 ) -> Vec<UnifiedMessage> {
     let mut filtered = messages;
 
+    let future_dates = future_dates::FutureDatePolicy::new(options.max_future_days);
+    filtered.retain_mut(|m| future_dates.admit(&mut m.date, &mut m.timestamp));
+
     if let Some(year) = &options.year {
         let year_prefix = format!("{}-", year);
         filtered.retain(|m| m.date.starts_with(&year_prefix));
@@ -2180,7 +3067,7 @@ fn filter_unified_messages(
     }
 
     if let Some(until) = &options.until {
-        filtered.retain(|m| m.date.as_str() <= until.as_str());
+        filtered.retain(|m| is_within_until(&m.date, until, options.until_exclusive));
     }
 
     filtered
@@ -2203,42 +3090,290 @@ fn workspace_bucket(msg: &UnifiedMessage) -> (String, Option<String>, String) {
     }
 }
 
+/// [`workspace_bucket`] with the project spelling of "unknown".
+fn project_bucket(msg: &UnifiedMessage) -> (String, Option<String>, String) {
+    let (group_key, key, label) = workspace_bucket(msg);
+    if key.is_none() {
+        return (group_key, None, UNKNOWN_PROJECT_LABEL.to_string());
+    }
+    (group_key, key, label)
+}
+
+/// What each distinct `(model_id, provider_id)` pair resolves to in the
+/// pricing data. Built once per report so rows can say where their cost came
+/// from without a lookup per message.
+type PricingSources = HashMap<(String, String), ResolvedPricing>;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ResolvedPricing {
+    /// The pricing dataset, `None` where nothing matched.
+    source: Option<String>,
+    /// See [`pricing::PricingService::cache_read_saving_per_token`]; zero
+    /// where nothing matched.
+    cache_read_saving: f64,
+}
+
+/// What a pair missing from a [`PricingSources`] resolves to.
+const UNRESOLVED_PRICING: ResolvedPricing = ResolvedPricing {
+    source: None,
+    cache_read_saving: 0.0,
+};
+
+fn resolve_pricing_sources(
+    messages: &[UnifiedMessage],
+    pricing: &pricing::PricingService,
+) -> PricingSources {
+    let mut sources = PricingSources::new();
+    for msg in messages {
+        resolve_pricing_source(&mut sources, pricing, msg);
+    }
+    sources
+}
+
+/// `msg`'s entry in `sources`, looked up in `pricing` the first time its
+/// model and provider are seen.
+fn resolve_pricing_source<'a>(
+    sources: &'a mut PricingSources,
+    pricing: &pricing::PricingService,
+    msg: &UnifiedMessage,
+) -> &'a ResolvedPricing {
+    sources
+        .entry((msg.model_id.clone(), msg.provider_id.clone()))
+        .or_insert_with(|| ResolvedPricing {
+            source: pricing.pricing_source(&msg.model_id, Some(&msg.provider_id)),
+            cache_read_saving: pricing
+                .cache_read_saving_per_token(&msg.model_id, Some(&msg.provider_id))
+                .unwrap_or(0.0),
+        })
+}
+
+fn lookup_pricing_source<'a>(
+    sources: &'a PricingSources,
+    msg: &UnifiedMessage,
+) -> &'a ResolvedPricing {
+    sources
+        .get(&(msg.model_id.clone(), msg.provider_id.clone()))
+        .unwrap_or(&UNRESOLVED_PRICING)
+}
+
+/// Where a report gets each message's [`ResolvedPricing`] from.
+enum ReportPricing<'a> {
+    /// No pricing data: it failed to load, or the report skipped it.
+    Unavailable,
+    /// Resolved up front, as a [`ReportBuilder`] does for its messages.
+    Resolved(&'a PricingSources),
+    /// Resolved as the message stream is folded, the first time each model
+    /// is seen.
+    Lazy(&'a pricing::PricingService, PricingSources),
+}
+
+impl<'a> ReportPricing<'a> {
+    fn lazy(pricing: Option<&'a pricing::PricingService>) -> Self {
+        pricing.map_or(Self::Unavailable, |pricing| {
+            Self::Lazy(pricing, PricingSources::new())
+        })
+    }
+
+    fn resolved(sources: Option<&'a PricingSources>) -> Self {
+        sources.map_or(Self::Unavailable, Self::Resolved)
+    }
+
+    fn is_available(&self) -> bool {
+        !matches!(self, Self::Unavailable)
+    }
+
+    /// `None` when pricing is unavailable.
+    fn resolve(&mut self, msg: &UnifiedMessage) -> Option<&ResolvedPricing> {
+        match self {
+            Self::Unavailable => None,
+            Self::Resolved(sources) => Some(lookup_pricing_source(sources, msg)),
+            Self::Lazy(pricing, sources) => Some(resolve_pricing_source(sources, pricing, msg)),
+        }
+    }
+}
+
+/// What `msg`'s cache reads saved over paying the input rate for them.
+fn cache_read_savings(msg: &UnifiedMessage, resolved: &ResolvedPricing) -> f64 {
+    let savings = msg.tokens.cache_read.max(0) as f64 * resolved.cache_read_saving;
+    if savings.is_finite() {
+        savings
+    } else {
+        0.0
+    }
+}
+
+fn message_pricing_source<'a>(msg: &UnifiedMessage, resolved: &'a ResolvedPricing) -> &'a str {
+    if msg.has_authoritative_cost() {
+        return "client";
+    }
+    resolved.source.as_deref().unwrap_or("none")
+}
+
+/// Providers billed by subscription rather than per token. Their models are
+/// expected to have no pricing data, so they never count as unpriced.
+const SUBSCRIPTION_PROVIDERS: &[&str] = &["github-copilot"];
+
+/// Whether `msg` has token usage that no pricing dataset matched and that no
+/// client priced itself, so its zero cost means "unknown" rather than "free".
+fn is_unpriced(msg: &UnifiedMessage, resolved: &ResolvedPricing) -> bool {
+    msg.cost == 0.0
+        && positive_token_total(&msg.tokens) > 0
+        && message_pricing_source(msg, resolved) == "none"
+        && !SUBSCRIPTION_PROVIDERS
+            .contains(&provider_identity::normalize_provider(&msg.provider_id).as_str())
+}
+
+/// Distinct model ids of the [`is_unpriced`] messages, sorted.
+#[cfg(test)]
+fn unpriced_model_ids(messages: &[UnifiedMessage], sources: &PricingSources) -> Vec<String> {
+    let models: BTreeSet<&str> = messages
+        .iter()
+        .filter(|msg| is_unpriced(msg, lookup_pricing_source(sources, msg)))
+        .map(|msg| msg.model_id.as_str())
+        .collect();
+    models.into_iter().map(str::to_string).collect()
+}
+
+/// The source that priced more than half of a row's messages, else `mixed`.
+fn dominant_pricing_source(counts: &HashMap<String, i64>) -> String {
+    let total: i64 = counts.values().sum();
+    counts
+        .iter()
+        .filter(|(_, count)| **count * 2 > total)
+        .map(|(source, _)| source.to_string())
+        .next()
+        .unwrap_or_else(|| "mixed".to_string())
+}
+
+/// Sum of the sizes that are known, or `None` when none are.
+pub fn sum_bytes(sizes: impl IntoIterator<Item = Option<i64>>) -> Option<i64> {
+    sizes.into_iter().flatten().reduce(i64::saturating_add)
+}
+
+#[cfg(test)]
 fn aggregate_model_usage_entries(
     messages: Vec<UnifiedMessage>,
     group_by: &GroupBy,
 ) -> Vec<ModelUsage> {
-    let mut model_map: HashMap<String, ModelUsage> = HashMap::new();
+    aggregate_model_usage_entries_with(messages, group_by, None)
+}
 
+/// [`aggregate_model_usage_entries`] that also fills each row's
+/// `pricing_source` from `pricing_sources`.
+#[cfg(test)]
+fn aggregate_model_usage_entries_with(
+    messages: Vec<UnifiedMessage>,
+    group_by: &GroupBy,
+    pricing_sources: Option<&PricingSources>,
+) -> Vec<ModelUsage> {
+    let mut aggregator = ModelUsageAggregator::new(group_by);
     for msg in messages {
-        let normalized = model_name_for_grouping(&msg.client, &msg.provider_id, &msg.model_id);
-        let (workspace_group_key, workspace_key, workspace_label) = workspace_bucket(&msg);
+        let resolved = pricing_sources.map(|sources| lookup_pricing_source(sources, &msg));
+        aggregator.add(msg, resolved);
+    }
+    aggregator.finish()
+}
+
+/// Folds messages into model-report rows one at a time, so a report can be
+/// built straight from the message stream.
+struct ModelUsageAggregator<'a> {
+    group_by: &'a GroupBy,
+    model_map: HashMap<String, ModelUsage>,
+    source_counts: HashMap<String, HashMap<String, i64>>,
+    model_sessions: HashMap<String, HashSet<String>>,
+    /// Per-row (cost, tokens) by model, for picking a session or project
+    /// row's label.
+    session_models: HashMap<String, HashMap<String, (f64, i64)>>,
+}
+
+impl<'a> ModelUsageAggregator<'a> {
+    fn new(group_by: &'a GroupBy) -> Self {
+        Self {
+            group_by,
+            model_map: HashMap::new(),
+            source_counts: HashMap::new(),
+            model_sessions: HashMap::new(),
+            session_models: HashMap::new(),
+        }
+    }
+
+    /// Add one message. `resolved` is `None` when the report has no pricing
+    /// data, which leaves the row's `pricing_source` unset.
+    fn add(&mut self, msg: UnifiedMessage, resolved: Option<&ResolvedPricing>) {
+        let group_by = self.group_by;
+        let mut normalized = model_name_for_grouping(&msg.client, &msg.provider_id, &msg.model_id);
+        if *group_by == GroupBy::Family {
+            normalized = model_family(&normalized).to_string();
+        }
+        let provider = normalize_provider(&msg.provider_id);
+        let (workspace_group_key, workspace_key, workspace_label) = if *group_by == GroupBy::Project
+        {
+            project_bucket(&msg)
+        } else {
+            workspace_bucket(&msg)
+        };
         let key = match group_by {
-            GroupBy::Model => normalized.clone(),
+            GroupBy::Model | GroupBy::Family => normalized.clone(),
             GroupBy::ClientModel => format!("{}:{}", msg.client, normalized),
             GroupBy::ClientProviderModel => {
-                format!("{}:{}:{}", msg.client, msg.provider_id, normalized)
+                format!("{}:{}:{}", msg.client, provider, normalized)
             }
             GroupBy::WorkspaceModel => format!("{}:{}", workspace_group_key, normalized),
-            GroupBy::Session => format!("{}:{}", msg.session_id, normalized),
-            GroupBy::ClientSession => {
+            GroupBy::Project => workspace_group_key,
+            GroupBy::Session => session_key(&msg),
+            GroupBy::SessionModel => format!("{}:{}", msg.session_id, normalized),
+            GroupBy::ClientSessionModel => {
                 format!("{}:{}:{}", msg.client, msg.session_id, normalized)
             }
         };
-        let merge_clients = matches!(group_by, GroupBy::Model | GroupBy::WorkspaceModel);
-        let session_grouped = matches!(group_by, GroupBy::Session | GroupBy::ClientSession);
-        let entry = model_map.entry(key).or_insert_with(|| ModelUsage {
+        let merge_clients = matches!(
+            group_by,
+            GroupBy::Model | GroupBy::WorkspaceModel | GroupBy::Project | GroupBy::Family
+        );
+        let workspace_grouped = matches!(group_by, GroupBy::WorkspaceModel | GroupBy::Project);
+        let session_grouped = matches!(
+            group_by,
+            GroupBy::Session | GroupBy::SessionModel | GroupBy::ClientSessionModel
+        );
+        if matches!(group_by, GroupBy::Session | GroupBy::Project) {
+            let (cost, tokens) = self
+                .session_models
+                .entry(key.clone())
+                .or_default()
+                .entry(normalized.clone())
+                .or_default();
+            if msg.cost.is_finite() {
+                *cost += msg.cost;
+            }
+            *tokens = tokens.saturating_add(positive_token_total(&msg.tokens));
+        }
+        let savings = resolved.map_or(0.0, |resolved| cache_read_savings(&msg, resolved));
+        if let Some(resolved) = resolved {
+            *self
+                .source_counts
+                .entry(key.clone())
+                .or_default()
+                .entry(message_pricing_source(&msg, resolved).to_string())
+                .or_default() += i64::from(msg.message_count.max(1));
+        }
+        let new_session = self
+            .model_sessions
+            .entry(key.clone())
+            .or_default()
+            .insert(session_key(&msg));
+        let entry = self.model_map.entry(key).or_insert_with(|| ModelUsage {
             client: msg.client.clone(),
             merged_clients: if merge_clients {
                 Some(msg.client.clone())
             } else {
                 None
             },
-            workspace_key: if matches!(group_by, GroupBy::WorkspaceModel) {
+            workspace_key: if workspace_grouped {
                 workspace_key.clone()
             } else {
                 None
             },
-            workspace_label: if matches!(group_by, GroupBy::WorkspaceModel) {
+            workspace_label: if workspace_grouped {
                 Some(workspace_label.clone())
             } else {
                 None
@@ -2249,16 +3384,35 @@ fn aggregate_model_usage_entries(
                 None
             },
             model: normalized.clone(),
-            provider: msg.provider_id.clone(),
+            provider: provider.clone(),
+            raw_provider: msg.provider_id.clone(),
             input: 0,
             output: 0,
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
             message_count: 0,
+            session_count: 0,
+            first_date: msg.date.clone(),
+            last_date: msg.date.clone(),
             cost: 0.0,
+            pricing_source: None,
+            cost_comparison: None,
+            estimated_savings: 0.0,
+            request_bytes: None,
+            response_bytes: None,
             performance: ModelPerformance::default(),
         });
+        if new_session {
+            entry.session_count += 1;
+        }
+        // Dates are `YYYY-MM-DD`, so lexicographic order is chronological.
+        if msg.date < entry.first_date {
+            entry.first_date = msg.date.clone();
+        }
+        if msg.date > entry.last_date {
+            entry.last_date = msg.date.clone();
+        }
 
         if merge_clients {
             if !entry.client.split(", ").any(|s| s == msg.client) {
@@ -2273,9 +3427,12 @@ fn aggregate_model_usage_entries(
         }
 
         if *group_by != GroupBy::ClientProviderModel
-            && !entry.provider.split(", ").any(|p| p == msg.provider_id)
+            && !entry.provider.split(", ").any(|p| p == provider)
         {
-            entry.provider = format!("{}, {}", entry.provider, msg.provider_id);
+            entry.provider = format!("{}, {}", entry.provider, provider);
+        }
+        if !entry.raw_provider.split(", ").any(|p| p == msg.provider_id) {
+            entry.raw_provider = format!("{}, {}", entry.raw_provider, msg.provider_id);
         }
 
         // saturating_add so clamped (i64::MAX) buckets from a corrupt source
@@ -2287,40 +3444,82 @@ fn aggregate_model_usage_entries(
         entry.reasoning = entry.reasoning.saturating_add(msg.tokens.reasoning);
         entry.message_count += msg.message_count.max(0);
         entry.cost += msg.cost;
+        entry.estimated_savings += savings;
+        entry.request_bytes = sum_bytes([entry.request_bytes, msg.request_bytes]);
+        entry.response_bytes = sum_bytes([entry.response_bytes, msg.response_bytes]);
+        if let Some(client_cost) = msg.client_cost {
+            let comparison = entry.cost_comparison.get_or_insert_with(Default::default);
+            comparison.client_cost += client_cost;
+            if msg.cost_source == CostSource::Estimated {
+                comparison.estimated_cost += msg.cost;
+            }
+            comparison.message_count += msg.message_count.max(0);
+        }
         entry
             .performance
             .record_message(positive_token_total(&msg.tokens), msg.duration_ms);
     }
 
-    let mut entries: Vec<ModelUsage> = model_map
-        .into_values()
-        .map(|mut entry| {
-            let total_tokens = entry
-                .input
-                .max(0)
-                .saturating_add(entry.output.max(0))
-                .saturating_add(entry.cache_read.max(0))
-                .saturating_add(entry.cache_write.max(0))
-                .saturating_add(entry.reasoning.max(0));
-            entry.performance.finalize(total_tokens);
-            let mut providers: Vec<&str> = entry.provider.split(", ").collect();
-            providers.sort_unstable();
-            providers.dedup();
-            entry.provider = providers.join(", ");
-            entry
-        })
-        .collect();
-    entries.sort_by(|a, b| match (a.cost.is_nan(), b.cost.is_nan()) {
-        (true, true) => std::cmp::Ordering::Equal,
-        (true, false) => std::cmp::Ordering::Greater,
-        (false, true) => std::cmp::Ordering::Less,
-        (false, false) => b
-            .cost
-            .partial_cmp(&a.cost)
-            .unwrap_or(std::cmp::Ordering::Equal),
-    });
+    fn finish(self) -> Vec<ModelUsage> {
+        let Self {
+            model_map,
+            source_counts,
+            session_models,
+            ..
+        } = self;
+        let mut entries: Vec<ModelUsage> = model_map
+            .into_iter()
+            .map(|(key, mut entry)| {
+                if let Some(models) = session_models.get(&key) {
+                    entry.model = dominant_model(models);
+                }
+                entry.pricing_source = source_counts.get(&key).map(dominant_pricing_source);
+                let total_tokens = entry
+                    .input
+                    .max(0)
+                    .saturating_add(entry.output.max(0))
+                    .saturating_add(entry.cache_read.max(0))
+                    .saturating_add(entry.cache_write.max(0))
+                    .saturating_add(entry.reasoning.max(0));
+                entry.performance.finalize(total_tokens);
+                let mut providers: Vec<&str> = entry.provider.split(", ").collect();
+                providers.sort_unstable();
+                providers.dedup();
+                entry.provider = providers.join(", ");
+                let mut raw_providers: Vec<&str> = entry.raw_provider.split(", ").collect();
+                raw_providers.sort_unstable();
+                raw_providers.dedup();
+                entry.raw_provider = raw_providers.join(", ");
+                entry
+            })
+            .collect();
+        sort_model_usage(&mut entries, ModelOrder::default());
 
-    entries
+        entries
+    }
+}
+
+/// The model with the highest cost, falling back to the most tokens (then
+/// name) so unpriced sessions still get a stable label.
+fn dominant_model(models: &HashMap<String, (f64, i64)>) -> String {
+    models
+        .iter()
+        .max_by(
+            |(a_name, (a_cost, a_tokens)), (b_name, (b_cost, b_tokens))| {
+                a_cost
+                    .total_cmp(b_cost)
+                    .then(a_tokens.cmp(b_tokens))
+                    .then(b_name.cmp(a_name))
+            },
+        )
+        .map(|(name, _)| name.clone())
+        .unwrap_or_default()
+}
+
+/// Session identity used for session counts, matching the TUI: the same
+/// session id under two clients is two sessions.
+fn session_key(msg: &UnifiedMessage) -> String {
+    format!("{}:{}", msg.client, msg.session_id)
 }
 
 fn positive_token_total(tokens: &TokenBreakdown) -> i64 {
@@ -2334,109 +3533,285 @@ fn positive_token_total(tokens: &TokenBreakdown) -> i64 {
         .saturating_add(tokens.reasoning.max(0))
 }
 
-/// Sum the (input, output, cache_read, cache_write) token fields across model
-/// usage entries with saturating_add, so clamped (i64::MAX) entry buckets from a
-/// corrupt source can't overflow the report-level totals (the entries are
-/// already saturated per-field by aggregate_model_usage_entries).
-fn model_report_token_totals(entries: &[ModelUsage]) -> (i64, i64, i64, i64) {
+/// Sum the (input, output, cache_read, cache_write, reasoning) token fields
+/// across model usage entries with saturating_add, so clamped (i64::MAX) entry
+/// buckets from a corrupt source can't overflow the report-level totals (the
+/// entries are already saturated per-field by aggregate_model_usage_entries).
+fn model_report_token_totals(entries: &[ModelUsage]) -> (i64, i64, i64, i64, i64) {
     entries.iter().fold(
-        (0, 0, 0, 0),
-        |(input, output, cache_read, cache_write), entry| {
+        (0, 0, 0, 0, 0),
+        |(input, output, cache_read, cache_write, reasoning), entry| {
             (
                 input.saturating_add(entry.input),
                 output.saturating_add(entry.output),
                 cache_read.saturating_add(entry.cache_read),
                 cache_write.saturating_add(entry.cache_write),
+                reasoning.saturating_add(entry.reasoning),
             )
         },
     )
 }
 
-pub async fn get_model_report(options: ReportOptions) -> Result<ModelReport, String> {
-    let start = Instant::now();
+/// Split `entries` into the rows that meet the `min_cost` / `min_tokens`
+/// thresholds and an [`OmittedUsage`] rollup of the rows that don't. An entry
+/// is dropped when it falls below *either* threshold; with
+/// `include_zero_cost`, rows costing exactly $0 are exempt from `min_cost`.
+fn apply_model_report_thresholds(
+    entries: Vec<ModelUsage>,
+    min_cost: Option<f64>,
+    min_tokens: Option<i64>,
+    include_zero_cost: bool,
+) -> (Vec<ModelUsage>, Option<OmittedUsage>) {
+    if min_cost.is_none() && min_tokens.is_none() {
+        return (entries, None);
+    }
+
+    let mut omitted = OmittedUsage::default();
+    let kept: Vec<ModelUsage> = entries
+        .into_iter()
+        .filter(|entry| {
+            let below_cost = min_cost.is_some_and(|min| entry.cost < min)
+                && !(include_zero_cost && entry.cost == 0.0);
+            let below_tokens = min_tokens.is_some_and(|min| {
+                positive_token_total(&TokenBreakdown {
+                    input: entry.input,
+                    output: entry.output,
+                    cache_read: entry.cache_read,
+                    cache_write: entry.cache_write,
+                    reasoning: entry.reasoning,
+                    ..Default::default()
+                }) < min
+            });
+            if !(below_cost || below_tokens) {
+                return true;
+            }
+            omitted.entries += 1;
+            omitted.input = omitted.input.saturating_add(entry.input);
+            omitted.output = omitted.output.saturating_add(entry.output);
+            omitted.cache_read = omitted.cache_read.saturating_add(entry.cache_read);
+            omitted.cache_write = omitted.cache_write.saturating_add(entry.cache_write);
+            omitted.reasoning = omitted.reasoning.saturating_add(entry.reasoning);
+            omitted.message_count = omitted.message_count.saturating_add(entry.message_count);
+            omitted.cost += entry.cost;
+            false
+        })
+        .collect();
 
-    let home_dir = get_home_dir_string(&options.home_dir)?;
+    let omitted = (omitted.entries > 0).then_some(omitted);
+    (kept, omitted)
+}
 
-    let clients: Vec<String> = options.clients.clone().unwrap_or_else(|| {
-        let mut clients: Vec<String> = ClientId::ALL
-            .iter()
-            .map(|c| c.as_str().to_string())
-            .collect();
-        clients.push("synthetic".to_string());
-        clients
-    });
+/// Parses once and builds any number of reports from the same messages, so a
+/// caller that wants a model report, a monthly report, and a graph scans every
+/// source once instead of three times.
+///
+/// It holds every selected message, so a caller that wants one report should
+/// use [`get_model_report`], [`get_monthly_report`], or [`generate_graph`]
+/// instead, which fold the [`parse_messages_iter`] stream as it is read.
+pub struct ReportBuilder {
+    options: ReportOptions,
+    messages: Vec<UnifiedMessage>,
+    client_timings: Vec<ClientTiming>,
+    pricing_sources: Option<PricingSources>,
+    parse_time: Duration,
+}
 
-    let pricing = load_pricing_for_local_parse().await;
-    let all_messages = parse_all_messages_with_pricing_with_env_strategy(
-        &home_dir,
-        &clients,
-        pricing.as_deref(),
-        options.use_env_roots,
-        &options.scanner_settings,
-    );
+impl ReportBuilder {
+    /// Parse and price the messages selected by `options`, with the same
+    /// pricing rules as [`get_model_report`] (including `no_pricing`).
+    pub async fn parse(options: ReportOptions) -> Result<Self, String> {
+        let start = Instant::now();
+        let pricing = load_report_pricing(&options).await;
+        Self::parse_with_pricing(options, pricing.as_deref(), start)
+    }
+
+    fn parse_with_pricing(
+        options: ReportOptions,
+        pricing: Option<&pricing::PricingService>,
+        start: Instant,
+    ) -> Result<Self, String> {
+        let messages = report_messages(&options, pricing)?;
+        let client_timings = messages.client_timings().to_vec();
+        let messages: Vec<UnifiedMessage> = messages.collect();
+        let pricing_sources = pricing.map(|pricing| resolve_pricing_sources(&messages, pricing));
+        Ok(Self {
+            options,
+            messages,
+            client_timings,
+            pricing_sources,
+            parse_time: start.elapsed(),
+        })
+    }
+
+    /// The parsed messages, after date, agent, and project filtering, in no
+    /// particular order.
+    pub fn messages(&self) -> &[UnifiedMessage] {
+        &self.messages
+    }
+
+    pub fn options(&self) -> &ReportOptions {
+        &self.options
+    }
+
+    pub fn model_report(&self) -> ModelReport {
+        build_model_report(
+            self.messages.iter().cloned(),
+            &self.options,
+            &self.client_timings,
+            ReportPricing::resolved(self.pricing_sources.as_ref()),
+            self.parse_time,
+        )
+    }
+
+    pub fn monthly_report(&self) -> MonthlyReport {
+        build_monthly_report(
+            self.messages.iter().cloned(),
+            ReportPricing::resolved(self.pricing_sources.as_ref()),
+            self.parse_time,
+        )
+    }
+
+    pub fn graph(&self) -> GraphResult {
+        build_graph(self.messages.clone(), &self.options, self.parse_time)
+    }
+}
+
+/// Milliseconds spent parsing plus the time since `start`, the
+/// `processing_time_ms` of a report built from a [`ReportBuilder`].
+fn report_processing_ms(parse_time: Duration, start: Instant) -> u32 {
+    (parse_time + start.elapsed()).as_millis() as u32
+}
+
+pub async fn get_model_report(options: ReportOptions) -> Result<ModelReport, String> {
+    let start = Instant::now();
+    let pricing = load_report_pricing(&options).await;
+    let messages = report_messages(&options, pricing.as_deref())?;
+    let client_timings = messages.client_timings().to_vec();
+    Ok(build_model_report(
+        messages,
+        &options,
+        &client_timings,
+        ReportPricing::lazy(pricing.as_deref()),
+        start.elapsed(),
+    ))
+}
+
+fn build_model_report(
+    messages: impl IntoIterator<Item = UnifiedMessage>,
+    options: &ReportOptions,
+    client_timings: &[ClientTiming],
+    mut pricing: ReportPricing<'_>,
+    parse_time: Duration,
+) -> ModelReport {
+    let start = Instant::now();
 
-    let filtered = filter_messages_for_report(all_messages, &options);
-    let entries = aggregate_model_usage_entries(filtered, &options.group_by);
+    let client_timings = options.client_timings.then(|| client_timings.to_vec());
+    let pricing_unavailable = !pricing.is_available() && !options.no_pricing;
+    let mut day_costs = options.daily_costs.then(BTreeMap::new);
+    let mut sessions: HashSet<String> = HashSet::new();
+    let mut unpriced: BTreeSet<String> = BTreeSet::new();
+    let mut aggregator = ModelUsageAggregator::new(&options.group_by);
+    for msg in messages {
+        let resolved = pricing.resolve(&msg);
+        if let Some(day_costs) = day_costs.as_mut() {
+            *day_costs.entry(msg.date.clone()).or_insert(0.0) += msg.cost;
+        }
+        sessions.insert(session_key(&msg));
+        if (resolved.is_some() || pricing_unavailable)
+            && is_unpriced(&msg, resolved.unwrap_or(&UNRESOLVED_PRICING))
+        {
+            unpriced.insert(msg.model_id.clone());
+        }
+        aggregator.add(msg, resolved);
+    }
+    let daily_costs = day_costs.map(daily_cost_series);
+    let total_sessions = sessions.len() as i32;
+    let unpriced_models: Vec<String> = unpriced.into_iter().collect();
+    let mut entries = aggregator.finish();
+    if options.model_order != ModelOrder::default() {
+        sort_model_usage(&mut entries, options.model_order);
+    }
+    if options.include_zero_cost && options.model_order.key == ModelOrderKey::Cost {
+        sort_zero_cost_by_tokens(&mut entries);
+    }
 
-    let (total_input, total_output, total_cache_read, total_cache_write) =
+    let (total_input, total_output, total_cache_read, total_cache_write, total_reasoning) =
         model_report_token_totals(&entries);
     let total_messages: i32 = entries.iter().map(|e| e.message_count).sum();
     // f64's Sum identity is -0.0, so an empty report would serialize as
     // "totalCost": -0.0; adding +0.0 normalizes the sign without changing
     // any non-zero total.
     let total_cost: f64 = entries.iter().map(|e| e.cost).sum::<f64>() + 0.0;
+    let total_estimated_savings: f64 =
+        entries.iter().map(|e| e.estimated_savings).sum::<f64>() + 0.0;
+    let (entries, omitted) = apply_model_report_thresholds(
+        entries,
+        options.min_cost,
+        options.min_tokens,
+        options.include_zero_cost,
+    );
 
-    Ok(ModelReport {
+    ModelReport {
         entries,
         total_input,
         total_output,
         total_cache_read,
         total_cache_write,
+        total_reasoning,
         total_messages,
+        total_sessions,
         total_cost,
-        processing_time_ms: start.elapsed().as_millis() as u32,
-    })
+        total_estimated_savings,
+        processing_time_ms: report_processing_ms(parse_time, start),
+        omitted,
+        client_timings,
+        daily_costs,
+        unpriced_models,
+        pricing_unavailable,
+    }
 }
 
 #[derive(Default)]
 struct MonthAggregator {
     models: HashSet<String>,
+    dates: HashSet<String>,
     input: i64,
     output: i64,
     cache_read: i64,
     cache_write: i64,
+    reasoning: i64,
     message_count: i32,
     cost: f64,
 }
 
 pub async fn get_monthly_report(options: ReportOptions) -> Result<MonthlyReport, String> {
     let start = Instant::now();
+    let pricing = load_report_pricing(&options).await;
+    let messages = report_messages(&options, pricing.as_deref())?;
+    Ok(build_monthly_report(
+        messages,
+        ReportPricing::lazy(pricing.as_deref()),
+        start.elapsed(),
+    ))
+}
 
-    let home_dir = get_home_dir_string(&options.home_dir)?;
-
-    let clients: Vec<String> = options.clients.clone().unwrap_or_else(|| {
-        let mut clients: Vec<String> = ClientId::ALL
-            .iter()
-            .map(|c| c.as_str().to_string())
-            .collect();
-        clients.push("synthetic".to_string());
-        clients
-    });
-
-    let pricing = load_pricing_for_local_parse().await;
-    let all_messages = parse_all_messages_with_pricing_with_env_strategy(
-        &home_dir,
-        &clients,
-        pricing.as_deref(),
-        options.use_env_roots,
-        &options.scanner_settings,
-    );
-
-    let filtered = filter_messages_for_report(all_messages, &options);
+fn build_monthly_report(
+    messages: impl IntoIterator<Item = UnifiedMessage>,
+    mut pricing: ReportPricing<'_>,
+    parse_time: Duration,
+) -> MonthlyReport {
+    let start = Instant::now();
+    let mut unpriced: BTreeSet<String> = BTreeSet::new();
 
     let mut month_map: HashMap<String, MonthAggregator> = HashMap::new();
 
-    for msg in filtered {
+    for msg in messages {
+        if pricing
+            .resolve(&msg)
+            .is_some_and(|resolved| is_unpriced(&msg, resolved))
+        {
+            unpriced.insert(msg.model_id.clone());
+        }
         let month = if msg.date.len() >= 7 {
             msg.date[..7].to_string()
         } else {
@@ -2445,6 +3820,7 @@ pub async fn get_monthly_report(options: ReportOptions) -> Result<MonthlyReport,
 
         let entry = month_map.entry(month).or_default();
 
+        entry.dates.insert(msg.date.clone());
         entry.models.insert(model_name_for_grouping(
             &msg.client,
             &msg.provider_id,
@@ -2456,21 +3832,32 @@ pub async fn get_monthly_report(options: ReportOptions) -> Result<MonthlyReport,
         entry.output = entry.output.saturating_add(msg.tokens.output);
         entry.cache_read = entry.cache_read.saturating_add(msg.tokens.cache_read);
         entry.cache_write = entry.cache_write.saturating_add(msg.tokens.cache_write);
+        entry.reasoning = entry.reasoning.saturating_add(msg.tokens.reasoning);
         entry.message_count += msg.message_count.max(0);
         entry.cost += msg.cost;
     }
 
     let mut entries: Vec<MonthlyUsage> = month_map
         .into_iter()
-        .map(|(month, agg)| MonthlyUsage {
-            month,
-            models: agg.models.into_iter().collect(),
-            input: agg.input,
-            output: agg.output,
-            cache_read: agg.cache_read,
-            cache_write: agg.cache_write,
-            message_count: agg.message_count,
-            cost: agg.cost,
+        .map(|(month, agg)| {
+            let active_days = agg.dates.len() as i32;
+            MonthlyUsage {
+                month,
+                models: agg.models.into_iter().collect(),
+                input: agg.input,
+                output: agg.output,
+                cache_read: agg.cache_read,
+                cache_write: agg.cache_write,
+                reasoning: agg.reasoning,
+                message_count: agg.message_count,
+                cost: agg.cost,
+                active_days,
+                avg_cost_per_active_day: if active_days > 0 {
+                    agg.cost / f64::from(active_days)
+                } else {
+                    0.0
+                },
+            }
         })
         .collect();
 
@@ -2481,11 +3868,12 @@ pub async fn get_monthly_report(options: ReportOptions) -> Result<MonthlyReport,
     // any non-zero total.
     let total_cost: f64 = entries.iter().map(|e| e.cost).sum::<f64>() + 0.0;
 
-    Ok(MonthlyReport {
+    MonthlyReport {
         entries,
         total_cost,
-        processing_time_ms: start.elapsed().as_millis() as u32,
-    })
+        processing_time_ms: report_processing_ms(parse_time, start),
+        unpriced_models: unpriced.into_iter().collect(),
+    }
 }
 
 #[derive(Default)]
@@ -2511,31 +3899,12 @@ pub async fn get_hourly_report(options: ReportOptions) -> Result<HourlyReport, S
 
     let start = Instant::now();
 
-    let home_dir = get_home_dir_string(&options.home_dir)?;
-
-    let clients: Vec<String> = options.clients.clone().unwrap_or_else(|| {
-        let mut clients: Vec<String> = ClientId::ALL
-            .iter()
-            .map(|c| c.as_str().to_string())
-            .collect();
-        clients.push("synthetic".to_string());
-        clients
-    });
-
-    let pricing = load_pricing_for_local_parse().await;
-    let all_messages = parse_all_messages_with_pricing_with_env_strategy(
-        &home_dir,
-        &clients,
-        pricing.as_deref(),
-        options.use_env_roots,
-        &options.scanner_settings,
-    );
-
-    let filtered = filter_messages_for_report(all_messages, &options);
+    let pricing = load_report_pricing(&options).await;
+    let messages = report_messages(&options, pricing.as_deref())?;
 
     let mut hour_map: HashMap<String, HourAggregator> = HashMap::new();
 
-    for msg in filtered {
+    for msg in messages {
         let hour_key = if msg.timestamp > 0 {
             let ts_secs = msg.timestamp / 1000;
             match Local.timestamp_opt(ts_secs, 0) {
@@ -2607,42 +3976,233 @@ pub async fn get_hourly_report(options: ReportOptions) -> Result<HourlyReport, S
     })
 }
 
-async fn generate_graph_with_loaded_pricing(
-    options: ReportOptions,
-    pricing: Option<&pricing::PricingService>,
-) -> Result<GraphResult, String> {
+/// Usage by hour of the day in the local timezone (`TZ` or the system
+/// setting), for spotting when in the day usage and spend peak.
+pub async fn get_hour_of_day_report(options: ReportOptions) -> Result<HourOfDayReport, String> {
     let start = Instant::now();
 
-    let home_dir = get_home_dir_string(&options.home_dir)?;
+    let pricing = load_report_pricing(&options).await;
+    let messages = report_messages(&options, pricing.as_deref())?;
+    let (entries, untimed_messages) = bucket_by_hour_of_day(messages, &chrono::Local);
+    // +0.0 normalizes the -0.0 Sum identity, as in the other reports.
+    let total_cost: f64 = entries.iter().map(|e| e.cost).sum::<f64>() + 0.0;
 
-    let clients: Vec<String> = options.clients.clone().unwrap_or_else(|| {
-        let mut clients: Vec<String> = ClientId::ALL
-            .iter()
-            .map(|c| c.as_str().to_string())
-            .collect();
-        clients.push("synthetic".to_string());
-        clients
+    Ok(HourOfDayReport {
+        entries,
+        total_cost,
+        untimed_messages,
+        processing_time_ms: start.elapsed().as_millis() as u32,
+    })
+}
+
+/// Fold messages into 24 hour-of-day buckets in `tz`. Returns the buckets and
+/// the number of messages skipped for lacking a timestamp.
+fn bucket_by_hour_of_day<Tz: chrono::TimeZone>(
+    messages: impl IntoIterator<Item = UnifiedMessage>,
+    tz: &Tz,
+) -> (Vec<HourOfDayUsage>, i32) {
+    use chrono::Timelike;
+
+    let mut entries: Vec<HourOfDayUsage> = (0..24)
+        .map(|hour| HourOfDayUsage {
+            hour,
+            ..Default::default()
+        })
+        .collect();
+    let mut untimed = 0;
+
+    for msg in messages {
+        let Some(hour) = (msg.timestamp > 0)
+            .then(|| tz.timestamp_millis_opt(msg.timestamp).single())
+            .flatten()
+            .map(|dt| dt.hour())
+        else {
+            untimed += msg.message_count.max(0);
+            continue;
+        };
+        let entry = &mut entries[hour as usize];
+        entry.input = entry.input.saturating_add(msg.tokens.input);
+        entry.output = entry.output.saturating_add(msg.tokens.output);
+        entry.cache_read = entry.cache_read.saturating_add(msg.tokens.cache_read);
+        entry.cache_write = entry.cache_write.saturating_add(msg.tokens.cache_write);
+        entry.reasoning = entry.reasoning.saturating_add(msg.tokens.reasoning);
+        entry.message_count += msg.message_count.max(0);
+        entry.cost += msg.cost;
+    }
+
+    (entries, untimed)
+}
+
+#[derive(Default)]
+struct SessionAggregator {
+    title: Option<String>,
+    workspace_label: Option<String>,
+    /// Per grouped model: (cost, tokens).
+    models: HashMap<String, (f64, i64)>,
+    tokens: TokenBreakdown,
+    message_count: i32,
+    first_date: String,
+    last_date: String,
+    cost: f64,
+    reconciliation: Option<SessionReconciliation>,
+}
+
+/// Fold messages into one [`SessionUsage`] per `client:session_id`, sorted by
+/// cost descending (then total tokens, then key).
+fn aggregate_session_usage(
+    messages: impl IntoIterator<Item = UnifiedMessage>,
+) -> Vec<SessionUsage> {
+    let mut sessions: HashMap<(String, String), SessionAggregator> = HashMap::new();
+
+    for msg in messages {
+        let model = model_name_for_grouping(&msg.client, &msg.provider_id, &msg.model_id);
+        let tokens = positive_token_total(&msg.tokens);
+        let entry = sessions.entry((msg.client, msg.session_id)).or_default();
+
+        if entry.title.is_none() {
+            entry.title = msg.session_title;
+        }
+        if entry.workspace_label.is_none() {
+            entry.workspace_label = msg.workspace_label;
+        }
+        let (model_cost, model_tokens) = entry.models.entry(model).or_default();
+        if msg.cost.is_finite() {
+            *model_cost += msg.cost;
+        }
+        *model_tokens = model_tokens.saturating_add(tokens);
+        // saturating_add so clamped (i64::MAX) buckets from a corrupt source
+        // can't overflow the fold.
+        entry.tokens.input = entry.tokens.input.saturating_add(msg.tokens.input);
+        entry.tokens.output = entry.tokens.output.saturating_add(msg.tokens.output);
+        entry.tokens.cache_read = entry
+            .tokens
+            .cache_read
+            .saturating_add(msg.tokens.cache_read);
+        entry.tokens.cache_write = entry
+            .tokens
+            .cache_write
+            .saturating_add(msg.tokens.cache_write);
+        entry.tokens.reasoning = entry.tokens.reasoning.saturating_add(msg.tokens.reasoning);
+        entry.message_count += msg.message_count.max(0);
+        entry.cost += msg.cost;
+        if let Some(reported) = msg.reported_total_tokens {
+            let reconciliation = entry.reconciliation.get_or_insert_with(Default::default);
+            reconciliation.reported_tokens = Some(
+                reconciliation
+                    .reported_tokens
+                    .unwrap_or(0)
+                    .saturating_add(reported),
+            );
+            reconciliation.counted_tokens = reconciliation.counted_tokens.saturating_add(tokens);
+        }
+        if let (Some(client_cost), CostSource::Estimated) = (msg.client_cost, msg.cost_source) {
+            let reconciliation = entry.reconciliation.get_or_insert_with(Default::default);
+            reconciliation.reported_cost =
+                Some(reconciliation.reported_cost.unwrap_or(0.0) + client_cost);
+            reconciliation.estimated_cost += msg.cost;
+        }
+        if entry.first_date.is_empty() || msg.date < entry.first_date {
+            entry.first_date = msg.date.clone();
+        }
+        if msg.date > entry.last_date {
+            entry.last_date = msg.date;
+        }
+    }
+
+    let mut entries: Vec<SessionUsage> = sessions
+        .into_iter()
+        .map(|((client, session_id), agg)| SessionUsage {
+            client,
+            session_id,
+            title: agg.title,
+            workspace_label: agg.workspace_label,
+            dominant_model: dominant_model(&agg.models),
+            input: agg.tokens.input,
+            output: agg.tokens.output,
+            cache_read: agg.tokens.cache_read,
+            cache_write: agg.tokens.cache_write,
+            reasoning: agg.tokens.reasoning,
+            total_tokens: positive_token_total(&agg.tokens),
+            message_count: agg.message_count,
+            first_date: agg.first_date,
+            last_date: agg.last_date,
+            cost: agg.cost,
+            reconciliation: agg.reconciliation,
+        })
+        .collect();
+
+    entries.sort_by(|a, b| {
+        b.cost
+            .partial_cmp(&a.cost)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.total_tokens.cmp(&a.total_tokens))
+            .then_with(|| a.client.cmp(&b.client))
+            .then_with(|| a.session_id.cmp(&b.session_id))
     });
+    entries
+}
 
-    let all_messages = parse_all_messages_with_pricing_with_env_strategy(
-        &home_dir,
-        &clients,
-        pricing,
-        options.use_env_roots,
-        &options.scanner_settings,
-    );
+/// Rank individual sessions across all clients by cost. Uses the same
+/// parse, pricing, and filter pipeline as [`get_model_report`].
+pub async fn get_top_sessions_report(options: ReportOptions) -> Result<TopSessionsReport, String> {
+    let start = Instant::now();
+
+    let pricing = load_report_pricing(&options).await;
+    let messages = report_messages(&options, pricing.as_deref())?;
+    let entries = aggregate_session_usage(messages);
+
+    // f64's Sum identity is -0.0, so an empty report would serialize as
+    // "totalCost": -0.0; adding +0.0 normalizes the sign without changing
+    // any non-zero total.
+    let total_cost: f64 = entries.iter().map(|e| e.cost).sum::<f64>() + 0.0;
 
-    let filtered = filter_messages_for_report(all_messages, &options);
+    Ok(TopSessionsReport {
+        entries,
+        total_cost,
+        processing_time_ms: start.elapsed().as_millis() as u32,
+    })
+}
+
+async fn generate_graph_with_loaded_pricing(
+    options: ReportOptions,
+    pricing: Option<&pricing::PricingService>,
+) -> Result<GraphResult, String> {
+    let start = Instant::now();
+    // Sessionizing needs every message of a session at once, so the graph
+    // holds the selected messages rather than folding the stream.
+    let messages: Vec<UnifiedMessage> = report_messages(&options, pricing)?.collect();
+    Ok(build_graph(messages, &options, start.elapsed()))
+}
+
+fn build_graph(
+    filtered: Vec<UnifiedMessage>,
+    options: &ReportOptions,
+    parse_time: Duration,
+) -> GraphResult {
+    let start = Instant::now();
 
     let intervals = sessionize::sessionize(&filtered, sessionize::DEFAULT_IDLE_GAP_MS);
     let time_metrics =
         sessionize::compute_time_metrics(&intervals, sessionize::DEFAULT_IDLE_GAP_MS);
 
     let daily_active_time = sessionize::compute_daily_active_time(&intervals);
-    let contributions = aggregator::aggregate_by_date(filtered);
+    let mut contributions = aggregator::aggregate_by_date(filtered);
+    if options.intensity_mode != IntensityMode::Linear
+        || options.intensity_basis != IntensityBasis::Cost
+    {
+        aggregator::calculate_intensities_with(
+            &mut contributions,
+            options.intensity_mode,
+            options.intensity_basis,
+        );
+    }
 
-    let processing_time_ms = start.elapsed().as_millis() as u32;
-    let mut result = aggregator::generate_graph_result(contributions, processing_time_ms);
+    let processing_time_ms = report_processing_ms(parse_time, start);
+    let mut result = aggregator::generate_graph_result_with(
+        contributions,
+        processing_time_ms,
+        options.active_threshold,
+    );
     result.time_metrics = Some(time_metrics);
 
     for contribution in &mut result.contributions {
@@ -2651,7 +4211,7 @@ async fn generate_graph_with_loaded_pricing(
         }
     }
 
-    Ok(result)
+    result
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -2663,26 +4223,7 @@ pub struct TimeMetricsReport {
 pub async fn get_time_metrics_report(options: ReportOptions) -> Result<TimeMetricsReport, String> {
     let start = Instant::now();
 
-    let home_dir = get_home_dir_string(&options.home_dir)?;
-
-    let clients: Vec<String> = options.clients.clone().unwrap_or_else(|| {
-        let mut clients: Vec<String> = ClientId::ALL
-            .iter()
-            .map(|c| c.as_str().to_string())
-            .collect();
-        clients.push("synthetic".to_string());
-        clients
-    });
-
-    let all_messages = parse_all_messages_with_pricing_with_env_strategy(
-        &home_dir,
-        &clients,
-        None,
-        options.use_env_roots,
-        &options.scanner_settings,
-    );
-
-    let filtered = filter_messages_for_report(all_messages, &options);
+    let filtered: Vec<UnifiedMessage> = report_messages(&options, None)?.collect();
 
     let intervals = sessionize::sessionize(&filtered, sessionize::DEFAULT_IDLE_GAP_MS);
     let metrics = sessionize::compute_time_metrics(&intervals, sessionize::DEFAULT_IDLE_GAP_MS);
@@ -2694,34 +4235,176 @@ pub async fn get_time_metrics_report(options: ReportOptions) -> Result<TimeMetri
 }
 
 pub async fn generate_graph(options: ReportOptions) -> Result<GraphResult, String> {
-    let pricing = pricing::PricingService::get_or_init().await?;
+    let pricing = pricing::PricingService::load(options.pricing_date)
+        .await?
+        .with_cost_source(options.cost_source);
     generate_graph_with_loaded_pricing(options, Some(&pricing)).await
 }
 
 pub async fn generate_local_graph_report(options: ReportOptions) -> Result<GraphResult, String> {
-    let pricing = load_pricing_for_local_parse().await;
+    let pricing = load_report_pricing(&options).await;
     generate_graph_with_loaded_pricing(options, pricing.as_deref()).await
 }
 
-fn filter_messages_for_report(
-    messages: Vec<UnifiedMessage>,
-    options: &ReportOptions,
-) -> Vec<UnifiedMessage> {
-    let mut filtered = messages;
+/// The locally parsed, priced messages selected by `options`, as an iterator
+/// in **no particular order**.
+///
+/// Applies the same client, `year`, `since`, `until`, agent, project, and
+/// model selection as [`get_model_report`], and is what it and the other
+/// report builders fold. Pricing follows the same rules too (including
+/// `no_pricing`), resolved per message as each source is parsed: some
+/// sources rewrite model ids afterwards and must be priced under the id
+/// they were logged with.
+///
+/// Every source is parsed and deduplicated before the first message comes
+/// out, but nothing is copied after that: each message is handed over by
+/// value and freed when the caller drops it, so folding the iterator never
+/// holds a second, filtered copy of the messages.
+pub async fn parse_messages_iter(options: ReportOptions) -> Result<UnorderedMessages, String> {
+    let pricing = load_report_pricing(&options).await;
+    report_messages(&options, pricing.as_deref())
+}
 
-    if let Some(year) = &options.year {
-        let year_prefix = format!("{}-", year);
-        filtered.retain(|m| m.date.starts_with(&year_prefix));
+/// Callback form of [`parse_messages_iter`]: calls `f` once per selected
+/// message, in **no particular order**.
+pub async fn for_each_message<F>(options: ReportOptions, f: F) -> Result<(), String>
+where
+    F: FnMut(UnifiedMessage),
+{
+    parse_messages_iter(options).await?.for_each(f);
+    Ok(())
+}
+
+/// Messages yielded by [`parse_messages_iter`].
+///
+/// There is no ordering guarantee: not by time, client, or session. Sources
+/// are parsed in parallel and the order may change between runs, so sort the
+/// messages yourself if you need an order.
+pub struct UnorderedMessages {
+    messages: std::vec::IntoIter<UnifiedMessage>,
+    window: ReportWindow,
+    future_dates: future_dates::FutureDatePolicy,
+    agents: AgentFilter,
+    projects: ProjectFilter,
+    models: ModelFilter,
+    timings: Vec<ClientTiming>,
+}
+
+impl UnorderedMessages {
+    /// Scan and parse time per enabled client for the parse behind this
+    /// iterator.
+    pub fn client_timings(&self) -> &[ClientTiming] {
+        &self.timings
     }
+}
 
-    if let Some(since) = &options.since {
-        filtered.retain(|m| m.date.as_str() >= since.as_str());
+impl Iterator for UnorderedMessages {
+    type Item = UnifiedMessage;
+
+    fn next(&mut self) -> Option<UnifiedMessage> {
+        self.messages.by_ref().find_map(|mut message| {
+            let keep = self
+                .future_dates
+                .admit(&mut message.date, &mut message.timestamp)
+                && self.window.contains(&message.date)
+                && self.agents.matches(message.agent.as_deref())
+                && self.projects.matches(&message)
+                && self.models.matches(&message);
+            keep.then_some(message)
+        })
     }
 
-    if let Some(until) = &options.until {
-        filtered.retain(|m| m.date.as_str() <= until.as_str());
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.messages.size_hint().1)
     }
-    filtered
+}
+
+/// The `year`/`since`/`until` date selection of a [`ReportOptions`].
+#[derive(Debug, Clone, Default)]
+struct ReportWindow {
+    year_prefix: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+    until_exclusive: bool,
+}
+
+impl ReportWindow {
+    fn from_options(options: &ReportOptions) -> Self {
+        Self {
+            year_prefix: options.year.as_ref().map(|year| format!("{}-", year)),
+            since: options.since.clone(),
+            until: options.until.clone(),
+            until_exclusive: options.until_exclusive,
+        }
+    }
+
+    fn contains(&self, date: &str) -> bool {
+        self.year_prefix
+            .as_ref()
+            .is_none_or(|prefix| date.starts_with(prefix.as_str()))
+            && self
+                .since
+                .as_ref()
+                .is_none_or(|since| date >= since.as_str())
+            && self
+                .until
+                .as_ref()
+                .is_none_or(|until| is_within_until(date, until, self.until_exclusive))
+    }
+}
+
+fn report_messages(
+    options: &ReportOptions,
+    pricing: Option<&pricing::PricingService>,
+) -> Result<UnorderedMessages, String> {
+    let home_dir = get_home_dir_string(&options.home_dir)?;
+
+    let clients: Vec<String> = options.clients.clone().unwrap_or_else(|| {
+        let mut clients: Vec<String> = ClientId::ALL
+            .iter()
+            .map(|c| c.as_str().to_string())
+            .collect();
+        clients.push("synthetic".to_string());
+        clients
+    });
+
+    let (messages, timings) = scan_pool::install(options.threads, || {
+        parse_all_messages_with_timings(
+            &home_dir,
+            &clients,
+            pricing,
+            options.use_env_roots,
+            &options.scanner_settings,
+            options.no_dedup,
+        )
+    })?;
+
+    Ok(UnorderedMessages {
+        messages: messages.into_iter(),
+        window: ReportWindow::from_options(options),
+        future_dates: future_dates::FutureDatePolicy::new(options.max_future_days),
+        agents: options.agent_filter.clone(),
+        projects: options.project_filter.clone(),
+        models: options.model_filter.clone(),
+        timings,
+    })
+}
+
+#[cfg(test)]
+fn filter_messages_for_report(
+    messages: Vec<UnifiedMessage>,
+    options: &ReportOptions,
+) -> Vec<UnifiedMessage> {
+    UnorderedMessages {
+        messages: messages.into_iter(),
+        window: ReportWindow::from_options(options),
+        future_dates: future_dates::FutureDatePolicy::new(options.max_future_days),
+        agents: options.agent_filter.clone(),
+        projects: options.project_filter.clone(),
+        models: options.model_filter.clone(),
+        timings: Vec::new(),
+    }
+    .collect()
 }
 
 fn is_headless_path(path: &Path, headless_roots: &[PathBuf]) -> bool {
@@ -2756,6 +4439,19 @@ fn pricing_multiplier(message: &UnifiedMessage) -> f64 {
     }
 }
 
+/// Refresh derived fields and price every message of one source. Lookups
+/// only read the shared [`pricing::PricingService`] (its match cache sits
+/// behind an `RwLock`), so large sources are priced in parallel.
+fn apply_pricing_to_messages(
+    messages: &mut [UnifiedMessage],
+    pricing: Option<&pricing::PricingService>,
+) {
+    messages.par_iter_mut().for_each(|message| {
+        message.refresh_derived_fields();
+        apply_pricing_if_available(message, pricing);
+    });
+}
+
 fn apply_pricing_if_available(
     message: &mut UnifiedMessage,
     pricing: Option<&pricing::PricingService>,
@@ -2774,6 +4470,11 @@ fn apply_pricing_if_available(
         &message.tokens,
     ) * pricing_multiplier(message);
 
+    // Keep what the client recorded so `--compare-estimate` can still show
+    // it after the estimate below replaces it.
+    if message.cost > 0.0 && message.cost_source != CostSource::Estimated {
+        message.client_cost.get_or_insert(message.cost);
+    }
     if calculated_cost > 0.0 {
         message.cost = calculated_cost;
         message.mark_estimated_cost();
@@ -2805,21 +4506,53 @@ where
     fresh.ok().or_else(|| stale().map(Arc::new))
 }
 
-async fn load_pricing_for_local_parse() -> Option<Arc<pricing::PricingService>> {
-    if std::env::var("TOKSCALE_PRICING_CACHE_ONLY")
+async fn load_report_pricing(options: &ReportOptions) -> Option<Arc<pricing::PricingService>> {
+    pricing_unless_disabled(options.no_pricing, || {
+        load_pricing_for_local_parse(options.cost_source, options.pricing_date)
+    })
+    .await
+}
+
+/// Run the pricing loader only when pricing is enabled, so `--no-pricing`
+/// never touches the network or the pricing cache.
+async fn pricing_unless_disabled<F, Fut>(
+    no_pricing: bool,
+    load: F,
+) -> Option<Arc<pricing::PricingService>>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Option<Arc<pricing::PricingService>>>,
+{
+    if no_pricing {
+        None
+    } else {
+        load().await
+    }
+}
+
+async fn load_pricing_for_local_parse(
+    cost_source: pricing::CostSourcePreference,
+    pricing_date: Option<chrono::NaiveDate>,
+) -> Option<Arc<pricing::PricingService>> {
+    let pricing = if let Some(date) = pricing_date {
+        pricing::PricingService::from_snapshot(date)
+            .ok()
+            .map(Arc::new)
+    } else if std::env::var("TOKSCALE_PRICING_CACHE_ONLY")
         .map(|value| matches!(value.as_str(), "1" | "true" | "TRUE" | "yes" | "YES"))
         .unwrap_or(false)
     {
-        return pricing::PricingService::load_cached_any_age().map(Arc::new);
-    }
-
-    // Interactive/local views should pick up newly released model pricing as soon
-    // as a fresh fetch succeeds, but still remain usable offline by falling back
-    // to any cached dataset when the network path fails.
-    select_local_parse_pricing(
-        pricing::PricingService::get_or_init().await,
-        pricing::PricingService::load_cached_any_age,
-    )
+        pricing::PricingService::load_cached_any_age().map(Arc::new)
+    } else {
+        // Interactive/local views should pick up newly released model pricing as soon
+        // as a fresh fetch succeeds, but still remain usable offline by falling back
+        // to any cached dataset when the network path fails.
+        select_local_parse_pricing(
+            pricing::PricingService::get_or_init().await,
+            pricing::PricingService::load_cached_any_age,
+        )
+    };
+    pricing.map(|pricing| Arc::new(pricing.with_cost_source(cost_source)))
 }
 
 fn resolve_local_parse_request(
@@ -2843,16 +4576,27 @@ fn parse_local_unified_messages_resolved(
     clients: &[String],
     pricing: Option<&pricing::PricingService>,
 ) -> Result<Vec<UnifiedMessage>, String> {
-    let messages = parse_all_messages_with_pricing_with_env_strategy(
-        home_dir,
-        clients,
-        pricing,
-        options.use_env_roots,
-        &options.scanner_settings,
-    );
+    let messages = scan_pool::install(options.threads, || {
+        parse_all_messages_with_pricing_with_env_strategy(
+            home_dir,
+            clients,
+            pricing,
+            options.use_env_roots,
+            &options.scanner_settings,
+            options.no_dedup,
+        )
+    })?;
     Ok(filter_unified_messages(messages, &options))
 }
 pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages, String> {
+    scan_pool::install(options.threads, || {
+        parse_local_clients_on_current_pool(options)
+    })?
+}
+
+fn parse_local_clients_on_current_pool(
+    options: LocalParseOptions,
+) -> Result<ParsedMessages, String> {
     let start = Instant::now();
 
     let home_dir = get_home_dir_string(&options.home_dir)?;
@@ -2886,7 +4630,7 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
     let mut counts = ClientCounts::new();
 
     let opencode_count: i32 = {
-        let mut seen: HashSet<String> = HashSet::new();
+        let mut seen = SeenKeys::unless_no_dedup(options.no_dedup);
         let mut count: i32 = 0;
 
         for db_path in &scan_result.opencode_dbs {
@@ -2899,7 +4643,7 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
                         // same session can end up in both `opencode.db` and
                         // `opencode-<channel>.db` if the user switches
                         // channels mid-session.
-                        if !key.is_empty() && !seen.insert(key.clone()) {
+                        if !key.is_empty() && !seen.insert(&key) {
                             return None;
                         }
                         Some((key, unified_to_parsed(&msg)))
@@ -2922,7 +4666,7 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
             .collect();
         let deduped: Vec<ParsedMessage> = json_msgs
             .into_iter()
-            .filter(|(key, _)| key.is_empty() || seen.insert(key.clone()))
+            .filter(|(key, _)| key.is_empty() || seen.insert(key))
             .map(|(_, msg)| msg)
             .collect();
         count += deduped.len() as i32;
@@ -2952,10 +4696,10 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
         .flatten()
         .collect();
 
-    let mut seen_keys: HashSet<String> = HashSet::new();
+    let mut seen_keys = SeenKeys::unless_no_dedup(options.no_dedup);
     let claude_msgs: Vec<ParsedMessage> = claude_msgs_raw
         .into_iter()
-        .filter(|(key, _)| key.is_empty() || seen_keys.insert(key.clone()))
+        .filter(|(key, _)| key.is_empty() || seen_keys.insert(key))
         .map(|(_, msg)| msg)
         .collect();
     let claude_count = claude_msgs.len() as i32;
@@ -2976,7 +4720,7 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
                 .collect::<Vec<_>>()
         })
         .collect();
-    let mut codex_seen: HashSet<String> = HashSet::new();
+    let mut codex_seen = SeenKeys::default();
     let codex_msgs: Vec<ParsedMessage> = codex_msgs_raw
         .into_iter()
         .filter(|message| should_keep_deduped_message(&mut codex_seen, message))
@@ -3147,7 +4891,7 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
         .par_iter()
         .flat_map(|path| sessions::gjc::parse_gjc_file(path))
         .collect();
-    let mut gjc_seen: HashSet<String> = HashSet::new();
+    let mut gjc_seen = SeenKeys::default();
     let gjc_msgs: Vec<ParsedMessage> = gjc_msgs_raw
         .into_iter()
         .filter(|message| should_keep_deduped_message(&mut gjc_seen, message))
@@ -3165,7 +4909,7 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
         .par_iter()
         .flat_map(|path| sessions::junie::parse_junie_file(path))
         .collect();
-    let mut junie_seen: HashSet<String> = HashSet::new();
+    let mut junie_seen = SeenKeys::default();
     let junie_msgs: Vec<ParsedMessage> = junie_msgs_raw
         .into_iter()
         .filter(|message| should_keep_deduped_message(&mut junie_seen, message))
@@ -3318,7 +5062,7 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
 
     let hermes_db_paths = scan_result.hermes_db_paths();
     if !hermes_db_paths.is_empty() {
-        let mut hermes_seen: HashSet<String> = HashSet::new();
+        let mut hermes_seen = SeenKeys::default();
         let hermes_msgs: Vec<ParsedMessage> = hermes_db_paths
             .iter()
             .flat_map(|db_path| sessions::hermes::parse_hermes_sqlite(db_path))
@@ -3456,7 +5200,7 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
     // database is authoritative only when the CLI client itself is selected;
     // Desktop-only reports still use the database for title/model metadata but
     // must not leak CLI usage into their result.
-    let mut devin_cli_seen = HashSet::new();
+    let mut devin_cli_seen = SeenKeys::default();
     let devin_cli_messages: Vec<UnifiedMessage> = if include_devin_cli {
         scan_result
             .devin_dbs
@@ -3518,14 +5262,14 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
         .par_iter()
         .flat_map(|path| sessions::codebuddy::parse_codebuddy_file(path))
         .collect();
-    let mut codebuddy_seen: HashSet<String> = HashSet::new();
+    let mut codebuddy_seen = SeenKeys::default();
     let codebuddy_msgs: Vec<ParsedMessage> = codebuddy_msgs_raw
         .into_iter()
         .filter(|message| {
             message
                 .dedup_key
                 .as_ref()
-                .is_none_or(|key| codebuddy_seen.insert(key.clone()))
+                .is_none_or(|key| codebuddy_seen.insert(key))
         })
         .map(|msg| unified_to_parsed(&msg))
         .collect();
@@ -3570,7 +5314,7 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
         .par_iter()
         .flat_map(|path| sessions::jcode::parse_jcode_file(path))
         .collect();
-    let mut jcode_seen: HashSet<String> = HashSet::new();
+    let mut jcode_seen = SeenKeys::default();
     let jcode_msgs: Vec<ParsedMessage> = jcode_msgs_raw
         .into_iter()
         .filter(|message| should_keep_deduped_message(&mut jcode_seen, message))
@@ -3580,6 +5324,15 @@ pub fn parse_local_clients(options: LocalParseOptions) -> Result<ParsedMessages,
     counts.set(ClientId::Jcode, jcode_count);
     messages.extend(jcode_msgs);
 
+    let aider_msgs: Vec<ParsedMessage> =
+        sessions::aider::parse_aider_sources(scan_result.get(ClientId::Aider))
+            .iter()
+            .map(unified_to_parsed)
+            .collect();
+    let aider_count = summed_parsed_message_count(&aider_msgs);
+    counts.set(ClientId::Aider, aider_count);
+    messages.extend(aider_msgs);
+
     if include_synthetic {
         if let Some(db_path) = &scan_result.synthetic_db {
             let synthetic_msgs: Vec<ParsedMessage> =
@@ -3630,7 +5383,7 @@ pub async fn parse_local_unified_messages(
     options: LocalParseOptions,
 ) -> Result<Vec<UnifiedMessage>, String> {
     let (home_dir, clients) = resolve_local_parse_request(&options)?;
-    let pricing = load_pricing_for_local_parse().await;
+    let pricing = load_pricing_for_local_parse(options.cost_source, options.pricing_date).await;
     parse_local_unified_messages_resolved(options, &home_dir, &clients, pricing.as_deref())
 }
 
@@ -3655,11 +5408,46 @@ fn unified_to_parsed(msg: &UnifiedMessage) -> ParsedMessage {
     }
 }
 
-fn should_keep_deduped_message(seen_keys: &mut HashSet<String>, message: &UnifiedMessage) -> bool {
+/// Dedup keys already kept for one client, and how many later messages were
+/// dropped as repeats of them.
+#[derive(Debug, Default)]
+struct SeenKeys {
+    keys: HashSet<String>,
+    duplicates: usize,
+    keep_all: bool,
+}
+
+impl SeenKeys {
+    /// Keys for a client whose dedup `--no-dedup` turns off; with `no_dedup`
+    /// set, every message is kept.
+    fn unless_no_dedup(no_dedup: bool) -> Self {
+        Self {
+            keep_all: no_dedup,
+            ..Self::default()
+        }
+    }
+
+    /// Record `key`, returning false (and counting a duplicate) when it was
+    /// already kept.
+    fn insert(&mut self, key: &str) -> bool {
+        if self.keep_all {
+            return true;
+        }
+        if self.keys.contains(key) {
+            self.duplicates += 1;
+            false
+        } else {
+            self.keys.insert(key.to_string());
+            true
+        }
+    }
+}
+
+fn should_keep_deduped_message(seen_keys: &mut SeenKeys, message: &UnifiedMessage) -> bool {
     message
         .dedup_key
-        .as_ref()
-        .is_none_or(|key| seen_keys.insert(key.clone()))
+        .as_deref()
+        .is_none_or(|key| seen_keys.insert(key))
 }
 
 fn summed_parsed_message_count(messages: &[ParsedMessage]) -> i32 {
@@ -3675,6 +5463,9 @@ fn filter_parsed_messages(
 ) -> Vec<ParsedMessage> {
     let mut filtered = messages;
 
+    let future_dates = future_dates::FutureDatePolicy::new(options.max_future_days);
+    filtered.retain_mut(|m| future_dates.admit(&mut m.date, &mut m.timestamp));
+
     if let Some(year) = &options.year {
         let year_prefix = format!("{}-", year);
         filtered.retain(|m| m.date.starts_with(&year_prefix));
@@ -3685,7 +5476,7 @@ fn filter_parsed_messages(
     }
 
     if let Some(until) = &options.until {
-        filtered.retain(|m| m.date.as_str() <= until.as_str());
+        filtered.retain(|m| is_within_until(&m.date, until, options.until_exclusive));
     }
     filtered
 }
@@ -3706,29 +5497,43 @@ pub fn parsed_to_unified(msg: &ParsedMessage, cost: f64) -> UnifiedMessage {
             cache_read: msg.cache_read,
             cache_write: msg.cache_write,
             reasoning: msg.reasoning,
+            ..Default::default()
         },
         cost,
         cost_source: CostSource::Unknown,
+        client_cost: None,
         duration_ms: msg.duration_ms,
         message_count: msg.message_count,
         agent: msg.agent.clone(),
         dedup_key: None,
         session_title: None,
         is_turn_start: false,
+        request_bytes: None,
+        response_bytes: None,
+        reported_total_tokens: None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        aggregate_model_usage_entries, apply_pricing_if_available, dedupe_latest_trae_messages,
-        filter_messages_for_report, generate_graph_with_loaded_pricing, message_cache,
-        normalize_model_for_grouping, parse_all_messages_with_pricing_with_env_strategy,
-        parse_local_clients, parsed_to_unified, pricing, retain_for_requested_clients, scanner,
-        select_local_parse_pricing, unified_to_parsed, ClientId, GroupBy, LocalParseOptions,
-        ReportOptions, TokenBreakdown, UnifiedMessage, UNKNOWN_WORKSPACE_LABEL,
+        aggregate_model_usage_entries, aggregate_model_usage_entries_with, aggregate_session_usage,
+        apply_pricing_if_available, apply_pricing_to_messages, bucket_by_hour_of_day,
+        build_monthly_report, daily_cost_series, dedupe_latest_trae_messages,
+        dominant_pricing_source, filter_messages_for_report, filter_parsed_messages,
+        filter_unified_messages, for_each_message, generate_graph_with_loaded_pricing,
+        get_model_report, get_monthly_report, message_cache, normalize_model_for_grouping,
+        parse_all_messages_with_pricing_with_env_strategy, parse_all_messages_with_timings,
+        parse_local_clients, parse_messages_iter, parsed_to_unified, pricing,
+        pricing_unless_disabled, report_messages, resolve_pricing_sources,
+        retain_for_requested_clients, scanner, select_local_parse_pricing, sort_model_usage,
+        sum_bytes, unified_to_parsed, unpriced_model_ids, AgentFilter, ClientId, GroupBy,
+        HourOfDayUsage, IntensityBasis, IntensityMode, LocalParseOptions, ModelFilter, ModelOrder,
+        ModelOrderKey, ModelPerformance, ModelUsage, ProjectFilter, ReportBuilder, ReportOptions,
+        ReportPricing, TokenBreakdown, UnifiedMessage, NO_AGENT_LABEL, UNKNOWN_PROJECT_LABEL,
+        UNKNOWN_WORKSPACE_LABEL,
     };
-    use std::collections::{HashMap, HashSet};
+    use std::collections::{BTreeMap, HashMap, HashSet};
     use std::io::Write;
     use std::str::FromStr;
     use std::sync::Arc;
@@ -3744,6 +5549,7 @@ mod tests {
             pricing,
             false,
             &scanner::ScannerSettings::default(),
+            false,
         )
     }
 
@@ -3757,11 +5563,23 @@ mod tests {
             cache_read: i64::MAX,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         };
         assert_eq!(t.total(), i64::MAX);
         assert_eq!(super::positive_token_total(&t), i64::MAX);
     }
 
+    #[test]
+    fn token_breakdown_without_cache_write_1h_still_deserializes() {
+        // Caches and exports written before the 1h cache-write split.
+        let t: TokenBreakdown = serde_json::from_str(
+            r#"{"input":1,"output":2,"cache_read":3,"cache_write":4,"reasoning":5}"#,
+        )
+        .unwrap();
+        assert_eq!(t.cache_write, 4);
+        assert_eq!(t.cache_write_1h, 0);
+    }
+
     #[test]
     fn model_aggregation_saturates_overflowing_token_folds() {
         // token_total_saturates_on_overlarge_buckets covers a single message's
@@ -3784,6 +5602,7 @@ mod tests {
                     cache_read: i64::MAX,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 0.0,
                 None,
@@ -3797,177 +5616,723 @@ mod tests {
     }
 
     #[test]
-    fn model_report_totals_saturate_across_groups() {
-        // aggregate_model_usage_entries saturates each entry's fields, so an
-        // entry can be i64::MAX. get_model_report sums the entries into the
-        // report-level totals via model_report_token_totals; two saturated
-        // entries (two distinct models) must not overflow that sum either.
-        let make = |model: &str| {
-            UnifiedMessage::new_with_dedup(
-                "antigravity-cli",
-                model,
-                "antigravity",
-                "session-overflow",
+    fn model_usage_groups_provider_spellings_and_keeps_raw_ids() {
+        let make = |provider: &str| {
+            UnifiedMessage::new(
+                "claude",
+                "claude-sonnet-4-5",
+                provider,
+                "session-a",
                 1_733_011_200_000,
                 TokenBreakdown {
-                    input: i64::MAX,
-                    output: 0,
-                    cache_read: i64::MAX,
+                    input: 10,
+                    output: 5,
+                    cache_read: 0,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
-                0.0,
-                None,
+                0.01,
             )
         };
 
-        let entries = aggregate_model_usage_entries(
-            vec![make("gemini-3-pro"), make("claude-opus-4-6")],
-            &GroupBy::Model,
-        );
-        assert_eq!(entries.len(), 2);
-        let (total_input, _total_output, total_cache_read, _total_cache_write) =
-            super::model_report_token_totals(&entries);
-        assert_eq!(total_input, i64::MAX);
-        assert_eq!(total_cache_read, i64::MAX);
+        for group_by in [GroupBy::ClientModel, GroupBy::ClientProviderModel] {
+            let entries = aggregate_model_usage_entries(
+                vec![make("anthropic"), make("Anthropic"), make("anthropic-beta")],
+                &group_by,
+            );
+            assert_eq!(entries.len(), 1, "{group_by}");
+            assert_eq!(entries[0].provider, "anthropic", "{group_by}");
+            assert_eq!(
+                entries[0].raw_provider, "Anthropic, anthropic, anthropic-beta",
+                "{group_by}"
+            );
+            assert_eq!(entries[0].message_count, 3, "{group_by}");
+        }
     }
 
-    fn make_workspace_message(
-        client: &str,
-        model_id: &str,
-        provider_id: &str,
-        session_id: &str,
-        cost: f64,
-        workspace_key: Option<&str>,
-        workspace_label: Option<&str>,
-    ) -> UnifiedMessage {
-        let mut msg = UnifiedMessage::new(
-            client,
-            model_id,
-            provider_id,
-            session_id,
-            1_733_011_200_000,
-            TokenBreakdown {
-                input: 10,
-                output: 5,
-                cache_read: 0,
-                cache_write: 0,
-                reasoning: 0,
-            },
-            cost,
+    #[test]
+    fn merge_providers_collapses_client_provider_model_rows() {
+        let make = |provider: &str| {
+            UnifiedMessage::new(
+                "opencode",
+                "claude-sonnet-4-5",
+                provider,
+                "session-a",
+                1_733_011_200_000,
+                TokenBreakdown {
+                    input: 10,
+                    output: 5,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                0.01,
+            )
+        };
+        let messages = || {
+            vec![
+                make("openrouter"),
+                make("anthropic-beta"),
+                make("anthropic"),
+            ]
+        };
+
+        let split = aggregate_model_usage_entries(messages(), &GroupBy::ClientProviderModel);
+        assert_eq!(split.len(), 2);
+
+        let merged = aggregate_model_usage_entries(
+            messages(),
+            &GroupBy::ClientProviderModel.with_merged_providers(true),
         );
-        msg.set_workspace(
-            workspace_key.map(str::to_string),
-            workspace_label.map(str::to_string),
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].client, "opencode");
+        assert_eq!(merged[0].provider, "anthropic, openrouter");
+        assert_eq!(
+            merged[0].raw_provider,
+            "anthropic, anthropic-beta, openrouter"
         );
-        msg
-    }
+        assert_eq!(merged[0].message_count, 3);
 
-    fn make_workbuddy_message(
-        session_id: &str,
-        timestamp: i64,
-        input: i64,
-        dedup_key: &str,
-    ) -> UnifiedMessage {
-        let mut msg = UnifiedMessage::new(
-            "workbuddy",
-            "glm-5.2",
-            "zai",
-            session_id,
-            timestamp,
-            TokenBreakdown {
-                input,
-                output: 0,
-                cache_read: 0,
-                cache_write: 0,
-                reasoning: 0,
-            },
-            0.0,
+        assert_eq!(
+            GroupBy::ClientProviderModel.with_merged_providers(false),
+            GroupBy::ClientProviderModel
         );
-        msg.dedup_key = Some(dedup_key.to_string());
-        msg
+        assert_eq!(GroupBy::Model.with_merged_providers(true), GroupBy::Model);
     }
 
-    fn make_trae_message(
-        session_id: &str,
-        timestamp: i64,
-        dedup_key: Option<&str>,
-        cost: f64,
-    ) -> UnifiedMessage {
-        UnifiedMessage::new_with_dedup(
-            "trae",
-            "gpt-5.2",
-            "openai",
-            session_id,
-            timestamp,
-            TokenBreakdown {
-                input: 10,
-                output: 5,
-                cache_read: 0,
-                cache_write: 0,
-                reasoning: 0,
-            },
+    #[test]
+    fn sort_model_usage_orders_by_each_key_and_direction() {
+        let make = |model: &str, input: i64, output: i64, messages: i32, cost: f64| ModelUsage {
+            client: "claude".to_string(),
+            merged_clients: None,
+            workspace_key: None,
+            workspace_label: None,
+            session_id: None,
+            model: model.to_string(),
+            provider: "anthropic".to_string(),
+            raw_provider: "anthropic".to_string(),
+            input,
+            output,
+            cache_read: 0,
+            cache_write: 0,
+            reasoning: 0,
+            message_count: messages,
+            session_count: 1,
+            first_date: "2024-12-01".to_string(),
+            last_date: "2024-12-01".to_string(),
             cost,
-            dedup_key.map(str::to_string),
-        )
+            pricing_source: None,
+            cost_comparison: None,
+            estimated_savings: 0.0,
+            request_bytes: None,
+            response_bytes: None,
+            performance: ModelPerformance::default(),
+        };
+        let fixture = || {
+            vec![
+                make("beta", 100, 10, 3, 2.0),
+                make("alpha", 10, 300, 1, 2.0),
+                make("gamma", 50, 5, 7, f64::NAN),
+                make("delta", 400, 0, 2, 0.5),
+            ]
+        };
+        let order_of = |key: ModelOrderKey, descending: Option<bool>| {
+            let mut entries = fixture();
+            sort_model_usage(&mut entries, ModelOrder::new(key, descending));
+            entries.into_iter().map(|e| e.model).collect::<Vec<_>>()
+        };
+
+        // Cost ties fall back to model name; NaN stays last both ways.
+        assert_eq!(
+            order_of(ModelOrderKey::Cost, None),
+            ["alpha", "beta", "delta", "gamma"]
+        );
+        assert_eq!(
+            order_of(ModelOrderKey::Cost, Some(false)),
+            ["delta", "alpha", "beta", "gamma"]
+        );
+        assert_eq!(
+            order_of(ModelOrderKey::Tokens, None),
+            ["delta", "alpha", "beta", "gamma"]
+        );
+        assert_eq!(
+            order_of(ModelOrderKey::Tokens, Some(false)),
+            ["gamma", "beta", "alpha", "delta"]
+        );
+        assert_eq!(
+            order_of(ModelOrderKey::Messages, None),
+            ["gamma", "beta", "delta", "alpha"]
+        );
+        assert_eq!(
+            order_of(ModelOrderKey::Messages, Some(false)),
+            ["alpha", "delta", "beta", "gamma"]
+        );
+        assert_eq!(
+            order_of(ModelOrderKey::Model, None),
+            ["alpha", "beta", "delta", "gamma"]
+        );
+        assert_eq!(
+            order_of(ModelOrderKey::Model, Some(true)),
+            ["gamma", "delta", "beta", "alpha"]
+        );
+        assert_eq!(
+            order_of(ModelOrderKey::Input, None),
+            ["delta", "beta", "gamma", "alpha"]
+        );
+        assert_eq!(
+            order_of(ModelOrderKey::Input, Some(false)),
+            ["alpha", "gamma", "beta", "delta"]
+        );
+        assert_eq!(
+            order_of(ModelOrderKey::Output, None),
+            ["alpha", "beta", "gamma", "delta"]
+        );
+        assert_eq!(
+            order_of(ModelOrderKey::Output, Some(false)),
+            ["delta", "gamma", "beta", "alpha"]
+        );
     }
 
     #[test]
-    fn workbuddy_fallback_dedups_by_session_not_date() {
-        const DAY1: i64 = 1_782_883_200_000;
-        const DAY2: i64 = 1_782_969_600_000;
+    fn model_order_key_parses_and_rejects_unknown_keys() {
+        for key in [
+            ModelOrderKey::Cost,
+            ModelOrderKey::Tokens,
+            ModelOrderKey::Messages,
+            ModelOrderKey::Model,
+            ModelOrderKey::Input,
+            ModelOrderKey::Output,
+        ] {
+            assert_eq!(ModelOrderKey::from_str(&key.to_string()).unwrap(), key);
+        }
+        assert_eq!(
+            ModelOrderKey::from_str(" Tokens ").unwrap(),
+            ModelOrderKey::Tokens
+        );
+        assert!(ModelOrderKey::from_str("date").is_err());
+        assert_eq!(
+            ModelOrder::default(),
+            ModelOrder::new(ModelOrderKey::Cost, Some(true))
+        );
+    }
 
-        // Session A has detailed coverage on DAY1.
-        let detailed = vec![make_workbuddy_message(
-            "sess-A",
-            DAY1,
-            100,
-            "workbuddy:detailed-A",
-        )];
-        let fallback = vec![
-            // Session A's cumulative SQLite aggregate is dated DAY2 (updated_at)
-            // even though its detailed activity was DAY1. The old date-overlap
-            // check kept it, double-counting the whole session on DAY2.
-            make_workbuddy_message("sess-A", DAY2, 5000, "workbuddy:fallback-A"),
-            // Session B has NO detailed coverage but its aggregate shares DAY1
-            // with session A's detail. The old check dropped it, losing usage.
-            make_workbuddy_message("sess-B", DAY1, 2000, "workbuddy:fallback-B"),
-        ];
+    #[test]
+    fn model_usage_family_grouping_rolls_up_models() {
+        let make = |client: &str, model: &str, input: i64| {
+            UnifiedMessage::new(
+                client,
+                model,
+                "anthropic",
+                "session-a",
+                1_733_011_200_000,
+                TokenBreakdown {
+                    input,
+                    output: 0,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                0.01,
+            )
+        };
 
-        let merged = super::merge_workbuddy_messages(detailed, fallback);
+        let mut entries = aggregate_model_usage_entries(
+            vec![
+                make("claude", "claude-opus-4-5", 10),
+                make("opencode", "claude-sonnet-4-5", 20),
+                make("claude", "claude-haiku-4-5", 30),
+                make("codex", "gpt-5-codex", 40),
+                make("codex", "my-local-model", 50),
+            ],
+            &GroupBy::Family,
+        );
+        entries.sort_by(|a, b| a.model.cmp(&b.model));
 
-        // Detailed A kept; fallback A dropped (session covered); fallback B kept.
-        assert_eq!(merged.len(), 2);
-        assert!(merged
+        let rows: Vec<(&str, i64)> = entries
             .iter()
-            .any(|message| message.dedup_key.as_deref() == Some("workbuddy:detailed-A")));
-        assert!(merged
-            .iter()
-            .any(|message| message.dedup_key.as_deref() == Some("workbuddy:fallback-B")));
-        assert!(!merged
+            .map(|e| (e.model.as_str(), e.input))
+            .collect();
+        assert_eq!(rows, vec![("claude", 60), ("gpt", 40), ("other", 50)]);
+        assert_eq!(
+            entries[0].merged_clients.as_deref(),
+            Some("claude, opencode")
+        );
+    }
+
+    #[test]
+    fn session_usage_ranks_sessions_by_cost() {
+        let make = |client: &str, session: &str, model: &str, day: i64, cost: f64| {
+            UnifiedMessage::new(
+                client,
+                model,
+                "anthropic",
+                session,
+                1_733_011_200_000 + day * 86_400_000,
+                TokenBreakdown {
+                    input: 100,
+                    output: 50,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                cost,
+            )
+        };
+
+        let entries = aggregate_session_usage(vec![
+            make("claude", "cheap", "claude-haiku-4-5", 0, 0.10),
+            make("opencode", "pricey", "claude-sonnet-4-5", 2, 0.50),
+            make("opencode", "pricey", "claude-opus-4-5", 1, 1.25),
+            make("claude", "cheap", "claude-haiku-4-5", 3, 0.20),
+            // Same session id under another client is a different session.
+            make("claude", "pricey", "claude-haiku-4-5", 0, 0.05),
+        ]);
+
+        let ranking: Vec<(&str, &str)> = entries
             .iter()
-            .any(|message| message.dedup_key.as_deref() == Some("workbuddy:fallback-A")));
+            .map(|e| (e.client.as_str(), e.session_id.as_str()))
+            .collect();
+        assert_eq!(
+            ranking,
+            vec![
+                ("opencode", "pricey"),
+                ("claude", "cheap"),
+                ("claude", "pricey")
+            ]
+        );
+
+        let top = &entries[0];
+        assert!((top.cost - 1.75).abs() < 1e-9);
+        assert_eq!(top.message_count, 2);
+        assert_eq!(top.total_tokens, 300);
+        assert_eq!(top.dominant_model, "claude-opus-4-5");
+        assert_eq!(top.first_date, "2024-12-02");
+        assert_eq!(top.last_date, "2024-12-03");
+        assert_eq!(entries[1].first_date, "2024-12-01");
+        assert_eq!(entries[1].last_date, "2024-12-04");
     }
 
     #[test]
-    fn workbuddy_fallback_kept_when_no_detailed_messages() {
-        // With zero detailed coverage, every fallback session survives.
-        let fallback = vec![
-            make_workbuddy_message("sess-A", 1_782_883_200_000, 1000, "workbuddy:fallback-A"),
-            make_workbuddy_message("sess-B", 1_782_969_600_000, 2000, "workbuddy:fallback-B"),
-        ];
+    fn session_usage_flags_openclaw_sessions_whose_reported_cost_diverges() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let transcript = |name: &str, reported_cost: f64| {
+            let path = temp_dir.path().join(format!("{name}.jsonl"));
+            let line = format!(
+                r#"{{"type":"message","message":{{"role":"assistant","model":"claude-sonnet-4-0","provider":"anthropic","usage":{{"input":100,"output":20,"totalTokens":120,"cost":{{"total":{reported_cost}}}}},"timestamp":1733011200000}}}}"#
+            );
+            std::fs::write(&path, line).unwrap();
+            crate::sessions::openclaw::parse_openclaw_transcript(&path)
+        };
+        let mut messages = transcript("steady", 0.14);
+        messages.extend(transcript("drifted", 0.50));
 
-        let merged = super::merge_workbuddy_messages(Vec::new(), fallback);
+        let litellm = HashMap::from([(
+            "claude-sonnet-4-0".to_string(),
+            pricing::ModelPricing {
+                input_cost_per_token: Some(0.001),
+                output_cost_per_token: Some(0.002),
+                ..Default::default()
+            },
+        )]);
+        let pricing = pricing::PricingService::new(litellm, HashMap::new());
+        apply_pricing_to_messages(&mut messages, Some(&pricing));
 
-        assert_eq!(merged.len(), 2);
+        let entries = aggregate_session_usage(messages);
+        let reconciliation = |session: &str| {
+            entries
+                .iter()
+                .find(|e| e.session_id == session)
+                .and_then(|e| e.reconciliation.clone())
+                .unwrap()
+        };
+
+        let steady = reconciliation("steady");
+        assert_eq!(steady.reported_tokens, Some(120));
+        assert_eq!(steady.counted_tokens, 120);
+        assert!(!steady.diverges(5.0));
+
+        // 100 * 0.001 + 20 * 0.002 = 0.14 against a reported 0.50.
+        let drifted = reconciliation("drifted");
+        assert_eq!(drifted.token_difference_pct(), Some(0.0));
+        let cost_pct = drifted.cost_difference_pct().unwrap();
+        assert!((cost_pct - (0.14 - 0.50) / 0.50 * 100.0).abs() < 1e-9);
+        assert!(drifted.diverges(5.0));
+        assert!(!drifted.diverges(80.0));
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn build_opencode_sqlite_payload(
-        created_ms: f64,
-        completed_ms: f64,
-        input: i64,
-        output: i64,
+    #[test]
+    fn model_usage_counts_distinct_sessions_per_group() {
+        let make = |client: &str, session: &str| {
+            UnifiedMessage::new(
+                client,
+                "claude-sonnet-4-5",
+                "anthropic",
+                session,
+                1_733_011_200_000,
+                TokenBreakdown {
+                    input: 10,
+                    output: 5,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                0.01,
+            )
+        };
+
+        let entries = aggregate_model_usage_entries(
+            vec![make("claude", "session-a"), make("claude", "session-a")],
+            &GroupBy::ClientModel,
+        );
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message_count, 2);
+        assert_eq!(entries[0].session_count, 1);
+
+        // Same session id under another client is a different session.
+        let entries = aggregate_model_usage_entries(
+            vec![
+                make("claude", "session-a"),
+                make("opencode", "session-a"),
+                make("opencode", "session-b"),
+            ],
+            &GroupBy::Model,
+        );
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].session_count, 3);
+    }
+
+    #[test]
+    fn model_usage_tracks_first_and_last_date_per_group() {
+        let make = |model: &str, date: &str| {
+            let mut msg = UnifiedMessage::new(
+                "claude",
+                model,
+                "anthropic",
+                "session-a",
+                1_733_011_200_000,
+                TokenBreakdown {
+                    input: 10,
+                    output: 5,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                0.01,
+            );
+            msg.date = date.to_string();
+            msg
+        };
+
+        let mut entries = aggregate_model_usage_entries(
+            vec![
+                make("claude-sonnet-4-5", "2024-12-03"),
+                make("claude-sonnet-4-5", "2024-11-28"),
+                make("claude-sonnet-4-5", "2025-01-15"),
+                make("claude-sonnet-4-5", "2024-12-31"),
+                make("claude-opus-4-1", "2024-12-10"),
+            ],
+            &GroupBy::Model,
+        );
+        entries.sort_by(|a, b| a.model.cmp(&b.model));
+        assert_eq!(entries.len(), 2);
+
+        // Single-message group: first and last are the same day.
+        assert_eq!(entries[0].model, "claude-opus-4-1");
+        assert_eq!(entries[0].first_date, "2024-12-10");
+        assert_eq!(entries[0].last_date, "2024-12-10");
+
+        assert_eq!(entries[1].model, "claude-sonnet-4-5");
+        assert_eq!(entries[1].first_date, "2024-11-28");
+        assert_eq!(entries[1].last_date, "2025-01-15");
+    }
+
+    #[test]
+    fn model_report_totals_saturate_across_groups() {
+        // aggregate_model_usage_entries saturates each entry's fields, so an
+        // entry can be i64::MAX. get_model_report sums the entries into the
+        // report-level totals via model_report_token_totals; two saturated
+        // entries (two distinct models) must not overflow that sum either.
+        let make = |model: &str| {
+            UnifiedMessage::new_with_dedup(
+                "antigravity-cli",
+                model,
+                "antigravity",
+                "session-overflow",
+                1_733_011_200_000,
+                TokenBreakdown {
+                    input: i64::MAX,
+                    output: 0,
+                    cache_read: i64::MAX,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                0.0,
+                None,
+            )
+        };
+
+        let entries = aggregate_model_usage_entries(
+            vec![make("gemini-3-pro"), make("claude-opus-4-6")],
+            &GroupBy::Model,
+        );
+        assert_eq!(entries.len(), 2);
+        let (total_input, _total_output, total_cache_read, _total_cache_write, _total_reasoning) =
+            super::model_report_token_totals(&entries);
+        assert_eq!(total_input, i64::MAX);
+        assert_eq!(total_cache_read, i64::MAX);
+    }
+
+    #[test]
+    fn model_report_thresholds_drop_rows_but_keep_totals() {
+        let make = |model: &str, input: i64, cost: f64| {
+            UnifiedMessage::new(
+                "claude",
+                model,
+                "anthropic",
+                "session-threshold",
+                1_733_011_200_000,
+                TokenBreakdown {
+                    input,
+                    output: 0,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                cost,
+            )
+        };
+        let entries = aggregate_model_usage_entries(
+            vec![
+                make("claude-opus-4-6", 1_000, 5.0),
+                make("claude-haiku-4-5", 10, 0.001),
+                make("claude-sonnet-4-5", 20, 0.5),
+            ],
+            &GroupBy::Model,
+        );
+        let (before_input, _, _, _, _) = super::model_report_token_totals(&entries);
+        let before_cost: f64 = entries.iter().map(|e| e.cost).sum();
+
+        let (kept, omitted) =
+            super::apply_model_report_thresholds(entries.clone(), Some(0.01), None, false);
+        assert_eq!(kept.len(), 2);
+        assert!(kept.iter().all(|e| e.model != "claude-haiku-4-5"));
+        let omitted = omitted.expect("haiku row falls below the cost threshold");
+        assert_eq!(omitted.entries, 1);
+        assert_eq!(omitted.input, 10);
+        assert!((omitted.cost - 0.001).abs() < 1e-12);
+
+        let kept_cost: f64 = kept.iter().map(|e| e.cost).sum();
+        assert!((kept_cost + omitted.cost - before_cost).abs() < 1e-12);
+        let kept_input: i64 = kept.iter().map(|e| e.input).sum();
+        assert_eq!(kept_input + omitted.input, before_input);
+
+        let (kept, omitted) =
+            super::apply_model_report_thresholds(entries.clone(), None, Some(15), false);
+        assert_eq!(kept.len(), 2);
+        assert_eq!(omitted.unwrap().input, 10);
+
+        let (kept, omitted) = super::apply_model_report_thresholds(entries, None, None, false);
+        assert_eq!(kept.len(), 3);
+        assert!(omitted.is_none());
+    }
+
+    #[test]
+    fn test_include_zero_cost_keeps_free_rows_and_orders_them_by_tokens() {
+        let make = |model: &str, input: i64, cost: f64| {
+            UnifiedMessage::new(
+                "copilot",
+                model,
+                "github_copilot",
+                "session-zero-cost",
+                1_733_011_200_000,
+                TokenBreakdown {
+                    input,
+                    output: 0,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                cost,
+            )
+        };
+        let mut entries = aggregate_model_usage_entries(
+            vec![
+                make("claude-opus-4-6", 1_000, 5.0),
+                make("a-small-free-model", 10, 0.0),
+                make("z-large-free-model", 50_000, 0.0),
+            ],
+            &GroupBy::Model,
+        );
+        super::sort_zero_cost_by_tokens(&mut entries);
+        let order: Vec<&str> = entries.iter().map(|e| e.model.as_str()).collect();
+        assert_eq!(
+            order,
+            vec![
+                "claude-opus-4-6",
+                "z-large-free-model",
+                "a-small-free-model"
+            ]
+        );
+
+        let (kept, omitted) =
+            super::apply_model_report_thresholds(entries.clone(), Some(0.01), None, true);
+        assert_eq!(kept.len(), 3);
+        assert!(omitted.is_none());
+
+        let (kept, omitted) =
+            super::apply_model_report_thresholds(entries, Some(0.01), None, false);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(omitted.unwrap().entries, 2);
+    }
+
+    fn make_workspace_message(
+        client: &str,
+        model_id: &str,
+        provider_id: &str,
+        session_id: &str,
+        cost: f64,
+        workspace_key: Option<&str>,
+        workspace_label: Option<&str>,
+    ) -> UnifiedMessage {
+        let mut msg = UnifiedMessage::new(
+            client,
+            model_id,
+            provider_id,
+            session_id,
+            1_733_011_200_000,
+            TokenBreakdown {
+                input: 10,
+                output: 5,
+                cache_read: 0,
+                cache_write: 0,
+                reasoning: 0,
+                ..Default::default()
+            },
+            cost,
+        );
+        msg.set_workspace(
+            workspace_key.map(str::to_string),
+            workspace_label.map(str::to_string),
+        );
+        msg
+    }
+
+    fn make_workbuddy_message(
+        session_id: &str,
+        timestamp: i64,
+        input: i64,
+        dedup_key: &str,
+    ) -> UnifiedMessage {
+        let mut msg = UnifiedMessage::new(
+            "workbuddy",
+            "glm-5.2",
+            "zai",
+            session_id,
+            timestamp,
+            TokenBreakdown {
+                input,
+                output: 0,
+                cache_read: 0,
+                cache_write: 0,
+                reasoning: 0,
+                ..Default::default()
+            },
+            0.0,
+        );
+        msg.dedup_key = Some(dedup_key.to_string());
+        msg
+    }
+
+    fn make_trae_message(
+        session_id: &str,
+        timestamp: i64,
+        dedup_key: Option<&str>,
+        cost: f64,
+    ) -> UnifiedMessage {
+        UnifiedMessage::new_with_dedup(
+            "trae",
+            "gpt-5.2",
+            "openai",
+            session_id,
+            timestamp,
+            TokenBreakdown {
+                input: 10,
+                output: 5,
+                cache_read: 0,
+                cache_write: 0,
+                reasoning: 0,
+                ..Default::default()
+            },
+            cost,
+            dedup_key.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn workbuddy_fallback_dedups_by_session_not_date() {
+        const DAY1: i64 = 1_782_883_200_000;
+        const DAY2: i64 = 1_782_969_600_000;
+
+        // Session A has detailed coverage on DAY1.
+        let detailed = vec![make_workbuddy_message(
+            "sess-A",
+            DAY1,
+            100,
+            "workbuddy:detailed-A",
+        )];
+        let fallback = vec![
+            // Session A's cumulative SQLite aggregate is dated DAY2 (updated_at)
+            // even though its detailed activity was DAY1. The old date-overlap
+            // check kept it, double-counting the whole session on DAY2.
+            make_workbuddy_message("sess-A", DAY2, 5000, "workbuddy:fallback-A"),
+            // Session B has NO detailed coverage but its aggregate shares DAY1
+            // with session A's detail. The old check dropped it, losing usage.
+            make_workbuddy_message("sess-B", DAY1, 2000, "workbuddy:fallback-B"),
+        ];
+
+        let merged = super::merge_workbuddy_messages(detailed, fallback);
+
+        // Detailed A kept; fallback A dropped (session covered); fallback B kept.
+        assert_eq!(merged.len(), 2);
+        assert!(merged
+            .iter()
+            .any(|message| message.dedup_key.as_deref() == Some("workbuddy:detailed-A")));
+        assert!(merged
+            .iter()
+            .any(|message| message.dedup_key.as_deref() == Some("workbuddy:fallback-B")));
+        assert!(!merged
+            .iter()
+            .any(|message| message.dedup_key.as_deref() == Some("workbuddy:fallback-A")));
+    }
+
+    #[test]
+    fn workbuddy_fallback_kept_when_no_detailed_messages() {
+        // With zero detailed coverage, every fallback session survives.
+        let fallback = vec![
+            make_workbuddy_message("sess-A", 1_782_883_200_000, 1000, "workbuddy:fallback-A"),
+            make_workbuddy_message("sess-B", 1_782_969_600_000, 2000, "workbuddy:fallback-B"),
+        ];
+
+        let merged = super::merge_workbuddy_messages(Vec::new(), fallback);
+
+        assert_eq!(merged.len(), 2);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn build_opencode_sqlite_payload(
+        created_ms: f64,
+        completed_ms: f64,
+        input: i64,
+        output: i64,
         reasoning: i64,
         cache_read: i64,
         cache_write: i64,
@@ -4221,24 +6586,26 @@ mod tests {
         assert_eq!(GroupBy::from_str("session").unwrap(), GroupBy::Session);
         assert_eq!(
             GroupBy::from_str("session,model").unwrap(),
-            GroupBy::Session
+            GroupBy::SessionModel
         );
         assert_eq!(
             GroupBy::from_str("session-model").unwrap(),
-            GroupBy::Session
+            GroupBy::SessionModel
         );
         assert_eq!(
             GroupBy::from_str("client,session").unwrap(),
-            GroupBy::ClientSession
+            GroupBy::ClientSessionModel
         );
         assert_eq!(
             GroupBy::from_str("client,session,model").unwrap(),
-            GroupBy::ClientSession
+            GroupBy::ClientSessionModel
         );
         assert_eq!(
             GroupBy::from_str("client-session-model").unwrap(),
-            GroupBy::ClientSession
+            GroupBy::ClientSessionModel
         );
+        assert_eq!(GroupBy::from_str("family").unwrap(), GroupBy::Family);
+        assert_eq!(GroupBy::from_str("model-family").unwrap(), GroupBy::Family);
         assert!(GroupBy::from_str("unknown").is_err());
     }
 
@@ -4255,7 +6622,9 @@ mod tests {
             GroupBy::ClientProviderModel,
             GroupBy::WorkspaceModel,
             GroupBy::Session,
-            GroupBy::ClientSession,
+            GroupBy::SessionModel,
+            GroupBy::ClientSessionModel,
+            GroupBy::Family,
         ];
 
         for variant in variants {
@@ -4299,6 +6668,7 @@ mod tests {
             cache_read: 25,
             cache_write: 0,
             reasoning: 25,
+            ..Default::default()
         };
         timed.duration_ms = Some(400);
 
@@ -4317,6 +6687,7 @@ mod tests {
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         };
 
         let entries = aggregate_model_usage_entries(vec![timed, untimed], &GroupBy::ClientModel);
@@ -4491,6 +6862,85 @@ mod tests {
         assert_eq!(entries[0].cost, 3.0);
     }
 
+    #[test]
+    fn test_project_grouping_merges_models_and_filters_by_project() {
+        let messages = vec![
+            make_workspace_message(
+                "claude",
+                "claude-sonnet-4-5-20250929",
+                "anthropic",
+                "session-1",
+                1.0,
+                Some("/repo-a"),
+                Some("repo-a"),
+            ),
+            make_workspace_message(
+                "codex",
+                "gpt-5",
+                "openai",
+                "session-2",
+                3.0,
+                Some("/repo-a"),
+                Some("repo-a"),
+            ),
+            make_workspace_message(
+                "claude",
+                "claude-sonnet-4-5-20250929",
+                "anthropic",
+                "session-3",
+                2.0,
+                Some("/repo-b"),
+                Some("repo-b"),
+            ),
+            make_workspace_message(
+                "claude",
+                "claude-sonnet-4-5-20250929",
+                "anthropic",
+                "session-4",
+                0.5,
+                None,
+                None,
+            ),
+        ];
+
+        let mut entries = aggregate_model_usage_entries(messages.clone(), &GroupBy::Project);
+        entries.sort_by(|a, b| a.workspace_label.cmp(&b.workspace_label));
+        let rows: Vec<_> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.workspace_label.as_deref().unwrap(),
+                    e.model.as_str(),
+                    e.cost,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (UNKNOWN_PROJECT_LABEL, "claude-sonnet-4-5", 0.5),
+                ("repo-a", "gpt-5", 4.0),
+                ("repo-b", "claude-sonnet-4-5", 2.0),
+            ]
+        );
+        assert_eq!(entries[1].merged_clients.as_deref(), Some("claude, codex"));
+
+        let filter = ProjectFilter::new(["Repo-A".to_string(), "/repo-b".to_string()]);
+        let kept: Vec<_> = messages
+            .iter()
+            .filter(|m| filter.matches(m))
+            .map(|m| m.session_id.as_str())
+            .collect();
+        assert_eq!(kept, vec!["session-1", "session-2", "session-3"]);
+
+        let unknown = ProjectFilter::new([UNKNOWN_PROJECT_LABEL.to_string()]);
+        assert!(messages
+            .iter()
+            .filter(|m| unknown.matches(m))
+            .all(|m| m.session_id == "session-4"));
+        assert!(ProjectFilter::default().matches(&messages[0]));
+    }
+
     #[test]
     fn test_parsed_round_trip_preserves_workspace_metadata() {
         let mut unified = UnifiedMessage::new(
@@ -4505,6 +6955,7 @@ mod tests {
                 cache_read: 2,
                 cache_write: 0,
                 reasoning: 1,
+                ..Default::default()
             },
             1.25,
         );
@@ -4572,7 +7023,7 @@ mod tests {
     fn test_session_grouping_merges_same_session_and_model() {
         // Two messages with the same session_id + same model — should collapse
         // into one row regardless of the client that produced them, because
-        // GroupBy::Session keys on (session_id, model) only.
+        // GroupBy::SessionModel keys on (session_id, model) only.
         let entries = aggregate_model_usage_entries(
             vec![
                 make_workspace_message(
@@ -4594,7 +7045,7 @@ mod tests {
                     None,
                 ),
             ],
-            &GroupBy::Session,
+            &GroupBy::SessionModel,
         );
 
         assert_eq!(entries.len(), 1);
@@ -4615,7 +7066,7 @@ mod tests {
                 make_workspace_message("codex", "gpt-5", "openai", "session-a", 1.0, None, None),
                 make_workspace_message("codex", "gpt-5", "openai", "session-b", 2.0, None, None),
             ],
-            &GroupBy::Session,
+            &GroupBy::SessionModel,
         );
 
         assert_eq!(entries.len(), 2);
@@ -4626,11 +7077,52 @@ mod tests {
         assert_eq!(session_ids, HashSet::from(["session-a", "session-b"]));
     }
 
+    #[test]
+    fn test_session_only_grouping_collapses_models_into_one_row() {
+        let mut earlier = make_workspace_message(
+            "claude",
+            "claude-haiku-4-5",
+            "anthropic",
+            "ses-1",
+            0.5,
+            None,
+            None,
+        );
+        earlier.date = "2024-11-30".to_string();
+        let entries = aggregate_model_usage_entries(
+            vec![
+                make_workspace_message(
+                    "claude",
+                    "claude-sonnet-4-5-20250929",
+                    "anthropic",
+                    "ses-1",
+                    2.0,
+                    None,
+                    None,
+                ),
+                earlier,
+                // Same id under another client is a different session.
+                make_workspace_message("amp", "gpt-5", "openai", "ses-1", 1.0, None, None),
+            ],
+            &GroupBy::Session,
+        );
+
+        assert_eq!(entries.len(), 2);
+        let claude = entries.iter().find(|e| e.client == "claude").unwrap();
+        assert_eq!(claude.session_id.as_deref(), Some("ses-1"));
+        assert_eq!(claude.model, "claude-sonnet-4-5");
+        assert_eq!(claude.message_count, 2);
+        assert_eq!(claude.input, 20);
+        assert!((claude.cost - 2.5).abs() < f64::EPSILON);
+        assert_eq!(claude.first_date, "2024-11-30");
+        assert_eq!(claude.session_count, 1);
+    }
+
     #[test]
     fn test_client_session_grouping_keeps_clients_separate() {
         // Same session_id seen by two different clients (unusual in practice
-        // but possible if parsers collide on an id space). ClientSession
-        // must yield two rows; Session would yield one (covered above).
+        // but possible if parsers collide on an id space). ClientSessionModel
+        // must yield two rows; SessionModel would yield one (covered above).
         let entries = aggregate_model_usage_entries(
             vec![
                 make_workspace_message(
@@ -4652,7 +7144,7 @@ mod tests {
                     None,
                 ),
             ],
-            &GroupBy::ClientSession,
+            &GroupBy::ClientSessionModel,
         );
 
         assert_eq!(entries.len(), 2);
@@ -4889,6 +7381,43 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_parse_all_messages_with_pricing_reads_legacy_moltbot_transcripts() {
+        let cache_home = tempfile::TempDir::new().unwrap();
+        let source_home = tempfile::TempDir::new().unwrap();
+        let original_home = std::env::var("HOME").ok();
+        std::env::set_var("HOME", cache_home.path());
+
+        {
+            let sessions = source_home.path().join(".moltbot/agents/main/sessions");
+            std::fs::create_dir_all(&sessions).unwrap();
+            std::fs::write(
+                sessions.join("legacy-session.jsonl"),
+                r#"{"type":"model_change","provider":"anthropic","modelId":"claude-sonnet-4-5"}
+{"type":"message","id":"msg1","message":{"role":"assistant","content":[],"usage":{"input":120,"output":30,"cacheRead":0,"cacheWrite":0},"timestamp":1700000000000}}
+{"type":"message","id":"msg2","message":{"role":"assistant","content":[],"usage":{"input":80,"output":20,"cacheRead":0,"cacheWrite":0},"timestamp":1700000060000}}"#,
+            )
+            .unwrap();
+
+            let messages = parse_all_messages_with_pricing(
+                source_home.path().to_str().unwrap(),
+                &["openclaw".to_string()],
+                None,
+            );
+
+            assert_eq!(messages.len(), 2);
+            assert!(messages.iter().all(|m| m.client == "openclaw"));
+            assert!(messages.iter().all(|m| m.session_id == "legacy-session"));
+            assert_eq!(messages.iter().map(|m| m.tokens.input).sum::<i64>(), 200);
+        }
+
+        match original_home {
+            Some(home) => std::env::set_var("HOME", home),
+            None => std::env::remove_var("HOME"),
+        }
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_parse_all_messages_with_pricing_kimi_deduplicates_repeated_status_updates() {
@@ -4934,8 +7463,10 @@ mod tests {
                 clients: Some(vec!["kimi".to_string()]),
                 since: None,
                 until: None,
+                until_exclusive: false,
                 year: None,
                 scanner_settings: scanner::ScannerSettings::default(),
+                ..Default::default()
             })
             .unwrap();
 
@@ -4984,6 +7515,7 @@ mod tests {
                     cache_read: 0,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 0.0,
             );
@@ -5022,6 +7554,7 @@ mod tests {
                         cache_read: 0,
                         cache_write: 0,
                         reasoning: 0,
+                        ..Default::default()
                     },
                     0.0,
                 )
@@ -5035,6 +7568,63 @@ mod tests {
         }
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_source_cache_serves_unchanged_files_on_warm_load() {
+        let cache_home = tempfile::TempDir::new().unwrap();
+        let source_home = tempfile::TempDir::new().unwrap();
+        let original_home = std::env::var("HOME").ok();
+        std::env::set_var("HOME", cache_home.path());
+
+        {
+            let message_dir = source_home
+                .path()
+                .join(".local/share/opencode/storage/message/project-1");
+            std::fs::create_dir_all(&message_dir).unwrap();
+            for (id, input) in [("msg_001", 10), ("msg_002", 20)] {
+                std::fs::write(
+                    message_dir.join(format!("{id}.json")),
+                    format!(
+                        r#"{{"id":"{id}","sessionID":"session-1","role":"assistant","modelID":"claude-sonnet-4-5","providerID":"anthropic","cost":0.5,"tokens":{{"input":{input},"output":5,"reasoning":0,"cache":{{"read":0,"write":0}}}},"time":{{"created":1733011200000}}}}"#
+                    ),
+                )
+                .unwrap();
+            }
+            let home = source_home.path().to_str().unwrap();
+            let clients = ["opencode".to_string()];
+            let sorted_inputs = |messages: &[UnifiedMessage]| {
+                let mut inputs: Vec<i64> = messages.iter().map(|m| m.tokens.input).collect();
+                inputs.sort_unstable();
+                inputs
+            };
+
+            let hits_before = message_cache::source_cache_hit_count();
+            let cold = parse_all_messages_with_pricing(home, &clients, None);
+            assert_eq!(message_cache::source_cache_hit_count(), hits_before);
+
+            let warm = parse_all_messages_with_pricing(home, &clients, None);
+            assert_eq!(message_cache::source_cache_hit_count(), hits_before + 2);
+            assert_eq!(sorted_inputs(&warm), sorted_inputs(&cold));
+            assert_eq!(warm.iter().map(|m| m.cost).sum::<f64>(), 1.0);
+
+            // Growing one file changes its size, so only the other file hits.
+            std::fs::write(
+                message_dir.join("msg_002.json"),
+                r#"{"id":"msg_002","sessionID":"session-1","role":"assistant","modelID":"claude-sonnet-4-5","providerID":"anthropic","cost":0.5,"tokens":{"input":200,"output":5,"reasoning":0,"cache":{"read":0,"write":0}},"time":{"created":1733011200000}}"#,
+            )
+            .unwrap();
+            let hits_before = message_cache::source_cache_hit_count();
+            let reloaded = parse_all_messages_with_pricing(home, &clients, None);
+            assert_eq!(message_cache::source_cache_hit_count(), hits_before + 1);
+            assert_eq!(sorted_inputs(&reloaded), vec![10, 200]);
+        }
+
+        match original_home {
+            Some(home) => std::env::set_var("HOME", home),
+            None => std::env::remove_var("HOME"),
+        }
+    }
+
     #[cfg(unix)]
     #[test]
     #[serial_test::serial]
@@ -5487,8 +8077,10 @@ mod tests {
                 clients: Some(vec!["opencode".to_string()]),
                 since: None,
                 until: None,
+                until_exclusive: false,
                 year: None,
                 scanner_settings: scanner::ScannerSettings::default(),
+                ..Default::default()
             })
             .unwrap();
 
@@ -5843,6 +8435,56 @@ mod tests {
         }
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_parse_all_messages_with_pricing_codex_deduplicates_untimestamped_overlapping_rollouts()
+    {
+        let cache_home = tempfile::TempDir::new().unwrap();
+        let source_home = tempfile::TempDir::new().unwrap();
+        let original_home = std::env::var("HOME").ok();
+        std::env::set_var("HOME", cache_home.path());
+
+        {
+            // Older rollouts carry neither event timestamps nor cumulative
+            // totals, so only the event's position identifies it. The second
+            // file is a longer copy of the first (a re-run rollout).
+            let sessions_dir = source_home.path().join(".codex/sessions");
+            std::fs::create_dir_all(&sessions_dir).unwrap();
+            let prefix = concat!(
+                r#"{"type":"session_meta","payload":{"id":"66666666-6666-7666-8666-666666666666","source":"interactive","model_provider":"openai","cwd":"/repo"}}"#,
+                "\n",
+                r#"{"type":"turn_context","payload":{"model":"gpt-5.5"}}"#,
+                "\n",
+                r#"{"type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":40,"output_tokens":4,"total_tokens":44}}}}"#,
+                "\n",
+                r#"{"type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":60,"output_tokens":6,"total_tokens":66}}}}"#,
+                "\n"
+            );
+            let extended = format!(
+                "{}{}\n",
+                prefix,
+                r#"{"type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":10,"output_tokens":1,"total_tokens":11}}}}"#
+            );
+            std::fs::write(sessions_dir.join("rollout-a.jsonl"), prefix).unwrap();
+            std::fs::write(sessions_dir.join("rollout-b.jsonl"), extended).unwrap();
+
+            let messages = parse_all_messages_with_pricing(
+                source_home.path().to_str().unwrap(),
+                &["codex".to_string()],
+                None,
+            );
+
+            assert_eq!(messages.len(), 3);
+            assert_eq!(messages.iter().map(|m| m.tokens.input).sum::<i64>(), 110);
+            assert_eq!(messages.iter().map(|m| m.tokens.output).sum::<i64>(), 11);
+        }
+
+        match original_home {
+            Some(home) => std::env::set_var("HOME", home),
+            None => std::env::remove_var("HOME"),
+        }
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_parse_all_messages_with_pricing_codex_deduplicates_parent_replay_across_forks() {
@@ -5973,8 +8615,10 @@ mod tests {
                 clients: Some(vec!["codex".to_string()]),
                 since: None,
                 until: None,
+                until_exclusive: false,
                 year: None,
                 scanner_settings: scanner::ScannerSettings::default(),
+                ..Default::default()
             })
             .unwrap();
 
@@ -6529,23 +9173,129 @@ mod tests {
                 .as_bytes(),
             )
             .unwrap();
-            file.flush().unwrap();
-
-            let warm_messages = parse_all_messages_with_pricing(
-                source_home.path().to_str().unwrap(),
-                &["codex".to_string()],
-                None,
-            );
+            file.flush().unwrap();
+
+            let warm_messages = parse_all_messages_with_pricing(
+                source_home.path().to_str().unwrap(),
+                &["codex".to_string()],
+                None,
+            );
+
+            std::env::set_var("HOME", fresh_cache_home.path());
+            let fresh_messages = parse_all_messages_with_pricing(
+                source_home.path().to_str().unwrap(),
+                &["codex".to_string()],
+                None,
+            );
+
+            assert_eq!(warm_messages, fresh_messages);
+            assert_eq!(warm_messages.len(), 2);
+        }
+
+        match original_home {
+            Some(home) => std::env::set_var("HOME", home),
+            None => std::env::remove_var("HOME"),
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_source_cache_does_not_reuse_priced_cost_without_pricing_service() {
+        let temp_home = tempfile::TempDir::new().unwrap();
+        let source_home = tempfile::TempDir::new().unwrap();
+        let original_home = std::env::var("HOME").ok();
+        std::env::set_var("HOME", temp_home.path());
+        {
+            let cursor_cache_dir = source_home.path().join(".config/tokscale/cursor-cache");
+            std::fs::create_dir_all(&cursor_cache_dir).unwrap();
+
+            let csv = r#"Date,Kind,Model,Max Mode,Input (w/ Cache Write),Input (w/o Cache Write),Cache Read,Output Tokens,Total Tokens,Cost
+"2026-03-04T12:00:00.000Z","Included","Composer 1.5","No","1200","1000","5000","2000","8000","0""#;
+            std::fs::write(cursor_cache_dir.join("usage.csv"), csv).unwrap();
+
+            let mut litellm = HashMap::new();
+            litellm.insert(
+                "Composer 1.5".into(),
+                pricing::ModelPricing {
+                    input_cost_per_token: Some(0.001),
+                    output_cost_per_token: Some(0.002),
+                    cache_read_input_token_cost: Some(0.0005),
+                    ..Default::default()
+                },
+            );
+            let pricing = pricing::PricingService::new(litellm, HashMap::new());
+
+            let repriced_messages = parse_all_messages_with_pricing(
+                source_home.path().to_str().unwrap(),
+                &["cursor".to_string()],
+                Some(&pricing),
+            );
+            assert_eq!(repriced_messages.len(), 1);
+            assert!(repriced_messages[0].cost > 0.0);
+
+            let cached_messages = parse_all_messages_with_pricing(
+                source_home.path().to_str().unwrap(),
+                &["cursor".to_string()],
+                None,
+            );
+
+            assert_eq!(cached_messages.len(), 1);
+            assert_eq!(cached_messages[0].cost, 0.0);
+        }
+
+        match original_home {
+            Some(home) => std::env::set_var("HOME", home),
+            None => std::env::remove_var("HOME"),
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_cursor_messages_carry_account_label_per_cache_file() {
+        let temp_home = tempfile::TempDir::new().unwrap();
+        let source_home = tempfile::TempDir::new().unwrap();
+        let original_home = std::env::var("HOME").ok();
+        std::env::set_var("HOME", temp_home.path());
+        {
+            let config_dir = source_home.path().join(".config/tokscale");
+            let cursor_cache_dir = config_dir.join("cursor-cache");
+            std::fs::create_dir_all(&cursor_cache_dir).unwrap();
+
+            let header = "Date,Kind,Model,Max Mode,Input (w/ Cache Write),Input (w/o Cache Write),Cache Read,Output Tokens,Total Tokens,Cost";
+            std::fs::write(
+                cursor_cache_dir.join("usage.csv"),
+                format!(
+                    "{header}\n\"2026-03-04T12:00:00.000Z\",\"Included\",\"gpt-5\",\"No\",\"100\",\"100\",\"0\",\"10\",\"110\",\"0.10\""
+                ),
+            )
+            .unwrap();
+            std::fs::write(
+                cursor_cache_dir.join("usage.work.csv"),
+                format!(
+                    "{header}\n\"2026-03-05T12:00:00.000Z\",\"Included\",\"claude-4-sonnet\",\"No\",\"200\",\"200\",\"0\",\"20\",\"220\",\"0.20\""
+                ),
+            )
+            .unwrap();
+            std::fs::write(
+                config_dir.join("cursor-credentials.json"),
+                r#"{"version":1,"activeAccountId":"personal","accounts":{
+                    "personal":{"sessionToken":"a","label":"Personal"},
+                    "work":{"sessionToken":"b","label":"Work"}}}"#,
+            )
+            .unwrap();
 
-            std::env::set_var("HOME", fresh_cache_home.path());
-            let fresh_messages = parse_all_messages_with_pricing(
+            let mut messages = parse_all_messages_with_pricing(
                 source_home.path().to_str().unwrap(),
-                &["codex".to_string()],
+                &["cursor".to_string()],
                 None,
             );
+            messages.sort_by(|a, b| a.date.cmp(&b.date));
 
-            assert_eq!(warm_messages, fresh_messages);
-            assert_eq!(warm_messages.len(), 2);
+            assert_eq!(messages.len(), 2);
+            assert_eq!(messages[0].model_id, "gpt-5");
+            assert_eq!(messages[0].agent.as_deref(), Some("Personal"));
+            assert_eq!(messages[1].model_id, "claude-4-sonnet");
+            assert_eq!(messages[1].agent.as_deref(), Some("Work"));
         }
 
         match original_home {
@@ -6555,85 +9305,507 @@ mod tests {
     }
 
     #[test]
-    #[serial_test::serial]
-    fn test_source_cache_does_not_reuse_priced_cost_without_pricing_service() {
-        let temp_home = tempfile::TempDir::new().unwrap();
-        let source_home = tempfile::TempDir::new().unwrap();
-        let original_home = std::env::var("HOME").ok();
-        std::env::set_var("HOME", temp_home.path());
-        {
-            let cursor_cache_dir = source_home.path().join(".config/tokscale/cursor-cache");
-            std::fs::create_dir_all(&cursor_cache_dir).unwrap();
+    fn test_parallel_pricing_matches_serial_pricing() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<pricing::PricingService>();
 
-            let csv = r#"Date,Kind,Model,Max Mode,Input (w/ Cache Write),Input (w/o Cache Write),Cache Read,Output Tokens,Total Tokens,Cost
-"2026-03-04T12:00:00.000Z","Included","Composer 1.5","No","1200","1000","5000","2000","8000","0""#;
-            std::fs::write(cursor_cache_dir.join("usage.csv"), csv).unwrap();
+        let mut litellm = HashMap::new();
+        litellm.insert(
+            "claude-sonnet-4-5".into(),
+            pricing::ModelPricing {
+                input_cost_per_token: Some(0.000003),
+                output_cost_per_token: Some(0.000015),
+                cache_read_input_token_cost: Some(0.0000003),
+                ..Default::default()
+            },
+        );
+        litellm.insert(
+            "gpt-5".into(),
+            pricing::ModelPricing {
+                input_cost_per_token: Some(0.00000125),
+                output_cost_per_token: Some(0.00001),
+                ..Default::default()
+            },
+        );
+        let pricing = pricing::PricingService::new(litellm, HashMap::new());
 
-            let mut litellm = HashMap::new();
-            litellm.insert(
-                "Composer 1.5".into(),
+        let models = [
+            ("claude", "claude-sonnet-4-5", "anthropic"),
+            ("codex", "gpt-5", "openai"),
+            ("opencode", "unpriced-model", "custom"),
+        ];
+        let messages: Vec<UnifiedMessage> = (0..3_000i64)
+            .map(|i| {
+                let (client, model, provider) = models[i as usize % models.len()];
+                UnifiedMessage::new(
+                    client,
+                    model,
+                    provider,
+                    "session-1",
+                    1_700_000_000_000 + i * 60_000,
+                    TokenBreakdown {
+                        input: 100 + i,
+                        output: 10 + i % 97,
+                        cache_read: i % 13 * 50,
+                        cache_write: 0,
+                        reasoning: 0,
+                        ..Default::default()
+                    },
+                    0.0,
+                )
+            })
+            .collect();
+
+        let mut serial = messages.clone();
+        for message in &mut serial {
+            message.refresh_derived_fields();
+            apply_pricing_if_available(message, Some(&pricing));
+        }
+        let mut parallel = messages;
+        apply_pricing_to_messages(&mut parallel, Some(&pricing));
+
+        assert!(serial.iter().any(|m| m.cost > 0.0));
+        let costs = |messages: &[UnifiedMessage]| -> Vec<(u64, crate::CostSource)> {
+            messages
+                .iter()
+                .map(|m| (m.cost.to_bits(), m.cost_source))
+                .collect()
+        };
+        assert_eq!(costs(&serial), costs(&parallel));
+    }
+
+    #[test]
+    fn test_apply_pricing_if_available_keeps_existing_cost_without_pricing() {
+        let mut msg = UnifiedMessage::new_with_agent(
+            "roocode",
+            "gpt-4o",
+            "provider",
+            "session-1",
+            1_733_011_200_000,
+            TokenBreakdown {
+                input: 10,
+                output: 5,
+                cache_read: 0,
+                cache_write: 0,
+                reasoning: 0,
+                ..Default::default()
+            },
+            0.42,
+            Some("planner".to_string()),
+        );
+
+        apply_pricing_if_available(&mut msg, None);
+
+        assert_eq!(msg.cost, 0.42);
+    }
+
+    #[test]
+    fn test_apply_pricing_if_available_overrides_cost_when_pricing_exists() {
+        let mut litellm = HashMap::new();
+        litellm.insert(
+            "gpt-4o".into(),
+            pricing::ModelPricing {
+                input_cost_per_token: Some(0.001),
+                output_cost_per_token: Some(0.002),
+                ..Default::default()
+            },
+        );
+        let pricing = pricing::PricingService::new(litellm, HashMap::new());
+
+        let mut msg = UnifiedMessage::new(
+            "codex",
+            "gpt-4o",
+            "provider",
+            "session-1",
+            1_733_011_200_000,
+            TokenBreakdown {
+                input: 10,
+                output: 5,
+                cache_read: 0,
+                cache_write: 0,
+                reasoning: 0,
+                ..Default::default()
+            },
+            0.0,
+        );
+
+        apply_pricing_if_available(&mut msg, Some(&pricing));
+
+        assert_eq!(msg.cost, 0.02);
+    }
+
+    #[test]
+    fn test_model_rows_record_the_pricing_source_that_matched() {
+        let price = || pricing::ModelPricing {
+            input_cost_per_token: Some(0.001),
+            output_cost_per_token: Some(0.002),
+            ..Default::default()
+        };
+        let litellm = HashMap::from([("gpt-4o".to_string(), price())]);
+        let openrouter = HashMap::from([("mistralai/mistral-large".to_string(), price())]);
+        let pricing = pricing::PricingService::new(litellm, openrouter);
+
+        let make = |model: &str, cost: f64| {
+            UnifiedMessage::new(
+                "opencode",
+                model,
+                "provider",
+                "session-1",
+                1_733_011_200_000,
+                TokenBreakdown {
+                    input: 10,
+                    output: 5,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                cost,
+            )
+        };
+        let mut reported = make("gpt-4o", 1.0);
+        reported.mark_provider_reported_cost();
+        let messages = vec![
+            make("gpt-4o", 0.0),
+            make("gpt-4o", 0.0),
+            reported,
+            make("mistral-large", 0.0),
+            make("mystery-model", 0.0),
+        ];
+
+        let sources = resolve_pricing_sources(&messages, &pricing);
+        let entries = aggregate_model_usage_entries_with(messages, &GroupBy::Model, Some(&sources));
+        let source_of = |model: &str| {
+            entries
+                .iter()
+                .find(|e| e.model == model)
+                .and_then(|e| e.pricing_source.clone())
+        };
+        // Two of the three gpt-4o messages were priced by LiteLLM.
+        assert_eq!(source_of("gpt-4o").as_deref(), Some("LiteLLM"));
+        assert_eq!(source_of("mistral-large").as_deref(), Some("OpenRouter"));
+        assert_eq!(source_of("mystery-model").as_deref(), Some("none"));
+
+        let mut even = HashMap::new();
+        even.insert("LiteLLM".to_string(), 1);
+        even.insert("client".to_string(), 1);
+        assert_eq!(dominant_pricing_source(&even), "mixed");
+    }
+
+    #[test]
+    fn test_model_report_estimates_cache_read_savings_from_known_rates() {
+        let litellm = HashMap::from([
+            (
+                "gpt-4o".to_string(),
                 pricing::ModelPricing {
-                    input_cost_per_token: Some(0.001),
-                    output_cost_per_token: Some(0.002),
-                    cache_read_input_token_cost: Some(0.0005),
+                    input_cost_per_token: Some(0.000_002_5),
+                    cache_read_input_token_cost: Some(0.000_001_25),
                     ..Default::default()
                 },
-            );
-            let pricing = pricing::PricingService::new(litellm, HashMap::new());
+            ),
+            (
+                "gpt-4o-mini".to_string(),
+                pricing::ModelPricing {
+                    input_cost_per_token: Some(0.000_000_15),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let pricing = pricing::PricingService::new(litellm, HashMap::new());
 
-            let repriced_messages = parse_all_messages_with_pricing(
-                source_home.path().to_str().unwrap(),
-                &["cursor".to_string()],
-                Some(&pricing),
-            );
-            assert_eq!(repriced_messages.len(), 1);
-            assert!(repriced_messages[0].cost > 0.0);
+        let make = |model: &str, cache_read: i64| {
+            UnifiedMessage::new(
+                "opencode",
+                model,
+                "openai",
+                "session-1",
+                1_733_011_200_000,
+                TokenBreakdown {
+                    input: 100,
+                    output: 0,
+                    cache_read,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                0.0,
+            )
+        };
+        let messages = vec![
+            make("gpt-4o", 600_000),
+            make("gpt-4o", 200_000),
+            // No cache-read rate: reads are billed like input, so no savings.
+            make("gpt-4o-mini", 1_000_000),
+            make("mystery-model", 1_000_000),
+        ];
 
-            let cached_messages = parse_all_messages_with_pricing(
-                source_home.path().to_str().unwrap(),
-                &["cursor".to_string()],
-                None,
-            );
+        let sources = resolve_pricing_sources(&messages, &pricing);
+        let report = super::build_model_report(
+            messages,
+            &ReportOptions::default(),
+            &[],
+            ReportPricing::Resolved(&sources),
+            std::time::Duration::ZERO,
+        );
+        let savings_of = |model: &str| {
+            report
+                .entries
+                .iter()
+                .find(|e| e.model == model)
+                .map(|e| e.estimated_savings)
+                .unwrap()
+        };
+        // 800k cache reads * ($2.50 - $1.25) per million.
+        assert!((savings_of("gpt-4o") - 1.0).abs() < 1e-9);
+        assert_eq!(savings_of("gpt-4o-mini"), 0.0);
+        assert_eq!(savings_of("mystery-model"), 0.0);
+        assert!((report.total_estimated_savings - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unpriced_models_lists_usage_that_matched_no_pricing() {
+        let litellm = HashMap::from([(
+            "gpt-4o".to_string(),
+            pricing::ModelPricing {
+                input_cost_per_token: Some(0.001),
+                ..Default::default()
+            },
+        )]);
+        let pricing = pricing::PricingService::new(litellm, HashMap::new());
+
+        let make = |model: &str, provider: &str, input: i64| {
+            UnifiedMessage::new(
+                "opencode",
+                model,
+                provider,
+                "session-1",
+                1_733_011_200_000,
+                TokenBreakdown {
+                    input,
+                    output: 0,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                0.0,
+            )
+        };
+        let messages = vec![
+            make("gpt-4o", "openai", 10),
+            make("zz-invented-model-9000", "acme", 10),
+            make("zz-invented-model-9000", "acme", 5),
+            // No tokens, so nothing is missing a price.
+            make("zz-idle-model", "acme", 0),
+            // Subscription usage is expected to be unpriced.
+            make("zz-copilot-only", "github-copilot", 10),
+        ];
+
+        let sources = resolve_pricing_sources(&messages, &pricing);
+        assert_eq!(
+            unpriced_model_ids(&messages, &sources),
+            vec!["zz-invented-model-9000".to_string()]
+        );
+
+        let report = build_monthly_report(
+            messages,
+            ReportPricing::Resolved(&sources),
+            std::time::Duration::ZERO,
+        );
+        assert_eq!(report.unpriced_models, vec!["zz-invented-model-9000"]);
+    }
+
+    #[test]
+    fn test_monthly_rows_count_active_days() {
+        let make = |timestamp: &str, cost: f64| {
+            UnifiedMessage::new(
+                "opencode",
+                "gpt-4o",
+                "openai",
+                "session-1",
+                chrono::DateTime::parse_from_rfc3339(timestamp)
+                    .unwrap()
+                    .timestamp_millis(),
+                TokenBreakdown {
+                    input: 10,
+                    output: 5,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                cost,
+            )
+        };
+        let messages = vec![
+            make("2026-03-02T12:00:00Z", 1.0),
+            make("2026-03-02T13:00:00Z", 2.0),
+            make("2026-03-10T12:00:00Z", 1.5),
+            make("2026-03-20T12:00:00Z", 1.5),
+            make("2026-04-01T12:00:00Z", 4.0),
+        ];
+
+        let report = build_monthly_report(
+            messages,
+            ReportPricing::Unavailable,
+            std::time::Duration::ZERO,
+        );
+        let march = &report.entries[0];
+        assert_eq!(march.month, "2026-03");
+        assert_eq!(march.active_days, 3);
+        assert!((march.avg_cost_per_active_day - 2.0).abs() < 1e-9);
+        let april = &report.entries[1];
+        assert_eq!(april.active_days, 1);
+        assert!((april.avg_cost_per_active_day - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hour_of_day_buckets_follow_the_timezone() {
+        let make = |timestamp: &str, output: i64, cost: f64| {
+            UnifiedMessage::new(
+                "claude",
+                "claude-sonnet-4-5",
+                "anthropic",
+                "session-1",
+                chrono::DateTime::parse_from_rfc3339(timestamp)
+                    .unwrap()
+                    .timestamp_millis(),
+                TokenBreakdown {
+                    input: 10,
+                    output,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning: 0,
+                    ..Default::default()
+                },
+                cost,
+            )
+        };
+        let messages = vec![
+            make("2026-03-02T09:15:00Z", 5, 1.0),
+            make("2026-03-09T09:59:59Z", 7, 2.0),
+            make("2026-03-02T23:30:00Z", 1, 0.5),
+            make("2026-03-03T00:00:00Z", 1, 0.25),
+            UnifiedMessage::new(
+                "claude",
+                "claude-sonnet-4-5",
+                "anthropic",
+                "session-1",
+                0,
+                TokenBreakdown::default(),
+                0.0,
+            ),
+        ];
 
-            assert_eq!(cached_messages.len(), 1);
-            assert_eq!(cached_messages[0].cost, 0.0);
-        }
+        let (utc, untimed) = bucket_by_hour_of_day(messages.clone(), &chrono::Utc);
+        assert_eq!(utc.len(), 24);
+        assert_eq!(untimed, 1);
+        assert_eq!(utc[9].message_count, 2);
+        assert_eq!(utc[9].output, 12);
+        assert!((utc[9].cost - 3.0).abs() < 1e-9);
+        assert_eq!(utc[23].message_count, 1);
+        assert_eq!(utc[0].message_count, 1);
+        assert_eq!(
+            utc[12],
+            HourOfDayUsage {
+                hour: 12,
+                ..Default::default()
+            }
+        );
 
-        match original_home {
-            Some(home) => std::env::set_var("HOME", home),
-            None => std::env::remove_var("HOME"),
-        }
+        // UTC+2: 09:xx becomes 11:xx and 23:30 wraps to 01:30.
+        let plus_two = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        let (shifted, _) = bucket_by_hour_of_day(messages, &plus_two);
+        assert_eq!(shifted[11].message_count, 2);
+        assert_eq!(shifted[1].message_count, 1);
+        assert_eq!(shifted[2].message_count, 1);
+        assert_eq!(shifted[9].message_count, 0);
     }
 
     #[test]
-    fn test_apply_pricing_if_available_keeps_existing_cost_without_pricing() {
-        let mut msg = UnifiedMessage::new_with_agent(
-            "roocode",
-            "gpt-4o",
-            "provider",
-            "session-1",
-            1_733_011_200_000,
-            TokenBreakdown {
-                input: 10,
-                output: 5,
-                cache_read: 0,
-                cache_write: 0,
-                reasoning: 0,
-            },
-            0.42,
-            Some("planner".to_string()),
+    fn test_monthly_rows_sum_reasoning_tokens() {
+        let make = |timestamp: &str, reasoning: i64| {
+            UnifiedMessage::new(
+                "codex",
+                "gpt-5",
+                "openai",
+                "session-1",
+                chrono::DateTime::parse_from_rfc3339(timestamp)
+                    .unwrap()
+                    .timestamp_millis(),
+                TokenBreakdown {
+                    input: 10,
+                    output: 5,
+                    cache_read: 0,
+                    cache_write: 0,
+                    reasoning,
+                    ..Default::default()
+                },
+                0.0,
+            )
+        };
+        let messages = vec![
+            make("2026-03-02T12:00:00Z", 40),
+            make("2026-03-15T12:00:00Z", 2),
+            make("2026-04-01T12:00:00Z", 0),
+        ];
+
+        let report = build_monthly_report(
+            messages,
+            ReportPricing::Unavailable,
+            std::time::Duration::ZERO,
         );
+        assert_eq!(report.entries[0].reasoning, 42);
+        assert_eq!(report.entries[0].output, 10);
+        assert_eq!(report.entries[1].reasoning, 0);
+    }
 
-        apply_pricing_if_available(&mut msg, None);
+    #[test]
+    fn test_daily_cost_series_fills_days_without_usage() {
+        let make = |timestamp: &str, cost: f64| {
+            UnifiedMessage::new(
+                "codex",
+                "gpt-5",
+                "openai",
+                "session-1",
+                chrono::DateTime::parse_from_rfc3339(timestamp)
+                    .unwrap()
+                    .timestamp_millis(),
+                TokenBreakdown::default(),
+                cost,
+            )
+        };
+        let messages = vec![
+            make("2026-03-04T12:00:00Z", 2.0),
+            make("2026-03-01T12:00:00Z", 1.0),
+            make("2026-03-01T13:00:00Z", 0.5),
+        ];
 
-        assert_eq!(msg.cost, 0.42);
+        let mut day_costs = BTreeMap::new();
+        for message in messages {
+            *day_costs.entry(message.date).or_insert(0.0) += message.cost;
+        }
+        let series: Vec<(String, f64)> = daily_cost_series(day_costs)
+            .into_iter()
+            .map(|day| (day.date, day.cost))
+            .collect();
+        assert_eq!(
+            series,
+            vec![
+                ("2026-03-01".to_string(), 1.5),
+                ("2026-03-02".to_string(), 0.0),
+                ("2026-03-03".to_string(), 0.0),
+                ("2026-03-04".to_string(), 2.0),
+            ]
+        );
+        assert!(daily_cost_series(BTreeMap::new()).is_empty());
     }
 
     #[test]
-    fn test_apply_pricing_if_available_overrides_cost_when_pricing_exists() {
+    fn test_apply_pricing_if_available_applies_zed_hosted_markup() {
         let mut litellm = HashMap::new();
         litellm.insert(
-            "gpt-4o".into(),
+            "claude-sonnet-4-5".into(),
             pricing::ModelPricing {
                 input_cost_per_token: Some(0.001),
                 output_cost_per_token: Some(0.002),
@@ -6643,9 +9815,9 @@ mod tests {
         let pricing = pricing::PricingService::new(litellm, HashMap::new());
 
         let mut msg = UnifiedMessage::new(
-            "codex",
-            "gpt-4o",
-            "provider",
+            "zed",
+            "claude-sonnet-4-5",
+            crate::sessions::zed::ZED_HOSTED_PROVIDER,
             "session-1",
             1_733_011_200_000,
             TokenBreakdown {
@@ -6654,17 +9826,20 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
 
         apply_pricing_if_available(&mut msg, Some(&pricing));
 
-        assert_eq!(msg.cost, 0.02);
+        assert!((msg.cost - 0.022).abs() < 1e-12);
     }
 
     #[test]
-    fn test_apply_pricing_if_available_applies_zed_hosted_markup() {
+    fn test_apply_pricing_if_available_skips_zed_markup_for_non_zed_client() {
+        // Non-zed client with provider_id "zed.dev" must not receive the +10%
+        // markup. The multiplier is gated on (client == "zed" AND provider).
         let mut litellm = HashMap::new();
         litellm.insert(
             "claude-sonnet-4-5".into(),
@@ -6677,7 +9852,7 @@ mod tests {
         let pricing = pricing::PricingService::new(litellm, HashMap::new());
 
         let mut msg = UnifiedMessage::new(
-            "zed",
+            "claudecode",
             "claude-sonnet-4-5",
             crate::sessions::zed::ZED_HOSTED_PROVIDER,
             "session-1",
@@ -6688,19 +9863,22 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
 
         apply_pricing_if_available(&mut msg, Some(&pricing));
 
-        assert!((msg.cost - 0.022).abs() < 1e-12);
+        // 10 * 0.001 + 5 * 0.002 = 0.020, no markup.
+        assert!((msg.cost - 0.020).abs() < 1e-12);
     }
 
     #[test]
-    fn test_apply_pricing_if_available_skips_zed_markup_for_non_zed_client() {
-        // Non-zed client with provider_id "zed.dev" must not receive the +10%
-        // markup. The multiplier is gated on (client == "zed" AND provider).
+    fn test_apply_pricing_if_available_skips_zed_markup_for_byok_provider() {
+        // A Zed message whose provider_id is the upstream provider directly
+        // (BYOK / non-hosted path) must not be marked up — the user is paying
+        // the upstream API directly, not through Zed.
         let mut litellm = HashMap::new();
         litellm.insert(
             "claude-sonnet-4-5".into(),
@@ -6713,9 +9891,9 @@ mod tests {
         let pricing = pricing::PricingService::new(litellm, HashMap::new());
 
         let mut msg = UnifiedMessage::new(
-            "claudecode",
+            "zed",
             "claude-sonnet-4-5",
-            crate::sessions::zed::ZED_HOSTED_PROVIDER,
+            "anthropic",
             "session-1",
             1_733_011_200_000,
             TokenBreakdown {
@@ -6724,24 +9902,21 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
 
         apply_pricing_if_available(&mut msg, Some(&pricing));
 
-        // 10 * 0.001 + 5 * 0.002 = 0.020, no markup.
         assert!((msg.cost - 0.020).abs() < 1e-12);
     }
 
     #[test]
-    fn test_apply_pricing_if_available_skips_zed_markup_for_byok_provider() {
-        // A Zed message whose provider_id is the upstream provider directly
-        // (BYOK / non-hosted path) must not be marked up — the user is paying
-        // the upstream API directly, not through Zed.
+    fn test_apply_pricing_if_available_uses_reasoning_for_gemini() {
         let mut litellm = HashMap::new();
         litellm.insert(
-            "claude-sonnet-4-5".into(),
+            "gemini-2.5-pro".into(),
             pricing::ModelPricing {
                 input_cost_per_token: Some(0.001),
                 output_cost_per_token: Some(0.002),
@@ -6751,9 +9926,9 @@ mod tests {
         let pricing = pricing::PricingService::new(litellm, HashMap::new());
 
         let mut msg = UnifiedMessage::new(
-            "zed",
-            "claude-sonnet-4-5",
-            "anthropic",
+            "gemini",
+            "gemini-2.5-pro",
+            "google",
             "session-1",
             1_733_011_200_000,
             TokenBreakdown {
@@ -6761,24 +9936,26 @@ mod tests {
                 output: 5,
                 cache_read: 0,
                 cache_write: 0,
-                reasoning: 0,
+                reasoning: 7,
+                ..Default::default()
             },
             0.0,
         );
 
         apply_pricing_if_available(&mut msg, Some(&pricing));
 
-        assert!((msg.cost - 0.020).abs() < 1e-12);
+        assert_eq!(msg.cost, 0.034);
     }
 
     #[test]
-    fn test_apply_pricing_if_available_uses_reasoning_for_gemini() {
+    fn test_apply_pricing_if_available_uses_gemini_reasoning_rate() {
         let mut litellm = HashMap::new();
         litellm.insert(
-            "gemini-2.5-pro".into(),
+            "gemini-2.5-flash".into(),
             pricing::ModelPricing {
                 input_cost_per_token: Some(0.001),
                 output_cost_per_token: Some(0.002),
+                reasoning_cost_per_token: Some(0.005),
                 ..Default::default()
             },
         );
@@ -6786,7 +9963,7 @@ mod tests {
 
         let mut msg = UnifiedMessage::new(
             "gemini",
-            "gemini-2.5-pro",
+            "gemini-2.5-flash",
             "google",
             "session-1",
             1_733_011_200_000,
@@ -6796,13 +9973,14 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 7,
+                ..Default::default()
             },
             0.0,
         );
 
         apply_pricing_if_available(&mut msg, Some(&pricing));
 
-        assert_eq!(msg.cost, 0.034);
+        assert!((msg.cost - 0.055).abs() < 1e-12);
     }
 
     #[test]
@@ -6831,6 +10009,7 @@ mod tests {
                 cache_read: 7,
                 cache_write: 0,
                 reasoning: 3,
+                ..Default::default()
             },
             0.0,
         );
@@ -6865,6 +10044,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
@@ -6907,6 +10087,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
@@ -6949,6 +10130,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
@@ -6993,6 +10175,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.123,
         );
@@ -7040,6 +10223,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 3,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
@@ -7082,6 +10266,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
@@ -7107,6 +10292,7 @@ mod tests {
                 cache_read: 50_000,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
@@ -7142,6 +10328,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
@@ -7176,6 +10363,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
@@ -7214,6 +10402,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
@@ -7257,6 +10446,26 @@ mod tests {
         assert!(!stale_called);
     }
 
+    #[test]
+    fn test_no_pricing_never_runs_the_pricing_loader() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        let mut loader_called = false;
+        let pricing = rt.block_on(pricing_unless_disabled(true, || {
+            loader_called = true;
+            async { panic!("--no-pricing must not load pricing") }
+        }));
+        assert!(pricing.is_none());
+        assert!(!loader_called);
+
+        let service = Arc::new(pricing::PricingService::new(HashMap::new(), HashMap::new()));
+        let pricing = rt.block_on(pricing_unless_disabled(false, || {
+            let service = Arc::clone(&service);
+            async move { Some(service) }
+        }));
+        assert!(Arc::ptr_eq(&pricing.unwrap(), &service));
+    }
+
     #[test]
     fn test_dedupe_latest_trae_messages_keeps_latest_timestamp_for_session() {
         let messages = vec![
@@ -7371,8 +10580,10 @@ mod tests {
             clients: Some(vec!["opencode".to_string(), "synthetic".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings::default(),
+            ..Default::default()
         })
         .unwrap();
 
@@ -7436,8 +10647,10 @@ mod tests {
             clients: Some(vec!["synthetic".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings::default(),
+            ..Default::default()
         })
         .unwrap();
 
@@ -7465,44 +10678,200 @@ mod tests {
         )
         .unwrap();
         std::fs::write(
-            message_dir.join("msg_missing.json"),
-            r#"{"id":"msg-missing","sessionID":"session-1","role":"assistant","modelID":"gpt-4o","providerID":"openai","tokens":{"input":10,"output":5,"reasoning":0,"cache":{"read":0,"write":0}},"time":{"created":1733011201000}}"#,
+            message_dir.join("msg_missing.json"),
+            r#"{"id":"msg-missing","sessionID":"session-1","role":"assistant","modelID":"gpt-4o","providerID":"openai","tokens":{"input":10,"output":5,"reasoning":0,"cache":{"read":0,"write":0}},"time":{"created":1733011201000}}"#,
+        )
+        .unwrap();
+
+        let mut litellm = HashMap::new();
+        litellm.insert(
+            "openai/gpt-4o".to_string(),
+            pricing::ModelPricing {
+                input_cost_per_token: Some(0.01),
+                output_cost_per_token: Some(0.02),
+                ..Default::default()
+            },
+        );
+        let pricing = pricing::PricingService::new(litellm, HashMap::new());
+        let messages = parse_all_messages_with_pricing_with_env_strategy(
+            temp_dir.path().to_str().unwrap(),
+            &["opencode".to_string()],
+            Some(&pricing),
+            false,
+            &scanner::ScannerSettings::default(),
+            false,
+        );
+
+        let embedded = messages
+            .iter()
+            .find(|message| message.dedup_key.as_deref() == Some("msg-reported"))
+            .expect("embedded-cost message should parse");
+        let missing = messages
+            .iter()
+            .find(|message| message.dedup_key.as_deref() == Some("msg-missing"))
+            .expect("missing-cost message should parse");
+        assert_eq!(
+            embedded.cost, 0.05,
+            "OpenCode computes cost at request time; the embedded value must not be overwritten by LiteLLM repricing"
+        );
+        assert_eq!(embedded.cost_source, crate::CostSource::ProviderReported);
+        assert_eq!(missing.cost, 0.2);
+        assert_eq!(missing.cost_source, crate::CostSource::Estimated);
+    }
+
+    #[test]
+    fn test_parse_timings_have_one_entry_per_enabled_client() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let message_dir = temp_dir
+            .path()
+            .join(".local/share/opencode/storage/message/project-1");
+        std::fs::create_dir_all(&message_dir).unwrap();
+        std::fs::write(
+            message_dir.join("msg_a.json"),
+            r#"{"id":"msg-a","sessionID":"session-1","role":"assistant","modelID":"gpt-4o","providerID":"openai","cost":0.05,"tokens":{"input":10,"output":5,"reasoning":0,"cache":{"read":0,"write":0}},"time":{"created":1733011200000}}"#,
+        )
+        .unwrap();
+        let home = temp_dir.path().to_str().unwrap();
+
+        let (messages, timings) = parse_all_messages_with_timings(
+            home,
+            &["claude".to_string(), "opencode".to_string()],
+            None,
+            false,
+            &scanner::ScannerSettings::default(),
+            false,
+        );
+        assert_eq!(messages.len(), 1);
+        let clients: Vec<&str> = timings.iter().map(|t| t.client.as_str()).collect();
+        assert_eq!(clients, vec!["opencode", "claude"]);
+        assert_eq!(timings[0].file_count, 1);
+        assert_eq!(timings[1].file_count, 0);
+        assert!(timings
+            .iter()
+            .all(|t| t.scan_ms >= 0.0 && t.parse_ms >= 0.0));
+
+        let (_, timings) = parse_all_messages_with_timings(
+            home,
+            &[],
+            None,
+            false,
+            &scanner::ScannerSettings::default(),
+            false,
+        );
+        assert_eq!(timings.len(), ClientId::COUNT);
+    }
+
+    fn write_session_fixtures(home: &std::path::Path, gzip: bool) {
+        use std::io::Write;
+
+        let write = |path: std::path::PathBuf, content: &str| {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            if gzip {
+                let mut file_name = path.file_name().unwrap().to_os_string();
+                file_name.push(".gz");
+                let file = std::fs::File::create(path.with_file_name(file_name)).unwrap();
+                let mut encoder =
+                    flate2::write::GzEncoder::new(file, flate2::Compression::default());
+                encoder.write_all(content.as_bytes()).unwrap();
+                encoder.finish().unwrap();
+            } else {
+                std::fs::write(path, content).unwrap();
+            }
+        };
+
+        write(
+            home.join(".claude/projects/-work-repo/session-a.jsonl"),
+            r#"{"type":"assistant","timestamp":"2024-12-01T10:00:00.000Z","requestId":"req_1","message":{"id":"msg_1","model":"claude-sonnet-4-5","usage":{"input_tokens":100,"output_tokens":50}}}"#,
+        );
+        write(
+            home.join(".codex/sessions/rollout-b.jsonl"),
+            concat!(
+                r#"{"timestamp":"2026-04-30T10:00:00Z","type":"session_meta","payload":{"id":"codex-session","source":"interactive","model_provider":"openai","cwd":"/repo"}}"#,
+                "\n",
+                r#"{"timestamp":"2026-04-30T10:00:01Z","type":"turn_context","payload":{"model":"gpt-5.2"}}"#,
+                "\n",
+                r#"{"timestamp":"2026-04-30T10:00:02Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":50,"cached_input_tokens":10,"output_tokens":15,"total_tokens":65},"last_token_usage":{"input_tokens":50,"cached_input_tokens":10,"output_tokens":15,"total_tokens":65}}}}"#,
+                "\n",
+            ),
+        );
+        write(
+            home.join(".gemini/tmp/123/chats/session-c.jsonl"),
+            concat!(
+                r#"{"sessionId":"gemini-session-1","projectHash":"abc123","startTime":"2026-05-01T00:00:00.000Z","lastUpdated":"2026-05-01T00:01:00.000Z"}"#,
+                "\n",
+                r#"{"id":"msg-1","timestamp":"2026-05-01T00:01:00.000Z","type":"gemini","model":"gemini-3.1-pro-preview","tokens":{"input":14918,"output":60,"cached":0,"thoughts":863,"tool":7,"total":15848}}"#,
+            ),
+        );
+        write(
+            home.join(".local/share/opencode/storage/message/project-1/msg_d.json"),
+            r#"{"id":"msg-d","sessionID":"session-1","role":"assistant","modelID":"gpt-4o","providerID":"openai","cost":0.05,"tokens":{"input":10,"output":5,"reasoning":0,"cache":{"read":0,"write":0}},"time":{"created":1733011200000}}"#,
+        );
+    }
+
+    #[test]
+    fn test_gzipped_session_files_parse_like_the_plain_ones() {
+        let clients = ["claude", "codex", "gemini", "opencode"].map(String::from);
+        let parse = |gzip: bool| {
+            let home = tempfile::TempDir::new().unwrap();
+            write_session_fixtures(home.path(), gzip);
+            let mut messages =
+                parse_all_messages_with_pricing(home.path().to_str().unwrap(), &clients, None);
+            messages.sort_by(|a, b| a.client.cmp(&b.client));
+            messages
+        };
+
+        let plain = parse(false);
+        assert_eq!(
+            plain.iter().map(|m| m.client.as_str()).collect::<Vec<_>>(),
+            clients
+        );
+        assert_eq!(parse(true), plain);
+    }
+
+    #[test]
+    fn test_parse_timings_count_duplicates_removed_per_client() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let opencode_message = r#"{"id":"msg-shared","sessionID":"session-1","role":"assistant","modelID":"gpt-4o","providerID":"openai","cost":0.05,"tokens":{"input":10,"output":5,"reasoning":0,"cache":{"read":0,"write":0}},"time":{"created":1733011200000}}"#;
+        for project in ["project-1", "project-2"] {
+            let message_dir = temp_dir
+                .path()
+                .join(".local/share/opencode/storage/message")
+                .join(project);
+            std::fs::create_dir_all(&message_dir).unwrap();
+            std::fs::write(message_dir.join("msg_shared.json"), opencode_message).unwrap();
+        }
+        // The same streamed response copied into a resumed session file.
+        let claude_dir = temp_dir.path().join(".claude/projects/-work-repo");
+        std::fs::create_dir_all(&claude_dir).unwrap();
+        let shared = r#"{"type":"assistant","timestamp":"2024-12-01T10:00:00.000Z","requestId":"req_1","message":{"id":"msg_1","model":"claude-sonnet-4-5","usage":{"input_tokens":100,"output_tokens":50}}}"#;
+        let own = r#"{"type":"assistant","timestamp":"2024-12-01T11:00:00.000Z","requestId":"req_2","message":{"id":"msg_2","model":"claude-sonnet-4-5","usage":{"input_tokens":10,"output_tokens":5}}}"#;
+        std::fs::write(claude_dir.join("original.jsonl"), shared).unwrap();
+        std::fs::write(
+            claude_dir.join("resumed.jsonl"),
+            format!("{shared}\n{own}\n"),
         )
         .unwrap();
+        let home = temp_dir.path().to_str().unwrap();
 
-        let mut litellm = HashMap::new();
-        litellm.insert(
-            "openai/gpt-4o".to_string(),
-            pricing::ModelPricing {
-                input_cost_per_token: Some(0.01),
-                output_cost_per_token: Some(0.02),
-                ..Default::default()
-            },
-        );
-        let pricing = pricing::PricingService::new(litellm, HashMap::new());
-        let messages = parse_all_messages_with_pricing_with_env_strategy(
-            temp_dir.path().to_str().unwrap(),
-            &["opencode".to_string()],
-            Some(&pricing),
+        let (messages, timings) = parse_all_messages_with_timings(
+            home,
+            &["claude".to_string(), "opencode".to_string()],
+            None,
             false,
             &scanner::ScannerSettings::default(),
+            false,
         );
 
-        let embedded = messages
-            .iter()
-            .find(|message| message.dedup_key.as_deref() == Some("msg-reported"))
-            .expect("embedded-cost message should parse");
-        let missing = messages
-            .iter()
-            .find(|message| message.dedup_key.as_deref() == Some("msg-missing"))
-            .expect("missing-cost message should parse");
-        assert_eq!(
-            embedded.cost, 0.05,
-            "OpenCode computes cost at request time; the embedded value must not be overwritten by LiteLLM repricing"
-        );
-        assert_eq!(embedded.cost_source, crate::CostSource::ProviderReported);
-        assert_eq!(missing.cost, 0.2);
-        assert_eq!(missing.cost_source, crate::CostSource::Estimated);
+        assert_eq!(messages.len(), 3);
+        let duplicates = |client: &str| {
+            timings
+                .iter()
+                .find(|t| t.client == client)
+                .unwrap()
+                .duplicates_removed
+        };
+        assert_eq!(duplicates("opencode"), 1);
+        assert_eq!(duplicates("claude"), 1);
     }
 
     #[test]
@@ -7534,6 +10903,7 @@ mod tests {
             Some(&pricing),
             false,
             &scanner::ScannerSettings::default(),
+            false,
         );
 
         let explicit_zero = messages
@@ -7581,12 +10951,57 @@ mod tests {
             None,
             false,
             &scanner::ScannerSettings::default(),
+            false,
         );
 
         assert_eq!(messages.len(), 1);
         assert_eq!(messages[0].cost, 0.03);
     }
 
+    #[test]
+    fn test_report_messages_yields_only_messages_inside_the_window() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let session_dir = temp_dir.path().join(".gjc/agent/sessions/project-1");
+        std::fs::create_dir_all(&session_dir).unwrap();
+        std::fs::write(
+            session_dir.join("session.jsonl"),
+            [
+                r#"{"type":"session","id":"gjc_ses_window","cwd":"/work/project-1"}"#,
+                r#"{"type":"message","id":"msg_early","message":{"role":"assistant","model":"gpt-4o","provider":"openai","timestamp":1733054400000,"usage":{"input":10,"output":5,"cost":{"total":0.01}}}}"#,
+                r#"{"type":"message","id":"msg_late","message":{"role":"assistant","model":"gpt-4o","provider":"openai","timestamp":1733227200000,"usage":{"input":20,"output":5,"cost":{"total":0.02}}}}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        let options = ReportOptions {
+            home_dir: Some(temp_dir.path().to_string_lossy().into_owned()),
+            clients: Some(vec!["gjc".to_string()]),
+            since: Some("2024-12-02".to_string()),
+            ..ReportOptions::default()
+        };
+
+        let yielded: Vec<UnifiedMessage> = report_messages(&options, None).unwrap().collect();
+        assert_eq!(yielded.len(), 1);
+        assert_eq!(yielded[0].date, "2024-12-03");
+        assert_eq!(yielded[0].cost, 0.02);
+
+        let all = ReportOptions {
+            since: None,
+            no_pricing: true,
+            ..options
+        };
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let mut total_cost = 0.0_f64;
+        rt.block_on(for_each_message(all.clone(), |message| {
+            total_cost += message.cost
+        }))
+        .unwrap();
+        assert!((total_cost - 0.03).abs() < 1e-9);
+        let iter = rt.block_on(parse_messages_iter(all)).unwrap();
+        assert_eq!(iter.client_timings().len(), 1);
+        assert_eq!(iter.count(), 2);
+    }
+
     #[test]
     fn test_parse_local_clients_honors_scanner_settings_opencode_db_paths() {
         // Regression guard: `parse_local_clients` used to call
@@ -7638,8 +11053,10 @@ mod tests {
             clients: Some(vec!["opencode".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings::default(),
+            ..Default::default()
         })
         .unwrap();
         assert_eq!(parsed_default.counts.get(ClientId::OpenCode), 0);
@@ -7653,11 +11070,13 @@ mod tests {
             clients: Some(vec!["opencode".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings {
                 opencode_db_paths: vec![external_db.clone()],
                 ..Default::default()
             },
+            ..Default::default()
         })
         .unwrap();
         assert_eq!(
@@ -7719,11 +11138,13 @@ mod tests {
             clients: Some(vec!["devin-cli".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings {
                 extra_scan_paths,
                 ..Default::default()
             },
+            ..Default::default()
         })
         .unwrap();
 
@@ -7794,8 +11215,10 @@ mod tests {
             clients: Some(vec!["devin-cli".to_string(), "devin-desktop".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings::default(),
+            ..Default::default()
         })
         .unwrap();
 
@@ -7876,8 +11299,10 @@ mod tests {
             clients: Some(vec!["devin-cli".to_string(), "devin-desktop".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings::default(),
+            ..Default::default()
         })
         .unwrap();
 
@@ -7941,11 +11366,13 @@ mod tests {
             clients: Some(vec!["devin-desktop".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings {
                 extra_scan_paths,
                 ..Default::default()
             },
+            ..Default::default()
         })
         .unwrap();
 
@@ -8093,8 +11520,10 @@ mod tests {
             clients: Some(vec!["hermes".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings::default(),
+            ..Default::default()
         })
         .unwrap();
         assert_eq!(parsed_default.counts.get(ClientId::Hermes), 0);
@@ -8108,11 +11537,13 @@ mod tests {
             clients: Some(vec!["hermes".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings {
                 extra_scan_paths,
                 ..Default::default()
             },
+            ..Default::default()
         })
         .unwrap();
 
@@ -8148,8 +11579,10 @@ mod tests {
             clients: Some(vec!["zed".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings::default(),
+            ..Default::default()
         })
         .unwrap();
         assert_eq!(parsed_default.counts.get(ClientId::Zed), 0);
@@ -8163,11 +11596,13 @@ mod tests {
             clients: Some(vec!["zed".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings {
                 extra_scan_paths,
                 ..Default::default()
             },
+            ..Default::default()
         })
         .unwrap();
 
@@ -8215,9 +11650,24 @@ mod tests {
                     clients: Some(clients),
                     since: None,
                     until: None,
+                    until_exclusive: false,
                     year: None,
                     group_by: GroupBy::default(),
                     scanner_settings: scanner::ScannerSettings::default(),
+                    min_cost: None,
+                    min_tokens: None,
+                    agent_filter: AgentFilter::default(),
+                    project_filter: ProjectFilter::default(),
+                    model_filter: ModelFilter::default(),
+                    client_timings: false,
+                    daily_costs: false,
+                    model_order: ModelOrder::default(),
+                    no_pricing: false,
+                    include_zero_cost: false,
+                    active_threshold: 0,
+                    intensity_mode: IntensityMode::default(),
+                    intensity_basis: IntensityBasis::default(),
+                    ..Default::default()
                 },
                 None,
             ))
@@ -8234,6 +11684,111 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_report_builder_matches_standalone_reports() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let sessions_dir = temp_dir
+            .path()
+            .join(".config/tokscale/antigravity-cache/sessions");
+        std::fs::create_dir_all(&sessions_dir).unwrap();
+        std::fs::write(
+            sessions_dir.join("ag-builder.jsonl"),
+            concat!(
+                r#"{"type":"usage","sessionId":"ag-builder","modelId":"model_placeholder_m84","timestamp":1711200000000,"input":12,"output":4,"cacheRead":2,"cacheWrite":0,"reasoning":1,"responseId":"resp-1"}"#,
+                "\n",
+                r#"{"type":"usage","sessionId":"ag-builder","modelId":"model_placeholder_m84","timestamp":1714000000000,"input":30,"output":9,"cacheRead":0,"cacheWrite":0,"reasoning":0,"responseId":"resp-2"}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+        let options = ReportOptions {
+            home_dir: Some(temp_dir.path().to_string_lossy().to_string()),
+            clients: Some(vec!["antigravity".to_string()]),
+            no_pricing: true,
+            ..Default::default()
+        };
+        let json = |value: serde_json::Value, volatile: &[&str]| {
+            let mut value = value;
+            for key in volatile {
+                value.as_object_mut().unwrap().remove(*key);
+            }
+            value
+        };
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        let builder = rt.block_on(ReportBuilder::parse(options.clone())).unwrap();
+        assert_eq!(builder.messages().len(), 2);
+
+        let models = rt.block_on(get_model_report(options.clone())).unwrap();
+        assert_eq!(
+            json(
+                serde_json::to_value(builder.model_report()).unwrap(),
+                &["processing_time_ms"]
+            ),
+            json(
+                serde_json::to_value(models).unwrap(),
+                &["processing_time_ms"]
+            )
+        );
+        let monthly = rt.block_on(get_monthly_report(options.clone())).unwrap();
+        assert_eq!(monthly.entries.len(), 2);
+        assert_eq!(
+            json(
+                serde_json::to_value(builder.monthly_report()).unwrap(),
+                &["processing_time_ms"]
+            ),
+            json(
+                serde_json::to_value(monthly).unwrap(),
+                &["processing_time_ms"]
+            )
+        );
+        let graph = rt
+            .block_on(generate_graph_with_loaded_pricing(options, None))
+            .unwrap();
+        assert_eq!(
+            json(serde_json::to_value(builder.graph()).unwrap(), &["meta"]),
+            json(serde_json::to_value(graph).unwrap(), &["meta"])
+        );
+    }
+
+    #[test]
+    fn test_headless_codex_captures_are_reported_as_headless() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let capture_dir = temp_dir.path().join(".config/tokscale/headless/codex");
+        std::fs::create_dir_all(&capture_dir).unwrap();
+        let line = r#"{"type":"turn.completed","model":"gpt-4o-mini","usage":{"input_tokens":120,"cached_input_tokens":20,"output_tokens":30}}"#;
+        std::fs::write(capture_dir.join("codex-a.jsonl"), format!("{line}\n")).unwrap();
+        // `tokscale headless --format json` writes the same stream as .json.
+        std::fs::write(capture_dir.join("codex-b.json"), format!("{line}\n")).unwrap();
+
+        let report = |agent_filter: AgentFilter| {
+            let options = ReportOptions {
+                home_dir: Some(temp_dir.path().to_string_lossy().to_string()),
+                clients: Some(vec!["codex".to_string()]),
+                group_by: GroupBy::Model,
+                agent_filter,
+                no_pricing: true,
+                ..Default::default()
+            };
+            tokio::runtime::Runtime::new()
+                .unwrap()
+                .block_on(get_model_report(options))
+                .unwrap()
+        };
+
+        let headless = report(AgentFilter::new(vec!["headless".to_string()], Vec::new()));
+        assert_eq!(headless.entries.len(), 1);
+        assert_eq!(headless.entries[0].model, "gpt-4o-mini");
+        assert_eq!(headless.entries[0].message_count, 2);
+        assert_eq!(headless.total_output, 60);
+
+        let untagged = report(AgentFilter::new(
+            vec![NO_AGENT_LABEL.to_string()],
+            Vec::new(),
+        ));
+        assert!(untagged.entries.is_empty());
+    }
+
     #[test]
     fn test_parse_local_clients_dedups_zed_threads_across_default_and_extra_dbs() {
         let temp_dir = tempfile::TempDir::new().unwrap();
@@ -8257,11 +11812,13 @@ mod tests {
             clients: Some(vec!["zed".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings {
                 extra_scan_paths,
                 ..Default::default()
             },
+            ..Default::default()
         })
         .unwrap();
 
@@ -8286,11 +11843,13 @@ mod tests {
             clients: Some(vec!["zed".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings {
                 extra_scan_paths,
                 ..Default::default()
             },
+            ..Default::default()
         })
         .unwrap();
 
@@ -8340,11 +11899,13 @@ mod tests {
             clients: Some(vec!["hermes".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings {
                 extra_scan_paths,
                 ..Default::default()
             },
+            ..Default::default()
         })
         .unwrap();
 
@@ -8417,11 +11978,13 @@ mod tests {
             clients: Some(vec!["claude".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings {
                 opencode_db_paths: vec![external_db.clone()],
                 ..Default::default()
             },
+            ..Default::default()
         })
         .unwrap();
 
@@ -8472,8 +12035,10 @@ mod tests {
             clients: Some(vec!["claude".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings::default(),
+            ..Default::default()
         })
         .unwrap();
 
@@ -8602,6 +12167,102 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_amp_rows_compare_client_credits_with_the_estimate() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let amp_dir = temp_dir.path().join(".local/share/amp/threads");
+        std::fs::create_dir_all(&amp_dir).unwrap();
+        let thread = r#"{
+            "id": "thread-amp-compare",
+            "created": 1775649600000,
+            "messages": [
+                {
+                    "role": "assistant",
+                    "messageId": 1,
+                    "usage": {
+                        "model": "claude-sonnet-4-0",
+                        "inputTokens": 100,
+                        "outputTokens": 20,
+                        "credits": 0.75
+                    }
+                },
+                {
+                    "role": "assistant",
+                    "messageId": 2,
+                    "usage": {
+                        "model": "claude-sonnet-4-0",
+                        "inputTokens": 50,
+                        "outputTokens": 10,
+                        "credits": 0.40
+                    }
+                }
+            ]
+        }"#;
+        std::fs::write(amp_dir.join("T-thread-amp-compare.json"), thread).unwrap();
+
+        let litellm = HashMap::from([(
+            "claude-sonnet-4-0".to_string(),
+            pricing::ModelPricing {
+                input_cost_per_token: Some(0.001),
+                output_cost_per_token: Some(0.002),
+                ..Default::default()
+            },
+        )]);
+        let pricing = pricing::PricingService::new(litellm, HashMap::new());
+        let messages = parse_all_messages_with_pricing(
+            temp_dir.path().to_str().unwrap(),
+            &["amp".to_string()],
+            Some(&pricing),
+        );
+
+        let entries = aggregate_model_usage_entries(messages, &GroupBy::Model);
+        assert_eq!(entries.len(), 1);
+        let comparison = entries[0].cost_comparison.clone().unwrap();
+        assert!((comparison.client_cost - 1.15).abs() < 1e-9);
+        // 100 * 0.001 + 20 * 0.002 plus 50 * 0.001 + 10 * 0.002.
+        assert!((comparison.estimated_cost - 0.21).abs() < 1e-9);
+        assert_eq!(comparison.message_count, 2);
+        assert!((entries[0].cost - comparison.estimated_cost).abs() < 1e-9);
+        let diff = comparison.percent_difference().unwrap();
+        assert!((diff - (0.21 - 1.15) / 1.15 * 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_aggregate_sums_only_known_byte_sizes() {
+        let message = |session: &str, bytes: Option<(i64, i64)>| {
+            let mut message = UnifiedMessage::new(
+                "cursor",
+                "composer-2",
+                "cursor",
+                session,
+                1_775_700_000_000,
+                TokenBreakdown {
+                    input: 10,
+                    ..Default::default()
+                },
+                0.0,
+            );
+            message.request_bytes = bytes.map(|(request, _)| request);
+            message.response_bytes = bytes.map(|(_, response)| response);
+            message
+        };
+
+        let entries = aggregate_model_usage_entries(
+            vec![
+                message("a", Some((1_000, 200))),
+                message("b", None),
+                message("c", Some((500, 50))),
+            ],
+            &GroupBy::Model,
+        );
+        assert_eq!(entries[0].request_bytes, Some(1_500));
+        assert_eq!(entries[0].response_bytes, Some(250));
+
+        let no_sizes = aggregate_model_usage_entries(vec![message("d", None)], &GroupBy::Model);
+        assert_eq!(no_sizes[0].request_bytes, None);
+        assert_eq!(sum_bytes([None, None]), None);
+    }
+
     #[test]
     fn test_parse_local_clients_amp_partial_ledger_recovers_message_fallback_day() {
         use chrono::TimeZone;
@@ -8663,8 +12324,10 @@ mod tests {
             clients: Some(vec!["amp".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: scanner::ScannerSettings::default(),
+            ..Default::default()
         })
         .unwrap();
 
@@ -8743,6 +12406,7 @@ mod tests {
                     cache_read: 0,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 0.05,
             ),
@@ -8751,4 +12415,235 @@ mod tests {
         let filtered = filter_messages_for_report(messages, &ReportOptions::default());
         assert_eq!(filtered.len(), 2);
     }
+
+    #[test]
+    fn test_model_filter_includes_then_excludes_by_normalized_id() {
+        let messages = || {
+            [
+                "claude-sonnet-4-5",
+                "claude-opus-4-5-20251101",
+                "gpt-5",
+                "text-embedding-3-small",
+                "gpt-5-experimental",
+            ]
+            .into_iter()
+            .map(|model| {
+                UnifiedMessage::new(
+                    "opencode",
+                    model,
+                    "anthropic",
+                    format!("session-{model}"),
+                    1_733_011_200_000,
+                    TokenBreakdown::default(),
+                    0.0,
+                )
+            })
+            .collect::<Vec<_>>()
+        };
+        let kept = |include: &[&str], exclude: &[&str]| {
+            let options = ReportOptions {
+                model_filter: ModelFilter::new(
+                    include.iter().map(|p| p.to_string()),
+                    exclude.iter().map(|p| p.to_string()),
+                )
+                .unwrap(),
+                ..Default::default()
+            };
+            let mut models: Vec<String> = filter_messages_for_report(messages(), &options)
+                .into_iter()
+                .map(|m| m.model_id)
+                .collect();
+            models.sort();
+            models
+        };
+
+        assert_eq!(kept(&[], &[]).len(), 5);
+        // Include-only: any pattern matching keeps the model. The dated
+        // opus id matches an anchored pattern through normalization.
+        assert_eq!(
+            kept(&["^claude-opus-4-5$", "^gpt-5$"], &[]),
+            vec!["claude-opus-4-5-20251101", "gpt-5"]
+        );
+        // Exclude-only.
+        assert_eq!(
+            kept(&[], &["embedding", "experimental"]),
+            vec!["claude-opus-4-5-20251101", "claude-sonnet-4-5", "gpt-5"]
+        );
+        // Combined: include first, then exclude from what is left.
+        assert_eq!(kept(&["^gpt"], &["experimental"]), vec!["gpt-5"]);
+
+        let err = ModelFilter::new(["claude(".to_string()], []).unwrap_err();
+        assert!(err.contains("Invalid model pattern 'claude('"), "{err}");
+    }
+
+    fn messages_on_days(days: &[&str]) -> Vec<UnifiedMessage> {
+        days.iter()
+            .map(|day| {
+                let mut msg = UnifiedMessage::new(
+                    "claude",
+                    "claude-sonnet-4-5",
+                    "anthropic",
+                    format!("session-{day}"),
+                    0,
+                    TokenBreakdown::default(),
+                    0.0,
+                );
+                msg.date = day.to_string();
+                msg
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_until_boundary_day_is_inclusive_by_default_and_dropped_when_exclusive() {
+        let days = ["2026-03-01", "2026-03-02", "2026-03-03"];
+        let dates = |messages: Vec<UnifiedMessage>| {
+            messages.into_iter().map(|m| m.date).collect::<Vec<_>>()
+        };
+
+        for until_exclusive in [false, true] {
+            let expected: &[&str] = if until_exclusive {
+                &["2026-03-01"]
+            } else {
+                &["2026-03-01", "2026-03-02"]
+            };
+
+            let report = ReportOptions {
+                until: Some("2026-03-02".to_string()),
+                until_exclusive,
+                ..Default::default()
+            };
+            assert_eq!(
+                dates(filter_messages_for_report(messages_on_days(&days), &report)),
+                expected,
+                "report, until_exclusive={until_exclusive}"
+            );
+
+            let local = LocalParseOptions {
+                until: Some("2026-03-02".to_string()),
+                until_exclusive,
+                ..Default::default()
+            };
+            assert_eq!(
+                dates(filter_unified_messages(messages_on_days(&days), &local)),
+                expected,
+                "unified, until_exclusive={until_exclusive}"
+            );
+
+            let parsed = messages_on_days(&days)
+                .iter()
+                .map(unified_to_parsed)
+                .collect();
+            let parsed_dates: Vec<String> = filter_parsed_messages(parsed, &local)
+                .into_iter()
+                .map(|m| m.date)
+                .collect();
+            assert_eq!(
+                parsed_dates, expected,
+                "parsed, until_exclusive={until_exclusive}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_far_future_messages_are_clamped_to_today() {
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let days = ["2026-03-01", "2999-12-31"];
+
+        let report = ReportOptions {
+            year: Some("2999".to_string()),
+            ..Default::default()
+        };
+        assert!(filter_messages_for_report(messages_on_days(&days), &report).is_empty());
+
+        let clamped = filter_messages_for_report(messages_on_days(&days), &Default::default());
+        assert_eq!(clamped[0].date, "2026-03-01");
+        assert_eq!(clamped[1].date, today);
+
+        let local = filter_unified_messages(messages_on_days(&days), &Default::default());
+        assert_eq!(local[1].date, today);
+
+        let limited = ReportOptions {
+            max_future_days: Some(30),
+            ..Default::default()
+        };
+        let kept = filter_messages_for_report(messages_on_days(&days), &limited);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].date, "2026-03-01");
+        let limited_local = LocalParseOptions {
+            max_future_days: Some(30),
+            ..Default::default()
+        };
+        let kept_local = filter_unified_messages(messages_on_days(&days), &limited_local);
+        assert_eq!(kept_local.len(), 1);
+        assert_eq!(kept_local[0].date, "2026-03-01");
+    }
+
+    fn messages_with_agents(agents: &[Option<&str>]) -> Vec<UnifiedMessage> {
+        let days: Vec<String> = (1..=agents.len())
+            .map(|day| format!("2026-03-{day:02}"))
+            .collect();
+        let day_refs: Vec<&str> = days.iter().map(String::as_str).collect();
+        messages_on_days(&day_refs)
+            .into_iter()
+            .zip(agents)
+            .map(|(mut msg, agent)| {
+                msg.agent = agent.map(str::to_string);
+                msg
+            })
+            .collect()
+    }
+
+    fn filtered_agents(options: &ReportOptions) -> Vec<Option<String>> {
+        let messages = messages_with_agents(&[Some("headless"), None, Some("build"), None]);
+        filter_messages_for_report(messages, options)
+            .into_iter()
+            .map(|m| m.agent)
+            .collect()
+    }
+
+    #[test]
+    fn test_agent_filter_keeps_only_headless_messages() {
+        let options = ReportOptions {
+            agent_filter: AgentFilter::new(vec!["Headless".to_string()], Vec::new()),
+            ..Default::default()
+        };
+        assert_eq!(
+            filtered_agents(&options),
+            vec![Some("headless".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_agent_filter_none_label_matches_untagged_messages() {
+        let options = ReportOptions {
+            agent_filter: AgentFilter::new(vec![NO_AGENT_LABEL.to_string()], Vec::new()),
+            ..Default::default()
+        };
+        assert_eq!(filtered_agents(&options), vec![None, None]);
+    }
+
+    #[test]
+    fn test_agent_filter_exclude_drops_matching_agents() {
+        let options = ReportOptions {
+            agent_filter: AgentFilter::new(Vec::new(), vec!["headless".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            filtered_agents(&options),
+            vec![None, Some("build".to_string()), None]
+        );
+
+        let options = ReportOptions {
+            agent_filter: AgentFilter::new(
+                vec!["headless".to_string(), "build".to_string()],
+                vec!["build".to_string()],
+            ),
+            ..Default::default()
+        };
+        assert_eq!(
+            filtered_agents(&options),
+            vec![Some("headless".to_string())]
+        );
+    }
 }
@@ -23,7 +23,9 @@ use std::time::UNIX_EPOCH;
 // 3: UnifiedMessage gained session_title, changing the bincode payload layout.
 // Old shards must read as Stale (silent rebuild), not Invalid (corruption
 // warning), so the format version moves with the struct.
-const CACHE_FORMAT_VERSION: u32 = 3;
+// 4: UnifiedMessage gained request_bytes/response_bytes.
+// 5: UnifiedMessage gained reported_total_tokens.
+const CACHE_FORMAT_VERSION: u32 = 5;
 // V2 intentionally starts cold and leaves source-message-cache.bin untouched:
 // the monolith did not record a trustworthy parser owner for migration.
 const CACHE_SHARD_DIRNAME: &str = "source-message-cache-v2";
@@ -39,8 +41,13 @@ thread_local! {
     static FULL_HASH_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
 }
 
+// Sources are parsed on rayon workers, so warm-load hits are counted globally
+// rather than per thread. Tests that read it must be serial.
+#[cfg(test)]
+static SOURCE_CACHE_HITS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
 fn cache_dir() -> Option<PathBuf> {
-    if crate::paths::is_config_dir_overridden()
+    if crate::paths::is_cache_dir_overridden()
         || dirs::config_dir().is_some()
         || cfg!(target_os = "macos") && dirs::home_dir().is_some()
     {
@@ -801,7 +808,9 @@ fn parser_version(client: ClientId) -> u32 {
         // These clients accumulated parser-only invalidations under the old
         // global schema. Their independent counters start from those histories
         // so future changes have an obvious local version to increment.
-        ClientId::Codex => 6,
+        // v6->v7: token_count rows without a timestamp or cumulative totals,
+        // and headless usage lines, now carry a line-offset dedup key.
+        ClientId::Codex => 7,
         // v4->v5: jcode's assistant-message timestamp is now back-calculated
         // to the turn start (timestamp - tool_duration_ms) instead of using
         // the recorded (end-anchored) timestamp directly. Follow-up to #890.
@@ -831,7 +840,10 @@ fn parser_version(client: ClientId) -> u32 {
         // Desktop v1 parsed a non-ACP shape and did not track its CLI title
         // lookup; its timestamp handling is unaffected by the #890 follow-up.
         ClientId::DevinDesktop => 2,
-        ClientId::Claude => 2,
+        // v3: cache writes carry the 1-hour TTL split (`cache_write_1h`).
+        // v4: compaction summaries and `<synthetic>` placeholders are skipped,
+        // and request-id-only entries now dedup on `request:<id>`.
+        ClientId::Claude => 4,
         // Junie's usage-event timestamp is now back-calculated to the call
         // start (timestampMs - usage.time) instead of the recorded
         // (end-anchored) timestampMs. Follow-up to #890.
@@ -1523,6 +1535,16 @@ fn full_hash_call_count() -> usize {
     FULL_HASH_CALLS.with(std::cell::Cell::get)
 }
 
+#[cfg(test)]
+pub(crate) fn record_source_cache_hit() {
+    SOURCE_CACHE_HITS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+}
+
+#[cfg(test)]
+pub(crate) fn source_cache_hit_count() -> usize {
+    SOURCE_CACHE_HITS.load(std::sync::atomic::Ordering::Relaxed)
+}
+
 #[cfg(test)]
 pub(crate) fn build_codex_incremental_cache(
     path: &Path,
@@ -1657,39 +1679,54 @@ mod tests {
     /// inside `temp_home`. CI runners can leak `XDG_CONFIG_HOME` /
     /// `XDG_CACHE_HOME` from the host, which would resolve cache shards outside
     /// the sandbox. Returns the previous values so the caller can restore.
-    fn sandbox_cache_env(
-        temp_home: &std::path::Path,
-    ) -> (
-        Option<std::ffi::OsString>,
-        Option<std::ffi::OsString>,
-        Option<std::ffi::OsString>,
-        Option<std::ffi::OsString>,
-    ) {
-        let prev_home = std::env::var_os("HOME");
-        let prev_xdg_config = std::env::var_os("XDG_CONFIG_HOME");
-        let prev_xdg_cache = std::env::var_os("XDG_CACHE_HOME");
-        let prev_override = std::env::var_os("TOKSCALE_CONFIG_DIR");
+    const SANDBOXED_CACHE_VARS: [&str; 5] = [
+        "HOME",
+        "XDG_CONFIG_HOME",
+        "XDG_CACHE_HOME",
+        "TOKSCALE_CONFIG_DIR",
+        "TOKSCALE_CACHE_DIR",
+    ];
+
+    fn sandbox_cache_env(temp_home: &std::path::Path) -> Vec<Option<std::ffi::OsString>> {
+        let prev = SANDBOXED_CACHE_VARS.iter().map(std::env::var_os).collect();
         unsafe {
             std::env::set_var("HOME", temp_home);
             std::env::set_var("XDG_CONFIG_HOME", temp_home.join(".config"));
             std::env::set_var("XDG_CACHE_HOME", temp_home.join(".cache"));
             std::env::remove_var("TOKSCALE_CONFIG_DIR");
+            std::env::remove_var("TOKSCALE_CACHE_DIR");
         }
-        (prev_home, prev_xdg_config, prev_xdg_cache, prev_override)
+        prev
     }
 
-    fn restore_cache_env(
-        prev: (
-            Option<std::ffi::OsString>,
-            Option<std::ffi::OsString>,
-            Option<std::ffi::OsString>,
-            Option<std::ffi::OsString>,
-        ),
-    ) {
-        restore_env_var("HOME", prev.0);
-        restore_env_var("XDG_CONFIG_HOME", prev.1);
-        restore_env_var("XDG_CACHE_HOME", prev.2);
-        restore_env_var("TOKSCALE_CONFIG_DIR", prev.3);
+    fn restore_cache_env(prev: Vec<Option<std::ffi::OsString>>) {
+        for (key, value) in SANDBOXED_CACHE_VARS.iter().zip(prev) {
+            restore_env_var(key, value);
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn cache_dir_follows_tokscale_cache_dir() {
+        let temp_home = TempDir::new().unwrap();
+        let prev_env = sandbox_cache_env(temp_home.path());
+        assert_eq!(
+            cache_shard_dir(),
+            Some(
+                temp_home
+                    .path()
+                    .join(".config/tokscale/cache")
+                    .join(CACHE_SHARD_DIRNAME)
+            )
+        );
+
+        let relocated = temp_home.path().join("scratch");
+        unsafe {
+            std::env::set_var("TOKSCALE_CACHE_DIR", &relocated);
+        }
+        assert_eq!(cache_shard_dir(), Some(relocated.join(CACHE_SHARD_DIRNAME)));
+        assert_eq!(cache_lock_path(), Some(relocated.join(CACHE_LOCK_FILENAME)));
+        restore_cache_env(prev_env);
     }
 
     fn write_temp_file(content: &[u8]) -> NamedTempFile {
@@ -1716,6 +1753,7 @@ mod tests {
                     cache_read: 3,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 0.0,
             )],
@@ -2057,8 +2095,8 @@ mod tests {
 
     #[test]
     fn test_codex_duration_parser_version_invalidates_v4_entries() {
-        assert_eq!(parser_version(ClientId::Codex), 6);
-        assert_eq!(parser_version(ClientId::Claude), 2);
+        assert_eq!(parser_version(ClientId::Codex), 7);
+        assert_eq!(parser_version(ClientId::Claude), 4);
     }
 
     #[test]
@@ -2322,6 +2360,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
         );
@@ -2356,6 +2395,7 @@ mod tests {
             None,
             false,
             &crate::scanner::ScannerSettings::default(),
+            false,
         );
         assert_eq!(first.len(), 1);
         assert_eq!(first[0].tokens.input, i64::MAX);
@@ -2388,6 +2428,7 @@ mod tests {
             None,
             false,
             &crate::scanner::ScannerSettings::default(),
+            false,
         );
         assert_eq!(second, first);
 
@@ -2897,6 +2938,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
             Some("trace-cache:chat".to_string()),
@@ -2914,6 +2956,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
             Some("trace-cache:chat".to_string()),
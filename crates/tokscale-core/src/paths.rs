@@ -11,8 +11,33 @@
 //! settings.json edits the user made via the documented path. This module
 //! enforces the unified `~/.config/tokscale/` location on macOS + Linux,
 //! while keeping the platform default on Windows.
+//!
+//! Every consumer resolves its directory through here: the pricing and
+//! message caches through [`get_cache_dir`], and the scanner's config-rooted
+//! paths (Cursor cache, headless captures) through [`config_dir_for_home`].
+//! `TOKSCALE_CONFIG_DIR` and `TOKSCALE_CACHE_DIR` therefore relocate all of
+//! them at once. A profile (`--profile` / `TOKSCALE_PROFILE`) moves the
+//! caches one level down into `<cache_dir>/<profile>/`; settings and
+//! credentials in the config dir stay shared.
 
 use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Environment variable that relocates the config dir.
+pub const CONFIG_DIR_ENV: &str = "TOKSCALE_CONFIG_DIR";
+/// Environment variable that relocates the cache dir on its own.
+pub const CACHE_DIR_ENV: &str = "TOKSCALE_CACHE_DIR";
+/// Environment variable that selects a cache profile.
+pub const PROFILE_ENV: &str = "TOKSCALE_PROFILE";
+
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Non-empty value of `var`; empty strings count as unset.
+fn env_dir(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
 
 /// Resolve the tokscale config dir, honoring `TOKSCALE_CONFIG_DIR` first.
 ///
@@ -32,10 +57,8 @@ use std::path::PathBuf;
 /// 4. Windows (and any other platform): `dirs::config_dir().join("tokscale")`.
 /// 5. Last-ditch fallback: `./.tokscale` so a missing HOME never panics.
 pub fn get_config_dir() -> PathBuf {
-    if let Some(custom) = std::env::var_os("TOKSCALE_CONFIG_DIR") {
-        if !custom.is_empty() {
-            return PathBuf::from(custom);
-        }
+    if let Some(custom) = env_dir(CONFIG_DIR_ENV) {
+        return custom;
     }
 
     #[cfg(target_os = "macos")]
@@ -50,15 +73,93 @@ pub fn get_config_dir() -> PathBuf {
         .unwrap_or_else(|| PathBuf::from(".tokscale"))
 }
 
-/// Resolve the tokscale cache dir as `<config_dir>/cache`.
+/// Resolve the tokscale cache dir: `TOKSCALE_CACHE_DIR` verbatim when set
+/// to a non-empty value, else `<config_dir>/cache`. With an active profile
+/// the result is that directory's `<profile>` subdirectory.
 ///
 /// Caches (TUI display data, source-message bincode, pricing JSON, the
 /// OpenCode migration record, Wrapped fonts/images) all live under this
-/// single subdirectory so an isolated profile (`TOKSCALE_CONFIG_DIR=...`)
+/// single directory so an isolated profile (`TOKSCALE_CONFIG_DIR=...`)
 /// covers everything in one shot, and so `rm -rf <cache_dir>` is always
-/// safe — no durable state mixed in.
+/// safe — no durable state mixed in. `TOKSCALE_CACHE_DIR` moves just the
+/// caches, e.g. onto a scratch disk, while settings stay put.
 pub fn get_cache_dir() -> PathBuf {
-    get_config_dir().join("cache")
+    let root = env_dir(CACHE_DIR_ENV).unwrap_or_else(|| get_config_dir().join("cache"));
+    match active_profile() {
+        Some(profile) => root.join(profile),
+        None => root,
+    }
+}
+
+/// Check that `name` can be used as a profile: non-empty ASCII letters,
+/// digits, `-` and `_`, so it is always a single path component.
+pub fn validate_profile_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid profile name '{name}': use letters, digits, '-' or '_'"
+        ))
+    }
+}
+
+/// Select the cache profile for this process, overriding `TOKSCALE_PROFILE`.
+/// Only the first call takes effect.
+pub fn set_profile(name: &str) -> Result<(), String> {
+    validate_profile_name(name)?;
+    let _ = PROFILE.set(name.to_string());
+    Ok(())
+}
+
+/// The profile set by [`set_profile`], else a valid non-empty
+/// `TOKSCALE_PROFILE`. Invalid environment values are ignored here; the CLI
+/// rejects them at startup.
+pub fn active_profile() -> Option<String> {
+    if let Some(profile) = PROFILE.get() {
+        return Some(profile.clone());
+    }
+    std::env::var(PROFILE_ENV)
+        .ok()
+        .filter(|name| validate_profile_name(name).is_ok())
+}
+
+/// The tokscale config dir as seen from `home_dir`, for paths the scanner
+/// resolves per home (the Cursor usage cache, headless captures).
+///
+/// With `use_env_roots` (no explicit `--home`) this agrees with
+/// [`get_config_dir`]: `TOKSCALE_CONFIG_DIR` first, then `XDG_CONFIG_HOME`
+/// on Linux and `%APPDATA%` on Windows. Without it, an explicit home is
+/// taken at face value: `<home>/.config/tokscale`, or
+/// `<home>/AppData/Roaming/tokscale` on Windows.
+pub fn config_dir_for_home(home_dir: &str, use_env_roots: bool) -> PathBuf {
+    if use_env_roots {
+        if let Some(custom) = env_dir(CONFIG_DIR_ENV) {
+            return custom;
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Ok(xdg_config_home) = std::env::var("XDG_CONFIG_HOME") {
+            return PathBuf::from(format!("{xdg_config_home}/tokscale"));
+        }
+
+        // Match get_config_dir() so default Windows scans read the same
+        // %APPDATA% root used by cache writers.
+        #[cfg(target_os = "windows")]
+        if let Some(dir) = dirs::config_dir() {
+            return dir.join("tokscale");
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    if !use_env_roots {
+        return std::path::Path::new(home_dir).join("AppData/Roaming/tokscale");
+    }
+
+    PathBuf::from(format!("{home_dir}/.config/tokscale"))
 }
 
 /// Whether `TOKSCALE_CONFIG_DIR` is explicitly set in the environment.
@@ -70,7 +171,14 @@ pub fn get_cache_dir() -> PathBuf {
 /// the historic `~/.cache/tokscale/` or `~/Library/Caches/tokscale/`
 /// locations defeats that contract.
 pub fn is_config_dir_overridden() -> bool {
-    std::env::var_os("TOKSCALE_CONFIG_DIR").is_some_and(|v| !v.is_empty())
+    env_dir(CONFIG_DIR_ENV).is_some()
+}
+
+/// Whether the cache dir was relocated, by `TOKSCALE_CACHE_DIR`,
+/// `TOKSCALE_CONFIG_DIR`, or a profile. Legacy cache probes are skipped in
+/// every case, so a profile never inherits the default profile's old caches.
+pub fn is_cache_dir_overridden() -> bool {
+    is_config_dir_overridden() || env_dir(CACHE_DIR_ENV).is_some() || active_profile().is_some()
 }
 
 /// Pre-#470 cache directory at `dirs::cache_dir()/tokscale`.
@@ -80,10 +188,11 @@ pub fn is_config_dir_overridden() -> bool {
 /// historically lived). On Linux this resolves to `$XDG_CACHE_HOME/tokscale`
 /// or `~/.cache/tokscale/`.
 ///
-/// Returns `None` when `TOKSCALE_CONFIG_DIR` is set so the override stays
-/// hermetic (no legacy-data leak into isolated profiles).
+/// Returns `None` when `TOKSCALE_CONFIG_DIR` or `TOKSCALE_CACHE_DIR` is set,
+/// or a profile is active, so the override stays hermetic (no legacy-data
+/// leak into isolated profiles).
 pub fn legacy_dirs_cache_dir() -> Option<PathBuf> {
-    if is_config_dir_overridden() {
+    if is_cache_dir_overridden() {
         return None;
     }
     dirs::cache_dir().map(|d| d.join("tokscale"))
@@ -97,10 +206,10 @@ pub fn legacy_dirs_cache_dir() -> Option<PathBuf> {
 /// [`legacy_dirs_cache_dir`]; on macOS it does NOT (Library/Caches vs
 /// `.cache`), so both legacy probes need to run during migration.
 ///
-/// Returns `None` when `TOKSCALE_CONFIG_DIR` is set or HOME cannot be
-/// resolved.
+/// Returns `None` when `TOKSCALE_CONFIG_DIR` or `TOKSCALE_CACHE_DIR` is set,
+/// a profile is active, or HOME cannot be resolved.
 pub fn legacy_dot_cache_tokscale_dir() -> Option<PathBuf> {
-    if is_config_dir_overridden() {
+    if is_cache_dir_overridden() {
         return None;
     }
     dirs::home_dir().map(|h| h.join(".cache").join("tokscale"))
@@ -113,37 +222,29 @@ mod tests {
     use std::env;
     use std::path::Path;
 
-    fn save_env() -> (
-        Option<std::ffi::OsString>,
-        Option<std::ffi::OsString>,
-        Option<std::ffi::OsString>,
-    ) {
-        (
-            env::var_os("TOKSCALE_CONFIG_DIR"),
-            env::var_os("HOME"),
-            env::var_os("XDG_CONFIG_HOME"),
-        )
+    const SAVED_VARS: [&str; 6] = [
+        "TOKSCALE_CONFIG_DIR",
+        "TOKSCALE_CACHE_DIR",
+        "TOKSCALE_PROFILE",
+        "TOKSCALE_HEADLESS_DIR",
+        "HOME",
+        "XDG_CONFIG_HOME",
+    ];
+
+    fn save_env() -> Vec<(&'static str, Option<std::ffi::OsString>)> {
+        SAVED_VARS
+            .iter()
+            .map(|var| (*var, env::var_os(var)))
+            .collect()
     }
 
-    fn restore_env(
-        prev: (
-            Option<std::ffi::OsString>,
-            Option<std::ffi::OsString>,
-            Option<std::ffi::OsString>,
-        ),
-    ) {
+    fn restore_env(prev: Vec<(&'static str, Option<std::ffi::OsString>)>) {
         unsafe {
-            match prev.0 {
-                Some(v) => env::set_var("TOKSCALE_CONFIG_DIR", v),
-                None => env::remove_var("TOKSCALE_CONFIG_DIR"),
-            }
-            match prev.1 {
-                Some(v) => env::set_var("HOME", v),
-                None => env::remove_var("HOME"),
-            }
-            match prev.2 {
-                Some(v) => env::set_var("XDG_CONFIG_HOME", v),
-                None => env::remove_var("XDG_CONFIG_HOME"),
+            for (var, value) in prev {
+                match value {
+                    Some(v) => env::set_var(var, v),
+                    None => env::remove_var(var),
+                }
             }
         }
     }
@@ -198,6 +299,7 @@ mod tests {
         let prev = save_env();
         unsafe {
             env::set_var("TOKSCALE_CONFIG_DIR", "/tmp/tokscale-cache-test");
+            env::remove_var("TOKSCALE_CACHE_DIR");
         }
         assert_eq!(
             get_cache_dir(),
@@ -206,6 +308,120 @@ mod tests {
         restore_env(prev);
     }
 
+    #[test]
+    #[serial]
+    fn cache_dir_override_moves_only_the_caches() {
+        let prev = save_env();
+        unsafe {
+            env::set_var("TOKSCALE_CONFIG_DIR", "/tmp/tokscale-config");
+            env::set_var("TOKSCALE_CACHE_DIR", "/tmp/tokscale-scratch");
+        }
+        assert_eq!(get_config_dir(), PathBuf::from("/tmp/tokscale-config"));
+        assert_eq!(get_cache_dir(), PathBuf::from("/tmp/tokscale-scratch"));
+
+        unsafe {
+            env::set_var("TOKSCALE_CACHE_DIR", "");
+        }
+        assert_eq!(get_cache_dir(), PathBuf::from("/tmp/tokscale-config/cache"));
+        restore_env(prev);
+    }
+
+    #[test]
+    #[serial]
+    fn env_overrides_redirect_every_subsystem() {
+        let prev = save_env();
+        unsafe {
+            env::set_var("TOKSCALE_CONFIG_DIR", "/tmp/tokscale-config");
+            env::set_var("TOKSCALE_CACHE_DIR", "/tmp/tokscale-scratch");
+            env::remove_var("TOKSCALE_HEADLESS_DIR");
+        }
+        let home = "/tmp/tokscale-home";
+
+        assert_eq!(
+            crate::pricing::cache::get_cache_path("pricing-litellm.json"),
+            PathBuf::from("/tmp/tokscale-scratch/pricing-litellm.json")
+        );
+        assert_eq!(
+            crate::pricing::history::history_dir(),
+            PathBuf::from("/tmp/tokscale-scratch/pricing-history")
+        );
+        assert_eq!(
+            crate::ClientId::Cursor
+                .data()
+                .resolve_path_with_env_strategy(home, true),
+            "/tmp/tokscale-config/cursor-cache"
+        );
+        assert_eq!(
+            crate::scanner::headless_roots(home)[0],
+            PathBuf::from("/tmp/tokscale-config/headless")
+        );
+
+        // An explicit --home is taken at face value.
+        assert_eq!(
+            config_dir_for_home(home, false),
+            PathBuf::from(format!("{home}/.config/tokscale"))
+        );
+        assert_eq!(
+            crate::scanner::headless_roots_with_env_strategy(home, false)[0],
+            PathBuf::from(format!("{home}/.config/tokscale/headless"))
+        );
+
+        // A cache-only override still keeps legacy cache probes hermetic.
+        unsafe {
+            env::remove_var("TOKSCALE_CONFIG_DIR");
+        }
+        assert!(is_cache_dir_overridden());
+        assert!(legacy_dirs_cache_dir().is_none());
+        assert!(legacy_dot_cache_tokscale_dir().is_none());
+        restore_env(prev);
+    }
+
+    #[test]
+    #[serial]
+    fn profiles_get_distinct_cache_dirs() {
+        let prev = save_env();
+        unsafe {
+            env::set_var("TOKSCALE_CONFIG_DIR", "/tmp/tokscale-config");
+            env::remove_var("TOKSCALE_CACHE_DIR");
+            env::set_var("TOKSCALE_PROFILE", "work");
+        }
+        let work = crate::pricing::cache::get_cache_path("pricing-litellm.json");
+        assert_eq!(
+            work,
+            PathBuf::from("/tmp/tokscale-config/cache/work/pricing-litellm.json")
+        );
+        assert!(is_cache_dir_overridden());
+
+        unsafe {
+            env::set_var("TOKSCALE_PROFILE", "personal");
+        }
+        let personal = crate::pricing::cache::get_cache_path("pricing-litellm.json");
+        assert_eq!(
+            personal,
+            PathBuf::from("/tmp/tokscale-config/cache/personal/pricing-litellm.json")
+        );
+        assert_ne!(work, personal);
+        // Settings and credentials stay shared across profiles.
+        assert_eq!(get_config_dir(), PathBuf::from("/tmp/tokscale-config"));
+
+        // A name that would escape the cache dir is ignored.
+        unsafe {
+            env::set_var("TOKSCALE_PROFILE", "../work");
+        }
+        assert_eq!(get_cache_dir(), PathBuf::from("/tmp/tokscale-config/cache"));
+        restore_env(prev);
+    }
+
+    #[test]
+    fn profile_names_are_single_path_components() {
+        assert!(validate_profile_name("work").is_ok());
+        assert!(validate_profile_name("client_a-2").is_ok());
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("..").is_err());
+        assert!(validate_profile_name("a/b").is_err());
+        assert!(validate_profile_name("my profile").is_err());
+    }
+
     #[test]
     #[serial]
     fn legacy_helpers_return_none_when_overridden() {
@@ -224,6 +440,7 @@ mod tests {
         let prev = save_env();
         unsafe {
             env::remove_var("TOKSCALE_CONFIG_DIR");
+            env::remove_var("TOKSCALE_CACHE_DIR");
         }
         assert!(
             legacy_dirs_cache_dir().is_some(),
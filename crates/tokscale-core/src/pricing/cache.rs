@@ -103,7 +103,7 @@ pub fn save_cache<T: Serialize>(filename: &str, data: &T) -> Result<(), std::io:
 }
 
 fn legacy_cache_paths(filename: &str) -> Vec<PathBuf> {
-    if crate::paths::is_config_dir_overridden() {
+    if crate::paths::is_cache_dir_overridden() {
         return Vec::new();
     }
 
@@ -51,10 +51,14 @@ struct CustomModelPricing {
     output_cost_per_token_above_200k_tokens: Option<f64>,
     output_cost_per_token_above_256k_tokens: Option<f64>,
     output_cost_per_token_above_272k_tokens: Option<f64>,
+    reasoning_cost_per_million_tokens: Option<f64>,
+    reasoning_cost_per_token: Option<f64>,
     cache_creation_input_token_cost_per_million_tokens: Option<f64>,
     cache_creation_input_token_cost_per_million_tokens_above_200k_tokens: Option<f64>,
     cache_creation_input_token_cost: Option<f64>,
     cache_creation_input_token_cost_above_200k_tokens: Option<f64>,
+    cache_creation_input_token_cost_per_million_tokens_above_1hr: Option<f64>,
+    cache_creation_input_token_cost_above_1hr: Option<f64>,
     cache_read_input_token_cost_per_million_tokens: Option<f64>,
     cache_read_input_token_cost_per_million_tokens_above_200k_tokens: Option<f64>,
     cache_read_input_token_cost_per_million_tokens_above_272k_tokens: Option<f64>,
@@ -137,6 +141,12 @@ impl CustomModelPricing {
                 "output_cost_per_million_tokens_above_272k_tokens",
                 "output_cost_per_token_above_272k_tokens",
             )?,
+            reasoning_cost_per_token: price_field(
+                self.reasoning_cost_per_million_tokens,
+                self.reasoning_cost_per_token,
+                "reasoning_cost_per_million_tokens",
+                "reasoning_cost_per_token",
+            )?,
             cache_creation_input_token_cost: price_field(
                 self.cache_creation_input_token_cost_per_million_tokens,
                 self.cache_creation_input_token_cost,
@@ -149,6 +159,12 @@ impl CustomModelPricing {
                 "cache_creation_input_token_cost_per_million_tokens_above_200k_tokens",
                 "cache_creation_input_token_cost_above_200k_tokens",
             )?,
+            cache_creation_input_token_cost_above_1hr: price_field(
+                self.cache_creation_input_token_cost_per_million_tokens_above_1hr,
+                self.cache_creation_input_token_cost_above_1hr,
+                "cache_creation_input_token_cost_per_million_tokens_above_1hr",
+                "cache_creation_input_token_cost_above_1hr",
+            )?,
             cache_read_input_token_cost: price_field(
                 self.cache_read_input_token_cost_per_million_tokens,
                 self.cache_read_input_token_cost,
@@ -0,0 +1,213 @@
+//! Dated pricing snapshots for recomputing old usage at the prices of the
+//! time (`--pricing-date`).
+//!
+//! Every live fetch writes the three upstream datasets to
+//! `<cache dir>/pricing-history/YYYY-MM-DD.json`, so there is at most one
+//! snapshot per day and the latest fetch of a day wins. Custom pricing is not
+//! part of a snapshot: it is the user's own file and always applies as-is.
+
+use super::cache;
+use super::litellm::ModelPricing;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const HISTORY_DIRNAME: &str = "pricing-history";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Serialize)]
+struct SnapshotRef<'a> {
+    litellm: &'a HashMap<String, ModelPricing>,
+    openrouter: &'a HashMap<String, ModelPricing>,
+    models_dev: &'a HashMap<String, ModelPricing>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Snapshot {
+    #[serde(default)]
+    pub litellm: HashMap<String, ModelPricing>,
+    #[serde(default)]
+    pub openrouter: HashMap<String, ModelPricing>,
+    #[serde(default)]
+    pub models_dev: HashMap<String, ModelPricing>,
+}
+
+/// Which snapshot serves a requested date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    Exact(NaiveDate),
+    /// No snapshot on the requested day; this is the closest one before it.
+    Earlier(NaiveDate),
+}
+
+impl Selection {
+    pub fn date(self) -> NaiveDate {
+        match self {
+            Selection::Exact(date) | Selection::Earlier(date) => date,
+        }
+    }
+}
+
+pub fn history_dir() -> PathBuf {
+    cache::get_cache_dir().join(HISTORY_DIRNAME)
+}
+
+fn snapshot_path(dir: &Path, date: NaiveDate) -> PathBuf {
+    dir.join(format!("{}.json", date.format(DATE_FORMAT)))
+}
+
+/// Snapshot dates present in `dir`, ascending. Files that aren't named
+/// `YYYY-MM-DD.json` are ignored.
+pub fn snapshot_dates(dir: &Path) -> Vec<NaiveDate> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dates: Vec<NaiveDate> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            let stem = name.to_str()?.strip_suffix(".json")?;
+            NaiveDate::parse_from_str(stem, DATE_FORMAT).ok()
+        })
+        .collect();
+    dates.sort_unstable();
+    dates
+}
+
+/// Pick the snapshot for `requested` out of `available`: the same day if
+/// present, otherwise the nearest earlier one. A date before all recorded
+/// history is an error, since a later snapshot would price the past with
+/// prices that didn't exist yet.
+pub fn select(available: &[NaiveDate], requested: NaiveDate) -> Result<Selection, String> {
+    match available.iter().copied().filter(|d| *d <= requested).max() {
+        Some(date) if date == requested => Ok(Selection::Exact(date)),
+        Some(date) => Ok(Selection::Earlier(date)),
+        None => match available.iter().min() {
+            Some(earliest) => Err(format!(
+                "No pricing snapshot on or before {}; pricing history starts at {}",
+                requested.format(DATE_FORMAT),
+                earliest.format(DATE_FORMAT)
+            )),
+            None => Err(format!(
+                "No pricing history recorded yet in {}; run any report online to start it",
+                history_dir().display()
+            )),
+        },
+    }
+}
+
+pub fn load(dir: &Path, date: NaiveDate) -> Result<Snapshot, String> {
+    let path = snapshot_path(dir, date);
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// Write today's snapshot, replacing an earlier one from the same day.
+pub fn record(
+    dir: &Path,
+    date: NaiveDate,
+    litellm: &HashMap<String, ModelPricing>,
+    openrouter: &HashMap<String, ModelPricing>,
+    models_dev: &HashMap<String, ModelPricing>,
+) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let content = serde_json::to_string(&SnapshotRef {
+        litellm,
+        openrouter,
+        models_dev,
+    })?;
+    let path = snapshot_path(dir, date);
+    let tmp_path = dir.join(format!(
+        ".{}.{}.tmp",
+        date.format(DATE_FORMAT),
+        std::process::id()
+    ));
+    let result = fs::write(&tmp_path, content)
+        .and_then(|()| crate::fs_atomic::replace_file(&tmp_path, &path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, DATE_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn select_prefers_exact_then_nearest_earlier() {
+        let available = [date("2025-01-10"), date("2025-03-01"), date("2025-02-01")];
+
+        assert_eq!(
+            select(&available, date("2025-02-01")),
+            Ok(Selection::Exact(date("2025-02-01")))
+        );
+        assert_eq!(
+            select(&available, date("2025-02-20")),
+            Ok(Selection::Earlier(date("2025-02-01")))
+        );
+        assert_eq!(
+            select(&available, date("2026-01-01")),
+            Ok(Selection::Earlier(date("2025-03-01")))
+        );
+    }
+
+    #[test]
+    fn select_errors_before_history_starts_or_without_history() {
+        let err = select(&[date("2025-01-10")], date("2024-12-31")).unwrap_err();
+        assert!(err.contains("on or before 2024-12-31"), "{err}");
+        assert!(err.contains("starts at 2025-01-10"), "{err}");
+
+        let err = select(&[], date("2024-12-31")).unwrap_err();
+        assert!(err.contains("No pricing history recorded yet"), "{err}");
+    }
+
+    #[test]
+    fn record_then_load_round_trips_and_lists_dates() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut litellm = HashMap::new();
+        litellm.insert(
+            "claude-sonnet-4-5".to_string(),
+            ModelPricing {
+                input_cost_per_token: Some(3e-6),
+                ..Default::default()
+            },
+        );
+
+        record(
+            dir.path(),
+            date("2025-02-01"),
+            &litellm,
+            &HashMap::new(),
+            &HashMap::new(),
+        )
+        .unwrap();
+        record(
+            dir.path(),
+            date("2025-01-10"),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        assert_eq!(
+            snapshot_dates(dir.path()),
+            vec![date("2025-01-10"), date("2025-02-01")]
+        );
+        let snapshot = load(dir.path(), date("2025-02-01")).unwrap();
+        assert_eq!(
+            snapshot.litellm["claude-sonnet-4-5"].input_cost_per_token,
+            Some(3e-6)
+        );
+        assert!(snapshot.openrouter.is_empty());
+        assert!(load(dir.path(), date("2025-03-01")).is_err());
+    }
+}
@@ -1,12 +1,11 @@
 use super::cache;
+use super::retry::{self, AttemptError};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 const CACHE_FILENAME: &str = "pricing-litellm.json";
-const PRICING_URL: &str =
+pub const PRICING_URL: &str =
     "https://raw.githubusercontent.com/BerriAI/litellm/main/model_prices_and_context_window.json";
-const MAX_RETRIES: u32 = 3;
-const INITIAL_BACKOFF_MS: u64 = 200;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ModelPricing {
@@ -20,8 +19,16 @@ pub struct ModelPricing {
     pub output_cost_per_token_above_200k_tokens: Option<f64>,
     pub output_cost_per_token_above_256k_tokens: Option<f64>,
     pub output_cost_per_token_above_272k_tokens: Option<f64>,
+    /// Separate rate for reasoning ("thinking") tokens, which some Gemini
+    /// models bill apart from output. When absent, reasoning is priced as
+    /// output.
+    #[serde(alias = "output_cost_per_reasoning_token")]
+    pub reasoning_cost_per_token: Option<f64>,
     pub cache_creation_input_token_cost: Option<f64>,
     pub cache_creation_input_token_cost_above_200k_tokens: Option<f64>,
+    /// Cache-write price for Anthropic's 1-hour cache TTL, applied to
+    /// `TokenBreakdown::cache_write_1h`.
+    pub cache_creation_input_token_cost_above_1hr: Option<f64>,
     pub cache_read_input_token_cost: Option<f64>,
     pub cache_read_input_token_cost_above_200k_tokens: Option<f64>,
     pub cache_read_input_token_cost_above_272k_tokens: Option<f64>,
@@ -47,71 +54,53 @@ pub async fn fetch() -> Result<PricingDataset, reqwest::Error> {
         .connect_timeout(std::time::Duration::from_secs(10))
         .build()?;
 
-    let mut last_error: Option<reqwest::Error> = None;
-
-    for attempt in 0..MAX_RETRIES {
-        match client.get(PRICING_URL).send().await {
-            Ok(response) => {
-                let status = response.status();
-
-                if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                    eprintln!(
-                        "[tokscale] LiteLLM HTTP {} (attempt {}/{})",
-                        status,
-                        attempt + 1,
-                        MAX_RETRIES
-                    );
-                    let _ = response.bytes().await;
-                    if attempt < MAX_RETRIES - 1 {
-                        tokio::time::sleep(std::time::Duration::from_millis(
-                            INITIAL_BACKOFF_MS * (1 << attempt),
-                        ))
-                        .await;
-                    }
-                    continue;
-                }
-
-                if !status.is_success() {
-                    eprintln!("[tokscale] LiteLLM HTTP {}", status);
-                    return Err(response.error_for_status().unwrap_err());
-                }
-
-                match response.json::<PricingDataset>().await {
-                    Ok(data) => {
-                        if let Err(e) = cache::save_cache(CACHE_FILENAME, &data) {
-                            eprintln!(
-                                "[tokscale] Warning: Failed to cache LiteLLM pricing at {}: {}",
-                                cache::get_cache_path(CACHE_FILENAME).display(),
-                                e
-                            );
-                        }
-                        return Ok(data);
-                    }
-                    Err(e) => {
-                        eprintln!("[tokscale] LiteLLM JSON parse failed: {}", e);
-                        return Err(e);
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!(
-                    "[tokscale] LiteLLM network error (attempt {}/{}): {}",
-                    attempt + 1,
-                    MAX_RETRIES,
-                    e
-                );
-                last_error = Some(e);
-                if attempt < MAX_RETRIES - 1 {
-                    tokio::time::sleep(std::time::Duration::from_millis(
-                        INITIAL_BACKOFF_MS * (1 << attempt),
-                    ))
-                    .await;
-                }
-            }
+    let attempts = retry::max_attempts();
+    let client = &client;
+    retry::retry_with_backoff(attempts, retry::backoff_delay, |attempt| async move {
+        let response = client.get(PRICING_URL).send().await.map_err(|e| {
+            eprintln!(
+                "[tokscale] LiteLLM network error (attempt {}/{}): {}",
+                attempt + 1,
+                attempts,
+                e
+            );
+            AttemptError::Transient(e)
+        })?;
+        let status = response.status();
+
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            eprintln!(
+                "[tokscale] LiteLLM HTTP {} (attempt {}/{})",
+                status,
+                attempt + 1,
+                attempts
+            );
+            return Err(AttemptError::Transient(
+                response.error_for_status().unwrap_err(),
+            ));
+        }
+
+        if !status.is_success() {
+            eprintln!("[tokscale] LiteLLM HTTP {}", status);
+            return Err(AttemptError::Permanent(
+                response.error_for_status().unwrap_err(),
+            ));
         }
-    }
 
-    Err(last_error.expect("should have error after retries"))
+        let data = response.json::<PricingDataset>().await.map_err(|e| {
+            eprintln!("[tokscale] LiteLLM JSON parse failed: {}", e);
+            AttemptError::Permanent(e)
+        })?;
+        if let Err(e) = cache::save_cache(CACHE_FILENAME, &data) {
+            eprintln!(
+                "[tokscale] Warning: Failed to cache LiteLLM pricing at {}: {}",
+                cache::get_cache_path(CACHE_FILENAME).display(),
+                e
+            );
+        }
+        Ok(data)
+    })
+    .await
 }
 
 #[cfg(test)]
@@ -1,6 +1,8 @@
 use super::{aliases, litellm::ModelPricing};
 use crate::{provider_identity, strip_parenthesized_reasoning_tier, TokenBreakdown};
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::RwLock;
 
 const PROVIDER_PREFIXES: &[&str] = &[
@@ -68,6 +70,15 @@ const FUZZY_BLOCKLIST: &[&str] = &[
     "router",
 ];
 
+/// Costs computed since the last [`take_sanitized_cost_count`] that skipped
+/// a NaN, infinite, or negative rate, or whose total overflowed to infinity.
+static SANITIZED_COST_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Return and reset the number of costs computed around unusable rates.
+pub fn take_sanitized_cost_count() -> usize {
+    SANITIZED_COST_COUNT.swap(0, Ordering::Relaxed)
+}
+
 const MAX_LOOKUP_CACHE_ENTRIES: usize = 512;
 const TIERED_PRICING_THRESHOLD_128K_TOKENS: f64 = 128_000.0;
 const TIERED_PRICING_THRESHOLD_200K_TOKENS: f64 = 200_000.0;
@@ -269,7 +280,40 @@ impl PricingLookup {
         provider_id: Option<&str>,
     ) -> Option<LookupResult> {
         let provider_id = normalize_provider_hint(provider_id);
-        let cache_key = build_lookup_cache_key(model_id, provider_id);
+        self.cached_lookup(build_lookup_cache_key(model_id, provider_id), || {
+            self.lookup_with_source_and_provider(model_id, None, provider_id)
+        })
+    }
+
+    /// [`Self::lookup_with_provider`] that tries `source` (`litellm`,
+    /// `openrouter`, ...) first and falls back to the usual precedence when
+    /// that source has no match. `None` is plain `lookup_with_provider`.
+    pub fn lookup_preferring_source(
+        &self,
+        model_id: &str,
+        provider_id: Option<&str>,
+        source: Option<&str>,
+    ) -> Option<LookupResult> {
+        let Some(source) = source else {
+            return self.lookup_with_provider(model_id, provider_id);
+        };
+        let provider_id = normalize_provider_hint(provider_id);
+        let cache_key = format!(
+            "{}>{}",
+            source.to_lowercase(),
+            build_lookup_cache_key(model_id, provider_id)
+        );
+        self.cached_lookup(cache_key, || {
+            self.lookup_with_source_and_provider(model_id, Some(source), provider_id)
+                .or_else(|| self.lookup_with_provider(model_id, provider_id))
+        })
+    }
+
+    fn cached_lookup(
+        &self,
+        cache_key: String,
+        lookup: impl FnOnce() -> Option<LookupResult>,
+    ) -> Option<LookupResult> {
         if let Some(cached) = self
             .lookup_cache
             .read()
@@ -283,7 +327,7 @@ impl PricingLookup {
             });
         }
 
-        let result = self.lookup_with_source_and_provider(model_id, None, provider_id);
+        let result = lookup();
 
         if let Ok(mut cache) = self.lookup_cache.write() {
             if cache.len() >= MAX_LOOKUP_CACHE_ENTRIES {
@@ -373,6 +417,19 @@ impl PricingLookup {
             )
         };
 
+        // 0.5. Bedrock/Vertex spellings (`us.anthropic.<model>-v2:0`,
+        // `<model>@20241022`) retry as the bare model id. A dataset that keys
+        // the decorated id itself still wins through its exact entry.
+        if self.exact_match_litellm(lower_ref).is_none() {
+            for candidate in strip_cloud_vendor_decorations(lower_ref) {
+                if let Some(result) = do_lookup(&candidate) {
+                    if !unsafe_claude_resolution(&result) {
+                        return Some(result);
+                    }
+                }
+            }
+        }
+
         // 1. Try direct lookup
         if let Some(result) = do_lookup(lower_ref) {
             if unsafe_claude_resolution(&result) {
@@ -1141,30 +1198,180 @@ impl PricingLookup {
         cache_write: i64,
         reasoning: i64,
     ) -> f64 {
+        self.calculate_cost_breakdown(model_id, input, output, cache_read, cache_write, reasoning)
+            .total
+    }
+
+    pub fn calculate_cost_with_provider(
+        &self,
+        model_id: &str,
+        provider_id: Option<&str>,
+        usage: &TokenBreakdown,
+    ) -> f64 {
+        self.calculate_cost_breakdown_with_provider(model_id, provider_id, usage)
+            .total
+    }
+
+    /// Per-component variant of [`Self::calculate_cost`]. The components sum
+    /// to `total`, which is exactly what `calculate_cost` returns.
+    pub fn calculate_cost_breakdown(
+        &self,
+        model_id: &str,
+        input: i64,
+        output: i64,
+        cache_read: i64,
+        cache_write: i64,
+        reasoning: i64,
+    ) -> CostBreakdown {
         let usage = TokenBreakdown {
             input,
             output,
             cache_read,
             cache_write,
             reasoning,
+            ..Default::default()
         };
-        self.calculate_cost_with_provider(model_id, None, &usage)
+        self.calculate_cost_breakdown_with_provider(model_id, None, &usage)
     }
 
-    pub fn calculate_cost_with_provider(
+    pub fn calculate_cost_breakdown_with_provider(
         &self,
         model_id: &str,
         provider_id: Option<&str>,
         usage: &TokenBreakdown,
-    ) -> f64 {
+    ) -> CostBreakdown {
+        self.calculate_cost_breakdown_preferring_source(model_id, provider_id, usage, None)
+    }
+
+    /// [`Self::calculate_cost_breakdown_with_provider`] priced through
+    /// [`Self::lookup_preferring_source`].
+    pub fn calculate_cost_breakdown_preferring_source(
+        &self,
+        model_id: &str,
+        provider_id: Option<&str>,
+        usage: &TokenBreakdown,
+        source: Option<&str>,
+    ) -> CostBreakdown {
         let provider_id = normalize_provider_hint(provider_id);
-        let result = match self.lookup_with_provider(model_id, provider_id) {
+        let result = match self.lookup_preferring_source(model_id, provider_id, source) {
             Some(r) => r,
-            None => return 0.0,
+            None => return CostBreakdown::default(),
         };
 
-        compute_cost_for_lookup(&result, provider_id, usage)
+        compute_cost_breakdown_for_lookup(&result, provider_id, usage)
     }
+
+    /// Every known pricing key with the label of the dataset it came from.
+    /// A model id present in several datasets is yielded once per dataset.
+    pub fn keys(&self) -> impl Iterator<Item = (&str, &'static str)> + '_ {
+        [
+            ("LiteLLM", &self.litellm),
+            ("OpenRouter", &self.openrouter),
+            ("Cursor", &self.cursor),
+            ("Sakana", &self.sakana),
+            ("Models.dev", &self.models_dev),
+        ]
+        .into_iter()
+        .flat_map(|(source, map)| map.keys().map(move |key| (key.as_str(), source)))
+    }
+
+    /// Rank known keys by similarity to `query`, best first. Keys that share
+    /// no ordered subsequence with the query are dropped.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchMatch> {
+        rank_search_matches(query, self.keys(), limit)
+    }
+}
+
+/// A pricing key returned by a fuzzy [`PricingLookup::search`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchMatch {
+    pub key: String,
+    pub source: String,
+    /// Similarity in `(0, 1]`; `1.0` is an exact (separator-insensitive) match.
+    pub score: f64,
+}
+
+pub(crate) fn rank_search_matches<'a>(
+    query: &str,
+    keys: impl Iterator<Item = (&'a str, &'static str)>,
+    limit: usize,
+) -> Vec<SearchMatch> {
+    let query = normalize_search_text(query);
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<SearchMatch> = keys
+        .filter_map(|(key, source)| {
+            search_score(&query, key).map(|score| SearchMatch {
+                key: key.to_string(),
+                source: source.to_string(),
+                score,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.key.len().cmp(&b.key.len()))
+            .then_with(|| a.key.cmp(&b.key))
+            .then_with(|| a.source.cmp(&b.source))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+/// Lowercase and fold `.`, `_` and whitespace into `-`, so `opus 4.6`
+/// and `claude-opus-4-6` compare equal.
+fn normalize_search_text(text: &str) -> String {
+    text.trim()
+        .chars()
+        .map(|c| match c {
+            '.' | '_' | ' ' => '-',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+/// Tiered similarity: exact > model-part prefix > substring > ordered
+/// subsequence. Within a tier, a query that covers more of the key wins.
+fn search_score(query: &str, key: &str) -> Option<f64> {
+    let key = normalize_search_text(key);
+    let model_part = key.rsplit('/').next().unwrap_or(&key);
+    if key == query || model_part == query {
+        return Some(1.0);
+    }
+
+    let coverage = |haystack: &str| query.len() as f64 / haystack.len().max(1) as f64;
+    if model_part.starts_with(query) {
+        return Some(0.75 + 0.2 * coverage(model_part));
+    }
+    if key.contains(query) {
+        return Some(0.5 + 0.2 * coverage(&key));
+    }
+
+    let mut remaining = key.chars();
+    let is_subsequence = query.chars().all(|wanted| remaining.any(|c| c == wanted));
+    is_subsequence.then(|| 0.25 * coverage(&key))
+}
+
+/// Cost of one usage record split by token bucket.
+///
+/// Reasoning tokens are billed at `reasoning_cost_per_token` when the model
+/// has one. Otherwise they are billed at the output rate and walk the same
+/// tier ladder as output, so `output_cost` and `reasoning_cost` are the
+/// combined output-side cost split pro rata by token count. `total` is the
+/// sum of the five components.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostBreakdown {
+    pub input_cost: f64,
+    pub output_cost: f64,
+    pub cache_read_cost: f64,
+    pub cache_write_cost: f64,
+    pub reasoning_cost: f64,
+    pub total: f64,
 }
 
 fn matches_model_or_snapshot(model_id: &str, base: &str) -> bool {
@@ -1245,21 +1452,12 @@ fn uses_openai_full_request_272k_pricing(result: &LookupResult, provider_id: Opt
     is_openai_full_request_272k_model(&key)
 }
 
-fn compute_cost_for_lookup(
+fn compute_cost_breakdown_for_lookup(
     result: &LookupResult,
     provider_id: Option<&str>,
     usage: &TokenBreakdown,
-) -> f64 {
-    let calculate = |pricing| {
-        compute_cost(
-            pricing,
-            usage.input,
-            usage.output,
-            usage.cache_read,
-            usage.cache_write,
-            usage.reasoning,
-        )
-    };
+) -> CostBreakdown {
+    let calculate = |pricing| compute_cost_breakdown_for_usage(pricing, &result.matched_key, usage);
     let total_input = usage
         .input
         .max(0)
@@ -1336,6 +1534,69 @@ fn compute_cost_for_lookup(
     calculate(&pricing)
 }
 
+/// [`compute_cost_breakdown`] for a [`TokenBreakdown`], pricing the 1-hour
+/// slice of `cache_write` at the 1-hour cache-write rate and the rest at the
+/// regular (5-minute) rate. `model_key` is the pricing key that matched,
+/// used to pick the 1-hour fallback rate.
+pub fn compute_cost_breakdown_for_usage(
+    pricing: &ModelPricing,
+    model_key: &str,
+    usage: &TokenBreakdown,
+) -> CostBreakdown {
+    let cache_write = usage.cache_write.max(0);
+    let cache_write_1h = usage.cache_write_1h.clamp(0, cache_write);
+    // Tiers are walked on the full cache_write count, so the 1-hour split
+    // never moves which tokens cross the long-context threshold.
+    let mut breakdown = compute_cost_breakdown(
+        pricing,
+        usage.input,
+        usage.output,
+        usage.cache_read,
+        cache_write,
+        usage.reasoning,
+    );
+    if cache_write_1h > 0 {
+        // The 1-hour slice trades its share of the tiered cost for the
+        // 1-hour rate.
+        let five_minute_share =
+            breakdown.cache_write_cost * cache_write_1h as f64 / cache_write as f64;
+        let one_hour_cost = cache_write_1h as f64 * one_hour_cache_write_price(pricing, model_key);
+        let delta = one_hour_cost - five_minute_share;
+        if delta.is_finite() {
+            breakdown.cache_write_cost += delta;
+            breakdown.total += delta;
+        }
+    }
+    breakdown
+}
+
+/// Anthropic bills 1-hour cache writes at 2x the base input price (5-minute
+/// writes are 1.25x). Claude entries that don't publish the 1-hour rate fall
+/// back to that multiplier; everything else, and Claude entries without an
+/// input rate, use the regular cache-write price.
+fn one_hour_cache_write_price(pricing: &ModelPricing, model_key: &str) -> f64 {
+    const ONE_HOUR_CACHE_WRITE_INPUT_MULTIPLIER: f64 = 2.0;
+
+    let valid = |price: Option<f64>| price.filter(|v| is_valid_price_value(*v));
+    let anthropic_fallback = || {
+        is_anthropic_model_key(model_key)
+            .then(|| valid(pricing.input_cost_per_token))
+            .flatten()
+            .map(|input| input * ONE_HOUR_CACHE_WRITE_INPUT_MULTIPLIER)
+    };
+    valid(pricing.cache_creation_input_token_cost_above_1hr)
+        .or_else(anthropic_fallback)
+        .or_else(|| valid(pricing.cache_creation_input_token_cost))
+        .unwrap_or(0.0)
+}
+
+/// Claude models, whether keyed bare (`claude-sonnet-4`), by provider
+/// (`anthropic/...`), or through a reseller (`bedrock/anthropic.claude-...`).
+fn is_anthropic_model_key(model_key: &str) -> bool {
+    let key = model_key.to_ascii_lowercase();
+    key.contains("claude") || key.contains("anthropic")
+}
+
 pub fn compute_cost(
     pricing: &ModelPricing,
     input: i64,
@@ -1344,7 +1605,29 @@ pub fn compute_cost(
     cache_write: i64,
     reasoning: i64,
 ) -> f64 {
-    let safe_price = |opt: Option<f64>| opt.filter(|v| is_valid_price_value(*v)).unwrap_or(0.0);
+    compute_cost_breakdown(pricing, input, output, cache_read, cache_write, reasoning).total
+}
+
+pub fn compute_cost_breakdown(
+    pricing: &ModelPricing,
+    input: i64,
+    output: i64,
+    cache_read: i64,
+    cache_write: i64,
+    reasoning: i64,
+) -> CostBreakdown {
+    // Bad upstream data (a NaN or infinite rate) must not leak into totals:
+    // an unusable rate counts as missing, so that component is skipped.
+    let sanitized = Cell::new(false);
+    let valid_price = |opt: Option<f64>| match opt {
+        Some(v) if is_valid_price_value(v) => Some(v),
+        Some(_) => {
+            sanitized.set(true);
+            None
+        }
+        None => None,
+    };
+    let safe_price = |opt: Option<f64>| valid_price(opt).unwrap_or(0.0);
     let tiered_cost = |tokens: f64, base: Option<f64>, tiers: &[(f64, Option<f64>)]| {
         let base_price = safe_price(base);
         let mut cost = 0.0;
@@ -1352,7 +1635,7 @@ pub fn compute_cost(
         let mut active_price = base_price;
 
         for (threshold, tier_price) in tiers {
-            let Some(tier_price) = tier_price.filter(|v| is_valid_price_value(*v)) else {
+            let Some(tier_price) = valid_price(*tier_price) else {
                 continue;
             };
 
@@ -1372,8 +1655,15 @@ pub fn compute_cost(
         cost + (tokens - lower_bound).max(0.0) * active_price
     };
 
+    // Without a dedicated reasoning rate, reasoning is folded into output so
+    // it walks the same tier ladder.
+    let reasoning_rate = valid_price(pricing.reasoning_cost_per_token);
     let input_clamped = input.max(0) as f64;
-    let output_clamped = output.max(0).saturating_add(reasoning.max(0)) as f64;
+    let reasoning_clamped = reasoning.max(0) as f64;
+    let output_clamped = match reasoning_rate {
+        Some(_) => output.max(0) as f64,
+        None => output.max(0).saturating_add(reasoning.max(0)) as f64,
+    };
     let cache_read_clamped = cache_read.max(0) as f64;
     let cache_write_clamped = cache_write.max(0) as f64;
 
@@ -1399,7 +1689,7 @@ pub fn compute_cost(
             ),
         ],
     );
-    let output_cost = tiered_cost(
+    let output_side_cost = tiered_cost(
         output_clamped,
         pricing.output_cost_per_token,
         &[
@@ -1451,7 +1741,46 @@ pub fn compute_cost(
         )],
     );
 
-    input_cost + output_cost + cache_read_cost + cache_write_cost
+    let (output_cost, reasoning_cost) = match reasoning_rate {
+        Some(rate) => (output_side_cost, reasoning_clamped * rate),
+        None => {
+            let reasoning_cost = if output_clamped > 0.0 {
+                output_side_cost * (reasoning_clamped / output_clamped)
+            } else {
+                0.0
+            };
+            (output_side_cost - reasoning_cost, reasoning_cost)
+        }
+    };
+
+    // Valid but absurd rates can still overflow; drop such a component too.
+    let finite = |cost: f64| {
+        if cost.is_finite() {
+            cost
+        } else {
+            sanitized.set(true);
+            0.0
+        }
+    };
+    let (input_cost, output_cost, reasoning_cost, cache_read_cost, cache_write_cost) = (
+        finite(input_cost),
+        finite(output_cost),
+        finite(reasoning_cost),
+        finite(cache_read_cost),
+        finite(cache_write_cost),
+    );
+    if sanitized.get() {
+        SANITIZED_COST_COUNT.fetch_add(1, Ordering::Relaxed);
+    }
+
+    CostBreakdown {
+        input_cost,
+        output_cost,
+        cache_read_cost,
+        cache_write_cost,
+        reasoning_cost,
+        total: input_cost + output_cost + reasoning_cost + cache_read_cost + cache_write_cost,
+    }
 }
 
 fn extract_model_family(model_id: &str) -> String {
@@ -1804,6 +2133,68 @@ fn strip_generic_provider_prefix(model_id: &str) -> Option<&str> {
     Some(terminal)
 }
 
+/// Bare model ids hidden behind a cloud vendor's spelling, most specific
+/// first. Bedrock wraps Anthropic models as `[region.]anthropic.<model>-v2:0`
+/// and Vertex pins snapshots as `<model>[-v2]@20241022`; neither shape is a
+/// LiteLLM key, so `anthropic.claude-3-5-sonnet-20241022-v2:0` retries as
+/// `claude-3-5-sonnet-20241022`, and `claude-3-5-sonnet-v2@20241022` as
+/// `claude-3-5-sonnet-20241022` then `claude-3-5-sonnet`. Any routing prefix
+/// (`bedrock/`) is dropped first. Returns nothing for undecorated ids.
+fn strip_cloud_vendor_decorations(model_id: &str) -> Vec<String> {
+    let terminal = model_id.rsplit('/').next().unwrap_or(model_id);
+
+    let mut bedrock = false;
+    let mut model = terminal;
+    if let Some((region, rest)) = terminal.split_once("anthropic.") {
+        let region_ok = region.is_empty()
+            || region
+                .strip_suffix('.')
+                .is_some_and(|r| !r.is_empty() && !r.contains('.'));
+        if region_ok && !rest.is_empty() {
+            bedrock = true;
+            model = rest;
+        }
+    }
+
+    let (model, snapshot) = match model.split_once('@') {
+        Some((model, snapshot)) => (model, Some(snapshot)),
+        None => (model, None),
+    };
+    if !bedrock && snapshot.is_none() {
+        return Vec::new();
+    }
+    let model = strip_cloud_version_suffix(model);
+    if model.is_empty() {
+        return Vec::new();
+    }
+
+    let mut candidates = Vec::new();
+    if let Some(snapshot) = snapshot {
+        if !snapshot.is_empty() && snapshot.bytes().all(|b| b.is_ascii_digit()) {
+            candidates.push(format!("{model}-{snapshot}"));
+        }
+    }
+    candidates.push(model.to_string());
+    candidates
+}
+
+/// Strips a Bedrock/Vertex revision suffix (`-v1`, `-v2:0`) from `model`.
+fn strip_cloud_version_suffix(model: &str) -> &str {
+    let Some((head, revision)) = model.rsplit_once("-v") else {
+        return model;
+    };
+    let (major, minor) = match revision.split_once(':') {
+        Some((major, minor)) => (major, Some(minor)),
+        None => (revision, None),
+    };
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if is_number(major) && minor.is_none_or(is_number) {
+        head
+    } else {
+        model
+    }
+}
+
 fn is_valid_price_value(value: f64) -> bool {
     value.is_finite() && value >= 0.0
 }
@@ -1826,9 +2217,11 @@ fn has_any_usable_pricing(pricing: &ModelPricing) -> bool {
         pricing.output_cost_per_token_above_200k_tokens,
         pricing.output_cost_per_token_above_256k_tokens,
         pricing.output_cost_per_token_above_272k_tokens,
+        pricing.reasoning_cost_per_token,
         pricing.cache_read_input_token_cost_above_200k_tokens,
         pricing.cache_read_input_token_cost_above_272k_tokens,
         pricing.cache_creation_input_token_cost_above_200k_tokens,
+        pricing.cache_creation_input_token_cost_above_1hr,
     ]
     .into_iter()
     .any(|opt| opt.is_some_and(is_valid_price_value))
@@ -2743,6 +3136,42 @@ mod tests {
         PricingLookup::new(mock_litellm(), mock_openrouter(), HashMap::new())
     }
 
+    #[test]
+    fn test_search_ranks_opus_variants_first() {
+        let lookup = create_lookup();
+        let matches = lookup.search("opus", 5);
+
+        // Substring hits outrank looser subsequence hits.
+        assert!(matches[..3].iter().all(|m| m.key.contains("opus")));
+        assert!(matches
+            .iter()
+            .any(|m| m.key == "claude-opus-4-5" && m.source == "LiteLLM"));
+        assert!(matches
+            .iter()
+            .any(|m| m.key == "anthropic/claude-opus-4-5" && m.source == "OpenRouter"));
+        assert!(matches.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_search_prefers_exact_and_ignores_separators() {
+        let lookup = create_lookup();
+        let matches = lookup.search("Claude Opus 4.5", 3);
+        assert_eq!(matches[0].key, "claude-opus-4-5");
+        assert_eq!(matches[0].score, 1.0);
+
+        assert!(lookup.search("zzzz-no-such-model", 5).is_empty());
+        assert!(lookup.search("   ", 5).is_empty());
+    }
+
+    #[test]
+    fn test_keys_label_each_source() {
+        let lookup = create_lookup();
+        let keys: Vec<_> = lookup.keys().collect();
+        assert!(keys.contains(&("gpt-4o", "LiteLLM")));
+        assert!(keys.contains(&("anthropic/claude-opus-4-5", "OpenRouter")));
+        assert_eq!(keys.len(), mock_litellm().len() + mock_openrouter().len());
+    }
+
     // =========================================================================
     // OPENCODE ZEN MODELS - GPT-5 FAMILY
     // All models from https://opencode.ai/docs/zen/
@@ -3919,6 +4348,64 @@ mod tests {
         assert_eq!(result.pricing.input_cost_per_token, Some(3e-6));
     }
 
+    #[test]
+    fn test_bedrock_id_resolves_to_litellm_model() {
+        let lookup = create_lookup();
+        for id in [
+            "anthropic.claude-3-5-sonnet-20241022-v2:0",
+            "us.anthropic.claude-3-5-sonnet-20241022-v2:0",
+            "bedrock/anthropic.claude-3-5-sonnet-20241022-v2:0",
+        ] {
+            let result = lookup.lookup(id).unwrap();
+            assert_eq!(result.matched_key, "claude-3-5-sonnet-20241022", "id: {id}");
+            assert_eq!(result.source, "LiteLLM");
+            assert!(lookup.calculate_cost(id, 1_000_000, 0, 0, 0, 0) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_vertex_id_resolves_to_litellm_model() {
+        let lookup = create_lookup();
+        for id in [
+            "claude-3-5-sonnet@20241022",
+            "claude-3-5-sonnet-v2@20241022",
+            "vertex_ai/claude-3-5-sonnet-v2@20241022",
+        ] {
+            let result = lookup.lookup(id).unwrap();
+            assert_eq!(result.matched_key, "claude-3-5-sonnet-20241022", "id: {id}");
+            assert_eq!(result.source, "LiteLLM");
+        }
+    }
+
+    #[test]
+    fn test_exact_bedrock_key_wins_over_stripped_model() {
+        let lookup = create_lookup();
+        let result = lookup
+            .lookup("bedrock/us.anthropic.claude-3-5-haiku-20241022-v1:0")
+            .unwrap();
+        assert_eq!(
+            result.matched_key,
+            "bedrock/us.anthropic.claude-3-5-haiku-20241022-v1:0"
+        );
+    }
+
+    #[test]
+    fn test_strip_cloud_vendor_decorations() {
+        assert_eq!(
+            strip_cloud_vendor_decorations("eu.anthropic.claude-sonnet-4-6-v1:0"),
+            vec!["claude-sonnet-4-6".to_string()]
+        );
+        assert_eq!(
+            strip_cloud_vendor_decorations("claude-opus-4-1@20250805"),
+            vec![
+                "claude-opus-4-1-20250805".to_string(),
+                "claude-opus-4-1".to_string()
+            ]
+        );
+        assert!(strip_cloud_vendor_decorations("claude-sonnet-4-6").is_empty());
+        assert!(strip_cloud_vendor_decorations("gpt-4o-v2").is_empty());
+    }
+
     /// Regression (B2): reversed-order sonnet ids must resolve to the sonnet
     /// key, not cross-family. Before reversed-order parsing was generalized
     /// beyond opus, `claude-4-6-sonnet` stripped down to `claude` and
@@ -4836,17 +5323,25 @@ mod tests {
             cache_read,
             cache_write,
             reasoning: 0,
+            ..Default::default()
         };
-        let cost =
-            compute_cost_for_lookup(&result, Some("openai"), &usage(200_000, 10_000, 72_000, 1));
+        let cost = compute_cost_breakdown_for_lookup(
+            &result,
+            Some("openai"),
+            &usage(200_000, 10_000, 72_000, 1),
+        )
+        .total;
         let expected = 200_000.0 * 0.000010 + 10_000.0 * 0.000045 + 72_000.0 * 0.000001 + 0.0000125;
         assert!((cost - expected).abs() < 1e-12);
 
-        let boundary = compute_cost_for_lookup(&result, None, &usage(200_000, 10_000, 72_000, 0));
+        let boundary =
+            compute_cost_breakdown_for_lookup(&result, None, &usage(200_000, 10_000, 72_000, 0))
+                .total;
         let boundary_expected = 200_000.0 * 0.000005 + 10_000.0 * 0.000030 + 72_000.0 * 0.0000005;
         assert!((boundary - boundary_expected).abs() < 1e-12);
 
-        let output_only = compute_cost_for_lookup(&result, None, &usage(1, 300_000, 0, 0));
+        let output_only =
+            compute_cost_breakdown_for_lookup(&result, None, &usage(1, 300_000, 0, 0)).total;
         assert!((output_only - (0.000005 + 300_000.0 * 0.000030)).abs() < 1e-12);
     }
 
@@ -5086,6 +5581,115 @@ mod tests {
         assert!((above_threshold - (200_000.0 * 0.0000003 + 0.0000004)).abs() < 1e-12);
     }
 
+    #[test]
+    fn test_compute_cost_for_usage_prices_one_hour_cache_writes_separately() {
+        let pricing: ModelPricing = serde_json::from_str(
+            r#"{
+                "input_cost_per_token": 0.000003,
+                "cache_creation_input_token_cost": 0.00000375,
+                "cache_creation_input_token_cost_above_1hr": 0.000006
+            }"#,
+        )
+        .unwrap();
+        let usage = TokenBreakdown {
+            input: 0,
+            output: 0,
+            cache_read: 0,
+            cache_write: 1_500,
+            reasoning: 0,
+            cache_write_1h: 500,
+        };
+
+        let breakdown = compute_cost_breakdown_for_usage(&pricing, "claude-sonnet-4", &usage);
+        let expected = 1_000.0 * 0.00000375 + 500.0 * 0.000006;
+
+        assert!((breakdown.cache_write_cost - expected).abs() < 1e-12);
+        assert!((breakdown.total - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_compute_cost_for_usage_one_hour_rate_falls_back_to_twice_input() {
+        let pricing = ModelPricing {
+            input_cost_per_token: Some(0.000003),
+            cache_creation_input_token_cost: Some(0.00000375),
+            ..Default::default()
+        };
+        let usage = TokenBreakdown {
+            input: 0,
+            output: 0,
+            cache_read: 0,
+            cache_write: 1_000,
+            reasoning: 0,
+            cache_write_1h: 1_000,
+        };
+
+        let cost = compute_cost_breakdown_for_usage(&pricing, "claude-sonnet-4", &usage).total;
+
+        assert!((cost - 1_000.0 * 0.000006).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_compute_cost_for_usage_one_hour_fallback_is_cache_write_rate_outside_anthropic() {
+        let pricing = ModelPricing {
+            input_cost_per_token: Some(0.000003),
+            cache_creation_input_token_cost: Some(0.00000375),
+            ..Default::default()
+        };
+        let usage = TokenBreakdown {
+            cache_write: 1_000,
+            cache_write_1h: 400,
+            ..Default::default()
+        };
+
+        let cost = compute_cost_breakdown_for_usage(&pricing, "gemini-2.5-pro", &usage).total;
+
+        assert!((cost - 1_000.0 * 0.00000375).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_compute_cost_for_usage_one_hour_split_keeps_the_long_context_tier() {
+        let pricing = ModelPricing {
+            cache_creation_input_token_cost: Some(0.000001),
+            cache_creation_input_token_cost_above_200k_tokens: Some(0.000002),
+            cache_creation_input_token_cost_above_1hr: Some(0.000001),
+            ..Default::default()
+        };
+        let usage = TokenBreakdown {
+            cache_write: 300_000,
+            cache_write_1h: 150_000,
+            ..Default::default()
+        };
+
+        let breakdown = compute_cost_breakdown_for_usage(&pricing, "claude-sonnet-4", &usage);
+
+        // The full 300k walks the tiers: 200k at the base rate and 100k above
+        // it. The 1h half then takes its share of that at the 1h rate.
+        let tiered = 200_000.0 * 0.000001 + 100_000.0 * 0.000002;
+        let expected = tiered / 2.0 + 150_000.0 * 0.000001;
+        assert!((breakdown.cache_write_cost - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compute_cost_for_usage_without_one_hour_split_matches_flat_cost() {
+        let pricing = ModelPricing {
+            input_cost_per_token: Some(0.000003),
+            cache_creation_input_token_cost: Some(0.00000375),
+            ..Default::default()
+        };
+        let usage = TokenBreakdown {
+            input: 100,
+            output: 0,
+            cache_read: 0,
+            cache_write: 1_000,
+            reasoning: 0,
+            ..Default::default()
+        };
+
+        let cost = compute_cost_breakdown_for_usage(&pricing, "claude-sonnet-4", &usage).total;
+
+        assert!((cost - compute_cost(&pricing, 100, 0, 0, 1_000, 0)).abs() < 1e-12);
+    }
+
     #[test]
     fn test_compute_cost_tiered_without_above_rate_uses_base_for_all_tokens() {
         let pricing = ModelPricing {
@@ -5122,6 +5726,40 @@ mod tests {
         assert!((compute_cost(&pricing_nan, 200_001, 0, 0, 0, 0) - expected).abs() < 1e-12);
     }
 
+    #[test]
+    fn test_compute_cost_skips_nan_base_rates() {
+        let pricing = ModelPricing {
+            input_cost_per_token: Some(f64::NAN),
+            output_cost_per_token: Some(0.000002),
+            cache_read_input_token_cost: Some(f64::INFINITY),
+            cache_creation_input_token_cost: Some(f64::NEG_INFINITY),
+            reasoning_cost_per_token: Some(f64::NAN),
+            ..Default::default()
+        };
+
+        let breakdown = compute_cost_breakdown(&pricing, 1_000, 500, 2_000, 300, 100);
+        assert!(breakdown.total.is_finite());
+        assert_eq!(breakdown.input_cost, 0.0);
+        assert_eq!(breakdown.cache_read_cost, 0.0);
+        assert_eq!(breakdown.cache_write_cost, 0.0);
+        // A NaN reasoning rate counts as missing, so reasoning is billed as
+        // output: (500 + 100) * 0.000002.
+        assert!((breakdown.total - 0.0012).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_compute_cost_drops_components_that_overflow() {
+        let pricing = ModelPricing {
+            input_cost_per_token: Some(f64::MAX),
+            output_cost_per_token: Some(0.000002),
+            ..Default::default()
+        };
+
+        let breakdown = compute_cost_breakdown(&pricing, 1_000, 500, 0, 0, 0);
+        assert_eq!(breakdown.input_cost, 0.0);
+        assert!((breakdown.total - 0.001).abs() < 1e-12);
+    }
+
     #[test]
     fn test_compute_cost_tiered_reasoning_boundary_at_200k_uses_base_output_rate() {
         let pricing = ModelPricing {
@@ -5136,6 +5774,52 @@ mod tests {
         assert!((cost - expected).abs() < 1e-12);
     }
 
+    #[test]
+    fn test_compute_cost_without_reasoning_rate_matches_folded_output() {
+        let pricing = ModelPricing {
+            input_cost_per_token: Some(0.000001),
+            output_cost_per_token: Some(0.000003),
+            output_cost_per_token_above_200k_tokens: Some(0.000004),
+            cache_read_input_token_cost: Some(0.0000001),
+            ..Default::default()
+        };
+
+        for (output, reasoning) in [(5, 7), (0, 40), (150_000, 100_000)] {
+            let split = compute_cost_breakdown(&pricing, 10, output, 3, 0, reasoning);
+            let folded = compute_cost_breakdown(&pricing, 10, output + reasoning, 3, 0, 0);
+            assert!((split.total - folded.total).abs() < 1e-12);
+            assert!((split.output_cost + split.reasoning_cost - folded.output_cost).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_compute_cost_bills_reasoning_at_dedicated_rate() {
+        let pricing = ModelPricing {
+            output_cost_per_token: Some(0.000003),
+            output_cost_per_token_above_200k_tokens: Some(0.000004),
+            reasoning_cost_per_token: Some(0.00001),
+            ..Default::default()
+        };
+
+        // Reasoning no longer pushes output over the 200k tier boundary.
+        let breakdown = compute_cost_breakdown(&pricing, 0, 199_999, 0, 0, 10);
+        assert!((breakdown.output_cost - 199_999.0 * 0.000003).abs() < 1e-12);
+        assert!((breakdown.reasoning_cost - 10.0 * 0.00001).abs() < 1e-12);
+        assert!(
+            (breakdown.total - (breakdown.output_cost + breakdown.reasoning_cost)).abs() < 1e-12
+        );
+    }
+
+    #[test]
+    fn test_reasoning_rate_reads_litellm_key() {
+        let pricing: ModelPricing = serde_json::from_str(
+            r#"{"input_cost_per_token":0.000001,"output_cost_per_token":0.000003,"output_cost_per_reasoning_token":0.0000035}"#,
+        )
+        .unwrap();
+
+        assert_eq!(pricing.reasoning_cost_per_token, Some(0.0000035));
+    }
+
     #[test]
     fn test_compute_cost_tiered_invalid_above_rate_falls_back_to_base_output_reasoning() {
         let pricing_negative = ModelPricing {
@@ -1,13 +1,15 @@
 pub mod aliases;
 pub mod cache;
 pub mod custom;
+pub mod history;
 pub mod litellm;
 pub mod lookup;
 pub mod models_dev;
 pub mod openrouter;
+pub mod retry;
 
 use custom::CustomPricing;
-use lookup::{compute_cost, LookupResult, PricingLookup};
+use lookup::{compute_cost_breakdown_for_usage, rank_search_matches, LookupResult, PricingLookup};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::OnceCell;
@@ -15,9 +17,54 @@ use tokio::sync::OnceCell;
 use crate::TokenBreakdown;
 
 pub use litellm::ModelPricing;
+pub use lookup::{CostBreakdown, SearchMatch};
 
 static PRICING_SERVICE: OnceCell<Arc<PricingService>> = OnceCell::const_new();
 
+/// Which dataset costs are priced from when a model is in more than one.
+/// Custom overrides always win, and a model the preferred dataset doesn't
+/// know falls back to the usual precedence.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CostSourcePreference {
+    /// The lookup's own precedence.
+    #[default]
+    Auto,
+    Litellm,
+    Openrouter,
+}
+
+impl CostSourcePreference {
+    fn force_source(self) -> Option<&'static str> {
+        match self {
+            Self::Auto => None,
+            Self::Litellm => Some("litellm"),
+            Self::Openrouter => Some("openrouter"),
+        }
+    }
+}
+
+impl std::str::FromStr for CostSourcePreference {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "litellm" => Ok(Self::Litellm),
+            "openrouter" => Ok(Self::Openrouter),
+            other => Err(format!(
+                "unknown cost source '{other}' (expected litellm, openrouter, or auto)"
+            )),
+        }
+    }
+}
+
+/// LiteLLM, OpenRouter, and models.dev datasets, in that order.
+type FetchedDatasets = (
+    HashMap<String, ModelPricing>,
+    HashMap<String, ModelPricing>,
+    HashMap<String, ModelPricing>,
+);
+
 // @keep: documents non-obvious filtering behavior — without this, the next person
 // will wonder why github_copilot entries disappear from the pricing data.
 /// Provider prefixes in LiteLLM data that use subscription-based pricing ($0.00)
@@ -25,8 +72,9 @@ static PRICING_SERVICE: OnceCell<Arc<PricingService>> = OnceCell::const_new();
 const EXCLUDED_LITELLM_PREFIXES: &[&str] = &["github_copilot/"];
 
 pub struct PricingService {
-    custom: CustomPricing,
-    lookup: PricingLookup,
+    custom: Arc<CustomPricing>,
+    lookup: Arc<PricingLookup>,
+    cost_source: CostSourcePreference,
 }
 
 impl PricingService {
@@ -52,14 +100,25 @@ impl PricingService {
         models_dev_data: HashMap<String, ModelPricing>,
     ) -> Self {
         Self {
-            custom,
-            lookup: PricingLookup::new_with_models_dev(
+            custom: Arc::new(custom),
+            lookup: Arc::new(PricingLookup::new_with_models_dev(
                 litellm_data,
                 openrouter_data,
                 Self::build_cursor_overrides(),
                 Self::build_sakana_overrides(),
                 models_dev_data,
-            ),
+            )),
+            cost_source: CostSourcePreference::Auto,
+        }
+    }
+
+    /// The same datasets, pricing costs from `cost_source` first. Cheap: the
+    /// datasets are shared, not copied.
+    pub fn with_cost_source(&self, cost_source: CostSourcePreference) -> Self {
+        Self {
+            custom: Arc::clone(&self.custom),
+            lookup: Arc::clone(&self.lookup),
+            cost_source,
         }
     }
 
@@ -178,24 +237,56 @@ impl PricingService {
             models_dev::fetch()
         );
 
-        let litellm_data = litellm_result.map_err(|e| e.to_string())?;
-        let litellm_data = Self::filter_litellm_data(litellm_data);
-        let models_dev_data = match models_dev_result {
-            Ok(data) => data,
-            Err(e) => {
-                eprintln!("[tokscale] models.dev fetch failed: {}", e);
-                HashMap::new()
-            }
-        };
+        let (litellm_data, openrouter_data, models_dev_data) = Self::settle_fetched_sources(
+            litellm_result.map_err(|e| e.to_string()),
+            openrouter_data,
+            models_dev_result.map_err(|e| e.to_string()),
+        )?;
+        if let Err(e) = history::record(
+            &history::history_dir(),
+            chrono::Local::now().date_naive(),
+            &litellm_data,
+            &openrouter_data,
+            &models_dev_data,
+        ) {
+            tracing::debug!("failed to record pricing snapshot: {}", e);
+        }
 
         Ok(Self::new_with_custom_and_models_dev(
             CustomPricing::load_from_default_path(),
-            litellm_data,
+            Self::filter_litellm_data(litellm_data),
             openrouter_data,
             models_dev_data,
         ))
     }
 
+    /// Decide what to build from the per-source fetch results (each already
+    /// retried). A failed source is logged and treated as empty as long as
+    /// another source produced data; only a LiteLLM failure with nothing else
+    /// to fall back on fails the whole fetch.
+    fn settle_fetched_sources(
+        litellm: Result<HashMap<String, ModelPricing>, String>,
+        openrouter: HashMap<String, ModelPricing>,
+        models_dev: Result<HashMap<String, ModelPricing>, String>,
+    ) -> Result<FetchedDatasets, String> {
+        let models_dev = models_dev.unwrap_or_else(|e| {
+            eprintln!("[tokscale] models.dev fetch failed: {}", e);
+            HashMap::new()
+        });
+        let litellm = match litellm {
+            Ok(data) => data,
+            Err(e) if !openrouter.is_empty() || !models_dev.is_empty() => {
+                eprintln!(
+                    "[tokscale] Warning: LiteLLM pricing fetch failed ({}); continuing with the other sources",
+                    e
+                );
+                HashMap::new()
+            }
+            Err(e) => return Err(e),
+        };
+        Ok((litellm, openrouter, models_dev))
+    }
+
     fn from_cached_datasets(
         litellm_data: Option<HashMap<String, ModelPricing>>,
         openrouter_data: Option<HashMap<String, ModelPricing>>,
@@ -213,6 +304,27 @@ impl PricingService {
         ))
     }
 
+    /// Pricing from the recorded snapshot for `requested`, or the nearest
+    /// earlier one, instead of live or cached data.
+    pub fn from_snapshot(requested: chrono::NaiveDate) -> Result<Self, String> {
+        let dir = history::history_dir();
+        let date = history::select(&history::snapshot_dates(&dir), requested)?.date();
+        let snapshot = history::load(&dir, date)?;
+        Ok(Self::new_with_custom_and_models_dev(
+            CustomPricing::load_from_default_path(),
+            Self::filter_litellm_data(snapshot.litellm),
+            snapshot.openrouter,
+            snapshot.models_dev,
+        ))
+    }
+
+    /// Which snapshot [`from_snapshot`](Self::from_snapshot) would use for
+    /// `requested`, without loading it.
+    pub fn select_snapshot(requested: chrono::NaiveDate) -> Result<history::Selection, String> {
+        let dir = history::history_dir();
+        history::select(&history::snapshot_dates(&dir), requested)
+    }
+
     pub fn load_cached_any_age() -> Option<Self> {
         Self::from_cached_datasets(
             litellm::load_cached_any_age(),
@@ -228,6 +340,15 @@ impl PricingService {
             .map(Arc::clone)
     }
 
+    /// The snapshot for `pricing_date` when one is given, otherwise live
+    /// data through [`get_or_init`](Self::get_or_init).
+    pub async fn load(pricing_date: Option<chrono::NaiveDate>) -> Result<Arc<Self>, String> {
+        match pricing_date {
+            Some(date) => Self::from_snapshot(date).map(Arc::new),
+            None => Self::get_or_init().await,
+        }
+    }
+
     pub fn lookup_with_source(
         &self,
         model_id: &str,
@@ -279,35 +400,118 @@ impl PricingService {
         cache_write: i64,
         reasoning: i64,
     ) -> f64 {
+        self.calculate_cost_breakdown(model_id, input, output, cache_read, cache_write, reasoning)
+            .total
+    }
+
+    pub fn calculate_cost_with_provider(
+        &self,
+        model_id: &str,
+        provider_id: Option<&str>,
+        usage: &TokenBreakdown,
+    ) -> f64 {
+        self.calculate_cost_breakdown_with_provider(model_id, provider_id, usage)
+            .total
+    }
+
+    /// Per-component variant of [`Self::calculate_cost`]; see [`CostBreakdown`].
+    pub fn calculate_cost_breakdown(
+        &self,
+        model_id: &str,
+        input: i64,
+        output: i64,
+        cache_read: i64,
+        cache_write: i64,
+        reasoning: i64,
+    ) -> CostBreakdown {
         let usage = TokenBreakdown {
             input,
             output,
             cache_read,
             cache_write,
             reasoning,
+            ..Default::default()
         };
-        self.calculate_cost_with_provider(model_id, None, &usage)
+        self.calculate_cost_breakdown_with_provider(model_id, None, &usage)
     }
 
-    pub fn calculate_cost_with_provider(
+    pub fn calculate_cost_breakdown_with_provider(
         &self,
         model_id: &str,
         provider_id: Option<&str>,
         usage: &TokenBreakdown,
-    ) -> f64 {
+    ) -> CostBreakdown {
+        self.calculate_cost_breakdown_with_preference(
+            model_id,
+            provider_id,
+            usage,
+            self.cost_source,
+        )
+    }
+
+    /// [`Self::calculate_cost_breakdown_with_provider`] with an explicit
+    /// dataset preference instead of this service's own.
+    pub fn calculate_cost_breakdown_with_preference(
+        &self,
+        model_id: &str,
+        provider_id: Option<&str>,
+        usage: &TokenBreakdown,
+        preference: CostSourcePreference,
+    ) -> CostBreakdown {
         if let Some(result) = self.custom.lookup_with_key(model_id) {
-            return compute_cost(
-                result.pricing,
-                usage.input,
-                usage.output,
-                usage.cache_read,
-                usage.cache_write,
-                usage.reasoning,
-            );
+            return compute_cost_breakdown_for_usage(result.pricing, result.matched_key, usage);
         }
 
+        self.lookup.calculate_cost_breakdown_preferring_source(
+            model_id,
+            provider_id,
+            usage,
+            preference.force_source(),
+        )
+    }
+
+    /// The dataset [`Self::calculate_cost_with_provider`] prices `model_id`
+    /// from (`Custom`, `LiteLLM`, `OpenRouter`, `Cursor`, ...), or `None`
+    /// when no price matches.
+    pub fn pricing_source(&self, model_id: &str, provider_id: Option<&str>) -> Option<String> {
+        if self.custom.lookup_with_key(model_id).is_some() {
+            return Some("Custom".to_string());
+        }
         self.lookup
-            .calculate_cost_with_provider(model_id, provider_id, usage)
+            .lookup_preferring_source(model_id, provider_id, self.cost_source.force_source())
+            .map(|result| result.source)
+    }
+
+    /// What one cache-read token saves over a fresh input token for
+    /// `model_id`: the input rate minus the cache-read rate, from the same
+    /// dataset [`Self::pricing_source`] reports. A missing cache-read rate
+    /// counts as the input rate, so it saves nothing. Tiered rates are
+    /// ignored. `None` when no price matches.
+    pub fn cache_read_saving_per_token(
+        &self,
+        model_id: &str,
+        provider_id: Option<&str>,
+    ) -> Option<f64> {
+        let result = self.lookup_custom(model_id).or_else(|| {
+            self.lookup.lookup_preferring_source(
+                model_id,
+                provider_id,
+                self.cost_source.force_source(),
+            )
+        })?;
+        let rate = |rate: Option<f64>| rate.filter(|rate| rate.is_finite() && *rate >= 0.0);
+        let Some(input) = rate(result.pricing.input_cost_per_token) else {
+            return Some(0.0);
+        };
+        let cache_read = rate(result.pricing.cache_read_input_token_cost).unwrap_or(input);
+        Some((input - cache_read).max(0.0))
+    }
+
+    /// Fuzzy-search every known pricing key, custom overrides included.
+    /// See [`PricingLookup::search`].
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchMatch> {
+        let custom_keys = self.custom.entries().map(|(key, _)| (key, "Custom"));
+        rank_search_matches(query, custom_keys.chain(self.lookup.keys()), limit)
     }
 
     fn lookup_custom(&self, model_id: &str) -> Option<LookupResult> {
@@ -325,6 +529,38 @@ impl PricingService {
 mod tests {
     use super::*;
 
+    #[test]
+    fn settle_fetched_sources_keeps_whichever_sources_succeeded() {
+        let data = |key: &str| HashMap::from([(key.to_string(), ModelPricing::default())]);
+
+        let (litellm, openrouter, models_dev) = PricingService::settle_fetched_sources(
+            Err("timed out".to_string()),
+            data("openai/gpt-4o"),
+            Err("HTTP 503".to_string()),
+        )
+        .unwrap();
+        assert!(litellm.is_empty());
+        assert!(openrouter.contains_key("openai/gpt-4o"));
+        assert!(models_dev.is_empty());
+
+        let (litellm, openrouter, _) = PricingService::settle_fetched_sources(
+            Ok(data("gpt-4o")),
+            HashMap::new(),
+            Ok(HashMap::new()),
+        )
+        .unwrap();
+        assert!(litellm.contains_key("gpt-4o"));
+        assert!(openrouter.is_empty());
+
+        let err = PricingService::settle_fetched_sources(
+            Err("timed out".to_string()),
+            HashMap::new(),
+            Err("HTTP 503".to_string()),
+        )
+        .unwrap_err();
+        assert_eq!(err, "timed out");
+    }
+
     fn model_pricing(input: f64, output: f64) -> ModelPricing {
         ModelPricing {
             input_cost_per_token: Some(input),
@@ -404,6 +640,7 @@ mod tests {
             cache_read: 50_000,
             cache_write: 20_000,
             reasoning: 0,
+            ..Default::default()
         };
 
         let cost =
@@ -637,6 +874,60 @@ mod tests {
         assert!((cost - expected).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_cursor_calculate_cost_breakdown_splits_components() {
+        let service = PricingService::new(HashMap::new(), HashMap::new());
+        let breakdown =
+            service.calculate_cost_breakdown("Composer 1", 1_000_000, 60_000, 50_000, 0, 40_000);
+        assert!((breakdown.input_cost - 1_000_000.0 * 0.00000125).abs() < 1e-10);
+        assert!((breakdown.output_cost - 60_000.0 * 0.00001).abs() < 1e-10);
+        assert!((breakdown.reasoning_cost - 40_000.0 * 0.00001).abs() < 1e-10);
+        assert!((breakdown.cache_read_cost - 50_000.0 * 1.25e-7).abs() < 1e-10);
+        assert_eq!(breakdown.cache_write_cost, 0.0);
+
+        let sum = breakdown.input_cost
+            + breakdown.output_cost
+            + breakdown.reasoning_cost
+            + breakdown.cache_read_cost
+            + breakdown.cache_write_cost;
+        assert!((breakdown.total - sum).abs() < 1e-10);
+        let cost = service.calculate_cost("Composer 1", 1_000_000, 60_000, 50_000, 0, 40_000);
+        assert_eq!(breakdown.total, cost);
+    }
+
+    #[test]
+    fn test_cursor_calculate_cost_breakdown_composer_2_cache_write_free() {
+        let service = PricingService::new(HashMap::new(), HashMap::new());
+        let breakdown = service.calculate_cost_breakdown("composer-2", 0, 0, 100_000, 500_000, 0);
+        assert_eq!(breakdown.cache_write_cost, 0.0);
+        assert!((breakdown.cache_read_cost - 100_000.0 * 2e-7).abs() < 1e-10);
+        assert!((breakdown.total - breakdown.cache_read_cost).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_calculate_cost_breakdown_unknown_model_is_zero() {
+        let service = PricingService::new(HashMap::new(), HashMap::new());
+        let breakdown =
+            service.calculate_cost_breakdown("totally-unknown-xyz", 1_000, 1_000, 0, 0, 0);
+        assert_eq!(breakdown, CostBreakdown::default());
+    }
+
+    #[test]
+    fn test_search_includes_custom_overrides() {
+        let service = custom_service(
+            HashMap::from([("acme-opus-internal".to_string(), model_pricing(1e-6, 2e-6))]),
+            HashMap::from([("claude-opus-4-5".to_string(), model_pricing(5e-6, 25e-6))]),
+            HashMap::new(),
+        );
+        let matches = service.search("opus", 10);
+        assert!(matches
+            .iter()
+            .any(|m| m.key == "acme-opus-internal" && m.source == "Custom"));
+        assert!(matches
+            .iter()
+            .any(|m| m.key == "claude-opus-4-5" && m.source == "LiteLLM"));
+    }
+
     #[test]
     fn test_cursor_returns_pricing_for_composer_1() {
         let service = PricingService::new(HashMap::new(), HashMap::new());
@@ -1168,4 +1459,56 @@ mod tests {
         let expected = 1_000_000.0 * 0.000002 + 100_000.0 * 0.000008;
         assert!((cost - expected).abs() < 1e-10);
     }
+
+    #[test]
+    fn cost_source_preference_picks_the_dataset_for_costs() {
+        let mut litellm = HashMap::new();
+        litellm.insert("gpt-fixture-model".into(), model_pricing(0.000002, 0.0));
+        let mut openrouter = HashMap::new();
+        openrouter.insert(
+            "openai/gpt-fixture-model".into(),
+            model_pricing(0.000003, 0.0),
+        );
+        openrouter.insert(
+            "openai/router-only-model".into(),
+            model_pricing(0.000005, 0.0),
+        );
+        let service = PricingService::new(litellm, openrouter);
+        let usage = TokenBreakdown {
+            input: 1_000_000,
+            ..Default::default()
+        };
+        let cost = |model: &str, preference| {
+            service
+                .calculate_cost_breakdown_with_preference(model, Some("openai"), &usage, preference)
+                .total
+        };
+
+        // The provider hint makes OpenRouter's `openai/` key the default match.
+        assert!((cost("gpt-fixture-model", CostSourcePreference::Auto) - 3.0).abs() < 1e-10);
+        assert!((cost("gpt-fixture-model", CostSourcePreference::Litellm) - 2.0).abs() < 1e-10);
+        assert!((cost("gpt-fixture-model", CostSourcePreference::Openrouter) - 3.0).abs() < 1e-10);
+        // A model the preferred dataset lacks still gets the usual price.
+        assert!((cost("router-only-model", CostSourcePreference::Litellm) - 5.0).abs() < 1e-10);
+
+        // A preferring copy shares the datasets and leaves the original alone.
+        let litellm_first = service.with_cost_source(CostSourcePreference::Litellm);
+        let default_cost = |service: &PricingService| {
+            service.calculate_cost_with_provider("gpt-fixture-model", Some("openai"), &usage)
+        };
+        assert!((default_cost(&litellm_first) - 2.0).abs() < 1e-10);
+        assert!((default_cost(&service) - 3.0).abs() < 1e-10);
+        assert_eq!(
+            litellm_first
+                .pricing_source("gpt-fixture-model", Some("openai"))
+                .as_deref(),
+            Some("LiteLLM")
+        );
+
+        assert_eq!(
+            "OpenRouter".parse::<CostSourcePreference>(),
+            Ok(CostSourcePreference::Openrouter)
+        );
+        assert!("models.dev".parse::<CostSourcePreference>().is_err());
+    }
 }
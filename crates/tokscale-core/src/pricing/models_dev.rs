@@ -1,12 +1,11 @@
 use super::cache;
 use super::litellm::ModelPricing;
+use super::retry::{self, AttemptError};
 use serde::Deserialize;
 use std::collections::HashMap;
 
 const CACHE_FILENAME: &str = "pricing-models-dev.json";
 const MODELS_DEV_URL: &str = "https://models.dev/api.json";
-const MAX_RETRIES: u32 = 3;
-const INITIAL_BACKOFF_MS: u64 = 200;
 const PER_MILLION: f64 = 1_000_000.0;
 
 #[derive(Deserialize)]
@@ -60,76 +59,58 @@ async fn fetch_inner(url: &str, use_cache: bool) -> Result<PricingDataset, reqwe
         .connect_timeout(std::time::Duration::from_secs(10))
         .build()?;
 
-    let mut last_error: Option<reqwest::Error> = None;
+    let attempts = retry::max_attempts();
+    let client = &client;
+    retry::retry_with_backoff(attempts, retry::backoff_delay, |attempt| async move {
+        let response = client.get(url).send().await.map_err(|e| {
+            eprintln!(
+                "[tokscale] models.dev network error (attempt {}/{}): {}",
+                attempt + 1,
+                attempts,
+                e
+            );
+            AttemptError::Transient(e)
+        })?;
+        let status = response.status();
+
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            eprintln!(
+                "[tokscale] models.dev HTTP {} (attempt {}/{})",
+                status,
+                attempt + 1,
+                attempts
+            );
+            return Err(AttemptError::Transient(
+                response.error_for_status().unwrap_err(),
+            ));
+        }
 
-    for attempt in 0..MAX_RETRIES {
-        match client.get(url).send().await {
-            Ok(response) => {
-                let status = response.status();
+        if !status.is_success() {
+            eprintln!("[tokscale] models.dev HTTP {}", status);
+            return Err(AttemptError::Permanent(
+                response.error_for_status().unwrap_err(),
+            ));
+        }
 
-                if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let content = response.text().await.map_err(AttemptError::Permanent)?;
+        match parse_dataset(&content) {
+            Ok(data) => {
+                if let Err(e) = cache::save_cache(CACHE_FILENAME, &data) {
                     eprintln!(
-                        "[tokscale] models.dev HTTP {} (attempt {}/{})",
-                        status,
-                        attempt + 1,
-                        MAX_RETRIES
+                        "[tokscale] Warning: Failed to cache models.dev pricing at {}: {}",
+                        cache::get_cache_path(CACHE_FILENAME).display(),
+                        e
                     );
-                    if attempt == MAX_RETRIES - 1 {
-                        return Err(response.error_for_status().unwrap_err());
-                    }
-                    let _ = response.bytes().await;
-                    tokio::time::sleep(std::time::Duration::from_millis(
-                        INITIAL_BACKOFF_MS * (1 << attempt),
-                    ))
-                    .await;
-                    continue;
-                }
-
-                if !status.is_success() {
-                    eprintln!("[tokscale] models.dev HTTP {}", status);
-                    return Err(response.error_for_status().unwrap_err());
-                }
-
-                let content = response.text().await?;
-                match parse_dataset(&content) {
-                    Ok(data) => {
-                        if let Err(e) = cache::save_cache(CACHE_FILENAME, &data) {
-                            eprintln!(
-                                "[tokscale] Warning: Failed to cache models.dev pricing at {}: {}",
-                                cache::get_cache_path(CACHE_FILENAME).display(),
-                                e
-                            );
-                        }
-                        return Ok(data);
-                    }
-                    Err(e) => {
-                        eprintln!("[tokscale] models.dev JSON parse failed: {}", e);
-                        return Ok(HashMap::new());
-                    }
                 }
+                Ok(data)
             }
             Err(e) => {
-                eprintln!(
-                    "[tokscale] models.dev network error (attempt {}/{}): {}",
-                    attempt + 1,
-                    MAX_RETRIES,
-                    e
-                );
-                last_error = Some(e);
-                if attempt < MAX_RETRIES - 1 {
-                    tokio::time::sleep(std::time::Duration::from_millis(
-                        INITIAL_BACKOFF_MS * (1 << attempt),
-                    ))
-                    .await;
-                }
+                eprintln!("[tokscale] models.dev JSON parse failed: {}", e);
+                Ok(HashMap::new())
             }
         }
-    }
-
-    match last_error {
-        Some(e) => Err(e),
-        None => Ok(HashMap::new()),
-    }
+    })
+    .await
 }
 
 fn map_providers(providers: HashMap<String, Provider>) -> PricingDataset {
@@ -181,7 +162,7 @@ mod tests {
         let url = format!("http://{}", listener.local_addr().unwrap());
 
         thread::spawn(move || {
-            for _ in 0..MAX_RETRIES {
+            for _ in 0..retry::DEFAULT_ATTEMPTS {
                 let Ok((mut stream, _)) = listener.accept() else {
                     return;
                 };
@@ -1,14 +1,13 @@
 use super::cache;
 use super::litellm::ModelPricing;
+use super::retry::{self, AttemptError};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Semaphore;
 
 const CACHE_FILENAME: &str = "pricing-openrouter.json";
-const MODELS_URL: &str = "https://openrouter.ai/api/v1/models";
-const MAX_RETRIES: u32 = 3;
-const INITIAL_BACKOFF_MS: u64 = 200;
+pub const MODELS_URL: &str = "https://openrouter.ai/api/v1/models";
 const MAX_CONCURRENT_REQUESTS: usize = 10;
 
 /// Structs for `/api/v1/models` endpoint (list all models).
@@ -190,82 +189,57 @@ pub async fn fetch_all_models() -> HashMap<String, ModelPricing> {
             .unwrap_or_default(),
     );
 
-    let mut last_error: Option<String> = None;
-
-    let models_with_fallback: Vec<(String, Option<ModelPricing>)> = 'retry: {
-        for attempt in 0..MAX_RETRIES {
-            let response = match client
-                .get(MODELS_URL)
-                .header("Content-Type", "application/json")
-                .send()
-                .await
-            {
-                Ok(r) => r,
-                Err(e) => {
-                    last_error = Some(format!("network error: {}", e));
-                    if attempt < MAX_RETRIES - 1 {
-                        tokio::time::sleep(std::time::Duration::from_millis(
-                            INITIAL_BACKOFF_MS * (1 << attempt),
-                        ))
-                        .await;
-                    }
-                    continue;
-                }
-            };
-
-            let status = response.status();
-            if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                last_error = Some(format!("HTTP {}", status));
-                let _ = response.bytes().await;
-                if attempt < MAX_RETRIES - 1 {
-                    tokio::time::sleep(std::time::Duration::from_millis(
-                        INITIAL_BACKOFF_MS * (1 << attempt),
-                    ))
-                    .await;
-                }
-                continue;
-            }
-
-            if !status.is_success() {
-                eprintln!("[tokscale] OpenRouter models API returned {}", status);
-                break 'retry Vec::new();
-            }
-
-            let data: ModelsListResponse = match response.json().await {
-                Ok(d) => d,
-                Err(e) => {
-                    eprintln!("[tokscale] OpenRouter models JSON parse failed: {}", e);
-                    break 'retry Vec::new();
-                }
-            };
-
-            break 'retry data
-                .data
-                .into_iter()
-                .map(|m| {
-                    let fallback = m.pricing.and_then(|p| {
-                        let input = parse_price(&p.prompt)?;
-                        let output = parse_price(&p.completion)?;
-                        Some(ModelPricing {
-                            input_cost_per_token: Some(input),
-                            output_cost_per_token: Some(output),
-                            cache_read_input_token_cost: None,
-                            cache_creation_input_token_cost: None,
-                            ..Default::default()
-                        })
-                    });
-                    (m.id, fallback)
-                })
-                .collect();
+    let attempts = retry::max_attempts();
+    let list_client = &client;
+    let listed = retry::retry_with_backoff(attempts, retry::backoff_delay, |_| async move {
+        let response = list_client
+            .get(MODELS_URL)
+            .header("Content-Type", "application/json")
+            .send()
+            .await
+            .map_err(|e| AttemptError::Transient(format!("network error: {}", e)))?;
+
+        let status = response.status();
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(AttemptError::Transient(format!("HTTP {}", status)));
+        }
+        if !status.is_success() {
+            return Err(AttemptError::Permanent(format!(
+                "models API returned {}",
+                status
+            )));
         }
 
-        if let Some(err) = &last_error {
-            eprintln!(
-                "[tokscale] OpenRouter fetch failed after {} retries: {}",
-                MAX_RETRIES, err
-            );
+        response
+            .json::<ModelsListResponse>()
+            .await
+            .map_err(|e| AttemptError::Permanent(format!("models JSON parse failed: {}", e)))
+    })
+    .await;
+
+    let models_with_fallback: Vec<(String, Option<ModelPricing>)> = match listed {
+        Ok(data) => data
+            .data
+            .into_iter()
+            .map(|m| {
+                let fallback = m.pricing.and_then(|p| {
+                    let input = parse_price(&p.prompt)?;
+                    let output = parse_price(&p.completion)?;
+                    Some(ModelPricing {
+                        input_cost_per_token: Some(input),
+                        output_cost_per_token: Some(output),
+                        cache_read_input_token_cost: None,
+                        cache_creation_input_token_cost: None,
+                        ..Default::default()
+                    })
+                });
+                (m.id, fallback)
+            })
+            .collect(),
+        Err(err) => {
+            eprintln!("[tokscale] OpenRouter fetch failed: {}", err);
+            Vec::new()
         }
-        Vec::new()
     };
 
     if models_with_fallback.is_empty() {
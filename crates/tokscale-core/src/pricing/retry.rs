@@ -0,0 +1,171 @@
+//! Bounded retry with exponential backoff for the pricing fetchers.
+//!
+//! The attempt count defaults to [`DEFAULT_ATTEMPTS`] and can be changed with
+//! `TOKSCALE_PRICING_RETRIES` (total attempts, clamped to
+//! `1..=MAX_ATTEMPTS`).
+
+use std::future::Future;
+use std::time::Duration;
+
+pub const RETRIES_ENV_VAR: &str = "TOKSCALE_PRICING_RETRIES";
+pub const DEFAULT_ATTEMPTS: u32 = 3;
+const MAX_ATTEMPTS: u32 = 10;
+const INITIAL_BACKOFF_MS: u64 = 200;
+const MAX_BACKOFF_MS: u64 = 5_000;
+
+/// Outcome of a single failed attempt.
+#[derive(Debug)]
+pub enum AttemptError<E> {
+    /// Network errors, 5xx, and 429: worth trying again.
+    Transient(E),
+    /// Anything retrying cannot fix (4xx, malformed body).
+    Permanent(E),
+}
+
+/// Attempt count from `TOKSCALE_PRICING_RETRIES`, or the default.
+pub fn max_attempts() -> u32 {
+    parse_attempts(std::env::var(RETRIES_ENV_VAR).ok().as_deref())
+}
+
+fn parse_attempts(raw: Option<&str>) -> u32 {
+    let Some(raw) = raw.map(str::trim).filter(|raw| !raw.is_empty()) else {
+        return DEFAULT_ATTEMPTS;
+    };
+    match raw.parse::<u32>() {
+        Ok(attempts) => attempts.clamp(1, MAX_ATTEMPTS),
+        Err(_) => {
+            eprintln!(
+                "[tokscale] Ignoring invalid {}='{}' (expected a whole number)",
+                RETRIES_ENV_VAR, raw
+            );
+            DEFAULT_ATTEMPTS
+        }
+    }
+}
+
+/// Delay before retry number `attempt + 1`: `200ms * 2^attempt`, capped,
+/// plus up to 50% random jitter so parallel fetchers don't retry in lockstep.
+pub fn backoff_delay(attempt: u32) -> Duration {
+    let base = INITIAL_BACKOFF_MS
+        .saturating_mul(1u64 << attempt.min(16))
+        .min(MAX_BACKOFF_MS);
+    Duration::from_millis(base + jitter_ms(base / 2))
+}
+
+fn jitter_ms(max: u64) -> u64 {
+    if max == 0 {
+        return 0;
+    }
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos() as u64)
+        .unwrap_or(0);
+    nanos % (max + 1)
+}
+
+/// Run `op` up to `attempts` times, sleeping `delay(attempt)` between
+/// transient failures. `op` receives the zero-based attempt number.
+/// Permanent failures and the last transient failure are returned as-is.
+pub async fn retry_with_backoff<T, E, F, Fut>(
+    attempts: u32,
+    delay: impl Fn(u32) -> Duration,
+    mut op: F,
+) -> Result<T, E>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, AttemptError<E>>>,
+{
+    let attempts = attempts.max(1);
+    let mut attempt = 0;
+    loop {
+        match op(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(AttemptError::Permanent(err)) => return Err(err),
+            Err(AttemptError::Transient(err)) => {
+                if attempt + 1 >= attempts {
+                    return Err(err);
+                }
+                tokio::time::sleep(delay(attempt)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn no_delay(_: u32) -> Duration {
+        Duration::ZERO
+    }
+
+    #[tokio::test]
+    async fn retries_transient_failures_until_success() {
+        let calls = Cell::new(0);
+        let result = retry_with_backoff(3, no_delay, |attempt| {
+            calls.set(calls.get() + 1);
+            async move {
+                if attempt < 2 {
+                    Err(AttemptError::Transient("blip"))
+                } else {
+                    Ok("dataset")
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Ok("dataset"));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_the_configured_attempts() {
+        let calls = Cell::new(0);
+        let result: Result<(), _> = retry_with_backoff(2, no_delay, |_| {
+            calls.set(calls.get() + 1);
+            async { Err(AttemptError::Transient("still down")) }
+        })
+        .await;
+
+        assert_eq!(result, Err("still down"));
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn permanent_failures_are_not_retried() {
+        let calls = Cell::new(0);
+        let result: Result<(), _> = retry_with_backoff(5, no_delay, |_| {
+            calls.set(calls.get() + 1);
+            async { Err(AttemptError::Permanent("404")) }
+        })
+        .await;
+
+        assert_eq!(result, Err("404"));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn attempts_come_from_the_env_value() {
+        assert_eq!(parse_attempts(None), DEFAULT_ATTEMPTS);
+        assert_eq!(parse_attempts(Some("")), DEFAULT_ATTEMPTS);
+        assert_eq!(parse_attempts(Some("5")), 5);
+        assert_eq!(parse_attempts(Some("0")), 1);
+        assert_eq!(parse_attempts(Some("99")), MAX_ATTEMPTS);
+        assert_eq!(parse_attempts(Some("lots")), DEFAULT_ATTEMPTS);
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_with_bounded_jitter() {
+        for attempt in 0..4 {
+            let base = INITIAL_BACKOFF_MS * (1 << attempt);
+            let delay = backoff_delay(attempt).as_millis() as u64;
+            assert!(
+                (base..=base + base / 2).contains(&delay),
+                "attempt {attempt}: {delay}ms"
+            );
+        }
+        assert!(backoff_delay(30).as_millis() as u64 <= MAX_BACKOFF_MS + MAX_BACKOFF_MS / 2);
+    }
+}
@@ -33,6 +33,28 @@ fn canonicalize_provider_segment(segment: &str) -> Option<String> {
     Some(canonical.into())
 }
 
+/// Report/grouping id for a raw `provider_id`: trimmed, case-folded, and with
+/// known spellings of one upstream collapsed (`Anthropic`, `anthropic-beta`
+/// -> `anthropic`).
+///
+/// Unlike [`canonical_provider`], this never drops or reroutes a provider
+/// (no `vertex` -> `anthropic`), so unknown ids pass through lowercased.
+pub fn normalize_provider(provider_id: &str) -> String {
+    let lower = provider_id.trim().to_lowercase();
+    let canonical = match lower.as_str() {
+        "anthropic-beta" | "anthropic_beta" => "anthropic",
+        "openai-codex" | "openai_codex" => "openai",
+        "gemini" => "google",
+        "x-ai" | "x_ai" | "x.ai" => "xai",
+        "z-ai" | "z_ai" | "z.ai" => "zai",
+        "moonshot" | "moonshot-ai" => "moonshotai",
+        "mistral" | "mistral-ai" => "mistralai",
+        "github_copilot" => "github-copilot",
+        _ => return lower,
+    };
+    canonical.to_string()
+}
+
 pub fn canonical_provider(raw: &str) -> Option<String> {
     provider_tags(raw).into_iter().next()
 }
@@ -195,6 +217,28 @@ pub fn inferred_provider_from_model(model: &str) -> Option<&'static str> {
     None
 }
 
+/// Coarse model family for `--group-by family` (`claude-opus-4-5` ->
+/// `claude`), derived from [`inferred_provider_from_model`]. Models no
+/// heuristic recognises fall into `other`.
+pub fn model_family(model: &str) -> &'static str {
+    match inferred_provider_from_model(model) {
+        Some("anthropic") => "claude",
+        Some("openai") => "gpt",
+        Some("google") => "gemini",
+        Some("xai") => "grok",
+        Some("deepseek") => "deepseek",
+        Some("minimax") => "minimax",
+        Some("mistral") => "mistral",
+        Some("meta") => "llama",
+        Some("qwen") => "qwen",
+        Some("sakana") => "fugu",
+        Some("moonshotai") => "kimi",
+        Some("xiaomi") => "mimo",
+        Some("zai") => "glm",
+        _ => "other",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,6 +414,54 @@ mod tests {
         assert!(!matches_provider_hint("openai/gpt-4", Some("unknown")));
     }
 
+    #[test]
+    fn test_normalize_provider_collapses_spellings() {
+        for raw in ["anthropic", "Anthropic", " ANTHROPIC ", "anthropic-beta"] {
+            assert_eq!(normalize_provider(raw), "anthropic", "{raw}");
+        }
+        for raw in ["openai", "OpenAI", "openai-codex"] {
+            assert_eq!(normalize_provider(raw), "openai", "{raw}");
+        }
+        for raw in ["xai", "x-ai", "X.AI"] {
+            assert_eq!(normalize_provider(raw), "xai", "{raw}");
+        }
+        assert_eq!(normalize_provider("Gemini"), "google");
+        assert_eq!(normalize_provider("GitHub_Copilot"), "github-copilot");
+    }
+
+    #[test]
+    fn test_normalize_provider_keeps_unknown_and_hosting_providers() {
+        assert_eq!(normalize_provider("Vertex"), "vertex");
+        assert_eq!(normalize_provider("OpenRouter"), "openrouter");
+        assert_eq!(normalize_provider("my-gateway-4"), "my-gateway-4");
+    }
+
+    #[test]
+    fn test_model_family_rolls_up_normalized_models() {
+        let cases = [
+            ("claude-opus-4-5", "claude"),
+            ("claude-haiku-4-5", "claude"),
+            ("gpt-5-codex", "gpt"),
+            ("o3-mini", "gpt"),
+            ("gemini-2-5-pro", "gemini"),
+            ("grok-code-fast-1", "grok"),
+            ("deepseek-v3", "deepseek"),
+            ("llama-3-3-70b", "llama"),
+            ("qwen3-coder-plus", "qwen"),
+            ("kimi-k2-5", "kimi"),
+            ("glm-4-6", "glm"),
+        ];
+        for (model, family) in cases {
+            assert_eq!(model_family(model), family, "{model}");
+        }
+    }
+
+    #[test]
+    fn test_model_family_falls_back_to_other() {
+        assert_eq!(model_family("my-local-model"), "other");
+        assert_eq!(model_family(""), "other");
+    }
+
     #[test]
     fn test_gjc_unknown_provider_passthrough() {
         // gjc's common providers ARE known and canonicalize as usual.
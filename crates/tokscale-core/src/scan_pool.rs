@@ -0,0 +1,66 @@
+//! Thread pool for the scan and parse fan-out.
+//!
+//! The parsers' `par_iter` calls normally run on rayon's global pool, which
+//! starts one thread per core. On many-core machines that can thrash the
+//! disk, so a scan whose options ask for a thread count runs inside a
+//! dedicated pool of that size instead.
+
+/// Environment variable read by [`threads_from_env`].
+pub const THREADS_ENV: &str = "TOKSCALE_SCAN_THREADS";
+
+/// Thread count from `TOKSCALE_SCAN_THREADS`. Unset or empty is `None`;
+/// anything other than a non-negative integer is an error.
+pub fn threads_from_env(env: impl Fn(&str) -> Option<String>) -> Result<Option<usize>, String> {
+    let Some(raw) = env(THREADS_ENV) else {
+        return Ok(None);
+    };
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    raw.parse::<usize>()
+        .map(Some)
+        .map_err(|_| format!("{THREADS_ENV} must be a thread count, got '{raw}'"))
+}
+
+/// Run `op` inside a pool of `threads` threads built for this scan, or
+/// directly (on the global pool) when `threads` is `0`.
+pub(crate) fn install<R: Send>(threads: usize, op: impl FnOnce() -> R + Send) -> Result<R, String> {
+    if threads == 0 {
+        return Ok(op());
+    }
+    Ok(build(threads)?.install(op))
+}
+
+fn build(threads: usize) -> Result<rayon::ThreadPool, String> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|index| format!("tokscale-scan-{index}"))
+        .build()
+        .map_err(|e| format!("failed to start {threads} scan threads: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requested_pool_size_is_respected() {
+        assert_eq!(install(3, rayon::current_num_threads), Ok(3));
+        assert_eq!(install(2, rayon::current_num_threads), Ok(2));
+        assert_eq!(
+            install(0, rayon::current_num_threads),
+            Ok(rayon::current_num_threads())
+        );
+    }
+
+    #[test]
+    fn reads_thread_count_from_env() {
+        let env = |value: Option<&'static str>| move |_: &str| value.map(str::to_string);
+        assert_eq!(threads_from_env(env(None)), Ok(None));
+        assert_eq!(threads_from_env(env(Some(" "))), Ok(None));
+        assert_eq!(threads_from_env(env(Some("8"))), Ok(Some(8)));
+        assert_eq!(threads_from_env(env(Some("0"))), Ok(Some(0)));
+        assert!(threads_from_env(env(Some("many"))).is_err());
+    }
+}
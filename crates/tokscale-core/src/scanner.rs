@@ -5,6 +5,7 @@
 use rayon::prelude::*;
 use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use walkdir::WalkDir;
 
 use crate::clients::ClientId;
@@ -99,6 +100,11 @@ pub struct ScanResult {
     /// VS Code Copilot chat session JSONL files discovered under
     /// `workspaceStorage/*/chatSessions/*.jsonl`.
     pub copilot_vscode_sessions: Vec<PathBuf>,
+    /// Clients this scan was asked for, in [`ClientId::iter`] order.
+    pub enabled: Vec<ClientId>,
+    /// Wall time spent walking each client's scan roots. Roots are walked in
+    /// parallel, so a client's time is the sum over its own roots.
+    pub scan_times: [Duration; ClientId::COUNT],
 }
 
 impl Default for ScanResult {
@@ -119,6 +125,8 @@ impl Default for ScanResult {
             opencode_json_dir: None,
             devin_dbs: Vec::new(),
             copilot_vscode_sessions: Vec::new(),
+            enabled: Vec::new(),
+            scan_times: [Duration::ZERO; ClientId::COUNT],
         }
     }
 }
@@ -132,6 +140,28 @@ impl ScanResult {
         &mut self.files[client as usize]
     }
 
+    /// Number of sources found for `client`: its scanned files plus any
+    /// SQLite databases discovered outside the generic file buckets.
+    pub fn source_count(&self, client: ClientId) -> usize {
+        let dbs = match client {
+            ClientId::OpenCode => self.opencode_dbs.len(),
+            ClientId::Copilot => {
+                usize::from(self.copilot_desktop_db.is_some()) + self.copilot_vscode_sessions.len()
+            }
+            ClientId::Kilo => usize::from(self.kilo_db.is_some()),
+            ClientId::Hermes => usize::from(self.hermes_db.is_some()),
+            ClientId::Goose => usize::from(self.goose_db.is_some()),
+            ClientId::Zed => usize::from(self.zed_db.is_some()),
+            ClientId::Kiro => usize::from(self.kiro_db.is_some()),
+            ClientId::Crush => self.crush_dbs.len(),
+            ClientId::Zcode => usize::from(self.zcode_db.is_some()),
+            ClientId::MiMoCode => self.micode_dbs.len(),
+            ClientId::DevinCli => self.devin_dbs.len(),
+            _ => 0,
+        };
+        self.get(client).len() + dbs
+    }
+
     /// Get total number of files found
     pub fn total_files(&self) -> usize {
         self.files.iter().map(|v| v.len()).sum()
@@ -179,6 +209,63 @@ impl ScanResult {
         paths
     }
 
+    /// Directories to watch for `models --watch`: each enabled client's root
+    /// plus the directory of every discovered source. Nested directories are
+    /// folded into their nearest listed ancestor, so watching the result
+    /// recursively sees each change once.
+    pub fn watch_dirs(&self, home_dir: &str, use_env_roots: bool) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = self
+            .enabled
+            .iter()
+            .map(|client| {
+                PathBuf::from(
+                    client
+                        .data()
+                        .resolve_path_with_env_strategy(home_dir, use_env_roots),
+                )
+            })
+            .filter(|root| root.is_dir())
+            .collect();
+
+        let sources = self
+            .files
+            .iter()
+            .flatten()
+            .chain(&self.opencode_dbs)
+            .chain(self.crush_dbs.iter().map(|crush| &crush.db_path))
+            .chain(&self.micode_dbs)
+            .chain(&self.devin_dbs)
+            .chain(&self.copilot_vscode_sessions)
+            .chain(
+                [
+                    &self.copilot_desktop_db,
+                    &self.synthetic_db,
+                    &self.kilo_db,
+                    &self.hermes_db,
+                    &self.goose_db,
+                    &self.zed_db,
+                    &self.kiro_db,
+                    &self.zcode_db,
+                ]
+                .into_iter()
+                .flatten(),
+            );
+        dirs.extend(sources.filter_map(|path| path.parent().map(Path::to_path_buf)));
+        dirs.extend(self.opencode_json_dir.iter().cloned());
+
+        // Path ordering is component-wise, so an ancestor always sorts
+        // before its descendants.
+        dirs.sort();
+        dirs.dedup();
+        let mut folded: Vec<PathBuf> = Vec::with_capacity(dirs.len());
+        for dir in dirs {
+            if !folded.iter().any(|kept| dir.starts_with(kept)) {
+                folded.push(dir);
+            }
+        }
+        folded
+    }
+
     /// Return every Zed threads SQLite database that should be parsed.
     pub fn zed_db_paths(&self) -> Vec<PathBuf> {
         let mut paths = Vec::new();
@@ -211,10 +298,7 @@ pub fn headless_roots_with_env_strategy(home_dir: &str, use_env_roots: bool) ->
     }
 
     let mut roots = Vec::new();
-    roots.push(PathBuf::from(format!(
-        "{}/.config/tokscale/headless",
-        home_dir
-    )));
+    roots.push(crate::paths::config_dir_for_home(home_dir, use_env_roots).join("headless"));
 
     let mac_root = PathBuf::from(format!(
         "{}/Library/Application Support/tokscale/headless",
@@ -281,6 +365,17 @@ pub fn scan_directory(root: &str, pattern: &str) -> Vec<PathBuf> {
                 "*.json" => file_name.ends_with(".json"),
                 "*.json|*.jsonl" => file_name.ends_with(".json") || file_name.ends_with(".jsonl"),
                 "*.jsonl" => file_name.ends_with(".jsonl"),
+                // Gzip-compressed logs, which the parsers decompress on read.
+                "*.json|*.json.gz" => {
+                    file_name.ends_with(".json") || file_name.ends_with(".json.gz")
+                }
+                "*.jsonl|*.jsonl.gz" => {
+                    file_name.ends_with(".jsonl") || file_name.ends_with(".jsonl.gz")
+                }
+                "*.json|*.jsonl|*.json.gz|*.jsonl.gz" => {
+                    let name = file_name.strip_suffix(".gz").unwrap_or(file_name);
+                    name.ends_with(".json") || name.ends_with(".jsonl")
+                }
                 "*.ndjson" => file_name.ends_with(".ndjson"),
                 "*.log" => file_name.ends_with(".log"),
                 "codebuddy-extension-log" => {
@@ -377,6 +472,7 @@ pub fn scan_directory(root: &str, pattern: &str) -> Vec<PathBuf> {
                 "session-usage.json" => file_name == "session-usage.json",
                 "chat-messages.json" => file_name == "chat-messages.json",
                 "workbuddy.db" => file_name == "workbuddy.db",
+                "aider-history" => crate::sessions::aider::is_aider_history_file(file_name),
                 "sessions.db" => file_name == "sessions.db",
                 "state.db" => file_name == "state.db",
                 "threads.db" => file_name == "threads.db",
@@ -462,6 +558,10 @@ pub fn built_in_extra_scan_paths_for(
             ClientId::Claude,
             PathBuf::from(format!("{}/.claude/transcripts", home_dir)),
         ));
+        paths.push((
+            ClientId::Claude,
+            PathBuf::from(format!("{}/.claude/history", home_dir)),
+        ));
         paths.extend(
             crate::cc_mirror::discover_claude_project_roots(Path::new(home_dir))
                 .into_iter()
@@ -571,6 +671,27 @@ struct CrushProject {
     data_dir: String,
 }
 
+/// OpenCode's data dir: `$OPENCODE_DATA_DIR` when set (and env roots are in
+/// use), otherwise `$XDG_DATA_HOME/opencode`, otherwise
+/// `~/.local/share/opencode`. Both the SQLite dbs and the legacy
+/// `storage/message` JSON tree live under it.
+fn resolve_opencode_data_dir(home_dir: &str, use_env_roots: bool) -> PathBuf {
+    if use_env_roots {
+        if let Some(data_dir) =
+            std::env::var_os("OPENCODE_DATA_DIR").filter(|value| !value.is_empty())
+        {
+            return PathBuf::from(data_dir);
+        }
+    }
+    PathBuf::from(
+        ClientId::OpenCode
+            .data()
+            .root
+            .resolve_with_env_strategy(home_dir, use_env_roots),
+    )
+    .join("opencode")
+}
+
 /// Discover every OpenCode SQLite database under the opencode data dir.
 ///
 /// Matches:
@@ -1125,6 +1246,10 @@ fn scan_all_clients_with_env_strategy_inner(
         enabled_with_devin_lookup.insert(ClientId::DevinCli);
     }
 
+    result.enabled = ClientId::iter()
+        .filter(|client| enabled.contains(client))
+        .collect();
+
     let headless_roots = headless_roots_with_env_strategy(home_dir, use_env_roots);
 
     // Define scan tasks
@@ -1151,6 +1276,7 @@ fn scan_all_clients_with_env_strategy_inner(
                 | ClientId::Gjc
                 | ClientId::MiMoCode
                 | ClientId::DevinCli
+                | ClientId::Aider
         ) {
             continue;
         }
@@ -1268,11 +1394,7 @@ fn scan_all_clients_with_env_strategy_inner(
     }
 
     if enabled.contains(&ClientId::OpenCode) {
-        let xdg_data = if use_env_roots {
-            std::env::var("XDG_DATA_HOME").unwrap_or_else(|_| format!("{}/.local/share", home_dir))
-        } else {
-            format!("{}/.local/share", home_dir)
-        };
+        let opencode_data_dir = resolve_opencode_data_dir(home_dir, use_env_roots);
 
         // OpenCode 1.2+: SQLite database(s) at ~/.local/share/opencode/opencode*.db
         //
@@ -1283,7 +1405,6 @@ fn scan_all_clients_with_env_strategy_inner(
         // under the data dir. See `getChannelPath` in
         // opencode/packages/opencode/src/storage/db.ts for the source of
         // the naming rule.
-        let opencode_data_dir = PathBuf::from(format!("{}/opencode", xdg_data));
         result.opencode_dbs = discover_opencode_dbs(&opencode_data_dir);
 
         // Merge user-configured `scanner.opencodeDbPaths` here, INSIDE the
@@ -1300,9 +1421,10 @@ fn scan_all_clients_with_env_strategy_inner(
         result.opencode_dbs.dedup();
 
         // OpenCode legacy: JSON files at ~/.local/share/opencode/storage/message/*/*.json
-        let opencode_path = ClientId::OpenCode
-            .data()
-            .resolve_path_with_env_strategy(home_dir, use_env_roots);
+        let opencode_path = opencode_data_dir
+            .join("storage/message")
+            .to_string_lossy()
+            .into_owned();
         result.opencode_json_dir = Some(PathBuf::from(&opencode_path));
         push_unique_scan_task(
             &mut tasks,
@@ -1385,13 +1507,16 @@ fn scan_all_clients_with_env_strategy_inner(
             codex_archived_path,
         );
 
-        // Codex headless: <headless_root>/codex/*.jsonl
+        // Codex headless: <headless_root>/codex/*.{json,jsonl}. Captures are
+        // a JSONL event stream either way; `tokscale headless --format json`
+        // only changes the extension.
         for root in &headless_roots {
-            push_unique_scan_task(
+            push_unique_scan_task_with_pattern(
                 &mut tasks,
                 &mut seen_scan_roots,
                 ClientId::Codex,
                 root.join("codex"),
+                "*.json|*.jsonl",
             );
         }
     }
@@ -1409,29 +1534,9 @@ fn scan_all_clients_with_env_strategy_inner(
         );
 
         // Legacy paths (Clawd -> Moltbot -> OpenClaw rebrand history)
-        let clawdbot_path = format!("{}/.clawdbot/agents", home_dir);
-        push_unique_scan_task(
-            &mut tasks,
-            &mut seen_scan_roots,
-            ClientId::OpenClaw,
-            clawdbot_path,
-        );
-
-        let moltbot_path = format!("{}/.moltbot/agents", home_dir);
-        push_unique_scan_task(
-            &mut tasks,
-            &mut seen_scan_roots,
-            ClientId::OpenClaw,
-            moltbot_path,
-        );
-
-        let moldbot_path = format!("{}/.moldbot/agents", home_dir);
-        push_unique_scan_task(
-            &mut tasks,
-            &mut seen_scan_roots,
-            ClientId::OpenClaw,
-            moldbot_path,
-        );
+        for root in openclaw_legacy_roots(home_dir) {
+            push_unique_scan_task(&mut tasks, &mut seen_scan_roots, ClientId::OpenClaw, root);
+        }
     }
 
     // Oh My Pi fork (https://github.com/can1357/oh-my-pi) — same JSONL format, different root
@@ -1813,17 +1918,19 @@ fn scan_all_clients_with_env_strategy_inner(
     }
 
     // Execute scans in parallel
-    let scan_results: Vec<(ClientId, Vec<PathBuf>)> = tasks
+    let scan_results: Vec<(ClientId, Vec<PathBuf>, Duration)> = tasks
         .into_par_iter()
         .map(|(client_id, path, pattern)| {
+            let started = Instant::now();
             let files = scan_directory(&path, pattern);
-            (client_id, files)
+            (client_id, files, started.elapsed())
         })
         .collect();
 
     // Aggregate results, deduplicating file paths across overlapping directories
     let mut seen: HashSet<PathBuf> = HashSet::new();
-    for (client_id, files) in scan_results {
+    for (client_id, files, elapsed) in scan_results {
+        result.scan_times[client_id as usize] += elapsed;
         for file in files {
             if seen.insert(file.clone()) {
                 result.get_mut(client_id).push(file);
@@ -1831,6 +1938,17 @@ fn scan_all_clients_with_env_strategy_inner(
         }
     }
 
+    if enabled.contains(&ClientId::OpenClaw) {
+        let openclaw_path = ClientId::OpenClaw
+            .data()
+            .resolve_path_with_env_strategy(home_dir, use_env_roots);
+        drop_migrated_openclaw_legacy_files(
+            result.get_mut(ClientId::OpenClaw),
+            Path::new(&openclaw_path),
+            &openclaw_legacy_roots(home_dir),
+        );
+    }
+
     if enabled.contains(&ClientId::Copilot) {
         let desktop_db = PathBuf::from(format!("{}/.copilot/data.db", home_dir));
         if desktop_db.is_file() {
@@ -1848,9 +1966,75 @@ fn scan_all_clients_with_env_strategy_inner(
         }
     }
 
+    if enabled.contains(&ClientId::Aider) {
+        let mut found = false;
+        for path in aider_history_candidates(home_dir, use_env_roots) {
+            if path.is_file() && seen.insert(path.clone()) {
+                result.get_mut(ClientId::Aider).push(path);
+                found = true;
+            }
+        }
+        if found {
+            result.get_mut(ClientId::Aider).sort_unstable();
+        }
+    }
+
     result
 }
 
+/// Agent roots left behind by OpenClaw's earlier names (Clawd -> Moltbot ->
+/// OpenClaw). Scanned alongside the primary `~/.openclaw/agents` root.
+fn openclaw_legacy_roots(home_dir: &str) -> Vec<PathBuf> {
+    [".clawdbot", ".moltbot", ".moldbot"]
+        .iter()
+        .map(|dir| Path::new(home_dir).join(dir).join("agents"))
+        .collect()
+}
+
+/// Drop legacy-root transcripts that also exist, at the same agent-relative
+/// path, under the primary OpenClaw root. The rebrand migration copies state
+/// forward, so a leftover legacy tree would otherwise count every migrated
+/// session twice; the primary copy wins because it keeps receiving updates.
+fn drop_migrated_openclaw_legacy_files(
+    files: &mut Vec<PathBuf>,
+    primary_root: &Path,
+    legacy_roots: &[PathBuf],
+) {
+    files.retain(|file| {
+        !legacy_roots.iter().any(|root| {
+            file.strip_prefix(root)
+                .is_ok_and(|relative| primary_root.join(relative).is_file())
+        })
+    });
+}
+
+/// Aider history files that live outside any walked root.
+///
+/// Aider writes `.aider.chat.history.md` / `.aider.llm.history` into the
+/// directory it was launched from, so there is no single tree to walk. The
+/// scanner picks up the pair sitting directly in `$HOME` plus the
+/// `AIDER_CHAT_HISTORY_FILE` / `AIDER_LLM_HISTORY_FILE` overrides; project
+/// directories are added through `scanner.extraScanPaths.aider` or
+/// `TOKSCALE_EXTRA_DIRS`, which are walked recursively.
+fn aider_history_candidates(home_dir: &str, use_env_roots: bool) -> Vec<PathBuf> {
+    let home = Path::new(home_dir);
+    let mut candidates = vec![
+        home.join(crate::sessions::aider::CHAT_HISTORY_FILE),
+        home.join(crate::sessions::aider::LLM_HISTORY_FILE),
+    ];
+    if use_env_roots {
+        for var in ["AIDER_CHAT_HISTORY_FILE", "AIDER_LLM_HISTORY_FILE"] {
+            if let Ok(value) = std::env::var(var) {
+                let trimmed = value.trim();
+                if !trimmed.is_empty() {
+                    candidates.push(PathBuf::from(trimmed));
+                }
+            }
+        }
+    }
+    candidates
+}
+
 pub fn scan_all_clients(home_dir: &str, clients: &[String]) -> ScanResult {
     scan_all_clients_with_env_strategy(home_dir, clients, true)
 }
@@ -1897,9 +2081,14 @@ mod tests {
     }
 
     fn scan_without_extra_dirs(home_dir: &str, clients: &[String]) -> ScanResult {
-        let mut extra = EnvGuard::capture(&["TOKSCALE_EXTRA_DIRS", "TOKSCALE_HEADLESS_DIR"]);
+        let mut extra = EnvGuard::capture(&[
+            "TOKSCALE_EXTRA_DIRS",
+            "TOKSCALE_HEADLESS_DIR",
+            "OPENCODE_DATA_DIR",
+        ]);
         extra.remove("TOKSCALE_EXTRA_DIRS");
         extra.remove("TOKSCALE_HEADLESS_DIR");
+        extra.remove("OPENCODE_DATA_DIR");
         scan_all_clients(home_dir, clients)
     }
 
@@ -1986,6 +2175,36 @@ mod tests {
         assert_eq!(all[5], (ClientId::Pi, PathBuf::from("f.jsonl")));
     }
 
+    #[test]
+    fn test_scan_result_watch_dirs_fold_nested_source_dirs() {
+        let home = TempDir::new().unwrap();
+        let claude_root = home.path().join(".claude/projects");
+        fs::create_dir_all(claude_root.join("proj-a")).unwrap();
+
+        let mut result = ScanResult {
+            enabled: vec![ClientId::Claude, ClientId::Codex],
+            ..ScanResult::default()
+        };
+        result
+            .get_mut(ClientId::Claude)
+            .push(claude_root.join("proj-a/session.jsonl"));
+        result
+            .get_mut(ClientId::Gemini)
+            .push(PathBuf::from("/data/gemini/tmp/a/chats/s1.json"));
+        result
+            .get_mut(ClientId::Gemini)
+            .push(PathBuf::from("/data/gemini/tmp/a/chats/s2.json"));
+        result.kilo_db = Some(PathBuf::from("/data/gemini/kilo.db"));
+
+        let dirs = result.watch_dirs(home.path().to_str().unwrap(), false);
+
+        // The Codex root does not exist, so only Claude's root is listed and
+        // its session directory folds into it.
+        let mut expected = vec![PathBuf::from("/data/gemini"), claude_root];
+        expected.sort();
+        assert_eq!(dirs, expected);
+    }
+
     #[test]
     fn test_scan_result_empty() {
         let result = ScanResult::default();
@@ -2051,6 +2270,36 @@ mod tests {
             .all(|p| p.extension().unwrap() == "jsonl"));
     }
 
+    #[test]
+    fn test_scan_directory_gzip_patterns() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+
+        File::create(path.join("session.jsonl")).unwrap();
+        File::create(path.join("old.jsonl.gz")).unwrap();
+        File::create(path.join("data.json.gz")).unwrap();
+        File::create(path.join("backup.tar.gz")).unwrap();
+
+        let names = |pattern: &str| {
+            let mut names: Vec<String> = scan_directory(path.to_str().unwrap(), pattern)
+                .iter()
+                .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(
+            names("*.jsonl|*.jsonl.gz"),
+            ["old.jsonl.gz", "session.jsonl"]
+        );
+        assert_eq!(names("*.json|*.json.gz"), ["data.json.gz"]);
+        assert_eq!(
+            names("*.json|*.jsonl|*.json.gz|*.jsonl.gz"),
+            ["data.json.gz", "old.jsonl.gz", "session.jsonl"]
+        );
+        assert_eq!(names("*.jsonl"), ["session.jsonl"]);
+    }
+
     #[test]
     fn test_scan_directory_log_pattern() {
         let dir = TempDir::new().unwrap();
@@ -2645,6 +2894,55 @@ mod tests {
         restore_env("XDG_DATA_HOME", previous_xdg);
     }
 
+    #[test]
+    #[serial]
+    fn test_scan_all_clients_honors_opencode_data_dir_override() {
+        let mut env = EnvGuard::capture(&["OPENCODE_DATA_DIR", "TOKSCALE_EXTRA_DIRS"]);
+        env.remove("TOKSCALE_EXTRA_DIRS");
+
+        let dir = TempDir::new().unwrap();
+        let home = dir.path().join("home");
+        let relocated = dir.path().join("relocated-opencode");
+        let message_dir = relocated.join("storage/message/proj1");
+        fs::create_dir_all(&message_dir).unwrap();
+        File::create(message_dir.join("msg_001.json"))
+            .unwrap()
+            .write_all(b"{}")
+            .unwrap();
+        let db_path = relocated.join("opencode.db");
+        File::create(&db_path).unwrap();
+        // The default XDG location must no longer be consulted.
+        setup_mock_opencode_dir(&home);
+
+        env.set("OPENCODE_DATA_DIR", &relocated);
+        let result = scan_all_clients_with_env_strategy(
+            home.to_str().unwrap(),
+            &["opencode".to_string()],
+            true,
+        );
+        assert_eq!(result.opencode_dbs, vec![db_path]);
+        assert_eq!(
+            result.opencode_json_dir,
+            Some(relocated.join("storage/message"))
+        );
+        assert_eq!(
+            result.get(ClientId::OpenCode),
+            &[message_dir.join("msg_001.json")]
+        );
+
+        // An explicit --home override ignores caller env roots, including this one.
+        let result = scan_all_clients_with_env_strategy(
+            home.to_str().unwrap(),
+            &["opencode".to_string()],
+            false,
+        );
+        assert!(result.opencode_dbs.is_empty());
+        assert_eq!(
+            result.opencode_json_dir,
+            Some(home.join(".local/share/opencode/storage/message"))
+        );
+    }
+
     #[test]
     fn test_is_opencode_db_filename_accepts_default_and_channel_variants() {
         // Default channel (`latest`/`beta`) and explicit-disable use this name.
@@ -3630,6 +3928,31 @@ mod tests {
         assert!(result.get(ClientId::OpenCode).is_empty());
     }
 
+    #[test]
+    fn test_scan_all_clients_claude_history_dir() {
+        let dir = TempDir::new().unwrap();
+        let home = dir.path();
+        setup_mock_claude_dir(home);
+        let history_dir = home.join(".claude/history/-Users-alice-repo");
+        fs::create_dir_all(&history_dir).unwrap();
+        let session = history_dir.join("session.jsonl");
+        File::create(&session).unwrap();
+
+        let result = scan_all_clients_with_env_strategy(
+            home.to_str().unwrap(),
+            &["claude".to_string()],
+            false,
+        );
+
+        assert_eq!(result.get(ClientId::Claude).len(), 2);
+        assert!(
+            result.get(ClientId::Claude).contains(&session),
+            "expected Claude history file {} in {:?}",
+            session.display(),
+            result.get(ClientId::Claude)
+        );
+    }
+
     #[test]
     fn test_scan_all_clients_claude_transcripts_without_projects_dir() {
         let dir = TempDir::new().unwrap();
@@ -3812,6 +4135,60 @@ mod tests {
             .any(|path| path.ends_with("session-reset.jsonl.reset.456")));
     }
 
+    #[test]
+    fn test_scan_all_clients_openclaw_includes_legacy_roots() {
+        let dir = TempDir::new().unwrap();
+        let home = dir.path();
+
+        for legacy in [".clawdbot", ".moltbot", ".moldbot"] {
+            let sessions = home.join(legacy).join("agents/main/sessions");
+            fs::create_dir_all(&sessions).unwrap();
+            File::create(sessions.join(format!("{}-session.jsonl", &legacy[1..]))).unwrap();
+        }
+
+        let result = scan_all_clients_with_env_strategy(
+            home.to_str().unwrap(),
+            &["openclaw".to_string()],
+            false,
+        );
+        let files = result.get(ClientId::OpenClaw);
+        assert_eq!(files.len(), 3);
+        for name in ["clawdbot", "moltbot", "moldbot"] {
+            assert!(files
+                .iter()
+                .any(|path| path.ends_with(format!("{}-session.jsonl", name))));
+        }
+    }
+
+    #[test]
+    fn test_scan_all_clients_openclaw_skips_legacy_copies_of_migrated_transcripts() {
+        let dir = TempDir::new().unwrap();
+        let home = dir.path();
+
+        let primary = home.join(".openclaw/agents/main/sessions");
+        let legacy = home.join(".moltbot/agents/main/sessions");
+        fs::create_dir_all(&primary).unwrap();
+        fs::create_dir_all(&legacy).unwrap();
+        File::create(primary.join("migrated.jsonl")).unwrap();
+        File::create(legacy.join("migrated.jsonl")).unwrap();
+        File::create(legacy.join("legacy-only.jsonl")).unwrap();
+
+        let result = scan_all_clients_with_env_strategy(
+            home.to_str().unwrap(),
+            &["openclaw".to_string()],
+            false,
+        );
+        let mut files = result.get(ClientId::OpenClaw).clone();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                legacy.join("legacy-only.jsonl"),
+                primary.join("migrated.jsonl"),
+            ]
+        );
+    }
+
     #[test]
     fn test_scan_all_clients_openclaw_deleted_transcript() {
         let dir = TempDir::new().unwrap();
@@ -4192,6 +4569,7 @@ mod tests {
 
         fs::create_dir_all(mac_root.join("codex")).unwrap();
         File::create(mac_root.join("codex").join("codex.jsonl")).unwrap();
+        File::create(mac_root.join("codex").join("codex-capture.json")).unwrap();
 
         let result = scan_all_clients(
             home.to_str().unwrap(),
@@ -4203,7 +4581,7 @@ mod tests {
         );
 
         assert!(result.get(ClientId::Claude).is_empty());
-        assert_eq!(result.get(ClientId::Codex).len(), 1);
+        assert_eq!(result.get(ClientId::Codex).len(), 2);
         assert!(result.get(ClientId::Gemini).is_empty());
     }
 
@@ -4303,6 +4681,38 @@ mod tests {
         assert!(result.get(ClientId::Claude).is_empty());
     }
 
+    #[test]
+    #[serial]
+    fn test_scan_all_clients_aider_home_and_extra_dirs() {
+        let dir = TempDir::new().unwrap();
+        let home = dir.path();
+        fs::write(home.join(".aider.chat.history.md"), "# aider chat\n").unwrap();
+        let project = home.join("code").join("app");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join(".aider.llm.history"), "{}\n").unwrap();
+        fs::write(project.join("notes.md"), "not aider").unwrap();
+
+        let mut env = EnvGuard::capture(&[
+            "TOKSCALE_EXTRA_DIRS",
+            "TOKSCALE_HEADLESS_DIR",
+            "AIDER_CHAT_HISTORY_FILE",
+            "AIDER_LLM_HISTORY_FILE",
+        ]);
+        env.remove("TOKSCALE_HEADLESS_DIR");
+        env.remove("AIDER_CHAT_HISTORY_FILE");
+        env.remove("AIDER_LLM_HISTORY_FILE");
+        env.set(
+            "TOKSCALE_EXTRA_DIRS",
+            format!("aider:{}", home.join("code").display()),
+        );
+
+        let result = scan_all_clients(home.to_str().unwrap(), &["aider".to_string()]);
+        let files = result.get(ClientId::Aider);
+        assert_eq!(files.len(), 2);
+        assert!(files.contains(&home.join(".aider.chat.history.md")));
+        assert!(files.contains(&project.join(".aider.llm.history")));
+    }
+
     #[test]
     fn test_scan_all_clients_grok() {
         let dir = TempDir::new().unwrap();
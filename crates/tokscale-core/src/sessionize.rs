@@ -401,6 +401,7 @@ mod tests {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             cost: 0.01,
             cost_source: Default::default(),
@@ -409,6 +410,10 @@ mod tests {
             dedup_key: None,
             session_title: None,
             is_turn_start: false,
+            request_bytes: None,
+            response_bytes: None,
+            reported_total_tokens: None,
+            client_cost: None,
             duration_ms: None,
         }
     }
@@ -455,6 +460,7 @@ mod tests {
                 cache_read: i64::MAX,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             };
             message
         };
@@ -0,0 +1,439 @@
+//! Aider history parser
+//!
+//! Aider keeps its history next to the repository it was launched in:
+//! - `.aider.chat.history.md`: the markdown transcript. Each run starts with a
+//!   `# aider chat started at ...` header followed by `> Main model: ...`
+//!   lines, and every reply is followed by a
+//!   `> Tokens: 3.5k sent, 1.2k received. Cost: ...` usage line.
+//! - `.aider.llm.history`: one JSON object per line. Only `message_send`
+//!   events carry usage; plain-text transcript lines are skipped.
+//!
+//! When a directory has both files the structured one wins, since its counts
+//! are exact while the markdown form rounds to `k`/`M` suffixes.
+
+use super::utils::{
+    extract_i64, extract_string, file_modified_timestamp_ms, parse_timestamp_value,
+};
+use super::{normalize_workspace_key, workspace_label_from_key, UnifiedMessage};
+use crate::{provider_identity, TokenBreakdown};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+pub const CHAT_HISTORY_FILE: &str = ".aider.chat.history.md";
+pub const LLM_HISTORY_FILE: &str = ".aider.llm.history";
+
+const CLIENT: &str = "aider";
+
+/// Whether a file name is one of the two Aider history files.
+pub fn is_aider_history_file(file_name: &str) -> bool {
+    file_name == CHAT_HISTORY_FILE || file_name == LLM_HISTORY_FILE
+}
+
+/// Parse every discovered Aider history file, reading one source per
+/// directory and preferring `.aider.llm.history` over the markdown transcript.
+pub fn parse_aider_sources(paths: &[PathBuf]) -> Vec<UnifiedMessage> {
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    let mut by_dir: BTreeMap<PathBuf, (Option<PathBuf>, Option<PathBuf>)> = BTreeMap::new();
+    for path in paths {
+        let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+        if !seen.insert(canonical.clone()) {
+            continue;
+        }
+        let dir = canonical
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let entry = by_dir.entry(dir).or_default();
+        // `AIDER_*_HISTORY_FILE` overrides can rename either file, so only the
+        // markdown extension decides which format a path holds.
+        if canonical.extension().is_some_and(|ext| ext == "md") {
+            entry.1 = Some(canonical);
+        } else {
+            entry.0 = Some(canonical);
+        }
+    }
+
+    let mut messages = Vec::new();
+    for (structured, markdown) in by_dir.into_values() {
+        let from_structured = structured
+            .as_deref()
+            .map(parse_aider_llm_history)
+            .unwrap_or_default();
+        if !from_structured.is_empty() {
+            messages.extend(from_structured);
+        } else if let Some(markdown) = markdown.as_deref() {
+            messages.extend(parse_aider_chat_history(markdown));
+        }
+    }
+    messages
+}
+
+/// Parse the structured `.aider.llm.history` file.
+pub fn parse_aider_llm_history(path: &Path) -> Vec<UnifiedMessage> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let (workspace_key, workspace_label) = workspace_for(path);
+    let fallback_timestamp = file_modified_timestamp_ms(path);
+
+    let mut messages = Vec::new();
+    for line in content.lines() {
+        let Ok(event) = serde_json::from_str::<Value>(line.trim()) else {
+            continue;
+        };
+        if event.get("event").and_then(Value::as_str) != Some("message_send") {
+            continue;
+        }
+        let Some(properties) = event.get("properties") else {
+            continue;
+        };
+        let Some(raw_model) = extract_string(properties.get("main_model")) else {
+            continue;
+        };
+
+        let tokens = TokenBreakdown {
+            input: extract_i64(properties.get("prompt_tokens"))
+                .unwrap_or(0)
+                .max(0),
+            output: extract_i64(properties.get("completion_tokens"))
+                .unwrap_or(0)
+                .max(0),
+            cache_read: extract_i64(properties.get("cache_hit_tokens"))
+                .unwrap_or(0)
+                .max(0),
+            cache_write: extract_i64(properties.get("cache_write_tokens"))
+                .unwrap_or(0)
+                .max(0),
+            reasoning: 0,
+            ..Default::default()
+        };
+        if tokens.input == 0 && tokens.output == 0 {
+            continue;
+        }
+
+        let timestamp = event
+            .get("time")
+            .and_then(parse_timestamp_value)
+            .unwrap_or(fallback_timestamp);
+        let session_id = extract_string(event.get("session_id"))
+            .unwrap_or_else(|| session_id_for(workspace_label.as_deref(), fallback_timestamp));
+        let cost = properties
+            .get("cost")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0)
+            .max(0.0);
+
+        let (model_id, provider_id) = split_model(&raw_model);
+        let mut message = UnifiedMessage::new(
+            CLIENT,
+            model_id,
+            provider_id,
+            session_id,
+            timestamp,
+            tokens,
+            cost,
+        );
+        message.set_workspace(workspace_key.clone(), workspace_label.clone());
+        messages.push(message);
+    }
+    messages
+}
+
+/// Parse the markdown `.aider.chat.history.md` transcript.
+pub fn parse_aider_chat_history(path: &Path) -> Vec<UnifiedMessage> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let (workspace_key, workspace_label) = workspace_for(path);
+    let fallback_timestamp = file_modified_timestamp_ms(path);
+
+    let mut session_started = fallback_timestamp;
+    let mut model: Option<String> = None;
+    let mut messages = Vec::new();
+
+    for line in content.lines() {
+        if let Some(started) = line.strip_prefix("# aider chat started at ") {
+            session_started = parse_local_datetime(started.trim()).unwrap_or(fallback_timestamp);
+            model = None;
+            continue;
+        }
+
+        let Some(note) = line.strip_prefix('>') else {
+            continue;
+        };
+        let note = note.trim();
+        if let Some(header_model) = parse_header_model(note) {
+            model = Some(header_model);
+            continue;
+        }
+        let Some(usage) = note.strip_prefix("Tokens:") else {
+            continue;
+        };
+        let Some(raw_model) = model.as_deref() else {
+            continue;
+        };
+        let Some((tokens, cost)) = parse_usage_line(usage) else {
+            continue;
+        };
+
+        let (model_id, provider_id) = split_model(raw_model);
+        let mut message = UnifiedMessage::new(
+            CLIENT,
+            model_id,
+            provider_id,
+            session_id_for(workspace_label.as_deref(), session_started),
+            session_started,
+            tokens,
+            cost,
+        );
+        message.set_workspace(workspace_key.clone(), workspace_label.clone());
+        messages.push(message);
+    }
+    messages
+}
+
+fn workspace_for(path: &Path) -> (Option<String>, Option<String>) {
+    let workspace_key = path
+        .parent()
+        .and_then(|dir| normalize_workspace_key(&dir.to_string_lossy()));
+    let workspace_label = workspace_key.as_deref().and_then(workspace_label_from_key);
+    (workspace_key, workspace_label)
+}
+
+fn session_id_for(workspace_label: Option<&str>, started_ms: i64) -> String {
+    format!("{}-{}", workspace_label.unwrap_or(CLIENT), started_ms)
+}
+
+/// Aider writes the session header in local time without an offset.
+fn parse_local_datetime(raw: &str) -> Option<i64> {
+    use chrono::TimeZone;
+
+    let naive = chrono::NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S").ok()?;
+    chrono::Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.timestamp_millis())
+}
+
+/// Model from a `Main model:`, `Model:` or `Models:` header line.
+fn parse_header_model(note: &str) -> Option<String> {
+    let rest = ["Main model:", "Models:", "Model:"]
+        .iter()
+        .find_map(|prefix| note.strip_prefix(prefix))?;
+    let model = rest
+        .split(" with ")
+        .next()
+        .unwrap_or(rest)
+        .split(',')
+        .next()
+        .unwrap_or(rest)
+        .trim();
+    (!model.is_empty()).then(|| model.to_string())
+}
+
+/// `openrouter/anthropic/claude-sonnet-4` keeps its routing prefix as the
+/// provider; bare model names fall back to inference from the model id.
+fn split_model(raw: &str) -> (String, String) {
+    if let Some((provider, model)) = raw.split_once('/') {
+        if !provider.is_empty() && !model.is_empty() {
+            let provider = provider_identity::canonical_provider(provider)
+                .unwrap_or_else(|| provider.to_string());
+            return (model.to_string(), provider);
+        }
+    }
+    let provider = provider_identity::inferred_provider_from_model(raw).unwrap_or("unknown");
+    (raw.to_string(), provider.to_string())
+}
+
+/// Parse the part of a usage line after `Tokens:`, e.g.
+/// `2.4k sent, 1.1k cache write, 5.6k cache hit, 301 received. Cost: $0.01 message, $0.04 session.`
+fn parse_usage_line(usage: &str) -> Option<(TokenBreakdown, f64)> {
+    let (token_part, cost_part) = match usage.split_once("Cost:") {
+        Some((tokens, cost)) => (tokens, Some(cost)),
+        None => (usage, None),
+    };
+
+    let mut tokens = TokenBreakdown::default();
+    let mut matched = false;
+    for segment in token_part.trim().trim_end_matches('.').split(", ") {
+        let segment = segment.trim();
+        let Some((count, label)) = segment.split_once(' ') else {
+            continue;
+        };
+        let Some(count) = parse_suffixed_count(count) else {
+            continue;
+        };
+        let slot = match label.trim() {
+            "sent" => &mut tokens.input,
+            "received" => &mut tokens.output,
+            "cache hit" => &mut tokens.cache_read,
+            "cache write" => &mut tokens.cache_write,
+            _ => continue,
+        };
+        *slot = count;
+        matched = true;
+    }
+    if !matched {
+        return None;
+    }
+
+    let cost = cost_part
+        .and_then(|cost| cost.split(',').next())
+        .map(|cost| cost.trim().trim_start_matches('$'))
+        .and_then(|cost| cost.split_whitespace().next())
+        .and_then(|cost| cost.parse::<f64>().ok())
+        .unwrap_or(0.0);
+    Some((tokens, cost))
+}
+
+/// Parse Aider's human-readable counts: `301`, `1,234`, `3.5k`, `1.2M`.
+fn parse_suffixed_count(raw: &str) -> Option<i64> {
+    let raw = raw.trim().replace(',', "");
+    let (number, multiplier) = match raw.chars().last()? {
+        'k' | 'K' => (&raw[..raw.len() - 1], 1_000.0),
+        'm' | 'M' => (&raw[..raw.len() - 1], 1_000_000.0),
+        _ => (raw.as_str(), 1.0),
+    };
+    let value = number.parse::<f64>().ok()?;
+    if !value.is_finite() || value < 0.0 {
+        return None;
+    }
+    Some((value * multiplier).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const CHAT_HISTORY: &str = r#"
+# aider chat started at 2026-03-02 09:15:00
+
+> /usr/local/bin/aider --model anthropic/claude-sonnet-4-5
+> Aider v0.86.1
+> Main model: anthropic/claude-sonnet-4-5 with diff edit format, infinite output
+> Weak model: anthropic/claude-haiku-4-5
+> Git repo: .git with 120 files
+
+#### add a --verbose flag
+
+Sure, here is the change.
+
+> Tokens: 3.5k sent, 1.2k received. Cost: $0.03 message, $0.03 session.
+
+#### also update the README
+
+> Tokens: 2.4k sent, 1.1k cache write, 5.6k cache hit, 301 received. Cost: $0.01 message, $0.04 session.
+
+# aider chat started at 2026-03-03 18:00:00
+
+> Models: gpt-4o with diff edit format, weak model gpt-4o-mini
+
+> Tokens: 1,234 sent, 56 received.
+"#;
+
+    const LLM_HISTORY: &str = r#"TO LLM 2026-03-02T09:15:10
+{"event": "message_send", "time": 1772442910, "properties": {"main_model": "gpt-5", "prompt_tokens": 3512, "completion_tokens": 1187, "cache_hit_tokens": 512, "cache_write_tokens": 0, "cost": 0.0301}}
+{"event": "command_add", "time": 1772442911, "properties": {}}
+{"event": "message_send", "time": 1772442990, "properties": {"main_model": "gpt-5", "prompt_tokens": 0, "completion_tokens": 0}}
+"#;
+
+    fn write_history(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_chat_history_reads_k_suffixed_usage_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = write_history(dir.path(), CHAT_HISTORY_FILE, CHAT_HISTORY);
+
+        let messages = parse_aider_chat_history(&path);
+        assert_eq!(messages.len(), 3);
+
+        let first = &messages[0];
+        assert_eq!(first.client, "aider");
+        assert_eq!(first.model_id, "claude-sonnet-4-5");
+        assert_eq!(first.provider_id, "anthropic");
+        assert_eq!(first.tokens.input, 3_500);
+        assert_eq!(first.tokens.output, 1_200);
+        assert_eq!(first.cost, 0.03);
+        assert_eq!(first.date, "2026-03-02");
+
+        let cached = &messages[1];
+        assert_eq!(cached.tokens.input, 2_400);
+        assert_eq!(cached.tokens.cache_write, 1_100);
+        assert_eq!(cached.tokens.cache_read, 5_600);
+        assert_eq!(cached.tokens.output, 301);
+        assert_eq!(cached.session_id, first.session_id);
+
+        let second_session = &messages[2];
+        assert_eq!(second_session.model_id, "gpt-4o");
+        assert_eq!(second_session.provider_id, "openai");
+        assert_eq!(second_session.tokens.input, 1_234);
+        assert_eq!(second_session.tokens.output, 56);
+        assert_eq!(second_session.cost, 0.0);
+        assert_ne!(second_session.session_id, first.session_id);
+        assert_eq!(second_session.date, "2026-03-03");
+    }
+
+    #[test]
+    fn test_parse_llm_history_reads_message_send_events() {
+        let dir = TempDir::new().unwrap();
+        let path = write_history(dir.path(), LLM_HISTORY_FILE, LLM_HISTORY);
+
+        let messages = parse_aider_llm_history(&path);
+        assert_eq!(messages.len(), 1);
+        let message = &messages[0];
+        assert_eq!(message.model_id, "gpt-5");
+        assert_eq!(message.provider_id, "openai");
+        assert_eq!(message.tokens.input, 3_512);
+        assert_eq!(message.tokens.output, 1_187);
+        assert_eq!(message.tokens.cache_read, 512);
+        assert_eq!(message.timestamp, 1_772_442_910_000);
+        assert_eq!(message.cost, 0.0301);
+        assert!(message.workspace_key.is_some());
+    }
+
+    #[test]
+    fn test_parse_sources_prefers_structured_history_per_directory() {
+        let both = TempDir::new().unwrap();
+        let markdown_only = TempDir::new().unwrap();
+        let paths = vec![
+            write_history(both.path(), CHAT_HISTORY_FILE, CHAT_HISTORY),
+            write_history(both.path(), LLM_HISTORY_FILE, LLM_HISTORY),
+            write_history(markdown_only.path(), CHAT_HISTORY_FILE, CHAT_HISTORY),
+        ];
+
+        let messages = parse_aider_sources(&paths);
+        let structured = messages
+            .iter()
+            .filter(|message| message.model_id == "gpt-5")
+            .count();
+        assert_eq!(structured, 1);
+        assert_eq!(messages.len(), 1 + 3);
+    }
+
+    #[test]
+    fn test_parse_sources_falls_back_to_markdown_without_usage_events() {
+        let dir = TempDir::new().unwrap();
+        let paths = vec![
+            write_history(dir.path(), CHAT_HISTORY_FILE, CHAT_HISTORY),
+            write_history(dir.path(), LLM_HISTORY_FILE, "TO LLM 2026-03-02T09:15:10\n"),
+        ];
+
+        assert_eq!(parse_aider_sources(&paths).len(), 3);
+    }
+
+    #[test]
+    fn test_parse_suffixed_count() {
+        assert_eq!(parse_suffixed_count("301"), Some(301));
+        assert_eq!(parse_suffixed_count("1,234"), Some(1_234));
+        assert_eq!(parse_suffixed_count("3.5k"), Some(3_500));
+        assert_eq!(parse_suffixed_count("1.2M"), Some(1_200_000));
+        assert_eq!(parse_suffixed_count("lots"), None);
+    }
+}
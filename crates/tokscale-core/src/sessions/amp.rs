@@ -160,6 +160,7 @@ fn parse_amp_ledger_records(
                     cache_read: tokens.cache_read_input_tokens.unwrap_or(0).max(0),
                     cache_write: tokens.cache_creation_input_tokens.unwrap_or(0).max(0),
                     reasoning: 0,
+                    ..Default::default()
                 },
                 cost: event.credits.unwrap_or(0.0).max(0.0),
             })
@@ -206,6 +207,7 @@ fn parse_amp_message_records(
                     cache_read: usage.cache_read_input_tokens.unwrap_or(0).max(0),
                     cache_write: usage.cache_creation_input_tokens.unwrap_or(0).max(0),
                     reasoning: 0,
+                    ..Default::default()
                 },
                 cost: usage.credits.unwrap_or(0.0).max(0.0),
             })
@@ -98,6 +98,7 @@ fn parse_usage_row(value: &Value, fallback_model: Option<&str>) -> Option<Unifie
             cache_read,
             cache_write,
             reasoning,
+            ..Default::default()
         },
         0.0,
         dedup_key,
@@ -3,13 +3,111 @@
 //! Cline is the upstream project that Roo Code and Kilo forked from, so it
 //! shares the same VS Code globalStorage task-log format and reuses the same
 //! parser helper.
+//!
+//! Tasks whose `ui_messages.json` carries no `api_req_started` events fall
+//! back to the sibling `api_conversation_history.json`, reading the
+//! Anthropic-style `usage` block on each assistant message.
 
 use super::roocode::parse_roo_kilo_file;
+use super::utils::{
+    extract_i64, extract_string, file_modified_timestamp_ms, parse_timestamp_value,
+    read_file_or_none,
+};
 use super::UnifiedMessage;
+use crate::{provider_identity, TokenBreakdown};
+use serde_json::Value;
+use std::collections::HashSet;
 use std::path::Path;
 
 pub fn parse_cline_file(path: &Path) -> Vec<UnifiedMessage> {
-    parse_roo_kilo_file(path, "cline")
+    let messages = parse_roo_kilo_file(path, "cline");
+    if !messages.is_empty() {
+        return messages;
+    }
+    match path.parent() {
+        Some(task_dir) => parse_cline_api_history(&task_dir.join("api_conversation_history.json")),
+        None => Vec::new(),
+    }
+}
+
+/// Parse usage blocks out of a task's `api_conversation_history.json`.
+///
+/// Messages are deduplicated by `id` within the task, since Cline rewrites
+/// the history on retries. Timestamps come from the message `ts` when
+/// present, otherwise from the task id (a millisecond epoch), otherwise the
+/// file mtime. A malformed file yields no messages.
+pub fn parse_cline_api_history(path: &Path) -> Vec<UnifiedMessage> {
+    let Some(mut bytes) = read_file_or_none(path) else {
+        return Vec::new();
+    };
+    let entries: Vec<Value> = match simd_json::from_slice(&mut bytes) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let task_id = path
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .and_then(|name| name.to_str())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("unknown")
+        .to_string();
+    let task_timestamp = task_id
+        .parse::<i64>()
+        .ok()
+        .and_then(|ms| parse_timestamp_value(&Value::from(ms)))
+        .unwrap_or_else(|| file_modified_timestamp_ms(path));
+
+    let mut seen_ids = HashSet::new();
+    let mut messages = Vec::new();
+    for entry in &entries {
+        let Some(usage) = entry.get("usage").filter(|usage| usage.is_object()) else {
+            continue;
+        };
+        if let Some(id) = extract_string(entry.get("id")) {
+            if !seen_ids.insert(id) {
+                continue;
+            }
+        }
+
+        let tokens = TokenBreakdown {
+            input: extract_i64(usage.get("input_tokens")).unwrap_or(0).max(0),
+            output: extract_i64(usage.get("output_tokens")).unwrap_or(0).max(0),
+            cache_read: extract_i64(usage.get("cache_read_input_tokens"))
+                .unwrap_or(0)
+                .max(0),
+            cache_write: extract_i64(usage.get("cache_creation_input_tokens"))
+                .unwrap_or(0)
+                .max(0),
+            reasoning: 0,
+            ..Default::default()
+        };
+        if tokens.input + tokens.output + tokens.cache_read + tokens.cache_write == 0 {
+            continue;
+        }
+
+        let model_id = extract_string(entry.get("model"))
+            .filter(|model| !model.trim().is_empty())
+            .unwrap_or_else(|| "unknown".to_string());
+        let provider =
+            provider_identity::inferred_provider_from_model(&model_id).unwrap_or("unknown");
+        let timestamp = entry
+            .get("ts")
+            .and_then(parse_timestamp_value)
+            .unwrap_or(task_timestamp);
+
+        messages.push(UnifiedMessage::new(
+            "cline",
+            model_id,
+            provider,
+            task_id.clone(),
+            timestamp,
+            tokens,
+            0.0,
+        ));
+    }
+
+    messages
 }
 
 #[cfg(test)]
@@ -81,4 +179,69 @@ mod tests {
         let messages = parse_cline_file(&task_dir.join("ui_messages.json"));
         assert!(messages.is_empty());
     }
+
+    #[test]
+    fn test_parse_cline_falls_back_to_api_history_usage() {
+        let dir = TempDir::new().unwrap();
+        let task_dir = dir.path().join("tasks").join("1771416000000");
+        fs::create_dir_all(&task_dir).unwrap();
+        fs::write(task_dir.join("ui_messages.json"), "[]").unwrap();
+        fs::write(
+            task_dir.join("api_conversation_history.json"),
+            r#"[
+  {"role": "user", "content": [{"type": "text", "text": "fix the build"}]},
+  {
+    "role": "assistant",
+    "id": "msg_01",
+    "model": "claude-sonnet-4-5",
+    "usage": {"input_tokens": 120, "output_tokens": 30, "cache_read_input_tokens": 500, "cache_creation_input_tokens": 80}
+  },
+  {"role": "user", "content": [{"type": "text", "text": "and the tests"}]},
+  {
+    "role": "assistant",
+    "id": "msg_02",
+    "model": "claude-sonnet-4-5",
+    "ts": 1771419600000,
+    "usage": {"input_tokens": 40, "output_tokens": 10}
+  },
+  {
+    "role": "assistant",
+    "id": "msg_02",
+    "model": "claude-sonnet-4-5",
+    "usage": {"input_tokens": 40, "output_tokens": 10}
+  }
+]"#,
+        )
+        .unwrap();
+
+        let messages = parse_cline_file(&task_dir.join("ui_messages.json"));
+        assert_eq!(messages.len(), 2);
+        assert!(messages.iter().all(|m| m.client == "cline"));
+        assert!(messages.iter().all(|m| m.session_id == "1771416000000"));
+        assert_eq!(messages[0].provider_id, "anthropic");
+        assert_eq!(messages[0].model_id, "claude-sonnet-4-5");
+        assert_eq!(messages[0].timestamp, 1_771_416_000_000);
+        assert_eq!(messages[0].date, "2026-02-18");
+        assert_eq!(messages[0].tokens.input, 120);
+        assert_eq!(messages[0].tokens.output, 30);
+        assert_eq!(messages[0].tokens.cache_read, 500);
+        assert_eq!(messages[0].tokens.cache_write, 80);
+        assert_eq!(messages[1].timestamp, 1_771_419_600_000);
+        assert_eq!(messages[1].tokens.input, 40);
+    }
+
+    #[test]
+    fn test_parse_cline_skips_malformed_api_history() {
+        let dir = TempDir::new().unwrap();
+        let task_dir = dir.path().join("tasks").join("cline-task-3");
+        fs::create_dir_all(&task_dir).unwrap();
+        fs::write(task_dir.join("ui_messages.json"), "[]").unwrap();
+        fs::write(
+            task_dir.join("api_conversation_history.json"),
+            r#"[{"role": "assistant", "usage": {"input_tokens": 5"#,
+        )
+        .unwrap();
+
+        assert!(parse_cline_file(&task_dir.join("ui_messages.json")).is_empty());
+    }
 }
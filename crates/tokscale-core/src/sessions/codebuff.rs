@@ -86,6 +86,7 @@ pub fn parse_codebuff_file(path: &Path) -> Vec<UnifiedMessage> {
                 cache_read: usage.cache_read_input_tokens.max(0),
                 cache_write: usage.cache_creation_input_tokens.max(0),
                 reasoning: 0,
+                ..Default::default()
             },
             usage.credits.max(0.0),
             Some(dedup_key),
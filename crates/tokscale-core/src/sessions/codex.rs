@@ -11,7 +11,8 @@
 //! Note: This parser has stateful logic to track model and delta calculations.
 
 use super::utils::{
-    extract_i64, extract_string, file_modified_timestamp_ms, parse_timestamp_value,
+    extract_i64, extract_string, file_modified_timestamp_ms, is_gzip_path, open_session_reader,
+    parse_timestamp_value, session_file_stem,
 };
 use super::{normalize_workspace_key, workspace_label_from_key, UnifiedMessage};
 use crate::provider_identity::inferred_provider_from_model;
@@ -197,6 +198,7 @@ impl CodexTotals {
             cache_read: clamped_cached,
             cache_write: 0,
             reasoning: self.reasoning.max(0),
+            ..Default::default()
         }
     }
 }
@@ -258,10 +260,7 @@ pub(crate) struct ParsedCodexFile {
 }
 
 fn session_id_from_path(path: &Path) -> String {
-    path.file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("unknown")
-        .to_string()
+    session_file_stem(path).unwrap_or("unknown").to_string()
 }
 
 fn codex_workspace_from_cwd(cwd: &str) -> (Option<String>, Option<String>) {
@@ -323,6 +322,7 @@ fn parse_codex_reader<R: BufRead>(
                 break;
             }
         };
+        let line_offset = consumed_offset;
         consumed_offset += bytes_read as u64;
 
         let trimmed = line.trim();
@@ -641,26 +641,33 @@ fn parse_codex_reader<R: BufRead>(
                         message.is_turn_start = true;
                         state.pending_turn_start = false;
                     }
+                    // Fork/subagent children replay the same upstream
+                    // token_count history into many sibling files. Those
+                    // replays carry identical cumulative totals but a
+                    // distinct per-file session id, so a session-scoped key
+                    // never collapses them and the totals get counted once
+                    // per sibling. Scope the key to the fork parent instead
+                    // so sibling replays share one key. Unrelated sessions
+                    // keep their own id and never merge.
+                    let dedup_scope_id = state
+                        .session_forked_from_id
+                        .as_deref()
+                        .or(state.session_id_from_meta.as_deref())
+                        .unwrap_or(session_id);
                     if parsed_timestamp.is_some() || total_usage.is_some() {
-                        // Fork/subagent children replay the same upstream
-                        // token_count history into many sibling files. Those
-                        // replays carry identical cumulative totals but a
-                        // distinct per-file session id, so a session-scoped key
-                        // never collapses them and the totals get counted once
-                        // per sibling. Scope the key to the fork parent instead
-                        // so sibling replays share one key. Unrelated sessions
-                        // keep their own id and never merge.
-                        let dedup_scope_id = state
-                            .session_forked_from_id
-                            .as_deref()
-                            .or(state.session_id_from_meta.as_deref())
-                            .unwrap_or(session_id);
                         set_codex_dedup_key(
                             &mut message,
                             model.as_deref().unwrap_or("unknown"),
                             dedup_scope_id,
                             total_usage,
                         );
+                    } else {
+                        // Nothing in the event itself is stable: the
+                        // timestamp is later replaced by the file mtime.
+                        // The event's byte offset is, and overlapping copies
+                        // of a rollout share it.
+                        message.dedup_key =
+                            Some(codex_line_offset_dedup_key(dedup_scope_id, line_offset));
                     }
                     message.set_workspace(
                         state.session_workspace_key.clone(),
@@ -731,6 +738,8 @@ fn parse_codex_reader<R: BufRead>(
         }
 
         if let Some((mut msg, used_fallback_timestamp)) = headless_message {
+            let dedup_scope_id = state.session_id_from_meta.as_deref().unwrap_or(session_id);
+            msg.dedup_key = Some(codex_line_offset_dedup_key(dedup_scope_id, line_offset));
             msg.set_workspace(
                 state.session_workspace_key.clone(),
                 state.session_workspace_label.clone(),
@@ -744,7 +753,14 @@ fn parse_codex_reader<R: BufRead>(
 
         let mut json_probe = trimmed.as_bytes().to_vec();
         if simd_json::from_slice::<Value>(&mut json_probe).is_err() {
-            parse_succeeded = false;
+            if line.ends_with('\n') {
+                parse_succeeded = false;
+            } else {
+                // A last line with no newline is a write still in progress,
+                // not corruption. Leave it unconsumed so the next
+                // incremental parse reads it once it is complete.
+                consumed_offset = line_offset;
+            }
             continue;
         }
     }
@@ -945,6 +961,12 @@ fn codex_token_count_dedup_key(
     )
 }
 
+/// Dedup key for usage rows that carry neither a timestamp nor cumulative
+/// totals. Offsets are absolute, so incremental re-parses agree with full ones.
+fn codex_line_offset_dedup_key(upstream_session_id: &str, line_offset: u64) -> String {
+    format!("codex:line-offset:{}:{}", upstream_session_id, line_offset)
+}
+
 fn set_codex_dedup_key(
     message: &mut UnifiedMessage,
     model: &str,
@@ -1001,14 +1023,12 @@ fn flush_pending_model_messages_as_unknown(
 
 /// Parse a Codex JSONL file with stateful tracking
 pub fn parse_codex_file(path: &Path) -> Vec<UnifiedMessage> {
-    let file = match std::fs::File::open(path) {
-        Ok(f) => f,
-        Err(_) => return Vec::new(),
+    let Some(reader) = open_session_reader(path) else {
+        return Vec::new();
     };
 
     let session_id = session_id_from_path(path);
     let fallback_timestamp = file_modified_timestamp_ms(path);
-    let reader = BufReader::new(file);
     let parsed = parse_codex_reader(
         reader,
         &session_id,
@@ -1059,6 +1079,24 @@ pub(crate) fn parse_codex_file_incremental(
     start_offset: u64,
     state: CodexParseState,
 ) -> ParsedCodexFile {
+    // A compressed log can't be resumed at a byte offset, so it is only
+    // ever read from the start (and never cached incrementally).
+    if is_gzip_path(path) {
+        let Some(reader) = open_session_reader(path).filter(|_| start_offset == 0) else {
+            return ParsedCodexFile {
+                messages: Vec::new(),
+                fallback_timestamp_indices: Vec::new(),
+                consumed_offset: start_offset,
+                parse_succeeded: false,
+                unresolved_model_events: false,
+                state,
+            };
+        };
+        let session_id = session_id_from_path(path);
+        let fallback_timestamp = file_modified_timestamp_ms(path);
+        return parse_codex_reader(reader, &session_id, fallback_timestamp, 0, state);
+    }
+
     let mut file = match std::fs::File::open(path) {
         Ok(file) => file,
         Err(_) => {
@@ -1165,6 +1203,7 @@ fn parse_codex_headless_line(
                 cache_read: usage.cached.max(0),
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
             agent,
@@ -1676,6 +1715,27 @@ mod tests {
         assert!(!incremental.parse_succeeded);
     }
 
+    #[test]
+    fn test_truncated_last_line_keeps_earlier_messages_and_is_left_unconsumed() {
+        let complete = concat!(
+            r#"{"type":"turn_context","payload":{"model":"gpt-5.4"}}"#,
+            "\n",
+            r#"{"type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":10,"cached_input_tokens":2,"output_tokens":3},"last_token_usage":{"input_tokens":10,"cached_input_tokens":2,"output_tokens":3}}}}"#,
+            "\n"
+        );
+        let file = create_test_file(&format!(
+            "{complete}{}",
+            r#"{"type":"event_msg","payload":{"type":"token_co"#
+        ));
+
+        let parsed = parse_codex_file_incremental(file.path(), 0, CodexParseState::default());
+
+        assert!(parsed.parse_succeeded);
+        assert_eq!(parsed.messages.len(), 1);
+        assert_eq!(parsed.messages[0].tokens.output, 3);
+        assert_eq!(parsed.consumed_offset, complete.len() as u64);
+    }
+
     #[test]
     fn test_parse_file_preserves_valid_messages_after_late_invalid_utf8_line_error() {
         let mut file = NamedTempFile::new().unwrap();
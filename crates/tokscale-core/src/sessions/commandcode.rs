@@ -168,6 +168,7 @@ pub fn parse_commandcode_file(path: &Path) -> Vec<UnifiedMessage> {
                         cache_read: 0,
                         cache_write: 0,
                         reasoning: 0,
+                        ..Default::default()
                     },
                     0.0,
                     Some(format!("{}:{}", resolved_session, assistant_index)),
@@ -829,6 +829,7 @@ pub(crate) fn normalize_input_tokens(
         cache_read: cache_read.max(0),
         cache_write: cache_write.max(0),
         reasoning: reasoning.max(0),
+        ..Default::default()
     }
 }
 
@@ -1822,6 +1823,7 @@ mod tests {
                 cache_read: 30,
                 cache_write: 4,
                 reasoning: 5,
+                ..Default::default()
             },
             dedup_key: "same-key".to_string(),
             agent: Some("fallback-agent".to_string()),
@@ -1845,6 +1847,7 @@ mod tests {
                 cache_read: 40,
                 cache_write: 8,
                 reasoning: 6,
+                ..Default::default()
             },
             dedup_key: "same-key".to_string(),
             agent: Some("recovered-agent".to_string()),
@@ -140,6 +140,7 @@ fn request_to_message(
         cache_read: 0,
         cache_write: 0,
         reasoning: reasoning_tokens.max(0),
+        ..Default::default()
     };
 
     let dedup_key = format!("copilot-vscode:{}:{}", session_id, timestamp_ms);
@@ -3,16 +3,42 @@
 //! Parses CSV files from the Cursor usage export API.
 //! CSV files are cached locally at ~/.config/tokscale/cursor-cache/*.csv
 //! (legacy single-account cache uses usage.csv; additional accounts may use usage.<account>.csv)
+//! Messages carry the account label in `agent` (see [`cursor_account_label`])
+//! so spend can be split between work and personal accounts.
 //!
 //! CSV Formats:
 //! - v1 (old): Date,Model,Input (w/ Cache Write),Input (w/o Cache Write),Cache Read,Output Tokens,Total Tokens,Cost,Cost to you
 //! - v2 (new): Date,Kind,Model,Max Mode,Input (w/ Cache Write),Input (w/o Cache Write),Cache Read,Output Tokens,Total Tokens,Cost
 //! - v3 (latest): Date,Cloud Agent ID,Automation ID,Kind,Model,Max Mode,Input (w/ Cache Write),Input (w/o Cache Write),Cache Read,Output Tokens,Total Tokens,Cost
+//!
+//! Exports may carry extra `Request Bytes` / `Response Bytes` columns after
+//! the fixed ones; they are looked up by name and fill
+//! `UnifiedMessage::request_bytes` / `response_bytes` when present.
 
 use super::UnifiedMessage;
 use crate::{provider_identity, TokenBreakdown};
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::Path;
 
+/// Credentials store written by `tokscale cursor login`, next to the cache
+/// directory (`~/.config/tokscale/cursor-credentials.json`). Only the fields
+/// needed to name an account are read.
+const CREDENTIALS_FILE_NAME: &str = "cursor-credentials.json";
+
+#[derive(Debug, Deserialize)]
+struct CredentialsStore {
+    #[serde(rename = "activeAccountId")]
+    active_account_id: String,
+    #[serde(default)]
+    accounts: HashMap<String, CredentialsAccount>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CredentialsAccount {
+    label: Option<String>,
+}
+
 fn account_id_from_cursor_cache_path(path: &Path) -> String {
     let file_name = path
         .file_name()
@@ -47,6 +73,76 @@ fn account_id_from_cursor_cache_path(path: &Path) -> String {
     "unknown".to_string()
 }
 
+/// Account label for a cached usage CSV, used to tell accounts apart.
+///
+/// `usage.csv` belongs to the active account and `usage.<id>.csv` to the
+/// account whose sanitized id is `<id>`. The label set at login wins; an
+/// unlabeled account falls back to its id. A lone legacy `usage.csv` with no
+/// credentials store stays unlabeled, so single-account setups are unchanged.
+pub fn cursor_account_label(path: &Path) -> Option<String> {
+    let file_account = account_id_from_cursor_cache_path(path);
+    let store = path
+        .parent()
+        .and_then(Path::parent)
+        .map(|config_dir| config_dir.join(CREDENTIALS_FILE_NAME))
+        .and_then(|store_path| std::fs::read_to_string(store_path).ok())
+        .and_then(|data| serde_json::from_str::<CredentialsStore>(&data).ok());
+
+    let Some(store) = store else {
+        return (file_account != "active").then_some(file_account);
+    };
+
+    let (account_id, account) = if file_account == "active" {
+        let id = store.active_account_id.as_str();
+        (id, store.accounts.get(id))
+    } else {
+        match store
+            .accounts
+            .iter()
+            .find(|(id, _)| sanitize_account_id(id) == file_account)
+        {
+            Some((id, account)) => (id.as_str(), Some(account)),
+            None => return Some(file_account),
+        }
+    };
+
+    account
+        .and_then(|account| account.label.as_deref())
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .unwrap_or(account_id)
+        .to_string()
+        .into()
+}
+
+/// Mirrors the CLI's cache-file naming so `usage.<id>.csv` maps back to its
+/// credentials entry.
+fn sanitize_account_id(account_id: &str) -> String {
+    let sanitized: String = account_id
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let trimmed = sanitized.trim_matches('-');
+    let result = if trimmed.len() > 80 {
+        &trimmed[..80]
+    } else {
+        trimmed
+    };
+    if result.is_empty() {
+        "account".to_string()
+    } else {
+        result.to_string()
+    }
+}
+
 /// Provider inference from model name
 fn infer_provider(model: &str) -> &'static str {
     provider_identity::inferred_provider_from_model(model).unwrap_or("cursor")
@@ -118,6 +214,9 @@ pub fn parse_cursor_file(path: &Path) -> Vec<UnifiedMessage> {
         (1, 2, 3, 4, 5, 7)
     };
 
+    let request_bytes_idx = column_index(&header_fields, "Request Bytes");
+    let response_bytes_idx = column_index(&header_fields, "Response Bytes");
+
     let account_id = account_id_from_cursor_cache_path(path);
 
     for line in lines {
@@ -175,7 +274,7 @@ pub fn parse_cursor_file(path: &Path) -> Vec<UnifiedMessage> {
         // Input tokens = input_without_cache_write
         let input = input_without_cache_write;
 
-        messages.push(UnifiedMessage::new(
+        let mut message = UnifiedMessage::new(
             "cursor",
             model,
             infer_provider(model),
@@ -187,14 +286,38 @@ pub fn parse_cursor_file(path: &Path) -> Vec<UnifiedMessage> {
                 cache_read: cache_read.max(0),
                 cache_write, // Already clamped above with .max(0)
                 reasoning: 0,
+                ..Default::default()
             },
             cost.max(0.0),
-        ));
+        );
+        message.request_bytes = request_bytes_idx.and_then(|idx| parse_bytes(&fields, idx));
+        message.response_bytes = response_bytes_idx.and_then(|idx| parse_bytes(&fields, idx));
+        messages.push(message);
     }
 
     messages
 }
 
+/// Index of the header column named `name`, ignoring case and quotes.
+fn column_index(header_fields: &[&str], name: &str) -> Option<usize> {
+    header_fields
+        .iter()
+        .position(|field| field.trim().trim_matches('"').eq_ignore_ascii_case(name))
+}
+
+/// A byte count at `idx`, or `None` when the row is short or the cell is
+/// blank or not a number.
+fn parse_bytes(fields: &[&str], idx: usize) -> Option<i64> {
+    fields
+        .get(idx)?
+        .trim()
+        .trim_matches('"')
+        .replace(',', "")
+        .parse::<i64>()
+        .ok()
+        .map(|bytes| bytes.max(0))
+}
+
 /// Simple CSV line parser that handles quoted fields
 fn parse_csv_line(line: &str) -> Vec<&str> {
     let mut fields = Vec::new();
@@ -397,4 +520,50 @@ mod tests {
         assert_eq!(messages[2].model_id, "composer-2");
         assert_eq!(messages[2].cost, 0.0);
     }
+
+    #[test]
+    fn test_parse_cursor_csv_byte_columns() {
+        let csv = r#"Date,Cloud Agent ID,Automation ID,Kind,Model,Max Mode,Input (w/ Cache Write),Input (w/o Cache Write),Cache Read,Output Tokens,Total Tokens,Cost,Request Bytes,Response Bytes
+"2026-04-09T20:01:10.528Z","","","On-Demand","composer-2","No","0","1200","0","300","1500","0.02","48213","9120"
+"2026-04-09T18:02:13.576Z","","","On-Demand","composer-2","No","0","800","0","200","1000","0.01","",""
+"2026-04-09T07:39:09.091Z","","","On-Demand","composer-2","No","0","500","0","100","600","0.01""#;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("usage.csv");
+        std::fs::write(&file_path, csv).unwrap();
+
+        let messages = parse_cursor_file(&file_path);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].request_bytes, Some(48213));
+        assert_eq!(messages[0].response_bytes, Some(9120));
+        assert_eq!(messages[1].request_bytes, None);
+        assert_eq!(messages[2].response_bytes, None);
+        assert_eq!(messages[0].tokens.input, 1200);
+    }
+
+    #[test]
+    fn test_cursor_account_label_resolves_from_credentials_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join("cursor-cache");
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        let active = cache_dir.join("usage.csv");
+        let work = cache_dir.join("usage.work-acct.csv");
+        let unknown = cache_dir.join("usage.other.csv");
+
+        // Without a credentials store only named cache files get a label.
+        assert_eq!(cursor_account_label(&active), None);
+        assert_eq!(cursor_account_label(&work).as_deref(), Some("work-acct"));
+
+        std::fs::write(
+            dir.path().join(CREDENTIALS_FILE_NAME),
+            r#"{"version":1,"activeAccountId":"me@example.com","accounts":{
+                "me@example.com":{"sessionToken":"a","label":"Personal"},
+                "Work Acct":{"sessionToken":"b","label":"  "}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(cursor_account_label(&active).as_deref(), Some("Personal"));
+        assert_eq!(cursor_account_label(&work).as_deref(), Some("Work Acct"));
+        assert_eq!(cursor_account_label(&unknown).as_deref(), Some("other"));
+    }
 }
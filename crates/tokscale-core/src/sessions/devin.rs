@@ -243,6 +243,7 @@ pub fn parse_devin_cli_sqlite(db_path: &Path) -> Vec<UnifiedMessage> {
                 cache_read: m.cache_read_tokens.unwrap_or(0).max(0),
                 cache_write: m.cache_creation_tokens.unwrap_or(0).max(0),
                 reasoning: 0,
+                ..Default::default()
             },
             None => TokenBreakdown::default(),
         };
@@ -571,6 +572,7 @@ pub fn parse_devin_desktop_ndjson_with_lookup(
                     cache_read,
                     cache_write,
                     reasoning: 0,
+                    ..Default::default()
                 },
             },
             line_index,
@@ -588,6 +590,7 @@ pub fn parse_devin_desktop_ndjson_with_lookup(
             cache_read: usage.cache_read,
             cache_write: usage.cache_write,
             reasoning: 0,
+            ..Default::default()
         };
         if tokens.total() == 0 {
             return Vec::new();
@@ -220,6 +220,7 @@ pub fn parse_droid_file(path: &Path) -> Vec<UnifiedMessage> {
             cache_read: usage.cache_read_tokens.unwrap_or(0).max(0),
             cache_write: usage.cache_creation_tokens.unwrap_or(0).max(0),
             reasoning: usage.thinking_tokens.unwrap_or(0).max(0),
+            ..Default::default()
         },
         0.0,
     )]
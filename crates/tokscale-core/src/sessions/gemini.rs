@@ -5,15 +5,15 @@
 //! `session-*.jsonl` chat recordings.
 
 use super::utils::{
-    extract_i64, extract_string, file_modified_timestamp_ms, parse_timestamp_value,
-    read_file_or_none,
+    extract_i64, extract_string, file_modified_timestamp_ms, open_session_reader,
+    parse_timestamp_value, read_file_or_none, session_file_extension, session_file_stem,
 };
 use super::UnifiedMessage;
 use crate::TokenBreakdown;
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashMap;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 use std::path::Path;
 
 /// Gemini session structure
@@ -110,15 +110,11 @@ pub fn parse_gemini_file(path: &Path) -> Vec<UnifiedMessage> {
 /// rather than the filename, because the filename stem differs from the wiki id
 /// for these formats.
 pub fn gemini_session_id_for_file(path: &Path) -> Option<String> {
-    let stem = || {
-        path.file_stem()
-            .and_then(|s| s.to_str())
-            .map(|s| s.to_string())
-    };
+    let stem = || session_file_stem(path).map(|s| s.to_string());
 
-    let content = std::fs::read_to_string(path).ok()?;
+    let content = String::from_utf8(read_file_or_none(path)?).ok()?;
 
-    if path.extension().and_then(|s| s.to_str()) == Some("jsonl") {
+    if session_file_extension(path) == Some("jsonl") {
         // Headless JSONL: the `init` line (or any line) carries the real id.
         for line in content.lines() {
             let trimmed = line.trim();
@@ -149,7 +145,7 @@ pub fn gemini_session_id_for_file(path: &Path) -> Option<String> {
 pub(crate) fn parse_gemini_file_with_cache_status(path: &Path) -> GeminiParseResult {
     let fallback_timestamp = file_modified_timestamp_ms(path);
 
-    if path.extension().and_then(|s| s.to_str()) == Some("jsonl") {
+    if session_file_extension(path) == Some("jsonl") {
         return parse_gemini_headless_jsonl(path, fallback_timestamp);
     }
 
@@ -207,11 +203,7 @@ pub(crate) fn parse_gemini_file_with_cache_status(path: &Path) -> GeminiParseRes
 
     let mut bytes = data;
     if let Ok(value) = simd_json::from_slice::<Value>(&mut bytes) {
-        let session_id = path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("unknown")
-            .to_string();
+        let session_id = session_file_stem(path).unwrap_or("unknown").to_string();
         let messages = parse_gemini_headless_value(&value, &session_id, fallback_timestamp);
         if !messages.is_empty() {
             return GeminiParseResult {
@@ -285,6 +277,7 @@ fn build_gemini_token_message(
             cache_read,
             cache_write: 0,
             reasoning: tokens.thoughts.unwrap_or(0).max(0),
+            ..Default::default()
         },
         0.0,
     )
@@ -307,23 +300,15 @@ fn parse_direct_gemini_token_message(
 }
 
 fn parse_gemini_headless_jsonl(path: &Path, fallback_timestamp: i64) -> GeminiParseResult {
-    let file = match std::fs::File::open(path) {
-        Ok(f) => f,
-        Err(_) => {
-            return GeminiParseResult {
-                messages: Vec::new(),
-                cacheable: true,
-            };
-        }
+    let Some(mut reader) = open_session_reader(path) else {
+        return GeminiParseResult {
+            messages: Vec::new(),
+            cacheable: true,
+        };
     };
 
-    let mut session_id = path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("unknown")
-        .to_string();
+    let mut session_id = session_file_stem(path).unwrap_or("unknown").to_string();
     let mut current_model: Option<String> = None;
-    let mut reader = BufReader::new(file);
     let mut messages = Vec::with_capacity(64);
     let mut direct_message_indices: HashMap<String, usize> = HashMap::new();
     let mut line_buffer = Vec::with_capacity(4096);
@@ -492,6 +477,7 @@ fn build_messages_from_stats(
                     cache_read,
                     cache_write: 0,
                     reasoning: usage.reasoning.max(0),
+                    ..Default::default()
                 },
                 0.0,
             )
@@ -207,6 +207,7 @@ pub fn parse_gjc_file(path: &Path) -> Vec<UnifiedMessage> {
             cache_read: usage.cache_read.unwrap_or(0).max(0),
             cache_write: usage.cache_write.unwrap_or(0).max(0),
             reasoning: 0,
+            ..Default::default()
         };
 
         let (cost, cost_source) = embedded_cost(&usage);
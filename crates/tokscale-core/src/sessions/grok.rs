@@ -83,6 +83,7 @@ impl ActiveTurn {
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
             Some(format!("grok:{}:{}", metadata.session_id, self.turn_index)),
@@ -300,6 +301,7 @@ fn append_signals_reconciliation(
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         },
         0.0,
         Some(format!("grok:{}:signals", metadata.session_id)),
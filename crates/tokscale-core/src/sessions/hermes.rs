@@ -149,6 +149,7 @@ pub fn parse_hermes_sqlite(db_path: &Path) -> Vec<UnifiedMessage> {
                     cache_read: cache_read.max(0),
                     cache_write: cache_write.max(0),
                     reasoning: reasoning.max(0),
+                    ..Default::default()
                 },
                 actual_cost.or(estimated_cost).unwrap_or(0.0).max(0.0),
                 Some(HERMES_AGENT_NAME.to_string()),
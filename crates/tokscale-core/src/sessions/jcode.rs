@@ -124,6 +124,7 @@ fn tokens_from_usage(usage: &JcodeTokenUsage) -> TokenBreakdown {
         cache_read,
         cache_write,
         reasoning: usage.reasoning_output_tokens.unwrap_or(0).max(0),
+        ..Default::default()
     }
 }
 
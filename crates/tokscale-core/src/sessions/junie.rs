@@ -247,6 +247,7 @@ fn tokens_from_usage(usage: &Value) -> TokenBreakdown {
             usage,
             &["reasoningTokens", "reasoningOutputTokens", "thinkingTokens"],
         ),
+        ..Default::default()
     }
 }
 
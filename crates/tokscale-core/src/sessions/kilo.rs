@@ -143,6 +143,7 @@ pub fn parse_kilo_sqlite_with_fallback(
                 cache_read: tokens.cache.read.max(0),
                 cache_write: tokens.cache.write.max(0),
                 reasoning: tokens.reasoning.unwrap_or(0).max(0),
+                ..Default::default()
             },
             msg.cost.unwrap_or(0.0).max(0.0),
             agent,
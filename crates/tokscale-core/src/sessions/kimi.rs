@@ -74,6 +74,7 @@ impl TokenUsage {
             cache_write,
             // Kimi wire protocols do not expose reasoning tokens; all reasoning included in output
             reasoning: 0,
+            ..Default::default()
         })
     }
 }
@@ -285,6 +285,7 @@ pub fn parse_kiro_file(path: &Path) -> Vec<UnifiedMessage> {
                     cache_read: 0,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 0.0,
                 Some(format!("{}:{}", session_id, index)),
@@ -650,6 +651,7 @@ fn parse_kiro_ide_session_file(path: &Path) -> Vec<UnifiedMessage> {
                         cache_read: 0,
                         cache_write: 0,
                         reasoning: 0,
+                        ..Default::default()
                     },
                     0.0,
                     Some(format!("{}:ide:{}", session_id, index)),
@@ -700,6 +702,7 @@ fn parse_kiro_ide_session_file(path: &Path) -> Vec<UnifiedMessage> {
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         },
         0.0,
         Some(format!("{}:ide-session", session_id)),
@@ -946,6 +949,7 @@ fn parse_kiro_global_storage_file(path: &Path) -> Vec<UnifiedMessage> {
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         },
         0.0,
         Some(dedup_key),
@@ -1091,6 +1095,7 @@ fn try_parse_kiro_execution_file(value: &Value, path: &Path) -> Option<Vec<Unifi
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         },
         0.0,
         Some(format!("execution:{}", execution_id)),
@@ -1180,6 +1185,7 @@ fn try_parse_kiro_workspace_session(
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         },
         0.0,
         Some(format!("{}:workspace-session", session_id)),
@@ -1385,6 +1391,7 @@ pub fn parse_kiro_sqlite(db_path: &Path) -> Vec<UnifiedMessage> {
                     cache_read: 0,
                     cache_write: 0,
                     reasoning: 0,
+                    ..Default::default()
                 },
                 0.0,
                 Some(format!("{}:{}", conversation_id, index)),
@@ -1668,6 +1675,7 @@ not valid json at all
                 cache_read: 0,
                 cache_write: 0,
                 reasoning: 0,
+                ..Default::default()
             },
             0.0,
             Some(dedup_key.to_string()),
@@ -297,6 +297,7 @@ pub fn parse_micode_sqlite(db_path: &Path) -> Vec<UnifiedMessage> {
                 cache_read,
                 cache_write,
                 reasoning,
+                ..Default::default()
             },
             cost,
             agent,
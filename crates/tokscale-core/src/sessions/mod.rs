@@ -2,6 +2,7 @@
 //!
 //! Each client has its own parser that converts to a unified message format.
 
+pub mod aider;
 pub mod amp;
 pub mod antigravity;
 pub mod antigravity_cli;
@@ -47,6 +48,16 @@ pub mod zcode;
 pub mod zed;
 
 use crate::TokenBreakdown;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Negative token buckets zeroed by [`UnifiedMessage`] constructors since the
+/// last [`take_clamped_token_count`].
+static CLAMPED_TOKEN_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Return and reset the number of negative token buckets clamped to zero.
+pub fn take_clamped_token_count() -> usize {
+    CLAMPED_TOKEN_COUNT.swap(0, Ordering::Relaxed)
+}
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -71,6 +82,11 @@ pub struct UnifiedMessage {
     pub cost: f64,
     #[serde(default)]
     pub cost_source: CostSource,
+    /// The cost the client recorded itself (Amp credits, Cursor's usage
+    /// CSV, ...) when pricing replaced it with an estimate. Set while
+    /// pricing, so it is never written to the message cache.
+    #[serde(skip)]
+    pub client_cost: Option<f64>,
     #[serde(default)]
     pub duration_ms: Option<i64>,
     #[serde(default = "default_message_count")]
@@ -87,6 +103,17 @@ pub struct UnifiedMessage {
     /// Used to count user interaction turns (as opposed to API message count).
     #[serde(default)]
     pub is_turn_start: bool,
+    /// Size of the request the client sent, when its logs record one
+    /// (Cursor's usage CSV). Useful for egress estimates next to tokens.
+    #[serde(default)]
+    pub request_bytes: Option<i64>,
+    /// Size of the response, recorded alongside `request_bytes`.
+    #[serde(default)]
+    pub response_bytes: Option<i64>,
+    /// The client's own token total for this message (OpenClaw's
+    /// `totalTokens`), kept so `--reconcile` can check the parsed buckets.
+    #[serde(default)]
+    pub reported_total_tokens: Option<i64>,
 }
 
 const fn default_message_count() -> i32 {
@@ -346,6 +373,11 @@ impl UnifiedMessage {
         dedup_key: Option<String>,
     ) -> Self {
         let date = timestamp_to_date(timestamp);
+        let mut tokens = tokens;
+        let clamped = tokens.clamp_negative();
+        if clamped > 0 {
+            CLAMPED_TOKEN_COUNT.fetch_add(clamped, Ordering::Relaxed);
+        }
         Self {
             client: client.into(),
             model_id: model_id.into(),
@@ -358,12 +390,16 @@ impl UnifiedMessage {
             tokens,
             cost,
             cost_source: CostSource::Unknown,
+            client_cost: None,
             duration_ms: None,
             message_count: default_message_count(),
             agent,
             dedup_key,
             session_title: None,
             is_turn_start: false,
+            request_bytes: None,
+            response_bytes: None,
+            reported_total_tokens: None,
         }
     }
 
@@ -459,6 +495,28 @@ mod tests {
     use super::*;
     use chrono::FixedOffset;
 
+    #[test]
+    fn unified_message_clamps_negative_token_buckets() {
+        let mut tokens = TokenBreakdown {
+            input: -10,
+            output: 5,
+            cache_read: -1,
+            cache_write: 0,
+            reasoning: i64::MIN,
+            cache_write_1h: -2,
+        };
+        let msg = UnifiedMessage::new("claude", "m", "p", "s", 0, tokens.clone(), 0.0);
+        assert_eq!(
+            msg.tokens,
+            TokenBreakdown {
+                output: 5,
+                ..TokenBreakdown::default()
+            }
+        );
+        assert_eq!(tokens.clamp_negative(), 4);
+        assert_eq!(tokens.clamp_negative(), 0);
+    }
+
     #[test]
     fn warp_cache_parser_preserves_requests_and_spend_without_tokens() {
         let file = tempfile::NamedTempFile::new().unwrap();
@@ -556,6 +614,7 @@ mod tests {
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         };
 
         let msg = UnifiedMessage::new(
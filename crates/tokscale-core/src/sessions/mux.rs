@@ -124,6 +124,7 @@ pub fn parse_mux_file(path: &Path) -> Vec<UnifiedMessage> {
                     cache_read: cached,
                     cache_write: cache_create,
                     reasoning,
+                    ..Default::default()
                 },
                 source_cost,
                 dedup_key,
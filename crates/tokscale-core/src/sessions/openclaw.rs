@@ -63,7 +63,6 @@ struct OpenClawUsage {
     #[serde(rename = "cacheWrite")]
     cache_write: Option<i64>,
     #[serde(rename = "totalTokens")]
-    #[allow(dead_code)]
     total_tokens: Option<i64>,
     cost: Option<OpenClawCost>,
 }
@@ -228,7 +227,7 @@ fn parse_openclaw_session(session_path: &Path, session_id: &str) -> Vec<UnifiedM
                     let timestamp = msg.timestamp.unwrap_or(file_mtime_ms);
                     let cost = usage.cost.and_then(|c| c.total).unwrap_or(0.0);
 
-                    messages.push(UnifiedMessage::new(
+                    let mut message = UnifiedMessage::new(
                         "openclaw",
                         model,
                         provider,
@@ -240,9 +239,12 @@ fn parse_openclaw_session(session_path: &Path, session_id: &str) -> Vec<UnifiedM
                             cache_read: usage.cache_read.unwrap_or(0).max(0),
                             cache_write: usage.cache_write.unwrap_or(0).max(0),
                             reasoning: 0,
+                            ..Default::default()
                         },
                         cost.max(0.0),
-                    ));
+                    );
+                    message.reported_total_tokens = usage.total_tokens.filter(|t| *t >= 0);
+                    messages.push(message);
                 }
             }
             _ => {}
@@ -282,6 +284,7 @@ mod tests {
         assert_eq!(messages[0].tokens.output, 50);
         assert_eq!(messages[0].tokens.cache_read, 200);
         assert_eq!(messages[0].cost, 0.05);
+        assert_eq!(messages[0].reported_total_tokens, Some(350));
     }
 
     #[test]
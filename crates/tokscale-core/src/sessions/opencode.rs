@@ -4,7 +4,7 @@
 //! - SQLite database (OpenCode 1.2+): ~/.local/share/opencode/opencode.db
 //! - Legacy JSON files: ~/.local/share/opencode/storage/message/
 
-use super::utils::{open_readonly_sqlite, read_file_or_none};
+use super::utils::{open_readonly_sqlite, read_file_or_none, session_file_stem};
 use super::{
     normalize_opencode_agent_name, normalize_workspace_key, workspace_label_from_key,
     UnifiedMessage,
@@ -232,11 +232,9 @@ pub fn parse_opencode_file(path: &Path) -> Option<UnifiedMessage> {
     let session_id = msg.session_id.unwrap_or_else(|| "unknown".to_string());
 
     // Use message ID from JSON or derive from filename for deduplication
-    let dedup_key = msg.id.or_else(|| {
-        path.file_stem()
-            .and_then(|s| s.to_str())
-            .map(|s| s.to_string())
-    });
+    let dedup_key = msg
+        .id
+        .or_else(|| session_file_stem(path).map(|s| s.to_string()));
     let cost = embedded_cost(msg.cost);
 
     let mut unified = UnifiedMessage::new_with_agent(
@@ -251,6 +249,7 @@ pub fn parse_opencode_file(path: &Path) -> Option<UnifiedMessage> {
             cache_read: tokens.cache.read.max(0),
             cache_write: tokens.cache.write.max(0),
             reasoning: tokens.reasoning.unwrap_or(0).max(0),
+            ..Default::default()
         },
         cost,
         agent,
@@ -363,6 +362,7 @@ impl OpenCodeSqliteAccumulator {
                 cache_read,
                 cache_write,
                 reasoning,
+                ..Default::default()
             },
             cost,
             agent,
@@ -563,7 +563,7 @@ fn migration_cache_path() -> std::path::PathBuf {
 }
 
 fn legacy_migration_cache_paths() -> Vec<std::path::PathBuf> {
-    if crate::paths::is_config_dir_overridden() {
+    if crate::paths::is_cache_dir_overridden() {
         return Vec::new();
     }
 
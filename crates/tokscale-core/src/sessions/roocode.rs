@@ -3,12 +3,17 @@
 //! Parses task-based logs from VS Code globalStorage directories:
 //! - tasks/<taskId>/ui_messages.json
 //! - tasks/<taskId>/api_conversation_history.json
+//!
+//! Each `api_req_started` entry is one API request, identified by its `ts`.
+//! Roo rewrites the entry as the request streams, so a task can hold the
+//! same request more than once; the last copy wins.
 
 use super::utils::{extract_i64, parse_timestamp_str, read_file_or_none};
 use super::UnifiedMessage;
-use crate::TokenBreakdown;
+use crate::{provider_identity, TokenBreakdown};
 use serde::Deserialize;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Deserialize)]
@@ -38,7 +43,8 @@ pub(crate) fn parse_roo_kilo_file(path: &Path, source: &str) -> Vec<UnifiedMessa
     let session_id = extract_session_id(path);
     let (model_id, agent) = read_task_metadata(path);
 
-    let mut messages = Vec::new();
+    let mut messages: Vec<UnifiedMessage> = Vec::new();
+    let mut index_by_request: HashMap<String, usize> = HashMap::new();
     for entry in entries {
         if entry.entry_type.as_deref() != Some("say")
             || entry.say.as_deref() != Some("api_req_started")
@@ -61,9 +67,9 @@ pub(crate) fn parse_roo_kilo_file(path: &Path, source: &str) -> Vec<UnifiedMessa
             None => continue,
         };
 
-        let provider = provider_from_api_protocol(payload.api_protocol.as_deref());
+        let provider = provider_from_api_protocol(payload.api_protocol.as_deref(), &model_id);
 
-        messages.push(UnifiedMessage::new_with_agent(
+        let mut message = UnifiedMessage::new_with_agent(
             source,
             model_id.clone(),
             provider,
@@ -75,10 +81,21 @@ pub(crate) fn parse_roo_kilo_file(path: &Path, source: &str) -> Vec<UnifiedMessa
                 cache_read: payload.cache_reads,
                 cache_write: payload.cache_writes,
                 reasoning: 0,
+                ..Default::default()
             },
             payload.cost,
             agent.clone(),
-        ));
+        );
+        let dedup_key = format!("{source}:{session_id}:{timestamp}");
+        message.dedup_key = Some(dedup_key.clone());
+
+        match index_by_request.get(&dedup_key) {
+            Some(&index) => messages[index] = message,
+            None => {
+                index_by_request.insert(dedup_key, messages.len());
+                messages.push(message);
+            }
+        }
     }
 
     messages
@@ -217,10 +234,13 @@ fn extract_f64(value: Option<&Value>) -> Option<f64> {
     })
 }
 
-fn provider_from_api_protocol(api_protocol: Option<&str>) -> String {
+/// The request's `apiProtocol` when present, else the provider implied by
+/// the model id.
+fn provider_from_api_protocol(api_protocol: Option<&str>, model_id: &str) -> String {
     api_protocol
         .map(str::trim)
         .filter(|value| !value.is_empty())
+        .or_else(|| provider_identity::inferred_provider_from_model(model_id))
         .unwrap_or("unknown")
         .to_string()
 }
@@ -361,6 +381,57 @@ after"#;
         assert!(messages.is_empty());
     }
 
+    #[test]
+    fn test_parse_roocode_multi_request_task_dedups_repeated_requests() {
+        let dir = TempDir::new().unwrap();
+        let ui_messages = r#"[
+  {
+    "type": "say",
+    "say": "api_req_started",
+    "ts": 1771416000000,
+    "text": "{\"cost\":0.01,\"tokensIn\":100,\"tokensOut\":0}"
+  },
+  {
+    "type": "say",
+    "say": "api_req_started",
+    "ts": 1771416000000,
+    "text": "{\"cost\":0.02,\"tokensIn\":100,\"tokensOut\":40,\"cacheReads\":10}"
+  },
+  {
+    "type": "say",
+    "say": "api_req_started",
+    "ts": 1771416060000,
+    "text": "{\"cost\":0.03,\"tokensIn\":200,\"tokensOut\":60,\"cacheReads\":30,\"cacheWrites\":5}"
+  },
+  {
+    "type": "say",
+    "say": "api_req_started",
+    "ts": 1771416120000,
+    "text": "{\"cost\":0.04,\"tokensIn\":300,\"tokensOut\":70}"
+  }
+]"#;
+        let history = r#"<environment_details>
+<model>claude-sonnet-4-5</model>
+</environment_details>"#;
+        let path = setup_task(&dir, "task-multi", ui_messages, Some(history));
+
+        let messages = parse_roocode_file(&path);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(
+            messages[0].dedup_key.as_deref(),
+            Some("roocode:task-multi:1771416000000")
+        );
+        assert_eq!(messages[0].tokens.output, 40);
+        assert!(messages.iter().all(|m| m.provider_id == "anthropic"));
+
+        let input: i64 = messages.iter().map(|m| m.tokens.input).sum();
+        let output: i64 = messages.iter().map(|m| m.tokens.output).sum();
+        let cache_read: i64 = messages.iter().map(|m| m.tokens.cache_read).sum();
+        let cost: f64 = messages.iter().map(|m| m.cost).sum();
+        assert_eq!((input, output, cache_read), (600, 170, 40));
+        assert!((cost - 0.09).abs() < 1e-9);
+    }
+
     #[test]
     fn test_extract_model_and_agent_prefers_slug_then_name() {
         let content = r#"
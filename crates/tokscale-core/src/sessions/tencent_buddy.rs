@@ -118,6 +118,7 @@ impl BuddyUsage {
                 self.completion_thinking_tokens_camel,
                 self.reasoning_tokens,
             ]),
+            ..Default::default()
         };
 
         (tokens.total() > 0).then_some(tokens)
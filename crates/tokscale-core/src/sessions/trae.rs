@@ -102,6 +102,7 @@ fn parse_session(client: &str, session: &serde_json::Value) -> Option<UnifiedMes
             cache_read,
             cache_write,
             reasoning: 0,
+            ..Default::default()
         },
         cost,
         dedup_key,
@@ -196,6 +197,29 @@ mod tests {
         assert!(msgs.is_empty());
     }
 
+    #[test]
+    fn test_negative_token_counts_are_treated_as_zero() {
+        let json = serde_json::json!([{
+            "model_name": "GPT-5.4",
+            "session_id": "corrupt-session",
+            "usage_time": 1776000000,
+            "dollar_float": 0.5,
+            "extra_info": {
+                "input_token": -1000,
+                "output_token": 500,
+                "cache_read_token": -200,
+                "cache_write_token": 0
+            }
+        }]);
+        let f = write_fixture(&json.to_string());
+        let msgs = parse_trae_file("trae", f.path());
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].tokens.input, 0);
+        assert_eq!(msgs[0].tokens.output, 500);
+        assert_eq!(msgs[0].tokens.cache_read, 0);
+        assert_eq!(msgs[0].tokens.total(), 500);
+    }
+
     #[test]
     fn test_normalize_model_names() {
         assert_eq!(normalize_trae_model("GPT-5.4"), "gpt-5.4");
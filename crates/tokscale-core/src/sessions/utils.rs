@@ -2,6 +2,7 @@
 
 use rusqlite::{Connection, OpenFlags};
 use serde_json::Value;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 use std::time::SystemTime;
 
@@ -94,8 +95,51 @@ pub(crate) fn open_readonly_sqlite(path: &Path) -> Option<Connection> {
 
 /// Read a file into bytes, returning `None` on any I/O error instead of propagating.
 /// Used by parsers that treat missing/unreadable session files as "no data".
+/// Gzip-compressed files (`*.json.gz`, `*.jsonl.gz`) come back decompressed.
 pub(crate) fn read_file_or_none(path: &Path) -> Option<Vec<u8>> {
-    std::fs::read(path).ok()
+    if !is_gzip_path(path) {
+        return std::fs::read(path).ok();
+    }
+    let mut data = Vec::new();
+    flate2::read::MultiGzDecoder::new(std::fs::File::open(path).ok()?)
+        .read_to_end(&mut data)
+        .ok()?;
+    Some(data)
+}
+
+/// True for gzip-compressed session logs, which end in `.gz` after their
+/// usual `.json` / `.jsonl` extension.
+pub(crate) fn is_gzip_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gz"))
+}
+
+/// Open a session log for line-by-line reading, decompressing gzip files on
+/// the fly. `None` when the file can't be opened.
+pub(crate) fn open_session_reader(path: &Path) -> Option<Box<dyn BufRead>> {
+    let file = std::fs::File::open(path).ok()?;
+    if is_gzip_path(path) {
+        Some(Box::new(BufReader::new(flate2::read::MultiGzDecoder::new(
+            file,
+        ))))
+    } else {
+        Some(Box::new(BufReader::new(file)))
+    }
+}
+
+/// [`Path::file_stem`] that looks through a trailing `.gz`, so
+/// `abc.jsonl.gz` and `abc.jsonl` both give `abc`.
+pub(crate) fn session_file_stem(path: &Path) -> Option<&str> {
+    let name = path.file_name()?.to_str()?;
+    let name = name.strip_suffix(".gz").unwrap_or(name);
+    Path::new(name).file_stem()?.to_str()
+}
+
+/// [`Path::extension`] that looks through a trailing `.gz`.
+pub(crate) fn session_file_extension(path: &Path) -> Option<&str> {
+    let name = path.file_name()?.to_str()?;
+    let name = name.strip_suffix(".gz").unwrap_or(name);
+    Path::new(name).extension()?.to_str()
 }
 
 /// Back-calculate a start anchor from a recorded end timestamp and an elapsed
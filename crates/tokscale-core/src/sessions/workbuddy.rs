@@ -142,6 +142,7 @@ fn usage_row_to_message(row: WorkBuddyUsageRow) -> UnifiedMessage {
             cache_read: 0,
             cache_write: 0,
             reasoning: 0,
+            ..Default::default()
         },
         0.0,
     );
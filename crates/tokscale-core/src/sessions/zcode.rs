@@ -96,6 +96,7 @@ impl ZcodeUsage {
             cache_read: raw_cache_read,
             cache_write: raw_cache_write,
             reasoning: raw_reasoning,
+            ..Default::default()
         })
     }
 }
@@ -182,6 +183,7 @@ pub fn parse_zcode_file(path: &Path) -> Vec<UnifiedMessage> {
                         cache_read: 0,
                         cache_write: 0,
                         reasoning: 0,
+                        ..Default::default()
                     }
                 };
 
@@ -465,6 +467,7 @@ pub fn parse_zcode_sqlite(db_path: &Path) -> Vec<UnifiedMessage> {
             cache_read: raw_cache_read.max(0),
             cache_write: raw_cache_write.max(0),
             reasoning: raw_reasoning.max(0),
+            ..Default::default()
         };
 
         if tokens.total() == 0 {
@@ -0,0 +1,206 @@
+//! SQLite sink for raw parsed messages (`tokscale export sqlite`).
+//!
+//! Writes one row per [`UnifiedMessage`] into a `messages` table so usage can
+//! be explored with ad-hoc SQL. Values are written as parsed: model ids are
+//! not alias-folded and nothing is aggregated.
+
+use crate::UnifiedMessage;
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+
+const SCHEMA: &str = "
+CREATE TABLE messages (
+    client      TEXT    NOT NULL,
+    model_id    TEXT    NOT NULL,
+    provider_id TEXT    NOT NULL,
+    session_id  TEXT    NOT NULL,
+    timestamp   INTEGER NOT NULL,
+    date        TEXT    NOT NULL,
+    input       INTEGER NOT NULL,
+    output      INTEGER NOT NULL,
+    cache_read  INTEGER NOT NULL,
+    cache_write INTEGER NOT NULL,
+    reasoning   INTEGER NOT NULL,
+    cost        REAL    NOT NULL,
+    agent       TEXT
+);
+CREATE INDEX idx_messages_date ON messages (date);
+CREATE INDEX idx_messages_model_id ON messages (model_id);
+";
+
+/// Write `messages` into a fresh SQLite database at `path` and return the
+/// number of rows written.
+///
+/// The database is built in a sibling temp file and moved into place once
+/// complete, so an existing file at `path` is replaced atomically and a
+/// failed export never leaves a half-written database behind.
+pub fn write_messages_sqlite<I>(path: &Path, messages: I) -> Result<usize, String>
+where
+    I: IntoIterator<Item = UnifiedMessage>,
+{
+    let tmp_path = temp_path_for(path);
+    // A leftover temp file from an interrupted run would otherwise make
+    // `CREATE TABLE` fail.
+    let _ = std::fs::remove_file(&tmp_path);
+
+    let result = write_to(&tmp_path, messages).and_then(|rows| {
+        crate::fs_atomic::replace_file(&tmp_path, path)
+            .map(|()| rows)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    });
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+fn write_to<I>(path: &Path, messages: I) -> Result<usize, String>
+where
+    I: IntoIterator<Item = UnifiedMessage>,
+{
+    let sql_err = |e: rusqlite::Error| format!("SQLite export failed: {}", e);
+
+    let mut conn = Connection::open(path).map_err(sql_err)?;
+    conn.execute_batch(SCHEMA).map_err(sql_err)?;
+
+    let tx = conn.transaction().map_err(sql_err)?;
+    let mut rows = 0;
+    {
+        let mut insert = tx
+            .prepare(
+                "INSERT INTO messages (
+                    client, model_id, provider_id, session_id, timestamp, date,
+                    input, output, cache_read, cache_write, reasoning, cost, agent
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            )
+            .map_err(sql_err)?;
+        for msg in messages {
+            insert
+                .execute(params![
+                    msg.client,
+                    msg.model_id,
+                    msg.provider_id,
+                    msg.session_id,
+                    msg.timestamp,
+                    msg.date,
+                    msg.tokens.input,
+                    msg.tokens.output,
+                    msg.tokens.cache_read,
+                    msg.tokens.cache_write,
+                    msg.tokens.reasoning,
+                    msg.cost,
+                    msg.agent,
+                ])
+                .map_err(sql_err)?;
+            rows += 1;
+        }
+    }
+    tx.commit().map_err(sql_err)?;
+    Ok(rows)
+}
+
+fn temp_path_for(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TokenBreakdown;
+
+    fn message(client: &str, model: &str, date: &str, agent: Option<&str>) -> UnifiedMessage {
+        let mut msg = UnifiedMessage::new(
+            client,
+            model,
+            "anthropic",
+            "session-a",
+            1_733_011_200_000,
+            TokenBreakdown {
+                input: 100,
+                output: 50,
+                cache_read: 10,
+                cache_write: 5,
+                reasoning: 2,
+                ..Default::default()
+            },
+            0.25,
+        );
+        msg.date = date.to_string();
+        msg.agent = agent.map(str::to_string);
+        msg
+    }
+
+    #[test]
+    fn writes_messages_table_with_indexes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("usage.db");
+
+        let rows = write_messages_sqlite(
+            &path,
+            vec![
+                message("claude", "claude-sonnet-4-5", "2024-12-01", None),
+                message("claude", "claude-sonnet-4-5", "2024-12-02", Some("build")),
+                message("opencode", "claude-opus-4-1", "2024-12-02", None),
+            ],
+        )
+        .unwrap();
+        assert_eq!(rows, 3);
+
+        let conn = Connection::open(&path).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM messages", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 3);
+
+        let (input, cost, agent): (i64, f64, Option<String>) = conn
+            .query_row(
+                "SELECT SUM(input), SUM(cost), MAX(agent) FROM messages WHERE date = '2024-12-02'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(input, 200);
+        assert!((cost - 0.5).abs() < 1e-9);
+        assert_eq!(agent.as_deref(), Some("build"));
+
+        let mut stmt = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'index' ORDER BY name")
+            .unwrap();
+        let indexes: Vec<String> = stmt
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(indexes, vec!["idx_messages_date", "idx_messages_model_id"]);
+    }
+
+    #[test]
+    fn replaces_existing_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("usage.db");
+
+        write_messages_sqlite(
+            &path,
+            vec![
+                message("claude", "claude-sonnet-4-5", "2024-12-01", None),
+                message("claude", "claude-sonnet-4-5", "2024-12-02", None),
+            ],
+        )
+        .unwrap();
+        let rows = write_messages_sqlite(
+            &path,
+            vec![message("claude", "claude-sonnet-4-5", "2024-12-03", None)],
+        )
+        .unwrap();
+        assert_eq!(rows, 1);
+
+        let conn = Connection::open(&path).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM messages", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+        assert!(!dir.path().join("usage.db.tmp").exists());
+    }
+}
@@ -0,0 +1,413 @@
+//! Per-file parse diagnostics for `tokscale validate`.
+//!
+//! The session parsers are deliberately lenient: a malformed line or record
+//! is skipped rather than failing the whole report. That is the right call
+//! for reporting, but it hides corrupt logs. This module re-runs each scanned
+//! file through its client's parser in isolation and, when a file yields no
+//! messages, looks for the first structural error so it can be surfaced.
+//! JSONL files also report how many lines were skipped, and whether the last
+//! one was cut off by a write still in progress; such a tail is expected and
+//! is never treated as an error on its own.
+//!
+//! Only file-based sources are covered. Clients that read a shared SQLite
+//! database (OpenCode's `opencode.db`, Goose, Zed, ...) are not part of the
+//! per-file scan results and are not linted here.
+
+use crate::clients::ClientId;
+use crate::{scanner, sessions, UnifiedMessage};
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default)]
+pub struct ValidateOptions {
+    pub home_dir: Option<String>,
+    pub use_env_roots: bool,
+    pub clients: Option<Vec<String>>,
+    pub scanner_settings: scanner::ScannerSettings,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceStatus {
+    /// The file yielded at least one message.
+    Parsed,
+    /// The file is well-formed but holds no usage (e.g. a session that never
+    /// reached the model).
+    Empty,
+    /// The file yielded no messages and could not be read or parsed.
+    Failed,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceValidation {
+    pub client: String,
+    pub path: PathBuf,
+    pub messages: usize,
+    pub status: SourceStatus,
+    /// First parse error, set only for [`SourceStatus::Failed`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Non-empty JSONL lines that are not valid JSON and were skipped.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub skipped_lines: usize,
+    /// The last JSONL line has no newline and does not parse, i.e. it was
+    /// still being written. Counted in `skipped_lines`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated_tail: bool,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+/// Line-by-line result of checking a JSONL document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonlScan {
+    pub skipped_lines: usize,
+    pub truncated_tail: bool,
+    /// First malformed line other than a truncated tail.
+    pub first_error: Option<String>,
+}
+
+/// Check every non-empty line of `content` as JSON. A malformed last line
+/// without a trailing newline is reported as a truncated tail rather than an
+/// error.
+pub fn scan_jsonl(content: &str) -> JsonlScan {
+    let mut scan = JsonlScan::default();
+    let ends_with_newline = content.ends_with('\n');
+    let mut lines = content.lines().enumerate().peekable();
+    while let Some((index, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Err(error) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        scan.skipped_lines += 1;
+        if lines.peek().is_none() && !ends_with_newline {
+            scan.truncated_tail = true;
+        } else if scan.first_error.is_none() {
+            scan.first_error = Some(format!("line {}: {}", index + 1, error));
+        }
+    }
+    scan
+}
+
+fn is_jsonl_name(name: &str) -> bool {
+    name.contains(".jsonl") || name.ends_with(".ndjson")
+}
+
+/// [`scan_jsonl`] for a file on disk; `None` for non-JSONL or unreadable
+/// files.
+fn scan_jsonl_file(path: &Path) -> Option<JsonlScan> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    if !is_jsonl_name(&name) {
+        return None;
+    }
+    let content = std::fs::read_to_string(path).ok()?;
+    Some(scan_jsonl(
+        content.strip_prefix('\u{feff}').unwrap_or(&content),
+    ))
+}
+
+/// Validate every session file the scanner finds for `options.clients`
+/// (all clients when `None`). Results are sorted by client, then path.
+pub fn validate_sources(options: &ValidateOptions) -> Result<Vec<SourceValidation>, String> {
+    let home_dir = crate::get_home_dir_string(&options.home_dir)?;
+    let clients: Vec<String> = options.clients.clone().unwrap_or_else(|| {
+        ClientId::iter()
+            .map(|client| client.as_str().to_string())
+            .collect()
+    });
+    let scan_result = scanner::scan_all_clients_with_scanner_settings(
+        &home_dir,
+        &clients,
+        options.use_env_roots,
+        &options.scanner_settings,
+    );
+
+    let claude_home = PathBuf::from(&home_dir);
+    let mut results: Vec<SourceValidation> = ClientId::iter()
+        .flat_map(|client| {
+            scan_result
+                .get(client)
+                .iter()
+                .map(move |path| (client, path))
+        })
+        .par_bridge()
+        .filter_map(|(client, path)| {
+            let messages = parse_source_file(client, path, &claude_home)?.len();
+            let (status, error) = if messages > 0 {
+                (SourceStatus::Parsed, None)
+            } else {
+                match diagnose_source_file(path) {
+                    Ok(()) => (SourceStatus::Empty, None),
+                    Err(error) => (SourceStatus::Failed, Some(error)),
+                }
+            };
+            let scan = scan_jsonl_file(path).unwrap_or_default();
+            Some(SourceValidation {
+                client: client.as_str().to_string(),
+                path: path.clone(),
+                messages,
+                status,
+                error,
+                skipped_lines: scan.skipped_lines,
+                truncated_tail: scan.truncated_tail,
+            })
+        })
+        .collect();
+    results.sort_by(|a, b| a.client.cmp(&b.client).then_with(|| a.path.cmp(&b.path)));
+    Ok(results)
+}
+
+/// Run the parser the report pipeline uses for `client` on a single file,
+/// without the source cache, pricing, or cross-file dedup. `None` for
+/// database-backed clients, whose scan entries are extra database paths
+/// rather than session files.
+fn parse_source_file(
+    client: ClientId,
+    path: &Path,
+    claude_home: &Path,
+) -> Option<Vec<UnifiedMessage>> {
+    use sessions::*;
+
+    let messages = match client {
+        ClientId::OpenCode => opencode::parse_opencode_file(path).into_iter().collect(),
+        ClientId::Claude => claudecode::parse_claude_file_with_home(path, Some(claude_home)),
+        ClientId::Codex => codex::parse_codex_file(path),
+        ClientId::Cursor => cursor::parse_cursor_file(path),
+        ClientId::Gemini => gemini::parse_gemini_file(path),
+        ClientId::Amp => amp::parse_amp_file(path),
+        ClientId::Droid => droid::parse_droid_file(path),
+        ClientId::OpenClaw => openclaw::parse_openclaw_transcript(path),
+        ClientId::Pi => pi::parse_pi_file(path),
+        ClientId::Kimi if kimi::is_kimi_code_path(path) => kimi::parse_kimi_code_file(path),
+        ClientId::Kimi => kimi::parse_kimi_file(path),
+        ClientId::Qwen => qwen::parse_qwen_file(path),
+        ClientId::RooCode => roocode::parse_roocode_file(path),
+        ClientId::KiloCode => kilocode::parse_kilocode_file(path),
+        ClientId::Mux => mux::parse_mux_file(path),
+        ClientId::Copilot => copilot::parse_copilot_file(path),
+        ClientId::Codebuff => codebuff::parse_codebuff_file(path),
+        ClientId::Antigravity => antigravity::parse_antigravity_file(path),
+        ClientId::Kiro => kiro::parse_kiro_file(path),
+        ClientId::Trae => trae::parse_trae_file("trae", path),
+        ClientId::Warp => warp::parse_warp_file(path),
+        ClientId::Cline => cline::parse_cline_file(path),
+        ClientId::Gjc => gjc::parse_gjc_file(path),
+        ClientId::Grok => grok::parse_grok_updates_file(path),
+        ClientId::Jcode => jcode::parse_jcode_file(path),
+        ClientId::CommandCode => commandcode::parse_commandcode_file(path),
+        ClientId::AntigravityCli => antigravity_cli::parse_antigravity_cli_file(path),
+        ClientId::Junie => junie::parse_junie_file(path),
+        ClientId::Zcode => zcode::parse_zcode_file(path),
+        ClientId::OpenCodeReview => opencodereview::parse_opencodereview_file(path),
+        ClientId::CodeBuddy => codebuddy::parse_codebuddy_file(path),
+        ClientId::WorkBuddy => workbuddy::parse_workbuddy_file(path),
+        ClientId::DevinDesktop => devin::parse_devin_desktop_ndjson(path),
+        ClientId::Aider => aider::parse_aider_sources(&[path.to_path_buf()]),
+        ClientId::Kilo
+        | ClientId::Crush
+        | ClientId::Hermes
+        | ClientId::Goose
+        | ClientId::Zed
+        | ClientId::MiMoCode
+        | ClientId::DevinCli => return None,
+    };
+    Some(messages)
+}
+
+/// Find the first structural error in a session file.
+///
+/// JSONL-style files are checked line by line and report the first line that
+/// is not valid JSON, ignoring a truncated last line; `.json` files must parse as a single document; SQLite
+/// files must open as a database. Other formats only need to be readable.
+pub fn diagnose_source_file(path: &Path) -> Result<(), String> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    if name.ends_with(".db") || name.ends_with(".sqlite") {
+        return diagnose_sqlite_file(path);
+    }
+
+    let bytes = std::fs::read(path).map_err(|e| format!("failed to read file: {}", e))?;
+    let content = String::from_utf8(bytes).map_err(|e| format!("not valid UTF-8: {}", e))?;
+    let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+
+    if is_jsonl_name(&name) {
+        if let Some(error) = scan_jsonl(content).first_error {
+            return Err(error);
+        }
+    } else if name.ends_with(".json") {
+        if content.trim().is_empty() {
+            return Ok(());
+        }
+        serde_json::from_str::<serde_json::Value>(content).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn diagnose_sqlite_file(path: &Path) -> Result<(), String> {
+    let conn =
+        rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("failed to open database: {}", e))?;
+    conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    })
+    .map(|_| ())
+    .map_err(|e| format!("failed to read database: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnose_reports_first_malformed_jsonl_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(&path, "{\"type\":\"user\"}\n\n{\"type\":\n{\"also\":bad}\n").unwrap();
+
+        let error = diagnose_source_file(&path).unwrap_err();
+        assert!(error.starts_with("line 3:"), "{error}");
+    }
+
+    #[test]
+    fn scan_jsonl_separates_a_truncated_tail_from_corrupt_lines() {
+        let complete = "{\"a\":1}\n{\"b\":2}\n";
+        assert_eq!(scan_jsonl(complete), JsonlScan::default());
+
+        let truncated = scan_jsonl("{\"a\":1}\n{\"b\":2}\n{\"c\":");
+        assert_eq!(truncated.skipped_lines, 1);
+        assert!(truncated.truncated_tail);
+        assert_eq!(truncated.first_error, None);
+
+        // The same bad line followed by a newline is corruption, not a write
+        // in progress.
+        let corrupt = scan_jsonl("{\"a\":1}\n{bad\n{\"c\":");
+        assert_eq!(corrupt.skipped_lines, 2);
+        assert!(corrupt.truncated_tail);
+        assert!(corrupt.first_error.unwrap().starts_with("line 2:"));
+    }
+
+    #[test]
+    fn diagnose_accepts_well_formed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let jsonl = dir.path().join("session.jsonl");
+        std::fs::write(&jsonl, "{\"a\":1}\n{\"b\":2}\n").unwrap();
+        let json = dir.path().join("session.json");
+        std::fs::write(&json, "\u{feff}{\"messages\":[]}").unwrap();
+        let csv = dir.path().join("usage.csv");
+        std::fs::write(&csv, "Date,Model\n").unwrap();
+
+        assert_eq!(diagnose_source_file(&jsonl), Ok(()));
+        assert_eq!(diagnose_source_file(&json), Ok(()));
+        assert_eq!(diagnose_source_file(&csv), Ok(()));
+    }
+
+    #[test]
+    fn diagnose_rejects_truncated_json_and_bad_databases() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("session.json");
+        std::fs::write(&json, "{\"messages\":[{\"id\":1},").unwrap();
+        let db = dir.path().join("state.db");
+        std::fs::write(&db, "definitely not sqlite").unwrap();
+
+        assert!(diagnose_source_file(&json).is_err());
+        assert!(diagnose_source_file(&db).unwrap_err().contains("database"));
+        assert!(diagnose_source_file(&dir.path().join("missing.jsonl"))
+            .unwrap_err()
+            .starts_with("failed to read file"));
+    }
+
+    #[test]
+    fn validate_sources_counts_a_truncated_tail_without_failing_the_file() {
+        let home = tempfile::tempdir().unwrap();
+        let codex_dir = home.path().join(".codex/sessions/2026/03/04");
+        std::fs::create_dir_all(&codex_dir).unwrap();
+        std::fs::write(
+            codex_dir.join("live.jsonl"),
+            concat!(
+                r#"{"type":"turn_context","payload":{"model":"gpt-5.4"}}"#,
+                "\n",
+                r#"{"type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":10,"cached_input_tokens":2,"output_tokens":3},"last_token_usage":{"input_tokens":10,"cached_input_tokens":2,"output_tokens":3}}}}"#,
+                "\n",
+                r#"{"type":"event_msg","payload":{"#,
+            ),
+        )
+        .unwrap();
+
+        let results = validate_sources(&ValidateOptions {
+            home_dir: Some(home.path().to_string_lossy().to_string()),
+            clients: Some(vec!["codex".to_string()]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, SourceStatus::Parsed);
+        assert_eq!(results[0].messages, 1);
+        assert_eq!(results[0].skipped_lines, 1);
+        assert!(results[0].truncated_tail);
+    }
+
+    #[test]
+    fn validate_sources_reports_parsed_empty_and_failed_files() {
+        let home = tempfile::tempdir().unwrap();
+        let cursor_dir = home.path().join(".config/tokscale/cursor-cache");
+        std::fs::create_dir_all(&cursor_dir).unwrap();
+        std::fs::write(
+            cursor_dir.join("usage.csv"),
+            "Date,Kind,Model,Max Mode,Input (w/ Cache Write),Input (w/o Cache Write),Cache Read,Output Tokens,Total Tokens,Cost\n\
+             \"2026-03-04T12:00:00.000Z\",\"Included\",\"gpt-5\",\"No\",\"100\",\"100\",\"0\",\"10\",\"110\",\"0.10\"\n",
+        )
+        .unwrap();
+        let codex_dir = home.path().join(".codex/sessions/2026/03/04");
+        std::fs::create_dir_all(&codex_dir).unwrap();
+        std::fs::write(
+            codex_dir.join("empty.jsonl"),
+            "{\"type\":\"session_meta\"}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            codex_dir.join("corrupt.jsonl"),
+            "{\"type\":\"session_meta\"\n",
+        )
+        .unwrap();
+
+        let results = validate_sources(&ValidateOptions {
+            home_dir: Some(home.path().to_string_lossy().to_string()),
+            clients: Some(vec!["codex".to_string(), "cursor".to_string()]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let summary: Vec<(&str, String, usize, SourceStatus)> = results
+            .iter()
+            .map(|r| {
+                let name = r.path.file_name().unwrap().to_string_lossy().to_string();
+                (r.client.as_str(), name, r.messages, r.status)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "codex",
+                    "corrupt.jsonl".to_string(),
+                    0,
+                    SourceStatus::Failed
+                ),
+                ("codex", "empty.jsonl".to_string(), 0, SourceStatus::Empty),
+                ("cursor", "usage.csv".to_string(), 1, SourceStatus::Parsed),
+            ]
+        );
+        assert!(results[0].error.as_deref().unwrap().starts_with("line 1:"));
+        assert!(results[1].error.is_none());
+    }
+}
@@ -103,8 +103,10 @@ async fn test_gjc_cost_precedence_end_to_end() {
         clients: Some(vec!["gjc".to_string()]),
         since: None,
         until: None,
+        until_exclusive: false,
         year: None,
         scanner_settings: ScannerSettings::default(),
+        ..Default::default()
     };
 
     let messages = parse_local_unified_messages_with_pricing(options, Some(&pricing))
@@ -216,8 +218,10 @@ async fn test_gjc_workspace_key_from_dashed_slug() {
         clients: Some(vec!["gjc".to_string()]),
         since: None,
         until: None,
+        until_exclusive: false,
         year: None,
         scanner_settings: ScannerSettings::default(),
+        ..Default::default()
     };
     let messages =
         parse_local_unified_messages_with_pricing(options, Some(&make_pricing_service()))
@@ -278,8 +282,10 @@ async fn test_gjc_recursive_glob_depth1_and_depth2() {
         clients: Some(vec!["gjc".to_string()]),
         since: None,
         until: None,
+        until_exclusive: false,
         year: None,
         scanner_settings: ScannerSettings::default(),
+        ..Default::default()
     };
     let messages =
         parse_local_unified_messages_with_pricing(options, Some(&make_pricing_service()))
@@ -346,8 +352,10 @@ async fn test_gjc_message_dedup_across_replayed_files() {
         clients: Some(vec!["gjc".to_string()]),
         since: None,
         until: None,
+        until_exclusive: false,
         year: None,
         scanner_settings: ScannerSettings::default(),
+        ..Default::default()
     };
     let messages =
         parse_local_unified_messages_with_pricing(options, Some(&make_pricing_service()))
@@ -56,8 +56,10 @@ async fn test_jcode_end_to_end_parsing_and_pricing() {
             clients: Some(vec!["jcode".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: ScannerSettings::default(),
+            ..Default::default()
         },
         Some(&pricing),
     )
@@ -106,8 +108,10 @@ async fn test_jcode_deduplicates_replayed_message_ids() {
             clients: Some(vec!["jcode".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: ScannerSettings::default(),
+            ..Default::default()
         },
         None,
     )
@@ -158,8 +162,10 @@ async fn test_jcode_journal_corrects_replayed_snapshot_duplicate() {
             clients: Some(vec!["jcode".to_string()]),
             since: None,
             until: None,
+            until_exclusive: false,
             year: None,
             scanner_settings: ScannerSettings::default(),
+            ..Default::default()
         },
         None,
     )
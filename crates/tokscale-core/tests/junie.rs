@@ -24,8 +24,10 @@ fn junie_options(home: &Path) -> LocalParseOptions {
         clients: Some(vec!["junie".to_string()]),
         since: None,
         until: None,
+        until_exclusive: false,
         year: None,
         scanner_settings: ScannerSettings::default(),
+        ..Default::default()
     }
 }
 